@@ -5,7 +5,7 @@ use crate::avm1::object::{Object, TObject};
 use crate::avm1::property::Attribute;
 use crate::avm1::runtime::skip_actions;
 use crate::avm1::scope::{Scope, ScopeClass};
-use crate::avm1::{fscommand, globals, scope, ArrayObject, ScriptObject, Value};
+use crate::avm1::{fscommand, globals, print, scope, ArrayObject, ScriptObject, Value};
 use crate::backend::navigator::{NavigationMethod, Request};
 use crate::context::UpdateContext;
 use crate::display_object::{
@@ -1198,6 +1198,13 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     fn action_get_url(&mut self, action: GetUrl) -> Result<FrameControl<'gc>, Error<'gc>> {
         let target = action.target.decode(self.encoding());
         let url = action.url.decode(self.encoding());
+
+        if let Some(print) = print::parse(&url) {
+            let target = AvmString::new(self.gc(), target.into_owned());
+            print::handle(print, target.into(), self)?;
+            return Ok(FrameControl::Continue);
+        }
+
         // TODO: Use `StageObject::get_level_by_path`.
         if target.starts_with(WStr::from_units(b"_level")) && target.len() > 6 {
             match target[6..].parse::<i32>() {
@@ -1257,6 +1264,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             return Ok(FrameControl::Continue);
         }
 
+        if let Some(print) = print::parse(&url) {
+            print::handle(print, target_val, self)?;
+            return Ok(FrameControl::Continue);
+        }
+
         // TODO: Use `StageObject::get_level_by_path`.
         let level_target = if target.starts_with(WStr::from_units(b"_level")) && target.len() >= 6 {
             match target[6..].parse::<f64>() {
@@ -59,6 +59,7 @@ pub(crate) mod system_ime;
 pub(crate) mod system_security;
 pub(crate) mod text_field;
 mod text_format;
+pub(crate) mod text_snapshot;
 pub(crate) mod transform;
 mod video;
 pub(crate) mod xml;
@@ -326,10 +327,11 @@ pub fn create_timer<'gc>(
     // Otherwise, set a repeat count of 0 (repeat indefinitely)
     //
     // We start the timer immediately
+    let movie = activation.base_clip().movie();
     let id = activation
         .context
         .timers
-        .add_timer(callback, interval, is_timeout);
+        .add_timer(callback, interval, is_timeout, movie);
 
     Ok(id.into())
 }
@@ -493,6 +495,7 @@ pub struct SystemPrototypes<'gc> {
     pub rectangle: Object<'gc>,
     pub rectangle_constructor: Object<'gc>,
     pub transform_constructor: Object<'gc>,
+    pub text_snapshot_constructor: Object<'gc>,
     pub shared_object_constructor: Object<'gc>,
     pub color_transform: Object<'gc>,
     pub color_transform_constructor: Object<'gc>,
@@ -671,6 +674,7 @@ pub fn create_globals<'gc>(
         function_proto,
         text_format_proto,
     );
+    let text_snapshot = text_snapshot::create_constructor(context, object_proto, function_proto);
     let array = array::create_array_object(context, array_proto, function_proto);
     let xmlnode = FunctionObject::constructor(
         gc_context,
@@ -958,6 +962,12 @@ pub fn create_globals<'gc>(
         text_format.into(),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "TextSnapshot",
+        text_snapshot.into(),
+        Attribute::DONT_ENUM,
+    );
     globals.define_value(gc_context, "XMLNode", xmlnode.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "XML", xml.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "String", string.into(), Attribute::DONT_ENUM);
@@ -1132,6 +1142,7 @@ pub fn create_globals<'gc>(
             rectangle: rectangle_proto,
             rectangle_constructor: rectangle,
             transform_constructor: transform,
+            text_snapshot_constructor: text_snapshot,
             shared_object_constructor: shared_object,
             color_transform: color_transform_proto,
             color_transform_constructor: color_transform,
@@ -568,6 +568,7 @@ fn draw<'gc>(
                 Transform {
                     matrix,
                     color_transform,
+                    ..Default::default()
                 },
                 smoothing,
                 blend_mode,
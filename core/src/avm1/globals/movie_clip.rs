@@ -82,6 +82,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "getNextHighestDepth" => method(mc_method!(get_next_highest_depth); DONT_ENUM | DONT_DELETE | VERSION_7);
     "getRect" => method(mc_method!(get_rect); DONT_ENUM | DONT_DELETE | VERSION_8);
     "getSWFVersion" => method(mc_method!(get_swf_version); DONT_ENUM | DONT_DELETE);
+    "getTextSnapshot" => method(mc_method!(get_text_snapshot); DONT_ENUM | DONT_DELETE | VERSION_6);
     "getURL" => method(mc_method!(get_url); DONT_ENUM | DONT_DELETE);
     "globalToLocal" => method(mc_method!(global_to_local); DONT_ENUM | DONT_DELETE);
     "gotoAndPlay" => method(mc_method!(goto_and_play); DONT_ENUM | DONT_DELETE);
@@ -600,7 +601,12 @@ fn begin_bitmap_fill<'gc>(
                 width: bitmap_data.width() as u16,
                 height: bitmap_data.height() as u16,
             };
-            let id = movie_clip.drawing_mut(activation.gc()).add_bitmap(bitmap);
+            // Flash matrix is in pixels. Scale from pixels to twips.
+            let scale_matrix =
+                Matrix::scale(Twips::TWIPS_PER_PIXEL as f32, Twips::TWIPS_PER_PIXEL as f32);
+            let id = movie_clip
+                .drawing_mut(activation.gc())
+                .add_bitmap(bitmap, scale_matrix.into());
 
             let mut matrix = avm1::globals::matrix::object_to_matrix_or_default(
                 args.get(1)
@@ -608,8 +614,7 @@ fn begin_bitmap_fill<'gc>(
                     .coerce_to_object(activation),
                 activation,
             )?;
-            // Flash matrix is in pixels. Scale from pixels to twips.
-            matrix *= Matrix::scale(Twips::TWIPS_PER_PIXEL as f32, Twips::TWIPS_PER_PIXEL as f32);
+            matrix *= scale_matrix;
 
             // `repeating` defaults to true, `smoothed` to false.
             // `smoothed` parameter may not be listed in some documentation.
@@ -1504,6 +1509,19 @@ fn get_rect<'gc>(
     get_bounds(movie_clip, activation, args)
 }
 
+fn get_text_snapshot<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let constructor = activation
+        .context
+        .avm1
+        .prototypes()
+        .text_snapshot_constructor;
+    constructor.construct(activation, &[movie_clip.object()])
+}
+
 fn get_swf_version<'gc>(
     movie_clip: MovieClip<'gc>,
     _activation: &mut Activation<'_, 'gc>,
@@ -3,6 +3,7 @@ use crate::avm1::object::{NativeObject, Object, TObject};
 use crate::avm1::property_decl::{define_properties_on, Declaration};
 use crate::avm1::{Activation, Error, ScriptObject, Value};
 use crate::avm1_stub;
+use crate::display_object::TDisplayObject;
 use crate::streams::NetStream;
 use crate::string::StringContext;
 
@@ -11,7 +12,11 @@ pub fn constructor<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let netstream = NetStream::new(activation.context.gc_context, Some(this.into()));
+    let netstream = NetStream::new(
+        activation.context.gc_context,
+        Some(this.into()),
+        activation.base_clip().movie(),
+    );
     this.set_native(
         activation.context.gc_context,
         NativeObject::NetStream(netstream),
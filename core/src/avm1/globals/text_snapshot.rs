@@ -0,0 +1,213 @@
+//! TextSnapshot object
+
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::NativeObject;
+use crate::avm1::object_reference::MovieClipReference;
+use crate::avm1::property_decl::{define_properties_on, Declaration};
+use crate::avm1::{Activation, ArrayObject, Error, Object, ScriptObject, TObject, Value};
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::string::{AvmString, StringContext};
+use crate::text_snapshot::TextSnapshot;
+use gc_arena::Collect;
+use swf::{Color, Point, Twips};
+
+#[derive(Copy, Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct TextSnapshotObject<'gc> {
+    clip: Option<MovieClipReference<'gc>>,
+}
+
+impl<'gc> TextSnapshotObject<'gc> {
+    fn new(activation: &mut Activation<'_, 'gc>, args: &[Value<'gc>]) -> Self {
+        let clip = match args {
+            [Value::MovieClip(clip), ..] => Some(*clip),
+            [Value::Object(clip), ..] => clip
+                .as_stage_object()
+                .and_then(|o| MovieClipReference::try_from_stage_object(activation, o)),
+            _ => None,
+        };
+        Self { clip }
+    }
+
+    fn clip(&self, activation: &mut Activation<'_, 'gc>) -> Option<DisplayObject<'gc>> {
+        let (_, _, clip) = self.clip?.resolve_reference(activation)?;
+        Some(clip)
+    }
+}
+
+macro_rules! text_snapshot_method {
+    ($index:literal) => {
+        |activation, this, args| method(activation, this, args, $index)
+    };
+}
+
+const PROTO_DECLS: &[Declaration] = declare_properties! {
+    "getCount" => method(text_snapshot_method!(1); DONT_ENUM | DONT_DELETE);
+    "setSelected" => method(text_snapshot_method!(2); DONT_ENUM | DONT_DELETE);
+    "getSelected" => method(text_snapshot_method!(3); DONT_ENUM | DONT_DELETE);
+    "getText" => method(text_snapshot_method!(4); DONT_ENUM | DONT_DELETE);
+    "getSelectedText" => method(text_snapshot_method!(5); DONT_ENUM | DONT_DELETE);
+    "hitTestTextNearPos" => method(text_snapshot_method!(6); DONT_ENUM | DONT_DELETE);
+    "findText" => method(text_snapshot_method!(7); DONT_ENUM | DONT_DELETE);
+    "setSelectColor" => method(text_snapshot_method!(8); DONT_ENUM | DONT_DELETE);
+    "getTextRunInfo" => method(text_snapshot_method!(9); DONT_ENUM | DONT_DELETE);
+};
+
+fn method<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    index: u8,
+) -> Result<Value<'gc>, Error<'gc>> {
+    const CONSTRUCTOR: u8 = 0;
+    const GET_COUNT: u8 = 1;
+    const SET_SELECTED: u8 = 2;
+    const GET_SELECTED: u8 = 3;
+    const GET_TEXT: u8 = 4;
+    const GET_SELECTED_TEXT: u8 = 5;
+    const HIT_TEST_TEXT_NEAR_POS: u8 = 6;
+    const FIND_TEXT: u8 = 7;
+    const SET_SELECT_COLOR: u8 = 8;
+    const GET_TEXT_RUN_INFO: u8 = 9;
+
+    if index == CONSTRUCTOR {
+        let text_snapshot = TextSnapshotObject::new(activation, args);
+        this.set_native(
+            activation.context.gc_context,
+            NativeObject::TextSnapshot(text_snapshot),
+        );
+        return Ok(this.into());
+    }
+
+    let NativeObject::TextSnapshot(this) = this.native() else {
+        return Ok(Value::Undefined);
+    };
+    let Some(container) = this.clip(activation).and_then(|clip| clip.as_container()) else {
+        return Ok(Value::Undefined);
+    };
+    let snapshot = TextSnapshot::new(container, activation.context.library);
+
+    let arg = |i: usize| args.get(i).copied().unwrap_or(Value::Undefined);
+    let swf_version = activation.swf_version();
+
+    Ok(match index {
+        GET_COUNT => snapshot.char_count().into(),
+        SET_SELECTED => {
+            let range = snapshot.range(
+                arg(0).coerce_to_i32(activation)?,
+                arg(1).coerce_to_i32(activation)?,
+            );
+            let selected = arg(2).as_bool(swf_version);
+            snapshot.set_selected(activation.context.gc_context, range, selected);
+            Value::Undefined
+        }
+        GET_SELECTED => {
+            let range = snapshot.range(
+                arg(0).coerce_to_i32(activation)?,
+                arg(1).coerce_to_i32(activation)?,
+            );
+            snapshot.is_selected(range).into()
+        }
+        GET_TEXT => {
+            let range = snapshot.range(
+                arg(0).coerce_to_i32(activation)?,
+                arg(1).coerce_to_i32(activation)?,
+            );
+            let text = snapshot.get_text(range, arg(2).as_bool(swf_version));
+            AvmString::new_utf8(activation.context.gc_context, text).into()
+        }
+        GET_SELECTED_TEXT => {
+            let text = snapshot.selected_text(arg(0).as_bool(swf_version));
+            AvmString::new_utf8(activation.context.gc_context, text).into()
+        }
+        HIT_TEST_TEXT_NEAR_POS => {
+            let point = Point::new(
+                Twips::from_pixels(arg(0).coerce_to_f64(activation)?),
+                Twips::from_pixels(arg(1).coerce_to_f64(activation)?),
+            );
+            let max_distance = match arg(2) {
+                Value::Undefined => Twips::ZERO,
+                value => Twips::from_pixels(value.coerce_to_f64(activation)?),
+            };
+            snapshot
+                .hit_test_near(point, max_distance)
+                .map_or(-1, |index| index as i32)
+                .into()
+        }
+        FIND_TEXT => {
+            let begin = arg(0).coerce_to_i32(activation)?.max(0) as usize;
+            let needle = arg(1).coerce_to_string(activation)?.to_string();
+            let case_sensitive = arg(2).as_bool(swf_version);
+            snapshot
+                .find_text(begin, &needle, case_sensitive)
+                .map_or(-1, |index| index as i32)
+                .into()
+        }
+        SET_SELECT_COLOR => {
+            let color = Color::from_rgb(arg(0).coerce_to_u32(activation)?, 255);
+            snapshot.set_select_color(activation.context.gc_context, color);
+            Value::Undefined
+        }
+        GET_TEXT_RUN_INFO => {
+            let range = snapshot.range(
+                arg(0).coerce_to_i32(activation)?,
+                arg(1).coerce_to_i32(activation)?,
+            );
+            let mut infos = Vec::new();
+            for (index, selected, char) in snapshot.run_info(range) {
+                let info = ScriptObject::new(
+                    activation.context.gc_context,
+                    Some(activation.context.avm1.prototypes().object),
+                );
+                let font = AvmString::new_utf8(
+                    activation.context.gc_context,
+                    char.font.descriptor().name(),
+                );
+                info.set("indexInRun", index.into(), activation)?;
+                info.set("selected", selected.into(), activation)?;
+                info.set("font", font.into(), activation)?;
+                info.set("color", char.color.to_rgb().into(), activation)?;
+                info.set("height", char.height.to_pixels().into(), activation)?;
+                info.set("matrix_a", char.matrix.a.into(), activation)?;
+                info.set("matrix_b", char.matrix.b.into(), activation)?;
+                info.set("matrix_c", char.matrix.c.into(), activation)?;
+                info.set("matrix_d", char.matrix.d.into(), activation)?;
+                info.set("matrix_tx", char.matrix.tx.to_pixels().into(), activation)?;
+                info.set("matrix_ty", char.matrix.ty.to_pixels().into(), activation)?;
+                for (name, corner) in ["corner00", "corner01", "corner10", "corner11"]
+                    .into_iter()
+                    .zip(char.corners)
+                {
+                    let x = AvmString::new_utf8(activation.context.gc_context, format!("{name}x"));
+                    let y = AvmString::new_utf8(activation.context.gc_context, format!("{name}y"));
+                    info.set(x, corner.x.to_pixels().into(), activation)?;
+                    info.set(y, corner.y.to_pixels().into(), activation)?;
+                }
+                infos.push(info.into());
+            }
+            ArrayObject::new(
+                activation.context.gc_context,
+                activation.context.avm1.prototypes().array,
+                infos,
+            )
+            .into()
+        }
+        _ => Value::Undefined,
+    })
+}
+
+pub fn create_constructor<'gc>(
+    context: &mut StringContext<'gc>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let text_snapshot_proto = ScriptObject::new(context.gc_context, Some(proto));
+    define_properties_on(PROTO_DECLS, context, text_snapshot_proto, fn_proto);
+    FunctionObject::constructor(
+        context.gc_context,
+        Executable::Native(text_snapshot_method!(0)),
+        constructor_to_fn!(text_snapshot_method!(0)),
+        fn_proto,
+        text_snapshot_proto.into(),
+    )
+}
@@ -0,0 +1,172 @@
+//! Handling of the `print` family of functions, which are compiled to `getURL` calls
+//! with `print:` or `printasbitmap:` URLs.
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::Value;
+use crate::avm_warn;
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, MovieClip, TDisplayObject};
+use crate::printing::render_page;
+use crate::string::WStr;
+use swf::Rectangle;
+
+/// How a clip gets printed, as given by a print URL.
+pub struct PrintOptions<'a> {
+    /// Whether the clip is printed as a bitmap (`printAsBitmap`) instead of as vectors (`print`).
+    pub as_bitmap: bool,
+
+    /// How the printed area is determined: `#bmovie`, `#bmax` or `#bframe`.
+    pub bounding_box: &'a WStr,
+}
+
+/// Parse a print URL.
+pub fn parse(url: &WStr) -> Option<PrintOptions<'_>> {
+    for (prefix, as_bitmap) in [
+        (WStr::from_units(b"print:"), false),
+        (WStr::from_units(b"printasbitmap:"), true),
+    ] {
+        if url.len() < prefix.len() {
+            continue;
+        }
+
+        let (head, tail) = url.split_at(prefix.len());
+        if head.eq_ignore_case(prefix) {
+            return Some(PrintOptions {
+                as_bitmap,
+                bounding_box: tail,
+            });
+        }
+    }
+
+    None
+}
+
+/// How the printed area of each frame is determined.
+enum BoundingBox {
+    /// The bounds of the frame labeled `#b`, or the stage if there's none (`#bmovie`).
+    Movie,
+
+    /// The bounds of all printed frames put together (`#bmax`).
+    Max,
+
+    /// The bounds of each frame (`#bframe`).
+    Frame,
+}
+
+impl BoundingBox {
+    fn parse(activation: &mut Activation<'_, '_>, bounding_box: &WStr) -> Self {
+        if bounding_box.eq_ignore_case(WStr::from_units(b"#bmax")) {
+            Self::Max
+        } else if bounding_box.eq_ignore_case(WStr::from_units(b"#bframe")) {
+            Self::Frame
+        } else {
+            if !bounding_box.is_empty()
+                && !bounding_box.eq_ignore_case(WStr::from_units(b"#bmovie"))
+            {
+                avm_warn!(
+                    activation,
+                    "print: Unknown bounding box {}, #bmovie is used",
+                    bounding_box
+                );
+            }
+            Self::Movie
+        }
+    }
+}
+
+/// The frames of a clip to print: the ones labeled `#p`, or all of them if there's none.
+fn printed_frames(clip: MovieClip<'_>) -> Vec<u16> {
+    let labels = clip.labels_in_range(1, clip.total_frames().saturating_add(1));
+    let frames: Vec<u16> = labels
+        .iter()
+        .filter(|(label, _)| label.eq_ignore_case(WStr::from_units(b"#p")))
+        .map(|(_, frame)| *frame)
+        .collect();
+    if frames.is_empty() {
+        (1..=clip.total_frames()).collect()
+    } else {
+        frames
+    }
+}
+
+pub fn handle<'gc>(
+    options: PrintOptions<'_>,
+    target: Value<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<(), Error<'gc>> {
+    let start = activation.target_clip_or_root();
+    let Some(object) = activation.resolve_target_display_object(start, target, true)? else {
+        avm_warn!(activation, "print: Couldn't find the clip to print");
+        return Ok(());
+    };
+    let bounding_box = BoundingBox::parse(activation, options.bounding_box);
+
+    let Some(clip) = object.as_movie_clip() else {
+        // Anything but a movie clip only has the one frame.
+        let page = render_page(
+            activation.context,
+            object,
+            object.bounds(),
+            options.as_bitmap,
+            true,
+        );
+        activation
+            .context
+            .ui
+            .print_pages(page.into_iter().collect());
+        return Ok(());
+    };
+
+    // The current frame is printed as it's displayed. Other frames are printed from copies of
+    // the clip, laid out as they are on the timeline.
+    let current_frame = clip.current_frame();
+    let mut copies = vec![];
+    let mut frame_object = |context: &mut UpdateContext<'gc>, frame| -> DisplayObject<'gc> {
+        if frame == current_frame {
+            object
+        } else {
+            let copy = clip.print_copy(context, frame);
+            copies.push(copy);
+            copy.into()
+        }
+    };
+
+    let frames: Vec<_> = printed_frames(clip)
+        .into_iter()
+        .map(|frame| frame_object(activation.context, frame))
+        .collect();
+
+    let fixed_area = match bounding_box {
+        BoundingBox::Movie => {
+            let labels = clip.labels_in_range(1, clip.total_frames().saturating_add(1));
+            match labels
+                .iter()
+                .find(|(label, _)| label.eq_ignore_case(WStr::from_units(b"#b")))
+            {
+                Some((_, frame)) => Some(frame_object(activation.context, *frame).bounds()),
+                None => Some(clip.movie().stage_size().clone()),
+            }
+        }
+        BoundingBox::Max => Some(frames.iter().fold(Rectangle::INVALID, |area, frame| {
+            area.union(&frame.bounds())
+        })),
+        BoundingBox::Frame => None,
+    };
+
+    // Each frame is printed on its own page, scaled to fit it.
+    let pages = frames
+        .iter()
+        .filter_map(|frame| {
+            let area = fixed_area.clone().unwrap_or_else(|| frame.bounds());
+            render_page(activation.context, *frame, area, options.as_bitmap, true)
+        })
+        .collect();
+
+    for copy in copies {
+        copy.discard_print_copy(activation.context);
+    }
+    activation.context.ui.print_pages(pages);
+
+    Ok(())
+}
@@ -4,10 +4,13 @@ use std::rc::Rc;
 
 use crate::avm2::class::{AllocatorFn, CustomConstructorFn};
 use crate::avm2::error::make_error_1107;
+use crate::avm2::game_input::GameInputObjects;
 use crate::avm2::globals::{
     init_builtin_system_classes, init_native_system_classes, SystemClassDefs, SystemClasses,
 };
+use crate::avm2::media_capture::MediaCaptureObjects;
 use crate::avm2::method::{Method, NativeMethodImpl};
+use crate::avm2::sampler::Sampler;
 use crate::avm2::scope::ScopeChain;
 use crate::avm2::script::{Script, TranslationUnit};
 use crate::context::UpdateContext;
@@ -47,7 +50,9 @@ mod events;
 mod filters;
 mod flv;
 mod function;
+pub mod game_input;
 pub mod globals;
+pub mod media_capture;
 mod metadata;
 mod method;
 mod multiname;
@@ -60,6 +65,7 @@ pub mod property;
 mod property_map;
 mod qname;
 mod regexp;
+mod sampler;
 mod scope;
 pub mod script;
 #[cfg(feature = "known_stubs")]
@@ -94,7 +100,14 @@ use self::api_version::ApiVersion;
 use self::object::WeakObject;
 use self::scope::Scope;
 
-const BROADCAST_WHITELIST: [&str; 4] = ["enterFrame", "exitFrame", "frameConstructed", "render"];
+const BROADCAST_WHITELIST: [&str; 6] = [
+    "enterFrame",
+    "exitFrame",
+    "frameConstructed",
+    "render",
+    "deviceAdded",
+    "deviceRemoved",
+];
 
 const PREALLOCATED_STACK_SIZE: usize = 120000;
 
@@ -182,6 +195,15 @@ pub struct Avm2<'gc> {
     #[collect(require_static)]
     pub root_api_version: ApiVersion,
 
+    /// The state of `flash.sampler`.
+    pub sampler: Sampler<'gc>,
+
+    /// The objects representing the connected gamepads.
+    pub game_input_objects: GameInputObjects<'gc>,
+
+    /// The objects representing cameras and microphones.
+    pub media_capture_objects: MediaCaptureObjects<'gc>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 
@@ -232,6 +254,11 @@ impl<'gc> Avm2<'gc> {
             // Set the lowest version for now - this will be overridden when we set our movie
             root_api_version: ApiVersion::AllVersions,
 
+            sampler: Default::default(),
+
+            game_input_objects: Default::default(),
+            media_capture_objects: Default::default(),
+
             #[cfg(feature = "avm_debug")]
             debug_output: false,
 
@@ -495,6 +522,88 @@ impl<'gc> Avm2<'gc> {
         bucket.push(object.downgrade());
     }
 
+    /// Remove a display object and all of its descendants from every
+    /// broadcast list.
+    ///
+    /// This is used to stop the broadcasts to the content of an unloaded
+    /// movie, which may still be referenced by the movie's own listeners.
+    pub fn unregister_broadcast_listeners_within(
+        context: &mut UpdateContext<'gc>,
+        root: DisplayObject<'gc>,
+    ) {
+        let gc_context = context.gc_context;
+        for bucket in context.avm2.broadcast_list.values_mut() {
+            bucket.retain(|entry| {
+                let Some(object) = entry.upgrade(gc_context) else {
+                    return false;
+                };
+
+                let mut dobj = object.as_display_object();
+                while let Some(ancestor) = dobj {
+                    if DisplayObject::ptr_eq(ancestor, root) {
+                        return false;
+                    }
+                    dobj = ancestor.parent();
+                }
+                true
+            });
+        }
+    }
+
+    /// Remove the event listeners defined by any of the given movies from the
+    /// stage, and their broadcast event listeners from every other object.
+    ///
+    /// Objects outside of an unloaded movie keep receiving events, so this
+    /// stops them from calling back into the code of the movie.
+    pub fn remove_listeners_of_movies(context: &mut UpdateContext<'gc>, movies: &[Arc<SwfMovie>]) {
+        let is_from_movies = |handler: Object<'gc>| {
+            handler
+                .as_executable()
+                .is_some_and(|exec| match exec.as_method() {
+                    Method::Bytecode(method) => {
+                        let owner = method.owner_movie();
+                        movies.iter().any(|movie| Arc::ptr_eq(movie, &owner))
+                    }
+                    Method::Native(_) => false,
+                })
+        };
+
+        // The stage loses all of the movies' listeners, other objects only
+        // those of broadcast events.
+        let mut dispatchers = Vec::new();
+        if let Some(stage) = context.stage.object2().as_object() {
+            dispatchers.push((stage, true));
+        }
+        for bucket in context.avm2.broadcast_list.values() {
+            for entry in bucket {
+                if let Some(object) = entry.upgrade(context.gc_context) {
+                    dispatchers.push((object, false));
+                }
+            }
+        }
+
+        let mut activation = Activation::from_nothing(context);
+        let internal_ns = activation.avm2().namespaces.flash_events_internal;
+        for (dispatcher, all_events) in dispatchers {
+            let Ok(Value::Object(dispatch_list)) = dispatcher.get_property(
+                &Multiname::new(internal_ns, "_dispatchList"),
+                &mut activation,
+            ) else {
+                continue;
+            };
+            if let Some(mut dispatch_list) =
+                dispatch_list.as_dispatch_mut(activation.context.gc_context)
+            {
+                dispatch_list.retain_handlers(|event, handler| {
+                    let is_broadcast = BROADCAST_WHITELIST
+                        .iter()
+                        .any(|x| AvmString::from(*x) == event);
+                    !((all_events || is_broadcast) && is_from_movies(handler))
+                });
+            };
+        }
+    }
+
     /// Dispatch an event on all objects in the current execution list.
     ///
     /// `on_type` specifies a class or interface constructor whose instances,
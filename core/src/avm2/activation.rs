@@ -14,6 +14,7 @@ use crate::avm2::object::{
 };
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::op::Op;
+use crate::avm2::sampler;
 use crate::avm2::scope::{search_scope_stack, Scope, ScopeChain};
 use crate::avm2::script::Script;
 use crate::avm2::value::Value;
@@ -1836,6 +1837,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let scope = self.create_scopechain();
 
         let new_fn = FunctionObject::from_function(self, method_entry, scope)?;
+        let function_class = self.avm2().classes().function;
+        sampler::record_allocation(self, new_fn.into(), function_class)?;
 
         self.push_stack(new_fn);
 
@@ -1878,6 +1881,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         let args = self.pop_stack_args(num_args);
         let array = ArrayStorage::from_args(&args[..]);
         let array_obj = ArrayObject::from_storage(self, array)?;
+        let array_class = self.avm2().classes().array;
+        sampler::record_allocation(self, array_obj, array_class)?;
 
         self.push_stack(array_obj);
 
@@ -16,6 +16,30 @@ pub enum CallNode<'gc> {
     },
 }
 
+impl<'gc> CallNode<'gc> {
+    pub fn display(&self, output: &mut WString) {
+        match self {
+            CallNode::GlobalInit(script) => {
+                let name = if let Some(tuint) = script.translation_unit() {
+                    if let Some(name) = tuint.name() {
+                        name.to_utf8_lossy().to_string()
+                    } else {
+                        "<No name>".to_string()
+                    }
+                } else {
+                    "<No translation unit>".to_string()
+                };
+
+                // NOTE: We intentionally diverge from Flash Player's output
+                // here - everything with the [] brackets is extra information
+                // added by Ruffle
+                output.push_utf8(&format!("global$init() [TU={}]", name));
+            }
+            CallNode::Method { method, class } => display_function(output, method, *class),
+        }
+    }
+}
+
 #[derive(Collect, Clone)]
 #[collect(no_drop)]
 pub struct CallStack<'gc> {
@@ -42,28 +66,15 @@ impl<'gc> CallStack<'gc> {
     pub fn display(&self, output: &mut WString) {
         for call in self.stack.iter().rev() {
             output.push_utf8("\n\tat ");
-            match call {
-                CallNode::GlobalInit(script) => {
-                    let name = if let Some(tuint) = script.translation_unit() {
-                        if let Some(name) = tuint.name() {
-                            name.to_utf8_lossy().to_string()
-                        } else {
-                            "<No name>".to_string()
-                        }
-                    } else {
-                        "<No translation unit>".to_string()
-                    };
-
-                    // NOTE: We intentionally diverge from Flash Player's output
-                    // here - everything with the [] brackets is extra information
-                    // added by Ruffle
-                    output.push_utf8(&format!("global$init() [TU={}]", name));
-                }
-                CallNode::Method { method, class } => display_function(output, method, *class),
-            }
+            call.display(output);
         }
     }
 
+    /// The calls currently on the stack, from outermost to innermost.
+    pub fn calls(&self) -> &[CallNode<'gc>] {
+        &self.stack
+    }
+
     pub fn is_empty(&self) -> bool {
         self.stack.is_empty()
     }
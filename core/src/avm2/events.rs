@@ -268,6 +268,16 @@ impl<'gc> DispatchList<'gc> {
         }
     }
 
+    /// Remove every handler that `keep` returns false for, given the event
+    /// type it handles and the handler itself.
+    pub fn retain_handlers(&mut self, mut keep: impl FnMut(AvmString<'gc>, Object<'gc>) -> bool) {
+        for (event, sheaf) in self.0.iter_mut() {
+            for set in sheaf.values_mut() {
+                set.retain(|h| keep(*event, h.handler));
+            }
+        }
+    }
+
     /// Determine if there are any event listeners in this dispatch list.
     pub fn has_event_listener(&self, event: impl Into<AvmString<'gc>>) -> bool {
         if let Some(event_sheaf) = self.get_event(event) {
@@ -2,6 +2,7 @@ use crate::avm2::activation::Activation;
 use crate::avm2::class::Class;
 use crate::avm2::method::{Method, ParamConfig};
 use crate::avm2::object::{ClassObject, Object};
+use crate::avm2::sampler;
 use crate::avm2::scope::ScopeChain;
 use crate::avm2::traits::TraitKind;
 use crate::avm2::value::Value;
@@ -91,6 +92,14 @@ impl<'gc> BoundMethod<'gc> {
         self.method
     }
 
+    pub fn scope(&self) -> ScopeChain<'gc> {
+        self.scope
+    }
+
+    pub fn bound_receiver(&self) -> Option<Object<'gc>> {
+        self.bound_receiver
+    }
+
     pub fn debug_full_name(&self) -> WString {
         let mut output = WString::new();
         display_function(&mut output, &self.as_method(), self.bound_class());
@@ -200,6 +209,7 @@ pub fn exec<'gc>(
                 .context
                 .avm2
                 .push_call(activation.context.gc_context, method, bound_class);
+            sampler::record_call(activation.context, method);
             (bm.method)(&mut activation, receiver, &arguments)
         }
         Method::Bytecode(bm) => {
@@ -243,6 +253,7 @@ pub fn exec<'gc>(
                 .context
                 .avm2
                 .push_call(activation.context.gc_context, method, bound_class);
+            sampler::record_call(activation.context, method);
             activation.run_actions(bm)
         }
     };
@@ -0,0 +1,171 @@
+//! The objects through which AVM2 code sees connected gamepads.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::globals::slots::*;
+use crate::avm2::object::{scriptobject_allocator, ArrayObject, EventObject, Object, TObject};
+use crate::avm2::{Avm2, Error, Value};
+use crate::context::UpdateContext;
+use crate::events::{GamepadId, PlayerEvent};
+use crate::input::GamepadControl;
+use crate::string::AvmString;
+use fnv::FnvHashMap;
+use gc_arena::Collect;
+
+/// The `GameInputDevice` of a gamepad, along with its `GameInputControl`s.
+#[derive(Collect, Clone)]
+#[collect(no_drop)]
+struct DeviceObjects<'gc> {
+    device: Object<'gc>,
+    controls: Vec<Object<'gc>>,
+}
+
+/// The objects representing the connected gamepads in one AVM2 instance.
+///
+/// Each gamepad is represented by a single `GameInputDevice`, so that `GameInput.getDeviceAt`
+/// and `GameInputEvent.device` agree, and control changes reach the objects scripts listen on.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct GameInputObjects<'gc> {
+    devices: FnvHashMap<GamepadId, DeviceObjects<'gc>>,
+}
+
+/// Returns the `GameInputDevice` representing a gamepad.
+pub fn device_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    gamepad: GamepadId,
+) -> Result<Object<'gc>, Error<'gc>> {
+    if let Some(objects) = activation.avm2().game_input_objects.devices.get(&gamepad) {
+        return Ok(objects.device);
+    }
+
+    // Neither class can be constructed by scripts, so they're allocated without running their
+    // constructors.
+    let device_class = activation.avm2().classes().gameinputdevice;
+    let device = scriptobject_allocator(device_class, activation)?;
+    device.set_slot(
+        FLASH_UI_GAME_INPUT_DEVICE__ID_SLOT,
+        (gamepad as u32).into(),
+        activation,
+    )?;
+
+    let control_class = activation.avm2().classes().gameinputcontrol;
+    let mut controls = Vec::with_capacity(GamepadControl::ALL.len());
+    for control in GamepadControl::ALL {
+        let object = scriptobject_allocator(control_class, activation)?;
+        let id = AvmString::new_utf8(activation.context.gc_context, control.id());
+        object.set_slot(
+            FLASH_UI_GAME_INPUT_CONTROL__DEVICE_SLOT,
+            device.into(),
+            activation,
+        )?;
+        object.set_slot(
+            FLASH_UI_GAME_INPUT_CONTROL__INDEX_SLOT,
+            (control.index() as u32).into(),
+            activation,
+        )?;
+        object.set_slot(FLASH_UI_GAME_INPUT_CONTROL__ID_SLOT, id.into(), activation)?;
+        controls.push(object);
+    }
+
+    let values = controls
+        .iter()
+        .map(|control| Value::from(*control))
+        .collect::<Vec<_>>();
+    let controls_array = ArrayObject::from_storage(activation, ArrayStorage::from_args(&values))?;
+    device.set_slot(
+        FLASH_UI_GAME_INPUT_DEVICE__CONTROLS_SLOT,
+        controls_array.into(),
+        activation,
+    )?;
+
+    activation
+        .avm2()
+        .game_input_objects
+        .devices
+        .insert(gamepad, DeviceObjects { device, controls });
+    Ok(device)
+}
+
+/// The gamepad represented by a `GameInputDevice`.
+pub fn device_gamepad(device: Object<'_>) -> GamepadId {
+    device
+        .get_slot(FLASH_UI_GAME_INPUT_DEVICE__ID_SLOT)
+        .as_u32() as GamepadId
+}
+
+/// The gamepad and control represented by a `GameInputControl`.
+pub fn control_of(control: Object<'_>) -> Option<(GamepadId, GamepadControl)> {
+    let device = control
+        .get_slot(FLASH_UI_GAME_INPUT_CONTROL__DEVICE_SLOT)
+        .as_object()?;
+    let index = control
+        .get_slot(FLASH_UI_GAME_INPUT_CONTROL__INDEX_SLOT)
+        .as_u32() as usize;
+    let control = *GamepadControl::ALL.get(index)?;
+    Some((device_gamepad(device), control))
+}
+
+fn broadcast_device_event<'gc>(
+    context: &mut UpdateContext<'gc>,
+    event_type: &'static str,
+    device: Object<'gc>,
+) {
+    let mut activation = Activation::from_nothing(context);
+    let class = activation.avm2().classes().gameinputevent;
+    let event_type = AvmString::new_utf8(activation.context.gc_context, event_type);
+    let args = [event_type.into(), false.into(), false.into(), device.into()];
+    match class.construct(&mut activation, &args) {
+        Ok(event) => {
+            let on_type = context.avm2.classes().gameinput;
+            Avm2::broadcast_event(context, event, on_type);
+        }
+        Err(e) => tracing::error!("Couldn't create a GameInputEvent: {:?}", e),
+    }
+}
+
+/// Lets scripts know that a gamepad was connected, disconnected or used.
+pub fn dispatch_gamepad_event(context: &mut UpdateContext<'_>, event: PlayerEvent) {
+    match event {
+        PlayerEvent::GamepadConnected { gamepad } => {
+            let mut activation = Activation::from_nothing(context);
+            match device_object(&mut activation, gamepad) {
+                Ok(device) => broadcast_device_event(context, "deviceAdded", device),
+                Err(e) => tracing::error!("Couldn't create a GameInputDevice: {:?}", e),
+            }
+        }
+        PlayerEvent::GamepadDisconnected { gamepad } => {
+            if let Some(objects) = context.avm2.game_input_objects.devices.remove(&gamepad) {
+                broadcast_device_event(context, "deviceRemoved", objects.device);
+            }
+        }
+        PlayerEvent::GamepadAxisChanged { gamepad, axis, .. } => {
+            dispatch_change(context, gamepad, GamepadControl::Axis(axis));
+        }
+        PlayerEvent::GamepadButtonDown { gamepad, button }
+        | PlayerEvent::GamepadButtonUp { gamepad, button } => {
+            dispatch_change(context, gamepad, GamepadControl::Button(button));
+        }
+        _ => {}
+    }
+}
+
+/// Dispatches `change` on a control, if scripts enabled its device.
+fn dispatch_change(context: &mut UpdateContext<'_>, gamepad: GamepadId, control: GamepadControl) {
+    let Some(objects) = context.avm2.game_input_objects.devices.get(&gamepad) else {
+        return;
+    };
+
+    let enabled = objects
+        .device
+        .get_slot(FLASH_UI_GAME_INPUT_DEVICE__ENABLED_SLOT)
+        .coerce_to_boolean();
+    let Some(target) = objects.controls.get(control.index()).copied() else {
+        return;
+    };
+
+    if enabled {
+        let event = EventObject::bare_default_event(context, "change");
+        Avm2::dispatch_event(context, event, target);
+    }
+}
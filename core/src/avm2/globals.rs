@@ -78,6 +78,8 @@ pub struct SystemClasses<'gc> {
     pub event: ClassObject<'gc>,
     pub fullscreenevent: ClassObject<'gc>,
     pub video: ClassObject<'gc>,
+    pub camera: ClassObject<'gc>,
+    pub microphone: ClassObject<'gc>,
     pub xml: ClassObject<'gc>,
     pub xml_list: ClassObject<'gc>,
     pub display_object: ClassObject<'gc>,
@@ -85,6 +87,13 @@ pub struct SystemClasses<'gc> {
     pub textfield: ClassObject<'gc>,
     pub textformat: ClassObject<'gc>,
     pub graphics: ClassObject<'gc>,
+    pub graphicsbitmapfill: ClassObject<'gc>,
+    pub graphicsendfill: ClassObject<'gc>,
+    pub graphicsgradientfill: ClassObject<'gc>,
+    pub graphicspath: ClassObject<'gc>,
+    pub graphicsshaderfill: ClassObject<'gc>,
+    pub graphicssolidfill: ClassObject<'gc>,
+    pub graphicsstroke: ClassObject<'gc>,
     pub loader: ClassObject<'gc>,
     pub loaderinfo: ClassObject<'gc>,
     pub bytearray: ClassObject<'gc>,
@@ -113,6 +122,7 @@ pub struct SystemClasses<'gc> {
     pub colortransform: ClassObject<'gc>,
     pub matrix: ClassObject<'gc>,
     pub matrix3d: ClassObject<'gc>,
+    pub perspectiveprojection: ClassObject<'gc>,
     pub illegaloperationerror: ClassObject<'gc>,
     pub eventdispatcher: ClassObject<'gc>,
     pub rectangle: ClassObject<'gc>,
@@ -165,13 +175,28 @@ pub struct SystemClasses<'gc> {
     pub filefilter: ClassObject<'gc>,
     pub font: ClassObject<'gc>,
     pub textline: ClassObject<'gc>,
+    pub groupelement: ClassObject<'gc>,
+    pub graphicelement: ClassObject<'gc>,
+    pub spacejustifier: ClassObject<'gc>,
     pub sampledataevent: ClassObject<'gc>,
+    pub activityevent: ClassObject<'gc>,
     pub avm1movie: ClassObject<'gc>,
     pub focusevent: ClassObject<'gc>,
     pub dictionary: ClassObject<'gc>,
     pub id3info: ClassObject<'gc>,
     pub textrun: ClassObject<'gc>,
     pub sharedobject: ClassObject<'gc>,
+    pub numberparseresult: ClassObject<'gc>,
+    pub currencyparseresult: ClassObject<'gc>,
+    pub sample: ClassObject<'gc>,
+    pub newobjectsample: ClassObject<'gc>,
+    pub deleteobjectsample: ClassObject<'gc>,
+    pub stackframe: ClassObject<'gc>,
+    pub gameinput: ClassObject<'gc>,
+    pub gameinputdevice: ClassObject<'gc>,
+    pub gameinputcontrol: ClassObject<'gc>,
+    pub gameinputevent: ClassObject<'gc>,
+    pub datagramsocketdataevent: ClassObject<'gc>,
 }
 
 #[derive(Clone, Collect)]
@@ -208,6 +233,7 @@ pub struct SystemClassDefs<'gc> {
 
     pub cubetexture: Class<'gc>,
     pub rectangletexture: Class<'gc>,
+    pub timer: Class<'gc>,
 }
 
 impl<'gc> SystemClasses<'gc> {
@@ -239,6 +265,8 @@ impl<'gc> SystemClasses<'gc> {
             event: object,
             fullscreenevent: object,
             video: object,
+            camera: object,
+            microphone: object,
             xml: object,
             xml_list: object,
             display_object: object,
@@ -246,6 +274,13 @@ impl<'gc> SystemClasses<'gc> {
             textfield: object,
             textformat: object,
             graphics: object,
+            graphicsbitmapfill: object,
+            graphicsendfill: object,
+            graphicsgradientfill: object,
+            graphicspath: object,
+            graphicsshaderfill: object,
+            graphicssolidfill: object,
+            graphicsstroke: object,
             loader: object,
             loaderinfo: object,
             bytearray: object,
@@ -272,6 +307,7 @@ impl<'gc> SystemClasses<'gc> {
             colortransform: object,
             matrix: object,
             matrix3d: object,
+            perspectiveprojection: object,
             illegaloperationerror: object,
             eventdispatcher: object,
             rectangle: object,
@@ -324,13 +360,28 @@ impl<'gc> SystemClasses<'gc> {
             filefilter: object,
             font: object,
             textline: object,
+            groupelement: object,
+            graphicelement: object,
+            spacejustifier: object,
             sampledataevent: object,
+            activityevent: object,
             avm1movie: object,
             focusevent: object,
             dictionary: object,
             id3info: object,
             textrun: object,
             sharedobject: object,
+            numberparseresult: object,
+            currencyparseresult: object,
+            sample: object,
+            newobjectsample: object,
+            deleteobjectsample: object,
+            stackframe: object,
+            gameinput: object,
+            gameinputdevice: object,
+            gameinputcontrol: object,
+            gameinputevent: object,
+            datagramsocketdataevent: object,
         }
     }
 }
@@ -376,6 +427,7 @@ impl<'gc> SystemClassDefs<'gc> {
 
             cubetexture: object,
             rectangletexture: object,
+            timer: object,
         }
     }
 }
@@ -881,6 +933,17 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.display", "Scene", scene),
             ("flash.display", "FrameLabel", framelabel),
             ("flash.display", "Graphics", graphics),
+            ("flash.display", "GraphicsBitmapFill", graphicsbitmapfill),
+            ("flash.display", "GraphicsEndFill", graphicsendfill),
+            (
+                "flash.display",
+                "GraphicsGradientFill",
+                graphicsgradientfill
+            ),
+            ("flash.display", "GraphicsPath", graphicspath),
+            ("flash.display", "GraphicsShaderFill", graphicsshaderfill),
+            ("flash.display", "GraphicsSolidFill", graphicssolidfill),
+            ("flash.display", "GraphicsStroke", graphicsstroke),
             ("flash.display", "Loader", loader),
             ("flash.display", "LoaderInfo", loaderinfo),
             ("flash.display", "MorphShape", morphshape),
@@ -927,8 +990,19 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.events", "AsyncErrorEvent", asyncerrorevent),
             ("flash.events", "ContextMenuEvent", contextmenuevent),
             ("flash.events", "FocusEvent", focusevent),
+            (
+                "flash.globalization",
+                "NumberParseResult",
+                numberparseresult
+            ),
+            (
+                "flash.globalization",
+                "CurrencyParseResult",
+                currencyparseresult
+            ),
             ("flash.geom", "Matrix", matrix),
             ("flash.geom", "Matrix3D", matrix3d),
+            ("flash.geom", "PerspectiveProjection", perspectiveprojection),
             ("flash.geom", "Point", point),
             ("flash.geom", "Rectangle", rectangle),
             ("flash.geom", "Transform", transform),
@@ -937,6 +1011,8 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.media", "SoundChannel", soundchannel),
             ("flash.media", "SoundTransform", soundtransform),
             ("flash.media", "Video", video),
+            ("flash.media", "Camera", camera),
+            ("flash.media", "Microphone", microphone),
             ("flash.net", "URLVariables", urlvariables),
             ("flash.net", "FileReference", filereference),
             ("flash.net", "FileFilter", filefilter),
@@ -951,6 +1027,9 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.text", "TextLineMetrics", textlinemetrics),
             ("flash.text", "TextRun", textrun),
             ("flash.text.engine", "TextLine", textline),
+            ("flash.text.engine", "GroupElement", groupelement),
+            ("flash.text.engine", "GraphicElement", graphicelement),
+            ("flash.text.engine", "SpaceJustifier", spacejustifier),
             ("flash.filters", "BevelFilter", bevelfilter),
             ("flash.filters", "BitmapFilter", bitmapfilter),
             ("flash.filters", "BlurFilter", blurfilter),
@@ -967,6 +1046,20 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
             ("flash.filters", "GradientGlowFilter", gradientglowfilter),
             ("flash.filters", "ShaderFilter", shaderfilter),
             ("flash.events", "SampleDataEvent", sampledataevent),
+            ("flash.events", "ActivityEvent", activityevent),
+            ("flash.sampler", "Sample", sample),
+            ("flash.sampler", "NewObjectSample", newobjectsample),
+            ("flash.sampler", "DeleteObjectSample", deleteobjectsample),
+            ("flash.sampler", "StackFrame", stackframe),
+            ("flash.ui", "GameInput", gameinput),
+            ("flash.ui", "GameInputDevice", gameinputdevice),
+            ("flash.ui", "GameInputControl", gameinputcontrol),
+            ("flash.events", "GameInputEvent", gameinputevent),
+            (
+                "flash.events",
+                "DatagramSocketDataEvent",
+                datagramsocketdataevent
+            ),
         ]
     );
 
@@ -984,6 +1077,7 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
                 graphicsgradientfill
             ),
             ("flash.display", "GraphicsPath", graphicspath),
+            ("flash.display", "GraphicsShaderFill", graphicsshaderfill),
             (
                 "flash.display",
                 "GraphicsTrianglePath",
@@ -997,6 +1091,7 @@ pub fn init_native_system_classes(activation: &mut Activation<'_, '_>) {
                 "RectangleTexture",
                 rectangletexture
             ),
+            ("flash.utils", "Timer", timer),
         ]
     );
 }
@@ -1,54 +1,140 @@
 package flash.desktop {
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
-    import flash.system.System;
+    import flash.filesystem.File;
 
     public class Clipboard {
-        private static var _generalClipboard = new Clipboard();
+        private static var _generalClipboard:Clipboard = null;
+
+        // Whether this is the system clipboard, whose standard formats are stored by the player.
+        private var _general:Boolean = false;
+
+        // The formats stored in this object, along with their data
+        // and the handlers rendering the data that hasn't been requested yet.
+        private var _formats:Array = [];
+        private var _data:Object = {};
+        private var _handlers:Object = {};
 
         public static function get generalClipboard(): Clipboard {
-            return Clipboard._generalClipboard;
+            if (_generalClipboard == null) {
+                _generalClipboard = new Clipboard();
+                _generalClipboard._general = true;
+            }
+            return _generalClipboard;
         }
 
         function Clipboard() {
             // TODO: This should only be callable in AIR
         }
 
+        private static native function generalFormats(): Array;
+        private static native function generalData(format: String): Object;
+        private static native function setGeneralData(format: String, data: Object): Boolean;
+        private static native function clearGeneralData(format: String): void;
+        private static native function clearGeneral(): void;
+
+        private function isGeneralFormat(format: String): Boolean {
+            if (!_general) {
+                return false;
+            }
+            switch (format) {
+                case ClipboardFormats.TEXT_FORMAT:
+                case ClipboardFormats.HTML_FORMAT:
+                case ClipboardFormats.RICH_TEXT_FORMAT:
+                case ClipboardFormats.URL_FORMAT:
+                case ClipboardFormats.FILE_LIST_FORMAT:
+                case ClipboardFormats.BITMAP_FORMAT:
+                    return true;
+                default:
+                    return false;
+            }
+        }
+
+        private function removeFormat(format: String): void {
+            var index:int = _formats.indexOf(format);
+            if (index != -1) {
+                _formats.splice(index, 1);
+            }
+            delete _data[format];
+            delete _handlers[format];
+        }
+
         public function get formats(): Array {
-            stub_getter("flash.desktop.Clipboard", "formats");
-            return new Array();
+            var formats:Array = _general ? generalFormats() : [];
+            return formats.concat(_formats);
         }
 
         public function clear(): void {
-            stub_method("flash.desktop.Clipboard", "clear");
+            if (_general) {
+                clearGeneral();
+            }
+            _formats = [];
+            _data = {};
+            _handlers = {};
         }
 
         public function clearData(format: String): void {
-            stub_method("flash.desktop.Clipboard", "clearData");
+            if (isGeneralFormat(format)) {
+                clearGeneralData(format);
+            } else {
+                removeFormat(format);
+            }
         }
 
+        // The data is never serialized, so every transfer mode gets the original objects.
         public function getData(format: String, transferMode: String = ClipboardTransferMode.ORIGINAL_PREFERRED): Object {
-            stub_method("flash.desktop.Clipboard", "getData");
-            return null;
+            if (isGeneralFormat(format)) {
+                var data:Object = generalData(format);
+                if (data != null && format == ClipboardFormats.FILE_LIST_FORMAT) {
+                    var files:Array = [];
+                    for each (var path:String in data) {
+                        files.push(new File(path));
+                    }
+                    return files;
+                }
+                return data;
+            }
+
+            if (_formats.indexOf(format) == -1) {
+                return null;
+            }
+            if (format in _handlers) {
+                _data[format] = _handlers[format]();
+                delete _handlers[format];
+            }
+            return _data[format];
         }
 
         public function hasFormat(format: String): Boolean {
-            stub_method("flash.desktop.Clipboard", "hasFormat");
-            return false;
+            return formats.indexOf(format) != -1;
         }
 
         public function setData(format: String, data: Object, serializable: Boolean = true): Boolean {
-            stub_method("flash.desktop.Clipboard", "setData");
-            if (format == ClipboardFormats.TEXT_FORMAT) {
-                System.setClipboard(data);
-                return true;
+            if (isGeneralFormat(format)) {
+                if (data is Array && format == ClipboardFormats.FILE_LIST_FORMAT) {
+                    var paths:Array = [];
+                    for each (var file:File in data) {
+                        paths.push(file.nativePath);
+                    }
+                    data = paths;
+                }
+                return setGeneralData(format, data);
             }
-            return false;
+
+            removeFormat(format);
+            _formats.push(format);
+            _data[format] = data;
+            return true;
         }
 
         public function setDataHandler(format: String, handler: Function, serializable: Boolean = true): Boolean {
-            stub_method("flash.desktop.Clipboard", "setDataHandler");
-            return false;
+            if (isGeneralFormat(format)) {
+                // The system clipboard can't ask for the data later, so it's rendered right away.
+                return setData(format, handler(), serializable);
+            }
+
+            removeFormat(format);
+            _formats.push(format);
+            _handlers[format] = handler;
+            return true;
         }
     }
 }
@@ -0,0 +1,234 @@
+//! `flash.desktop.Clipboard` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::object::{ArrayObject, BitmapDataObject, ByteArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::{ClipboardBitmap, ClipboardData, ClipboardFormat, UiBackend};
+use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper, Color};
+use crate::string::{AvmString, WStr};
+use gc_arena::GcCell;
+
+/// The formats stored on the system clipboard, by their names in `ClipboardFormats`.
+const FORMATS: [(&str, ClipboardFormat); 6] = [
+    ("air:text", ClipboardFormat::Text),
+    ("air:html", ClipboardFormat::Html),
+    ("air:rtf", ClipboardFormat::RichText),
+    ("air:url", ClipboardFormat::Url),
+    ("air:file list", ClipboardFormat::FileList),
+    ("air:bitmap", ClipboardFormat::Bitmap),
+];
+
+fn format_from_name(name: &WStr) -> Option<ClipboardFormat> {
+    FORMATS
+        .iter()
+        .find(|(format_name, _)| name == WStr::from_units(format_name.as_bytes()))
+        .map(|(_, format)| *format)
+}
+
+fn format_name(format: ClipboardFormat) -> &'static str {
+    FORMATS
+        .iter()
+        .find(|(_, f)| *f == format)
+        .map(|(name, _)| *name)
+        .expect("Every format has a name")
+}
+
+/// The data on the system clipboard, except for the given format.
+fn other_data(ui: &mut dyn UiBackend, format: ClipboardFormat) -> Vec<ClipboardData> {
+    ui.clipboard_formats()
+        .into_iter()
+        .filter(|f| *f != format)
+        .filter_map(|f| ui.clipboard_data(f))
+        .collect()
+}
+
+fn data_to_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    data: ClipboardData,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let gc_context = activation.context.gc_context;
+    Ok(match data {
+        ClipboardData::Text(text) | ClipboardData::Html(text) | ClipboardData::Url(text) => {
+            AvmString::new_utf8(gc_context, text).into()
+        }
+        ClipboardData::RichText(bytes) => {
+            ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(bytes))?.into()
+        }
+        ClipboardData::FileList(paths) => {
+            let paths = paths
+                .into_iter()
+                .map(|path| Some(AvmString::new_utf8(gc_context, path).into()))
+                .collect();
+            ArrayObject::from_storage(activation, ArrayStorage::from_storage(paths))?.into()
+        }
+        ClipboardData::Bitmap(bitmap) => {
+            let pixels = bitmap
+                .rgba
+                .chunks_exact(4)
+                .map(|pixel| {
+                    Color::argb(pixel[3], pixel[0], pixel[1], pixel[2]).to_premultiplied_alpha(true)
+                })
+                .collect();
+            let bitmap_data =
+                BitmapData::new_with_pixels(bitmap.width, bitmap.height, true, pixels);
+            let bitmap_data = BitmapDataWrapper::new(GcCell::new(gc_context, bitmap_data));
+            let class = activation.avm2().classes().bitmapdata;
+            BitmapDataObject::from_bitmap_data_internal(activation, bitmap_data, class)?.into()
+        }
+    })
+}
+
+/// Converts data set by a movie into the given format, if it has the right type.
+fn value_to_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    format: ClipboardFormat,
+    value: Value<'gc>,
+) -> Result<Option<ClipboardData>, Error<'gc>> {
+    if matches!(value, Value::Null | Value::Undefined) {
+        return Ok(None);
+    }
+
+    let data = match format {
+        ClipboardFormat::Text => {
+            ClipboardData::Text(value.coerce_to_string(activation)?.to_string())
+        }
+        ClipboardFormat::Html => {
+            ClipboardData::Html(value.coerce_to_string(activation)?.to_string())
+        }
+        ClipboardFormat::Url => ClipboardData::Url(value.coerce_to_string(activation)?.to_string()),
+        ClipboardFormat::RichText => {
+            match value
+                .as_object()
+                .and_then(|o| o.as_bytearray().map(|b| b.bytes().to_vec()))
+            {
+                Some(bytes) => ClipboardData::RichText(bytes),
+                None => return Ok(None),
+            }
+        }
+        ClipboardFormat::FileList => {
+            let Some(paths) = value
+                .as_object()
+                .and_then(|o| o.as_array_storage().map(|a| a.iter().collect::<Vec<_>>()))
+            else {
+                return Ok(None);
+            };
+            let mut file_list = Vec::with_capacity(paths.len());
+            for path in paths.into_iter().flatten() {
+                file_list.push(path.coerce_to_string(activation)?.to_string());
+            }
+            ClipboardData::FileList(file_list)
+        }
+        ClipboardFormat::Bitmap => {
+            let Some(bitmap_data) = value.as_object().and_then(|o| o.as_bitmap_data()) else {
+                return Ok(None);
+            };
+            let rgba = bitmap_data
+                .sync(activation.context.renderer)
+                .read()
+                .pixels()
+                .iter()
+                .flat_map(|pixel| {
+                    let pixel = pixel.to_un_multiplied_alpha();
+                    [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+                })
+                .collect();
+            ClipboardData::Bitmap(ClipboardBitmap {
+                width: bitmap_data.width(),
+                height: bitmap_data.height(),
+                rgba,
+            })
+        }
+    };
+
+    Ok(Some(data))
+}
+
+/// Implements `Clipboard.formats` for the formats on the system clipboard
+pub fn general_formats<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let formats: Vec<Value<'gc>> = activation
+        .context
+        .ui
+        .clipboard_formats()
+        .into_iter()
+        .map(|format| format_name(format).into())
+        .collect();
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&formats))?.into())
+}
+
+/// Implements `Clipboard.getData` for the formats on the system clipboard
+pub fn general_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let format = args.get_string(activation, 0)?;
+
+    let data =
+        format_from_name(&format).and_then(|format| activation.context.ui.clipboard_data(format));
+    match data {
+        Some(data) => data_to_value(activation, data),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `Clipboard.setData` for the formats on the system clipboard
+pub fn set_general_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let format = args.get_string(activation, 0)?;
+    let Some(format) = format_from_name(&format) else {
+        return Ok(false.into());
+    };
+    if !activation.context.ui.supports_clipboard_format(format) {
+        return Ok(false.into());
+    }
+    let Some(data) = value_to_data(activation, format, args.get_value(1))? else {
+        return Ok(false.into());
+    };
+
+    let ui = &mut *activation.context.ui;
+    let mut clipboard = other_data(ui, format);
+    clipboard.push(data);
+    ui.set_clipboard_data(clipboard);
+
+    Ok(true.into())
+}
+
+/// Implements `Clipboard.clearData` for the formats on the system clipboard
+pub fn clear_general_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let format = args.get_string(activation, 0)?;
+
+    if let Some(format) = format_from_name(&format) {
+        let ui = &mut *activation.context.ui;
+        let clipboard = other_data(ui, format);
+        ui.set_clipboard_data(clipboard);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Clipboard.clear` for the system clipboard
+pub fn clear_general<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.ui.set_clipboard_data(vec![]);
+
+    Ok(Value::Undefined)
+}
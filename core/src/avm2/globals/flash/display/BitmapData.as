@@ -104,5 +104,30 @@ package flash.display {
             stub_method("flash.display.BitmapData", "generateFilterRect");
             return sourceRect.clone();
         }
+
+        [API("680")]
+        public function encode(rect:Rectangle, compressor:Object, byteArray:ByteArray = null):ByteArray {
+            if (compressor == null) {
+                throw new TypeError("Error #2007: Parameter compressor must be non-null.", 2007);
+            }
+            if (byteArray == null) {
+                byteArray = new ByteArray();
+            }
+            if (compressor is PNGEncoderOptions) {
+                this.encodePNG(rect, PNGEncoderOptions(compressor).fastCompression, byteArray);
+            } else if (compressor is JPEGEncoderOptions) {
+                this.encodeJPEG(rect, JPEGEncoderOptions(compressor).quality, byteArray);
+            } else if (compressor is JPEGXREncoderOptions) {
+                // JPEG XR encoding isn't implemented yet. The `jpegxr` crate can only decode images,
+                // so nothing is written to the ByteArray.
+                stub_method("flash.display.BitmapData", "encode", "with JPEGXREncoderOptions");
+            } else {
+                throw new ArgumentError("Error #2008: Parameter compressor must be one of the accepted values.", 2008);
+            }
+            return byteArray;
+        }
+
+        private native function encodePNG(rect:Rectangle, fastCompression:Boolean, byteArray:ByteArray):void;
+        private native function encodeJPEG(rect:Rectangle, quality:uint, byteArray:ByteArray):void;
     }
 }
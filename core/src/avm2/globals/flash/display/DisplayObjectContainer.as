@@ -40,8 +40,7 @@ package flash.display {
         public native function areInaccessibleObjectsUnderPoint(point:Point):Boolean;
 
         public function get textSnapshot():TextSnapshot {
-            stub_getter("flash.display.DisplayObjectContainer", "textSnapshot")
-            return new TextSnapshot();
+            return new TextSnapshot(this);
         }
     }
 }
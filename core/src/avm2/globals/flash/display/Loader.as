@@ -6,7 +6,6 @@ package flash.display {
     import flash.utils.ByteArray;
     import flash.net.URLRequest;
     import flash.events.UncaughtErrorEvents;
-    import __ruffle__.stub_method;
 
     [Ruffle(InstanceAllocator)]
     public class Loader extends DisplayObjectContainer {
@@ -28,14 +27,9 @@ package flash.display {
 
         public native function unload():void;
 
-        public function unloadAndStop(gc:Boolean = true):void {
-            stub_method("flash.display.Loader", "unloadAndStop");
-            this.unload();
-        }
+        public native function unloadAndStop(gc:Boolean = true):void;
 
-        public function close():void {
-            stub_method("flash.display.Loader", "close");
-        }
+        public native function close():void;
 
         override public function addChild(child:DisplayObject):DisplayObject {
             throw new IllegalOperationError("Error #2069: The Loader class does not implement this method.", 2069);
@@ -14,7 +14,7 @@ use crate::avm2::vector::VectorStorage;
 use crate::avm2::Error;
 use crate::avm2_stub_method;
 use crate::bitmap::bitmap_data::{
-    BitmapData, BitmapDataWrapper, ChannelOptions, ThresholdOperation,
+    BitmapData, BitmapDataWrapper, ChannelOptions, EncodeFormat, ThresholdOperation,
 };
 use crate::bitmap::bitmap_data::{BitmapDataDrawError, IBitmapDrawable};
 use crate::bitmap::{is_size_valid, operations};
@@ -351,6 +351,68 @@ pub fn copy_pixels_to_byte_array<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Encodes the given region of a `BitmapData` into a `ByteArray`.
+fn encode_to_byte_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    bitmap_data: BitmapDataWrapper<'gc>,
+    args: &[Value<'gc>],
+    format: EncodeFormat,
+) -> Result<(), Error<'gc>> {
+    bitmap_data.check_valid(activation)?;
+    let rectangle = args.get_object(activation, 0, "rect")?;
+    let storage = args.get_object(activation, 2, "byteArray")?;
+    let mut storage = storage.as_bytearray_mut().unwrap();
+    let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rectangle)?;
+    operations::encode(
+        activation,
+        bitmap_data,
+        x,
+        y,
+        width,
+        height,
+        format,
+        &mut storage,
+    )
+}
+
+/// Implements `BitmapData.encode` with `PNGEncoderOptions`.
+pub fn encode_png<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.as_bitmap_data() {
+        let fast_compression = args.get_bool(1);
+        encode_to_byte_array(
+            activation,
+            bitmap_data,
+            args,
+            EncodeFormat::Png { fast_compression },
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.encode` with `JPEGEncoderOptions`.
+pub fn encode_jpeg<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.as_bitmap_data() {
+        let quality = args.get_u32(activation, 1)?.clamp(1, 100) as u8;
+        encode_to_byte_array(
+            activation,
+            bitmap_data,
+            args,
+            EncodeFormat::Jpeg { quality },
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_vector<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -368,93 +368,139 @@ pub fn set_y<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `z`'s getter.
 pub fn get_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "z");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(dobj.z().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `z`'s setter.
 pub fn set_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "z");
+    if let Some(dobj) = this.as_display_object() {
+        let z = args.get_f64(activation, 0)?;
+        dobj.set_z(activation.context.gc_context, z);
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Wraps a rotation into the range reported by Flash, (-180, 180].
+fn normalize_rotation(degrees: f64) -> f64 {
+    let rem = degrees % 360.0;
+    if rem <= 180.0 {
+        rem
+    } else {
+        rem - 360.0
+    }
+}
+
+/// Implements `rotationX`'s getter.
 pub fn get_rotation_x<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationX");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(normalize_rotation(dobj.rotation_x().into()).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationX`'s setter.
 pub fn set_rotation_x<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationX");
+    if let Some(dobj) = this.as_display_object() {
+        let new_rotation = args.get_f64(activation, 0)?;
+        dobj.set_rotation_x(activation.context.gc_context, Degrees::from(new_rotation));
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s getter.
 pub fn get_rotation_y<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationY");
-    Ok(0.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(normalize_rotation(dobj.rotation_y().into()).into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `rotationY`'s setter.
 pub fn set_rotation_y<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationY");
+    if let Some(dobj) = this.as_display_object() {
+        let new_rotation = args.get_f64(activation, 0)?;
+        dobj.set_rotation_y(activation.context.gc_context, Degrees::from(new_rotation));
+    }
+
     Ok(Value::Undefined)
 }
 
+/// Implements `rotationZ`'s getter, which is the same as `rotation`.
 pub fn get_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "rotationZ");
-    Ok(0.into())
+    get_rotation(activation, this, args)
 }
 
+/// Implements `rotationZ`'s setter, which is the same as `rotation`.
 pub fn set_rotation_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "rotationZ");
-    Ok(Value::Undefined)
+    set_rotation(activation, this, args)
 }
 
+/// Implements `scaleZ`'s getter.
 pub fn get_scale_z<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.DisplayObject", "scaleZ");
-    Ok(1.into())
+    if let Some(dobj) = this.as_display_object() {
+        return Ok(dobj.scale_z().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
+/// Implements `scaleZ`'s setter.
 pub fn set_scale_z<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.DisplayObject", "scaleZ");
+    if let Some(dobj) = this.as_display_object() {
+        let new_scale = args.get_f64(activation, 0)?;
+        dobj.set_scale_z(activation.context.gc_context, new_scale);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -466,13 +512,7 @@ pub fn get_rotation<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(dobj) = this.as_display_object() {
         let rot: f64 = dobj.rotation(activation.context.gc_context).into();
-        let rem = rot % 360.0;
-
-        if rem <= 180.0 {
-            return Ok(rem.into());
-        } else {
-            return Ok((rem - 360.0).into());
-        }
+        return Ok(normalize_rotation(rot).into());
     }
 
     Ok(Value::Undefined)
@@ -974,7 +1014,7 @@ pub fn get_bounds<'gc>(
         let mut out_bounds = if DisplayObject::ptr_eq(dobj, target) {
             // Getting the clips bounds in its own coordinate space; no AABB transform needed.
             bounds
-        } else {
+        } else if !dobj.is_in_3d() && !target.is_in_3d() {
             // Transform AABB to target space.
             // Calculate the matrix to transform into the target coordinate space, and transform the above AABB.
             // Note that this doesn't produce as tight of an AABB as if we had used `bounds_with_transform` with
@@ -982,6 +1022,23 @@ pub fn get_bounds<'gc>(
             let to_global_matrix = dobj.local_to_global_matrix();
             let to_target_matrix = target.global_to_local_matrix().unwrap_or_default();
             to_target_matrix * to_global_matrix * bounds
+        } else {
+            // 3D transforms can't be described by a single matrix,
+            // so the bounds are projected onto the stage and back into the target space.
+            let world_bounds = dobj.world_bounds();
+            if world_bounds.is_valid() {
+                [
+                    Point::new(world_bounds.x_min, world_bounds.y_min),
+                    Point::new(world_bounds.x_max, world_bounds.y_min),
+                    Point::new(world_bounds.x_min, world_bounds.y_max),
+                    Point::new(world_bounds.x_max, world_bounds.y_max),
+                ]
+                .into_iter()
+                .filter_map(|corner| target.global_to_local(corner))
+                .fold(Rectangle::default(), Rectangle::encompass)
+            } else {
+                world_bounds
+            }
         };
         if !out_bounds.is_valid() {
             out_bounds = Rectangle::ZERO;
@@ -5,17 +5,24 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error::{make_error_2004, make_error_2007, make_error_2008, Error2004Type};
-use crate::avm2::globals::flash::geom::transform::object_to_matrix;
-use crate::avm2::object::{Object, TObject, VectorObject};
+use crate::avm2::globals::flash::display::shader_job::get_shader_args;
+use crate::avm2::globals::flash::geom::transform::{matrix_to_object, object_to_matrix};
+use crate::avm2::globals::slots::{
+    FLASH_DISPLAY_GRAPHICS__FILL_SOURCES_SLOT, FLASH_DISPLAY_SHAPE__GRAPHICS_SLOT,
+    FLASH_DISPLAY_SPRITE__GRAPHICS_SLOT,
+};
+use crate::avm2::object::{ArrayObject, Object, TObject, VectorObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::vector::VectorStorage;
 use crate::avm2::{ArrayStorage, Error};
 use crate::avm2_stub_method;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::drawing::Drawing;
 use crate::string::{AvmString, WStr};
-use ruffle_render::shape_utils::{DrawCommand, FillRule, GradientType};
+use ruffle_render::bitmap::BitmapInfo;
+use ruffle_render::matrix::Matrix as RenderMatrix;
+use ruffle_render::shape_utils::{DrawCommand, DrawPath, FillRule, GradientType, ShaderFill};
 use std::f64::consts::FRAC_1_SQRT_2;
 use swf::{
     Color, FillStyle, Fixed16, Fixed8, Gradient, GradientInterpolation, GradientRecord,
@@ -28,6 +35,81 @@ fn color_from_args(rgb: u32, alpha: f64) -> Color {
     Color::from_rgb(rgb, (alpha * 255.0) as u8)
 }
 
+/// Remember the `BitmapData` or `Shader` that a bitmap of the drawing came from,
+/// so that `readGraphicsData` can hand it back out.
+fn set_fill_source<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
+    id: u16,
+    source: Object<'gc>,
+) -> Result<(), Error<'gc>> {
+    let sources = match graphics.get_slot(FLASH_DISPLAY_GRAPHICS__FILL_SOURCES_SLOT) {
+        Value::Object(sources) => sources,
+        _ => {
+            let sources = ArrayObject::empty(activation)?;
+            graphics.set_slot(
+                FLASH_DISPLAY_GRAPHICS__FILL_SOURCES_SLOT,
+                sources.into(),
+                activation,
+            )?;
+            sources
+        }
+    };
+    sources
+        .as_array_storage_mut(activation.context.gc_context)
+        .expect("Fill sources should be an Array")
+        .set(id as usize, source.into());
+
+    Ok(())
+}
+
+/// Add a bitmap to the drawing, remembering the `BitmapData` it came from.
+fn add_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
+    drawing: &mut Drawing,
+    bitmap: BitmapInfo,
+    fill_scale: Matrix,
+    bitmap_data: Object<'gc>,
+) -> Result<u16, Error<'gc>> {
+    let id = drawing.add_bitmap(bitmap, fill_scale);
+    set_fill_source(activation, graphics, id, bitmap_data)?;
+    Ok(id)
+}
+
+/// Build a fill running `shader`, with the shader's coordinates mapped through `matrix`.
+fn shader_fill<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    shader: Object<'gc>,
+    matrix: Matrix,
+) -> Result<ShaderFill, Error<'gc>> {
+    let (shader_handle, arguments) = get_shader_args(shader, activation)?;
+    // Shaders work in pixels, like bitmaps.
+    let scale_matrix = Matrix::scale(
+        (Twips::TWIPS_PER_PIXEL as i16).into(),
+        (Twips::TWIPS_PER_PIXEL as i16).into(),
+    );
+    Ok(ShaderFill {
+        shader: shader_handle,
+        arguments,
+        matrix: matrix * scale_matrix,
+    })
+}
+
+/// Add a shader fill to the drawing, remembering the `Shader` it came from.
+/// Returns the style to fill paths with.
+fn add_shader_fill<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
+    drawing: &mut Drawing,
+    fill: ShaderFill,
+    shader: Object<'gc>,
+) -> Result<FillStyle, Error<'gc>> {
+    let id = drawing.add_shader_fill(fill.clone());
+    set_fill_source(activation, graphics, id, shader)?;
+    Ok(fill.bitmap_fill(id))
+}
+
 /// Implements `Graphics.beginFill`.
 pub fn begin_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -52,9 +134,9 @@ pub fn begin_bitmap_fill<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(this) = this.as_display_object() {
-        let bitmap = args
-            .get_object(activation, 0, "bitmap")?
+    if let Some(dobj) = this.as_display_object() {
+        let bitmap_data = args.get_object(activation, 0, "bitmap")?;
+        let bitmap = bitmap_data
             .as_bitmap_data()
             .expect("Bitmap argument is ensured to be a BitmapData from actionscript");
         let matrix = if let Some(matrix) = args.try_get_object(activation, 1) {
@@ -69,7 +151,7 @@ pub fn begin_bitmap_fill<'gc>(
         let handle =
             bitmap.bitmap_handle(activation.context.gc_context, activation.context.renderer);
 
-        let bitmap = ruffle_render::bitmap::BitmapInfo {
+        let bitmap = BitmapInfo {
             handle,
             width: bitmap.width() as u16,
             height: bitmap.height() as u16,
@@ -79,8 +161,15 @@ pub fn begin_bitmap_fill<'gc>(
             (Twips::TWIPS_PER_PIXEL as i16).into(),
         );
 
-        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
-            let id = draw.add_bitmap(bitmap);
+        if let Some(mut draw) = dobj.as_drawing(activation.context.gc_context) {
+            let id = add_bitmap(
+                activation,
+                this,
+                &mut draw,
+                bitmap,
+                scale_matrix,
+                bitmap_data,
+            )?;
             draw.set_fill_style(Some(FillStyle::Bitmap {
                 id,
                 matrix: matrix * scale_matrix,
@@ -93,6 +182,32 @@ pub fn begin_bitmap_fill<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Graphics.beginShaderFill`.
+pub fn begin_shader_fill<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(dobj) = this.as_display_object() {
+        let shader = args.get_object(activation, 0, "shader")?;
+        let matrix = if let Some(matrix) = args.try_get_object(activation, 1) {
+            Matrix::from(object_to_matrix(matrix, activation)?)
+        } else {
+            // Users can explicitly pass in `null` to mean identity matrix
+            Matrix::IDENTITY
+        };
+
+        let fill = shader_fill(activation, shader, matrix)?;
+
+        if let Some(mut draw) = dobj.as_drawing(activation.context.gc_context) {
+            let style = add_shader_fill(activation, this, &mut draw, fill, shader)?;
+            draw.set_fill_style(Some(style));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `Graphics.beginGradientFill`.
 pub fn begin_gradient_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -247,10 +362,15 @@ pub fn clear<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(this) = this.as_display_object() {
-        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+    if let Some(dobj) = this.as_display_object() {
+        if let Some(mut draw) = dobj.as_drawing(activation.context.gc_context) {
             draw.clear()
         }
+        this.set_slot(
+            FLASH_DISPLAY_GRAPHICS__FILL_SOURCES_SLOT,
+            Value::Null,
+            activation,
+        )?;
     }
 
     Ok(Value::Undefined)
@@ -944,21 +1064,40 @@ pub fn copy_from<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(this) = this.as_display_object() {
-        let source = args
-            .get_object(activation, 0, "sourceGraphics")?
+    if let Some(dobj) = this.as_display_object() {
+        let source_graphics = args.get_object(activation, 0, "sourceGraphics")?;
+        let source = source_graphics
             .as_display_object()
             .expect("Bad sourceGraphics");
 
-        let source = source
-            .as_drawing(activation.context.gc_context)
-            .expect("Missing drawing for sourceGraphics");
+        {
+            let source = source
+                .as_drawing(activation.context.gc_context)
+                .expect("Missing drawing for sourceGraphics");
+
+            let mut target_drawing = dobj
+                .as_drawing(activation.context.gc_context)
+                .expect("Missing drawing for target");
 
-        let mut target_drawing = this
-            .as_drawing(activation.context.gc_context)
-            .expect("Missing drawing for target");
+            target_drawing.copy_from(&source);
+        }
 
-        target_drawing.copy_from(&source);
+        // The copied drawing uses the same fill sources as the original.
+        let sources = match source_graphics.get_slot(FLASH_DISPLAY_GRAPHICS__FILL_SOURCES_SLOT) {
+            Value::Object(sources) => {
+                let storage = sources
+                    .as_array_storage()
+                    .expect("Fill sources should be an Array")
+                    .clone();
+                ArrayObject::from_storage(activation, storage)?.into()
+            }
+            _ => Value::Null,
+        };
+        this.set_slot(
+            FLASH_DISPLAY_GRAPHICS__FILL_SOURCES_SLOT,
+            sources,
+            activation,
+        )?;
     }
     Ok(Value::Undefined)
 }
@@ -1231,12 +1370,12 @@ pub fn draw_graphics_data<'gc>(
         .get_object(activation, 0, "graphicsData")?
         .as_vector_storage()
     {
-        let this = this.as_display_object().expect("Bad this");
+        let dobj = this.as_display_object().expect("Bad this");
 
-        if let Some(mut drawing) = this.as_drawing(activation.context.gc_context) {
+        if let Some(mut drawing) = dobj.as_drawing(activation.context.gc_context) {
             for elem in vector.iter() {
                 if let Some(obj) = elem.as_object() {
-                    handle_igraphics_data(activation, &mut drawing, &obj)?;
+                    handle_igraphics_data(activation, this, &mut drawing, &obj)?;
                 }
             }
         };
@@ -1251,9 +1390,9 @@ pub fn line_bitmap_style<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(this) = this.as_display_object() {
-        let bitmap = args
-            .get_object(activation, 0, "bitmap")?
+    if let Some(dobj) = this.as_display_object() {
+        let bitmap_data = args.get_object(activation, 0, "bitmap")?;
+        let bitmap = bitmap_data
             .as_bitmap_data()
             .expect("Bitmap argument is ensured to be a BitmapData from actionscript");
         let matrix = if let Some(matrix) = args.try_get_object(activation, 1) {
@@ -1268,7 +1407,7 @@ pub fn line_bitmap_style<'gc>(
         let handle =
             bitmap.bitmap_handle(activation.context.gc_context, activation.context.renderer);
 
-        let bitmap = ruffle_render::bitmap::BitmapInfo {
+        let bitmap = BitmapInfo {
             handle,
             width: bitmap.width() as u16,
             height: bitmap.height() as u16,
@@ -1278,8 +1417,15 @@ pub fn line_bitmap_style<'gc>(
             Fixed16::from_f64(bitmap.height as f64),
         );
 
-        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
-            let id = draw.add_bitmap(bitmap);
+        if let Some(mut draw) = dobj.as_drawing(activation.context.gc_context) {
+            let id = add_bitmap(
+                activation,
+                this,
+                &mut draw,
+                bitmap,
+                scale_matrix,
+                bitmap_data,
+            )?;
             draw.set_line_fill_style(FillStyle::Bitmap {
                 id,
                 matrix: matrix * scale_matrix,
@@ -1295,13 +1441,385 @@ pub fn line_bitmap_style<'gc>(
 /// Implements `Graphics.readGraphicsData`
 pub fn read_graphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "readGraphicsData");
+    let recurse = args.get_bool(0);
+
+    let mut graphics_data = Vec::new();
+    if let Some(dobj) = this.as_display_object() {
+        let sources = this
+            .get_slot(FLASH_DISPLAY_GRAPHICS__FILL_SOURCES_SLOT)
+            .as_object();
+        read_drawing_data(
+            activation,
+            dobj,
+            sources,
+            RenderMatrix::IDENTITY,
+            &mut graphics_data,
+        )?;
+        if recurse {
+            read_children_data(activation, dobj, RenderMatrix::IDENTITY, &mut graphics_data)?;
+        }
+    }
+
     let value_type = activation.avm2().class_defs().igraphicsdata;
-    let new_storage = VectorStorage::new(0, false, Some(value_type), activation);
-    Ok(VectorObject::from_vector(new_storage, activation)?.into())
+    let storage = VectorStorage::from_values(graphics_data, false, Some(value_type));
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
+
+/// Appends the `IGraphicsData` of the drawings of the children of `dobj`, and of their
+/// own children, in the coordinate space that `matrix` transforms `dobj` into.
+fn read_children_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    dobj: DisplayObject<'gc>,
+    matrix: RenderMatrix,
+    graphics_data: &mut Vec<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let Some(container) = dobj.as_container() else {
+        return Ok(());
+    };
+
+    // Constructing the `IGraphicsData` objects runs ActionScript, which may change the children.
+    let children: Vec<_> = container.iter_render_list().collect();
+    for child in children {
+        let matrix = matrix * *child.base().matrix();
+        let sources = fill_sources(activation, child);
+        read_drawing_data(activation, child, sources, matrix, graphics_data)?;
+        read_children_data(activation, child, matrix, graphics_data)?;
+    }
+
+    Ok(())
+}
+
+/// The `BitmapData` and `Shader` objects used by the drawing of a `Shape` or `Sprite`, if any.
+fn fill_sources<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    dobj: DisplayObject<'gc>,
+) -> Option<Object<'gc>> {
+    let object = dobj.object2().as_object()?;
+    let classes = activation.avm2().classes();
+    let graphics = if object.is_of_type(classes.shape.inner_class_definition()) {
+        object.get_slot(FLASH_DISPLAY_SHAPE__GRAPHICS_SLOT)
+    } else if object.is_of_type(classes.sprite.inner_class_definition()) {
+        object.get_slot(FLASH_DISPLAY_SPRITE__GRAPHICS_SLOT)
+    } else {
+        return None;
+    };
+
+    graphics
+        .as_object()?
+        .get_slot(FLASH_DISPLAY_GRAPHICS__FILL_SOURCES_SLOT)
+        .as_object()
+}
+
+/// Appends the `IGraphicsData` describing the drawing of `dobj`, transformed by `matrix`.
+fn read_drawing_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    dobj: DisplayObject<'gc>,
+    sources: Option<Object<'gc>>,
+    matrix: RenderMatrix,
+    graphics_data: &mut Vec<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    // Work on a copy, as constructing the `IGraphicsData` objects runs ActionScript.
+    let Some(drawing) = dobj
+        .as_drawing(activation.context.gc_context)
+        .map(|drawing| drawing.clone())
+    else {
+        return Ok(());
+    };
+
+    let mut is_stroking = false;
+    for path in drawing.recorded_paths() {
+        let commands = match &path {
+            DrawPath::Fill { commands, .. } | DrawPath::Stroke { commands, .. } => commands,
+        };
+        // Styles that were replaced before anything was drawn with them leave nothing behind.
+        if commands
+            .iter()
+            .all(|command| matches!(command, DrawCommand::MoveTo(_)))
+        {
+            continue;
+        }
+
+        match path {
+            DrawPath::Fill {
+                style,
+                commands,
+                winding_rule,
+            } => {
+                // Fills are stored separately from the lines around them.
+                if is_stroking {
+                    let stroke = activation
+                        .avm2()
+                        .classes()
+                        .graphicsstroke
+                        .construct(activation, &[])?;
+                    graphics_data.push(stroke.into());
+                    is_stroking = false;
+                }
+
+                let fill = fill_style_to_object(activation, &drawing, style, sources, matrix)?;
+                graphics_data.push(fill.into());
+                let path = path_to_object(activation, &commands, winding_rule, matrix)?;
+                graphics_data.push(path.into());
+                let end_fill = activation
+                    .avm2()
+                    .classes()
+                    .graphicsendfill
+                    .construct(activation, &[])?;
+                graphics_data.push(end_fill.into());
+            }
+            DrawPath::Stroke {
+                style, commands, ..
+            } => {
+                let stroke = line_style_to_object(activation, &drawing, style, sources, matrix)?;
+                graphics_data.push(stroke.into());
+                is_stroking = true;
+                let path = path_to_object(activation, &commands, FillRule::EvenOdd, matrix)?;
+                graphics_data.push(path.into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds a `GraphicsPath` out of drawing commands, transforming their points by `matrix`.
+fn path_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    commands: &[DrawCommand],
+    winding_rule: FillRule,
+    matrix: RenderMatrix,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let push_point = |data: &mut Vec<Value<'gc>>, point: Point<Twips>| {
+        let point = matrix * point;
+        data.push(point.x.to_pixels().into());
+        data.push(point.y.to_pixels().into());
+    };
+
+    let mut command_values = Vec::with_capacity(commands.len());
+    let mut data = Vec::with_capacity(commands.len() * 2);
+    let mut last_command_is_move = false;
+    for command in commands {
+        let is_move = matches!(command, DrawCommand::MoveTo(_));
+        match *command {
+            DrawCommand::MoveTo(point) => {
+                // A move directly following another one replaces it.
+                if last_command_is_move {
+                    data.truncate(data.len() - 2);
+                } else {
+                    command_values.push(1.into());
+                }
+                push_point(&mut data, point);
+            }
+            DrawCommand::LineTo(point) => {
+                command_values.push(2.into());
+                push_point(&mut data, point);
+            }
+            DrawCommand::QuadraticCurveTo { control, anchor } => {
+                command_values.push(3.into());
+                push_point(&mut data, control);
+                push_point(&mut data, anchor);
+            }
+            DrawCommand::CubicCurveTo {
+                control_a,
+                control_b,
+                anchor,
+            } => {
+                command_values.push(6.into());
+                push_point(&mut data, control_a);
+                push_point(&mut data, control_b);
+                push_point(&mut data, anchor);
+            }
+        }
+        last_command_is_move = is_move;
+    }
+
+    let commands = VectorStorage::from_values(
+        command_values,
+        false,
+        Some(activation.avm2().class_defs().int),
+    );
+    let commands = VectorObject::from_vector(commands, activation)?;
+    let data = VectorStorage::from_values(data, false, Some(activation.avm2().class_defs().number));
+    let data = VectorObject::from_vector(data, activation)?;
+    let winding = match winding_rule {
+        FillRule::EvenOdd => "evenOdd",
+        FillRule::NonZero => "nonZero",
+    };
+
+    activation
+        .avm2()
+        .classes()
+        .graphicspath
+        .construct(activation, &[commands.into(), data.into(), winding.into()])
+}
+
+/// Builds the `IGraphicsFill` matching a fill style of `drawing`, with its matrix transformed
+/// by `matrix`. `sources` holds the `BitmapData` and `Shader` objects used by the drawing, if any.
+fn fill_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    drawing: &Drawing,
+    style: &FillStyle,
+    sources: Option<Object<'gc>>,
+    matrix: RenderMatrix,
+) -> Result<Object<'gc>, Error<'gc>> {
+    match style {
+        FillStyle::Color(color) => {
+            let alpha = f64::from(color.a) / 255.0;
+            activation
+                .avm2()
+                .classes()
+                .graphicssolidfill
+                .construct(activation, &[color.to_rgb().into(), alpha.into()])
+        }
+        FillStyle::LinearGradient(gradient) => {
+            gradient_to_object(activation, "linear", gradient, 0.0, matrix)
+        }
+        FillStyle::RadialGradient(gradient) => {
+            gradient_to_object(activation, "radial", gradient, 0.0, matrix)
+        }
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => gradient_to_object(activation, "radial", gradient, focal_point.to_f64(), matrix),
+        FillStyle::Bitmap {
+            id,
+            matrix: fill_matrix,
+            is_smoothed,
+            is_repeating,
+        } => {
+            // A drawing copied from a SWF shape has no `BitmapData` to hand back.
+            let source = sources
+                .and_then(|sources| {
+                    let sources = sources.as_array_storage()?;
+                    sources.get(*id as usize)
+                })
+                .unwrap_or(Value::Null);
+            // Undo the scaling applied when the fill was begun. Bitmap fills of SWF shapes
+            // and shader fills are scaled from pixels to twips.
+            let fill_scale = drawing
+                .bitmap_fill_scale(*id)
+                .map_or(RenderMatrix::PIXELS_TO_TWIPS, RenderMatrix::from);
+            let matrix = matrix
+                * RenderMatrix::from(*fill_matrix)
+                * fill_scale.inverse().unwrap_or_default();
+            let matrix = matrix_to_object(matrix, activation)?;
+
+            if drawing.shader_fill(*id).is_some() {
+                return activation
+                    .avm2()
+                    .classes()
+                    .graphicsshaderfill
+                    .construct(activation, &[source, matrix]);
+            }
+
+            activation.avm2().classes().graphicsbitmapfill.construct(
+                activation,
+                &[
+                    source,
+                    matrix,
+                    (*is_repeating).into(),
+                    (*is_smoothed).into(),
+                ],
+            )
+        }
+    }
+}
+
+/// Builds a `GraphicsGradientFill` out of a gradient, with its matrix transformed by `matrix`.
+fn gradient_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    gradient_type: &'static str,
+    gradient: &Gradient,
+    focal_point: f64,
+    matrix: RenderMatrix,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let colors: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| record.color.to_rgb().into())
+        .collect();
+    let alphas: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| (f64::from(record.color.a) / 255.0).into())
+        .collect();
+    let ratios: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| record.ratio.into())
+        .collect();
+
+    let colors = ArrayObject::from_storage(activation, ArrayStorage::from_args(&colors))?;
+    let alphas = ArrayObject::from_storage(activation, ArrayStorage::from_args(&alphas))?;
+    let ratios = ArrayObject::from_storage(activation, ArrayStorage::from_args(&ratios))?;
+    let matrix = matrix_to_object(matrix * RenderMatrix::from(gradient.matrix), activation)?;
+    let spread = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation = match gradient.interpolation {
+        GradientInterpolation::Rgb => "rgb",
+        GradientInterpolation::LinearRgb => "linearRGB",
+    };
+
+    activation.avm2().classes().graphicsgradientfill.construct(
+        activation,
+        &[
+            gradient_type.into(),
+            colors.into(),
+            alphas.into(),
+            ratios.into(),
+            matrix,
+            spread.into(),
+            interpolation.into(),
+            focal_point.into(),
+        ],
+    )
+}
+
+/// Builds a `GraphicsStroke` out of a line style, with the matrix of its fill transformed
+/// by `matrix`.
+fn line_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    drawing: &Drawing,
+    style: &LineStyle,
+    sources: Option<Object<'gc>>,
+    matrix: RenderMatrix,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let scale_mode = match (style.allow_scale_x(), style.allow_scale_y()) {
+        (true, true) => "normal",
+        (true, false) => "horizontal",
+        (false, true) => "vertical",
+        (false, false) => "none",
+    };
+    let caps = match style.start_cap() {
+        LineCapStyle::Round => "round",
+        LineCapStyle::None => "none",
+        LineCapStyle::Square => "square",
+    };
+    let (joints, miter_limit) = match style.join_style() {
+        LineJoinStyle::Round => ("round", 3.0),
+        LineJoinStyle::Bevel => ("bevel", 3.0),
+        LineJoinStyle::Miter(miter_limit) => ("miter", miter_limit.to_f64()),
+    };
+    let fill = fill_style_to_object(activation, drawing, style.fill_style(), sources, matrix)?;
+
+    activation.avm2().classes().graphicsstroke.construct(
+        activation,
+        &[
+            style.width().to_pixels().into(),
+            style.is_pixel_hinted().into(),
+            scale_mode.into(),
+            caps.into(),
+            joints.into(),
+            miter_limit.into(),
+            fill.into(),
+        ],
+    )
 }
 
 fn read_point<'gc>(
@@ -1428,13 +1946,14 @@ fn process_commands<'gc>(
 
 fn handle_igraphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
     drawing: &mut Drawing,
     obj: &Object<'gc>,
 ) -> Result<(), Error<'gc>> {
     let class = obj.instance_class();
 
     if class == activation.avm2().class_defs().graphicsbitmapfill {
-        let style = handle_bitmap_fill(activation, drawing, obj)?;
+        let style = handle_bitmap_fill(activation, graphics, drawing, obj)?;
         drawing.set_fill_style(Some(style));
     } else if class == activation.avm2().class_defs().graphicsendfill {
         drawing.set_fill_style(None);
@@ -1463,8 +1982,8 @@ fn handle_igraphics_data<'gc>(
         let style = handle_solid_fill(activation, obj)?;
         drawing.set_fill_style(Some(style));
     } else if class == activation.avm2().class_defs().graphicsshaderfill {
-        tracing::warn!("Graphics shader fill unimplemented {:?}", class);
-        drawing.set_fill_style(None);
+        let style = handle_shader_fill(activation, graphics, drawing, obj)?;
+        drawing.set_fill_style(Some(style));
     } else if class == activation.avm2().class_defs().graphicsstroke {
         let thickness = obj
             .get_public_property("thickness", activation)?
@@ -1483,7 +2002,7 @@ fn handle_igraphics_data<'gc>(
                 let fill = obj.get_public_property("fill", activation)?.as_object();
 
                 if let Some(fill) = fill {
-                    handle_igraphics_fill(activation, drawing, &fill)?
+                    handle_igraphics_fill(activation, graphics, drawing, &fill)?
                 } else {
                     None
                 }
@@ -1566,13 +2085,14 @@ fn handle_graphics_triangle_path<'gc>(
 
 fn handle_igraphics_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
     drawing: &mut Drawing,
     obj: &Object<'gc>,
 ) -> Result<Option<FillStyle>, Error<'gc>> {
     let class = obj.instance_class();
 
     if class == activation.avm2().class_defs().graphicsbitmapfill {
-        let style = handle_bitmap_fill(activation, drawing, obj)?;
+        let style = handle_bitmap_fill(activation, graphics, drawing, obj)?;
         Ok(Some(style))
     } else if class == activation.avm2().class_defs().graphicsendfill {
         Ok(None)
@@ -1583,8 +2103,8 @@ fn handle_igraphics_fill<'gc>(
         let style = handle_solid_fill(activation, obj)?;
         Ok(Some(style))
     } else if class == activation.avm2().class_defs().graphicsshaderfill {
-        tracing::warn!("Graphics shader fill unimplemented {:?}", class);
-        Ok(None)
+        let style = handle_shader_fill(activation, graphics, drawing, obj)?;
+        Ok(Some(style))
     } else {
         tracing::warn!("Unknown graphics fill class {:?}", class);
         Ok(None)
@@ -1692,13 +2212,15 @@ fn handle_gradient_fill<'gc>(
 
 fn handle_bitmap_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
     drawing: &mut Drawing,
     obj: &Object<'gc>,
 ) -> Result<FillStyle, Error<'gc>> {
-    let bitmap_data = obj
+    let bitmap_object = obj
         .get_public_property("bitmapData", activation)?
         .as_object()
-        .ok_or_else(|| make_error_2007(activation, "bitmap"))?
+        .ok_or_else(|| make_error_2007(activation, "bitmap"))?;
+    let bitmap_data = bitmap_object
         .as_bitmap_data()
         .expect("Bitmap argument is ensured to be a BitmapData from actionscript");
 
@@ -1723,7 +2245,7 @@ fn handle_bitmap_fill<'gc>(
     let handle =
         bitmap_data.bitmap_handle(activation.context.gc_context, activation.context.renderer);
 
-    let bitmap = ruffle_render::bitmap::BitmapInfo {
+    let bitmap = BitmapInfo {
         handle,
         width: bitmap_data.width() as u16,
         height: bitmap_data.height() as u16,
@@ -1734,7 +2256,14 @@ fn handle_bitmap_fill<'gc>(
         Fixed16::from_f64(bitmap.height as f64),
     );
 
-    let id = drawing.add_bitmap(bitmap);
+    let id = add_bitmap(
+        activation,
+        graphics,
+        drawing,
+        bitmap,
+        scale_matrix,
+        bitmap_object,
+    )?;
 
     let style = FillStyle::Bitmap {
         id,
@@ -1745,3 +2274,28 @@ fn handle_bitmap_fill<'gc>(
 
     Ok(style)
 }
+
+fn handle_shader_fill<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    graphics: Object<'gc>,
+    drawing: &mut Drawing,
+    obj: &Object<'gc>,
+) -> Result<FillStyle, Error<'gc>> {
+    let shader = obj
+        .get_public_property("shader", activation)?
+        .as_object()
+        .ok_or_else(|| make_error_2007(activation, "shader"))?;
+
+    let matrix = obj
+        .get_public_property("matrix", activation)?
+        .as_object()
+        .and_then(|matrix| {
+            let matrix = Matrix::from(object_to_matrix(matrix, activation).ok()?);
+
+            Some(matrix)
+        })
+        .unwrap_or(Matrix::IDENTITY);
+
+    let fill = shader_fill(activation, shader, matrix)?;
+    add_shader_fill(activation, graphics, drawing, fill, shader)
+}
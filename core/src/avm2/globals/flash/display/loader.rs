@@ -15,8 +15,8 @@ use crate::avm2::ClassObject;
 use crate::avm2::{Error, Object};
 use crate::avm2_stub_method;
 use crate::backend::navigator::{NavigationMethod, Request};
-use crate::display_object::LoaderDisplay;
 use crate::display_object::MovieClip;
+use crate::display_object::{DisplayObject, LoaderDisplay};
 use crate::loader::LoadManager;
 use crate::loader::MovieLoaderVMData;
 use crate::tag_utils::SwfMovie;
@@ -275,9 +275,6 @@ pub fn unload<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // TODO: Broadcast an "unload" event on the LoaderInfo
-    avm2_stub_method!(activation, "flash.display.Loader", "unload");
-
     let loader_info = this
         .get_slot(FLASH_DISPLAY_LOADER__CONTENT_LOADER_INFO_SLOT)
         .as_object()
@@ -289,3 +286,34 @@ pub fn unload<'gc>(
 
     Ok(Value::Undefined)
 }
+
+pub fn unload_and_stop<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // There's no way to force a collection, so `gc` is ignored.
+    if let Some(DisplayObject::LoaderDisplay(loader)) = this.as_display_object() {
+        loader.stop_content(activation.context);
+    }
+
+    unload(activation, this, args)
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let loader_info = this
+        .get_slot(FLASH_DISPLAY_LOADER__CONTENT_LOADER_INFO_SLOT)
+        .as_object()
+        .unwrap();
+
+    activation
+        .context
+        .load_manager
+        .cancel_movie_loads(loader_info);
+
+    Ok(Value::Undefined)
+}
@@ -9,8 +9,11 @@ package flash.filesystem {
     public class File extends FileReference {
         private static var _applicationDirectory:File = null;
 
+        private var _nativePath:String;
+
         public function File(path:String = null) {
             stub_constructor("flash.filesystem.File");
+            _nativePath = path;
         }
 
         public static function get applicationDirectory():File {
@@ -27,6 +30,10 @@ package flash.filesystem {
             return new File();
         }
 
+        public function get nativePath():String {
+            return _nativePath;
+        }
+
         public function get exists():Boolean {
             stub_getter("flash.filesystem.File", "exists");
 
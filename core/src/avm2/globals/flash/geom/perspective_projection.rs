@@ -0,0 +1,11 @@
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// The width that a projection's field of view spans, which is the width of the stage.
+pub fn viewport_width<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (width, _) = activation.context.stage.stage_size();
+    Ok(width.into())
+}
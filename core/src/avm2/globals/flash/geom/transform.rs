@@ -1,10 +1,12 @@
 use crate::avm2::globals::slots::*;
+use crate::avm2::object::VectorObject;
 use crate::avm2::parameters::ParametersExt;
+use crate::avm2::vector::VectorStorage;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::display_object::TDisplayObject;
 use crate::prelude::{DisplayObject, Matrix, Twips};
-use crate::{avm2_stub_getter, avm2_stub_setter};
 use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::perspective_projection::PerspectiveProjection;
 use ruffle_render::quality::StageQuality;
 use swf::{ColorTransform, Fixed8, Rectangle};
 
@@ -52,14 +54,10 @@ pub fn get_matrix_3d<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // FIXME: This Matrix3D is generated from the 2D Matrix.
-    // It does not work when the matrix contains any transformation in 3D.
-    // Support native Matrix3D.
-    avm2_stub_getter!(activation, "flash.geom.Transform", "matrix3D");
-
-    let matrix = *get_display_object(this, activation)?.base().matrix();
-    let matrix3d = Matrix3D::from(matrix);
-    matrix3d_to_object(matrix3d, activation)
+    match get_display_object(this, activation)?.matrix3d() {
+        Some(matrix3d) => matrix3d_to_object(matrix3d, activation),
+        None => Ok(Value::Null),
+    }
 }
 
 pub fn set_matrix_3d<'gc>(
@@ -67,14 +65,13 @@ pub fn set_matrix_3d<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // FIXME: This sets 2D Matrix generated from the given Matrix3D, ignoring 3D parameters.
-    // Support native Matrix3D.
-    avm2_stub_setter!(activation, "flash.geom.Transform", "matrix3D");
-
-    let matrix3d = object_to_matrix3d(args.get_object(activation, 0, "value")?, activation)?;
-    let matrix = Matrix::from(matrix3d);
-    let matrix = matrix_to_object(matrix, activation)?;
-    set_matrix(activation, this, &[matrix])
+    let matrix3d = match args.try_get_object(activation, 0) {
+        Some(object) => Some(object_to_matrix3d(object, activation)?),
+        None => None,
+    };
+    let dobj = get_display_object(this, activation)?;
+    dobj.set_matrix3d(activation.context.gc_context, matrix3d);
+    Ok(Value::Undefined)
 }
 
 pub fn get_matrix<'gc>(
@@ -82,7 +79,12 @@ pub fn get_matrix<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let matrix = *get_display_object(this, activation)?.base().matrix();
+    let dobj = get_display_object(this, activation)?;
+    if dobj.matrix3d().is_some() {
+        // Objects in 3D space don't have a 2D matrix.
+        return Ok(Value::Null);
+    }
+    let matrix = *dobj.base().matrix();
     matrix_to_object(matrix, activation)
 }
 
@@ -96,6 +98,10 @@ pub fn set_matrix<'gc>(
     // remain its previous non-null value.
     let matrix = object_to_matrix(args.get_object(activation, 0, "value")?, activation)?;
     let dobj = get_display_object(this, activation)?;
+    if dobj.matrix3d().is_some() {
+        // Setting a 2D matrix moves the object back out of 3D space.
+        dobj.set_matrix3d(activation.context.gc_context, None);
+    }
     dobj.set_matrix(activation.context.gc_context, matrix);
     if let Some(parent) = dobj.parent() {
         // Self-transform changes are automatically handled,
@@ -105,6 +111,39 @@ pub fn set_matrix<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let projection = dobj.perspective_projection().or_else(|| {
+        // The root always has a projection, even if one was never set.
+        dobj.is_root().then(|| {
+            let (width, height) = activation.context.stage.stage_size();
+            PerspectiveProjection::for_viewport(width.into(), height.into())
+        })
+    });
+    match projection {
+        Some(projection) => perspective_projection_to_object(projection, activation),
+        None => Ok(Value::Null),
+    }
+}
+
+pub fn set_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let projection = match args.try_get_object(activation, 0) {
+        Some(object) => Some(object_to_perspective_projection(object, activation)?),
+        None => None,
+    };
+    let dobj = get_display_object(this, activation)?;
+    dobj.set_perspective_projection(activation.context.gc_context, projection);
+    Ok(Value::Undefined)
+}
+
 pub fn get_concatenated_matrix<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -209,12 +248,17 @@ fn matrix3d_to_object<'gc>(
     matrix: Matrix3D,
     activation: &mut Activation<'_, 'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let args = matrix.raw_data.map(Into::into);
+    let raw_data = VectorStorage::from_values(
+        matrix.raw_data.map(Into::into).to_vec(),
+        false,
+        Some(activation.avm2().class_defs().number),
+    );
+    let raw_data = VectorObject::from_vector(raw_data, activation)?;
     let object = activation
         .avm2()
         .classes()
         .matrix3d
-        .construct(activation, &args)?;
+        .construct(activation, &[raw_data.into()])?;
     Ok(object.into())
 }
 
@@ -241,6 +285,48 @@ fn object_to_matrix3d<'gc>(
     Ok(Matrix3D { raw_data })
 }
 
+fn perspective_projection_to_object<'gc>(
+    projection: PerspectiveProjection,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (center_x, center_y) = projection.center;
+    let center = activation
+        .avm2()
+        .classes()
+        .point
+        .construct(activation, &[center_x.into(), center_y.into()])?;
+    let object = activation
+        .avm2()
+        .classes()
+        .perspectiveprojection
+        .construct(activation, &[])?;
+    object.set_public_property("fieldOfView", projection.field_of_view.into(), activation)?;
+    object.set_public_property("projectionCenter", center.into(), activation)?;
+    Ok(object.into())
+}
+
+fn object_to_perspective_projection<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<PerspectiveProjection, Error<'gc>> {
+    let field_of_view = object
+        .get_public_property("fieldOfView", activation)?
+        .coerce_to_number(activation)?;
+    let center = object
+        .get_public_property("projectionCenter", activation)?
+        .coerce_to_object(activation)?;
+    let center_x = center
+        .get_public_property("x", activation)?
+        .coerce_to_number(activation)?;
+    let center_y = center
+        .get_public_property("y", activation)?
+        .coerce_to_number(activation)?;
+    Ok(PerspectiveProjection {
+        field_of_view,
+        center: (center_x, center_y),
+    })
+}
+
 pub fn matrix_to_object<'gc>(
     matrix: Matrix,
     activation: &mut Activation<'_, 'gc>,
@@ -0,0 +1,71 @@
+//! `flash.globalization` namespace
+
+use crate::avm2::object::VectorObject;
+use crate::avm2::vector::VectorStorage;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::globalization::{resolve_locale, LastOperationStatus, LocaleData};
+use crate::string::AvmString;
+
+pub mod currency_formatter;
+pub mod date_time_formatter;
+pub mod number_formatter;
+
+/// Creates a `Vector.<String>` out of a list of strings.
+fn string_vector<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    strings: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let values = strings
+        .into_iter()
+        .map(|s| AvmString::new_utf8(activation.context.gc_context, s.as_ref()).into())
+        .collect();
+    let storage =
+        VectorStorage::from_values(values, false, Some(activation.avm2().class_defs().string));
+    Ok(VectorObject::from_vector(storage, activation)?.into())
+}
+
+/// Implements `getAvailableLocaleIDNames` of all the formatters.
+fn available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    string_vector(activation, crate::globalization::available_locale_names())
+}
+
+/// Finds the locale for a requested locale name, storing its name and the status of the lookup
+/// in the given slots.
+fn init_locale<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    requested: AvmString<'gc>,
+    name_slot: u32,
+    status_slot: u32,
+) -> Result<&'static LocaleData, Error<'gc>> {
+    let language = activation.context.ui.language();
+    let (locale, status) = resolve_locale(&requested.to_utf8_lossy(), &language);
+
+    let name = AvmString::new_utf8(activation.context.gc_context, locale.name);
+    this.set_slot(name_slot, name.into(), activation)?;
+    set_status(activation, this, status_slot, status)?;
+    Ok(locale)
+}
+
+/// Looks up the locale whose name is stored in the given slot.
+fn actual_locale<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    name_slot: u32,
+) -> Result<&'static LocaleData, Error<'gc>> {
+    let name = this.get_slot(name_slot).coerce_to_string(activation)?;
+    let language = activation.context.ui.language();
+    Ok(resolve_locale(&name.to_utf8_lossy(), &language).0)
+}
+
+fn set_status<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    status_slot: u32,
+    status: LastOperationStatus,
+) -> Result<(), Error<'gc>> {
+    let status = AvmString::new_utf8(activation.context.gc_context, status.as_str());
+    this.set_slot(status_slot, status.into(), activation)
+}
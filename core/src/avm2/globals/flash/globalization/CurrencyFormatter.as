@@ -1,18 +1,205 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_method;
+    import flash.globalization.CurrencyParseResult;
+    import flash.globalization.LastOperationStatus;
+    import flash.globalization.NationalDigitsType;
 
     public final class CurrencyFormatter {
+        [Ruffle(InternalSlot)]
+        private var _actualLocaleName:String;
 
-        public var fractionalDigits: int = 0;
+        [Ruffle(InternalSlot)]
+        private var _currencyCode:String;
 
-        public function CurrencyFormatter(locale: String) {
-            stub_constructor("flash.globalization.CurrencyFormatter");
+        [Ruffle(InternalSlot)]
+        private var _currencySymbol:String;
+
+        [Ruffle(InternalSlot)]
+        private var _decimalSeparator:String;
+
+        [Ruffle(InternalSlot)]
+        private var _digitsType:uint = NationalDigitsType.EUROPEAN;
+
+        [Ruffle(InternalSlot)]
+        private var _fractionalDigits:int;
+
+        [Ruffle(InternalSlot)]
+        private var _groupingPattern:String;
+
+        [Ruffle(InternalSlot)]
+        private var _groupingSeparator:String;
+
+        [Ruffle(InternalSlot)]
+        private var _lastOperationStatus:String = LastOperationStatus.NO_ERROR;
+
+        [Ruffle(InternalSlot)]
+        private var _leadingZero:Boolean = true;
+
+        [Ruffle(InternalSlot)]
+        private var _negativeCurrencyFormat:uint;
+
+        [Ruffle(InternalSlot)]
+        private var _negativeSymbol:String = "-";
+
+        [Ruffle(InternalSlot)]
+        private var _positiveCurrencyFormat:uint;
+
+        [Ruffle(InternalSlot)]
+        private var _trailingZeros:Boolean = true;
+
+        [Ruffle(InternalSlot)]
+        private var _useGrouping:Boolean = true;
+
+        private var _localeIDName:String;
+
+        private static function throwNonNull(name: String) {
+            throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
+        }
+
+        public function CurrencyFormatter(requestedLocaleIDName:String) {
+            if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
+            this._localeIDName = requestedLocaleIDName;
+            this.init(requestedLocaleIDName);
         }
 
-        public function format(value:Number, withCurrencySymbol:Boolean = false):String {
-            stub_method("flash.globalization.CurrencyFormatter", "format");
-            return value.toString();
+        // Applies the conventions and currency of the locale that best matches the requested one.
+        private native function init(requestedLocaleIDName:String):void;
+
+        public function get actualLocaleIDName():String {
+            return this._actualLocaleName;
+        }
+
+        public function get currencyISOCode():String {
+            return this._currencyCode;
+        }
+
+        public function get currencySymbol():String {
+            return this._currencySymbol;
+        }
+
+        public function get decimalSeparator():String {
+            return this._decimalSeparator;
+        }
+        public function set decimalSeparator(value:String):void {
+            if (value == null) throwNonNull("decimalSeparator");
+            this._decimalSeparator = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get digitsType():uint {
+            return this._digitsType;
+        }
+        public function set digitsType(value:uint):void {
+            this._digitsType = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get fractionalDigits():int {
+            return this._fractionalDigits;
+        }
+        public function set fractionalDigits(value:int):void {
+            this._fractionalDigits = value < 0 ? 0 : value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get groupingPattern():String {
+            return this._groupingPattern;
+        }
+        public function set groupingPattern(value:String):void {
+            if (value == null) throwNonNull("groupingPattern");
+            this._groupingPattern = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get groupingSeparator():String {
+            return this._groupingSeparator;
+        }
+        public function set groupingSeparator(value:String):void {
+            if (value == null) throwNonNull("groupingSeparator");
+            this._groupingSeparator = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get lastOperationStatus():String {
+            return this._lastOperationStatus;
+        }
+
+        public function get leadingZero():Boolean {
+            return this._leadingZero;
+        }
+        public function set leadingZero(value:Boolean):void {
+            this._leadingZero = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get negativeCurrencyFormat():uint {
+            return this._negativeCurrencyFormat;
+        }
+        public function set negativeCurrencyFormat(value:uint):void {
+            if (value > 15) {
+                throw new ArgumentError("Error #2008: Parameter negativeCurrencyFormat must be one of the accepted values.", 2008);
+            }
+            this._negativeCurrencyFormat = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get negativeSymbol():String {
+            return this._negativeSymbol;
+        }
+        public function set negativeSymbol(value:String):void {
+            if (value == null) throwNonNull("negativeSymbol");
+            this._negativeSymbol = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get positiveCurrencyFormat():uint {
+            return this._positiveCurrencyFormat;
+        }
+        public function set positiveCurrencyFormat(value:uint):void {
+            if (value > 3) {
+                throw new ArgumentError("Error #2008: Parameter positiveCurrencyFormat must be one of the accepted values.", 2008);
+            }
+            this._positiveCurrencyFormat = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get requestedLocaleIDName():String {
+            return this._localeIDName;
+        }
+
+        public function get trailingZeros():Boolean {
+            return this._trailingZeros;
+        }
+        public function set trailingZeros(value:Boolean):void {
+            this._trailingZeros = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public function get useGrouping():Boolean {
+            return this._useGrouping;
+        }
+        public function set useGrouping(value:Boolean):void {
+            this._useGrouping = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+        }
+
+        public native function format(value:Number, withCurrencySymbol:Boolean = false):String;
+
+        public function formattingWithCurrencySymbolIsSafe(requestedISOCode:String):Boolean {
+            if (requestedISOCode == null) throwNonNull("requestedISOCode");
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
+            return requestedISOCode == this._currencyCode;
+        }
+
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
+
+        public native function parse(inputString:String):CurrencyParseResult;
+
+        public function setCurrency(currencyISOCode:String, currencySymbol:String):void {
+            if (currencyISOCode == null) throwNonNull("currencyISOCode");
+            if (currencySymbol == null) throwNonNull("currencySymbol");
+            this._currencyCode = currencyISOCode;
+            this._currencySymbol = currencySymbol;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
     }
 }
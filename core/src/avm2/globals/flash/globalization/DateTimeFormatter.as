@@ -1,12 +1,20 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
+    import flash.globalization.DateTimeNameContext;
+    import flash.globalization.DateTimeNameStyle;
+    import flash.globalization.DateTimeStyle;
     import flash.globalization.LastOperationStatus;
 
     public final class DateTimeFormatter {
-        private var _dateStyle:String;
+        [Ruffle(InternalSlot)]
+        private var _actualLocaleName:String;
+
+        [Ruffle(InternalSlot)]
         private var _dateTimePattern:String;
+
+        [Ruffle(InternalSlot)]
+        private var _lastOperationStatus:String = LastOperationStatus.NO_ERROR;
+
+        private var _dateStyle:String;
         private var _localeIDName:String;
         private var _timeStyle:String;
 
@@ -14,22 +22,50 @@ package flash.globalization {
             throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
         }
 
+        private static function throwNotAccepted(name: String) {
+            throw new ArgumentError("Error #2008: Parameter " + name + " must be one of the accepted values.", 2008);
+        }
+
+        private static function isStyle(style:String):Boolean {
+            return style == DateTimeStyle.LONG || style == DateTimeStyle.MEDIUM ||
+                style == DateTimeStyle.SHORT || style == DateTimeStyle.NONE;
+        }
+
+        private static function checkNameArguments(nameStyle:String, context:String) {
+            if (nameStyle == null) throwNonNull("nameStyle");
+            if (context == null) throwNonNull("context");
+            if (nameStyle != DateTimeNameStyle.FULL && nameStyle != DateTimeNameStyle.LONG_ABBREVIATION &&
+                nameStyle != DateTimeNameStyle.SHORT_ABBREVIATION) {
+                throwNotAccepted("nameStyle");
+            }
+            if (context != DateTimeNameContext.FORMAT && context != DateTimeNameContext.STANDALONE) {
+                throwNotAccepted("context");
+            }
+        }
+
         public function DateTimeFormatter(requestedLocaleIDName:String, dateStyle:String = "long", timeStyle:String = "long") {
-            stub_constructor("flash.globalization.DateTimeFormatter");
             if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
             this._localeIDName = requestedLocaleIDName;
-            this._dateTimePattern = "EEEE, MMMM d, yyyy h:mm:ss a";
+            this.init(requestedLocaleIDName);
+
+            // The status of finding the locale outlives setting the styles.
+            var status:String = this._lastOperationStatus;
             this.setDateTimeStyles(dateStyle, timeStyle);
+            this._lastOperationStatus = status;
         }
 
+        // Finds the locale that best matches the requested one.
+        private native function init(requestedLocaleIDName:String):void;
+
+        // Builds the pattern of the given styles from the conventions of the locale.
+        private native function stylesPattern(dateStyle:String, timeStyle:String):String;
+
         public function get actualLocaleIDName():String {
-            stub_getter("flash.globalization.DateTimeFormatter", "actualLocaleIDName");
-            return this._localeIDName;
+            return this._actualLocaleName;
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.DateTimeFormatter", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get requestedLocaleIDName():String {
@@ -37,65 +73,67 @@ package flash.globalization {
         }
 
         public function format(dateTime:Date):String {
-            stub_method("flash.globalization.DateTimeFormatter", "format");
             if (dateTime == null) throwNonNull("dateTime");
-            return dateTime.toString();
+            return this.formatDate(dateTime, false);
         }
 
         public function formatUTC(dateTime:Date):String {
-            stub_method("flash.globalization.DateTimeFormatter", "formatUTC");
             if (dateTime == null) throwNonNull("dateTime");
-            return dateTime.toUTCString();
+            return this.formatDate(dateTime, true);
         }
 
-        public static function getAvailableLocaleIDNames():Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getAvailableLocaleIDNames");
-            return new <String>["en-US"];
-        }
+        private native function formatDate(dateTime:Date, utc:Boolean):String;
+
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
 
         public function getDateStyle():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             return this._dateStyle;
         }
 
         public function getDateTimePattern():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             return this._dateTimePattern;
         }
 
-        public function getFirstWeekday():int {
-            stub_method("flash.globalization.DateTimeFormatter", "getFirstWeekday");
-            return 0;
-        }
+        public native function getFirstWeekday():int;
 
         public function getMonthNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getMonthNames");
-            if (nameStyle == null) throwNonNull("nameStyle");
-            if (context == null) throwNonNull("context");
-            return new <String>["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+            checkNameArguments(nameStyle, context);
+            return this.monthNames(nameStyle, context == DateTimeNameContext.FORMAT);
         }
 
+        private native function monthNames(nameStyle:String, inFormat:Boolean):Vector.<String>;
+
         public function getTimeStyle():String {
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
             return this._timeStyle;
         }
 
         public function getWeekdayNames(nameStyle:String = "full", context:String = "standalone"):Vector.<String> {
-            stub_method("flash.globalization.DateTimeFormatter", "getWeekdayNames");
-            if (nameStyle == null) throwNonNull("nameStyle");
-            if (context == null) throwNonNull("context");
-            return new <String>["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+            checkNameArguments(nameStyle, context);
+            return this.weekdayNames(nameStyle);
         }
 
+        private native function weekdayNames(nameStyle:String):Vector.<String>;
+
         public function setDateTimePattern(pattern:String):void {
-            stub_method("flash.globalization.DateTimeFormatter", "setDateTimePattern");
             if (pattern == null) throwNonNull("pattern");
             this._dateTimePattern = pattern;
+            this._dateStyle = DateTimeStyle.CUSTOM;
+            this._timeStyle = DateTimeStyle.CUSTOM;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function setDateTimeStyles(dateStyle:String, timeStyle:String):void {
-            stub_method("flash.globalization.DateTimeFormatter", "setDateTimeStyles");
             if (dateStyle == null) throwNonNull("dateStyle");
             if (timeStyle == null) throwNonNull("timeStyle");
+            if (!isStyle(dateStyle)) throwNotAccepted("dateStyle");
+            if (!isStyle(timeStyle)) throwNotAccepted("timeStyle");
             this._dateStyle = dateStyle;
             this._timeStyle = timeStyle;
+            this._dateTimePattern = this.stylesPattern(dateStyle, timeStyle);
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
     }
-}
\ No newline at end of file
+}
@@ -1,22 +1,55 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
-    import flash.globalization.LastOperationStatus;
+    import flash.globalization.NumberFormatter;
 
     public final class LocaleID {
         public static const DEFAULT:String = "i-default";
 
+        private static const RTL_LANGUAGES:Array = ["ar", "dv", "fa", "he", "iw", "ps", "syr", "ug", "ur", "yi"];
+        private static const RTL_SCRIPTS:Array = ["Arab", "Hebr", "Syrc", "Thaa"];
+
         private var _name:String;
+        private var _lastOperationStatus:String;
+
+        private var _language:String = "";
+        private var _script:String = "";
+        private var _region:String = "";
+        private var _variant:String = "";
+        private var _keywords:String = "";
+
+        private static function throwNonNull(name: String) {
+            throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
+        }
 
         public function LocaleID(name:String) {
-            stub_constructor("flash.globalization.LocaleID");
+            if (name == null) throwNonNull("name");
             this._name = name;
+
+            // Report whether we have data for this locale the same way the formatters do.
+            this._lastOperationStatus = new NumberFormatter(name).lastOperationStatus;
+
+            var id:String = name;
+            var at:int = id.indexOf("@");
+            if (at >= 0) {
+                this._keywords = id.substring(at + 1);
+                id = id.substring(0, at);
+            }
+
+            var parts:Array = id.split("_").join("-").split("-");
+            this._language = String(parts.shift()).toLowerCase();
+            if (parts.length > 0 && /^[A-Za-z]{4}$/.test(parts[0])) {
+                var script:String = parts.shift();
+                this._script = script.charAt(0).toUpperCase() + script.substring(1).toLowerCase();
+            }
+            if (parts.length > 0 && /^([A-Za-z]{2}|[0-9]{3})$/.test(parts[0])) {
+                this._region = String(parts.shift()).toUpperCase();
+            }
+            if (parts.length > 0) {
+                this._variant = parts.join("_").toUpperCase();
+            }
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.LocaleID", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get name():String {
@@ -24,38 +57,67 @@ package flash.globalization {
         }
 
         public static function determinePreferredLocales(want:Vector.<String>, have:Vector.<String>, keyword:String = "userinterface"):Vector.<String> {
-            stub_method("flash.globalization.LocaleID", "determinePreferredLocales");
-            return null;
+            if (want == null) throwNonNull("want");
+            if (have == null) throwNonNull("have");
+
+            var result:Vector.<String> = new Vector.<String>();
+            var addMatches = function(exact:Boolean):void {
+                for each (var wanted:String in want) {
+                    var wantedID:LocaleID = new LocaleID(wanted);
+                    for each (var candidate:String in have) {
+                        if (result.indexOf(candidate) >= 0) {
+                            continue;
+                        }
+                        var candidateID:LocaleID = new LocaleID(candidate);
+                        var matches:Boolean = exact
+                            ? candidate.toLowerCase().split("_").join("-") == wanted.toLowerCase().split("_").join("-")
+                            : candidateID._language == wantedID._language;
+                        if (matches) {
+                            result.push(candidate);
+                        }
+                    }
+                }
+            };
+            addMatches(true);
+            addMatches(false);
+            return result;
         }
 
         public function getKeysAndValues():Object {
-            stub_method("flash.globalization.LocaleID", "getKeysAndValues");
-            return new Object();
+            var result:Object = new Object();
+            if (this._keywords.length == 0) {
+                return result;
+            }
+            for each (var pair:String in this._keywords.split(";")) {
+                var equals:int = pair.indexOf("=");
+                if (equals > 0) {
+                    result[pair.substring(0, equals)] = pair.substring(equals + 1);
+                }
+            }
+            return result;
         }
 
         public function getLanguage():String {
-            stub_method("flash.globalization.LocaleID", "getLanguage");
-            return this._name;
+            return this._language;
         }
 
         public function getRegion():String {
-            stub_method("flash.globalization.LocaleID", "getRegion");
-            return "";
+            return this._region;
         }
 
         public function getScript():String {
-            stub_method("flash.globalization.LocaleID", "getScript");
-            return "";
+            return this._script;
         }
 
         public function getVariant():String {
-            stub_method("flash.globalization.LocaleID", "getVariant");
-            return "";
+            return this._variant;
         }
 
         public function isRightToLeft():Boolean {
-            stub_method("flash.globalization.LocaleID", "isRightToLeft");
-            return false;
+            if (this._script.length > 0) {
+                return RTL_SCRIPTS.indexOf(this._script) >= 0;
+            }
+            return RTL_LANGUAGES.indexOf(this._language) >= 0;
         }
     }
 }
@@ -1,39 +1,71 @@
 package flash.globalization {
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_method;
     import flash.globalization.LastOperationStatus;
     import flash.globalization.NationalDigitsType;
     import flash.globalization.NumberParseResult;
 
     public final class NumberFormatter {
-        private var _decimalSeparator = ".";
-        private var _digitsType = NationalDigitsType.EUROPEAN;
-        private var _fractionalDigits = 2;
-        private var _groupingPattern = "3;*";
-        private var _groupingSeparator = ",";
-        private var _leadingZero = true;
+        [Ruffle(InternalSlot)]
+        private var _actualLocaleName:String;
+
+        [Ruffle(InternalSlot)]
+        private var _decimalSeparator:String;
+
+        [Ruffle(InternalSlot)]
+        private var _digitsType:uint = NationalDigitsType.EUROPEAN;
+
+        [Ruffle(InternalSlot)]
+        private var _fractionalDigits:int = 2;
+
+        [Ruffle(InternalSlot)]
+        private var _groupingPattern:String;
+
+        [Ruffle(InternalSlot)]
+        private var _groupingSeparator:String;
+
+        [Ruffle(InternalSlot)]
+        private var _lastOperationStatus:String = LastOperationStatus.NO_ERROR;
+
+        [Ruffle(InternalSlot)]
+        private var _leadingZero:Boolean = true;
+
+        [Ruffle(InternalSlot)]
+        private var _negativeNumberFormat:uint;
+
+        [Ruffle(InternalSlot)]
+        private var _negativeSymbol:String = "-";
+
+        [Ruffle(InternalSlot)]
+        private var _trailingZeros:Boolean = true;
+
+        [Ruffle(InternalSlot)]
+        private var _useGrouping:Boolean = true;
+
         private var _localeIDName:String;
-        private var _negativeNumberFormat = 1;
-        private var _negativeSymbol = "-";
-        private var _trailingZeros = true;
-        private var _useGrouping = true;
+
+        private static function throwNonNull(name: String) {
+            throw new TypeError("Error #2007: Parameter " + name + " must be non-null.", 2007);
+        }
 
         public function NumberFormatter(requestedLocaleIDName:String) {
-            stub_constructor("flash.globalization.NumberFormatter");
+            if (requestedLocaleIDName == null) throwNonNull("requestedLocaleIDName");
             this._localeIDName = requestedLocaleIDName;
+            this.init(requestedLocaleIDName);
         }
 
+        // Applies the conventions of the locale that best matches the requested one.
+        private native function init(requestedLocaleIDName:String):void;
+
         public function get actualLocaleIDName():String {
-            stub_getter("flash.globalization.NumberFormatter", "actualLocaleIDName");
-            return this._localeIDName;
+            return this._actualLocaleName;
         }
 
         public function get decimalSeparator():String {
             return this._decimalSeparator;
         }
         public function set decimalSeparator(value:String):void {
+            if (value == null) throwNonNull("decimalSeparator");
             this._decimalSeparator = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get digitsType():uint {
@@ -41,32 +73,37 @@ package flash.globalization {
         }
         public function set digitsType(value:uint):void {
             this._digitsType = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get fractionalDigits():int {
             return this._fractionalDigits;
         }
         public function set fractionalDigits(value:int):void {
-            this._fractionalDigits = value;
+            this._fractionalDigits = value < 0 ? 0 : value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get groupingPattern():String {
             return this._groupingPattern;
         }
         public function set groupingPattern(value:String):void {
+            if (value == null) throwNonNull("groupingPattern");
             this._groupingPattern = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get groupingSeparator():String {
             return this._groupingSeparator;
         }
         public function set groupingSeparator(value:String):void {
+            if (value == null) throwNonNull("groupingSeparator");
             this._groupingSeparator = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get lastOperationStatus():String {
-            stub_getter("flash.globalization.NumberFormatter", "lastOperationStatus");
-            return LastOperationStatus.NO_ERROR;
+            return this._lastOperationStatus;
         }
 
         public function get leadingZero():Boolean {
@@ -74,22 +111,27 @@ package flash.globalization {
         }
         public function set leadingZero(value:Boolean):void {
             this._leadingZero = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get negativeNumberFormat():uint {
             return this._negativeNumberFormat;
         }
         public function set negativeNumberFormat(value:uint):void {
-            if (value >= 0 && value <= 4) {
-                this._negativeNumberFormat = value;
+            if (value > 4) {
+                throw new ArgumentError("Error #2008: Parameter negativeNumberFormat must be one of the accepted values.", 2008);
             }
+            this._negativeNumberFormat = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get negativeSymbol():String {
             return this._negativeSymbol;
         }
         public function set negativeSymbol(value:String):void {
+            if (value == null) throwNonNull("negativeSymbol");
             this._negativeSymbol = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get requestedLocaleIDName():String {
@@ -101,6 +143,7 @@ package flash.globalization {
         }
         public function set trailingZeros(value:Boolean):void {
             this._trailingZeros = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
         public function get useGrouping():Boolean {
@@ -108,36 +151,19 @@ package flash.globalization {
         }
         public function set useGrouping(value:Boolean):void {
             this._useGrouping = value;
+            this._lastOperationStatus = LastOperationStatus.NO_ERROR;
         }
 
-        public function formatInt(value:int):String {
-            stub_method("flash.globalization.NumberFormatter", "formatInt");
-            return value.toString();
-        }
+        public native function formatInt(value:int):String;
 
-        public function formatNumber(value:Number):String {
-            stub_method("flash.globalization.NumberFormatter", "formatNumber");
-            return value.toString();
-        }
+        public native function formatNumber(value:Number):String;
 
-        public function formatUint(value:uint):String {
-            stub_method("flash.globalization.NumberFormatter", "formatUint");
-            return value.toString();
-        }
+        public native function formatUint(value:uint):String;
 
-        public static function getAvailableLocaleIDNames():Vector.<String> {
-            stub_method("flash.globalization.NumberFormatter", "getAvailableLocaleIDNames");
-            return new <String>["en-US"];
-        }
+        public static native function getAvailableLocaleIDNames():Vector.<String>;
 
-        public function parse(parseString:String):NumberParseResult {
-            stub_method("flash.globalization.NumberFormatter", "parse");
-            return new NumberParseResult();
-        }
+        public native function parse(parseString:String):NumberParseResult;
 
-        public function parseNumber(parseString:String):Number {
-            stub_method("flash.globalization.NumberFormatter", "parseNumber");
-            return Number(parseString);
-        }
+        public native function parseNumber(parseString:String):Number;
     }
 }
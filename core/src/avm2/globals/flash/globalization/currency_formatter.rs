@@ -0,0 +1,211 @@
+//! `flash.globalization.CurrencyFormatter` native methods
+
+use super::{available_locale_id_names, init_locale, set_status};
+use crate::avm2::globals::slots::*;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::globalization::{self, LastOperationStatus, NumberFormat};
+use crate::string::AvmString;
+
+/// Reads the formatting settings of a `CurrencyFormatter`.
+fn number_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<NumberFormat, Error<'gc>> {
+    let string = |activation: &mut Activation<'_, 'gc>, slot| -> Result<String, Error<'gc>> {
+        Ok(this
+            .get_slot(slot)
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned())
+    };
+
+    Ok(NumberFormat {
+        decimal_separator: string(
+            activation,
+            FLASH_GLOBALIZATION_CURRENCY_FORMATTER__DECIMAL_SEPARATOR_SLOT,
+        )?,
+        grouping_separator: string(
+            activation,
+            FLASH_GLOBALIZATION_CURRENCY_FORMATTER__GROUPING_SEPARATOR_SLOT,
+        )?,
+        grouping_pattern: string(
+            activation,
+            FLASH_GLOBALIZATION_CURRENCY_FORMATTER__GROUPING_PATTERN_SLOT,
+        )?,
+        use_grouping: this
+            .get_slot(FLASH_GLOBALIZATION_CURRENCY_FORMATTER__USE_GROUPING_SLOT)
+            .coerce_to_boolean(),
+        fractional_digits: this
+            .get_slot(FLASH_GLOBALIZATION_CURRENCY_FORMATTER__FRACTIONAL_DIGITS_SLOT)
+            .coerce_to_i32(activation)?
+            .max(0) as u32,
+        trailing_zeros: this
+            .get_slot(FLASH_GLOBALIZATION_CURRENCY_FORMATTER__TRAILING_ZEROS_SLOT)
+            .coerce_to_boolean(),
+        leading_zero: this
+            .get_slot(FLASH_GLOBALIZATION_CURRENCY_FORMATTER__LEADING_ZERO_SLOT)
+            .coerce_to_boolean(),
+        negative_symbol: string(
+            activation,
+            FLASH_GLOBALIZATION_CURRENCY_FORMATTER__NEGATIVE_SYMBOL_SLOT,
+        )?,
+        // Currency amounts place the negative symbol using the currency formats instead.
+        negative_number_format: 1,
+        digits_type: this
+            .get_slot(FLASH_GLOBALIZATION_CURRENCY_FORMATTER__DIGITS_TYPE_SLOT)
+            .coerce_to_u32(activation)?,
+    })
+}
+
+fn set_status_of<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    status: LastOperationStatus,
+) -> Result<(), Error<'gc>> {
+    set_status(
+        activation,
+        this,
+        FLASH_GLOBALIZATION_CURRENCY_FORMATTER__LAST_OPERATION_STATUS_SLOT,
+        status,
+    )
+}
+
+/// Implements `CurrencyFormatter.init`
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedLocaleIDName")?;
+    let locale = init_locale(
+        activation,
+        this,
+        requested,
+        FLASH_GLOBALIZATION_CURRENCY_FORMATTER__ACTUAL_LOCALE_NAME_SLOT,
+        FLASH_GLOBALIZATION_CURRENCY_FORMATTER__LAST_OPERATION_STATUS_SLOT,
+    )?;
+
+    let mc = activation.context.gc_context;
+    for (slot, value) in [
+        (
+            FLASH_GLOBALIZATION_CURRENCY_FORMATTER__CURRENCY_CODE_SLOT,
+            locale.currency_iso_code,
+        ),
+        (
+            FLASH_GLOBALIZATION_CURRENCY_FORMATTER__CURRENCY_SYMBOL_SLOT,
+            locale.currency_symbol,
+        ),
+        (
+            FLASH_GLOBALIZATION_CURRENCY_FORMATTER__DECIMAL_SEPARATOR_SLOT,
+            locale.decimal_separator,
+        ),
+        (
+            FLASH_GLOBALIZATION_CURRENCY_FORMATTER__GROUPING_SEPARATOR_SLOT,
+            locale.grouping_separator,
+        ),
+        (
+            FLASH_GLOBALIZATION_CURRENCY_FORMATTER__GROUPING_PATTERN_SLOT,
+            locale.grouping_pattern,
+        ),
+    ] {
+        this.set_slot(slot, AvmString::new_utf8(mc, value).into(), activation)?;
+    }
+    this.set_slot(
+        FLASH_GLOBALIZATION_CURRENCY_FORMATTER__FRACTIONAL_DIGITS_SLOT,
+        locale.currency_fractional_digits.into(),
+        activation,
+    )?;
+    this.set_slot(
+        FLASH_GLOBALIZATION_CURRENCY_FORMATTER__POSITIVE_CURRENCY_FORMAT_SLOT,
+        locale.positive_currency_format.into(),
+        activation,
+    )?;
+    this.set_slot(
+        FLASH_GLOBALIZATION_CURRENCY_FORMATTER__NEGATIVE_CURRENCY_FORMAT_SLOT,
+        locale.negative_currency_format.into(),
+        activation,
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `CurrencyFormatter.format`
+pub fn format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    let with_currency_symbol = args.get_bool(1);
+
+    let format = number_format(activation, this)?;
+    let currency_slot = if with_currency_symbol {
+        FLASH_GLOBALIZATION_CURRENCY_FORMATTER__CURRENCY_SYMBOL_SLOT
+    } else {
+        FLASH_GLOBALIZATION_CURRENCY_FORMATTER__CURRENCY_CODE_SLOT
+    };
+    let currency = this.get_slot(currency_slot).coerce_to_string(activation)?;
+    let positive_format = this
+        .get_slot(FLASH_GLOBALIZATION_CURRENCY_FORMATTER__POSITIVE_CURRENCY_FORMAT_SLOT)
+        .coerce_to_u32(activation)?;
+    let negative_format = this
+        .get_slot(FLASH_GLOBALIZATION_CURRENCY_FORMATTER__NEGATIVE_CURRENCY_FORMAT_SLOT)
+        .coerce_to_u32(activation)?;
+
+    let text = globalization::format_currency(
+        value,
+        &format,
+        &currency.to_utf8_lossy(),
+        positive_format,
+        negative_format,
+    );
+    set_status_of(activation, this, LastOperationStatus::NoError)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, text).into())
+}
+
+/// Implements `CurrencyFormatter.getAvailableLocaleIDNames`
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
+
+/// Implements `CurrencyFormatter.parse`
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string_non_null(activation, 0, "inputString")?;
+    let format = number_format(activation, this)?;
+    let iso_code = this
+        .get_slot(FLASH_GLOBALIZATION_CURRENCY_FORMATTER__CURRENCY_CODE_SLOT)
+        .coerce_to_string(activation)?;
+    let symbol = this
+        .get_slot(FLASH_GLOBALIZATION_CURRENCY_FORMATTER__CURRENCY_SYMBOL_SLOT)
+        .coerce_to_string(activation)?;
+
+    let result = globalization::parse_currency(
+        &text.to_utf8_lossy(),
+        &format,
+        &iso_code.to_utf8_lossy(),
+        &symbol.to_utf8_lossy(),
+    );
+    let args: Vec<Value<'gc>> = match result {
+        Some((value, currency)) => {
+            set_status_of(activation, this, LastOperationStatus::NoError)?;
+            let currency = AvmString::new_utf8(activation.context.gc_context, currency);
+            vec![value.into(), currency.into()]
+        }
+        None => {
+            set_status_of(activation, this, LastOperationStatus::ParseError)?;
+            vec![]
+        }
+    };
+
+    let result_class = activation.avm2().classes().currencyparseresult;
+    Ok(result_class.construct(activation, &args)?.into())
+}
@@ -0,0 +1,149 @@
+//! `flash.globalization.DateTimeFormatter` native methods
+
+use super::{actual_locale, available_locale_id_names, init_locale, set_status, string_vector};
+use crate::avm2::globals::slots::*;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::globalization::{self, LastOperationStatus, LocaleData};
+use crate::locale::get_timezone;
+use crate::string::AvmString;
+
+fn locale<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<&'static LocaleData, Error<'gc>> {
+    actual_locale(
+        activation,
+        this,
+        FLASH_GLOBALIZATION_DATE_TIME_FORMATTER__ACTUAL_LOCALE_NAME_SLOT,
+    )
+}
+
+fn set_status_of<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    status: LastOperationStatus,
+) -> Result<(), Error<'gc>> {
+    set_status(
+        activation,
+        this,
+        FLASH_GLOBALIZATION_DATE_TIME_FORMATTER__LAST_OPERATION_STATUS_SLOT,
+        status,
+    )
+}
+
+/// Implements `DateTimeFormatter.init`
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedLocaleIDName")?;
+    init_locale(
+        activation,
+        this,
+        requested,
+        FLASH_GLOBALIZATION_DATE_TIME_FORMATTER__ACTUAL_LOCALE_NAME_SLOT,
+        FLASH_GLOBALIZATION_DATE_TIME_FORMATTER__LAST_OPERATION_STATUS_SLOT,
+    )?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `DateTimeFormatter.stylesPattern`
+pub fn styles_pattern<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date_style = args.get_string_non_null(activation, 0, "dateStyle")?;
+    let time_style = args.get_string_non_null(activation, 1, "timeStyle")?;
+
+    let locale = locale(activation, this)?;
+    let pattern = globalization::date_time_pattern(
+        locale,
+        &date_style.to_utf8_lossy(),
+        &time_style.to_utf8_lossy(),
+    );
+    Ok(AvmString::new_utf8(activation.context.gc_context, pattern).into())
+}
+
+/// Implements `DateTimeFormatter.formatDate`
+pub fn format_date<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let date_time = args
+        .get_object(activation, 0, "dateTime")?
+        .as_date_object()
+        .and_then(|date| date.date_time());
+    let utc = args.get_bool(1);
+
+    let text = match date_time {
+        Some(date_time) => {
+            let date_time = if utc {
+                date_time.fixed_offset()
+            } else {
+                date_time.with_timezone(&get_timezone())
+            };
+            let pattern = this
+                .get_slot(FLASH_GLOBALIZATION_DATE_TIME_FORMATTER__DATE_TIME_PATTERN_SLOT)
+                .coerce_to_string(activation)?;
+            let locale = locale(activation, this)?;
+            globalization::format_date_time(&pattern.to_utf8_lossy(), &date_time, locale)
+        }
+        // Invalid dates have nothing to format.
+        None => String::new(),
+    };
+    set_status_of(activation, this, LastOperationStatus::NoError)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, text).into())
+}
+
+/// Implements `DateTimeFormatter.getAvailableLocaleIDNames`
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
+
+/// Implements `DateTimeFormatter.getFirstWeekday`
+pub fn get_first_weekday<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locale = locale(activation, this)?;
+    set_status_of(activation, this, LastOperationStatus::NoError)?;
+    Ok(locale.first_weekday.into())
+}
+
+/// Implements `DateTimeFormatter.monthNames`
+pub fn month_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name_style = args.get_string_non_null(activation, 0, "nameStyle")?;
+    let in_format = args.get_bool(1);
+
+    let locale = locale(activation, this)?;
+    let names = globalization::month_names(locale, &name_style.to_utf8_lossy(), in_format);
+    set_status_of(activation, this, LastOperationStatus::NoError)?;
+    string_vector(activation, names)
+}
+
+/// Implements `DateTimeFormatter.weekdayNames`
+pub fn weekday_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name_style = args.get_string_non_null(activation, 0, "nameStyle")?;
+
+    let locale = locale(activation, this)?;
+    let names = globalization::weekday_names(locale, &name_style.to_utf8_lossy());
+    set_status_of(activation, this, LastOperationStatus::NoError)?;
+    string_vector(activation, names)
+}
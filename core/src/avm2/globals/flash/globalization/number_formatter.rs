@@ -0,0 +1,208 @@
+//! `flash.globalization.NumberFormatter` native methods
+
+use super::{available_locale_id_names, init_locale, set_status};
+use crate::avm2::globals::slots::*;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::globalization::{self, LastOperationStatus, NumberFormat};
+use crate::string::AvmString;
+
+/// Reads the formatting settings of a `NumberFormatter`.
+fn number_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<NumberFormat, Error<'gc>> {
+    let string = |activation: &mut Activation<'_, 'gc>, slot| -> Result<String, Error<'gc>> {
+        Ok(this
+            .get_slot(slot)
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned())
+    };
+
+    Ok(NumberFormat {
+        decimal_separator: string(
+            activation,
+            FLASH_GLOBALIZATION_NUMBER_FORMATTER__DECIMAL_SEPARATOR_SLOT,
+        )?,
+        grouping_separator: string(
+            activation,
+            FLASH_GLOBALIZATION_NUMBER_FORMATTER__GROUPING_SEPARATOR_SLOT,
+        )?,
+        grouping_pattern: string(
+            activation,
+            FLASH_GLOBALIZATION_NUMBER_FORMATTER__GROUPING_PATTERN_SLOT,
+        )?,
+        use_grouping: this
+            .get_slot(FLASH_GLOBALIZATION_NUMBER_FORMATTER__USE_GROUPING_SLOT)
+            .coerce_to_boolean(),
+        fractional_digits: this
+            .get_slot(FLASH_GLOBALIZATION_NUMBER_FORMATTER__FRACTIONAL_DIGITS_SLOT)
+            .coerce_to_i32(activation)?
+            .max(0) as u32,
+        trailing_zeros: this
+            .get_slot(FLASH_GLOBALIZATION_NUMBER_FORMATTER__TRAILING_ZEROS_SLOT)
+            .coerce_to_boolean(),
+        leading_zero: this
+            .get_slot(FLASH_GLOBALIZATION_NUMBER_FORMATTER__LEADING_ZERO_SLOT)
+            .coerce_to_boolean(),
+        negative_symbol: string(
+            activation,
+            FLASH_GLOBALIZATION_NUMBER_FORMATTER__NEGATIVE_SYMBOL_SLOT,
+        )?,
+        negative_number_format: this
+            .get_slot(FLASH_GLOBALIZATION_NUMBER_FORMATTER__NEGATIVE_NUMBER_FORMAT_SLOT)
+            .coerce_to_u32(activation)?,
+        digits_type: this
+            .get_slot(FLASH_GLOBALIZATION_NUMBER_FORMATTER__DIGITS_TYPE_SLOT)
+            .coerce_to_u32(activation)?,
+    })
+}
+
+fn set_status_of<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    status: LastOperationStatus,
+) -> Result<(), Error<'gc>> {
+    set_status(
+        activation,
+        this,
+        FLASH_GLOBALIZATION_NUMBER_FORMATTER__LAST_OPERATION_STATUS_SLOT,
+        status,
+    )
+}
+
+/// Formats a number with the settings of a `NumberFormatter`.
+fn format_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    value: f64,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let format = number_format(activation, this)?;
+    let text = globalization::format_number(value, &format);
+    set_status_of(activation, this, LastOperationStatus::NoError)?;
+    Ok(AvmString::new_utf8(activation.context.gc_context, text).into())
+}
+
+/// Implements `NumberFormatter.init`
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let requested = args.get_string_non_null(activation, 0, "requestedLocaleIDName")?;
+    let locale = init_locale(
+        activation,
+        this,
+        requested,
+        FLASH_GLOBALIZATION_NUMBER_FORMATTER__ACTUAL_LOCALE_NAME_SLOT,
+        FLASH_GLOBALIZATION_NUMBER_FORMATTER__LAST_OPERATION_STATUS_SLOT,
+    )?;
+
+    let mc = activation.context.gc_context;
+    for (slot, value) in [
+        (
+            FLASH_GLOBALIZATION_NUMBER_FORMATTER__DECIMAL_SEPARATOR_SLOT,
+            locale.decimal_separator,
+        ),
+        (
+            FLASH_GLOBALIZATION_NUMBER_FORMATTER__GROUPING_SEPARATOR_SLOT,
+            locale.grouping_separator,
+        ),
+        (
+            FLASH_GLOBALIZATION_NUMBER_FORMATTER__GROUPING_PATTERN_SLOT,
+            locale.grouping_pattern,
+        ),
+    ] {
+        this.set_slot(slot, AvmString::new_utf8(mc, value).into(), activation)?;
+    }
+    this.set_slot(
+        FLASH_GLOBALIZATION_NUMBER_FORMATTER__NEGATIVE_NUMBER_FORMAT_SLOT,
+        locale.negative_number_format.into(),
+        activation,
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NumberFormatter.formatInt`
+pub fn format_int<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_i32(activation, 0)?;
+    format_value(activation, this, value.into())
+}
+
+/// Implements `NumberFormatter.formatNumber`
+pub fn format_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_f64(activation, 0)?;
+    format_value(activation, this, value)
+}
+
+/// Implements `NumberFormatter.formatUint`
+pub fn format_uint<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get_u32(activation, 0)?;
+    format_value(activation, this, value.into())
+}
+
+/// Implements `NumberFormatter.getAvailableLocaleIDNames`
+pub fn get_available_locale_id_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    available_locale_id_names(activation)
+}
+
+/// Implements `NumberFormatter.parse`
+pub fn parse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string_non_null(activation, 0, "parseString")?;
+    let format = number_format(activation, this)?;
+
+    let result = globalization::parse_number(&text.to_utf8_lossy(), &format, false);
+    let args: Vec<Value<'gc>> = match result {
+        Some((value, start, end)) => {
+            set_status_of(activation, this, LastOperationStatus::NoError)?;
+            vec![value.into(), start.into(), end.into()]
+        }
+        None => {
+            set_status_of(activation, this, LastOperationStatus::ParseError)?;
+            vec![]
+        }
+    };
+
+    let result_class = activation.avm2().classes().numberparseresult;
+    Ok(result_class.construct(activation, &args)?.into())
+}
+
+/// Implements `NumberFormatter.parseNumber`
+pub fn parse_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string_non_null(activation, 0, "parseString")?;
+    let format = number_format(activation, this)?;
+
+    let result = globalization::parse_number(&text.to_utf8_lossy(), &format, true);
+    let (status, value) = match result {
+        Some((value, _, _)) => (LastOperationStatus::NoError, value),
+        None => (LastOperationStatus::ParseError, f64::NAN),
+    };
+    set_status_of(activation, this, status)?;
+    Ok(value.into())
+}
@@ -5,125 +5,148 @@ package flash.media {
     import flash.display.BitmapData;
 
     public final class Camera extends EventDispatcher {
+        [Ruffle(InternalSlot)]
+        private var _index:int = 0;
+
+        [Ruffle(InternalSlot)]
+        private var _name:String = null;
+
+        [Ruffle(InternalSlot)]
+        private var _width:int = 160;
+
+        [Ruffle(InternalSlot)]
+        private var _height:int = 120;
+
+        [Ruffle(InternalSlot)]
+        private var _fps:Number = 15;
+
+        [Ruffle(InternalSlot)]
+        private var _currentFPS:Number = 0;
+
+        [Ruffle(InternalSlot)]
+        private var _activityLevel:Number = -1;
+
+        [Ruffle(InternalSlot)]
+        private var _motionLevel:int = 50;
+
+        [Ruffle(InternalSlot)]
+        private var _motionTimeout:int = 2000;
+
+        [Ruffle(InternalSlot)]
+        private var _muted:Boolean = false;
+
+        private var _bandwidth:int = 16384;
+        private var _quality:int = 0;
+        private var _keyFrameInterval:int = 15;
+        private var _loopback:Boolean = false;
+
         [API("682")]
-        public function copyToByteArray(rect:Rectangle, destination:ByteArray) {
-            __ruffle__.stub_method("flash.media.Camera", "copyToByteArray");
-        }
+        public native function copyToByteArray(rect:Rectangle, destination:ByteArray):void;
 
         [API("682")]
-        public function copyToVector(rect:Rectangle, destination:Vector.<uint>) {
-            __ruffle__.stub_method("flash.media.Camera", "copyToVector");
+        public function copyToVector(rect:Rectangle, destination:Vector.<uint>):void {
+            var bytes:ByteArray = new ByteArray();
+            this.copyToByteArray(rect, bytes);
+
+            bytes.position = 0;
+            destination.length = bytes.length / 4;
+            for (var i:uint = 0; i < destination.length; i++) {
+                destination[i] = bytes.readUnsignedInt();
+            }
         }
 
         [API("682")]
-        public function drawToBitmapData(destination:BitmapData) {
-            __ruffle__.stub_method("flash.media.Camera", "drawToBitmapData");
-        }
+        public function drawToBitmapData(destination:BitmapData):void {
+            var rect:Rectangle = new Rectangle(0, 0, Math.min(this.width, destination.width), Math.min(this.height, destination.height));
+            var bytes:ByteArray = new ByteArray();
+            this.copyToByteArray(rect, bytes);
 
-        public static function getCamera(name: String = null):Camera {
-            __ruffle__.stub_method("flash.media.Camera", "getCamera");
-            return null;
+            bytes.position = 0;
+            destination.setPixels(rect, bytes);
         }
 
-        public function setKeyFrameInterval(keyFrameInterval:int) {
-            __ruffle__.stub_method("flash.media.Camera", "setKeyFrameInterval");
-        }
+        public static native function getCamera(name:String = null):Camera;
 
-        public function setLoopback(compress:Boolean = false) {
-            __ruffle__.stub_method("flash.media.Camera", "setLoopback");
+        public function setKeyFrameInterval(keyFrameInterval:int):void {
+            this._keyFrameInterval = keyFrameInterval;
         }
 
-        public function setMode(width:int, height:int, fps:Number, favorArea:Boolean = true) {
-            __ruffle__.stub_method("flash.media.Camera", "setMode");
+        public function setLoopback(compress:Boolean = false):void {
+            this._loopback = compress;
         }
 
-        public function setMotionLevel(motionLevel:int, timeout:int = 2000) {
-            __ruffle__.stub_method("flash.media.Camera", "setMotionLevel");
-        }
+        public native function setMode(width:int, height:int, fps:Number, favorArea:Boolean = true):void;
 
-        public function setQuality(bandwidth:int, quality:int) {
-            __ruffle__.stub_method("flash.media.Camera", "setQuality");
+        public function setMotionLevel(motionLevel:int, timeout:int = 2000):void {
+            this._motionLevel = motionLevel;
+            this._motionTimeout = timeout;
         }
 
-        public function get activityLevel(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "activityLevel");
-            return 0;
+        public function setQuality(bandwidth:int, quality:int):void {
+            this._bandwidth = bandwidth;
+            this._quality = quality;
         }
 
-        public function get bandwidth(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "bandwidth");
-            return 0;
+        public function get activityLevel():Number {
+            return this._activityLevel;
         }
 
-        public function get currentFPS(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "currentFPS");
-            return 0;
+        public function get bandwidth():int {
+            return this._bandwidth;
         }
 
-        public function get fps(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "fps");
-            return 0;
+        public function get currentFPS():Number {
+            return this._currentFPS;
         }
 
-        public function get height(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "height");
-            return 0;
+        public function get fps():Number {
+            return this._fps;
         }
 
-        public function get index(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "index");
-            return 0;
+        public function get height():int {
+            return this._height;
         }
 
-        public static function get isSupported(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "isSupported");
-            return false;
+        public function get index():int {
+            return this._index;
         }
 
-        public function get keyFrameInterval(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "keyFrameInterval");
-            return 0;
+        public static function get isSupported():Boolean {
+            return true;
         }
 
-        public function get loopback(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "loopback");
-            return false;
+        public function get keyFrameInterval():int {
+            return this._keyFrameInterval;
         }
 
-        public function get motionLevel(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "motionLevel");
-            return 0;
+        public function get loopback():Boolean {
+            return this._loopback;
         }
 
-        public function get motionTimeout(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "motionTimeout");
-            return 0;
+        public function get motionLevel():int {
+            return this._motionLevel;
         }
 
-        public function get muted(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "muted");
-            return true;
+        public function get motionTimeout():int {
+            return this._motionTimeout;
         }
 
-        public function get name(): String {
-            __ruffle__.stub_getter("flash.media.Camera", "name");
-            return "";
+        public function get muted():Boolean {
+            return this._muted;
         }
 
-        public static function get names(): Array {
-            __ruffle__.stub_getter("flash.media.Camera", "names");
-            return [];
+        public function get name():String {
+            return this._name;
         }
-        
-        public function get quality(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "quality");
-            return 0;
+
+        public static native function get names():Array;
+
+        public function get quality():int {
+            return this._quality;
         }
-        
-        public function get width(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "width");
-            return 0;
+
+        public function get width():int {
+            return this._width;
         }
     }
-
-}
\ No newline at end of file
+}
@@ -3,23 +3,47 @@ package flash.media {
 
     public final class Microphone extends EventDispatcher {
         
+        [Ruffle(InternalSlot)]
+        private var _index:int = 0;
+
+        [Ruffle(InternalSlot)]
+        private var _name:String = null;
+
+        [Ruffle(InternalSlot)]
+        private var _rate:int = 8;
+
+        [Ruffle(InternalSlot)]
+        private var _gain:Number = 50;
+
+        [Ruffle(InternalSlot)]
+        private var _silenceLevel:Number = 10;
+
+        [Ruffle(InternalSlot)]
+        private var _silenceTimeout:int = 2000;
+
+        [Ruffle(InternalSlot)]
+        private var _activityLevel:Number = -1;
+
+        [Ruffle(InternalSlot)]
+        private var _muted:Boolean = false;
+
         [API("672")]
         public static function getEnhancedMicrophone(index:int = -1):Microphone {
             __ruffle__.stub_method("flash.media.Microphone", "getEnhancedMicrophone");
-            return new Microphone();
+            return getMicrophone(index);
         }
 
-        public static function getMicrophone(index:int = -1):Microphone {
-            __ruffle__.stub_method("flash.media.Microphone", "getMicrophone");
-            return new Microphone();
-        }
+        public static native function getMicrophone(index:int = -1):Microphone;
 
         public function setLoopBack(isLooped:Boolean=true) {
             __ruffle__.stub_method("flash.media.Microphone", "setLoopBack");
         }
 
         public function setSilenceLevel(silenceLevel:Number, timeout:int = -1) {
-            __ruffle__.stub_method("flash.media.Microphone", "setSilenceLevel");
+            this._silenceLevel = Math.max(0, Math.min(100, silenceLevel));
+            if (timeout >= 0) {
+                this._silenceTimeout = timeout;
+            }
         }
 
         public function setUseEchoSuppression(isEchoSuppressed:Boolean) {
@@ -27,8 +51,7 @@ package flash.media {
         }
 
         public function get activityLevel():Number {
-            __ruffle__.stub_getter("flash.media.Microphone", "activityLevel");
-            return 0.0;
+            return this._activityLevel;
         }
 
         public function get codec():String {
@@ -79,38 +102,30 @@ package flash.media {
         }
 
         public function get gain():Number {
-            __ruffle__.stub_getter("flash.media.Microphone", "gain");
-            return 0.0;
+            return this._gain;
         }
 
         public function set gain(gain:Number) {
-            __ruffle__.stub_setter("flash.media.Microphone", "gain");
+            this._gain = Math.max(0, Math.min(100, gain));
         }
 
         public function get index():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "index");
-            return 0;
+            return this._index;
         }
 
         public static function get isSupported():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "isSupported");
-            return false;
+            return true;
         }
 
         public function get muted():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "muted");
-            return true;
+            return this._muted;
         }
 
-        public function get name():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "name");
-            return "";
+        public function get name():String {
+            return this._name;
         }
 
-        public static function get names():Array {
-            __ruffle__.stub_getter("flash.media.Microphone", "names");
-            return [];
-        }
+        public static native function get names():Array;
 
         public function get noiseSuppressionLevel():int {
             __ruffle__.stub_getter("flash.media.Microphone", "noiseSuppressionLevel");
@@ -122,22 +137,22 @@ package flash.media {
         }
 
         public function get rate():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "rate");
-            return 0;
+            return this._rate;
         }
 
         public function set rate(level:int) {
-            __ruffle__.stub_setter("flash.media.Microphone", "rate");
+            // Other rates are ignored.
+            if (level == 5 || level == 8 || level == 11 || level == 16 || level == 22 || level == 44) {
+                this._rate = level;
+            }
         }
 
-        public function get silenceLevel():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "silenceLevel");
-            return 0;
+        public function get silenceLevel():Number {
+            return this._silenceLevel;
         }
 
         public function get silenceTimeout():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "silenceTimeout");
-            return 0;
+            return this._silenceTimeout;
         }
 
         public function get soundTransform():flash.media.SoundTransform {
@@ -0,0 +1,106 @@
+//! `flash.media.Camera` native methods
+
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::globals::slots::*;
+use crate::avm2::media_capture::{camera_object, frame_pixels, restart_camera};
+use crate::avm2::object::{ArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::string::AvmString;
+
+/// Implements `Camera.getCamera`
+pub fn get_camera<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = activation.context.media_capture.camera_names();
+
+    // Cameras are named by their index in `Camera.names`, the first one being the default.
+    let index = match args.try_get_string(activation, 0)? {
+        Some(name) => match name.to_utf8_lossy().parse::<usize>() {
+            Ok(index) => index,
+            Err(_) => return Ok(Value::Null),
+        },
+        None => 0,
+    };
+    let Some(name) = names.get(index) else {
+        return Ok(Value::Null);
+    };
+
+    Ok(camera_object(activation, index, name)?.into())
+}
+
+/// Implements `Camera.names`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = activation
+        .context
+        .media_capture
+        .camera_names()
+        .into_iter()
+        .map(|name| AvmString::new_utf8(activation.context.gc_context, name).into())
+        .collect::<Vec<Value<'gc>>>();
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&names))?.into())
+}
+
+/// Implements `Camera.setMode`
+pub fn set_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let width = args.get_i32(activation, 0)?.max(1);
+    let height = args.get_i32(activation, 1)?.max(1);
+    let fps = args.get_f64(activation, 2)?;
+    let fps = if fps > 0.0 { fps } else { 1.0 };
+
+    // The mode the camera actually captures at is only known once the backend restarted it.
+    this.set_slot(FLASH_MEDIA_CAMERA__WIDTH_SLOT, width.into(), activation)?;
+    this.set_slot(FLASH_MEDIA_CAMERA__HEIGHT_SLOT, height.into(), activation)?;
+    this.set_slot(FLASH_MEDIA_CAMERA__FPS_SLOT, fps.into(), activation)?;
+    restart_camera(activation.context, this);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.copyToByteArray`
+pub fn copy_to_byte_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rect = args.get_object(activation, 0, "rect")?;
+    let destination = args.get_object(activation, 1, "destination")?;
+
+    let mut coords = [0; 4];
+    for (coord, name) in coords.iter_mut().zip(["x", "y", "width", "height"]) {
+        *coord = rect
+            .get_public_property(name, activation)?
+            .coerce_to_i32(activation)?
+            .max(0) as u32;
+    }
+    let [x, y, width, height] = coords;
+
+    let pixels = activation
+        .avm2()
+        .media_capture_objects
+        .camera_frame(this)
+        .map(|frame| frame_pixels(frame, x, y, width, height))
+        .unwrap_or_default();
+
+    let mut bytearray = destination
+        .as_bytearray_mut()
+        .expect("Parameter must be a bytearray!");
+    for pixel in pixels {
+        bytearray
+            .write_unsigned_int(pixel)
+            .map_err(|e| e.to_avm(activation))?;
+    }
+
+    Ok(Value::Undefined)
+}
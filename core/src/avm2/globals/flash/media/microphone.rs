@@ -0,0 +1,46 @@
+//! `flash.media.Microphone` native methods
+
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::media_capture::microphone_object;
+use crate::avm2::object::ArrayObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::string::AvmString;
+
+/// Implements `Microphone.getMicrophone`
+pub fn get_microphone<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = activation.context.media_capture.microphone_names();
+
+    // An index of -1 stands for the default microphone, which is the first one.
+    let index = args.get_i32(activation, 0)?;
+    let index = if index == -1 { 0 } else { index };
+    let Some(name) = usize::try_from(index)
+        .ok()
+        .and_then(|index| names.get(index))
+    else {
+        return Ok(Value::Null);
+    };
+
+    Ok(microphone_object(activation, index as usize, name)?.into())
+}
+
+/// Implements `Microphone.names`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = activation
+        .context
+        .media_capture
+        .microphone_names()
+        .into_iter()
+        .map(|name| AvmString::new_utf8(activation.context.gc_context, name).into())
+        .collect::<Vec<Value<'gc>>>();
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&names))?.into())
+}
@@ -1,6 +1,8 @@
 //! `flash.media.Sound` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::Endian;
+use crate::avm2::error::{make_error_2008, make_error_2037};
 use crate::avm2::object::{Object, QueuedPlay, SoundChannelObject, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
@@ -179,6 +181,7 @@ pub fn play<'gc>(
             sound_info,
             sound_transform,
             sound_channel,
+            movie: activation.caller_movie_or_root(),
         };
         if sound_object.play(queued_play, activation)? {
             return Ok(sound_channel.into());
@@ -194,23 +197,46 @@ pub fn play<'gc>(
 /// `Sound.extract`
 pub fn extract<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "extract");
+    let this = this.as_sound_object().unwrap();
 
-    let bytearray = args.try_get_object(activation, 0);
+    let target = args.get_object(activation, 0, "target")?;
     let length = args.get_f64(activation, 1)?;
+    let start_position = args.get_f64(activation, 2)?;
 
-    if let Some(bytearray) = bytearray {
-        if let Some(mut bytearray) = bytearray.as_bytearray_mut() {
-            bytearray
-                .write_bytes(vec![0u8; length.ceil() as usize].as_slice())
-                .map_err(|e| e.to_avm(activation))?;
-        }
+    let Some(sound) = this.sound_handle() else {
+        return Ok(0.into());
+    };
+
+    // A negative start position continues from where the last extraction stopped.
+    let start = if start_position >= 0.0 {
+        start_position as u32
+    } else {
+        this.extract_position()
+    };
+    let samples = activation
+        .context
+        .audio
+        .extract_sound(sound, start, length.max(0.0) as u32)
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to extract samples from sound: {e}");
+            vec![]
+        });
+    this.set_extract_position(start.saturating_add(samples.len() as u32));
+
+    let mut bytearray = target.as_bytearray_mut().unwrap();
+    for [left, right] in &samples {
+        bytearray
+            .write_float(*left)
+            .map_err(|e| e.to_avm(activation))?;
+        bytearray
+            .write_float(*right)
+            .map_err(|e| e.to_avm(activation))?;
     }
 
-    Ok(Value::Undefined)
+    Ok(samples.len().into())
 }
 
 /// `Sound.close`
@@ -246,6 +272,10 @@ pub fn load<'gc>(
         avm2_stub_method!(activation, "flash.media.Sound", "load", "with context");
     }
 
+    if let Some(sound) = this.as_sound_object() {
+        sound.set_has_data_source();
+    }
+
     let future = activation.context.load_manager.load_sound_avm2(
         activation.context.player.clone(),
         this,
@@ -265,6 +295,11 @@ pub fn load_compressed_data_from_byte_array<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let bytearray = args.get_object(activation, 0, "bytes")?;
     let bytes_length = args.get_u32(activation, 1)?;
+
+    if this.as_sound_object().unwrap().sound_handle().is_some() {
+        return Err(make_error_2037(activation));
+    }
+
     let bytearray = bytearray.as_bytearray().unwrap();
 
     // FIXME - determine the actual errors thrown by Flash Player
@@ -308,10 +343,77 @@ pub fn load_compressed_data_from_byte_array<'gc>(
 /// `Sound.loadPCMFromByteArray`
 pub fn load_pcm_from_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "loadPCMFromByteArray");
+    let this = this.as_sound_object().unwrap();
+
+    let bytearray = args.get_object(activation, 0, "bytes")?;
+    let samples = args.get_u32(activation, 1)?;
+    let format = args.get_string(activation, 2)?;
+    let is_stereo = args.get_bool(3);
+    let sample_rate = args.get_f64(activation, 4)?;
+
+    let is_float = if &format == b"float" {
+        true
+    } else if &format == b"short" {
+        false
+    } else {
+        return Err(make_error_2008(activation, "format"));
+    };
+    if !(1.0..=f64::from(u16::MAX)).contains(&sample_rate) {
+        return Err(make_error_2008(activation, "sampleRate"));
+    }
+    if this.sound_handle().is_some() {
+        return Err(make_error_2037(activation));
+    }
+
+    // Read as many whole samples as the `ByteArray` holds. Float samples are kept as they are,
+    // and shorts are converted to the little-endian 16-bit PCM that the audio backend plays.
+    let bytearray = bytearray.as_bytearray().unwrap();
+    let value_size = if is_float { 4 } else { 2 };
+    let frame_size = value_size * if is_stereo { 2 } else { 1 };
+    let num_frames = (samples as usize).min(bytearray.bytes_available() / frame_size);
+    let bytes = bytearray
+        .read_bytes(num_frames * frame_size)
+        .map_err(|e| e.to_avm(activation))?;
+    let endian = bytearray.endian();
+    let result = if is_float {
+        let data: Vec<f32> = bytes
+            .chunks_exact(value_size)
+            .map(|value| match endian {
+                Endian::Big => f32::from_be_bytes(value.try_into().unwrap()),
+                Endian::Little => f32::from_le_bytes(value.try_into().unwrap()),
+            })
+            .collect();
+        drop(bytearray);
+        activation
+            .context
+            .audio
+            .register_float_pcm(&data, sample_rate as u16, is_stereo)
+    } else {
+        let data: Vec<u8> = bytes
+            .chunks_exact(value_size)
+            .flat_map(|value| {
+                let sample = match endian {
+                    Endian::Big => i16::from_be_bytes(value.try_into().unwrap()),
+                    Endian::Little => i16::from_le_bytes(value.try_into().unwrap()),
+                };
+                sample.to_le_bytes()
+            })
+            .collect();
+        drop(bytearray);
+        activation
+            .context
+            .audio
+            .register_pcm(&data, sample_rate as u16, is_stereo)
+    };
+
+    let handle = result.map_err(|e| {
+        Error::RustError(format!("Failed to register sound from bytearray: {e:?}").into())
+    })?;
+    this.set_sound(activation.context, handle)?;
+
     Ok(Value::Undefined)
 }
 
@@ -75,3 +75,20 @@ pub fn attach_net_stream<'gc>(
 
     Ok(Value::Undefined)
 }
+
+/// Implements `Video.attachCamera`
+pub fn attach_camera<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this.as_display_object().and_then(|dobj| dobj.as_video()) {
+        let feed = args
+            .try_get_object(activation, 0)
+            .and_then(|camera| activation.avm2().media_capture_objects.camera_feed(camera));
+
+        video.attach_camera(activation.context, feed);
+    }
+
+    Ok(Value::Undefined)
+}
@@ -1,6 +1,64 @@
 package flash.net {
+    import flash.events.EventDispatcher;
+    import flash.utils.ByteArray;
+
     [API("668")] // AIR 2.0
-    public class DatagramSocket {
+    public class DatagramSocket extends EventDispatcher {
+        [Ruffle(InternalSlot)]
+        private var _bound:Boolean = false;
+
+        [Ruffle(InternalSlot)]
+        private var _connected:Boolean = false;
+
+        [Ruffle(InternalSlot)]
+        private var _localAddress:String = null;
+
+        [Ruffle(InternalSlot)]
+        private var _localPort:int = 0;
+
+        [Ruffle(InternalSlot)]
+        private var _remoteAddress:String = null;
+
+        [Ruffle(InternalSlot)]
+        private var _remotePort:int = 0;
+
+        public function DatagramSocket() {
+        }
+
+        public static native function get isSupported():Boolean;
+
+        public function get bound():Boolean {
+            return this._bound;
+        }
+
+        public function get connected():Boolean {
+            return this._connected;
+        }
+
+        public function get localAddress():String {
+            return this._localAddress;
+        }
+
+        public function get localPort():int {
+            return this._localPort;
+        }
+
+        public function get remoteAddress():String {
+            return this._remoteAddress;
+        }
+
+        public function get remotePort():int {
+            return this._remotePort;
+        }
+
+        public native function bind(localPort:int = 0, localAddress:String = "0.0.0.0"):void;
+
+        public native function connect(remoteAddress:String, remotePort:int):void;
+
+        public native function send(bytes:ByteArray, offset:uint = 0, length:uint = 0, address:String = null, port:int = 0):void;
+
+        public native function receive():void;
 
+        public native function close():void;
     }
-}
\ No newline at end of file
+}
@@ -21,13 +21,9 @@ package flash.net {
 
         }
 
-        public function appendBytes(bytes:ByteArray) {
-            stub_method("flash.net.NetStream", "appendBytes");
-        }
+        public native function appendBytes(bytes:ByteArray);
 
-        public function appendBytesAction(action:String) {
-            stub_method("flash.net.NetStream", "appendBytesAction");
-        }
+        public native function appendBytesAction(action:String);
 
         public function attach(connection:NetConnection) {
             stub_method("flash.net.NetStream", "attach");
@@ -41,9 +37,7 @@ package flash.net {
             stub_method("flash.net.NetStream", "attachCamera");
         }
 
-        public function close() {
-            stub_method("flash.net.NetStream", "close");
-        }
+        public native function close();
 
         [API("674")]
         public function dispose() {
@@ -0,0 +1,289 @@
+//! `flash.net.DatagramSocket` native methods
+
+use crate::avm2::error::{
+    illegal_operation_error, io_error, make_error_2004, range_error, Error2004Type,
+};
+use crate::avm2::globals::slots::*;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::context::UpdateContext;
+use crate::datagram_socket::{
+    reset_slots, set_bound_slots, Datagram, DatagramSocketAction, DatagramSocketHandle,
+};
+use crate::string::AvmString;
+use std::net::IpAddr;
+
+/// The address a socket gets bound to when it's used without having been bound.
+const ANY_ADDRESS: &str = "0.0.0.0";
+
+fn to_port<'gc>(activation: &mut Activation<'_, 'gc>, port: i32) -> Result<u16, Error<'gc>> {
+    port.try_into().map_err(|_| {
+        match range_error(
+            activation,
+            "Error #2003: Invalid socket port number specified.",
+            2003,
+        ) {
+            Ok(err) => Error::AvmError(err),
+            Err(e) => e,
+        }
+    })
+}
+
+/// The error Flash throws for operations the socket isn't in a state for, like binding it twice.
+fn invalid_socket_error<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match illegal_operation_error(
+        activation,
+        "Error #2002: Operation attempted on invalid socket.",
+        2002,
+    ) {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
+
+fn make_io_error<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    message: &str,
+    code: u32,
+) -> Error<'gc> {
+    match io_error(activation, message, code) {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
+
+fn to_address<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    address: AvmString<'gc>,
+) -> Result<String, Error<'gc>> {
+    let address = address.to_utf8_lossy().into_owned();
+    if address.parse::<IpAddr>().is_err() {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    }
+    Ok(address)
+}
+
+/// Binds a `DatagramSocket`, which completes right away if the backend binds synchronously.
+///
+/// Otherwise, the socket only reads as bound once the backend reports the address it's bound to.
+fn bind_socket<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    address: String,
+    port: u16,
+) -> Result<DatagramSocketHandle, Error<'gc>> {
+    let movie = activation.caller_movie_or_root();
+    let UpdateContext {
+        datagram_sockets,
+        navigator,
+        ..
+    } = activation.context;
+    let handle = datagram_sockets.bind(*navigator, this, address, port, movie);
+
+    match activation.context.datagram_sockets.take_bind_result(handle) {
+        Some(DatagramSocketAction::Bound(_, address, port)) => {
+            set_bound_slots(activation, this, address, port);
+        }
+        Some(DatagramSocketAction::BindFailed(_)) => {
+            activation.context.datagram_sockets.close(handle);
+            return Err(make_io_error(
+                activation,
+                "Error #2031: Socket Error.",
+                2031,
+            ));
+        }
+        _ => {}
+    }
+
+    Ok(handle)
+}
+
+/// The socket of a `DatagramSocket`, which gets bound to any address if it wasn't bound yet.
+fn bound_socket<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<DatagramSocketHandle, Error<'gc>> {
+    match activation.context.datagram_sockets.handle_of(this) {
+        Some(handle) => Ok(handle),
+        None => bind_socket(activation, this, ANY_ADDRESS.to_string(), 0),
+    }
+}
+
+/// Implements `DatagramSocket.isSupported`
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .navigator
+        .supports_datagram_sockets()
+        .into())
+}
+
+/// Implements `DatagramSocket.bind`
+pub fn bind<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let port = args.get_i32(activation, 0)?;
+    let port = to_port(activation, port)?;
+    let address = args.get_string_non_null(activation, 1, "localAddress")?;
+    let address = to_address(activation, address)?;
+
+    if activation
+        .context
+        .datagram_sockets
+        .handle_of(this)
+        .is_some()
+    {
+        return Err(invalid_socket_error(activation));
+    }
+
+    bind_socket(activation, this, address, port)?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DatagramSocket.connect`
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let address = args.get_string_non_null(activation, 0, "remoteAddress")?;
+    let address = to_address(activation, address)?;
+    let port = args.get_i32(activation, 1)?;
+    let port = to_port(activation, port)?;
+
+    if this
+        .get_slot(FLASH_NET_DATAGRAM_SOCKET__CONNECTED_SLOT)
+        .coerce_to_boolean()
+    {
+        return Err(invalid_socket_error(activation));
+    }
+
+    let handle = bound_socket(activation, this)?;
+    activation
+        .context
+        .datagram_sockets
+        .connect(handle, address.clone(), port);
+
+    let address = AvmString::new_utf8(activation.context.gc_context, address);
+    this.set_slot(
+        FLASH_NET_DATAGRAM_SOCKET__CONNECTED_SLOT,
+        true.into(),
+        activation,
+    )?;
+    this.set_slot(
+        FLASH_NET_DATAGRAM_SOCKET__REMOTE_ADDRESS_SLOT,
+        address.into(),
+        activation,
+    )?;
+    this.set_slot(
+        FLASH_NET_DATAGRAM_SOCKET__REMOTE_PORT_SLOT,
+        port.into(),
+        activation,
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DatagramSocket.send`
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bytearray = args.get_object(activation, 0, "bytes")?;
+    let offset = args.get_u32(activation, 1)? as usize;
+    let length = args.get_u32(activation, 2)? as usize;
+    let address = args.try_get_string(activation, 3)?;
+    let port = args.get_i32(activation, 4)?;
+
+    let connected = this
+        .get_slot(FLASH_NET_DATAGRAM_SOCKET__CONNECTED_SLOT)
+        .coerce_to_boolean();
+    let (address, port) = if connected {
+        // A connected socket can only send to the address it's connected to.
+        if address.is_some() || port != 0 {
+            return Err(invalid_socket_error(activation));
+        }
+
+        let address = this
+            .get_slot(FLASH_NET_DATAGRAM_SOCKET__REMOTE_ADDRESS_SLOT)
+            .coerce_to_string(activation)?;
+        let port = this
+            .get_slot(FLASH_NET_DATAGRAM_SOCKET__REMOTE_PORT_SLOT)
+            .as_i32();
+        (address.to_utf8_lossy().into_owned(), port as u16)
+    } else {
+        let Some(address) = address else {
+            return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+        };
+        (to_address(activation, address)?, to_port(activation, port)?)
+    };
+
+    let data = {
+        let bytearray = bytearray
+            .as_bytearray()
+            .expect("Parameter must be a bytearray!");
+
+        // If length is 0, the remaining bytes of the ByteArray from the offset are sent.
+        let length = if length != 0 {
+            length
+        } else {
+            bytearray.len().saturating_sub(offset)
+        };
+        bytearray
+            .read_at(length, offset)
+            .map_err(|e| e.to_avm(activation))?
+            .to_vec()
+    };
+
+    let handle = bound_socket(activation, this)?;
+    activation.context.datagram_sockets.send(
+        handle,
+        Datagram {
+            address,
+            port,
+            data,
+        },
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DatagramSocket.receive`
+pub fn receive<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let handle = bound_socket(activation, this)?;
+    activation.context.datagram_sockets.receive(handle);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DatagramSocket.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(handle) = activation.context.datagram_sockets.handle_of(this) else {
+        return Err(make_io_error(
+            activation,
+            "Error #2002: Operation attempted on invalid socket.",
+            2002,
+        ));
+    };
+
+    activation.context.datagram_sockets.close(handle);
+    reset_slots(activation, this);
+
+    Ok(Value::Undefined)
+}
@@ -1,6 +1,7 @@
-use crate::avm2::error::{make_error_2004, Error2004Type};
+use crate::avm2::error::{make_error_2004, make_error_2008, Error2004Type};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::streams::AppendBytesAction;
 
 pub use crate::avm2::object::netstream_allocator as net_stream_allocator;
 
@@ -41,12 +42,67 @@ pub fn play<'gc>(
             .map(|v| v.coerce_to_string(activation))
             .transpose()?;
 
+        // `play(null)` puts the stream into data generation mode.
+        if matches!(args.get(0), Some(Value::Null)) {
+            ns.start_data_generation(activation.context);
+        }
+
         ns.play(activation.context, name);
     }
 
     Ok(Value::Undefined)
 }
 
+pub fn append_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let bytearray = args.get_object(activation, 0, "bytes")?;
+        let mut data = bytearray.as_bytearray().unwrap().bytes().to_vec();
+
+        ns.append_bytes(activation.context, &mut data);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn append_bytes_action<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let action = args.get_string(activation, 0)?;
+        let action = if &action == b"resetBegin" {
+            AppendBytesAction::ResetBegin
+        } else if &action == b"resetSeek" {
+            AppendBytesAction::ResetSeek
+        } else if &action == b"endSequence" {
+            AppendBytesAction::EndSequence
+        } else {
+            return Err(make_error_2008(activation, "action"));
+        };
+
+        ns.append_bytes_action(activation.context, action);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        ns.close(activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn pause<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
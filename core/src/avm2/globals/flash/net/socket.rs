@@ -41,11 +41,18 @@ pub fn connect<'gc>(
         .try_into()
         .map_err(|_| invalid_port_number(activation))?;
 
+    let movie = activation.caller_movie_or_root();
     let UpdateContext {
         sockets, navigator, ..
     } = activation.context;
 
-    sockets.connect_avm2(*navigator, socket, host.to_utf8_lossy().into_owned(), port);
+    sockets.connect_avm2(
+        *navigator,
+        socket,
+        host.to_utf8_lossy().into_owned(),
+        port,
+        movie,
+    );
 
     Ok(Value::Undefined)
 }
@@ -1,6 +1,61 @@
 package flash.printing {
+    import flash.display.Sprite;
     import flash.events.EventDispatcher;
+    import flash.geom.Rectangle;
 
     public class PrintJob extends EventDispatcher {
+        [Ruffle(InternalSlot)]
+        private var _started:Boolean = false;
+
+        [Ruffle(InternalSlot)]
+        private var _paperWidth:int = 0;
+
+        [Ruffle(InternalSlot)]
+        private var _paperHeight:int = 0;
+
+        [Ruffle(InternalSlot)]
+        private var _pageWidth:int = 0;
+
+        [Ruffle(InternalSlot)]
+        private var _pageHeight:int = 0;
+
+        [Ruffle(InternalSlot)]
+        private var _orientation:String = null;
+
+        public function PrintJob() {
+        }
+
+        [API("670")]
+        public static function get isSupported():Boolean {
+            return true;
+        }
+
+        public function get paperWidth():int {
+            return this._paperWidth;
+        }
+
+        public function get paperHeight():int {
+            return this._paperHeight;
+        }
+
+        public function get pageWidth():int {
+            return this._pageWidth;
+        }
+
+        public function get pageHeight():int {
+            return this._pageHeight;
+        }
+
+        public function get orientation():String {
+            return this._orientation;
+        }
+
+        public native function start():Boolean;
+
+        public native function addPage(sprite:Sprite, printArea:Rectangle = null, options:PrintJobOptions = null, frameNum:int = 0):void;
+
+        public native function send():void;
+
+        public native function terminate():void;
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,152 @@
+//! `flash.printing.PrintJob` native methods
+
+use crate::avm2::error::{error, make_error_2007};
+use crate::avm2::globals::flash::display::display_object::object_to_rectangle;
+use crate::avm2::globals::slots::*;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::avm2_stub_method;
+use crate::display_object::TDisplayObject;
+use crate::printing::{render_page, PrintJob, PAGE_HEIGHT, PAGE_WIDTH, PAPER_HEIGHT, PAPER_WIDTH};
+use crate::string::AvmString;
+
+fn is_started(this: Object<'_>) -> bool {
+    this.get_slot(FLASH_PRINTING_PRINT_JOB__STARTED_SLOT)
+        .coerce_to_boolean()
+}
+
+/// Implements `PrintJob.start`
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Only one print job can be active at a time.
+    if activation.context.print_job.is_some() {
+        return Ok(false.into());
+    }
+    *activation.context.print_job = Some(PrintJob::default());
+
+    let orientation = AvmString::new_utf8(activation.context.gc_context, "portrait");
+    this.set_slot(
+        FLASH_PRINTING_PRINT_JOB__STARTED_SLOT,
+        true.into(),
+        activation,
+    )?;
+    this.set_slot(
+        FLASH_PRINTING_PRINT_JOB__PAPER_WIDTH_SLOT,
+        (PAPER_WIDTH as i32).into(),
+        activation,
+    )?;
+    this.set_slot(
+        FLASH_PRINTING_PRINT_JOB__PAPER_HEIGHT_SLOT,
+        (PAPER_HEIGHT as i32).into(),
+        activation,
+    )?;
+    this.set_slot(
+        FLASH_PRINTING_PRINT_JOB__PAGE_WIDTH_SLOT,
+        (PAGE_WIDTH as i32).into(),
+        activation,
+    )?;
+    this.set_slot(
+        FLASH_PRINTING_PRINT_JOB__PAGE_HEIGHT_SLOT,
+        (PAGE_HEIGHT as i32).into(),
+        activation,
+    )?;
+    this.set_slot(
+        FLASH_PRINTING_PRINT_JOB__ORIENTATION_SLOT,
+        orientation.into(),
+        activation,
+    )?;
+
+    Ok(true.into())
+}
+
+/// Implements `PrintJob.addPage`
+pub fn add_page<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if !is_started(this) {
+        return Err(Error::AvmError(error(
+            activation,
+            "Error #2057: The page could not be added to the print job.",
+            2057,
+        )?));
+    }
+
+    let sprite = args.get_object(activation, 0, "sprite")?;
+    let Some(sprite) = sprite.as_display_object() else {
+        return Err(make_error_2007(activation, "sprite"));
+    };
+
+    let area = match args.try_get_object(activation, 1) {
+        Some(print_area) => object_to_rectangle(activation, print_area)?,
+        None => sprite.bounds(),
+    };
+
+    let as_bitmap = match args.try_get_object(activation, 2) {
+        Some(options) => options
+            .get_public_property("printAsBitmap", activation)?
+            .coerce_to_boolean(),
+        None => false,
+    };
+
+    if args.get_i32(activation, 3)? != 0 {
+        avm2_stub_method!(
+            activation,
+            "flash.printing.PrintJob",
+            "addPage",
+            "with frameNum"
+        );
+    }
+
+    if let Some(page) = render_page(activation.context, sprite, area, as_bitmap, false) {
+        if let Some(print_job) = activation.context.print_job.as_mut() {
+            print_job.pages.push(page);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.send`
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if !is_started(this) {
+        return Ok(Value::Undefined);
+    }
+
+    this.set_slot(
+        FLASH_PRINTING_PRINT_JOB__STARTED_SLOT,
+        false.into(),
+        activation,
+    )?;
+    if let Some(print_job) = activation.context.print_job.take() {
+        activation.context.ui.print_pages(print_job.pages);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.terminate`
+pub fn terminate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if is_started(this) {
+        this.set_slot(
+            FLASH_PRINTING_PRINT_JOB__STARTED_SLOT,
+            false.into(),
+            activation,
+        )?;
+        *activation.context.print_job = None;
+    }
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,420 @@
+//! `flash.sampler` namespace
+
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::call_stack::CallNode;
+use crate::avm2::globals::slots::*;
+use crate::avm2::method::Method;
+use crate::avm2::object::{ArrayObject, QNameObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::property::Property;
+use crate::avm2::sampler::{sample_time, value_size, Sample};
+use crate::avm2::vtable::VTable;
+use crate::avm2::{Activation, Error, Multiname, Object, TObject, Value};
+use crate::string::{AvmString, WString};
+
+pub mod new_object_sample;
+
+/// Which of the methods of a property to look up.
+#[derive(Clone, Copy)]
+enum Accessor {
+    Method,
+    Getter,
+    Setter,
+}
+
+/// Finds the method an object has under a name, also looking at the instance methods when
+/// the object is a class. A missing name refers to the constructor of a class.
+fn find_method<'gc>(
+    object: Object<'gc>,
+    name: Option<QNameObject<'gc>>,
+    accessor: Accessor,
+) -> Option<Method<'gc>> {
+    let Some(name) = name else {
+        return match accessor {
+            Accessor::Method => object.as_class_object().map(|class| class.init_method()),
+            _ => None,
+        };
+    };
+
+    let (vtable, property) = find_trait(object, &name.name())?;
+    let disp_id = match (accessor, property) {
+        (Accessor::Method, Property::Method { disp_id }) => disp_id,
+        (
+            Accessor::Getter,
+            Property::Virtual {
+                get: Some(disp_id), ..
+            },
+        ) => disp_id,
+        (
+            Accessor::Setter,
+            Property::Virtual {
+                set: Some(disp_id), ..
+            },
+        ) => disp_id,
+        _ => return None,
+    };
+    vtable.get_method(disp_id)
+}
+
+fn find_trait<'gc>(object: Object<'gc>, name: &Multiname<'gc>) -> Option<(VTable<'gc>, Property)> {
+    let vtable = object.vtable();
+    if let Some(property) = vtable.get_trait(name) {
+        return Some((vtable, property));
+    }
+
+    let instance_vtable = object.as_class_object()?.instance_vtable();
+    let property = instance_vtable.get_trait(name)?;
+    Some((instance_vtable, property))
+}
+
+fn invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    args: &[Value<'gc>],
+    accessor: Accessor,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(object) = args.try_get_object(activation, 0) else {
+        return Ok((-1).into());
+    };
+    let name = args
+        .try_get_object(activation, 1)
+        .and_then(|name| name.as_qname_object());
+
+    Ok(match find_method(object, name, accessor) {
+        Some(method) => activation.avm2().sampler.invocation_count(method).into(),
+        None => (-1).into(),
+    })
+}
+
+/// Creates the `StackFrame`s of a sampled call stack.
+fn stack_frames<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    stack: &[CallNode<'gc>],
+) -> Result<Object<'gc>, Error<'gc>> {
+    let stack_frame_class = activation.avm2().classes().stackframe;
+
+    let mut frames = Vec::with_capacity(stack.len());
+    for call in stack {
+        let mut name = WString::new();
+        call.display(&mut name);
+        // Stack frames are named without the parameter list that stack traces show.
+        let name = name.to_utf8_lossy();
+        let name = name.split("()").next().unwrap_or_default();
+        let name = AvmString::new_utf8(activation.context.gc_context, name);
+
+        let frame = stack_frame_class.construct(activation, &[])?;
+        frame.set_slot(
+            FLASH_SAMPLER_STACK_FRAME__NAME_SLOT,
+            name.into(),
+            activation,
+        )?;
+        frames.push(Some(frame.into()));
+    }
+
+    ArrayObject::from_storage(activation, ArrayStorage::from_storage(frames))
+}
+
+/// Creates the `Sample` object for a recorded sample.
+fn sample_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    sample: &Sample<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let (object, time, stack) = match sample {
+        Sample::Stack { time, stack } => {
+            let object = activation
+                .avm2()
+                .classes()
+                .sample
+                .construct(activation, &[])?;
+            (object, time, stack)
+        }
+        Sample::NewObject {
+            time,
+            stack,
+            id,
+            class,
+            size,
+            ..
+        } => {
+            let sample_class = activation.avm2().classes().newobjectsample;
+            let object = sample_class.construct(activation, &[])?;
+            object.set_slot(
+                FLASH_SAMPLER_NEW_OBJECT_SAMPLE__ID_SLOT,
+                (*id).into(),
+                activation,
+            )?;
+            object.set_slot(
+                FLASH_SAMPLER_NEW_OBJECT_SAMPLE__TYPE_SLOT,
+                (*class).into(),
+                activation,
+            )?;
+            object.set_slot(
+                FLASH_SAMPLER_NEW_OBJECT_SAMPLE__SIZE_SLOT,
+                (*size as f64).into(),
+                activation,
+            )?;
+            (object, time, stack)
+        }
+        Sample::DeleteObject {
+            time,
+            stack,
+            id,
+            size,
+        } => {
+            let sample_class = activation.avm2().classes().deleteobjectsample;
+            let object = sample_class.construct(activation, &[])?;
+            object.set_slot(
+                FLASH_SAMPLER_DELETE_OBJECT_SAMPLE__ID_SLOT,
+                (*id).into(),
+                activation,
+            )?;
+            object.set_slot(
+                FLASH_SAMPLER_DELETE_OBJECT_SAMPLE__SIZE_SLOT,
+                (*size as f64).into(),
+                activation,
+            )?;
+            (object, time, stack)
+        }
+    };
+
+    object.set_slot(FLASH_SAMPLER_SAMPLE__TIME_SLOT, (*time).into(), activation)?;
+    let stack = stack_frames(activation, stack)?;
+    object.set_slot(FLASH_SAMPLER_SAMPLE__STACK_SLOT, stack.into(), activation)?;
+    Ok(object)
+}
+
+/// Implements `flash.sampler.clearSamples`
+pub fn clear_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler.clear_samples();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.getGetterInvocationCount`
+pub fn get_getter_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    invocation_count(activation, args, Accessor::Getter)
+}
+
+/// Implements `flash.sampler.getInvocationCount`
+pub fn get_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    invocation_count(activation, args, Accessor::Method)
+}
+
+/// Implements `flash.sampler.getSetterInvocationCount`
+pub fn get_setter_invocation_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    invocation_count(activation, args, Accessor::Setter)
+}
+
+/// Implements `flash.sampler.getLexicalScopes`
+pub fn get_lexical_scopes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(function) = args
+        .try_get_object(activation, 0)
+        .and_then(|function| function.as_function_object())
+    else {
+        return Ok(Value::Null);
+    };
+
+    let scope = function.scope();
+    let scopes = (0..)
+        .map_while(|index| scope.get(index))
+        .map(|scope| Some(scope.values().into()))
+        .collect();
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(scopes))?.into())
+}
+
+/// Implements `flash.sampler.getMemberNames`
+pub fn get_member_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = args.get_object(activation, 0, "o")?;
+    let instance_names = args.get_bool(1);
+
+    let vtable = match object.as_class_object() {
+        Some(class) if instance_names => class.instance_vtable(),
+        _ => object.vtable(),
+    };
+    let mut names: Vec<_> = vtable
+        .resolved_traits()
+        .iter()
+        .map(|(name, ns, _)| Multiname::new(ns, name))
+        .collect();
+
+    if !instance_names {
+        let mut last_index = object.get_next_enumerant(0, activation)?;
+        while let Some(index) = last_index.filter(|index| *index != 0) {
+            let name = object
+                .get_enumerant_name(index, activation)?
+                .coerce_to_string(activation)?;
+            names.push(Multiname::new(
+                activation.avm2().namespaces.public_all(),
+                name,
+            ));
+            last_index = object.get_next_enumerant(index, activation)?;
+        }
+    }
+
+    let names = names
+        .into_iter()
+        .map(|name| Some(QNameObject::from_name(activation, name).into()))
+        .collect();
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(names))?.into())
+}
+
+/// Implements `flash.sampler.getSampleCount`
+pub fn get_sample_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let time = sample_time(activation.context);
+    let mc = activation.context.gc_context;
+    let count = activation.avm2().sampler.samples(mc, time).len();
+    Ok((count as f64).into())
+}
+
+/// Implements `flash.sampler.getSamples`
+pub fn get_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let time = sample_time(activation.context);
+    let mc = activation.context.gc_context;
+    let sampler = &mut activation.avm2().sampler;
+    let samples = sampler.samples(mc, time).to_vec();
+
+    // Creating the sample objects shouldn't show up in the samples.
+    let was_active = sampler.is_active();
+    sampler.pause();
+
+    let mut objects = Vec::with_capacity(samples.len());
+    let mut result = Ok(());
+    for sample in &samples {
+        match sample_object(activation, sample) {
+            Ok(object) => objects.push(Some(object.into())),
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+
+    if was_active {
+        activation.avm2().sampler.start();
+    }
+    result?;
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(objects))?.into())
+}
+
+/// Implements `flash.sampler.getSavedThis`
+pub fn get_saved_this<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(args
+        .try_get_object(activation, 0)
+        .and_then(|function| function.as_function_object())
+        .and_then(|function| function.bound_receiver())
+        .map_or(Value::Null, Value::from))
+}
+
+/// Implements `flash.sampler.getSize`
+pub fn get_size<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((value_size(args.get_value(0)) as f64).into())
+}
+
+/// Implements `flash.sampler.isGetterSetter`
+pub fn is_getter_setter<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let object = args.get_object(activation, 0, "obj")?;
+    let Some(name) = args
+        .try_get_object(activation, 1)
+        .and_then(|name| name.as_qname_object())
+    else {
+        return Ok(false.into());
+    };
+
+    let property = find_trait(object, &name.name()).map(|(_, property)| property);
+    Ok(matches!(property, Some(Property::Virtual { .. })).into())
+}
+
+/// Implements `flash.sampler.pauseSampling`
+pub fn pause_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler.pause();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.sampleInternalAllocs`
+pub fn sample_internal_allocs<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let everything = args.get_bool(0);
+    activation.avm2().sampler.set_internal_allocs(everything);
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.setSamplerCallback`
+pub fn set_sampler_callback<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let callback = args.try_get_object(activation, 0);
+    activation.avm2().sampler.set_callback(callback);
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.startSampling`
+pub fn start_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler.start();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.stopSampling`
+pub fn stop_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler.stop();
+    Ok(Value::Undefined)
+}
@@ -1,8 +1,9 @@
 package flash.sampler {
     public final class DeleteObjectSample extends Sample {
+        [Ruffle(InternalSlot)]
         public const id:Number;
-     
+
+        [Ruffle(InternalSlot)]
         public const size:Number;
     }
 }
-
@@ -1,20 +1,19 @@
 package flash.sampler {
-    import __ruffle__.stub_getter;
-
     public final class NewObjectSample extends Sample {
+        [Ruffle(InternalSlot)]
         public const id:Number;
-     
+
+        [Ruffle(InternalSlot)]
         public const type:Class;
 
-        public function get object():* {
-            stub_getter("flash.sampler.NewObjectSample", "object");
-            return {};
-        }
+        [Ruffle(InternalSlot)]
+        private var _size:Number;
+
+        // Returns `undefined` once the object has been collected.
+        public native function get object():*;
 
         public function get size():Number {
-            stub_getter("flash.sampler.NewObjectSample", "size");
-            return 0;
+            return this._size;
         }
     }
 }
-
@@ -1,8 +1,9 @@
 package flash.sampler {
     public class Sample {
+        [Ruffle(InternalSlot)]
         public const time:Number;
-      
+
+        [Ruffle(InternalSlot)]
         public const stack:Array;
     }
 }
-
@@ -1,5 +1,6 @@
 package flash.sampler {
     public final class StackFrame {
+        [Ruffle(InternalSlot)]
         public const name:String;
 
         public const file:String;
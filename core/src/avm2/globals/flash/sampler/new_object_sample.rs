@@ -0,0 +1,21 @@
+//! `flash.sampler.NewObjectSample` native methods
+
+use crate::avm2::globals::slots::*;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+
+/// Implements `NewObjectSample.object`
+pub fn get_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = this
+        .get_slot(FLASH_SAMPLER_NEW_OBJECT_SAMPLE__ID_SLOT)
+        .coerce_to_number(activation)?;
+    let mc = activation.context.gc_context;
+    Ok(activation
+        .avm2()
+        .sampler
+        .tracked_object(mc, id)
+        .map_or(Value::Undefined, Value::from))
+}
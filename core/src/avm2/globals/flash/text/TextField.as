@@ -9,7 +9,6 @@ package flash.text {
     [Ruffle(InstanceAllocator)]
     public class TextField extends InteractiveObject {
         internal var _styleSheet:StyleSheet;
-        internal var _useRichTextClipboard:Boolean;
 
         public native function get alwaysShowSelection():Boolean;
         public native function set alwaysShowSelection(value:Boolean):void;
@@ -94,14 +93,8 @@ package flash.text {
         public native function get type():String;
         public native function set type(value:String):void;
 
-        public function get useRichTextClipboard():Boolean {
-            return this._useRichTextClipboard;
-        }
-
-        public function set useRichTextClipboard(value:Boolean):void {
-            this._useRichTextClipboard = value;
-            stub_setter("flash.text.TextField", "useRichTextClipboard");
-        }
+        public native function get useRichTextClipboard():Boolean;
+        public native function set useRichTextClipboard(value:Boolean):void;
 
         public native function get wordWrap():Boolean;
         public native function set wordWrap(value:Boolean):void;
@@ -1,4 +1,3 @@
 //! `flash.text.engine` namespace
 
 pub mod text_block;
-pub mod text_line;
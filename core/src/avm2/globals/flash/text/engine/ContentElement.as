@@ -5,6 +5,7 @@ package flash.text.engine {
         public var userData;
 
         internal var _text:String = null;
+        internal var _textBlock:TextBlock = null;
         
         private var _elementFormat:ElementFormat;
         
@@ -27,6 +28,22 @@ package flash.text.engine {
         
         public function set elementFormat(value:ElementFormat):void {
             this._elementFormat = value;
+            this.contentChanged();
+        }
+
+        public function get textBlock():TextBlock {
+            return this._textBlock;
+        }
+
+        internal function setTextBlock(value:TextBlock):void {
+            this._textBlock = value;
+        }
+
+        // Lets the block containing this element know that its content has to be read again.
+        internal function contentChanged():void {
+            if (this._textBlock != null) {
+                this._textBlock.invalidateContent();
+            }
         }
     }
 }
@@ -2,39 +2,45 @@ package flash.text.engine {
     import flash.display.DisplayObject;
     import flash.events.EventDispatcher;
 
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_getter;
-    import __ruffle__.stub_setter;
-
     public final class GraphicElement extends ContentElement {
+        private var _graphic:DisplayObject;
+        private var _elementWidth:Number;
+        private var _elementHeight:Number;
+
         public function GraphicElement(graphic:DisplayObject = null, elementWidth:Number = 15.0, elementHeight:Number = 15.0, elementFormat:ElementFormat = null, eventMirror:EventDispatcher = null, textRotation:String = "rotate0") {
-            stub_constructor("flash.text.engine.GraphicElement");
+            super(elementFormat, eventMirror, textRotation);
+            // A graphic takes up a single character in the text of a TextBlock.
+            this._text = "\uFDEF";
+            this._graphic = graphic;
+            this._elementWidth = elementWidth;
+            this._elementHeight = elementHeight;
         }
 
         public function get elementHeight():Number {
-            stub_getter("flash.text.engine.GraphicElement", "elementHeight");
-            return 15.0;
+            return this._elementHeight;
         }
+
         public function set elementHeight(value:Number):void {
-            stub_setter("flash.text.engine.GraphicElement", "elementHeight");
+            this._elementHeight = value;
+            this.contentChanged();
         }
 
         public function get elementWidth():Number {
-            stub_getter("flash.text.engine.GraphicElement", "elementWidth");
-            return 15.0;
+            return this._elementWidth;
         }
 
         public function set elementWidth(value:Number):void {
-            stub_setter("flash.text.engine.GraphicElement", "elementWidth");
+            this._elementWidth = value;
+            this.contentChanged();
         }
 
         public function get graphic():DisplayObject {
-            stub_getter("flash.text.engine.GraphicElement", "graphic");
-            return null;
+            return this._graphic;
         }
 
         public function set graphic(value:DisplayObject):void {
-            stub_setter("flash.text.engine.GraphicElement", "graphic");
+            this._graphic = value;
+            this.contentChanged();
         }
     }
-}
\ No newline at end of file
+}
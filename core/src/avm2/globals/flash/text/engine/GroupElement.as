@@ -27,11 +27,18 @@ package flash.text.engine {
          }
 
         public function setElements(elements:Vector.<ContentElement>):void {
+            if (this._elements != null) {
+                for (var i = 0; i < this._elements.length; i++) {
+                    this._elements[i].setTextBlock(null);
+                }
+            }
             if (elements == null) {
                 this._elements = new Vector.<ContentElement>();
             } else {
                 this._elements = elements.AS3::concat();
             }
+            this.setTextBlock(this._textBlock);
+            this.contentChanged();
         }
 
         public function replaceElements(beginIndex:int, endIndex:int, newElements:Vector.<ContentElement>):Vector.<ContentElement> {
@@ -45,11 +52,16 @@ package flash.text.engine {
             }
 
             var old = this._elements.AS3::splice(beginIndex, endIndex - beginIndex);
+            for (var i = 0; i < old.length; i++) {
+                old[i].setTextBlock(null);
+            }
             if (newElements) {
-                for (var i = 0; i < newElements.length; i++) {
+                for (i = 0; i < newElements.length; i++) {
                     this._elements.AS3::insertAt(beginIndex + i, newElements[i]);
+                    newElements[i].setTextBlock(this._textBlock);
                 }
             }
+            this.contentChanged();
             return old;
         }
 
@@ -67,9 +79,18 @@ package flash.text.engine {
             element.text = text.slice(0, splitIndex);
             var newTextElement = new TextElement(text.slice(splitIndex));
             this._elements.AS3::insertAt(elementIndex + 1, newTextElement);
+            newTextElement.setTextBlock(this._textBlock);
+            this.contentChanged();
             return newTextElement;
         }
 
+        override internal function setTextBlock(value:TextBlock):void {
+            super.setTextBlock(value);
+            for (var i = 0; i < this._elements.length; i++) {
+                this._elements[i].setTextBlock(value);
+            }
+        }
+
         // FIXME: This is wrong, FP doesn't do an override of `get text` in GroupElement
         override public function get text():String {
             var resultingText:String = "";
@@ -1,6 +1,5 @@
 package flash.text.engine {
-    import __ruffle__.stub_method;
-
+    [Ruffle(InstanceAllocator)]
     public final class TextBlock {
         public var userData;
 
@@ -16,6 +15,7 @@ package flash.text.engine {
 
         internal var _textLineCreationResult:String = null;
         internal var _firstLine:TextLine = null;
+        internal var _lastLine:TextLine = null;
 
 
         public function TextBlock(content:ContentElement = null,
@@ -129,28 +129,83 @@ package flash.text.engine {
         }
 
         public function set content(value:ContentElement):void {
+            if (this._content != null) {
+                this._content.setTextBlock(null);
+            }
             this._content = value;
+            if (value != null) {
+                value.setTextBlock(this);
+            }
+            this.invalidateContent();
         }
 
-        public native function createTextLine(previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0, fitSomething:Boolean = false):TextLine;
+        // Drops the flattened content, so that the next line created reads the content again.
+        internal native function invalidateContent():void;
+
+        public function createTextLine(previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0.0, fitSomething:Boolean = false):TextLine {
+            return this.createLine(null, previousLine, width, lineOffset, fitSomething);
+        }
 
-        public function recreateTextLine(textLine:TextLine, previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0, fitSomething:Boolean = false):TextLine {
-            if (textLine == null) {
+        public function recreateTextLine(textLine:TextLine, previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0.0, fitSomething:Boolean = false):TextLine {
+            if (textLine == null || textLine == previousLine) {
                 throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
             }
 
-            if (previousLine) {
+            // Text layout modules often depend on getting the same textLine back,
+            // so it's laid out again rather than replaced.
+            return this.createLine(textLine, previousLine, width, lineOffset, fitSomething);
+        }
+
+        private function createLine(textLine:TextLine, previousLine:TextLine, width:Number, lineOffset:Number, fitSomething:Boolean):TextLine {
+            if (previousLine != null && previousLine._textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            // This also rejects NaN.
+            if (!(width >= 0 && width <= TextLine.MAX_LINE_WIDTH)) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var beginIndex:int = 0;
+            if (previousLine != null) {
+                beginIndex = previousLine._textBlockBeginIndex + previousLine._rawTextLength;
+            }
+
+            var line:TextLine = this.layoutLine(textLine, beginIndex, previousLine == null, width, lineOffset, fitSomething);
+            if (line == null) {
                 return null;
             }
 
-            stub_method("flash.text.engine.TextBlock", "recreateTextLine");
+            // The lines that followed the previous line don't follow on from this one.
+            var staleLine:TextLine = previousLine != null ? previousLine._nextLine : this._firstLine;
+            while (staleLine != null) {
+                var nextLine:TextLine = staleLine._nextLine;
+                if (staleLine != line) {
+                    staleLine._validity = TextLineValidity.INVALID;
+                    staleLine._textBlock = null;
+                }
+                staleLine._previousLine = null;
+                staleLine._nextLine = null;
+                staleLine = nextLine;
+            }
 
-            // FIXME: Properly recalculate new properties of new TextLine. Text layout
-            // modules often depend on this returning the same textLine, so we can't
-            // call `createTextLine` again.
-            return textLine;
+            line._textBlock = this;
+            line._validity = TextLineValidity.VALID;
+            line._specifiedWidth = width;
+            line._previousLine = previousLine;
+            if (previousLine != null) {
+                previousLine._nextLine = line;
+            } else {
+                this._firstLine = line;
+            }
+            this._lastLine = line;
+
+            return line;
         }
 
+        // Lays out the line starting at `beginIndex` into `textLine`, or into a new line if it's null.
+        // Returns null if no line could be created, see `textLineCreationResult`.
+        private native function layoutLine(textLine:TextLine, beginIndex:int, isFirstLine:Boolean, width:Number, lineOffset:Number, fitSomething:Boolean):TextLine;
+
         public function get textLineCreationResult():String {
             return this._textLineCreationResult;
         }
@@ -160,16 +215,40 @@ package flash.text.engine {
         }
 
         public function get lastLine():TextLine {
-            return this._firstLine;
+            return this._lastLine;
         }
-        
+
         public function releaseLines(start:TextLine, end:TextLine):void {
-            if (start != end || end != this._firstLine) {
-                stub_method("flash.text.engine.TextBlock", "releaseLines", "with start != end or multiple lines");
-                return;
+            if (start == null || end == null || start._textBlock != this || end._textBlock != this) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var before:TextLine = start._previousLine;
+            var after:TextLine = end._nextLine;
+
+            var line:TextLine = start;
+            while (line != null) {
+                var nextLine:TextLine = line._nextLine;
+                line._validity = TextLineValidity.INVALID;
+                line._textBlock = null;
+                line._previousLine = null;
+                line._nextLine = null;
+                if (line == end) {
+                    break;
+                }
+                line = nextLine;
+            }
+
+            if (before != null) {
+                before._nextLine = after;
+            } else {
+                this._firstLine = after;
+            }
+            if (after != null) {
+                after._previousLine = before;
+            } else {
+                this._lastLine = before;
             }
-            this._firstLine._validity = "invalid";
-            this._firstLine._textBlock = null;
         }
     }
 }
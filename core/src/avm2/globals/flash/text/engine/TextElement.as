@@ -12,6 +12,7 @@ package flash.text.engine {
         // Contrary to the documentation, TextElement does not implement a getter here. It inherits the getter from ContentElement.
         public function set text(value:String):void {
             this._text = value;
+            this.contentChanged();
         }
 
         public function replaceText(beginIndex:int, endIndex:int, newText:String):void {
@@ -1,23 +1,34 @@
 package flash.text.engine {
-    import __ruffle__.stub_getter;
     import __ruffle__.stub_setter;
-    import __ruffle__.stub_method;
 
     import flash.display.DisplayObjectContainer;
     import flash.errors.IllegalOperationError;
+    import flash.geom.Point;
     import flash.geom.Rectangle;
     import flash.ui.ContextMenu;
 
-    // FIXME: None of the DisplayObjectContainer methods actually work on
-    // the TextLine class in Ruffle, despite the methods working fine in FP-
-    // however, it's unlikely that SWFs will actually attempt to add children
-    // to a TextLine.
+    // NOTE: Ruffle renders the text of a line with internal text fields, which
+    // are children of the line alongside the graphics of any GraphicElements.
     [Ruffle(Abstract)]
     public final class TextLine extends DisplayObjectContainer {
         internal var _specifiedWidth:Number = 0.0;
         internal var _textBlock:TextBlock = null;
+        internal var _textBlockBeginIndex:int = 0;
         internal var _rawTextLength:int = 0;
         internal var _validity:String = "valid";
+        internal var _previousLine:TextLine = null;
+        internal var _nextLine:TextLine = null;
+
+        // These are set by the TextBlock when it lays out this line.
+        internal var _ascent:Number = 0.0;
+        internal var _descent:Number = 0.0;
+        internal var _textWidth:Number = 0.0;
+        internal var _unjustifiedTextWidth:Number = 0.0;
+        internal var _hasGraphicElement:Boolean = false;
+        internal var _hasTabs:Boolean = false;
+        internal var _atomBounds:Array = [];
+        internal var _atomBeginIndices:Array = [];
+        internal var _atomEndIndices:Array = [];
 
         public static const MAX_LINE_WIDTH:int = 1000000;
 
@@ -28,8 +39,7 @@ package flash.text.engine {
         }
 
         public function get textBlockBeginIndex():int {
-            stub_getter("flash.text.engine.TextLine", "textBlockBeginIndex");
-            return 0;
+            return this._textBlockBeginIndex;
         }
 
         public function get specifiedWidth():Number {
@@ -41,25 +51,26 @@ package flash.text.engine {
         }
 
         public function get ascent():Number {
-            stub_getter("flash.text.engine.TextLine", "ascent");
-            return 12.0;
+            return this._ascent;
         }
 
         public function get descent():Number {
-            stub_getter("flash.text.engine.TextLine", "descent");
-            return 3.0;
+            return this._descent;
         }
 
         public function get unjustifiedTextWidth():Number {
-            stub_getter("flash.text.engine.TextLine", "unjustifiedTextWidth");
-            return this._specifiedWidth;
+            return this._unjustifiedTextWidth;
+        }
+
+        public function get textWidth():Number {
+            return this._textWidth;
         }
 
-        public native function get textWidth():Number;
-        public native function get textHeight():Number;
+        public function get textHeight():Number {
+            return this._ascent + this._descent;
+        }
 
         public function get validity():String {
-            stub_getter("flash.text.engine.TextLine", "validity");
             return this._validity;
         }
 
@@ -69,46 +80,81 @@ package flash.text.engine {
         }
 
         public function get hasGraphicElement():Boolean {
-            stub_getter("flash.text.engine.TextLine", "hasGraphicElement");
-            return false;
+            return this._hasGraphicElement;
         }
 
         public function get atomCount():int {
-            stub_getter("flash.text.engine.TextLine", "atomCount");
-            return this._rawTextLength;
+            return this._atomBounds.length;
         }
 
         public function get nextLine():TextLine {
-            return null;
+            return this._nextLine;
         }
 
         public function get previousLine():TextLine {
-            return null;
+            return this._previousLine;
         }
 
+        // Baseline positions are relative to the roman baseline, which is the origin of the line.
         public function getBaselinePosition(baseline:String):Number {
-            stub_method("flash.text.engine.TextLine", "getBaselinePosition");
-            return 0.0;
+            switch (baseline) {
+                case TextBaseline.ROMAN:
+                    return 0.0;
+                case TextBaseline.ASCENT:
+                case TextBaseline.IDEOGRAPHIC_TOP:
+                    return -this._ascent;
+                case TextBaseline.DESCENT:
+                case TextBaseline.IDEOGRAPHIC_BOTTOM:
+                    return this._descent;
+                case TextBaseline.IDEOGRAPHIC_CENTER:
+                    return (this._descent - this._ascent) / 2;
+                default:
+                    throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
         }
 
         public function get hasTabs():Boolean {
-            stub_getter("flash.text.engine.TextLine", "hasTabs");
-            return false;
+            return this._hasTabs;
         }
 
         public function getAtomIndexAtPoint(stageX:Number, stageY:Number):int {
-            stub_method("flash.text.engine.TextLine", "getAtomIndexAtPoint");
+            var point:Point = this.globalToLocal(new Point(stageX, stageY));
+            for (var i:int = 0; i < this._atomBounds.length; i++) {
+                if (this._atomBounds[i].containsPoint(point)) {
+                    return i;
+                }
+            }
             return -1;
         }
 
         public function getAtomIndexAtCharIndex(charIndex:int):int {
-            stub_method("flash.text.engine.TextLine", "getAtomIndexAtCharIndex");
+            for (var i:int = 0; i < this._atomBeginIndices.length; i++) {
+                if (charIndex >= this._atomBeginIndices[i] && charIndex < this._atomEndIndices[i]) {
+                    return i;
+                }
+            }
             return -1;
         }
 
         public function getAtomBounds(index:int):Rectangle {
-            stub_method("flash.text.engine.TextLine", "getAtomBounds");
-            return new Rectangle(0, 0, 0, 0);
+            this.checkAtomIndex(index);
+            return this._atomBounds[index].clone();
+        }
+
+        public function getAtomTextBlockBeginIndex(index:int):int {
+            this.checkAtomIndex(index);
+            return this._atomBeginIndices[index];
+        }
+
+        public function getAtomTextBlockEndIndex(index:int):int {
+            this.checkAtomIndex(index);
+            return this._atomEndIndices[index];
+        }
+
+        private function checkAtomIndex(index:int):void {
+            if (index < 0 || index >= this._atomBounds.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
         }
 
         // This function does nothing in Flash Player 32
@@ -4,189 +4,553 @@ use crate::avm2::globals::flash::display::display_object::initialize_for_allocat
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
-use crate::avm2::Multiname;
-use crate::avm2_stub_method;
-use crate::display_object::{EditText, TDisplayObject};
-use crate::html::TextFormat;
-use crate::string::WStr;
+use crate::avm2::{ArrayObject, ArrayStorage, Multiname};
+use crate::display_object::{
+    DisplayObject, EditText, MovieClip, TDisplayObject, TDisplayObjectContainer,
+};
+use crate::font::FontType;
+use crate::html::{resolve_font, TextFormat};
+use crate::string::WString;
+use crate::tag_utils::SwfMovie;
+use crate::text_engine::{
+    BlockContent, BreakOpportunity, Justifier, LineCreationResult, LineItem, LineJustification,
+    LineLayout, RunContent, RunFormat, TabAlignment, TabStop,
+};
+use std::sync::Arc;
+use swf::Twips;
 
-pub fn create_text_line<'gc>(
+pub use crate::avm2::object::text_block_allocator;
+
+/// Implements `TextBlock.layoutLine`, which lays out a single line of the block.
+pub fn layout_line<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let namespaces = activation.avm2().namespaces;
-    avm2_stub_method!(activation, "flash.text.TextBlock", "createTextLine");
+    let text_line = args.try_get_object(activation, 0);
+    let begin_index = args.get_u32(activation, 1)? as usize;
+    let is_first_line = args.get_bool(2);
+    let width = args.get_f64(activation, 3)?;
+    let line_offset = args.get_f64(activation, 4)?;
+    let fit_something = args.get_bool(5);
 
-    let previous_text_line = args.try_get_object(activation, 0);
-    let width = args.get_f64(activation, 1)?;
+    let Some(content) = this.get_public_property("content", activation)?.as_object() else {
+        return Ok(Value::Null);
+    };
 
-    let content = this.get_public_property("content", activation)?;
+    // FP returns a null TextLine when the text of the content is null.
+    if matches!(
+        content.get_public_property("text", activation)?,
+        Value::Null | Value::Undefined
+    ) {
+        return Ok(Value::Null);
+    }
 
-    let content = if matches!(content, Value::Null) {
+    let Some(text_block) = this.as_text_block() else {
         return Ok(Value::Null);
-    } else {
-        content.as_object().unwrap()
     };
 
-    let text = match previous_text_line {
-        Some(_) => {
-            // Some SWFs rely on eventually getting `null` from createLineText.
-            // TODO: Support multiple lines
-            this.set_property(
-                &Multiname::new(
-                    namespaces.flash_text_engine_internal,
-                    "_textLineCreationResult",
-                ),
-                "complete".into(),
-                activation,
-            )?;
+    // Flattening the content is the slow part, so it's only done again once the content changes.
+    let mut block = match text_block.content() {
+        Some(block) => block,
+        None => {
+            let mut block = BlockContent::default();
+            flatten_content(activation, &mut block, content, None)?;
+            text_block.set_content(activation.gc(), Some(block.clone()));
+            block
+        }
+    };
+    block.set_settings(tab_stops(activation, this)?, justifier(activation, this)?);
+
+    let layout = if !is_first_line && begin_index >= block.text().len() {
+        Err(LineCreationResult::Complete)
+    } else {
+        block.layout_line(begin_index, width, line_offset, fit_something)
+    };
+    let layout = match layout {
+        Ok(layout) => layout,
+        Err(result) => {
+            set_internal_property(activation, this, "_textLineCreationResult", result.as_str())?;
             return Ok(Value::Null);
         }
-        // Get the content element's text property.
-        // TODO: GraphicElement?
-        None => {
-            let txt = content
-                .get_public_property("text", activation)
-                .unwrap_or_else(|_| activation.strings().empty().into());
-
-            if matches!(txt, Value::Null) {
-                // FP returns a null TextLine when `o` is null- note that
-                // `o` is already coerced to a String because of the AS bindings.
-                return Ok(Value::Null);
-            } else {
-                txt.coerce_to_string(activation)
-                    .expect("Guaranteed by AS bindings")
+    };
+
+    let text_line = match text_line {
+        Some(text_line) => {
+            if let Some(mut container) = text_line
+                .as_display_object()
+                .and_then(|display_object| display_object.as_container())
+            {
+                container.remove_range(activation.context, ..);
             }
+            text_line
+        }
+        None => {
+            let class = activation.avm2().classes().textline;
+            let movie = line_movie(activation);
+            let display_object = MovieClip::new(movie, activation.context.gc_context);
+            let instance = initialize_for_allocator(activation, display_object.into(), class)?;
+            class.call_init(instance.into(), &[], activation)?;
+            instance
         }
     };
 
-    let class = activation.avm2().classes().textline;
-    let movie = activation.caller_movie_or_root();
-
-    // FIXME: TextLine should be its own DisplayObject
-    let display_object: EditText =
-        EditText::new_tlf(activation.context, movie, 0.0, 0.0, width, 15.0);
+    if let Some(display_object) = text_line.as_display_object() {
+        populate_line(activation, display_object, &block, &layout);
+    }
+    store_line_metrics(activation, text_line, &layout)?;
 
-    display_object.set_text(text.as_wstr(), activation.context);
+    set_internal_property(
+        activation,
+        this,
+        "_textLineCreationResult",
+        layout.result.as_str(),
+    )?;
 
-    // FIXME: This needs to use `intrinsic_bounds` to measure the width
-    // of the provided text, and set the width of the EditText to that.
-    // Some games depend on this (e.g. Realm Grinder).
+    Ok(text_line.into())
+}
 
-    let element_format = content
-        .get_public_property("elementFormat", activation)?
-        .as_object();
+/// The movie that the lines of a block, and the fonts they use, belong to.
+///
+/// Lines are laid out by `TextBlock.createLine`, which is part of playerglobal,
+/// so the caller movie would be playerglobal itself rather than the movie using the block.
+fn line_movie(activation: &Activation<'_, '_>) -> Arc<SwfMovie> {
+    activation.context.swf.clone()
+}
 
-    apply_format(activation, display_object, text.as_wstr(), element_format)?;
+/// Implements `TextBlock.invalidateContent`, called whenever the content of the block changes.
+pub fn invalidate_content<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(text_block) = this.as_text_block() {
+        text_block.set_content(activation.gc(), None);
+    }
 
-    let instance = initialize_for_allocator(activation, display_object.into(), class)?;
-    class.call_init(instance.into(), &[], activation)?;
+    Ok(Value::Undefined)
+}
 
-    instance.set_property(
-        &Multiname::new(namespaces.flash_text_engine_internal, "_textBlock"),
-        this.into(),
+fn set_internal_property<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+    value: impl Into<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let namespaces = activation.avm2().namespaces;
+    object.set_property(
+        &Multiname::new(namespaces.flash_text_engine_internal, name),
+        value.into(),
         activation,
-    )?;
+    )
+}
+
+/// Add the text and graphics of a laid out line to its display object.
+fn populate_line<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    text_line: DisplayObject<'gc>,
+    block: &BlockContent<'gc>,
+    layout: &LineLayout,
+) {
+    let Some(mut container) = text_line.as_container() else {
+        return;
+    };
+    let movie = line_movie(activation);
+
+    for item in &layout.items {
+        let child: DisplayObject<'gc> = match *item {
+            LineItem::Text {
+                run,
+                begin_index,
+                end_index,
+                x,
+                letter_spacing,
+            } => {
+                let format = &block.runs()[run].format;
+                let text = &block.text()[begin_index..end_index];
+                let text_format = TextFormat {
+                    letter_spacing: Some(letter_spacing),
+                    ..format.text_format.clone()
+                };
+
+                let edit_text =
+                    EditText::new_tlf(activation.context, movie.clone(), 0.0, 0.0, 0.0, 0.0);
+                edit_text.set_is_device_font(activation.context, format.is_device_font);
+                edit_text.set_text(text, activation.context);
+                edit_text.set_text_format(0, text.len(), text_format.clone(), activation.context);
+                edit_text.set_new_text_format(text_format, activation.context);
+
+                let (width, height) = edit_text.measure_text(activation.context);
+                edit_text.set_width(
+                    activation.context,
+                    (width + EditText::GUTTER * 2).to_pixels(),
+                );
+                edit_text.set_height(
+                    activation.context,
+                    (height + EditText::GUTTER * 2).to_pixels(),
+                );
+
+                // Text fields are positioned by their top left corner, while the
+                // text of a line sits on its baseline.
+                let ascent = edit_text
+                    .line_metrics(0)
+                    .map_or(Twips::ZERO, |metrics| metrics.ascent);
+                edit_text.set_x(
+                    activation.context.gc_context,
+                    Twips::from_pixels(x) - EditText::GUTTER,
+                );
+                edit_text.set_y(
+                    activation.context.gc_context,
+                    Twips::from_pixels(format.baseline_shift) - EditText::GUTTER - ascent,
+                );
+                edit_text.set_alpha(activation.context.gc_context, format.alpha);
+                edit_text.into()
+            }
+            LineItem::Graphic { run, x } => {
+                let run = &block.runs()[run];
+                let RunContent::Graphic {
+                    graphic: Some(graphic),
+                    height,
+                    ..
+                } = &run.content
+                else {
+                    continue;
+                };
+
+                // Graphics sit on the baseline.
+                graphic.set_x(activation.context.gc_context, Twips::from_pixels(x));
+                graphic.set_y(
+                    activation.context.gc_context,
+                    Twips::from_pixels(run.format.baseline_shift - *height),
+                );
+                *graphic
+            }
+        };
+
+        child.set_placed_by_script(activation.context.gc_context, true);
+        let index = container.num_children();
+        container.insert_at_index(activation.context, child, index);
+    }
+}
 
-    instance.set_property(
-        &Multiname::new(namespaces.flash_text_engine_internal, "_specifiedWidth"),
-        args.get_value(1),
+/// Store the metrics of a laid out line on its `TextLine` object.
+fn store_line_metrics<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    text_line: Object<'gc>,
+    layout: &LineLayout,
+) -> Result<(), Error<'gc>> {
+    let mut atom_bounds = Vec::with_capacity(layout.atoms.len());
+    for atom in &layout.atoms {
+        let bounds = activation.avm2().classes().rectangle.construct(
+            activation,
+            &[
+                atom.x.into(),
+                (-atom.ascent).into(),
+                atom.width.into(),
+                (atom.ascent + atom.descent).into(),
+            ],
+        )?;
+        atom_bounds.push(Value::from(bounds));
+    }
+    let atom_bounds = ArrayObject::from_storage(activation, ArrayStorage::from_iter(atom_bounds))?;
+    let atom_begin_indices = ArrayObject::from_storage(
         activation,
+        layout
+            .atoms
+            .iter()
+            .map(|atom| Value::from(atom.begin_index as u32))
+            .collect(),
     )?;
-
-    instance.set_property(
-        &Multiname::new(namespaces.flash_text_engine_internal, "_rawTextLength"),
-        text.len().into(),
+    let atom_end_indices = ArrayObject::from_storage(
         activation,
+        layout
+            .atoms
+            .iter()
+            .map(|atom| Value::from(atom.end_index as u32))
+            .collect(),
     )?;
 
-    this.set_property(
-        &Multiname::new(
-            namespaces.flash_text_engine_internal,
-            "_textLineCreationResult",
+    let values: [(&'static str, Value<'gc>); 11] = [
+        ("_textBlockBeginIndex", (layout.begin_index as u32).into()),
+        (
+            "_rawTextLength",
+            ((layout.end_index - layout.begin_index) as u32).into(),
         ),
-        "success".into(),
-        activation,
-    )?;
+        ("_ascent", layout.ascent.into()),
+        ("_descent", layout.descent.into()),
+        ("_textWidth", layout.text_width.into()),
+        (
+            "_unjustifiedTextWidth",
+            layout.unjustified_text_width.into(),
+        ),
+        ("_hasGraphicElement", layout.has_graphic_element.into()),
+        ("_hasTabs", layout.has_tabs.into()),
+        ("_atomBounds", atom_bounds.into()),
+        ("_atomBeginIndices", atom_begin_indices.into()),
+        ("_atomEndIndices", atom_end_indices.into()),
+    ];
+    for (name, value) in values {
+        set_internal_property(activation, text_line, name, value)?;
+    }
 
-    this.set_property(
-        &Multiname::new(namespaces.flash_text_engine_internal, "_firstLine"),
-        instance.into(),
-        activation,
-    )?;
+    Ok(())
+}
+
+/// Flatten a content element into runs of the block, with groups
+/// contributing the content of each of their elements in turn.
+fn flatten_content<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    block: &mut BlockContent<'gc>,
+    element: Object<'gc>,
+    group_format: Option<Object<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let group_element = activation.avm2().classes().groupelement;
+    let graphic_element = activation.avm2().classes().graphicelement;
+    let element_format = element
+        .get_public_property("elementFormat", activation)?
+        .as_object()
+        .or(group_format);
+
+    if element.is_of_type(group_element.inner_class_definition()) {
+        let count = element
+            .get_public_property("elementCount", activation)?
+            .coerce_to_u32(activation)?;
+        for index in 0..count {
+            let child =
+                element.call_public_property("getElementAt", &[index.into()], activation)?;
+            if let Some(child) = child.as_object() {
+                flatten_content(activation, block, child, element_format)?;
+            }
+        }
+        return Ok(());
+    }
 
-    Ok(instance.into())
+    let format = run_format(activation, element_format)?;
+    if element.is_of_type(graphic_element.inner_class_definition()) {
+        let graphic = element
+            .get_public_property("graphic", activation)?
+            .as_object()
+            .and_then(|graphic| graphic.as_display_object());
+        let width = element
+            .get_public_property("elementWidth", activation)?
+            .coerce_to_number(activation)?;
+        let height = element
+            .get_public_property("elementHeight", activation)?
+            .coerce_to_number(activation)?;
+        block.push_graphic(graphic, width, height, format);
+    } else {
+        let text = match element.get_public_property("text", activation)? {
+            Value::Null | Value::Undefined => activation.strings().empty(),
+            text => text.coerce_to_string(activation)?,
+        };
+        block.push_text(&text, format);
+    }
+
+    Ok(())
 }
 
-fn apply_format<'gc>(
+/// Read the formatting of a run from an `ElementFormat`, or its defaults if there is none.
+fn run_format<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    display_object: EditText<'gc>,
-    text: &WStr,
     element_format: Option<Object<'gc>>,
-) -> Result<(), Error<'gc>> {
+) -> Result<RunFormat<'gc>, Error<'gc>> {
+    let mut font_name = WString::from_utf8("_serif");
+    let mut bold = false;
+    let mut italic = false;
+    let mut is_device_font = true;
+    let mut size = 12.0;
+    let mut color = 0;
+    let mut alpha = 1.0;
+    let mut tracking_left = 0.0;
+    let mut tracking_right = 0.0;
+    let mut baseline_shift = 0.0;
+    let mut kerning = true;
+    let mut break_opportunity = BreakOpportunity::Auto;
+
     if let Some(element_format) = element_format {
-        // TODO: Support more ElementFormat properties
-        let color = element_format
+        size = get_number(activation, element_format, "fontSize")?;
+        alpha = get_number(activation, element_format, "alpha")?;
+        tracking_left = get_number(activation, element_format, "trackingLeft")?;
+        tracking_right = get_number(activation, element_format, "trackingRight")?;
+        baseline_shift = get_number(activation, element_format, "baselineShift")?;
+        color = element_format
             .get_public_property("color", activation)?
             .coerce_to_u32(activation)?;
-        let size = element_format
-            .get_public_property("fontSize", activation)?
-            .coerce_to_number(activation)?;
+        kerning = &element_format
+            .get_public_property("kerning", activation)?
+            .coerce_to_string(activation)?
+            != b"off";
 
-        let (font, bold, italic, is_device_font) = if let Value::Object(font_description) =
-            element_format.get_public_property("fontDescription", activation)?
-        {
-            (
-                Some(
-                    font_description
-                        .get_public_property("fontName", activation)?
-                        .coerce_to_string(activation)?
-                        .as_wstr()
-                        .into(),
-                ),
-                Some(
-                    &font_description
-                        .get_public_property("fontWeight", activation)?
-                        .coerce_to_string(activation)?
-                        == b"bold",
-                ),
-                Some(
-                    &font_description
-                        .get_public_property("fontPosture", activation)?
-                        .coerce_to_string(activation)?
-                        == b"italic",
-                ),
-                &font_description
-                    .get_public_property("fontLookup", activation)?
-                    .coerce_to_string(activation)?
-                    == b"device",
-            )
+        let opportunity = element_format
+            .get_public_property("breakOpportunity", activation)?
+            .coerce_to_string(activation)?;
+        break_opportunity = if &opportunity == b"any" {
+            BreakOpportunity::Any
+        } else if &opportunity == b"none" {
+            BreakOpportunity::None
+        } else if &opportunity == b"all" {
+            BreakOpportunity::All
         } else {
-            (None, None, None, true)
+            BreakOpportunity::Auto
         };
 
-        let format = TextFormat {
-            color: Some(swf::Color::from_rgb(color, 0xFF)),
+        if let Some(font_description) = element_format
+            .get_public_property("fontDescription", activation)?
+            .as_object()
+        {
+            font_name = font_description
+                .get_public_property("fontName", activation)?
+                .coerce_to_string(activation)?
+                .as_wstr()
+                .into();
+            bold = &font_description
+                .get_public_property("fontWeight", activation)?
+                .coerce_to_string(activation)?
+                == b"bold";
+            italic = &font_description
+                .get_public_property("fontPosture", activation)?
+                .coerce_to_string(activation)?
+                == b"italic";
+            is_device_font = &font_description
+                .get_public_property("fontLookup", activation)?
+                .coerce_to_string(activation)?
+                == b"device";
+        }
+    }
+
+    // This matches the font a TLF text field would pick to render the run.
+    let font_type = if is_device_font {
+        FontType::Device
+    } else {
+        FontType::EmbeddedCFF
+    };
+    let movie = line_movie(activation);
+    let font = resolve_font(
+        activation.context,
+        &movie,
+        font_type,
+        &font_name.to_utf8_lossy(),
+        bold,
+        italic,
+    );
+
+    Ok(RunFormat {
+        font,
+        text_format: TextFormat {
+            font: Some(font_name),
             size: Some(size),
-            font,
-            bold,
-            italic,
+            color: Some(swf::Color::from_rgb(color, 0xFF)),
+            bold: Some(bold),
+            italic: Some(italic),
+            kerning: Some(kerning),
             ..TextFormat::default()
-        };
+        },
+        is_device_font,
+        size,
+        alpha,
+        tracking_left,
+        tracking_right,
+        baseline_shift,
+        kerning,
+        break_opportunity,
+    })
+}
 
-        display_object.set_is_device_font(activation.context, is_device_font);
-        display_object.set_text_format(0, text.len(), format.clone(), activation.context);
-        display_object.set_new_text_format(format, activation.context);
-    } else {
-        display_object.set_is_device_font(activation.context, true);
+fn get_number<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    name: &'static str,
+) -> Result<f64, Error<'gc>> {
+    object
+        .get_public_property(name, activation)?
+        .coerce_to_number(activation)
+}
+
+fn tab_stops<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    text_block: Object<'gc>,
+) -> Result<Vec<TabStop>, Error<'gc>> {
+    let Some(tab_stops) = text_block
+        .get_public_property("tabStops", activation)?
+        .as_object()
+    else {
+        return Ok(vec![]);
+    };
+    let Some(tab_stops) = tab_stops
+        .as_vector_storage()
+        .map(|storage| storage.iter().collect::<Vec<_>>())
+    else {
+        return Ok(vec![]);
+    };
+
+    let mut result = vec![];
+    for tab_stop in tab_stops.into_iter().filter_map(|value| value.as_object()) {
+        let alignment = tab_stop
+            .get_public_property("alignment", activation)?
+            .coerce_to_string(activation)?;
+        let alignment = if &alignment == b"center" {
+            TabAlignment::Center
+        } else if &alignment == b"end" {
+            TabAlignment::End
+        } else if &alignment == b"decimal" {
+            TabAlignment::Decimal
+        } else {
+            TabAlignment::Start
+        };
+        let position = tab_stop
+            .get_public_property("position", activation)?
+            .coerce_to_number(activation)?;
+        let decimal_alignment_token = tab_stop
+            .get_public_property("decimalAlignmentToken", activation)?
+            .coerce_to_string(activation)?
+            .as_wstr()
+            .into();
+        result.push(TabStop {
+            alignment,
+            position,
+            decimal_alignment_token,
+        });
     }
 
-    display_object.set_word_wrap(true, activation.context);
+    Ok(result)
+}
+
+fn justifier<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    text_block: Object<'gc>,
+) -> Result<Option<Justifier>, Error<'gc>> {
+    let Some(justifier) = text_block
+        .get_public_property("textJustifier", activation)?
+        .as_object()
+    else {
+        return Ok(None);
+    };
 
-    let measured_text = display_object.measure_text(activation.context);
+    let line_justification = justifier
+        .get_public_property("lineJustification", activation)?
+        .coerce_to_string(activation)?;
+    let line_justification = if &line_justification == b"allButLast" {
+        LineJustification::AllButLast
+    } else if &line_justification == b"allButMandatoryBreak" {
+        LineJustification::AllButMandatoryBreak
+    } else if &line_justification == b"allIncludingLast" {
+        LineJustification::AllIncludingLast
+    } else {
+        LineJustification::Unjustified
+    };
 
-    display_object.set_height(activation.context, measured_text.1.to_pixels());
+    // Unlike a SpaceJustifier, an EastAsianJustifier always spreads lines between characters.
+    let space_justifier = activation.avm2().classes().spacejustifier;
+    let letter_spacing = if justifier.is_of_type(space_justifier.inner_class_definition()) {
+        justifier
+            .get_public_property("letterSpacing", activation)?
+            .coerce_to_boolean()
+    } else {
+        true
+    };
 
-    Ok(())
+    Ok(Some(Justifier {
+        line_justification,
+        letter_spacing,
+    }))
 }
@@ -659,6 +659,39 @@ pub fn set_type<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_use_rich_text_clipboard<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    else {
+        return Ok(Value::Undefined);
+    };
+
+    Ok(this.use_rich_text_clipboard().into())
+}
+
+pub fn set_use_rich_text_clipboard<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    else {
+        return Ok(Value::Undefined);
+    };
+
+    let value = args.get_bool(0);
+    this.set_use_rich_text_clipboard(activation.context, value);
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_word_wrap<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -0,0 +1,183 @@
+//! `flash.text.TextSnapshot` native methods
+
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::globals::slots::*;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, ArrayObject, Error, Object, TObject, Value};
+use crate::display_object::TDisplayObject;
+use crate::string::AvmString;
+use crate::text_snapshot::TextSnapshot;
+use swf::{Color, Point, Twips};
+
+/// Takes a snapshot of the static text in the container of this `TextSnapshot`.
+fn snapshot<'gc>(activation: &mut Activation<'_, 'gc>, this: Object<'gc>) -> TextSnapshot<'gc> {
+    this.get_slot(FLASH_TEXT_TEXT_SNAPSHOT__CONTAINER_SLOT)
+        .as_object()
+        .and_then(|object| object.as_display_object())
+        .and_then(|display_object| display_object.as_container())
+        .map(|container| TextSnapshot::new(container, activation.context.library))
+        .unwrap_or_default()
+}
+
+/// Implements `TextSnapshot.charCount`
+pub fn get_char_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(snapshot(activation, this).char_count().into())
+}
+
+/// Implements `TextSnapshot.findText`
+pub fn find_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin_index = args.get_i32(activation, 0)?.max(0) as usize;
+    let text_to_find = args.get_string(activation, 1)?.to_string();
+    let case_sensitive = args.get_bool(2);
+
+    let snapshot = snapshot(activation, this);
+    Ok(snapshot
+        .find_text(begin_index, &text_to_find, case_sensitive)
+        .map_or(-1, |index| index as i32)
+        .into())
+}
+
+/// Implements `TextSnapshot.getSelected`
+pub fn get_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin_index = args.get_i32(activation, 0)?;
+    let end_index = args.get_i32(activation, 1)?;
+
+    let snapshot = snapshot(activation, this);
+    Ok(snapshot
+        .is_selected(snapshot.range(begin_index, end_index))
+        .into())
+}
+
+/// Implements `TextSnapshot.getSelectedText`
+pub fn get_selected_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let include_line_endings = args.get_bool(0);
+
+    let text = snapshot(activation, this).selected_text(include_line_endings);
+    Ok(AvmString::new_utf8(activation.context.gc_context, text).into())
+}
+
+/// Implements `TextSnapshot.getText`
+pub fn get_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin_index = args.get_i32(activation, 0)?;
+    let end_index = args.get_i32(activation, 1)?;
+    let include_line_endings = args.get_bool(2);
+
+    let snapshot = snapshot(activation, this);
+    let text = snapshot.get_text(snapshot.range(begin_index, end_index), include_line_endings);
+    Ok(AvmString::new_utf8(activation.context.gc_context, text).into())
+}
+
+/// Implements `TextSnapshot.getTextRunInfo`
+pub fn get_text_run_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin_index = args.get_i32(activation, 0)?;
+    let end_index = args.get_i32(activation, 1)?;
+
+    let snapshot = snapshot(activation, this);
+    let mut infos = Vec::new();
+    for (index, selected, char) in snapshot.run_info(snapshot.range(begin_index, end_index)) {
+        let info = activation
+            .avm2()
+            .classes()
+            .object
+            .construct(activation, &[])?;
+        let font =
+            AvmString::new_utf8(activation.context.gc_context, char.font.descriptor().name());
+        info.set_public_property("indexInRun", index.into(), activation)?;
+        info.set_public_property("selected", selected.into(), activation)?;
+        info.set_public_property("font", font.into(), activation)?;
+        info.set_public_property("color", char.color.to_rgb().into(), activation)?;
+        info.set_public_property("height", char.height.to_pixels().into(), activation)?;
+        info.set_public_property("matrix_a", char.matrix.a.into(), activation)?;
+        info.set_public_property("matrix_b", char.matrix.b.into(), activation)?;
+        info.set_public_property("matrix_c", char.matrix.c.into(), activation)?;
+        info.set_public_property("matrix_d", char.matrix.d.into(), activation)?;
+        info.set_public_property("matrix_tx", char.matrix.tx.to_pixels().into(), activation)?;
+        info.set_public_property("matrix_ty", char.matrix.ty.to_pixels().into(), activation)?;
+        for (name, corner) in ["corner00", "corner01", "corner10", "corner11"]
+            .into_iter()
+            .zip(char.corners)
+        {
+            let x = AvmString::new_utf8(activation.context.gc_context, format!("{name}x"));
+            let y = AvmString::new_utf8(activation.context.gc_context, format!("{name}y"));
+            info.set_public_property(x, corner.x.to_pixels().into(), activation)?;
+            info.set_public_property(y, corner.y.to_pixels().into(), activation)?;
+        }
+        infos.push(Some(info.into()));
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(infos))?.into())
+}
+
+/// Implements `TextSnapshot.hitTestTextNearPos`
+pub fn hit_test_text_near_pos<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let point = Point::new(
+        Twips::from_pixels(args.get_f64(activation, 0)?),
+        Twips::from_pixels(args.get_f64(activation, 1)?),
+    );
+    let max_distance = Twips::from_pixels(args.get_f64(activation, 2)?);
+
+    let snapshot = snapshot(activation, this);
+    Ok(snapshot
+        .hit_test_near(point, max_distance)
+        .map_or(-1.0, |index| index as f64)
+        .into())
+}
+
+/// Implements `TextSnapshot.setSelectColor`
+pub fn set_select_color<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let color = Color::from_rgb(args.get_u32(activation, 0)?, 255);
+
+    snapshot(activation, this).set_select_color(activation.context.gc_context, color);
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextSnapshot.setSelected`
+pub fn set_selected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let begin_index = args.get_i32(activation, 0)?;
+    let end_index = args.get_i32(activation, 1)?;
+    let select = args.get_bool(2);
+
+    let snapshot = snapshot(activation, this);
+    snapshot.set_selected(
+        activation.context.gc_context,
+        snapshot.range(begin_index, end_index),
+        select,
+    );
+    Ok(Value::Undefined)
+}
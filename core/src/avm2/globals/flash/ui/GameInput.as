@@ -4,15 +4,11 @@ package flash.ui {
     [API("688")]
     public final class GameInput extends EventDispatcher {
         public static function get isSupported():Boolean {
-            return false;
+            return true;
         }
 
-        public static function get numDevices():int {
-            return 0;
-        }
+        public static native function get numDevices():int;
 
-        public static function getDeviceAt(index:int):GameInputDevice {
-            throw new RangeError("Error #1506: The specified range is invalid.", 1506);
-        }
+        public static native function getDeviceAt(index:int):GameInputDevice;
     }
-}
\ No newline at end of file
+}
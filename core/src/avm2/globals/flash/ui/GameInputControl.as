@@ -3,8 +3,29 @@ package flash.ui {
 
     [API("688")]
     public dynamic class GameInputControl extends EventDispatcher {
+        [Ruffle(InternalSlot)]
+        private var _device:GameInputDevice;
+
+        [Ruffle(InternalSlot)]
+        private var _index:int;
+
+        [Ruffle(InternalSlot)]
+        private var _id:String;
+
         public function GameInputControl() {
             throw new ArgumentError("Error #2012: GameInputControl$ class cannot be instantiated.", 2012)
         }
+
+        public function get device():GameInputDevice {
+            return this._device;
+        }
+
+        public function get id():String {
+            return this._id;
+        }
+
+        public native function get minValue():Number;
+        public native function get maxValue():Number;
+        public native function get value():Number;
     }
-}
\ No newline at end of file
+}
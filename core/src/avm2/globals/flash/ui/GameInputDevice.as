@@ -1,4 +1,8 @@
 package flash.ui {
+    import __ruffle__.stub_method;
+
+    import flash.utils.ByteArray;
+
     // The AS3 docs say this is only available in AIR 3.7.
     // That was determined to be a lie.
     [API("688")]
@@ -7,5 +11,64 @@ package flash.ui {
         // control values. If `startCachingSamples` returns samples that
         // require more memory than you specify, it throws a memory error.
         public static const MAX_BUFFER_SIZE:int = 32000;
+
+        [Ruffle(InternalSlot)]
+        private var _id:int;
+
+        [Ruffle(InternalSlot)]
+        private var _controls:Array;
+
+        [Ruffle(InternalSlot)]
+        private var _enabled:Boolean = false;
+
+        private var _sampleInterval:int = 0;
+
+        public function get enabled():Boolean {
+            return this._enabled;
+        }
+
+        public function set enabled(value:Boolean):void {
+            this._enabled = value;
+        }
+
+        public function get id():String {
+            return "ruffle-gamepad-" + this._id;
+        }
+
+        public function get name():String {
+            return "Gamepad";
+        }
+
+        public function get numControls():int {
+            return this._controls.length;
+        }
+
+        public function getControlAt(i:int):GameInputControl {
+            if (i < 0 || i >= this._controls.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            return this._controls[i];
+        }
+
+        public function get sampleInterval():int {
+            return this._sampleInterval;
+        }
+
+        public function set sampleInterval(value:int):void {
+            this._sampleInterval = value;
+        }
+
+        public function startCachingSamples(numSamples:int, controls:Vector.<String>):void {
+            stub_method("flash.ui.GameInputDevice", "startCachingSamples");
+        }
+
+        public function stopCachingSamples():void {
+            stub_method("flash.ui.GameInputDevice", "stopCachingSamples");
+        }
+
+        public function getCachedSamples(data:ByteArray, append:Boolean = false):int {
+            stub_method("flash.ui.GameInputDevice", "getCachedSamples");
+            return 0;
+        }
     }
 }
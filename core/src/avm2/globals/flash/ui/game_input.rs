@@ -0,0 +1,36 @@
+//! `flash.ui.GameInput` native methods
+
+use crate::avm2::error::range_error;
+use crate::avm2::game_input::device_object;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Implements `GameInput.numDevices`
+pub fn get_num_devices<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((activation.context.input.gamepads().count() as i32).into())
+}
+
+/// Implements `GameInput.getDeviceAt`
+pub fn get_device_at<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let gamepad = usize::try_from(index)
+        .ok()
+        .and_then(|index| activation.context.input.gamepads().nth(index));
+
+    match gamepad {
+        Some(gamepad) => Ok(device_object(activation, gamepad)?.into()),
+        None => Err(Error::AvmError(range_error(
+            activation,
+            "Error #1506: The specified range is invalid.",
+            1506,
+        )?)),
+    }
+}
@@ -0,0 +1,41 @@
+//! `flash.ui.GameInputControl` native methods
+
+use crate::avm2::game_input::control_of;
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Implements `GameInputControl.minValue`
+pub fn get_min_value<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(control_of(this)
+        .map_or(0.0, |(_, control)| control.min_value())
+        .into())
+}
+
+/// Implements `GameInputControl.maxValue`
+pub fn get_max_value<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(control_of(this)
+        .map_or(0.0, |(_, control)| control.max_value())
+        .into())
+}
+
+/// Implements `GameInputControl.value`
+pub fn get_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = control_of(this).map_or(0.0, |(gamepad, control)| {
+        activation
+            .context
+            .input
+            .gamepad_control_value(gamepad, control)
+    });
+    Ok(value.into())
+}
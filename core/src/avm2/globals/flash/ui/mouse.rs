@@ -1,9 +1,14 @@
 //! `flash.ui.Mouse` builtin
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::Object;
+use crate::avm2::error::{make_error_2004, make_error_2008, Error2004Type};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::backend::ui::{CustomMouseCursor, MouseCursor};
+use crate::context::UpdateContext;
+use crate::string::AvmString;
 
 pub fn hide<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -22,3 +27,162 @@ pub fn show<'gc>(
     activation.context.ui.set_mouse_visible(true);
     Ok(Value::Undefined)
 }
+
+/// Implements `Mouse.supportsNativeCursor`'s getter
+pub fn get_supports_native_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.supports_custom_mouse_cursors().into())
+}
+
+/// Implements `Mouse.cursor`'s getter
+pub fn get_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mouse_data = &activation.context.mouse_data;
+    let name = match mouse_data.cursor {
+        None => "auto",
+        Some(MouseCursor::Arrow) => "arrow",
+        Some(MouseCursor::Hand) => "button",
+        Some(MouseCursor::Grab) => "hand",
+        Some(MouseCursor::IBeam) => "ibeam",
+        Some(MouseCursor::Custom(id)) => {
+            let name = mouse_data
+                .custom_cursors
+                .iter()
+                .find(|(_, custom_id)| **custom_id == id)
+                .map(|(name, _)| name.as_str())
+                .unwrap_or("auto");
+            return Ok(AvmString::new_utf8(activation.context.gc_context, name).into());
+        }
+    };
+
+    Ok(name.into())
+}
+
+/// Implements `Mouse.cursor`'s setter
+pub fn set_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string_non_null(activation, 0, "cursor")?;
+
+    let cursor = match &*name.to_utf8_lossy() {
+        "auto" => None,
+        "arrow" => Some(MouseCursor::Arrow),
+        "button" => Some(MouseCursor::Hand),
+        "hand" => Some(MouseCursor::Grab),
+        "ibeam" => Some(MouseCursor::IBeam),
+        name => match activation.context.mouse_data.custom_cursors.get(name) {
+            Some(id) => Some(MouseCursor::Custom(*id)),
+            None => return Err(make_error_2008(activation, "cursor")),
+        },
+    };
+    // The player shows the new cursor on its next tick.
+    activation.context.mouse_data.cursor = cursor;
+
+    Ok(Value::Undefined)
+}
+
+/// The largest width and height of a cursor registered with `Mouse.registerCursor`.
+const MAX_CURSOR_SIZE: u32 = 32;
+
+/// Implements `Mouse.registerCursor`
+pub fn register_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string_non_null(activation, 0, "name")?;
+    let cursor = args.get_object(activation, 1, "cursor")?;
+
+    let data = cursor
+        .get_public_property("data", activation)?
+        .as_object()
+        .and_then(|data| {
+            data.as_vector_storage()
+                .map(|data| data.iter().collect::<Vec<_>>())
+        })
+        .unwrap_or_default();
+    let frame_rate = cursor
+        .get_public_property("frameRate", activation)?
+        .coerce_to_number(activation)?;
+    let hot_spot = cursor.get_public_property("hotSpot", activation)?;
+    let hot_spot = match hot_spot.as_object() {
+        Some(point) => (
+            point
+                .get_public_property("x", activation)?
+                .coerce_to_u32(activation)?,
+            point
+                .get_public_property("y", activation)?
+                .coerce_to_u32(activation)?,
+        ),
+        None => (0, 0),
+    };
+
+    // Every frame of a cursor has the size of the first one.
+    let mut size = None;
+    let mut frames = Vec::with_capacity(data.len());
+    for bitmap_data in data {
+        let Some(bitmap_data) = bitmap_data.as_object().and_then(|o| o.as_bitmap_data()) else {
+            return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+        };
+        let (width, height) = *size.get_or_insert((bitmap_data.width(), bitmap_data.height()));
+        if bitmap_data.width() != width
+            || bitmap_data.height() != height
+            || width > MAX_CURSOR_SIZE
+            || height > MAX_CURSOR_SIZE
+        {
+            return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+        }
+
+        let pixels = bitmap_data
+            .sync(activation.context.renderer)
+            .read()
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let pixel = pixel.to_un_multiplied_alpha();
+                [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+            })
+            .collect();
+        frames.push(pixels);
+    }
+    let Some((width, height)) = size else {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    };
+    if hot_spot.0 >= width || hot_spot.1 >= height {
+        return Err(make_error_2004(activation, Error2004Type::ArgumentError));
+    }
+
+    let cursor = CustomMouseCursor {
+        width,
+        height,
+        frames,
+        hot_spot,
+        frame_rate: if frame_rate > 0.0 { frame_rate } else { 0.0 },
+    };
+    let UpdateContext { mouse_data, ui, .. } = activation.context;
+    mouse_data.register_cursor(*ui, name.to_utf8_lossy().into_owned(), cursor);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.unregisterCursor`
+pub fn unregister_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string_non_null(activation, 0, "name")?;
+
+    let UpdateContext { mouse_data, ui, .. } = activation.context;
+    mouse_data.unregister_cursor(*ui, &name.to_utf8_lossy());
+
+    Ok(Value::Undefined)
+}
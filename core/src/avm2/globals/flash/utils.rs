@@ -49,10 +49,12 @@ pub fn set_interval<'gc>(
         .get(1)
         .expect("setInterval: not enough arguments")
         .coerce_to_number(activation)?;
+    let movie = activation.caller_movie_or_root();
     Ok(Value::Integer(activation.context.timers.add_timer(
         callback,
         interval as i32,
         false,
+        movie,
     )))
 }
 
@@ -92,10 +94,12 @@ pub fn set_timeout<'gc>(
         .get(1)
         .expect("setTimeout: not enough arguments")
         .coerce_to_number(activation)?;
+    let movie = activation.caller_movie_or_root();
     Ok(Value::Integer(activation.context.timers.add_timer(
         callback,
         interval as i32,
         true,
+        movie,
     )))
 }
 
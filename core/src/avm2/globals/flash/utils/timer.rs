@@ -6,6 +6,7 @@ use crate::avm2::object::TObject;
 use crate::avm2::value::Value;
 use crate::avm2::Multiname;
 use crate::avm2::{Error, Object};
+use crate::context::UpdateContext;
 use crate::timer::TimerCallback;
 
 /// Implements `Timer.stop`
@@ -54,6 +55,7 @@ pub fn start<'gc>(
         // Note - we deliberately do *not* check if currentCount is less than repeatCount.
         // Calling 'start' on a timer that has currentCount >= repeatCount will tick exactly
         // once, and then stop immediately. This is handled by Timer.onUpdate
+        let movie = activation.caller_movie_or_root();
         let id = activation.context.timers.add_timer(
             TimerCallback::Avm2Callback {
                 closure: on_update,
@@ -61,6 +63,7 @@ pub fn start<'gc>(
             },
             delay as _,
             false,
+            movie,
         );
         this.set_slot(FLASH_UTILS_TIMER__TIMER_ID_SLOT, id.into(), activation)?;
     }
@@ -81,8 +84,31 @@ pub fn update_delay<'gc>(
         .get_slot(FLASH_UTILS_TIMER__DELAY_SLOT)
         .coerce_to_i32(activation)?;
 
-    if id != -1 {
-        activation.context.timers.set_delay(id, delay);
+    if id != -1 && !activation.context.timers.set_delay(id, delay) {
+        // The native timer was removed, such as by unloading the movie that started it.
+        this.set_slot(FLASH_UTILS_TIMER__TIMER_ID_SLOT, (-1).into(), activation)?;
     }
     Ok(Value::Undefined)
 }
+
+/// Marks the `Timer` that `callback` belongs to, if any, as no longer running.
+///
+/// This is used when native timers are removed without the `Timer` stopping them.
+pub fn mark_stopped<'gc>(context: &mut UpdateContext<'gc>, callback: &TimerCallback<'gc>) {
+    let TimerCallback::Avm2Callback { closure, .. } = callback else {
+        return;
+    };
+    let Some(timer) = closure
+        .as_function_object()
+        .and_then(|closure| closure.bound_receiver())
+    else {
+        return;
+    };
+    if timer.is_of_type(context.avm2.class_defs().timer) {
+        timer.set_slot_no_coerce(
+            FLASH_UTILS_TIMER__TIMER_ID_SLOT,
+            (-1).into(),
+            context.gc_context,
+        );
+    }
+}
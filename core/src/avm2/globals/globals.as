@@ -163,6 +163,7 @@ include "flash/events/AVPauseAtPeriodEndEvent.as"
 include "flash/events/AVStatusEvent.as"
 include "flash/events/ContextMenuEvent.as"
 include "flash/events/DataEvent.as"
+include "flash/events/DatagramSocketDataEvent.as"
 include "flash/events/DRMAuthenticationCompleteEvent.as"
 include "flash/events/DRMAuthenticationErrorEvent.as"
 include "flash/events/DRMLicenseRequestEvent.as"
@@ -0,0 +1,587 @@
+//! The objects through which AVM2 code captures cameras and microphones.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::globals::slots::*;
+use crate::avm2::object::{ByteArrayObject, Object, TObject};
+use crate::avm2::{Avm2, Error};
+use crate::backend::media_capture::{CameraMode, CapturedFrame};
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use fnv::FnvHashMap;
+use gc_arena::Collect;
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapInfo, PixelRegion};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// The latest frame of a camera, shared with the `Video`s the camera is attached to.
+#[derive(Clone, Debug, Default)]
+pub struct CameraFeed(Rc<RefCell<Option<BitmapInfo>>>);
+
+impl CameraFeed {
+    /// The latest frame of the camera, if it captured any.
+    pub fn frame(&self) -> Option<BitmapInfo> {
+        self.0.borrow().clone()
+    }
+
+    /// Whether a `Video` displays this feed, which is what keeps a camera capturing.
+    fn is_attached(&self) -> bool {
+        Rc::strong_count(&self.0) > 1
+    }
+}
+
+/// A `Camera`, along with the state of its capture.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct CameraObject<'gc> {
+    object: Object<'gc>,
+
+    #[collect(require_static)]
+    feed: CameraFeed,
+
+    /// The mode the camera captures at, while it's capturing.
+    #[collect(require_static)]
+    mode: Option<CameraMode>,
+
+    /// Whether the backend refused to capture the camera.
+    muted: bool,
+
+    /// The latest frame of the camera, kept around for `Camera.copyToByteArray`.
+    #[collect(require_static)]
+    last_frame: Option<CapturedFrame>,
+
+    /// The times at which frames were captured during the last second.
+    frame_times: VecDeque<f64>,
+
+    /// The time, in milliseconds, since the camera was first requested.
+    time: f64,
+
+    /// The last time motion above `Camera.motionLevel` was detected.
+    last_motion: f64,
+
+    /// Whether an `activity` event announcing motion was the last one dispatched.
+    active: bool,
+}
+
+/// A `Microphone`, along with the state of its capture.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct MicrophoneObject<'gc> {
+    object: Object<'gc>,
+
+    /// The sample rate the microphone captures at, in Hz, while it's capturing.
+    sample_rate: Option<u32>,
+
+    /// Whether the backend refused to capture the microphone.
+    muted: bool,
+
+    /// The number of samples dispatched so far.
+    position: f64,
+
+    /// The time, in milliseconds, since the microphone was first requested.
+    time: f64,
+
+    /// The last time sound above `Microphone.silenceLevel` was detected.
+    last_sound: f64,
+
+    /// Whether an `activity` event announcing sound was the last one dispatched.
+    active: bool,
+}
+
+/// The `Camera`s and `Microphone`s of one AVM2 instance.
+///
+/// Each device is represented by a single object, so that every call to `Camera.getCamera`
+/// or `Microphone.getMicrophone` for the same device returns the same object.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct MediaCaptureObjects<'gc> {
+    cameras: FnvHashMap<usize, CameraObject<'gc>>,
+    microphones: FnvHashMap<usize, MicrophoneObject<'gc>>,
+}
+
+/// Returns the `Camera` representing a camera of the media capture backend.
+pub fn camera_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    index: usize,
+    name: &str,
+) -> Result<Object<'gc>, Error<'gc>> {
+    if let Some(camera) = activation.avm2().media_capture_objects.cameras.get(&index) {
+        return Ok(camera.object);
+    }
+
+    let object = activation
+        .avm2()
+        .classes()
+        .camera
+        .construct(activation, &[])?;
+    let name = AvmString::new_utf8(activation.context.gc_context, name);
+    object.set_slot(
+        FLASH_MEDIA_CAMERA__INDEX_SLOT,
+        (index as u32).into(),
+        activation,
+    )?;
+    object.set_slot(FLASH_MEDIA_CAMERA__NAME_SLOT, name.into(), activation)?;
+
+    activation.avm2().media_capture_objects.cameras.insert(
+        index,
+        CameraObject {
+            object,
+            feed: Default::default(),
+            mode: None,
+            muted: false,
+            last_frame: None,
+            frame_times: VecDeque::new(),
+            time: 0.0,
+            last_motion: 0.0,
+            active: false,
+        },
+    );
+    Ok(object)
+}
+
+/// Returns the `Microphone` representing a microphone of the media capture backend.
+pub fn microphone_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    index: usize,
+    name: &str,
+) -> Result<Object<'gc>, Error<'gc>> {
+    if let Some(microphone) = activation
+        .avm2()
+        .media_capture_objects
+        .microphones
+        .get(&index)
+    {
+        return Ok(microphone.object);
+    }
+
+    let object = activation
+        .avm2()
+        .classes()
+        .microphone
+        .construct(activation, &[])?;
+    let name = AvmString::new_utf8(activation.context.gc_context, name);
+    object.set_slot(
+        FLASH_MEDIA_MICROPHONE__INDEX_SLOT,
+        (index as u32).into(),
+        activation,
+    )?;
+    object.set_slot(FLASH_MEDIA_MICROPHONE__NAME_SLOT, name.into(), activation)?;
+
+    activation.avm2().media_capture_objects.microphones.insert(
+        index,
+        MicrophoneObject {
+            object,
+            sample_rate: None,
+            muted: false,
+            position: 0.0,
+            time: 0.0,
+            last_sound: 0.0,
+            active: false,
+        },
+    );
+    Ok(object)
+}
+
+fn camera_index(camera: Object<'_>) -> usize {
+    camera.get_slot(FLASH_MEDIA_CAMERA__INDEX_SLOT).as_u32() as usize
+}
+
+impl<'gc> MediaCaptureObjects<'gc> {
+    /// The feed to display in a `Video` the given `Camera` gets attached to.
+    pub fn camera_feed(&self, camera: Object<'gc>) -> Option<CameraFeed> {
+        self.cameras
+            .get(&camera_index(camera))
+            .map(|camera| camera.feed.clone())
+    }
+
+    /// The latest frame captured by a `Camera`.
+    pub fn camera_frame(&self, camera: Object<'gc>) -> Option<&CapturedFrame> {
+        self.cameras
+            .get(&camera_index(camera))
+            .and_then(|camera| camera.last_frame.as_ref())
+    }
+}
+
+/// Stops a `Camera` so that it gets restarted in the mode set by `Camera.setMode`.
+pub fn restart_camera(context: &mut UpdateContext<'_>, camera: Object<'_>) {
+    let index = camera_index(camera);
+    if let Some(camera) = context.avm2.media_capture_objects.cameras.get_mut(&index) {
+        if camera.mode.take().is_some() {
+            context.media_capture.stop_camera(index);
+        }
+    }
+}
+
+/// The sample rate, in Hz, of a `Microphone.rate` in kHz.
+fn sample_rate(rate: i32) -> u32 {
+    match rate {
+        5 => 5512,
+        11 => 11025,
+        16 => 16000,
+        22 => 22050,
+        44 => 44100,
+        _ => 8000,
+    }
+}
+
+fn dispatch_status<'gc>(context: &mut UpdateContext<'gc>, target: Object<'gc>, code: &'static str) {
+    let mut activation = Activation::from_nothing(context);
+    if let Ok(event) = activation.avm2().classes().statusevent.construct(
+        &mut activation,
+        &[
+            "status".into(),
+            false.into(),
+            false.into(),
+            code.into(),
+            "status".into(),
+        ],
+    ) {
+        Avm2::dispatch_event(activation.context, event, target);
+    }
+}
+
+fn dispatch_activity<'gc>(context: &mut UpdateContext<'gc>, target: Object<'gc>, activating: bool) {
+    let mut activation = Activation::from_nothing(context);
+    if let Ok(event) = activation.avm2().classes().activityevent.construct(
+        &mut activation,
+        &[
+            "activity".into(),
+            false.into(),
+            false.into(),
+            activating.into(),
+        ],
+    ) {
+        Avm2::dispatch_event(activation.context, event, target);
+    }
+}
+
+/// Captures the cameras attached to a `Video` and the microphones with `sampleData` listeners,
+/// and dispatches their events.
+pub fn update_capture_devices(context: &mut UpdateContext<'_>, dt: f64) {
+    let cameras: Vec<usize> = context
+        .avm2
+        .media_capture_objects
+        .cameras
+        .keys()
+        .copied()
+        .collect();
+    for index in cameras {
+        update_camera(context, index, dt);
+    }
+
+    let microphones: Vec<usize> = context
+        .avm2
+        .media_capture_objects
+        .microphones
+        .keys()
+        .copied()
+        .collect();
+    for index in microphones {
+        update_microphone(context, index, dt);
+    }
+}
+
+fn update_camera(context: &mut UpdateContext<'_>, index: usize, dt: f64) {
+    let Some(camera) = context.avm2.media_capture_objects.cameras.get_mut(&index) else {
+        return;
+    };
+    let object = camera.object;
+    camera.time += dt;
+
+    if !camera.feed.is_attached() {
+        if camera.mode.take().is_some() {
+            context.media_capture.stop_camera(index);
+            *camera.feed.0.borrow_mut() = None;
+        }
+        return;
+    }
+
+    if camera.mode.is_none() {
+        if camera.muted {
+            return;
+        }
+
+        let requested = CameraMode {
+            width: object.get_slot(FLASH_MEDIA_CAMERA__WIDTH_SLOT).as_u32(),
+            height: object.get_slot(FLASH_MEDIA_CAMERA__HEIGHT_SLOT).as_u32(),
+            fps: object.get_slot(FLASH_MEDIA_CAMERA__FPS_SLOT).as_f64(),
+        };
+        let Some(mode) = context.media_capture.start_camera(index, requested) else {
+            camera.muted = true;
+            let mut activation = Activation::from_nothing(context);
+            let _ = object.set_slot(FLASH_MEDIA_CAMERA__MUTED_SLOT, true.into(), &mut activation);
+            dispatch_status(context, object, "Camera.Muted");
+            return;
+        };
+        camera.mode = Some(mode);
+
+        let mut activation = Activation::from_nothing(context);
+        for (slot, value) in [
+            (FLASH_MEDIA_CAMERA__WIDTH_SLOT, mode.width.into()),
+            (FLASH_MEDIA_CAMERA__HEIGHT_SLOT, mode.height.into()),
+            (FLASH_MEDIA_CAMERA__FPS_SLOT, mode.fps.into()),
+        ] {
+            let _ = object.set_slot(slot, value, &mut activation);
+        }
+    }
+
+    let Some(camera) = context.avm2.media_capture_objects.cameras.get_mut(&index) else {
+        return;
+    };
+    let elapsed = Duration::from_secs_f64(dt.max(0.0) / 1000.0);
+    let Some(frame) = context.media_capture.camera_frame(index, elapsed) else {
+        return;
+    };
+
+    let bitmap = Bitmap::new(
+        frame.width,
+        frame.height,
+        BitmapFormat::Rgb,
+        frame.rgb.clone(),
+    );
+    let mut feed = camera.feed.0.borrow_mut();
+    let result = match &*feed {
+        Some(info)
+            if u32::from(info.width) == frame.width && u32::from(info.height) == frame.height =>
+        {
+            context.renderer.update_texture(
+                &info.handle,
+                bitmap,
+                PixelRegion::for_whole_size(frame.width, frame.height),
+            )
+        }
+        _ => context.renderer.register_bitmap(bitmap).map(|handle| {
+            *feed = Some(BitmapInfo {
+                handle,
+                width: frame.width as u16,
+                height: frame.height as u16,
+            });
+        }),
+    };
+    drop(feed);
+    if let Err(e) = result {
+        tracing::error!("Couldn't upload a camera frame: {}", e);
+    }
+    *context.needs_render = true;
+
+    // The activity level is the average change of the pixels since the previous frame.
+    let activity = match &camera.last_frame {
+        Some(last) if last.rgb.len() == frame.rgb.len() && !frame.rgb.is_empty() => {
+            let difference: u64 = last
+                .rgb
+                .iter()
+                .zip(&frame.rgb)
+                .map(|(a, b)| u64::from(a.abs_diff(*b)))
+                .sum();
+            (difference as f64 * 100.0 / (frame.rgb.len() as f64 * 255.0)).min(100.0)
+        }
+        _ => 0.0,
+    };
+    camera.last_frame = Some(frame);
+
+    camera.frame_times.push_back(camera.time);
+    while camera
+        .frame_times
+        .front()
+        .is_some_and(|time| *time <= camera.time - 1000.0)
+    {
+        camera.frame_times.pop_front();
+    }
+    let current_fps = camera.frame_times.len();
+
+    let motion_level = object
+        .get_slot(FLASH_MEDIA_CAMERA__MOTION_LEVEL_SLOT)
+        .as_f64();
+    let motion_timeout = object
+        .get_slot(FLASH_MEDIA_CAMERA__MOTION_TIMEOUT_SLOT)
+        .as_f64();
+    let activating = if activity >= motion_level {
+        camera.last_motion = camera.time;
+        (!camera.active).then_some(true)
+    } else if camera.active && camera.time - camera.last_motion >= motion_timeout {
+        Some(false)
+    } else {
+        None
+    };
+    if let Some(activating) = activating {
+        camera.active = activating;
+    }
+
+    let mut activation = Activation::from_nothing(context);
+    let _ = object.set_slot(
+        FLASH_MEDIA_CAMERA__ACTIVITY_LEVEL_SLOT,
+        activity.into(),
+        &mut activation,
+    );
+    let _ = object.set_slot(
+        FLASH_MEDIA_CAMERA__CURRENT_FPS_SLOT,
+        (current_fps as f64).into(),
+        &mut activation,
+    );
+
+    if let Some(activating) = activating {
+        dispatch_activity(context, object, activating);
+    }
+}
+
+fn update_microphone(context: &mut UpdateContext<'_>, index: usize, dt: f64) {
+    let Some(microphone) = context.avm2.media_capture_objects.microphones.get(&index) else {
+        return;
+    };
+    let object = microphone.object;
+
+    let mut activation = Activation::from_nothing(context);
+    let listening = object
+        .call_public_property("hasEventListener", &["sampleData".into()], &mut activation)
+        .map(|value| value.coerce_to_boolean())
+        .unwrap_or_default();
+
+    let Some(microphone) = context
+        .avm2
+        .media_capture_objects
+        .microphones
+        .get_mut(&index)
+    else {
+        return;
+    };
+    microphone.time += dt;
+
+    let rate = sample_rate(object.get_slot(FLASH_MEDIA_MICROPHONE__RATE_SLOT).as_i32());
+    if microphone.sample_rate.is_some() && (!listening || microphone.sample_rate != Some(rate)) {
+        microphone.sample_rate = None;
+        context.media_capture.stop_microphone(index);
+    }
+    if !listening {
+        return;
+    }
+
+    if microphone.sample_rate.is_none() {
+        if microphone.muted {
+            return;
+        }
+
+        if !context.media_capture.start_microphone(index, rate) {
+            microphone.muted = true;
+            let mut activation = Activation::from_nothing(context);
+            let _ = object.set_slot(
+                FLASH_MEDIA_MICROPHONE__MUTED_SLOT,
+                true.into(),
+                &mut activation,
+            );
+            dispatch_status(context, object, "Microphone.Muted");
+            return;
+        }
+        microphone.sample_rate = Some(rate);
+    }
+
+    let elapsed = Duration::from_secs_f64(dt.max(0.0) / 1000.0);
+    let mut samples = context.media_capture.microphone_samples(index, elapsed);
+
+    // A gain of 50 leaves the signal as is.
+    let gain = object.get_slot(FLASH_MEDIA_MICROPHONE__GAIN_SLOT).as_f64() as f32 / 50.0;
+    let mut peak = 0.0f32;
+    for sample in &mut samples {
+        *sample = (*sample * gain).clamp(-1.0, 1.0);
+        peak = peak.max(sample.abs());
+    }
+    let activity = f64::from(peak) * 100.0;
+
+    let silence_level = object
+        .get_slot(FLASH_MEDIA_MICROPHONE__SILENCE_LEVEL_SLOT)
+        .as_f64();
+    let silence_timeout = object
+        .get_slot(FLASH_MEDIA_MICROPHONE__SILENCE_TIMEOUT_SLOT)
+        .as_f64();
+    let activating = if activity > silence_level {
+        microphone.last_sound = microphone.time;
+        (!microphone.active).then_some(true)
+    } else if microphone.active && microphone.time - microphone.last_sound >= silence_timeout {
+        Some(false)
+    } else {
+        None
+    };
+    if let Some(activating) = activating {
+        microphone.active = activating;
+    }
+
+    let position = microphone.position;
+    microphone.position += samples.len() as f64;
+
+    let mut activation = Activation::from_nothing(context);
+    let _ = object.set_slot(
+        FLASH_MEDIA_MICROPHONE__ACTIVITY_LEVEL_SLOT,
+        activity.into(),
+        &mut activation,
+    );
+
+    if let Some(activating) = activating {
+        dispatch_activity(activation.context, object, activating);
+    }
+
+    if samples.is_empty() {
+        return;
+    }
+
+    let mut activation = Activation::from_nothing(context);
+    let data = samples
+        .iter()
+        .flat_map(|sample| sample.to_be_bytes())
+        .collect();
+    let data =
+        match ByteArrayObject::from_storage(&mut activation, ByteArrayStorage::from_vec(data)) {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::error!("Couldn't create the data of a microphone: {:?}", e);
+                return;
+            }
+        };
+
+    match activation.avm2().classes().sampledataevent.construct(
+        &mut activation,
+        &[
+            "sampleData".into(),
+            false.into(),
+            false.into(),
+            position.into(),
+            data.into(),
+        ],
+    ) {
+        Ok(event) => {
+            Avm2::dispatch_event(activation.context, event, object);
+        }
+        Err(e) => tracing::error!("Couldn't create a SampleDataEvent: {:?}", e),
+    }
+}
+
+/// Stops capturing every device, as the objects representing them are going away.
+pub fn stop_capture_devices(context: &mut UpdateContext<'_>) {
+    let objects = std::mem::take(&mut context.avm2.media_capture_objects);
+    for (index, camera) in objects.cameras {
+        if camera.mode.is_some() {
+            context.media_capture.stop_camera(index);
+        }
+    }
+    for (index, microphone) in objects.microphones {
+        if microphone.sample_rate.is_some() {
+            context.media_capture.stop_microphone(index);
+        }
+    }
+}
+
+/// The pixels of a captured frame within the given rectangle, in ARGB.
+pub fn frame_pixels(frame: &CapturedFrame, x: u32, y: u32, width: u32, height: u32) -> Vec<u32> {
+    let x_end = (x + width).min(frame.width);
+    let y_end = (y + height).min(frame.height);
+
+    let mut pixels = Vec::new();
+    for y in y.min(y_end)..y_end {
+        for x in x.min(x_end)..x_end {
+            let i = ((y * frame.width + x) * 3) as usize;
+            let [r, g, b] = [frame.rgb[i], frame.rgb[i + 1], frame.rgb[i + 2]];
+            pixels.push(0xFF000000 | (u32::from(r) << 16) | (u32::from(g) << 8) | u32::from(b));
+        }
+    }
+    pixels
+}
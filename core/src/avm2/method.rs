@@ -15,6 +15,7 @@ use gc_arena::lock::{Lock, RefLock};
 use gc_arena::{Collect, Gc, GcCell, Mutation};
 use std::borrow::Cow;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 use std::sync::Arc;
 use swf::avm2::types::{
@@ -385,6 +386,28 @@ impl<'gc> From<Gc<'gc, BytecodeMethod<'gc>>> for Method<'gc> {
     }
 }
 
+/// Methods are compared by identity.
+impl PartialEq for Method<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Method::Native(a), Method::Native(b)) => Gc::ptr_eq(*a, *b),
+            (Method::Bytecode(a), Method::Bytecode(b)) => Gc::ptr_eq(*a, *b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Method<'_> {}
+
+impl Hash for Method<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Method::Native(method) => Gc::as_ptr(*method).hash(state),
+            Method::Bytecode(method) => Gc::as_ptr(*method).hash(state),
+        }
+    }
+}
+
 impl<'gc> Method<'gc> {
     /// Define a builtin method with a particular param configuration.
     pub fn from_builtin_and_params(
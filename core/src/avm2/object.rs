@@ -61,6 +61,7 @@ mod sound_object;
 mod soundchannel_object;
 mod stage3d_object;
 mod stage_object;
+mod text_block_object;
 mod textformat_object;
 mod texture_object;
 mod vector_object;
@@ -140,6 +141,9 @@ pub use crate::avm2::object::stage3d_object::{
     stage_3d_allocator, Stage3DObject, Stage3DObjectWeak,
 };
 pub use crate::avm2::object::stage_object::{StageObject, StageObjectWeak};
+pub use crate::avm2::object::text_block_object::{
+    text_block_allocator, TextBlockObject, TextBlockObjectWeak,
+};
 pub use crate::avm2::object::textformat_object::{
     textformat_allocator, TextFormatObject, TextFormatObjectWeak,
 };
@@ -201,6 +205,7 @@ use crate::font::Font;
         FontObject(FontObject<'gc>),
         LocalConnectionObject(LocalConnectionObject<'gc>),
         SharedObjectObject(SharedObjectObject<'gc>),
+        TextBlockObject(TextBlockObject<'gc>),
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -1315,6 +1320,10 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     fn as_shared_object(&self) -> Option<SharedObjectObject<'gc>> {
         None
     }
+
+    fn as_text_block(&self) -> Option<TextBlockObject<'gc>> {
+        None
+    }
 }
 
 pub enum ObjectPtr {}
@@ -1367,6 +1376,7 @@ impl<'gc> Object<'gc> {
             Self::FontObject(o) => WeakObject::FontObject(FontObjectWeak(Gc::downgrade(o.0))),
             Self::LocalConnectionObject(o) => WeakObject::LocalConnectionObject(LocalConnectionObjectWeak(Gc::downgrade(o.0))),
             Self::SharedObjectObject(o) => WeakObject::SharedObjectObject(SharedObjectObjectWeak(Gc::downgrade(o.0))),
+            Self::TextBlockObject(o) => WeakObject::TextBlockObject(TextBlockObjectWeak(Gc::downgrade(o.0))),
         }
     }
 }
@@ -1429,6 +1439,7 @@ pub enum WeakObject<'gc> {
     FontObject(FontObjectWeak<'gc>),
     LocalConnectionObject(LocalConnectionObjectWeak<'gc>),
     SharedObjectObject(SharedObjectObjectWeak<'gc>),
+    TextBlockObject(TextBlockObjectWeak<'gc>),
 }
 
 impl<'gc> WeakObject<'gc> {
@@ -1474,6 +1485,7 @@ impl<'gc> WeakObject<'gc> {
             Self::FontObject(o) => GcWeak::as_ptr(o.0) as *const ObjectPtr,
             Self::LocalConnectionObject(o) => GcWeak::as_ptr(o.0) as *const ObjectPtr,
             Self::SharedObjectObject(o) => GcWeak::as_ptr(o.0) as *const ObjectPtr,
+            Self::TextBlockObject(o) => GcWeak::as_ptr(o.0) as *const ObjectPtr,
         }
     }
 
@@ -1519,6 +1531,7 @@ impl<'gc> WeakObject<'gc> {
             Self::FontObject(o) => FontObject(o.0.upgrade(mc)?).into(),
             Self::LocalConnectionObject(o) => LocalConnectionObject(o.0.upgrade(mc)?).into(),
             Self::SharedObjectObject(o) => SharedObjectObject(o.0.upgrade(mc)?).into(),
+            Self::TextBlockObject(o) => TextBlockObject(o.0.upgrade(mc)?).into(),
         })
     }
 }
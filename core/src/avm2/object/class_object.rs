@@ -9,6 +9,7 @@ use crate::avm2::object::function_object::FunctionObject;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::property::Property;
+use crate::avm2::sampler;
 use crate::avm2::scope::{Scope, ScopeChain};
 use crate::avm2::value::Value;
 use crate::avm2::vtable::{ClassBoundMethod, VTable};
@@ -671,11 +672,15 @@ impl<'gc> ClassObject<'gc> {
         arguments: &[Value<'gc>],
     ) -> Result<Object<'gc>, Error<'gc>> {
         if let Some(custom_constructor) = self.custom_constructor() {
-            custom_constructor(activation, arguments)
+            let instance = custom_constructor(activation, arguments)?;
+            sampler::record_allocation(activation, instance, self)?;
+
+            Ok(instance)
         } else {
             let instance_allocator = self.instance_allocator();
 
             let instance = instance_allocator(self, activation)?;
+            sampler::record_allocation(activation, instance, self)?;
 
             self.call_init(instance.into(), arguments, activation)?;
 
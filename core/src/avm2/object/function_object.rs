@@ -6,6 +6,7 @@ use crate::avm2::function::BoundMethod;
 use crate::avm2::method::{Method, NativeMethod};
 use crate::avm2::object::script_object::{ScriptObject, ScriptObjectData};
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::sampler;
 use crate::avm2::scope::ScopeChain;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
@@ -186,6 +187,7 @@ impl<'gc> FunctionObject<'gc> {
             Some(prototype),
             object_class.instance_vtable(),
         );
+        sampler::record_allocation(activation, instance, object_class)?;
 
         self.call(activation, instance.into(), arguments)?;
 
@@ -203,6 +205,16 @@ impl<'gc> FunctionObject<'gc> {
     pub fn num_parameters(&self) -> usize {
         self.0.exec.borrow().num_parameters()
     }
+
+    /// The scope this function was defined in.
+    pub fn scope(&self) -> ScopeChain<'gc> {
+        self.0.exec.borrow().scope()
+    }
+
+    /// The receiver this function is always called with, if it is a method closure.
+    pub fn bound_receiver(&self) -> Option<Object<'gc>> {
+        self.0.exec.borrow().bound_receiver()
+    }
 }
 
 impl<'gc> TObject<'gc> for FunctionObject<'gc> {
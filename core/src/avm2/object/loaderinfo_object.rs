@@ -360,6 +360,12 @@ impl<'gc> LoaderInfoObject<'gc> {
     }
 
     pub fn unload(&self, activation: &mut Activation<'_, 'gc>) {
+        // Cancel the load of the content, if it's still in progress
+        activation
+            .context
+            .load_manager
+            .cancel_movie_loads((*self).into());
+
         // Reset properties
         let empty_swf = Arc::new(SwfMovie::empty(activation.context.swf.version()));
         let loader_stream = LoaderStream::NotYetLoaded(empty_swf, None, false);
@@ -374,11 +380,17 @@ impl<'gc> LoaderInfoObject<'gc> {
 
         // Remove the Loader's content element, and ignore the resulting
         // error if the loader hadn't loaded it.
-        let _ = crate::avm2::globals::flash::display::display_object_container::remove_child_at(
-            activation,
-            loader,
-            &[0.into()],
-        );
+        let removed =
+            crate::avm2::globals::flash::display::display_object_container::remove_child_at(
+                activation,
+                loader,
+                &[0.into()],
+            );
+
+        if removed.is_ok() {
+            let unload_evt = EventObject::bare_default_event(activation.context, "unload");
+            Avm2::dispatch_event(activation.context, unload_evt, (*self).into());
+        }
     }
 }
 
@@ -14,7 +14,11 @@ pub fn netstream_allocator<'gc>(
 ) -> Result<Object<'gc>, Error<'gc>> {
     let base = ScriptObjectData::new(class);
 
-    let ns = NetStream::new(activation.context.gc_context, None);
+    let ns = NetStream::new(
+        activation.context.gc_context,
+        None,
+        activation.caller_movie_or_root(),
+    );
     let this: Object<'gc> = NetStreamObject(Gc::new(
         activation.context.gc_context,
         NetStreamObjectData { base, ns },
@@ -1,15 +1,17 @@
 //! Object representation for sounds
 
 use crate::avm2::activation::Activation;
+use crate::avm2::bytearray::{ByteArrayStorage, Endian};
 use crate::avm2::object::script_object::ScriptObjectData;
-use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::object::{ByteArrayObject, ClassObject, Object, ObjectPtr, TObject};
 use crate::avm2::Avm2;
 use crate::avm2::Error;
 use crate::avm2::EventObject;
-use crate::backend::audio::{AudioManager, SoundHandle};
+use crate::backend::audio::{AudioManager, SoundHandle, SoundInstanceHandle};
 use crate::context::UpdateContext;
 use crate::display_object::SoundTransform;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use core::fmt;
 use gc_arena::barrier::unlock;
 use gc_arena::{
@@ -17,7 +19,9 @@ use gc_arena::{
     Collect, Gc, GcWeak, Mutation,
 };
 use id3::{Tag, TagLike};
+use std::cell::Cell;
 use std::io::Cursor;
+use std::sync::Arc;
 use swf::SoundInfo;
 
 use super::SoundChannelObject;
@@ -37,6 +41,8 @@ pub fn sound_allocator<'gc>(
                 queued_plays: Vec::new(),
             }),
             id3: Lock::new(None),
+            extract_position: Cell::new(0),
+            has_data_source: Cell::new(false),
         },
     ))
     .into())
@@ -70,6 +76,13 @@ pub struct SoundObjectData<'gc> {
 
     /// ID3Info Object
     id3: Lock<Option<Object<'gc>>>,
+
+    /// The sample that `Sound.extract` continues from when no start position is given.
+    extract_position: Cell<u32>,
+
+    /// Whether this sound was given data to play, by loading it or by being embedded.
+    /// Only sounds without any are generated by their `sampleData` listeners.
+    has_data_source: Cell<bool>,
 }
 
 const _: () = assert!(std::mem::offset_of!(SoundObjectData, base) == 0);
@@ -97,6 +110,10 @@ pub struct QueuedPlay<'gc> {
     pub sound_transform: Option<SoundTransform>,
     pub sound_channel: SoundChannelObject<'gc>,
     pub position: f64,
+
+    /// The movie whose script played the sound.
+    #[collect(require_static)]
+    pub movie: Arc<SwfMovie>,
 }
 
 impl<'gc> SoundObject<'gc> {
@@ -108,12 +125,37 @@ impl<'gc> SoundObject<'gc> {
         }
     }
 
+    pub fn extract_position(self) -> u32 {
+        self.0.extract_position.get()
+    }
+
+    pub fn set_extract_position(self, position: u32) {
+        self.0.extract_position.set(position);
+    }
+
+    /// Marks this sound as having data to play, even if it hasn't finished loading yet.
+    pub fn set_has_data_source(self) {
+        self.0.has_data_source.set(true);
+    }
+
     /// Returns `true` if a `SoundChannel` should be returned back to the AVM2 caller.
     pub fn play(
         self,
         queued: QueuedPlay<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<bool, Error<'gc>> {
+        // A sound without any data of its own generates it with `sampleData` listeners instead.
+        if !self.0.has_data_source.get()
+            && self
+                .call_public_property("hasEventListener", &["sampleData".into()], activation)?
+                .coerce_to_boolean()
+        {
+            if let Some(instance) = activation.context.start_dynamic_sound(self) {
+                attach_sound_channel(queued, instance, activation);
+            }
+            return Ok(true);
+        }
+
         let mut sound_data = unlock!(
             Gc::write(activation.context.gc_context, self.0),
             SoundObjectData,
@@ -147,6 +189,7 @@ impl<'gc> SoundObject<'gc> {
             sound_data
         )
         .borrow_mut();
+        self.set_has_data_source();
         let mut activation = Activation::from_nothing(context);
         match &mut *sound_data {
             SoundData::NotLoaded { queued_plays } => {
@@ -162,6 +205,62 @@ impl<'gc> SoundObject<'gc> {
         Ok(())
     }
 
+    /// Dispatches a `sampleData` event to this sound, and returns the samples its listeners
+    /// wrote to the event's `data`.
+    ///
+    /// `position` is the number of samples that listeners have provided before.
+    pub fn request_sample_data(
+        self,
+        context: &mut UpdateContext<'gc>,
+        position: f64,
+    ) -> Vec<[f32; 2]> {
+        let mut activation = Activation::from_nothing(context);
+        let data = match self.dispatch_sample_data(&mut activation, position) {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::error!("Error dispatching sampleData event: {e:?}");
+                return vec![];
+            }
+        };
+
+        let storage = data.as_bytearray().expect("Created as a ByteArray");
+        let endian = storage.endian();
+        let read_float = |bytes: &[u8]| {
+            let bytes: [u8; 4] = bytes.try_into().expect("Chunks are 4 bytes long");
+            match endian {
+                Endian::Big => f32::from_be_bytes(bytes),
+                Endian::Little => f32::from_le_bytes(bytes),
+            }
+        };
+        // Each sample is a pair of floats, for the left and right channel.
+        storage
+            .bytes()
+            .chunks_exact(8)
+            .map(|sample| [read_float(&sample[..4]), read_float(&sample[4..])])
+            .collect()
+    }
+
+    /// Dispatches a `sampleData` event to this sound, and returns the `ByteArray` given to it.
+    fn dispatch_sample_data(
+        self,
+        activation: &mut Activation<'_, 'gc>,
+        position: f64,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let data = ByteArrayObject::from_storage(activation, ByteArrayStorage::new())?;
+        let event = activation.avm2().classes().sampledataevent.construct(
+            activation,
+            &[
+                "sampleData".into(),
+                false.into(),
+                false.into(),
+                position.into(),
+                data.into(),
+            ],
+        )?;
+        Avm2::dispatch_event(activation.context, event, self.into());
+        Ok(data)
+    }
+
     pub fn id3(self) -> Option<Object<'gc>> {
         self.0.id3.get()
     }
@@ -265,23 +364,32 @@ fn play_queued<'gc>(
         .context
         .start_sound(sound, &queued.sound_info, None, None)
     {
-        if let Some(sound_transform) = queued.sound_transform {
-            activation
-                .context
-                .set_local_sound_transform(instance, sound_transform);
-        }
-
-        queued
-            .sound_channel
-            .as_sound_channel()
-            .unwrap()
-            .set_sound_instance(activation, instance);
+        attach_sound_channel(queued, instance, activation);
+    }
+    Ok(true)
+}
 
+/// Connects the `SoundChannel` of a queued play to the sound instance that was started for it.
+fn attach_sound_channel<'gc>(
+    queued: QueuedPlay<'gc>,
+    instance: SoundInstanceHandle,
+    activation: &mut Activation<'_, 'gc>,
+) {
+    if let Some(sound_transform) = queued.sound_transform {
         activation
             .context
-            .attach_avm2_sound_channel(instance, queued.sound_channel);
+            .set_local_sound_transform(instance, sound_transform);
     }
-    Ok(true)
+
+    queued
+        .sound_channel
+        .as_sound_channel()
+        .unwrap()
+        .set_sound_instance(activation, instance);
+
+    activation
+        .context
+        .attach_avm2_sound_channel(instance, queued.sound_channel, queued.movie);
 }
 
 impl<'gc> TObject<'gc> for SoundObject<'gc> {
@@ -0,0 +1,85 @@
+//! Object representation for TextBlocks
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::Error;
+use crate::text_engine::BlockContent;
+use gc_arena::barrier::unlock;
+use gc_arena::{lock::RefLock, Collect, Gc, GcWeak, Mutation};
+use std::fmt;
+
+/// A class instance allocator that allocates TextBlock objects.
+pub fn text_block_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    Ok(TextBlockObject(Gc::new(
+        activation.gc(),
+        TextBlockObjectData {
+            base: ScriptObjectData::new(class),
+            content: RefLock::new(None),
+        },
+    ))
+    .into())
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct TextBlockObject<'gc>(pub Gc<'gc, TextBlockObjectData<'gc>>);
+
+#[derive(Clone, Collect, Copy, Debug)]
+#[collect(no_drop)]
+pub struct TextBlockObjectWeak<'gc>(pub GcWeak<'gc, TextBlockObjectData<'gc>>);
+
+#[derive(Collect)]
+#[collect(no_drop)]
+#[repr(C, align(8))]
+pub struct TextBlockObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The flattened content of the block, or `None` if it has to be flattened again.
+    content: RefLock<Option<BlockContent<'gc>>>,
+}
+
+const _: () = assert!(std::mem::offset_of!(TextBlockObjectData, base) == 0);
+const _: () = assert!(
+    std::mem::align_of::<TextBlockObjectData>() == std::mem::align_of::<ScriptObjectData>()
+);
+
+impl<'gc> TextBlockObject<'gc> {
+    pub fn content(&self) -> Option<BlockContent<'gc>> {
+        self.0.content.borrow().clone()
+    }
+
+    pub fn set_content(&self, mc: &Mutation<'gc>, content: Option<BlockContent<'gc>>) {
+        *unlock!(Gc::write(mc, self.0), TextBlockObjectData, content).borrow_mut() = content;
+    }
+}
+
+impl<'gc> TObject<'gc> for TextBlockObject<'gc> {
+    fn gc_base(&self) -> Gc<'gc, ScriptObjectData<'gc>> {
+        // SAFETY: Object data is repr(C), and a compile-time assert ensures
+        // that the ScriptObjectData stays at offset 0 of the struct- so the
+        // layouts are compatible
+
+        unsafe { Gc::cast(self.0) }
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        Gc::as_ptr(self.0) as *const ObjectPtr
+    }
+
+    fn as_text_block(&self) -> Option<TextBlockObject<'gc>> {
+        Some(*self)
+    }
+}
+
+impl fmt::Debug for TextBlockObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextBlockObject")
+            .field("ptr", &Gc::as_ptr(self.0))
+            .finish()
+    }
+}
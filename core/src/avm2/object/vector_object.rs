@@ -3,6 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::sampler;
 use crate::avm2::value::Value;
 use crate::avm2::vector::VectorStorage;
 use crate::avm2::Error;
@@ -85,6 +86,7 @@ impl<'gc> VectorObject<'gc> {
             },
         ))
         .into();
+        sampler::record_allocation(activation, object, applied_class)?;
 
         Ok(object)
     }
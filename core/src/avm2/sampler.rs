@@ -0,0 +1,340 @@
+//! Memory and invocation sampling, as exposed by `flash.sampler`.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::call_stack::{CallNode, CallStack};
+use crate::avm2::method::Method;
+use crate::avm2::object::{ClassObject, Object, ScriptObjectData, TObject, WeakObject};
+use crate::avm2::{Error, Value};
+use crate::context::UpdateContext;
+use fnv::FnvHashMap;
+use gc_arena::{Collect, Mutation};
+use std::collections::BTreeMap;
+use std::mem::size_of;
+use web_time::Instant;
+
+/// The time between two samples of the call stack, in microseconds.
+const SAMPLE_INTERVAL: f64 = 1000.0;
+
+/// The number of samples after which the sampler callback gets to process them.
+pub const SAMPLE_BUFFER_SIZE: usize = 8192;
+
+/// The most samples kept without a sampler callback to process them.
+/// Once there are more, the oldest ones are thrown away.
+pub const MAX_SAMPLES: usize = SAMPLE_BUFFER_SIZE * 8;
+
+/// A sample recorded while sampling was running.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub enum Sample<'gc> {
+    /// The call stack at a point in time.
+    Stack {
+        time: f64,
+        stack: Vec<CallNode<'gc>>,
+    },
+
+    /// The allocation of an object.
+    NewObject {
+        time: f64,
+        stack: Vec<CallNode<'gc>>,
+        id: f64,
+        class: ClassObject<'gc>,
+        object: WeakObject<'gc>,
+        size: usize,
+    },
+
+    /// The collection of an object that was allocated while sampling.
+    DeleteObject {
+        time: f64,
+        stack: Vec<CallNode<'gc>>,
+        id: f64,
+        size: usize,
+    },
+}
+
+/// An object allocated while sampling, which gets a `DeleteObject` sample once it is collected.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct TrackedObject<'gc> {
+    object: WeakObject<'gc>,
+    size: usize,
+}
+
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct Sampler<'gc> {
+    /// Whether `startSampling` was called without a matching `stopSampling`.
+    sampling: bool,
+
+    /// Whether sampling was paused with `pauseSampling`.
+    paused: bool,
+
+    /// Whether allocations made outside of any ActionScript code are sampled.
+    internal_allocs: bool,
+
+    samples: Vec<Sample<'gc>>,
+
+    /// The objects whose `NewObject` sample is still kept, keyed by their id.
+    tracked_objects: BTreeMap<u64, TrackedObject<'gc>>,
+
+    next_id: u64,
+
+    last_stack_sample: f64,
+
+    /// How often each method was called while sampling.
+    ///
+    /// The methods are kept alive by this map, so that a method allocated later at the same
+    /// address can't inherit the count of a collected one.
+    invocation_counts: FnvHashMap<Method<'gc>, u32>,
+
+    /// The function to call once the sample buffer fills up.
+    callback: Option<Object<'gc>>,
+}
+
+impl<'gc> Sampler<'gc> {
+    /// Whether calls and allocations are currently being recorded.
+    pub fn is_active(&self) -> bool {
+        self.sampling && !self.paused
+    }
+
+    pub fn start(&mut self) {
+        self.sampling = true;
+        self.paused = false;
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Whether sampling was started, and whether it was paused.
+    pub fn state(&self) -> (bool, bool) {
+        (self.sampling, self.paused)
+    }
+
+    pub fn set_state(&mut self, (sampling, paused): (bool, bool)) {
+        self.sampling = sampling;
+        self.paused = paused;
+    }
+
+    /// Stops sampling, throwing away everything that was recorded.
+    pub fn stop(&mut self) {
+        self.sampling = false;
+        self.paused = false;
+        self.samples.clear();
+        self.tracked_objects.clear();
+        self.invocation_counts.clear();
+    }
+
+    pub fn clear_samples(&mut self) {
+        forget_objects(&mut self.tracked_objects, &self.samples);
+        self.samples.clear();
+    }
+
+    pub fn set_internal_allocs(&mut self, internal_allocs: bool) {
+        self.internal_allocs = internal_allocs;
+    }
+
+    pub fn callback(&self) -> Option<Object<'gc>> {
+        self.callback
+    }
+
+    pub fn set_callback(&mut self, callback: Option<Object<'gc>>) {
+        self.callback = callback;
+    }
+
+    /// Whether the sample buffer is full enough for the callback to be called.
+    pub fn is_buffer_full(&self) -> bool {
+        self.samples.len() >= SAMPLE_BUFFER_SIZE
+    }
+
+    /// Records a call to a method, which is already on top of the call stack.
+    pub fn record_call(&mut self, method: Method<'gc>, call_stack: &CallStack<'gc>, time: f64) {
+        *self.invocation_counts.entry(method).or_default() += 1;
+
+        if time - self.last_stack_sample >= SAMPLE_INTERVAL {
+            self.last_stack_sample = time;
+            self.push_sample(Sample::Stack {
+                time,
+                stack: capture_stack(call_stack),
+            });
+        }
+    }
+
+    /// Records the allocation of an object of the given class.
+    pub fn record_allocation(
+        &mut self,
+        object: Object<'gc>,
+        class: ClassObject<'gc>,
+        call_stack: &CallStack<'gc>,
+        time: f64,
+    ) {
+        if call_stack.is_empty() && !self.internal_allocs {
+            return;
+        }
+
+        self.next_id += 1;
+        let id = self.next_id;
+        let size = object_size(object);
+        let object = object.downgrade();
+
+        self.tracked_objects
+            .insert(id, TrackedObject { object, size });
+        self.push_sample(Sample::NewObject {
+            time,
+            stack: capture_stack(call_stack),
+            id: id as f64,
+            class,
+            object,
+            size,
+        });
+    }
+
+    /// Adds a sample, making room for it if nothing is going to process the samples.
+    fn push_sample(&mut self, sample: Sample<'gc>) {
+        if self.callback.is_none() && self.samples.len() >= MAX_SAMPLES {
+            // Throw away a whole buffer at once, so that this doesn't happen for every sample.
+            forget_objects(
+                &mut self.tracked_objects,
+                &self.samples[..SAMPLE_BUFFER_SIZE],
+            );
+            self.samples.drain(..SAMPLE_BUFFER_SIZE);
+        }
+        self.samples.push(sample);
+    }
+
+    /// Returns the recorded samples, after adding samples for any tracked objects that have
+    /// been collected since the last time.
+    pub fn samples(&mut self, mc: &Mutation<'gc>, time: f64) -> &[Sample<'gc>] {
+        let samples = &mut self.samples;
+        self.tracked_objects.retain(|&id, tracked| {
+            if tracked.object.upgrade(mc).is_some() {
+                return true;
+            }
+            samples.push(Sample::DeleteObject {
+                time,
+                stack: Vec::new(),
+                id: id as f64,
+                size: tracked.size,
+            });
+            false
+        });
+        &self.samples
+    }
+
+    /// Finds an object allocated while sampling, if it hasn't been collected yet.
+    pub fn tracked_object(&self, mc: &Mutation<'gc>, id: f64) -> Option<Object<'gc>> {
+        self.tracked_objects
+            .get(&(id as u64))
+            .and_then(|tracked| tracked.object.upgrade(mc))
+    }
+
+    /// How often a method was called while sampling.
+    pub fn invocation_count(&self, method: Method<'gc>) -> u32 {
+        self.invocation_counts
+            .get(&method)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// The time since the player started, in microseconds, as used for sample times.
+pub fn sample_time(context: &UpdateContext<'_>) -> f64 {
+    Instant::now()
+        .duration_since(context.start_time)
+        .as_micros() as f64
+}
+
+/// Records a call to a method if sampling is running.
+///
+/// This must be called after the method has been pushed onto the call stack.
+pub fn record_call<'gc>(context: &mut UpdateContext<'gc>, method: Method<'gc>) {
+    if !context.avm2.sampler.is_active() {
+        return;
+    }
+
+    let time = sample_time(context);
+    let call_stack = context.avm2.call_stack();
+    context
+        .avm2
+        .sampler
+        .record_call(method, &call_stack.borrow(), time);
+}
+
+/// Records the allocation of an object if sampling is running, letting the sampler callback
+/// process the samples once the sample buffer is full.
+pub fn record_allocation<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+    class: ClassObject<'gc>,
+) -> Result<(), Error<'gc>> {
+    if !activation.avm2().sampler.is_active() {
+        return Ok(());
+    }
+
+    let time = sample_time(activation.context);
+    let call_stack = activation.avm2().call_stack();
+    activation
+        .avm2()
+        .sampler
+        .record_allocation(object, class, &call_stack.borrow(), time);
+
+    let sampler = &mut activation.avm2().sampler;
+    if let Some(callback) = sampler.callback().filter(|_| sampler.is_buffer_full()) {
+        // Sampling is paused while the callback runs, so that it doesn't sample itself.
+        let state = sampler.state();
+        sampler.pause();
+        let paused_state = sampler.state();
+        let result = Value::from(callback).call(activation, Value::Null, &[]);
+
+        // The callback may have started, paused or stopped sampling itself.
+        let sampler = &mut activation.avm2().sampler;
+        if sampler.state() == paused_state {
+            sampler.set_state(state);
+        }
+        result?;
+    }
+    Ok(())
+}
+
+/// Stops tracking the objects allocated in the given samples, as they are being thrown away.
+/// These objects won't get a `DeleteObject` sample anymore.
+fn forget_objects(tracked_objects: &mut BTreeMap<u64, TrackedObject<'_>>, samples: &[Sample<'_>]) {
+    for sample in samples {
+        if let Sample::NewObject { id, .. } = sample {
+            tracked_objects.remove(&(*id as u64));
+        }
+    }
+}
+
+/// Copies the call stack, with the innermost call first.
+fn capture_stack<'gc>(call_stack: &CallStack<'gc>) -> Vec<CallNode<'gc>> {
+    call_stack.calls().iter().rev().cloned().collect()
+}
+
+/// Estimates the memory used by an object, not counting the objects it refers to.
+fn object_size(object: Object<'_>) -> usize {
+    let slots = object.vtable().default_slots().len();
+    let dynamic_properties = object.base().values().as_hashmap().len();
+    let contents = if let Some(array) = object.as_array_storage() {
+        array.length() * size_of::<Value>()
+    } else if let Some(bytes) = object.as_bytearray() {
+        bytes.len()
+    } else {
+        0
+    };
+
+    size_of::<ScriptObjectData>()
+        + slots * size_of::<Value>()
+        + dynamic_properties * 2 * size_of::<Value>()
+        + contents
+}
+
+/// Estimates the memory used by a value.
+pub fn value_size(value: Value<'_>) -> usize {
+    match value {
+        Value::Undefined | Value::Null => 0,
+        Value::Bool(_) | Value::Integer(_) => 4,
+        Value::Number(_) => 8,
+        Value::String(string) => size_of::<Value>() + string.len() * 2,
+        Value::Object(object) => object_size(object),
+    }
+}
@@ -1,5 +1,6 @@
 pub mod audio;
 pub mod log;
+pub mod media_capture;
 pub mod navigator;
 pub mod storage;
 pub mod ui;
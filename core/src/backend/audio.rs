@@ -1,13 +1,15 @@
 use crate::{
     avm1::{NativeObject, Object as Avm1Object, TObject as _},
-    avm2::{Avm2, EventObject as Avm2EventObject, SoundChannelObject},
+    avm2::{object::SoundObject, Avm2, EventObject as Avm2EventObject, SoundChannelObject},
     buffer::Substream,
     context::UpdateContext,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
+    tag_utils::SwfMovie,
 };
 use downcast_rs::Downcast;
 use gc_arena::Collect;
 use slotmap::{new_key_type, Key, SlotMap};
+use std::sync::Arc;
 
 #[cfg(feature = "audio")]
 pub mod decoders;
@@ -93,6 +95,49 @@ pub trait AudioBackend: Downcast {
     /// Registers MP3 audio from an external source.
     fn register_mp3(&mut self, data: &[u8]) -> Result<SoundHandle, DecodeError>;
 
+    /// Registers raw PCM audio from an external source, such as `Sound.loadPCMFromByteArray`.
+    /// `data` holds 16-bit little-endian samples, with the channels interleaved if `is_stereo`
+    /// is set.
+    fn register_pcm(
+        &mut self,
+        data: &[u8],
+        sample_rate: u16,
+        is_stereo: bool,
+    ) -> Result<SoundHandle, RegisterError> {
+        let frame_size = if is_stereo { 4 } else { 2 };
+        self.register_sound(&swf::Sound {
+            id: 0,
+            format: swf::SoundFormat {
+                compression: swf::AudioCompression::Uncompressed,
+                sample_rate,
+                is_stereo,
+                is_16_bit: true,
+            },
+            num_samples: (data.len() / frame_size) as u32,
+            data,
+        })
+    }
+
+    /// Registers 32-bit float PCM audio from an external source, such as `Sound.loadPCMFromByteArray`.
+    /// `data` holds the samples, with the channels interleaved if `is_stereo` is set.
+    ///
+    /// Backends that don't keep float samples convert them to 16-bit and use `register_pcm`.
+    fn register_float_pcm(
+        &mut self,
+        data: &[f32],
+        sample_rate: u16,
+        is_stereo: bool,
+    ) -> Result<SoundHandle, RegisterError> {
+        let data: Vec<u8> = data
+            .iter()
+            .flat_map(|sample| {
+                let sample = (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16;
+                sample.to_le_bytes()
+            })
+            .collect();
+        self.register_pcm(&data, sample_rate, is_stereo)
+    }
+
     /// Plays a sound.
     fn start_sound(
         &mut self,
@@ -130,6 +175,46 @@ pub trait AudioBackend: Downcast {
         stream_info: &SoundStreamInfo,
     ) -> Result<SoundInstanceHandle, DecodeError>;
 
+    /// Starts playing a "dynamic" sound, whose audio is generated by the movie while it plays,
+    /// such as a `Sound` with `sampleData` listeners.
+    ///
+    /// The sound plays the samples given to `append_dynamic_samples`, and plays silence whenever
+    /// it runs out of them.
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+        Ok(SoundInstanceHandle::null())
+    }
+
+    /// Adds 44.1KHz stereo samples to the end of a playing dynamic sound.
+    ///
+    /// If `is_last` is set, the sound ends once all of its samples have played.
+    fn append_dynamic_samples(
+        &mut self,
+        _instance: SoundInstanceHandle,
+        _samples: &[[f32; 2]],
+        _is_last: bool,
+    ) {
+    }
+
+    /// Get the number of samples of a dynamic sound that are waiting to be played.
+    /// Returns `None` if the sound is not/no longer playing, or if this backend doesn't play
+    /// dynamic sounds.
+    fn get_dynamic_sound_buffered(&self, _instance: SoundInstanceHandle) -> Option<usize> {
+        None
+    }
+
+    /// Decodes part of a registered sound into 44.1KHz stereo samples, for `Sound.extract`.
+    /// `start` and `num_samples` are measured in 44.1KHz sample frames.
+    /// Fewer samples are returned if the sound ends first, or none at all if this backend
+    /// doesn't decode sounds.
+    fn extract_sound(
+        &self,
+        _sound: SoundHandle,
+        _start: u32,
+        _num_samples: u32,
+    ) -> Result<Vec<[f32; 2]>, DecodeError> {
+        Ok(vec![])
+    }
+
     /// Stops a playing sound instance.
     /// No-op if the sound is not playing.
     fn stop_sound(&mut self, sound: SoundInstanceHandle);
@@ -360,6 +445,12 @@ impl<'gc> AudioManager<'gc> {
     /// The default timeline stream buffer time in seconds.
     pub const DEFAULT_STREAM_BUFFER_TIME: i32 = 5;
 
+    /// The fewest samples a `sampleData` listener can provide without ending its sound.
+    pub const MIN_DYNAMIC_SAMPLES: usize = 2048;
+
+    /// The most samples a `sampleData` listener can provide at once.
+    pub const MAX_DYNAMIC_SAMPLES: usize = 8192;
+
     /// The threshold in seconds where an audio stream is considered too out-of-sync and will be stopped.
     pub const STREAM_RESTART_THRESHOLD: f64 = 1.0;
 
@@ -379,6 +470,8 @@ impl<'gc> AudioManager<'gc> {
 
     /// Update state of active sounds. Should be called once per frame.
     pub fn update_sounds(context: &mut UpdateContext<'gc>) {
+        Self::request_dynamic_samples(context);
+
         // We can't use 'context' to construct an event inside the
         // 'retain()' closure, so we queue the events up here, and fire
         // them after running 'retain()'
@@ -438,6 +531,79 @@ impl<'gc> AudioManager<'gc> {
         context.audio_manager.update_sound_transforms(context.audio);
     }
 
+    /// Asks the `sampleData` listeners of dynamic sounds for more samples, until each of them
+    /// has enough buffered to keep playing until the next frame.
+    fn request_dynamic_samples(context: &mut UpdateContext<'gc>) {
+        // A frame rate of 0 runs a frame on every tick.
+        let frame_rate = *context.frame_rate;
+        let samples_per_frame = if frame_rate > 0.0 {
+            (44100.0 / frame_rate) as usize
+        } else {
+            0
+        };
+        // However long a frame lasts, keep a little more than it needs buffered.
+        let wanted_samples = samples_per_frame.saturating_add(Self::MIN_DYNAMIC_SAMPLES);
+
+        let dynamic_sounds: Vec<_> = context
+            .audio_manager
+            .sounds
+            .iter()
+            .filter_map(|sound| {
+                let dynamic = sound.dynamic.as_ref()?;
+                (!dynamic.is_finished).then_some((sound.instance, dynamic.source))
+            })
+            .collect();
+
+        for (instance, source) in dynamic_sounds {
+            while let Some(buffered) = context.audio.get_dynamic_sound_buffered(instance) {
+                if buffered >= wanted_samples {
+                    break;
+                }
+
+                // Listeners may stop the sound, so look it up again every time.
+                let Some(position) = context
+                    .audio_manager
+                    .sounds
+                    .iter()
+                    .find(|sound| sound.instance == instance)
+                    .and_then(|sound| sound.dynamic.as_ref())
+                    .map(|dynamic| dynamic.position)
+                else {
+                    break;
+                };
+
+                let mut samples = source.request_sample_data(context, position);
+                if samples.len() > Self::MAX_DYNAMIC_SAMPLES {
+                    tracing::warn!(
+                        "sampleData: {} samples provided, only using the first {}",
+                        samples.len(),
+                        Self::MAX_DYNAMIC_SAMPLES
+                    );
+                    samples.truncate(Self::MAX_DYNAMIC_SAMPLES);
+                }
+                // Providing too few samples ends the sound once they've played.
+                let is_last = samples.len() < Self::MIN_DYNAMIC_SAMPLES;
+                context
+                    .audio
+                    .append_dynamic_samples(instance, &samples, is_last);
+
+                if let Some(dynamic) = context
+                    .audio_manager
+                    .sounds
+                    .iter_mut()
+                    .find(|sound| sound.instance == instance)
+                    .and_then(|sound| sound.dynamic.as_mut())
+                {
+                    dynamic.position += samples.len() as f64;
+                    dynamic.is_finished = is_last;
+                }
+                if is_last {
+                    break;
+                }
+            }
+        }
+    }
+
     /// Starts a sound and optionally associates it with a Display Object.
     /// Sounds associated with DOs are an AVM1/Timeline concept and should not be called from AVM2 scripts.
     pub fn start_sound(
@@ -458,6 +624,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object,
                 avm2_object: None,
                 stream_start_frame: None,
+                movie: None,
+                dynamic: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -467,10 +635,44 @@ impl<'gc> AudioManager<'gc> {
         }
     }
 
+    /// Starts a sound whose samples are generated by the `sampleData` listeners of an AVM2 `Sound`.
+    pub fn start_dynamic_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        source: SoundObject<'gc>,
+    ) -> Option<SoundInstanceHandle> {
+        if self.sounds.len() < Self::MAX_SOUNDS {
+            let handle = audio.start_dynamic_sound().ok()?;
+            let instance = SoundInstance {
+                sound: None,
+                instance: handle,
+                display_object: None,
+                transform: display_object::SoundTransform::default(),
+                avm1_object: None,
+                avm2_object: None,
+                stream_start_frame: None,
+                movie: None,
+                dynamic: Some(DynamicSound {
+                    source,
+                    position: 0.0,
+                    is_finished: false,
+                }),
+            };
+            audio.set_sound_transform(handle, self.transform_for_sound(&instance));
+            self.sounds.push(instance);
+            Some(handle)
+        } else {
+            None
+        }
+    }
+
+    /// Associates a sound instance with the AVM2 `SoundChannel` controlling it,
+    /// and with the movie whose script played it.
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
         avm2_object: SoundChannelObject<'gc>,
+        movie: Arc<SwfMovie>,
     ) {
         if let Some(i) = self
             .sounds
@@ -479,6 +681,7 @@ impl<'gc> AudioManager<'gc> {
         {
             let instance = &mut self.sounds[i];
             instance.avm2_object = Some(avm2_object);
+            instance.movie = Some(movie);
         }
     }
 
@@ -505,6 +708,22 @@ impl<'gc> AudioManager<'gc> {
         });
     }
 
+    /// Stops any sound played by the scripts of the given movie.
+    pub fn stop_sounds_for_movie(&mut self, audio: &mut dyn AudioBackend, movie: &Arc<SwfMovie>) {
+        self.sounds.retain(move |other| {
+            if other
+                .movie
+                .as_ref()
+                .is_some_and(|other| Arc::ptr_eq(other, movie))
+            {
+                audio.stop_sound(other.instance);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
     /// Stops any sound associated with the given Display Object.
     /// Sounds associated with DOs are an AVM1/Timeline concept and should not be called from AVM2 scripts.
     pub fn stop_sounds_with_display_object(
@@ -583,6 +802,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: Some(clip_frame),
+                movie: None,
+                dynamic: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -609,6 +830,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: None,
+                movie: None,
+                dynamic: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -824,6 +1047,28 @@ pub struct SoundInstance<'gc> {
     avm2_object: Option<SoundChannelObject<'gc>>,
 
     stream_start_frame: Option<u16>,
+
+    /// The movie whose script played this sound, if it was played by an AVM2 `Sound`.
+    #[collect(require_static)]
+    movie: Option<Arc<SwfMovie>>,
+
+    /// The state of this sound, if its samples are generated by `sampleData` listeners.
+    dynamic: Option<DynamicSound<'gc>>,
+}
+
+/// The state of a sound whose samples are generated by the `sampleData` listeners of a `Sound`.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+struct DynamicSound<'gc> {
+    /// The `Sound` that `sampleData` events are dispatched to.
+    source: SoundObject<'gc>,
+
+    /// The number of samples provided by listeners so far.
+    /// This is given to listeners as `SampleDataEvent.position`.
+    position: f64,
+
+    /// Whether a listener provided too few samples, ending the sound.
+    is_finished: bool,
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
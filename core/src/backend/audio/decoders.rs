@@ -14,7 +14,7 @@ pub use adpcm::AdpcmDecoder;
 pub use mp3::{mp3_metadata, Mp3Decoder};
 #[cfg(feature = "nellymoser")]
 pub use nellymoser::NellymoserDecoder;
-pub use pcm::PcmDecoder;
+pub use pcm::{FloatPcmDecoder, PcmDecoder};
 
 use crate::backend::audio::{SoundStreamInfo, SoundStreamWrapping};
 use crate::buffer::{Slice, Substream, SubstreamChunksIter};
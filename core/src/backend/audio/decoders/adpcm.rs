@@ -116,35 +116,37 @@ impl<R: Read> Iterator for AdpcmDecoder<R> {
     type Item = [i16; 2];
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.sample_num == 0 {
+        // Each packet of 4096 samples starts with its first sample, followed by 4095 codes.
+        let is_packet_start = self.sample_num == 0;
+        self.sample_num = (self.sample_num + 1) % 4096;
+
+        if is_packet_start {
             // The initial sample values are NOT byte-aligned.
             for channel in &mut self.channels {
                 channel.sample = self.inner.read_signed(16).ok()?;
                 channel.step_index = self.inner.read::<u16>(6).ok()? as i16;
             }
-        }
-
-        self.sample_num = (self.sample_num + 1) % 4095;
-
-        for channel in &mut self.channels {
-            let step = STEP_TABLE[channel.step_index as usize];
+        } else {
+            for channel in &mut self.channels {
+                let step = STEP_TABLE[channel.step_index as usize];
 
-            // `data` is sign-magnitude, NOT two's complement.
-            let data = self.inner.read::<u32>(self.bits_per_sample as u32).ok()?;
-            let sign_mask = 1 << (self.bits_per_sample - 1);
-            let magnitude = data & !sign_mask;
+                // `data` is sign-magnitude, NOT two's complement.
+                let data = self.inner.read::<u32>(self.bits_per_sample as u32).ok()?;
+                let sign_mask = 1 << (self.bits_per_sample - 1);
+                let magnitude = data & !sign_mask;
 
-            // (data + 0.5) * step / 2^(bits_per_sample - 2)
-            let delta = (self.decoder)(step, magnitude);
+                // (data + 0.5) * step / 2^(bits_per_sample - 2)
+                let delta = (self.decoder)(step, magnitude);
 
-            channel.sample = if (data & sign_mask) != 0 {
-                (channel.sample as i32 - delta as i32).max(i16::MIN.into())
-            } else {
-                (channel.sample as i32 + delta as i32).min(i16::MAX.into())
-            } as i16;
+                channel.sample = if (data & sign_mask) != 0 {
+                    (channel.sample as i32 - delta as i32).max(i16::MIN.into())
+                } else {
+                    (channel.sample as i32 + delta as i32).min(i16::MAX.into())
+                } as i16;
 
-            channel.step_index += INDEX_TABLE[self.bits_per_sample - 2][magnitude as usize];
-            channel.step_index = channel.step_index.clamp(0, STEP_TABLE.len() as i16 - 1);
+                channel.step_index += INDEX_TABLE[self.bits_per_sample - 2][magnitude as usize];
+                channel.step_index = channel.step_index.clamp(0, STEP_TABLE.len() as i16 - 1);
+            }
         }
 
         let left = self.channels[0].sample;
@@ -1,5 +1,6 @@
 use super::{Decoder, SeekableDecoder};
 use byteorder::{LittleEndian, ReadBytesExt};
+use dasp::Sample;
 use std::io::{Cursor, Read};
 
 /// Decoder for PCM audio data in a Flash file.
@@ -75,3 +76,70 @@ impl<R: AsRef<[u8]> + Send + Sync> SeekableDecoder for PcmDecoder<Cursor<R>> {
         self.inner.set_position(pos);
     }
 }
+
+/// Decoder for 32-bit float PCM audio data, as given to `Sound.loadPCMFromByteArray`.
+/// The samples are little-endian, and are converted to 16-bit as they're played.
+pub struct FloatPcmDecoder<R: Read> {
+    inner: R,
+    sample_rate: u16,
+    is_stereo: bool,
+}
+
+impl<R: Read> FloatPcmDecoder<R> {
+    pub fn new(inner: R, is_stereo: bool, sample_rate: u16) -> Self {
+        Self {
+            inner,
+            is_stereo,
+            sample_rate,
+        }
+    }
+
+    /// Reads the next sample frame, without converting it to 16-bit.
+    pub fn next_frame(&mut self) -> Option<[f32; 2]> {
+        let left = self.inner.read_f32::<LittleEndian>().ok()?;
+        let right = if self.is_stereo {
+            self.inner.read_f32::<LittleEndian>().ok()?
+        } else {
+            left
+        };
+        Some([left, right])
+    }
+}
+
+impl<R: Read> Iterator for FloatPcmDecoder<R> {
+    type Item = [i16; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let [left, right] = self.next_frame()?;
+        Some([left.to_sample(), right.to_sample()])
+    }
+}
+
+impl<R: Read + Send + Sync> Decoder for FloatPcmDecoder<R> {
+    #[inline]
+    fn num_channels(&self) -> u8 {
+        if self.is_stereo {
+            2
+        } else {
+            1
+        }
+    }
+
+    #[inline]
+    fn sample_rate(&self) -> u16 {
+        self.sample_rate
+    }
+}
+
+impl<R: AsRef<[u8]> + Send + Sync> SeekableDecoder for FloatPcmDecoder<Cursor<R>> {
+    #[inline]
+    fn reset(&mut self) {
+        self.inner.set_position(0);
+    }
+
+    #[inline]
+    fn seek_to_sample_frame(&mut self, frame: u32) {
+        let pos = u64::from(frame) * u64::from(self.num_channels()) * 4;
+        self.inner.set_position(pos);
+    }
+}
@@ -1,9 +1,10 @@
-use super::decoders::{self, AdpcmDecoder, Decoder, PcmDecoder, SeekableDecoder};
+use super::decoders::{self, AdpcmDecoder, Decoder, FloatPcmDecoder, PcmDecoder, SeekableDecoder};
 use super::{SoundHandle, SoundInstanceHandle, SoundStreamInfo, SoundTransform};
 use crate::backend::audio::{DecodeError, RegisterError};
 use crate::buffer::Substream;
 use crate::tag_utils::SwfSlice;
-use slotmap::SlotMap;
+use slotmap::{SecondaryMap, SlotMap};
+use std::collections::VecDeque;
 use std::io::Cursor;
 use std::sync::{Arc, Mutex, RwLock};
 use swf::AudioCompression;
@@ -70,6 +71,9 @@ pub struct AudioMixer {
 
     /// The last two windows of output samples.
     output_memory: Arc<RwLock<CircBuf>>,
+
+    /// The sample buffers of playing dynamic sounds, which the movie appends to as they play.
+    dynamic_sounds: SecondaryMap<SoundInstanceHandle, Arc<Mutex<DynamicSoundBuffer>>>,
 }
 
 /// An audio stream.
@@ -159,6 +163,10 @@ struct Sound {
     /// `skip_sample_frames` indicates how many sample frames to skip to bypass the delay.
     /// This is `0` unless `format.compression` is `AudioCompression::Mp3`.
     skip_sample_frames: u16,
+
+    /// Whether `data` holds 32-bit float PCM, as registered by `register_float_pcm`.
+    /// SWF files have no such format, so `format` describes it as uncompressed.
+    is_float: bool,
 }
 
 /// An actively playing instance of a sound.
@@ -245,6 +253,7 @@ impl AudioMixer {
             num_output_channels,
             output_sample_rate,
             output_memory: Arc::new(RwLock::new(CircBuf::new())),
+            dynamic_sounds: SecondaryMap::new(),
         }
     }
 
@@ -294,9 +303,17 @@ impl AudioMixer {
     ///  * "Event" sounds on the timeline with custom start/loop settings
     ///  * ActionScript sounds that may have a custom start and loop setting
     fn make_seekable_decoder(
-        format: &swf::SoundFormat,
+        sound: &Sound,
         data: Cursor<ArcAsRef>,
     ) -> Result<Box<dyn SeekableDecoder>, decoders::Error> {
+        let format = &sound.format;
+        if sound.is_float {
+            return Ok(Box::new(FloatPcmDecoder::new(
+                data,
+                format.is_stereo,
+                format.sample_rate,
+            )));
+        }
         let decoder: Box<dyn SeekableDecoder> = match format.compression {
             AudioCompression::UncompressedUnknownEndian => {
                 // Cross fingers that it's little endian.
@@ -332,17 +349,22 @@ impl AudioMixer {
     }
 
     /// Transforms a `Stream` into a new `Stream` that matches the output sample rate.
-    fn make_resampler(&self, mut stream: impl Stream) -> impl Stream {
+    fn make_resampler(&self, stream: impl Stream) -> impl Stream {
+        Self::resample(stream, self.output_sample_rate)
+    }
+
+    /// Transforms a `Stream` into a new `Stream` with the given sample rate.
+    fn resample(mut stream: impl Stream, sample_rate: u32) -> impl Stream {
         // TODO: Allow interpolator to be user-configurable?
         let left = stream.next();
         let right = stream.next();
         let interpolator = dasp::interpolate::linear::Linear::new(left, right);
-        let sample_rate = stream.source_sample_rate().into();
+        let source_sample_rate = stream.source_sample_rate().into();
         ConverterStream(dasp::signal::interpolate::Converter::from_hz_to_hz(
             stream,
             interpolator,
-            sample_rate,
-            self.output_sample_rate.into(),
+            source_sample_rate,
+            sample_rate.into(),
         ))
     }
 
@@ -357,7 +379,7 @@ impl AudioMixer {
         data: Cursor<ArcAsRef>,
     ) -> Result<Box<dyn Stream>, DecodeError> {
         // Instantiate a decoder for the compression that the sound data uses.
-        let decoder = Self::make_seekable_decoder(&sound.format, data)?;
+        let decoder = Self::make_seekable_decoder(sound, data)?;
 
         // Wrap the decoder into an event sound stream (controls looping/envelope)
         let stream = EventSoundStream::new_with_settings(
@@ -522,6 +544,7 @@ impl AudioMixer {
             data: Arc::from(data),
             num_sample_frames: swf_sound.num_samples,
             skip_sample_frames,
+            is_float: false,
         };
         Ok(self.sounds.insert(sound))
     }
@@ -542,6 +565,7 @@ impl AudioMixer {
             data,
             num_sample_frames: metadata.num_sample_frames,
             skip_sample_frames: 0,
+            is_float: false,
         };
         Ok(self.sounds.insert(sound))
     }
@@ -551,6 +575,32 @@ impl AudioMixer {
         Err(decoders::Error::UnhandledCompression(AudioCompression::Mp3))
     }
 
+    /// Registers 32-bit float PCM audio with the audio mixer, keeping the samples as they are.
+    pub fn register_float_pcm(
+        &mut self,
+        data: &[f32],
+        sample_rate: u16,
+        is_stereo: bool,
+    ) -> Result<SoundHandle, RegisterError> {
+        let num_channels = if is_stereo { 2 } else { 1 };
+        let sound = Sound {
+            format: swf::SoundFormat {
+                compression: AudioCompression::Uncompressed,
+                sample_rate,
+                is_stereo,
+                is_16_bit: true,
+            },
+            data: data
+                .iter()
+                .flat_map(|sample| sample.to_le_bytes())
+                .collect(),
+            num_sample_frames: (data.len() / num_channels) as u32,
+            skip_sample_frames: 0,
+            is_float: true,
+        };
+        Ok(self.sounds.insert(sound))
+    }
+
     /// Starts a timeline audio stream.
     pub fn start_stream(
         &mut self,
@@ -582,6 +632,7 @@ impl AudioMixer {
         let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
         // Create a stream that decodes and resamples the sound.
         let stream = if sound.skip_sample_frames == 0
+            && !sound.is_float
             && settings.in_sample.is_none()
             && settings.out_sample.is_none()
             && settings.num_loops <= 1
@@ -622,6 +673,117 @@ impl AudioMixer {
         Ok(handle)
     }
 
+    /// Starts a dynamic sound, which plays the samples given to `append_dynamic_samples`.
+    pub fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+        let buffer = Arc::new(Mutex::new(DynamicSoundBuffer::default()));
+        let stream = Box::new(self.make_resampler(DynamicSoundStream::new(Arc::clone(&buffer))));
+
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        // Forget the buffers of any dynamic sounds that have finished since.
+        self.dynamic_sounds
+            .retain(|instance, _| sound_instances.contains_key(instance));
+        let handle = sound_instances.insert(SoundInstance::new_stream(stream));
+        self.dynamic_sounds.insert(handle, buffer);
+        Ok(handle)
+    }
+
+    /// Adds 44.1KHz stereo samples to the end of a playing dynamic sound.
+    ///
+    /// If `is_last` is set, the sound ends once all of its samples have played.
+    pub fn append_dynamic_samples(
+        &mut self,
+        instance: SoundInstanceHandle,
+        samples: &[[f32; 2]],
+        is_last: bool,
+    ) {
+        use dasp::Sample;
+
+        if let Some(buffer) = self.dynamic_sounds.get(instance) {
+            let mut buffer = buffer.lock().expect("Cannot be called reentrant");
+            buffer.samples.extend(
+                samples
+                    .iter()
+                    .map(|[left, right]| [left.to_sample::<i16>(), right.to_sample::<i16>()]),
+            );
+            buffer.is_finished |= is_last;
+        }
+    }
+
+    /// Decodes part of a registered sound into 44.1KHz stereo samples.
+    ///
+    /// `start` and `num_samples` are measured in 44.1KHz sample frames. Fewer samples are
+    /// returned if the sound ends first.
+    pub fn extract_sound(
+        &self,
+        sound: SoundHandle,
+        start: u32,
+        num_samples: u32,
+    ) -> Result<Vec<[f32; 2]>, DecodeError> {
+        use dasp::{signal::Signal, Sample};
+
+        let Some(sound) = self.sounds.get(sound) else {
+            return Ok(vec![]);
+        };
+        let sample_rate = u32::from(sound.format.sample_rate).max(1);
+        let length = (u64::from(sound.num_sample_frames) * 44100 / u64::from(sample_rate)) as u32;
+        let num_samples = num_samples.min(length.saturating_sub(start));
+
+        let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
+        if sound.is_float {
+            // Float PCM is read as it is, rather than converted to 16-bit as it is when played.
+            let mut decoder =
+                FloatPcmDecoder::new(data, sound.format.is_stereo, sound.format.sample_rate);
+            decoder
+                .seek_to_sample_frame((u64::from(start) * u64::from(sample_rate) / 44100) as u32);
+            let mut signal = dasp::signal::from_iter(std::iter::from_fn(|| decoder.next_frame()));
+            let left = signal.next();
+            let right = signal.next();
+            let interpolator = dasp::interpolate::linear::Linear::new(left, right);
+            let mut signal = dasp::signal::interpolate::Converter::from_hz_to_hz(
+                signal,
+                interpolator,
+                sample_rate.into(),
+                44100.0,
+            );
+            return Ok((0..num_samples).map(|_| signal.next()).collect());
+        }
+
+        // Decode the sound the same way as it plays, but from the requested start,
+        // then resample it to 44.1KHz regardless of the output sample rate.
+        let decoder = Self::make_seekable_decoder(sound, data)?;
+        let settings = swf::SoundInfo {
+            event: swf::SoundEvent::Start,
+            in_sample: Some(start),
+            out_sample: None,
+            num_loops: 1,
+            envelope: None,
+        };
+        let stream = EventSoundStream::new_with_settings(
+            decoder,
+            &settings,
+            sound.num_sample_frames,
+            sound.skip_sample_frames,
+        );
+        let mut stream = Self::resample(stream, 44100);
+        Ok((0..num_samples)
+            .map(|_| stream.next().map(|sample| sample.to_sample::<f32>()))
+            .collect())
+    }
+
+    /// Returns the number of samples of a playing dynamic sound that are waiting to be played.
+    pub fn get_dynamic_sound_buffered(&self, instance: SoundInstanceHandle) -> Option<usize> {
+        self.dynamic_sounds.get(instance).map(|buffer| {
+            buffer
+                .lock()
+                .expect("Cannot be called reentrant")
+                .samples
+                .len()
+        })
+    }
+
     /// Stops a playing sound instance.
     pub fn stop_sound(&mut self, sound: SoundInstanceHandle) {
         let mut sound_instances = self
@@ -629,6 +791,7 @@ impl AudioMixer {
             .lock()
             .expect("Cannot be called reentrant");
         sound_instances.remove(sound);
+        self.dynamic_sounds.remove(sound);
     }
 
     pub fn stop_all_sounds(&mut self) {
@@ -637,6 +800,7 @@ impl AudioMixer {
             .lock()
             .expect("Cannot be called reentrant");
         sound_instances.clear();
+        self.dynamic_sounds.clear();
     }
 
     /// Returns the position of a playing sound in milliseconds.
@@ -777,6 +941,69 @@ impl Default for ArcAsRef {
     }
 }
 
+/// The samples of a dynamic sound that are yet to be played.
+#[derive(Default)]
+struct DynamicSoundBuffer {
+    samples: VecDeque<[i16; 2]>,
+
+    /// Whether the sound ends once `samples` runs out, instead of waiting for more.
+    is_finished: bool,
+}
+
+/// A stream for dynamic sounds, whose samples are generated by the movie while they play.
+///
+/// The stream plays silence whenever it runs out of samples, until it is told that no more are coming.
+struct DynamicSoundStream {
+    buffer: Arc<Mutex<DynamicSoundBuffer>>,
+    position: u32,
+    is_exhausted: bool,
+}
+
+impl DynamicSoundStream {
+    /// Dynamic sounds are always 44.1KHz.
+    const SAMPLE_RATE: u16 = 44100;
+
+    fn new(buffer: Arc<Mutex<DynamicSoundBuffer>>) -> Self {
+        Self {
+            buffer,
+            position: 0,
+            is_exhausted: false,
+        }
+    }
+}
+
+impl Stream for DynamicSoundStream {
+    #[inline]
+    fn source_position(&self) -> u32 {
+        self.position
+    }
+
+    #[inline]
+    fn source_sample_rate(&self) -> u16 {
+        Self::SAMPLE_RATE
+    }
+}
+
+impl dasp::signal::Signal for DynamicSoundStream {
+    type Frame = [i16; 2];
+
+    fn next(&mut self) -> [i16; 2] {
+        let mut buffer = self.buffer.lock().expect("Cannot be called reentrant");
+        if let Some(frame) = buffer.samples.pop_front() {
+            self.position += 1;
+            frame
+        } else {
+            self.is_exhausted = buffer.is_finished;
+            Default::default()
+        }
+    }
+
+    #[inline]
+    fn is_exhausted(&self) -> bool {
+        self.is_exhausted
+    }
+}
+
 /// A stream for event sound instances with custom envelopes, start/end point, or loop settings.
 struct EventSoundStream {
     decoder: Box<dyn SeekableDecoder>,
@@ -1078,6 +1305,16 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.register_mp3(data)
         }
 
+        #[inline]
+        fn register_float_pcm(
+            &mut self,
+            data: &[f32],
+            sample_rate: u16,
+            is_stereo: bool,
+        ) -> Result<SoundHandle, RegisterError> {
+            self.$mixer.register_float_pcm(data, sample_rate, is_stereo)
+        }
+
         #[inline]
         fn start_stream(
             &mut self,
@@ -1110,6 +1347,37 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.stop_sound(sound)
         }
 
+        #[inline]
+        fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, DecodeError> {
+            self.$mixer.start_dynamic_sound()
+        }
+
+        #[inline]
+        fn append_dynamic_samples(
+            &mut self,
+            instance: SoundInstanceHandle,
+            samples: &[[f32; 2]],
+            is_last: bool,
+        ) {
+            self.$mixer
+                .append_dynamic_samples(instance, samples, is_last)
+        }
+
+        #[inline]
+        fn get_dynamic_sound_buffered(&self, instance: SoundInstanceHandle) -> Option<usize> {
+            self.$mixer.get_dynamic_sound_buffered(instance)
+        }
+
+        #[inline]
+        fn extract_sound(
+            &self,
+            sound: SoundHandle,
+            start: u32,
+            num_samples: u32,
+        ) -> Result<Vec<[f32; 2]>, DecodeError> {
+            self.$mixer.extract_sound(sound, start, num_samples)
+        }
+
         #[inline]
         fn stop_all_sounds(&mut self) {
             self.$mixer.stop_all_sounds()
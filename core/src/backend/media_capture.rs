@@ -0,0 +1,147 @@
+//! Capture of video and sound from cameras and microphones.
+
+use downcast_rs::{impl_downcast, Downcast};
+use std::time::Duration;
+
+/// The size and frame rate a camera captures at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraMode {
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+}
+
+impl Default for CameraMode {
+    /// The mode cameras capture at until a movie calls `Camera.setMode`.
+    fn default() -> Self {
+        Self {
+            width: 160,
+            height: 120,
+            fps: 15.0,
+        }
+    }
+}
+
+/// A frame captured by a camera.
+#[derive(Clone, Debug)]
+pub struct CapturedFrame {
+    pub width: u32,
+    pub height: u32,
+
+    /// The pixels of the frame, in 24-bit RGB.
+    pub rgb: Vec<u8>,
+}
+
+/// Converts mono samples from the rate of a device to the rate a movie asked for.
+pub struct Resampler {
+    /// The number of device samples per output sample.
+    step: f64,
+
+    /// The position of the next output sample, after the previous device sample.
+    position: f64,
+
+    previous: f32,
+}
+
+impl Resampler {
+    pub fn new(device_rate: u32, sample_rate: u32) -> Self {
+        Self {
+            step: f64::from(device_rate) / f64::from(sample_rate),
+            position: 0.0,
+            previous: 0.0,
+        }
+    }
+
+    pub fn process(&mut self, input: impl Iterator<Item = f32>, output: &mut Vec<f32>) {
+        for sample in input {
+            while self.position < 1.0 {
+                output.push(self.previous + (sample - self.previous) * self.position as f32);
+                self.position += self.step;
+            }
+            self.position -= 1.0;
+            self.previous = sample;
+        }
+    }
+}
+
+/// Access to the cameras and microphones of the machine.
+///
+/// Devices are polled once per tick while a movie uses them. The time elapsed since the last
+/// poll is passed along, so that virtual devices can produce frames and samples at the right
+/// pace.
+pub trait MediaCaptureBackend: Downcast {
+    /// The names of the cameras that can be captured.
+    fn camera_names(&self) -> Vec<String>;
+
+    /// Starts capturing a camera, in the supported mode closest to the requested one.
+    ///
+    /// Returns the mode the camera captures at, or `None` if it can't be captured
+    /// (for example because the user denied access to it).
+    fn start_camera(&mut self, index: usize, mode: CameraMode) -> Option<CameraMode>;
+
+    /// Stops capturing a camera.
+    fn stop_camera(&mut self, index: usize);
+
+    /// The latest frame captured by a camera since the last poll, if any.
+    fn camera_frame(&mut self, index: usize, elapsed: Duration) -> Option<CapturedFrame>;
+
+    /// The names of the microphones that can be captured.
+    fn microphone_names(&self) -> Vec<String>;
+
+    /// Starts capturing a microphone at the given sample rate, in Hz.
+    ///
+    /// Returns `false` if it can't be captured.
+    fn start_microphone(&mut self, index: usize, sample_rate: u32) -> bool;
+
+    /// Stops capturing a microphone.
+    fn stop_microphone(&mut self, index: usize);
+
+    /// The mono samples, from -1 to 1, captured by a microphone since the last poll.
+    fn microphone_samples(&mut self, index: usize, elapsed: Duration) -> Vec<f32>;
+}
+impl_downcast!(MediaCaptureBackend);
+
+/// MediaCaptureBackend without any camera or microphone.
+pub struct NullMediaCaptureBackend {}
+
+impl NullMediaCaptureBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl MediaCaptureBackend for NullMediaCaptureBackend {
+    fn camera_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    fn start_camera(&mut self, _index: usize, _mode: CameraMode) -> Option<CameraMode> {
+        None
+    }
+
+    fn stop_camera(&mut self, _index: usize) {}
+
+    fn camera_frame(&mut self, _index: usize, _elapsed: Duration) -> Option<CapturedFrame> {
+        None
+    }
+
+    fn microphone_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    fn start_microphone(&mut self, _index: usize, _sample_rate: u32) -> bool {
+        false
+    }
+
+    fn stop_microphone(&mut self, _index: usize) {}
+
+    fn microphone_samples(&mut self, _index: usize, _elapsed: Duration) -> Vec<f32> {
+        vec![]
+    }
+}
+
+impl Default for NullMediaCaptureBackend {
+    fn default() -> Self {
+        NullMediaCaptureBackend::new()
+    }
+}
@@ -1,5 +1,6 @@
 //! Browser-related platform functions
 
+use crate::datagram_socket::{Datagram, DatagramSocketAction, DatagramSocketHandle};
 use crate::loader::Error;
 use crate::socket::{ConnectionState, SocketAction, SocketHandle};
 use crate::string::WStr;
@@ -301,6 +302,31 @@ pub trait NavigatorBackend: Downcast {
         receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
     );
+
+    /// Handle any request to bind a UDP socket
+    ///
+    /// Use [DatagramSocketAction::Bound] or [DatagramSocketAction::BindFailed] to notify AVM
+    /// whether the socket could be bound.
+    ///
+    /// Packets to send arrive through the receiver, addressed to their destination.
+    ///
+    /// Use [DatagramSocketAction::Data] to send received packets to AVM side,
+    /// addressed from their source.
+    ///
+    /// When the Sender of the Receiver is dropped then this task should end.
+    fn bind_datagram_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        handle: DatagramSocketHandle,
+        receiver: Receiver<Datagram>,
+        sender: Sender<DatagramSocketAction>,
+    );
+
+    /// Whether `bind_datagram_socket` can bind sockets at all.
+    fn supports_datagram_sockets(&self) -> bool {
+        false
+    }
 }
 impl_downcast!(NavigatorBackend);
 
@@ -445,6 +471,19 @@ impl NavigatorBackend for NullNavigatorBackend {
             .try_send(SocketAction::Connect(handle, ConnectionState::Failed))
             .expect("working channel send");
     }
+
+    fn bind_datagram_socket(
+        &mut self,
+        _host: String,
+        _port: u16,
+        handle: DatagramSocketHandle,
+        _receiver: Receiver<Datagram>,
+        sender: Sender<DatagramSocketAction>,
+    ) {
+        sender
+            .try_send(DatagramSocketAction::BindFailed(handle))
+            .expect("working channel send");
+    }
 }
 
 // The following functions are helper functions used in different
@@ -68,6 +68,18 @@ pub trait UiBackend: Downcast {
     /// Changes the mouse cursor image.
     fn set_mouse_cursor(&mut self, cursor: MouseCursor);
 
+    /// Registers a cursor, to be displayed when `set_mouse_cursor` is given `MouseCursor::Custom`
+    /// with the same id.
+    fn register_mouse_cursor(&mut self, id: CustomCursorId, cursor: CustomMouseCursor);
+
+    /// Forgets a cursor registered with `register_mouse_cursor`.
+    fn unregister_mouse_cursor(&mut self, id: CustomCursorId);
+
+    /// Whether cursors registered with `register_mouse_cursor` can be displayed.
+    fn supports_custom_mouse_cursors(&self) -> bool {
+        false
+    }
+
     /// Get the clipboard content
     fn clipboard_content(&mut self) -> String;
 
@@ -79,6 +91,46 @@ pub trait UiBackend: Downcast {
     /// Sets the clipboard to the given content.
     fn set_clipboard_content(&mut self, content: String);
 
+    /// Whether `set_clipboard_data` can store data of the given format on the clipboard.
+    fn supports_clipboard_format(&self, format: ClipboardFormat) -> bool {
+        format == ClipboardFormat::Text
+    }
+
+    /// Get the formats of the data on the clipboard.
+    ///
+    /// Backends that only support plain text don't need to implement the typed clipboard
+    /// methods, which default to the plain text clipboard.
+    fn clipboard_formats(&mut self) -> Vec<ClipboardFormat> {
+        if self.clipboard_content().is_empty() {
+            vec![]
+        } else {
+            vec![ClipboardFormat::Text]
+        }
+    }
+
+    /// Get the data on the clipboard in the given format, if any.
+    fn clipboard_data(&mut self, format: ClipboardFormat) -> Option<ClipboardData> {
+        match format {
+            ClipboardFormat::Text => Some(self.clipboard_content())
+                .filter(|text| !text.is_empty())
+                .map(ClipboardData::Text),
+            _ => None,
+        }
+    }
+
+    /// Replaces the content of the clipboard with the given data, which has at most one
+    /// entry per format.
+    fn set_clipboard_data(&mut self, data: Vec<ClipboardData>) {
+        let text = data
+            .into_iter()
+            .find_map(|data| match data {
+                ClipboardData::Text(text) => Some(text),
+                _ => None,
+            })
+            .unwrap_or_default();
+        self.set_clipboard_content(text);
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError>;
 
     /// Displays a message about an error during root movie download.
@@ -128,9 +180,99 @@ pub trait UiBackend: Downcast {
 
     /// Mark that any previously open dialog has been closed
     fn close_file_dialog(&mut self);
+
+    /// Sends the pages of a print job to the user, for example by saving them to a file.
+    fn print_pages(&mut self, pages: Vec<PrintedPage>);
 }
 impl_downcast!(UiBackend);
 
+/// A page of a print job, rendered to an image.
+/// Communicated from the core to the UI backend via `UiBackend::print_pages`.
+#[derive(Clone, Debug)]
+pub struct PrintedPage {
+    /// The width of the image, in pixels.
+    pub width: u32,
+
+    /// The height of the image, in pixels.
+    pub height: u32,
+
+    /// The resolution the page was rendered at, which gives the size of the printed image.
+    pub dpi: f64,
+
+    /// The pixels of the image, in RGBA order.
+    pub rgba: Vec<u8>,
+}
+
+/// A format of the data on the clipboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClipboardFormat {
+    /// Plain text.
+    /// Equivalent to AS3 `ClipboardFormats.TEXT_FORMAT`.
+    Text,
+
+    /// An HTML fragment.
+    /// Equivalent to AS3 `ClipboardFormats.HTML_FORMAT`.
+    Html,
+
+    /// A Rich Text Format document.
+    /// Equivalent to AS3 `ClipboardFormats.RICH_TEXT_FORMAT`.
+    RichText,
+
+    /// A URL.
+    /// Equivalent to AS3 `ClipboardFormats.URL_FORMAT`.
+    Url,
+
+    /// A list of files.
+    /// Equivalent to AS3 `ClipboardFormats.FILE_LIST_FORMAT`.
+    FileList,
+
+    /// An image.
+    /// Equivalent to AS3 `ClipboardFormats.BITMAP_FORMAT`.
+    Bitmap,
+}
+
+/// The data on the clipboard in one of its formats.
+/// Communicated between the core and the UI backend via `UiBackend::clipboard_data`
+/// and `UiBackend::set_clipboard_data`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClipboardData {
+    Text(String),
+    Html(String),
+    RichText(Vec<u8>),
+    Url(String),
+
+    /// The native paths of the files.
+    FileList(Vec<String>),
+
+    Bitmap(ClipboardBitmap),
+}
+
+impl ClipboardData {
+    pub fn format(&self) -> ClipboardFormat {
+        match self {
+            ClipboardData::Text(_) => ClipboardFormat::Text,
+            ClipboardData::Html(_) => ClipboardFormat::Html,
+            ClipboardData::RichText(_) => ClipboardFormat::RichText,
+            ClipboardData::Url(_) => ClipboardFormat::Url,
+            ClipboardData::FileList(_) => ClipboardFormat::FileList,
+            ClipboardData::Bitmap(_) => ClipboardFormat::Bitmap,
+        }
+    }
+}
+
+/// An image on the clipboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClipboardBitmap {
+    /// The width of the image, in pixels.
+    pub width: u32,
+
+    /// The height of the image, in pixels.
+    pub height: u32,
+
+    /// The pixels of the image, in RGBA order.
+    pub rgba: Vec<u8>,
+}
+
 /// A mouse cursor icon displayed by the Flash Player.
 /// Communicated from the core to the UI backend via `UiBackend::set_mouse_cursor`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -150,6 +292,36 @@ pub enum MouseCursor {
     /// The grabby-dragging hand icon.
     /// Equivalent to AS3 `MouseCursor.HAND`.
     Grab,
+
+    /// A cursor registered with `UiBackend::register_mouse_cursor`.
+    /// Equivalent to a name given to AS3 `Mouse.registerCursor`.
+    Custom(CustomCursorId),
+}
+
+/// Identifies a cursor registered with `UiBackend::register_mouse_cursor`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CustomCursorId(pub u32);
+
+/// A cursor made of bitmaps, registered by a movie with `Mouse.registerCursor`.
+/// Communicated from the core to the UI backend via `UiBackend::register_mouse_cursor`.
+#[derive(Clone, Debug)]
+pub struct CustomMouseCursor {
+    /// The width of every frame, in pixels.
+    pub width: u32,
+
+    /// The height of every frame, in pixels.
+    pub height: u32,
+
+    /// The images of the cursor, each made of pixels in RGBA order.
+    ///
+    /// Cursors with several frames are animated.
+    pub frames: Vec<Vec<u8>>,
+
+    /// The position within the images that points at the mouse position, in pixels.
+    pub hot_spot: (u32, u32),
+
+    /// The rate at which animated cursors go through their frames, in frames per second.
+    pub frame_rate: f64,
 }
 
 /// UiBackend that does nothing.
@@ -170,6 +342,10 @@ impl UiBackend for NullUiBackend {
 
     fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
 
+    fn register_mouse_cursor(&mut self, _id: CustomCursorId, _cursor: CustomMouseCursor) {}
+
+    fn unregister_mouse_cursor(&mut self, _id: CustomCursorId) {}
+
     fn clipboard_content(&mut self) -> String {
         "".into()
     }
@@ -223,6 +399,8 @@ impl UiBackend for NullUiBackend {
     ) -> Option<DialogResultFuture> {
         None
     }
+
+    fn print_pages(&mut self, _pages: Vec<PrintedPage>) {}
 }
 
 impl Default for NullUiBackend {
@@ -2,6 +2,9 @@ use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
 use crate::display_object::{DisplayObject, DisplayObjectWeak, TDisplayObject};
 use bitflags::bitflags;
 use gc_arena::{Collect, Mutation};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+use image::{ExtendedColorType, ImageEncoder, ImageError};
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle, PixelRegion, SyncHandle};
 use ruffle_wstr::WStr;
@@ -738,6 +741,59 @@ impl<'gc> BitmapData<'gc> {
         &self.pixels
     }
 
+    /// Encodes the given region of this bitmap as an image file, as used by `BitmapData.encode`.
+    /// The region must lie within the bounds of this bitmap.
+    pub fn encode(&self, region: PixelRegion, format: EncodeFormat) -> Result<Vec<u8>, ImageError> {
+        // JPEGs can't store transparency, so the alpha channel is simply dropped
+        let has_alpha = self.transparency && matches!(format, EncodeFormat::Png { .. });
+        let channels = if has_alpha { 4 } else { 3 };
+        let mut pixels =
+            Vec::with_capacity(region.width() as usize * region.height() as usize * channels);
+        for y in region.y_min..region.y_max {
+            for x in region.x_min..region.x_max {
+                let color = self.get_pixel32_raw(x, y).to_un_multiplied_alpha();
+                pixels.extend_from_slice(&[color.red(), color.green(), color.blue()]);
+                if has_alpha {
+                    pixels.push(color.alpha());
+                }
+            }
+        }
+        let color_type = if has_alpha {
+            ExtendedColorType::Rgba8
+        } else {
+            ExtendedColorType::Rgb8
+        };
+
+        let mut output = Vec::new();
+        match format {
+            EncodeFormat::Png { fast_compression } => {
+                // Fast compression skips filtering entirely, trading a larger file for speed.
+                // Otherwise the usual zlib level and adaptive filtering give a well compressed
+                // file without the very slow search of the best compression level.
+                let (compression, filter) = if fast_compression {
+                    (CompressionType::Fast, FilterType::NoFilter)
+                } else {
+                    (CompressionType::Default, FilterType::Adaptive)
+                };
+                PngEncoder::new_with_quality(&mut output, compression, filter).write_image(
+                    &pixels,
+                    region.width(),
+                    region.height(),
+                    color_type,
+                )?;
+            }
+            EncodeFormat::Jpeg { quality } => {
+                JpegEncoder::new_with_quality(&mut output, quality).write_image(
+                    &pixels,
+                    region.width(),
+                    region.height(),
+                    color_type,
+                )?;
+            }
+        }
+        Ok(output)
+    }
+
     // Updates the data stored with our `BitmapHandle` if this `BitmapData`
     // is dirty
     pub fn update_dirty_texture(&mut self, renderer: &mut dyn RenderBackend) {
@@ -834,6 +890,19 @@ fn copy_pixels_to_bitmapdata(
     }
 }
 
+/// The image formats that `BitmapData.encode` can produce.
+#[derive(Copy, Clone, Debug)]
+pub enum EncodeFormat {
+    Png {
+        /// Favour encoding speed over file size.
+        fast_compression: bool,
+    },
+    Jpeg {
+        /// The quality of the image, from 1 to 100.
+        quality: u8,
+    },
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum ThresholdOperation {
     Equals,
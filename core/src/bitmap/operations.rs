@@ -3,8 +3,8 @@ use crate::avm2::error::make_error_2006;
 use crate::avm2::vector::VectorStorage;
 use crate::avm2::{Activation, Error, Value as Avm2Value};
 use crate::bitmap::bitmap_data::{
-    BitmapData, BitmapDataDrawError, BitmapDataWrapper, ChannelOptions, Color, IBitmapDrawable,
-    LehmerRng, ThresholdOperation,
+    BitmapData, BitmapDataDrawError, BitmapDataWrapper, ChannelOptions, Color, EncodeFormat,
+    IBitmapDrawable, LehmerRng, ThresholdOperation,
 };
 use crate::bitmap::turbulence::Turbulence;
 use crate::context::{RenderContext, UpdateContext};
@@ -1705,6 +1705,34 @@ pub fn get_pixels_as_byte_array<'gc>(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn encode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: BitmapDataWrapper,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+    format: EncodeFormat,
+    result: &mut ByteArrayStorage,
+) -> Result<(), Error<'gc>> {
+    let mut region = PixelRegion::for_region_i32(x, y, width, height);
+    region.clamp(target.width(), target.height());
+    if region.width() == 0 || region.height() == 0 {
+        return Ok(());
+    }
+
+    let read = target.read_area(region, activation.context.renderer);
+    let bytes = read
+        .encode(region, format)
+        .map_err(|e| Error::RustError(e.into()))?;
+    result
+        .write_bytes(&bytes)
+        .map_err(|e| e.to_avm(activation))?;
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn set_pixels_from_byte_array<'gc>(
     mc: &Mutation<'gc>,
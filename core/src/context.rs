@@ -9,18 +9,21 @@ use crate::avm1::SystemProperties;
 use crate::avm1::TObject;
 use crate::avm1::{Object as Avm1Object, Value as Avm1Value};
 use crate::avm2::api_version::ApiVersion;
-use crate::avm2::object::LoaderInfoObject;
+use crate::avm2::media_capture::stop_capture_devices;
+use crate::avm2::object::{LoaderInfoObject, SoundObject};
 use crate::avm2::Activation as Avm2Activation;
 use crate::avm2::TObject as _;
 use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
     log::LogBackend,
+    media_capture::MediaCaptureBackend,
     navigator::NavigatorBackend,
     storage::StorageBackend,
     ui::UiBackend,
 };
 use crate::context_menu::ContextMenuState;
+use crate::datagram_socket::DatagramSockets;
 use crate::display_object::{EditText, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
@@ -33,6 +36,7 @@ use crate::net_connection::NetConnections;
 use crate::player::PostFrameCallback;
 use crate::player::{MouseData, Player};
 use crate::prelude::*;
+use crate::printing::PrintJob;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::{AvmString, StringContext};
@@ -104,6 +108,9 @@ pub struct UpdateContext<'gc> {
     /// The UI backend, used to detect user interactions.
     pub ui: &'gc mut dyn UiBackend,
 
+    /// The media capture backend, used to capture cameras and microphones.
+    pub media_capture: &'gc mut dyn MediaCaptureBackend,
+
     /// The storage backend, used for storing persistent state
     pub storage: &'gc mut dyn StorageBackend,
 
@@ -213,11 +220,16 @@ pub struct UpdateContext<'gc> {
 
     pub sockets: &'gc mut Sockets<'gc>,
 
+    pub datagram_sockets: &'gc mut DatagramSockets<'gc>,
+
     /// List of active NetConnection instances.
     pub net_connections: &'gc mut NetConnections<'gc>,
 
     pub local_connections: &'gc mut LocalConnections<'gc>,
 
+    /// The print job that was started and hasn't been sent yet.
+    pub print_job: &'gc mut Option<PrintJob>,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     pub dynamic_root: gc_arena::DynamicRootSet<'gc>,
 
@@ -264,13 +276,18 @@ impl<'gc> UpdateContext<'gc> {
             .start_sound(self.audio, sound, settings, owner, avm1_object)
     }
 
+    pub fn start_dynamic_sound(&mut self, source: SoundObject<'gc>) -> Option<SoundInstanceHandle> {
+        self.audio_manager.start_dynamic_sound(self.audio, source)
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
         avm2_object: SoundChannelObject<'gc>,
+        movie: Arc<SwfMovie>,
     ) {
         self.audio_manager
-            .attach_avm2_sound_channel(instance, avm2_object);
+            .attach_avm2_sound_channel(instance, avm2_object, movie);
     }
 
     pub fn stop_sound(&mut self, instance: SoundInstanceHandle) {
@@ -282,6 +299,10 @@ impl<'gc> UpdateContext<'gc> {
             .stop_sounds_with_handle(self.audio, sound)
     }
 
+    pub fn stop_sounds_for_movie(&mut self, movie: &Arc<SwfMovie>) {
+        self.audio_manager.stop_sounds_for_movie(self.audio, movie)
+    }
+
     pub fn stop_sounds_with_display_object(&mut self, display_object: DisplayObject<'gc>) {
         self.audio_manager
             .stop_sounds_with_display_object(self.audio, display_object)
@@ -433,6 +454,8 @@ impl<'gc> UpdateContext<'gc> {
 
         // Clean up the stage before loading another root movie.
         self.sockets.close_all();
+        self.datagram_sockets.close_all();
+        stop_capture_devices(self);
         self.timers.remove_all();
 
         self.set_root_movie(movie);
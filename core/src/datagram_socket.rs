@@ -0,0 +1,344 @@
+//! UDP sockets, as exposed to AIR content through `flash.net.DatagramSocket`.
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::globals::slots::*;
+use crate::avm2::object::ByteArrayObject;
+use crate::avm2::{Activation, Avm2, EventObject, Object, TObject, Value};
+use crate::backend::navigator::NavigatorBackend;
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
+use async_channel::{unbounded, Receiver, Sender};
+use gc_arena::Collect;
+use slotmap::{new_key_type, SlotMap};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+new_key_type! {
+    pub struct DatagramSocketHandle;
+}
+
+/// A packet sent or received through a datagram socket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Datagram {
+    /// The address of the other end: the destination of a sent packet,
+    /// or the source of a received one.
+    pub address: String,
+    pub port: u16,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DatagramSocketAction {
+    /// The socket was bound to the given local address and port.
+    ///
+    /// The port is the one picked by the system when binding to port 0.
+    Bound(DatagramSocketHandle, String, u16),
+    BindFailed(DatagramSocketHandle),
+    Data(DatagramSocketHandle, Datagram),
+    Close(DatagramSocketHandle),
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct DatagramSocket<'gc> {
+    target: Object<'gc>,
+    #[collect(require_static)]
+    sender: Sender<Datagram>,
+    receiving: Cell<bool>,
+    remote: RefCell<Option<(String, u16)>>,
+
+    /// The movie that bound this socket.
+    #[collect(require_static)]
+    movie: Arc<SwfMovie>,
+}
+
+/// Manages the collection of bound `DatagramSocket`s.
+pub struct DatagramSockets<'gc> {
+    sockets: SlotMap<DatagramSocketHandle, DatagramSocket<'gc>>,
+
+    receiver: Receiver<DatagramSocketAction>,
+    sender: Sender<DatagramSocketAction>,
+
+    /// Actions received while looking for the result of a bind, to be handled on the next update.
+    pending: VecDeque<DatagramSocketAction>,
+}
+
+unsafe impl Collect for DatagramSockets<'_> {
+    fn trace(&self, cc: &gc_arena::Collection) {
+        for (_, socket) in self.sockets.iter() {
+            socket.trace(cc)
+        }
+    }
+}
+
+impl<'gc> DatagramSockets<'gc> {
+    pub fn empty() -> Self {
+        let (sender, receiver) = unbounded();
+
+        Self {
+            sockets: SlotMap::with_key(),
+            receiver,
+            sender,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Binds a `DatagramSocket` to a local address and port.
+    pub fn bind(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        target: Object<'gc>,
+        host: String,
+        port: u16,
+        movie: Arc<SwfMovie>,
+    ) -> DatagramSocketHandle {
+        let (sender, receiver) = unbounded();
+
+        let handle = self.sockets.insert(DatagramSocket {
+            target,
+            sender,
+            receiving: Cell::new(false),
+            remote: RefCell::new(None),
+            movie,
+        });
+
+        // NOTE: This call will send DatagramSocketAction::Bound or BindFailed to sender.
+        backend.bind_datagram_socket(host, port, handle, receiver, self.sender.clone());
+
+        handle
+    }
+
+    /// Takes the `Bound` or `BindFailed` action of a socket, if the backend already sent it.
+    ///
+    /// Backends that can bind right away report it before `bind_datagram_socket` returns, which
+    /// lets `DatagramSocket.bind` complete synchronously like in Flash.
+    pub fn take_bind_result(
+        &mut self,
+        handle: DatagramSocketHandle,
+    ) -> Option<DatagramSocketAction> {
+        while let Ok(action) = self.receiver.try_recv() {
+            self.pending.push_back(action);
+        }
+
+        let index = self.pending.iter().position(|action| match action {
+            DatagramSocketAction::Bound(bound, ..) => *bound == handle,
+            DatagramSocketAction::BindFailed(failed) => *failed == handle,
+            _ => false,
+        })?;
+        self.pending.remove(index)
+    }
+
+    /// The handle of the socket a `DatagramSocket` is bound to.
+    pub fn handle_of(&self, target: Object<'gc>) -> Option<DatagramSocketHandle> {
+        self.sockets
+            .iter()
+            .find(|(_, socket)| Object::ptr_eq(socket.target, target))
+            .map(|(handle, _)| handle)
+    }
+
+    pub fn send(&mut self, handle: DatagramSocketHandle, datagram: Datagram) {
+        if let Some(socket) = self.sockets.get(handle) {
+            // We use an unbounded channel, so this should only ever error if the channel is
+            // closed (the receiver was dropped)
+            if let Err(e) = socket.sender.try_send(datagram) {
+                tracing::error!("Failed to send datagram: {:?}", e);
+            }
+        }
+    }
+
+    /// Restricts a socket to exchanging packets with the given address and port.
+    pub fn connect(&mut self, handle: DatagramSocketHandle, address: String, port: u16) {
+        if let Some(socket) = self.sockets.get(handle) {
+            *socket.remote.borrow_mut() = Some((address, port));
+        }
+    }
+
+    /// Starts dispatching the packets received by a socket.
+    ///
+    /// Packets arriving before this is called are dropped.
+    pub fn receive(&mut self, handle: DatagramSocketHandle) {
+        if let Some(socket) = self.sockets.get(handle) {
+            socket.receiving.set(true);
+        }
+    }
+
+    pub fn close_all(&mut self) {
+        // NOTE: By dropping the senders, the backend tasks will end automatically.
+        self.sockets.clear();
+    }
+
+    pub fn close(&mut self, handle: DatagramSocketHandle) {
+        self.sockets.remove(handle);
+    }
+
+    /// Closes all sockets bound by the given movie.
+    pub fn close_for_movie(context: &mut UpdateContext<'gc>, movie: &Arc<SwfMovie>) {
+        let handles: Vec<_> = context
+            .datagram_sockets
+            .sockets
+            .iter()
+            .filter(|(_, socket)| Arc::ptr_eq(&socket.movie, movie))
+            .map(|(handle, _)| handle)
+            .collect();
+
+        for handle in handles {
+            let Some(socket) = context.datagram_sockets.sockets.remove(handle) else {
+                continue;
+            };
+
+            let mut activation = Activation::from_nothing(context);
+            reset_slots(&mut activation, socket.target);
+        }
+    }
+
+    pub fn update_sockets(context: &mut UpdateContext<'gc>) {
+        let mut actions: Vec<_> = context.datagram_sockets.pending.drain(..).collect();
+
+        while let Ok(action) = context.datagram_sockets.receiver.try_recv() {
+            actions.push(action)
+        }
+
+        for action in actions {
+            match action {
+                DatagramSocketAction::Bound(handle, address, port) => {
+                    let Some(socket) = context.datagram_sockets.sockets.get(handle) else {
+                        // Socket must have been closed before it got bound.
+                        continue;
+                    };
+                    let target = socket.target;
+
+                    let mut activation = Activation::from_nothing(context);
+                    set_bound_slots(&mut activation, target, address, port);
+                }
+                DatagramSocketAction::BindFailed(handle) => {
+                    let Some(socket) = context.datagram_sockets.sockets.remove(handle) else {
+                        continue;
+                    };
+                    let target = socket.target;
+
+                    let mut activation = Activation::from_nothing(context);
+                    reset_slots(&mut activation, target);
+
+                    let io_error_evt = activation
+                        .avm2()
+                        .classes()
+                        .ioerrorevent
+                        .construct(
+                            &mut activation,
+                            &[
+                                "ioError".into(),
+                                false.into(),
+                                false.into(),
+                                "Error #2031: Socket Error.".into(),
+                                2031.into(),
+                            ],
+                        )
+                        .expect("IOErrorEvent should be constructed");
+
+                    Avm2::dispatch_event(activation.context, io_error_evt, target);
+                }
+                DatagramSocketAction::Data(handle, datagram) => {
+                    let Some(socket) = context.datagram_sockets.sockets.get(handle) else {
+                        // Socket must have been closed before we could send event.
+                        continue;
+                    };
+                    if !socket.receiving.get() {
+                        continue;
+                    }
+
+                    // A connected socket only accepts packets from the address it's connected to.
+                    if let Some((address, port)) = &*socket.remote.borrow() {
+                        if *address != datagram.address || *port != datagram.port {
+                            continue;
+                        }
+                    }
+                    let target = socket.target;
+
+                    let mut activation = Activation::from_nothing(context);
+                    let local_address =
+                        target.get_slot(FLASH_NET_DATAGRAM_SOCKET__LOCAL_ADDRESS_SLOT);
+                    let local_port = target.get_slot(FLASH_NET_DATAGRAM_SOCKET__LOCAL_PORT_SLOT);
+                    let src_address = AvmString::new_utf8(activation.gc(), datagram.address);
+                    let storage = ByteArrayStorage::from_vec(datagram.data);
+                    let data = match ByteArrayObject::from_storage(&mut activation, storage) {
+                        Ok(data) => data,
+                        Err(e) => {
+                            tracing::error!("Couldn't create the data of a datagram: {:?}", e);
+                            continue;
+                        }
+                    };
+
+                    let data_evt = activation
+                        .avm2()
+                        .classes()
+                        .datagramsocketdataevent
+                        .construct(
+                            &mut activation,
+                            &[
+                                "data".into(),
+                                false.into(),
+                                false.into(),
+                                src_address.into(),
+                                datagram.port.into(),
+                                local_address,
+                                local_port,
+                                data.into(),
+                            ],
+                        )
+                        .expect("DatagramSocketDataEvent should be constructed");
+
+                    Avm2::dispatch_event(activation.context, data_evt, target);
+                }
+                DatagramSocketAction::Close(handle) => {
+                    let Some(socket) = context.datagram_sockets.sockets.remove(handle) else {
+                        // Socket must have been closed before we could send event.
+                        continue;
+                    };
+                    let target = socket.target;
+
+                    let mut activation = Activation::from_nothing(context);
+                    reset_slots(&mut activation, target);
+
+                    let close_evt = EventObject::bare_default_event(activation.context, "close");
+                    Avm2::dispatch_event(activation.context, close_evt, target);
+                }
+            }
+        }
+    }
+}
+
+/// Marks a `DatagramSocket` as bound to the address and port the backend reported.
+pub fn set_bound_slots<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: Object<'gc>,
+    address: String,
+    port: u16,
+) {
+    let address = AvmString::new_utf8(activation.gc(), address);
+    for (slot, value) in [
+        (FLASH_NET_DATAGRAM_SOCKET__BOUND_SLOT, true.into()),
+        (
+            FLASH_NET_DATAGRAM_SOCKET__LOCAL_ADDRESS_SLOT,
+            address.into(),
+        ),
+        (FLASH_NET_DATAGRAM_SOCKET__LOCAL_PORT_SLOT, port.into()),
+    ] {
+        let _ = target.set_slot(slot, value, activation);
+    }
+}
+
+/// Marks a `DatagramSocket` as no longer bound nor connected.
+pub fn reset_slots<'gc>(activation: &mut Activation<'_, 'gc>, target: Object<'gc>) {
+    for (slot, value) in [
+        (FLASH_NET_DATAGRAM_SOCKET__BOUND_SLOT, false.into()),
+        (FLASH_NET_DATAGRAM_SOCKET__CONNECTED_SLOT, false.into()),
+        (FLASH_NET_DATAGRAM_SOCKET__LOCAL_ADDRESS_SLOT, Value::Null),
+        (FLASH_NET_DATAGRAM_SOCKET__LOCAL_PORT_SLOT, 0.into()),
+        (FLASH_NET_DATAGRAM_SOCKET__REMOTE_ADDRESS_SLOT, Value::Null),
+        (FLASH_NET_DATAGRAM_SOCKET__REMOTE_PORT_SLOT, 0.into()),
+    ] {
+        let _ = target.set_slot(slot, value, activation);
+    }
+}
@@ -17,6 +17,8 @@ use crate::vminterface::Instantiator;
 use bitflags::bitflags;
 use gc_arena::{Collect, Mutation};
 use ruffle_macros::enum_trait_object;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::perspective_projection::PerspectiveProjection;
 use ruffle_render::pixel_bender::PixelBenderShaderHandle;
 use ruffle_render::transform::{Transform, TransformStack};
 use std::cell::{Ref, RefMut};
@@ -62,7 +64,7 @@ use ruffle_render::blend::ExtendedBlendMode;
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::filters::Filter;
 pub use stage::{Stage, StageAlign, StageDisplayState, StageScaleMode, WindowMode};
-pub use text::Text;
+pub use text::{StaticTextChar, Text};
 pub use video::Video;
 
 use self::loader_display::LoaderDisplayWeak;
@@ -213,6 +215,15 @@ pub struct DisplayObjectBase<'gc> {
 
     skew: f64,
 
+    // The 3D transform properties `z`, `rotationX`, `rotationY` and `scaleZ`.
+    // These only take effect once the object has been given a 3D matrix.
+    z: f64,
+    #[collect(require_static)]
+    rotation_x: Degrees,
+    #[collect(require_static)]
+    rotation_y: Degrees,
+    scale_z: f64,
+
     /// The next display object in order of execution.
     ///
     /// `None` in an AVM2 movie.
@@ -284,6 +295,10 @@ impl Default for DisplayObjectBase<'_> {
             scale_x: Percent::from_unit(1.0),
             scale_y: Percent::from_unit(1.0),
             skew: 0.0,
+            z: 0.0,
+            rotation_x: Degrees::from_radians(0.0),
+            rotation_y: Degrees::from_radians(0.0),
+            scale_z: 1.0,
             next_avm1_clip: None,
             masker: None,
             maskee: None,
@@ -339,6 +354,84 @@ impl<'gc> DisplayObjectBase<'gc> {
     pub fn set_matrix(&mut self, matrix: Matrix) {
         self.transform.matrix = matrix;
         self.set_scale_rotation_cached(false);
+        self.update_matrix3d();
+    }
+
+    pub fn matrix3d(&self) -> Option<&Matrix3D> {
+        self.transform.matrix3d.as_ref()
+    }
+
+    /// Replaces the 3D matrix of this object, splitting it up into the individual properties.
+    /// Setting `None` moves the object back to 2D, keeping only its 2D properties.
+    fn set_matrix3d(&mut self, matrix3d: Option<Matrix3D>) {
+        self.set_transformed_by_script(true);
+        if let Some(matrix3d) = matrix3d {
+            let components = matrix3d.decompose();
+            let [x, y, z] = components.translation;
+            let [rotation_x, rotation_y, rotation_z] = components.rotation;
+            let [scale_x, scale_y, scale_z] = components.scale;
+            self.transform.matrix = Matrix::translate(Twips::from_pixels(x), Twips::from_pixels(y))
+                * Matrix::rotate(rotation_z as f32)
+                * Matrix::scale(scale_x as f32, scale_y as f32);
+            self.set_scale_rotation_cached(false);
+            self.z = z;
+            self.rotation_x = Degrees::from_radians(rotation_x);
+            self.rotation_y = Degrees::from_radians(rotation_y);
+            self.scale_z = scale_z;
+        } else {
+            self.z = 0.0;
+            self.rotation_x = Degrees::from_radians(0.0);
+            self.rotation_y = Degrees::from_radians(0.0);
+            self.scale_z = 1.0;
+        }
+        self.transform.matrix3d = matrix3d;
+        self.recheck_cache_as_bitmap();
+    }
+
+    /// Moves this object into 3D space, if it isn't already.
+    fn enable_matrix3d(&mut self) {
+        if self.transform.matrix3d.is_none() {
+            self.transform.matrix3d = Some(Matrix3D::IDENTITY);
+            self.recheck_cache_as_bitmap();
+        }
+    }
+
+    /// Rebuilds the 3D matrix from the 2D matrix and the 3D properties.
+    /// Does nothing for objects that aren't in 3D.
+    fn update_matrix3d(&mut self) {
+        if self.transform.matrix3d.is_none() {
+            return;
+        }
+        self.cache_scale_rotation();
+        let mut rotation_z = self.rotation.into_radians();
+        if rotation_z.is_nan() {
+            rotation_z = 0.0;
+        }
+        let matrix = self.transform.matrix;
+        // Flash scales before rotating around X and Y, so take the 2D rotation back out of the
+        // 2D matrix, leaving its scale and skew, and put it back after the other rotations.
+        let scale_skew = Matrix::rotate(-rotation_z as f32)
+            * Matrix {
+                tx: Twips::ZERO,
+                ty: Twips::ZERO,
+                ..matrix
+            };
+        self.transform.matrix3d = Some(
+            Matrix3D::translation(matrix.tx.to_pixels(), matrix.ty.to_pixels(), self.z)
+                * Matrix3D::rotation_z(rotation_z)
+                * Matrix3D::rotation_y(self.rotation_y.into_radians())
+                * Matrix3D::rotation_x(self.rotation_x.into_radians())
+                * Matrix3D::from(scale_skew)
+                * Matrix3D::scale(1.0, 1.0, self.scale_z),
+        );
+    }
+
+    pub fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.transform.perspective_projection
+    }
+
+    fn set_perspective_projection(&mut self, projection: Option<PerspectiveProjection>) {
+        self.transform.perspective_projection = projection;
     }
 
     pub fn color_transform(&self) -> &ColorTransform {
@@ -361,6 +454,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         let changed = self.transform.matrix.tx != x;
         self.set_transformed_by_script(true);
         self.transform.matrix.tx = x;
+        self.update_matrix3d();
         changed
     }
 
@@ -372,6 +466,59 @@ impl<'gc> DisplayObjectBase<'gc> {
         let changed = self.transform.matrix.ty != y;
         self.set_transformed_by_script(true);
         self.transform.matrix.ty = y;
+        self.update_matrix3d();
+        changed
+    }
+
+    fn z(&self) -> f64 {
+        self.z
+    }
+
+    fn set_z(&mut self, z: f64) -> bool {
+        let changed = self.z != z || self.transform.matrix3d.is_none();
+        self.set_transformed_by_script(true);
+        self.z = z;
+        self.enable_matrix3d();
+        self.update_matrix3d();
+        changed
+    }
+
+    fn rotation_x(&self) -> Degrees {
+        self.rotation_x
+    }
+
+    fn set_rotation_x(&mut self, degrees: Degrees) -> bool {
+        let changed = self.rotation_x != degrees || self.transform.matrix3d.is_none();
+        self.set_transformed_by_script(true);
+        self.rotation_x = degrees;
+        self.enable_matrix3d();
+        self.update_matrix3d();
+        changed
+    }
+
+    fn rotation_y(&self) -> Degrees {
+        self.rotation_y
+    }
+
+    fn set_rotation_y(&mut self, degrees: Degrees) -> bool {
+        let changed = self.rotation_y != degrees || self.transform.matrix3d.is_none();
+        self.set_transformed_by_script(true);
+        self.rotation_y = degrees;
+        self.enable_matrix3d();
+        self.update_matrix3d();
+        changed
+    }
+
+    fn scale_z(&self) -> f64 {
+        self.scale_z
+    }
+
+    fn set_scale_z(&mut self, value: f64) -> bool {
+        let changed = self.scale_z != value || self.transform.matrix3d.is_none();
+        self.set_transformed_by_script(true);
+        self.scale_z = value;
+        self.enable_matrix3d();
+        self.update_matrix3d();
         changed
     }
 
@@ -444,6 +591,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         matrix.b = (self.scale_x.unit() * sin_x) as f32;
         matrix.c = (self.scale_y.unit() * -sin_y) as f32;
         matrix.d = (self.scale_y.unit() * cos_y) as f32;
+        self.update_matrix3d();
 
         changed
     }
@@ -478,6 +626,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         let matrix = &mut self.transform.matrix;
         matrix.a = (cos * value.unit()) as f32;
         matrix.b = (sin * value.unit()) as f32;
+        self.update_matrix3d();
 
         changed
     }
@@ -512,6 +661,7 @@ impl<'gc> DisplayObjectBase<'gc> {
         let matrix = &mut self.transform.matrix;
         matrix.c = (-sin * value.unit()) as f32;
         matrix.d = (cos * value.unit()) as f32;
+        self.update_matrix3d();
 
         changed
     }
@@ -740,7 +890,10 @@ impl<'gc> DisplayObjectBase<'gc> {
     }
 
     fn recheck_cache_as_bitmap(&mut self) {
-        let should_cache = self.is_bitmap_cached_preference() || !self.filters.is_empty();
+        // Like filters, 3D transforms are applied to a flattened bitmap of the object.
+        let should_cache = self.is_bitmap_cached_preference()
+            || !self.filters.is_empty()
+            || self.transform.matrix3d.is_some();
         if should_cache && self.cache.is_none() {
             self.cache = Some(Default::default());
         } else if !should_cache && self.cache.is_some() {
@@ -812,7 +965,18 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
-    context.transform_stack.push(this.base().transform());
+    // 3D objects are rendered into their bitmap cache without their own transform,
+    // and that bitmap is then projected into place.
+    let projected =
+        context.use_bitmap_cache && this.is_bitmap_cached() && this.base().matrix3d().is_some();
+    if projected {
+        context.transform_stack.push(&Transform {
+            color_transform: *this.base().color_transform(),
+            ..Default::default()
+        });
+    } else {
+        context.transform_stack.push(this.base().transform());
+    }
     let blend_mode = this.blend_mode();
     let original_commands = if blend_mode != ExtendedBlendMode::Normal {
         Some(std::mem::take(&mut context.commands))
@@ -923,6 +1087,7 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
                     ty: -offset_y,
                     ..cache_info.base_transform.matrix
                 },
+                ..Default::default()
             });
             let mut offscreen_context = RenderContext {
                 renderer: context.renderer,
@@ -946,21 +1111,43 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
 
         // When rendering it back, ensure we're only keeping the translation - scale/rotation is within the image already
         apply_standard_mask_and_scroll(this, context, |context| {
+            let tx = context.transform_stack.transform().matrix.tx + offset_x;
+            let ty = context.transform_stack.transform().matrix.ty + offset_y;
+            let matrix3d = if projected {
+                let matrix3d = this
+                    .base()
+                    .matrix3d()
+                    .and_then(|matrix3d| projected_bitmap_matrix(this, context, *matrix3d, tx, ty));
+                if matrix3d.is_none() {
+                    // An ancestor has been squashed flat, so there's nothing to draw.
+                    return;
+                }
+                matrix3d
+            } else {
+                None
+            };
             context.commands.render_bitmap(
                 cache_info.handle,
                 Transform {
                     matrix: Matrix {
-                        tx: context.transform_stack.transform().matrix.tx + offset_x,
-                        ty: context.transform_stack.transform().matrix.ty + offset_y,
+                        tx,
+                        ty,
                         ..Default::default()
                     },
                     color_transform: cache_info.base_transform.color_transform,
+                    matrix3d,
+                    perspective_projection: None,
                 },
                 true,
                 PixelSnapping::Always, // cacheAsBitmap forces pixel snapping
             )
         });
     } else {
+        if projected {
+            // We couldn't use the bitmap cache after all, so fall back to the 2D transform.
+            context.transform_stack.pop();
+            context.transform_stack.push(this.base().transform());
+        }
         if let Some(background) = this.opaque_background() {
             // This is intended for use with cacheAsBitmap, but can be set for non-cached objects too
             // It wants the entire bounding box to be cleared before any draws happen
@@ -996,6 +1183,63 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     context.transform_stack.pop();
 }
 
+/// Calculates the matrix that projects the cached bitmap of a 3D object onto the screen.
+///
+/// The bitmap was drawn with the transform of the parent, but not the object's own, with its
+/// top-left corner at `(tx, ty)`. Its pixels are mapped back into the parent's local space,
+/// transformed by the object's 3D matrix, and then projected in stage space using the
+/// perspective projection of the nearest ancestor that has one.
+fn projected_bitmap_matrix<'gc>(
+    this: DisplayObject<'gc>,
+    context: &RenderContext<'_, 'gc>,
+    matrix3d: Matrix3D,
+    tx: Twips,
+    ty: Twips,
+) -> Option<Matrix3D> {
+    let parent_matrix = context.transform_stack.transform().matrix;
+    let view_matrix = context.stage.view_matrix();
+    let parent_to_stage = view_matrix.inverse()? * parent_matrix;
+    let bitmap_to_parent = parent_matrix.inverse()? * Matrix::translate(tx, ty);
+
+    let (stage_width, stage_height) = context.stage.stage_size();
+    let projection =
+        inherited_perspective_projection(this, stage_width.into(), stage_height.into());
+
+    Some(
+        Matrix3D::from(view_matrix)
+            * projection.to_matrix3d(stage_width.into())
+            * Matrix3D::from(parent_to_stage)
+            * matrix3d
+            * Matrix3D::from(bitmap_to_parent),
+    )
+}
+
+/// The perspective projection that a 3D object is drawn with, in stage space.
+///
+/// This is the projection of the nearest ancestor that has one,
+/// or else the default projection for a stage of the given size.
+fn inherited_perspective_projection<'gc>(
+    this: DisplayObject<'gc>,
+    stage_width: f64,
+    stage_height: f64,
+) -> PerspectiveProjection {
+    let mut node = this.parent();
+    while let Some(ancestor) = node {
+        if let Some(projection) = ancestor.perspective_projection() {
+            // The projection center is in the local space of the object that owns it.
+            let (center_x, center_y) = projection.center;
+            let center = ancestor.local_to_global_matrix()
+                * Point::new(Twips::from_pixels(center_x), Twips::from_pixels(center_y));
+            return PerspectiveProjection {
+                center: (center.x.to_pixels(), center.y.to_pixels()),
+                ..projection
+            };
+        }
+        node = ancestor.parent();
+    }
+    PerspectiveProjection::for_viewport(stage_width, stage_height)
+}
+
 /// This applies the **standard** method of `mask` and `scrollRect`.
 ///
 /// It uses the stencil buffer so that any pixel drawn in the mask will allow the inner contents to show.
@@ -1026,7 +1270,7 @@ pub fn apply_standard_mask_and_scroll<'gc, F>(
         // Translate everything that we render (including DisplayObject.mask)
         context.transform_stack.push(&Transform {
             matrix: Matrix::translate(-rect.x_min, -rect.y_min),
-            color_transform: Default::default(),
+            ..Default::default()
         });
     }
 
@@ -1140,12 +1384,66 @@ pub trait TDisplayObject<'gc>:
 
     /// The local bounding box of this object including children, in its parent's coordinate system.
     fn local_bounds(&self) -> Rectangle<Twips> {
+        if self.base().matrix3d().is_some() {
+            return self.projected_bounds();
+        }
         self.bounds_with_transform(self.base().matrix())
     }
 
     /// The world bounding box of this object including children, relative to the stage.
     fn world_bounds(&self) -> Rectangle<Twips> {
-        self.bounds_with_transform(&self.local_to_global_matrix())
+        if !self.is_in_3d() {
+            return self.bounds_with_transform(&self.local_to_global_matrix());
+        }
+
+        // The bounds are projected up through each ancestor in turn,
+        // as 3D objects can't be described by a single matrix.
+        let mut bounds = self.local_bounds();
+        let mut node = self.parent();
+        while let Some(display_object) = node {
+            if display_object.as_stage().is_some() {
+                break;
+            }
+            if let Some(rect) = display_object.scroll_rect() {
+                bounds = Matrix::translate(-rect.x_min, -rect.y_min) * bounds;
+            }
+            bounds = if display_object.base().matrix3d().is_some() {
+                display_object.project_rect_to_parent(&bounds)
+            } else {
+                *display_object.base().matrix() * bounds
+            };
+            node = display_object.parent();
+        }
+        bounds
+    }
+
+    /// The bounding box of this 3D object including children, in its parent's coordinate system,
+    /// as it's drawn after being projected.
+    fn projected_bounds(&self) -> Rectangle<Twips> {
+        self.project_rect_to_parent(&self.bounds())
+    }
+
+    /// Projects a rectangle in the local space of this 3D object into its parent's space,
+    /// returning the bounding box of the result.
+    /// Rectangles that end up partly behind the viewer have no bounds.
+    fn project_rect_to_parent(&self, rect: &Rectangle<Twips>) -> Rectangle<Twips> {
+        if !rect.is_valid() {
+            return Default::default();
+        }
+        let corners = [
+            Point::new(rect.x_min, rect.y_min),
+            Point::new(rect.x_max, rect.y_min),
+            Point::new(rect.x_min, rect.y_max),
+            Point::new(rect.x_max, rect.y_max),
+        ];
+        let mut bounds = Rectangle::default();
+        for corner in corners {
+            let Some(corner) = self.local_to_parent(corner) else {
+                return Default::default();
+            };
+            bounds = bounds.encompass(corner);
+        }
+        bounds
     }
 
     /// Bounds used for drawing debug rects and picking objects.
@@ -1180,8 +1478,12 @@ pub trait TDisplayObject<'gc>:
 
         if let Some(ctr) = self.as_container() {
             for child in ctr.iter_render_list() {
-                let matrix = *matrix * *child.base().matrix();
-                bounds = bounds.union(&child.bounds_with_transform(&matrix));
+                let child_bounds = if child.base().matrix3d().is_some() {
+                    *matrix * child.projected_bounds()
+                } else {
+                    child.bounds_with_transform(&(*matrix * *child.base().matrix()))
+                };
+                bounds = bounds.union(&child_bounds);
             }
         }
 
@@ -1277,13 +1579,120 @@ pub trait TDisplayObject<'gc>:
 
     /// Converts a local position to a global stage position
     fn local_to_global(&self, local: Point<Twips>) -> Point<Twips> {
-        self.local_to_global_matrix() * local
+        if !self.is_in_3d() {
+            return self.local_to_global_matrix() * local;
+        }
+
+        let mut point = local;
+        let mut node = Some((*self).into());
+        while let Some(display_object) = node {
+            if display_object.as_stage().is_some() {
+                break;
+            }
+            if let Some(rect) = display_object.scroll_rect() {
+                point = Matrix::translate(-rect.x_min, -rect.y_min) * point;
+            }
+            // Points that end up behind the viewer keep their 2D position.
+            point = display_object
+                .local_to_parent(point)
+                .unwrap_or_else(|| *display_object.base().matrix() * point);
+            node = display_object.parent();
+        }
+        point
     }
 
     /// Converts a local position on the stage to a local position on this display object
     /// Returns `None` if the object has zero scale.
     fn global_to_local(&self, global: Point<Twips>) -> Option<Point<Twips>> {
-        self.global_to_local_matrix().map(|matrix| matrix * global)
+        if !self.is_in_3d() {
+            return self.global_to_local_matrix().map(|matrix| matrix * global);
+        }
+
+        let mut ancestors = vec![];
+        let mut node = Some((*self).into());
+        while let Some(display_object) = node {
+            if display_object.as_stage().is_some() {
+                break;
+            }
+            ancestors.push(display_object);
+            node = display_object.parent();
+        }
+
+        let mut point = global;
+        for display_object in ancestors.into_iter().rev() {
+            point = display_object.parent_to_local(point)?;
+            if let Some(rect) = display_object.scroll_rect() {
+                point = Matrix::translate(rect.x_min, rect.y_min) * point;
+            }
+        }
+        Some(point)
+    }
+
+    /// Converts a position in the parent's space to a position in this object's local space,
+    /// through its 3D transform if it has one.
+    /// Returns `None` if the object has zero scale, or is seen edge-on.
+    fn parent_to_local(&self, point: Point<Twips>) -> Option<Point<Twips>> {
+        match self.local_to_parent_projection() {
+            Some(projection) => {
+                let (x, y) =
+                    projection.unproject_point(point.x.to_pixels(), point.y.to_pixels())?;
+                Some(Point::new(Twips::from_pixels(x), Twips::from_pixels(y)))
+            }
+            None => self.base().matrix().inverse().map(|matrix| matrix * point),
+        }
+    }
+
+    /// Converts a position in this object's local space to a position in the parent's space,
+    /// through its 3D transform if it has one.
+    /// Returns `None` if the position ends up behind the viewer.
+    fn local_to_parent(&self, point: Point<Twips>) -> Option<Point<Twips>> {
+        match self.local_to_parent_projection() {
+            Some(projection) => {
+                let (x, y) = projection.project_point(point.x.to_pixels(), point.y.to_pixels())?;
+                Some(Point::new(Twips::from_pixels(x), Twips::from_pixels(y)))
+            }
+            None => Some(*self.base().matrix() * point),
+        }
+    }
+
+    /// Maps points on the z = 0 plane of this object's local space into its parent's space,
+    /// through its 3D matrix and the perspective projection that it's drawn with.
+    /// Returns `None` if this object isn't in 3D, or its parent has zero scale.
+    fn local_to_parent_projection(&self) -> Option<Matrix3D> {
+        let matrix3d = self.matrix3d()?;
+        let parent_to_stage = self
+            .parent()
+            .map(|parent| parent.local_to_global_matrix())
+            .unwrap_or_default();
+        let stage_to_parent = parent_to_stage.inverse()?;
+
+        // Objects off the display list are projected as if they were on a stage
+        // the size of their movie.
+        let mut stage = None;
+        let mut node = self.parent();
+        while let Some(display_object) = node {
+            stage = display_object.as_stage();
+            node = display_object.parent();
+        }
+        let (stage_width, stage_height) = match stage {
+            Some(stage) => {
+                let (width, height) = stage.stage_size();
+                (width.into(), height.into())
+            }
+            None => {
+                let movie = self.movie();
+                (movie.width().to_pixels(), movie.height().to_pixels())
+            }
+        };
+        let projection =
+            inherited_perspective_projection((*self).into(), stage_width, stage_height);
+
+        Some(
+            Matrix3D::from(stage_to_parent)
+                * projection.to_matrix3d(stage_width)
+                * Matrix3D::from(parent_to_stage)
+                * matrix3d,
+        )
     }
 
     /// Converts the mouse position on the stage to a local position on this display object.
@@ -1416,6 +1825,117 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
+    /// The `z` position in pixels of this display object in local space.
+    /// Returned by the `z` ActionScript property.
+    fn z(&self) -> f64 {
+        self.base().z()
+    }
+
+    /// Sets the `z` position in pixels of this display object in local space.
+    /// Set by the `z` ActionScript property, which moves the object into 3D space.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_z(&self, gc_context: &Mutation<'gc>, z: f64) {
+        if self.base_mut(gc_context).set_z(z) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The rotation in degrees around the X axis of this display object in local space.
+    /// Returned by the `rotationX` ActionScript property.
+    fn rotation_x(&self) -> Degrees {
+        self.base().rotation_x()
+    }
+
+    /// Sets the rotation in degrees around the X axis of this display object in local space.
+    /// Set by the `rotationX` ActionScript property, which moves the object into 3D space.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_rotation_x(&self, gc_context: &Mutation<'gc>, degrees: Degrees) {
+        if self.base_mut(gc_context).set_rotation_x(degrees) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The rotation in degrees around the Y axis of this display object in local space.
+    /// Returned by the `rotationY` ActionScript property.
+    fn rotation_y(&self) -> Degrees {
+        self.base().rotation_y()
+    }
+
+    /// Sets the rotation in degrees around the Y axis of this display object in local space.
+    /// Set by the `rotationY` ActionScript property, which moves the object into 3D space.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_rotation_y(&self, gc_context: &Mutation<'gc>, degrees: Degrees) {
+        if self.base_mut(gc_context).set_rotation_y(degrees) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The Z axis scale for this display object in local space.
+    /// Returned by the `scaleZ` ActionScript property.
+    fn scale_z(&self) -> f64 {
+        self.base().scale_z()
+    }
+
+    /// Sets the Z axis scale for this display object in local space.
+    /// Set by the `scaleZ` ActionScript property, which moves the object into 3D space.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_scale_z(&self, gc_context: &Mutation<'gc>, value: f64) {
+        if self.base_mut(gc_context).set_scale_z(value) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The 3D transformation matrix of this object, if it has been moved into 3D space.
+    fn matrix3d(&self) -> Option<Matrix3D> {
+        self.base().matrix3d().copied()
+    }
+
+    /// Sets the 3D transformation matrix of this object.
+    /// Setting `None` flattens the object back into 2D.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_matrix3d(&self, gc_context: &Mutation<'gc>, matrix3d: Option<Matrix3D>) {
+        self.base_mut(gc_context).set_matrix3d(matrix3d);
+        if let Some(parent) = self.parent() {
+            parent.invalidate_cached_bitmap(gc_context);
+        }
+    }
+
+    /// Whether this object, or any of its ancestors, is in 3D space.
+    fn is_in_3d(&self) -> bool {
+        let mut node = Some((*self).into());
+        while let Some(display_object) = node {
+            if display_object.base().matrix3d().is_some() {
+                return true;
+            }
+            node = display_object.parent();
+        }
+        false
+    }
+
+    /// The projection used to render any 3D children of this object, if it has its own.
+    fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.base().perspective_projection()
+    }
+
+    /// Sets the projection used to render any 3D children of this object.
+    fn set_perspective_projection(
+        &self,
+        gc_context: &Mutation<'gc>,
+        projection: Option<PerspectiveProjection>,
+    ) {
+        self.base_mut(gc_context)
+            .set_perspective_projection(projection);
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
     /// Gets the pixel width of the AABB containing this display object in local space.
     /// Returned by the ActionScript `_width`/`width` properties.
     fn width(&self) -> f64 {
@@ -12,7 +12,7 @@ use crate::avm2::{
     Activation as Avm2Activation, EventObject as Avm2EventObject, Object as Avm2Object,
     StageObject as Avm2StageObject, TObject as _,
 };
-use crate::backend::ui::MouseCursor;
+use crate::backend::ui::{ClipboardData, ClipboardFormat, MouseCursor};
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::interactive::{
     InteractiveObject, InteractiveObjectBase, TInteractiveObject,
@@ -212,7 +212,7 @@ impl<'gc> EditText<'gc> {
     ///
     /// See <https://open-flash.github.io/mirrors/as2-language-reference/TextFormat.html#getTextExtent()>.
     /// See <https://help.adobe.com/en_US/FlashPlatform/reference/actionscript/3/flash/text/TextLineMetrics.html>.
-    const GUTTER: Twips = Twips::new(40);
+    pub const GUTTER: Twips = Twips::new(40);
 
     /// Creates a new `EditText` from an SWF `DefineEditText` tag.
     pub fn from_swf_tag(
@@ -617,6 +617,20 @@ impl<'gc> EditText<'gc> {
             .set(EditTextFlag::ALWAYS_SHOW_SELECTION, value);
     }
 
+    pub fn use_rich_text_clipboard(self) -> bool {
+        self.0
+            .read()
+            .flags
+            .contains(EditTextFlag::USE_RICH_TEXT_CLIPBOARD)
+    }
+
+    pub fn set_use_rich_text_clipboard(self, context: &mut UpdateContext<'gc>, value: bool) {
+        self.0
+            .write(context.gc())
+            .flags
+            .set(EditTextFlag::USE_RICH_TEXT_CLIPBOARD, value);
+    }
+
     pub fn is_device_font(self) -> bool {
         !self.0.read().flags.contains(EditTextFlag::USE_OUTLINES)
     }
@@ -704,6 +718,21 @@ impl<'gc> EditText<'gc> {
         self.relayout(context);
     }
 
+    /// Replace the text in the range [from, to) with formatted text, keeping its formatting.
+    pub fn replace_with_formatted(
+        self,
+        from: usize,
+        to: usize,
+        spans: &FormatSpans,
+        context: &mut UpdateContext<'gc>,
+    ) {
+        self.0
+            .write(context.gc_context)
+            .text_spans
+            .replace_with_formatted(from, to, spans);
+        self.relayout(context);
+    }
+
     /// Construct a base text transform for a particular `EditText` span.
     ///
     /// This `text_transform` is separate from and relative to the base
@@ -1157,6 +1186,7 @@ impl<'gc> EditText<'gc> {
                             context.transform_stack.push(&Transform {
                                 matrix: transform.matrix,
                                 color_transform: ColorTransform::IDENTITY,
+                                ..Default::default()
                             });
                         } else {
                             context.transform_stack.push(transform);
@@ -1515,6 +1545,58 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// Copy the selected text to the clipboard, along with its formatting
+    /// if the rich text clipboard is used.
+    fn copy_selection(self, selection: TextSelection, context: &mut UpdateContext<'gc>) {
+        let read = self.0.read();
+        let text = read.text_spans.text()[selection.start()..selection.end()].to_string();
+        if read.flags.contains(EditTextFlag::USE_RICH_TEXT_CLIPBOARD) {
+            let html = read
+                .text_spans
+                .slice(selection.start(), selection.end())
+                .to_html()
+                .to_string();
+            drop(read);
+            context
+                .ui
+                .set_clipboard_data(vec![ClipboardData::Text(text), ClipboardData::Html(html)]);
+        } else {
+            drop(read);
+            context.ui.set_clipboard_content(text);
+        }
+    }
+
+    /// The formatted text on the clipboard, if the rich text clipboard is used
+    /// and it matches the text to paste.
+    fn formatted_clipboard_text(
+        self,
+        text: &WStr,
+        context: &mut UpdateContext<'gc>,
+    ) -> Option<FormatSpans> {
+        if !self.use_rich_text_clipboard() {
+            return None;
+        }
+        let Some(ClipboardData::Html(html)) = context.ui.clipboard_data(ClipboardFormat::Html)
+        else {
+            return None;
+        };
+
+        let read = self.0.read();
+        let mut spans = FormatSpans::from_html(
+            &WString::from_utf8(&html),
+            read.text_spans.default_format().clone(),
+            read.flags.contains(EditTextFlag::MULTILINE),
+            read.flags.contains(EditTextFlag::CONDENSE_WHITE),
+            read.static_data.swf.version(),
+        );
+
+        // The last paragraph ends with a newline, which wasn't part of the copied text.
+        if spans.text().len() == text.len() + 1 {
+            spans = spans.slice(0, text.len());
+        }
+        (spans.text() == text).then_some(spans)
+    }
+
     pub fn is_text_control_applicable(
         self,
         control_code: TextControlCode,
@@ -1624,8 +1706,7 @@ impl<'gc> EditText<'gc> {
                 );
             }
             TextControlCode::Copy => {
-                let text = &self.text()[selection.start()..selection.end()];
-                context.ui.set_clipboard_content(text.to_string());
+                self.copy_selection(selection, context);
             }
             TextControlCode::Paste => 'paste: {
                 let text = context.ui.clipboard_content();
@@ -1646,7 +1727,17 @@ impl<'gc> EditText<'gc> {
                 }
 
                 if text.len() <= self.available_chars() {
-                    self.replace_text(selection.start(), selection.end(), text, context);
+                    match self.formatted_clipboard_text(text, context) {
+                        Some(spans) => self.replace_with_formatted(
+                            selection.start(),
+                            selection.end(),
+                            &spans,
+                            context,
+                        ),
+                        None => {
+                            self.replace_text(selection.start(), selection.end(), text, context)
+                        }
+                    }
                     let new_pos = selection.start() + text.len();
                     if is_selectable {
                         self.set_selection(
@@ -1663,8 +1754,7 @@ impl<'gc> EditText<'gc> {
                 }
             }
             TextControlCode::Cut => {
-                let text = &self.text()[selection.start()..selection.end()];
-                context.ui.set_clipboard_content(text.to_string());
+                self.copy_selection(selection, context);
 
                 self.replace_text(selection.start(), selection.end(), WStr::empty(), context);
                 if is_selectable {
@@ -2860,6 +2950,7 @@ bitflags::bitflags! {
         const HAS_BACKGROUND = 1 << 1;
         const CONDENSE_WHITE = 1 << 13;
         const ALWAYS_SHOW_SELECTION = 1 << 14;
+        const USE_RICH_TEXT_CLIPBOARD = 1 << 15;
 
         // The following bits need to match `swf::EditTextFlag`.
         const READ_ONLY = 1 << 3;
@@ -207,7 +207,9 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             let Some(local_matrix) = self.global_to_local_matrix() else {
                 return false;
             };
-            let point = local_matrix * point;
+            let Some(point) = self.global_to_local(point) else {
+                return false;
+            };
             if let Some(drawing) = &self.0.read().drawing {
                 if drawing.hit_test(point, &local_matrix) {
                     return true;
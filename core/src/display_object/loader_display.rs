@@ -1,7 +1,11 @@
+use crate::avm2::globals::flash::utils::timer;
 use crate::avm2::Activation;
+use crate::avm2::Avm2;
 use crate::avm2::Object as Avm2Object;
+use crate::character::Character;
 use crate::context::RenderContext;
 use crate::context::UpdateContext;
+use crate::datagram_socket::DatagramSockets;
 use crate::display_object::InteractiveObject;
 use crate::display_object::TInteractiveObject;
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr};
@@ -10,6 +14,7 @@ use crate::prelude::*;
 
 use crate::display_object::container::ChildContainer;
 use crate::display_object::interactive::InteractiveObjectBase;
+use crate::streams::StreamManager;
 use crate::tag_utils::SwfMovie;
 use core::fmt;
 use gc_arena::GcWeakCell;
@@ -60,6 +65,72 @@ impl<'gc> LoaderDisplay<'gc> {
     pub fn downgrade(self) -> LoaderDisplayWeak<'gc> {
         LoaderDisplayWeak(GcCell::downgrade(self.0))
     }
+
+    /// Stop everything the loaded content is doing, before it gets unloaded.
+    ///
+    /// This stops the clips and sounds of the content, and removes it from the
+    /// frame broadcasts. The timers, sockets and streams of the loaded movie,
+    /// and of any movie loaded by a `Loader` within it, are closed as well, and
+    /// the listeners their code added to the stage and to the frame broadcasts
+    /// of other objects are removed.
+    pub fn stop_content(self, context: &mut UpdateContext<'gc>) {
+        let Some(content) = self.child_by_index(0) else {
+            return;
+        };
+
+        let mut movies = vec![content.movie()];
+        let mut objects = vec![content];
+        while let Some(object) = objects.pop() {
+            context.stop_sounds_with_display_object(object);
+
+            if let Some(clip) = object.as_movie_clip() {
+                clip.stop(context);
+            }
+
+            if let DisplayObject::LoaderDisplay(loader) = object {
+                if let Some(nested) = loader.child_by_index(0) {
+                    movies.push(nested.movie());
+                }
+            }
+
+            if let Some(container) = object.as_container() {
+                objects.extend(container.iter_render_list());
+            }
+        }
+
+        Avm2::unregister_broadcast_listeners_within(context, content);
+        Avm2::remove_listeners_of_movies(context, &movies);
+
+        for movie in &movies {
+            // Sounds played from script aren't tied to a display object, so
+            // they're found through the sounds of the movie's library.
+            let sounds: Vec<_> = context
+                .library
+                .library_for_movie(movie.clone())
+                .map(|library| {
+                    library
+                        .characters()
+                        .values()
+                        .filter_map(|character| match character {
+                            Character::Sound(sound) => Some(*sound),
+                            _ => None,
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            for sound in sounds {
+                context.stop_sounds_with_handle(sound);
+            }
+            context.stop_sounds_for_movie(movie);
+
+            for callback in context.timers.remove_for_movie(movie) {
+                timer::mark_stopped(context, &callback);
+            }
+            context.sockets.close_for_movie(movie);
+            DatagramSockets::close_for_movie(context, movie);
+            StreamManager::close_for_movie(context, movie);
+        }
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for LoaderDisplay<'gc> {
@@ -1107,6 +1107,57 @@ impl<'gc> MovieClip<'gc> {
         self.0.read().current_frame()
     }
 
+    /// Creates an unparented copy of this clip, laid out as it is on the given frame of its
+    /// timeline, so that a frame other than the current one can be printed.
+    ///
+    /// The copy doesn't make any sound, and must be discarded with `discard_print_copy` once it's
+    /// printed.
+    pub fn print_copy(self, context: &mut UpdateContext<'gc>, frame: FrameNumber) -> Self {
+        let read = self.0.read();
+        let copy = MovieClip::new_with_data(
+            context.gc_context,
+            read.id(),
+            read.static_data.swf.clone(),
+            read.total_frames(),
+        );
+        {
+            let mut write = copy.0.write(context.gc_context);
+            write.static_data = read.static_data;
+            write.flags = MovieClipFlags::PRINT_COPY;
+        }
+        drop(read);
+
+        copy.run_goto(context, frame, true);
+        copy
+    }
+
+    /// Discards a copy made by `print_copy`, without running any of the scripts it queued.
+    pub fn discard_print_copy(self, context: &mut UpdateContext<'gc>) {
+        fn mark_pending_removal<'gc>(object: DisplayObject<'gc>, gc_context: &Mutation<'gc>) {
+            object.set_avm1_pending_removal(gc_context, true);
+            if let Some(container) = object.as_container() {
+                for child in container.iter_render_list() {
+                    mark_pending_removal(child, gc_context);
+                }
+            }
+        }
+
+        // Clips pending removal get unloaded without dispatching `onUnload`, and the actions
+        // they queued are dropped.
+        mark_pending_removal(self.into(), context.gc_context);
+        self.avm1_unload(context);
+    }
+
+    /// Whether this clip is part of a copy made by `print_copy`.
+    fn is_in_print_copy(self) -> bool {
+        let mut top: DisplayObject<'gc> = self.into();
+        while let Some(parent) = top.parent() {
+            top = parent;
+        }
+        top.as_movie_clip()
+            .is_some_and(|clip| clip.0.read().flags.contains(MovieClipFlags::PRINT_COPY))
+    }
+
     /// Return the current scene.
     pub fn current_scene(self) -> Option<Scene> {
         let current_frame = self.0.read().current_frame();
@@ -2810,8 +2861,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                 }
             }
 
-            let point = local_matrix * point;
-            if let Some(drawing) = self.drawing() {
+            if let (Some(point), Some(drawing)) = (self.global_to_local(point), self.drawing()) {
                 if drawing.hit_test(point, &local_matrix) {
                     return true;
                 }
@@ -3160,7 +3210,7 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
 
             // Check drawing, because this selects the current clip, it must have mouse enabled
             if self.mouse_enabled() && check_non_interactive {
-                let point = local_matrix * point;
+                let point = self.global_to_local(point)?;
                 if let Some(drawing) = self.drawing() {
                     if drawing.hit_test(point, &local_matrix) {
                         return Some(this);
@@ -3305,7 +3355,9 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
 
             // Check drawing, because this selects the current clip, it must have mouse enabled
             if self.world_bounds().contains(point) {
-                let point = local_matrix * point;
+                let Some(point) = self.global_to_local(point) else {
+                    return Avm2MousePick::Miss;
+                };
 
                 if let Some(drawing) = self.drawing() {
                     if drawing.hit_test(point, &local_matrix) {
@@ -4769,6 +4821,9 @@ impl<'gc, 'a> MovieClip<'gc> {
         if mc.playing() {
             if let (Some(stream_info), None) = (&mc.static_data.audio_stream_info, mc.audio_stream)
             {
+                if self.is_in_print_copy() {
+                    return Ok(());
+                }
                 let slice = mc
                     .static_data
                     .swf
@@ -4790,6 +4845,9 @@ impl<'gc, 'a> MovieClip<'gc> {
         reader: &mut SwfStream<'a>,
     ) -> Result<(), Error> {
         let start_sound = reader.read_start_sound_1()?;
+        if self.is_in_print_copy() {
+            return Ok(());
+        }
         AudioManager::perform_sound_event(
             self.into(),
             context,
@@ -5177,6 +5235,11 @@ bitflags! {
 
         /// Whether this `MovieClip` has been post-instantiated yet.
         const POST_INSTANTIATED = 1 << 5;
+
+        /// Whether this `MovieClip` is a copy made to print one of its frames.
+        ///
+        /// Copies, and the clips they contain, don't make any sound.
+        const PRINT_COPY = 1 << 6;
     }
 }
 
@@ -828,7 +828,7 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
     fn render(&self, context: &mut RenderContext<'_, 'gc>) {
         context.transform_stack.push(&Transform {
             matrix: self.0.read().viewport_matrix,
-            color_transform: Default::default(),
+            ..Default::default()
         });
 
         // All of our Stage3D instances get rendered *underneath* the main stage.
@@ -3,7 +3,8 @@ use crate::avm2::{
 };
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr};
-use crate::font::TextRenderSettings;
+use crate::font::{Font, Glyph, TextRenderSettings};
+use crate::library::Library;
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
@@ -12,6 +13,7 @@ use gc_arena::{Collect, GcCell, Mutation};
 use ruffle_render::commands::CommandHandler;
 use ruffle_render::transform::Transform;
 use std::cell::{Ref, RefMut};
+use std::ops::Range;
 use std::sync::Arc;
 
 #[derive(Clone, Collect, Copy)]
@@ -34,6 +36,57 @@ pub struct TextData<'gc> {
     #[collect(require_static)]
     render_settings: TextRenderSettings,
     avm2_object: Option<Avm2Object<'gc>>,
+
+    /// Which characters have been selected through a `TextSnapshot`.
+    #[collect(require_static)]
+    selection: Vec<bool>,
+
+    /// The color drawn behind selected characters.
+    #[collect(require_static)]
+    select_color: swf::Color,
+}
+
+/// A character of a static text, as laid out by its text records.
+pub struct StaticTextChar<'gc> {
+    pub character: char,
+
+    /// Whether this character begins a new line of the text.
+    pub starts_line: bool,
+
+    pub font: Font<'gc>,
+    pub color: swf::Color,
+    pub height: Twips,
+
+    /// The transform from glyph space to the text's local space.
+    pub matrix: Matrix,
+
+    /// The corners of the box taken up by this character, in the text's local space.
+    /// In order: top left, bottom left, top right and bottom right.
+    pub corners: [Point<Twips>; 4],
+}
+
+/// A glyph of a static text, positioned in the space of its text records.
+struct PositionedGlyph<'a, 'gc> {
+    font: &'a Font<'gc>,
+    glyph: &'a Glyph,
+    matrix: Matrix,
+    color: swf::Color,
+    height: Twips,
+    advance: Twips,
+    starts_line: bool,
+}
+
+impl PositionedGlyph<'_, '_> {
+    /// The box taken up by this glyph on its line, spanning its advance and the
+    /// ascent and descent of its font.
+    fn bounds(&self) -> Rectangle<Twips> {
+        Rectangle {
+            x_min: self.matrix.tx,
+            x_max: self.matrix.tx + self.advance,
+            y_min: self.matrix.ty - self.font.get_baseline_for_height(self.height),
+            y_max: self.matrix.ty + self.font.get_descent_for_height(self.height),
+        }
+    }
 }
 
 impl<'gc> Text<'gc> {
@@ -58,6 +111,8 @@ impl<'gc> Text<'gc> {
                 ),
                 render_settings: Default::default(),
                 avm2_object: None,
+                selection: Vec::new(),
+                select_color: swf::Color::from_rgb(0xFFFF00, 255),
             },
         ))
     }
@@ -66,6 +121,117 @@ impl<'gc> Text<'gc> {
         self.0.write(gc_context).render_settings = settings;
         self.invalidate_cached_bitmap(gc_context);
     }
+
+    /// Returns the characters of this text, in the order they are laid out.
+    pub fn chars(self, library: &Library<'gc>) -> Vec<StaticTextChar<'gc>> {
+        let text_transform = self.0.read().static_data.text_transform;
+        let mut chars = Vec::new();
+        self.for_each_glyph(library, |_, glyph| {
+            let bounds = glyph.bounds();
+            chars.push(StaticTextChar {
+                character: glyph.glyph.character(),
+                starts_line: glyph.starts_line,
+                font: *glyph.font,
+                color: glyph.color,
+                height: glyph.height,
+                matrix: text_transform * glyph.matrix,
+                corners: [
+                    text_transform * Point::new(bounds.x_min, bounds.y_min),
+                    text_transform * Point::new(bounds.x_min, bounds.y_max),
+                    text_transform * Point::new(bounds.x_max, bounds.y_min),
+                    text_transform * Point::new(bounds.x_max, bounds.y_max),
+                ],
+            });
+        });
+        chars
+    }
+
+    pub fn is_char_selected(self, index: usize) -> bool {
+        self.0.read().selection.get(index).copied().unwrap_or(false)
+    }
+
+    /// Selects or deselects the characters in the given range.
+    pub fn set_chars_selected(
+        self,
+        gc_context: &Mutation<'gc>,
+        range: Range<usize>,
+        selected: bool,
+    ) {
+        let mut write = self.0.write(gc_context);
+        if write.selection.len() < range.end {
+            write.selection.resize(range.end, false);
+        }
+        write.selection[range].fill(selected);
+        drop(write);
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    pub fn set_select_color(self, gc_context: &Mutation<'gc>, color: swf::Color) {
+        self.0.write(gc_context).select_color = color;
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    /// Calls `f` with the index and position of every glyph of this text.
+    ///
+    /// Characters without a glyph in their font are skipped, and don't count towards the index.
+    fn for_each_glyph<'lib>(
+        self,
+        library: &Library<'lib>,
+        mut f: impl FnMut(usize, PositionedGlyph<'_, 'lib>),
+    ) {
+        let Some(library) = library.library_for_movie(self.movie()) else {
+            return;
+        };
+        let tf = self.0.read();
+
+        let mut color = swf::Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 0,
+        };
+        let mut font_id = 0;
+        let mut height = Twips::ZERO;
+        let mut matrix = Matrix::default();
+        let mut index = 0;
+        let mut line_y = None;
+        for block in &tf.static_data.text_blocks {
+            if let Some(x) = block.x_offset {
+                matrix.tx = x;
+            }
+            if let Some(y) = block.y_offset {
+                matrix.ty = y;
+            }
+            color = block.color.unwrap_or(color);
+            font_id = block.font_id.unwrap_or(font_id);
+            height = block.height.unwrap_or(height);
+            if let Some(font) = library.get_font(font_id) {
+                let scale = (height.get() as f32) / font.scale();
+                matrix.a = scale;
+                matrix.d = scale;
+                for c in &block.glyphs {
+                    if let Some(glyph) = font.get_glyph(c.index as usize) {
+                        let advance = Twips::new(c.advance);
+                        f(
+                            index,
+                            PositionedGlyph {
+                                font: &font,
+                                glyph,
+                                matrix,
+                                color,
+                                height,
+                                advance,
+                                starts_line: line_y != Some(matrix.ty),
+                            },
+                        );
+                        line_y = Some(matrix.ty);
+                        index += 1;
+                        matrix.tx += advance;
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Text<'gc> {
@@ -111,57 +277,47 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext) {
-        let tf = self.0.read();
+        let (text_transform, select_color, has_selection) = {
+            let tf = self.0.read();
+            (
+                tf.static_data.text_transform,
+                tf.select_color,
+                tf.selection.contains(&true),
+            )
+        };
         context.transform_stack.push(&Transform {
-            matrix: tf.static_data.text_transform,
+            matrix: text_transform,
             ..Default::default()
         });
 
-        let mut color = swf::Color {
-            r: 0,
-            g: 0,
-            b: 0,
-            a: 0,
-        };
-        let mut font_id = 0;
-        let mut height = Twips::ZERO;
-        let mut transform: Transform = Default::default();
-        for block in &tf.static_data.text_blocks {
-            if let Some(x) = block.x_offset {
-                transform.matrix.tx = x;
-            }
-            if let Some(y) = block.y_offset {
-                transform.matrix.ty = y;
-            }
-            color = block.color.unwrap_or(color);
-            font_id = block.font_id.unwrap_or(font_id);
-            height = block.height.unwrap_or(height);
-            if let Some(font) = context
-                .library
-                .library_for_movie(self.movie())
-                .unwrap()
-                .get_font(font_id)
-            {
-                let scale = (height.get() as f32) / font.scale();
-                transform.matrix.a = scale;
-                transform.matrix.d = scale;
-                transform.color_transform.set_mult_color(&color);
-                for c in &block.glyphs {
-                    if let Some(glyph) = font.get_glyph(c.index as usize) {
-                        if let Some(glyph_shape_handle) = glyph.shape_handle(context.renderer) {
-                            context.transform_stack.push(&transform);
-                            context.commands.render_shape(
-                                glyph_shape_handle,
-                                context.transform_stack.transform(),
-                            );
-                            context.transform_stack.pop();
-                        }
-
-                        transform.matrix.tx += Twips::new(c.advance);
-                    }
+        let library = context.library;
+        if has_selection {
+            // The selection is drawn behind all of the glyphs.
+            self.for_each_glyph(library, |index, glyph| {
+                if self.is_char_selected(index) {
+                    context.commands.draw_rect(
+                        select_color,
+                        context.transform_stack.transform().matrix
+                            * Matrix::create_box_from_rectangle(&glyph.bounds()),
+                    );
                 }
-            }
+            });
         }
+
+        self.for_each_glyph(library, |_, glyph| {
+            if let Some(glyph_shape_handle) = glyph.glyph.shape_handle(context.renderer) {
+                let mut transform = Transform {
+                    matrix: glyph.matrix,
+                    ..Default::default()
+                };
+                transform.color_transform.set_mult_color(&glyph.color);
+                context.transform_stack.push(&transform);
+                context
+                    .commands
+                    .render_shape(glyph_shape_handle, context.transform_stack.transform());
+                context.transform_stack.pop();
+            }
+        });
         context.transform_stack.pop();
     }
 
@@ -191,7 +347,10 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
             let Some(text_matrix) = tf.static_data.text_transform.inverse() else {
                 return false;
             };
-            point = text_matrix * local_matrix * point;
+            let Some(local_point) = self.global_to_local(point) else {
+                return false;
+            };
+            point = text_matrix * local_point;
 
             let mut font_id = 0;
             let mut height = Twips::ZERO;
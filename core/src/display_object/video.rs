@@ -1,6 +1,7 @@
 //! Video player display object
 
 use crate::avm1::{Object as Avm1Object, StageObject as Avm1StageObject, Value as Avm1Value};
+use crate::avm2::media_capture::CameraFeed;
 use crate::avm2::{
     Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
     Value as Avm2Value,
@@ -101,7 +102,7 @@ pub enum VideoSource<'gc> {
     /// particular character. If you need to mutate the video source, consider
     /// reallocating a new source for your specific video instead.
     ///
-    /// This warning does not apply to `NetStream` or `Unconnected` videos,
+    /// This warning does not apply to `NetStream`, `Camera` or `Unconnected` videos,
     /// which are never aliased.
     Swf {
         /// The video stream definition.
@@ -119,6 +120,12 @@ pub enum VideoSource<'gc> {
         /// The stream the video is downloaded from.
         stream: NetStream<'gc>,
     },
+    /// An attached Camera.
+    Camera {
+        /// The frames captured by the camera.
+        #[collect(require_static)]
+        feed: CameraFeed,
+    },
     Unconnected,
 }
 
@@ -192,6 +199,29 @@ impl<'gc> Video<'gc> {
         video.keyframes = BTreeSet::new();
     }
 
+    /// Convert this Video into a Camera sourced video, or disconnect it when no camera is given.
+    ///
+    /// Existing video state related to the old video stream will be dropped.
+    pub fn attach_camera(self, context: &mut UpdateContext<'gc>, feed: Option<CameraFeed>) {
+        let mut video = self.0.write(context.gc_context);
+
+        // The old source may only be collected much later, so a camera feed is released right
+        // away to stop the camera from capturing.
+        let mut old_source = video.source.write(context.gc_context);
+        if matches!(*old_source, VideoSource::Camera { .. }) {
+            *old_source = VideoSource::Unconnected;
+        }
+        drop(old_source);
+
+        let source = match feed {
+            Some(feed) => VideoSource::Camera { feed },
+            None => VideoSource::Unconnected,
+        };
+        video.source = GcCell::new(context.gc_context, source);
+        video.stream = VideoStream::Uninstantiated(0);
+        video.keyframes = BTreeSet::new();
+    }
+
     /// Preload frame data from an SWF.
     ///
     /// This function yields an error if this video player is not playing an
@@ -216,6 +246,7 @@ impl<'gc> Video<'gc> {
                 frames.insert(tag.frame_num.into(), (subslice.start, subslice.end));
             }
             VideoSource::NetStream { .. } => {}
+            VideoSource::Camera { .. } => {}
             VideoSource::Unconnected { .. } => {}
         }
     }
@@ -244,6 +275,7 @@ impl<'gc> Video<'gc> {
         let num_frames = match &*read.source.read() {
             VideoSource::Swf { streamdef, .. } => streamdef.num_frames as usize,
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -332,6 +364,7 @@ impl<'gc> Video<'gc> {
                 }
             },
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -435,6 +468,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 }
             }
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -496,6 +530,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         match &*self.0.read().source.read() {
             VideoSource::Swf { streamdef, .. } => streamdef.id,
             VideoSource::NetStream { .. } => 0,
+            VideoSource::Camera { .. } => 0,
             VideoSource::Unconnected { .. } => 0,
         }
     }
@@ -541,6 +576,11 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 stream.last_decoded_bitmap(),
                 None,
             ),
+            VideoSource::Camera { feed } => match feed.frame() {
+                Some(frame) => (false, None, read.movie.version(), Some(frame), None),
+                // The camera hasn't captured anything yet.
+                None => return context.transform_stack.pop(),
+            },
             VideoSource::Unconnected { .. } => return context.transform_stack.pop(),
         };
 
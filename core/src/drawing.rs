@@ -1,12 +1,14 @@
 use crate::context::RenderContext;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
-use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, BitmapSize, BitmapSource};
+use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, BitmapSize, BitmapSource, SyncHandle};
 use ruffle_render::commands::CommandHandler;
+use ruffle_render::pixel_bender::PixelBenderShaderHandle;
 use ruffle_render::shape_utils::{
     cubic_curve_bounds, quadratic_curve_bounds, DistilledShape, DrawCommand, DrawPath, FillRule,
+    ShaderFill, ShaderFillArea,
 };
 use std::cell::{Cell, RefCell};
-use swf::{FillStyle, LineStyle, Point, Rectangle, Twips};
+use swf::{FillStyle, LineStyle, Matrix, Point, Rectangle, Twips};
 
 #[derive(Clone, Debug)]
 pub struct Drawing {
@@ -15,7 +17,7 @@ pub struct Drawing {
     edge_bounds: Rectangle<Twips>,
     dirty: Cell<bool>,
     paths: Vec<DrawingPath>,
-    bitmaps: Vec<BitmapInfo>,
+    bitmaps: Vec<DrawingBitmap>,
     current_fill: Option<DrawingFill>,
     current_line: Option<DrawingLine>,
     pending_lines: Vec<DrawingLine>,
@@ -244,73 +246,171 @@ impl Drawing {
         self.dirty.set(true);
     }
 
-    pub fn add_bitmap(&mut self, bitmap: BitmapInfo) -> u16 {
+    /// Add a bitmap to fill paths with, returning its id.
+    ///
+    /// `fill_scale` is the scaling appended to the movie's matrix in the fill style,
+    /// so that the original matrix can be recovered.
+    pub fn add_bitmap(&mut self, bitmap: BitmapInfo, fill_scale: Matrix) -> u16 {
         let id = self.bitmaps.len() as u16;
-        self.bitmaps.push(bitmap);
+        self.bitmaps
+            .push(DrawingBitmap::Bitmap { bitmap, fill_scale });
         id
     }
 
-    /// Obtain a `ShapeHandle` that represents this `Drawing`, or `None` if it is empty.
-    pub fn register_or_replace(&self, renderer: &mut dyn RenderBackend) -> Option<ShapeHandle> {
-        if self.dirty.get() {
-            let mut paths = Vec::with_capacity(self.paths.len());
-
-            for path in &self.paths {
-                match path {
-                    DrawingPath::Fill(fill) => {
-                        paths.push(DrawPath::Fill {
-                            style: &fill.style,
-                            commands: fill.commands.to_owned(),
-                            winding_rule: fill.rule,
-                        });
-                    }
-                    DrawingPath::Line(line) => {
-                        paths.push(DrawPath::Stroke {
-                            style: &line.style,
-                            commands: line.commands.to_owned(),
-                            is_closed: line.is_closed,
-                        });
+    /// The scaling appended to the movie's matrix when filling with the bitmap with the given id.
+    /// This is `None` for shader fills and bitmaps that weren't added to this drawing.
+    pub fn bitmap_fill_scale(&self, id: u16) -> Option<Matrix> {
+        match self.bitmaps.get(id as usize)? {
+            DrawingBitmap::Bitmap { fill_scale, .. } => Some(*fill_scale),
+            DrawingBitmap::Shader { .. } => None,
+        }
+    }
+
+    /// Add a shader fill, returning the id of the bitmap that it gets rendered into.
+    /// Use `ShaderFill::bitmap_fill` with this id to fill a path with the shader.
+    pub fn add_shader_fill(&mut self, fill: ShaderFill) -> u16 {
+        let id = self.bitmaps.len() as u16;
+        self.bitmaps.push(DrawingBitmap::Shader {
+            fill,
+            rendered: RefCell::new(None),
+        });
+        id
+    }
+
+    /// The shader fill rendered into the bitmap with the given id, if any.
+    pub fn shader_fill(&self, id: u16) -> Option<&ShaderFill> {
+        match self.bitmaps.get(id as usize)? {
+            DrawingBitmap::Bitmap { .. } => None,
+            DrawingBitmap::Shader { fill, .. } => Some(fill),
+        }
+    }
+
+    /// Run every shader fill over the area covered by this drawing.
+    ///
+    /// The area is limited to the size of the viewport, beyond which the rendered bitmap repeats.
+    fn render_shader_fills(&self, renderer: &mut dyn RenderBackend) {
+        let viewport = renderer.viewport_dimensions();
+        for bitmap in &self.bitmaps {
+            let DrawingBitmap::Shader { fill, rendered } = bitmap else {
+                continue;
+            };
+
+            let previous = rendered.take();
+            let bitmap = fill
+                .area_for_bounds(&self.shape_bounds, viewport.width, viewport.height)
+                .and_then(|area| {
+                    // The shader only needs to be compiled again when the area changes.
+                    let shader = match previous {
+                        Some(previous) if previous.area == area => Ok(previous.shader),
+                        _ => fill.shader_for_area(renderer, area),
+                    };
+                    let result = shader.and_then(|shader| {
+                        let (handle, sync_handle) = fill.render(renderer, shader.clone(), area)?;
+                        Ok(RenderedShaderFill {
+                            bitmap: BitmapInfo {
+                                handle,
+                                width: area.width as u16,
+                                height: area.height as u16,
+                            },
+                            area,
+                            shader,
+                            _sync_handle: sync_handle,
+                        })
+                    });
+                    match result {
+                        Ok(rendered) => Some(rendered),
+                        Err(e) => {
+                            tracing::error!("Failed to render shader fill: {e}");
+                            None
+                        }
                     }
+                });
+            rendered.replace(bitmap);
+        }
+    }
+
+    /// The paths of this drawing, in the order they are rendered.
+    ///
+    /// Lines drawn while a fill is still open are closed back to the start of that fill.
+    pub fn paths(&self) -> Vec<DrawPath<'_>> {
+        self.collect_paths(true)
+    }
+
+    /// The paths of this drawing, holding only the commands that were drawn.
+    ///
+    /// Unlike `paths`, lines drawn while a fill is still open are left as they are.
+    pub fn recorded_paths(&self) -> Vec<DrawPath<'_>> {
+        self.collect_paths(false)
+    }
+
+    fn collect_paths(&self, close_open_fill: bool) -> Vec<DrawPath<'_>> {
+        let mut paths = Vec::with_capacity(self.paths.len());
+
+        for path in &self.paths {
+            match path {
+                DrawingPath::Fill(fill) => {
+                    paths.push(DrawPath::Fill {
+                        style: &fill.style,
+                        commands: fill.commands.to_owned(),
+                        winding_rule: fill.rule,
+                    });
+                }
+                DrawingPath::Line(line) => {
+                    paths.push(DrawPath::Stroke {
+                        style: &line.style,
+                        commands: line.commands.to_owned(),
+                        is_closed: line.is_closed,
+                    });
                 }
             }
+        }
 
-            if let Some(fill) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style: &fill.style,
-                    commands: fill.commands.to_owned(),
-                    winding_rule: fill.rule,
-                })
-            }
+        if let Some(fill) = &self.current_fill {
+            paths.push(DrawPath::Fill {
+                style: &fill.style,
+                commands: fill.commands.to_owned(),
+                winding_rule: fill.rule,
+            })
+        }
 
-            for line in &self.pending_lines {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo(self.fill_start));
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
+        for line in &self.pending_lines {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if close_open_fill && self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo(self.fill_start));
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
 
-            if let Some(line) = &self.current_line {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo(self.fill_start));
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
-            }
+        if let Some(line) = &self.current_line {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if close_open_fill && self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo(self.fill_start));
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
+
+        paths
+    }
+
+    /// Obtain a `ShapeHandle` that represents this `Drawing`, or `None` if it is empty.
+    pub fn register_or_replace(&self, renderer: &mut dyn RenderBackend) -> Option<ShapeHandle> {
+        if self.dirty.get() {
+            self.render_shader_fills(renderer);
+            let paths = self.paths();
 
             let handle = if paths.is_empty() {
                 None
@@ -434,13 +534,56 @@ impl Drawing {
 
 impl BitmapSource for Drawing {
     fn bitmap_size(&self, id: u16) -> Option<BitmapSize> {
-        self.bitmaps.get(id as usize).map(|bm| BitmapSize {
+        self.bitmaps.get(id as usize)?.with_info(|bm| BitmapSize {
             width: bm.width,
             height: bm.height,
         })
     }
     fn bitmap_handle(&self, id: u16, _backend: &mut dyn RenderBackend) -> Option<BitmapHandle> {
-        self.bitmaps.get(id as usize).map(|bm| bm.handle.clone())
+        self.bitmaps
+            .get(id as usize)?
+            .with_info(|bm| bm.handle.clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum DrawingBitmap {
+    Bitmap {
+        bitmap: BitmapInfo,
+        fill_scale: Matrix,
+    },
+
+    /// A shader fill, along with the bitmap it was last rendered into.
+    Shader {
+        fill: ShaderFill,
+        rendered: RefCell<Option<RenderedShaderFill>>,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct RenderedShaderFill {
+    bitmap: BitmapInfo,
+
+    /// The area of the shader's coordinate space that the bitmap covers.
+    area: ShaderFillArea,
+
+    /// The shader that was run over `area`, kept so that it isn't compiled again.
+    shader: PixelBenderShaderHandle,
+
+    /// Tracks the shader's writes to the bitmap, which the backend may still be performing.
+    /// This is kept for as long as the bitmap is drawn.
+    _sync_handle: Box<dyn SyncHandle>,
+}
+
+impl DrawingBitmap {
+    fn with_info<T>(&self, f: impl FnOnce(&BitmapInfo) -> T) -> Option<T> {
+        match self {
+            DrawingBitmap::Bitmap { bitmap, .. } => Some(f(bitmap)),
+            DrawingBitmap::Shader { rendered, .. } => rendered
+                .borrow()
+                .as_ref()
+                .map(|rendered| f(&rendered.bitmap)),
+        }
     }
 }
 
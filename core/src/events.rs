@@ -31,11 +31,24 @@ pub enum PlayerEvent {
         delta: MouseWheelDelta,
     },
     GamepadButtonDown {
+        gamepad: GamepadId,
         button: GamepadButton,
     },
     GamepadButtonUp {
+        gamepad: GamepadId,
         button: GamepadButton,
     },
+    GamepadConnected {
+        gamepad: GamepadId,
+    },
+    GamepadDisconnected {
+        gamepad: GamepadId,
+    },
+    GamepadAxisChanged {
+        gamepad: GamepadId,
+        axis: GamepadAxis,
+        value: f64,
+    },
     TextInput {
         codepoint: char,
     },
@@ -783,6 +796,9 @@ impl ButtonKeyCode {
     }
 }
 
+/// An identifier given by the frontend to each connected gamepad.
+pub type GamepadId = usize;
+
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
 pub enum GamepadButton {
@@ -801,3 +817,14 @@ pub enum GamepadButton {
     DPadLeft,
     DPadRight,
 }
+
+/// An analog stick axis of a gamepad, ranging from -1.0 to 1.0.
+///
+/// The vertical axes go up as their value goes up.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+}
@@ -59,8 +59,7 @@ pub struct EvalParameters {
 
 impl EvalParameters {
     /// Construct eval parameters from their individual parts.
-    #[allow(dead_code)]
-    fn from_parts(height: Twips, letter_spacing: Twips, kerning: bool) -> Self {
+    pub fn from_parts(height: Twips, letter_spacing: Twips, kerning: bool) -> Self {
         Self {
             height,
             letter_spacing,
@@ -208,6 +207,7 @@ impl FontFace {
                             shape_handle: Default::default(),
                             shape: GlyphShape::Drawing(drawing),
                             advance,
+                            character,
                         })
                     } else {
                         let advance = Twips::new(face.glyph_hor_advance(glyph_id)? as i32);
@@ -216,6 +216,7 @@ impl FontFace {
                             shape_handle: Default::default(),
                             shape: GlyphShape::None,
                             advance,
+                            character,
                         })
                     }
                 })
@@ -414,6 +415,7 @@ impl<'gc> Font<'gc> {
                 let glyph = Glyph {
                     shape_handle: None.into(),
                     advance: Twips::new(swf_glyph.advance.into()),
+                    character: char::from_u32(code.into()).unwrap_or(char::REPLACEMENT_CHARACTER),
                     shape: GlyphShape::Swf(RefCell::new(SwfGlyphOrShape::Glyph(swf_glyph))),
                 };
 
@@ -788,6 +790,9 @@ pub struct Glyph {
 
     shape: GlyphShape,
     advance: Twips,
+
+    /// The character this glyph represents, used to recover the text of static text.
+    character: char,
 }
 
 impl Glyph {
@@ -805,6 +810,10 @@ impl Glyph {
     pub fn advance(&self) -> Twips {
         self.advance
     }
+
+    pub fn character(&self) -> char {
+        self.character
+    }
 }
 
 /// Structure which identifies a particular font by name and properties.
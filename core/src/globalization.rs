@@ -0,0 +1,120 @@
+//! Locale-aware formatting and parsing of numbers, currencies and dates, backing `flash.globalization`.
+
+mod date_time;
+mod locale_data;
+mod number;
+
+pub use date_time::{date_time_pattern, format_date_time, month_names, weekday_names};
+pub use locale_data::LocaleData;
+pub use number::{format_currency, format_number, parse_currency, parse_number, NumberFormat};
+
+use crate::backend::ui::LanguageIdentifier;
+use locale_data::LOCALES;
+
+/// The name used to request the user's preferred locale.
+pub const DEFAULT_LOCALE_NAME: &str = "i-default";
+
+/// The status of the most recent operation of a `flash.globalization` object,
+/// reported through its `lastOperationStatus` property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LastOperationStatus {
+    NoError,
+    ParseError,
+    UsingDefaultWarning,
+    UsingFallbackWarning,
+}
+
+impl LastOperationStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LastOperationStatus::NoError => "noError",
+            LastOperationStatus::ParseError => "parseError",
+            LastOperationStatus::UsingDefaultWarning => "usingDefaultWarning",
+            LastOperationStatus::UsingFallbackWarning => "usingFallbackWarning",
+        }
+    }
+}
+
+/// The names of all the locales we have data for.
+///
+/// This is a fixed list of common locales rather than the full set known to CLDR, see
+/// the `locale_data` module for which ones are covered.
+pub fn available_locale_names() -> impl Iterator<Item = &'static str> {
+    LOCALES.iter().map(|locale| locale.name)
+}
+
+/// Finds the locale to use for a requested locale name.
+///
+/// The default locale name resolves to the user's language, reported as
+/// [`LastOperationStatus::UsingDefaultWarning`]. A locale we don't have data for falls back to
+/// another locale of the same language, or to US English if there is none, which is reported as
+/// [`LastOperationStatus::UsingFallbackWarning`] even when it was the user's language.
+pub fn resolve_locale(
+    requested: &str,
+    user_language: &LanguageIdentifier,
+) -> (&'static LocaleData, LastOperationStatus) {
+    let (name, status) = if requested == DEFAULT_LOCALE_NAME {
+        (
+            user_language.to_string(),
+            LastOperationStatus::UsingDefaultWarning,
+        )
+    } else {
+        (requested.to_string(), LastOperationStatus::NoError)
+    };
+
+    // Both separators are accepted, and any keywords (after '@') don't affect formatting.
+    let name = name.replace('_', "-");
+    let name = name.split('@').next().unwrap_or_default();
+    if let Some(locale) = LOCALES.iter().find(|l| l.name.eq_ignore_ascii_case(name)) {
+        return (locale, status);
+    }
+
+    let language = name.split('-').next().unwrap_or_default();
+    let locale = LOCALES
+        .iter()
+        .find(|l| {
+            l.name
+                .split('-')
+                .next()
+                .is_some_and(|l| l.eq_ignore_ascii_case(language))
+        })
+        .unwrap_or(&LOCALES[0]);
+    (locale, LastOperationStatus::UsingFallbackWarning)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(requested: &str, user_language: &str) -> (&'static str, LastOperationStatus) {
+        let (locale, status) = resolve_locale(requested, &user_language.parse().unwrap());
+        (locale.name, status)
+    }
+
+    #[test]
+    fn known_locales() {
+        assert_eq!(
+            resolve("de_DE@collation=phonebook", "en-US"),
+            ("de-DE", LastOperationStatus::NoError)
+        );
+        assert_eq!(
+            resolve(DEFAULT_LOCALE_NAME, "ja-JP"),
+            ("ja-JP", LastOperationStatus::UsingDefaultWarning)
+        );
+    }
+
+    #[test]
+    fn fallbacks() {
+        use LastOperationStatus::UsingFallbackWarning;
+        assert_eq!(resolve("de-AT", "en-US"), ("de-DE", UsingFallbackWarning));
+        assert_eq!(resolve("xx-YY", "en-US"), ("en-US", UsingFallbackWarning));
+        assert_eq!(
+            resolve(DEFAULT_LOCALE_NAME, "fr-CA"),
+            ("fr-FR", UsingFallbackWarning)
+        );
+        assert_eq!(
+            resolve(DEFAULT_LOCALE_NAME, "nl-NL"),
+            ("en-US", UsingFallbackWarning)
+        );
+    }
+}
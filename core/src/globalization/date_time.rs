@@ -0,0 +1,206 @@
+//! Formatting of dates and times with `DateTimeFormatter` patterns.
+
+use super::LocaleData;
+use chrono::{DateTime, Datelike, FixedOffset, Timelike};
+
+/// Builds the pattern for a combination of `DateTimeStyle`s, where any style other than
+/// `long`, `medium` and `short` leaves out that part.
+pub fn date_time_pattern(locale: &LocaleData, date_style: &str, time_style: &str) -> String {
+    let style_index = |style: &str| match style {
+        "long" => Some(0),
+        "medium" => Some(1),
+        "short" => Some(2),
+        _ => None,
+    };
+
+    let date = style_index(date_style).map(|i| locale.date_patterns[i]);
+    let time = style_index(time_style).map(|i| locale.time_patterns[i]);
+    match (date, time) {
+        (Some(date), Some(time)) => format!("{date} {time}"),
+        (Some(pattern), None) | (None, Some(pattern)) => pattern.to_string(),
+        (None, None) => String::new(),
+    }
+}
+
+/// Shortens a name to its first letter, as used for the `shortAbbreviation` name style.
+fn narrow_name(abbreviation: &str) -> String {
+    match abbreviation.chars().next() {
+        Some(c) if !c.is_ascii_digit() => c.to_uppercase().collect(),
+        _ => abbreviation.to_string(),
+    }
+}
+
+/// Returns the month names of a locale in a `DateTimeNameStyle`, as used either inside of a date
+/// or on their own.
+pub fn month_names(locale: &LocaleData, name_style: &str, in_format: bool) -> Vec<String> {
+    let full = match locale.format_month_names {
+        Some(names) if in_format => names,
+        _ => locale.month_names,
+    };
+    names_in_style(&full, &locale.month_abbreviations, name_style)
+}
+
+/// Returns the weekday names of a locale in a `DateTimeNameStyle`, starting from Sunday.
+pub fn weekday_names(locale: &LocaleData, name_style: &str) -> Vec<String> {
+    names_in_style(
+        &locale.weekday_names,
+        &locale.weekday_abbreviations,
+        name_style,
+    )
+}
+
+fn names_in_style(full: &[&str], abbreviations: &[&str], name_style: &str) -> Vec<String> {
+    match name_style {
+        "longAbbreviation" => abbreviations.iter().map(|n| n.to_string()).collect(),
+        "shortAbbreviation" => abbreviations.iter().map(|n| narrow_name(n)).collect(),
+        _ => full.iter().map(|n| n.to_string()).collect(),
+    }
+}
+
+/// Formats a date according to a `DateTimeFormatter` pattern.
+///
+/// Runs of a pattern letter stand for a field of the date, with the length of the run choosing
+/// between its forms. Text in single quotes is copied as is, with `''` standing for a quote.
+pub fn format_date_time(
+    pattern: &str,
+    date: &DateTime<FixedOffset>,
+    locale: &LocaleData,
+) -> String {
+    let mut out = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            if chars.next_if_eq(&'\'').is_some() {
+                out.push('\'');
+                continue;
+            }
+            while let Some(c) = chars.next() {
+                if c == '\'' && chars.next_if_eq(&'\'').is_none() {
+                    break;
+                }
+                out.push(c);
+            }
+            continue;
+        }
+
+        if !c.is_ascii_alphabetic() {
+            out.push(c);
+            continue;
+        }
+
+        let mut count = 1;
+        while chars.next_if_eq(&c).is_some() {
+            count += 1;
+        }
+        format_field(&mut out, c, count, date, locale);
+    }
+    out
+}
+
+fn format_field(
+    out: &mut String,
+    letter: char,
+    count: usize,
+    date: &DateTime<FixedOffset>,
+    locale: &LocaleData,
+) {
+    let number = |out: &mut String, value: i64| out.push_str(&format!("{value:0count$}"));
+    let month = date.month0() as usize;
+    let weekday = date.weekday().num_days_from_sunday() as usize;
+
+    match letter {
+        'G' => out.push_str(if date.year() > 0 { "AD" } else { "BC" }),
+        'y' if count == 2 => out.push_str(&format!("{:02}", date.year().rem_euclid(100))),
+        'y' => number(out, date.year().into()),
+        'M' | 'L' => match count {
+            1 | 2 => number(out, month as i64 + 1),
+            3 => out.push_str(locale.month_abbreviations[month]),
+            4 => out.push_str(&month_names(locale, "full", letter == 'M')[month]),
+            _ => out.push_str(&narrow_name(locale.month_abbreviations[month])),
+        },
+        'd' => number(out, date.day().into()),
+        'D' => number(out, date.ordinal().into()),
+        'E' => match count {
+            1..=3 => out.push_str(locale.weekday_abbreviations[weekday]),
+            4 => out.push_str(locale.weekday_names[weekday]),
+            _ => out.push_str(&narrow_name(locale.weekday_abbreviations[weekday])),
+        },
+        'Q' | 'q' => {
+            let quarter = month as i64 / 3 + 1;
+            if count <= 2 {
+                number(out, quarter);
+            } else {
+                out.push_str(&format!("Q{quarter}"));
+            }
+        }
+        'w' => number(out, date.iso_week().week().into()),
+        'W' => {
+            // Count the weeks of the month from the locale's first day of the week.
+            let first_of_month = (weekday as i64 - (date.day0() as i64 % 7)).rem_euclid(7);
+            let offset = (first_of_month - locale.first_weekday as i64).rem_euclid(7);
+            number(out, (date.day0() as i64 + offset) / 7 + 1);
+        }
+        'F' => number(out, date.day0() as i64 / 7 + 1),
+        'a' => out.push_str(locale.am_pm[(date.hour() >= 12) as usize]),
+        'h' => number(out, (date.hour() as i64 + 11) % 12 + 1),
+        'H' => number(out, date.hour().into()),
+        'K' => number(out, date.hour() as i64 % 12),
+        'k' => number(out, date.hour() as i64 + 1),
+        'm' => number(out, date.minute().into()),
+        's' => number(out, date.second().into()),
+        'S' => {
+            let millis = format!("{:03}", date.timestamp_subsec_millis().min(999));
+            out.extend(millis.chars().chain(std::iter::repeat('0')).take(count));
+        }
+        'z' | 'Z' | 'v' => {
+            let offset = date.offset().local_minus_utc();
+            let sign = if offset < 0 { '-' } else { '+' };
+            let (hours, minutes) = (offset.abs() / 3600, offset.abs() % 3600 / 60);
+            if letter == 'Z' {
+                out.push_str(&format!("{sign}{hours:02}{minutes:02}"));
+            } else {
+                out.push_str(&format!("GMT{sign}{hours:02}:{minutes:02}"));
+            }
+        }
+        _ => out.extend(std::iter::repeat_n(letter, count)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ui::US_ENGLISH;
+    use crate::globalization::resolve_locale;
+    use chrono::TimeZone;
+
+    fn format(locale: &str, pattern: &str) -> String {
+        let (locale, _) = resolve_locale(locale, &US_ENGLISH);
+        let date = FixedOffset::east_opt(20700)
+            .unwrap()
+            .with_ymd_and_hms(2001, 2, 3, 16, 5, 6)
+            .unwrap();
+        format_date_time(pattern, &date, locale)
+    }
+
+    #[test]
+    fn styles() {
+        let (en_us, _) = resolve_locale("en-US", &US_ENGLISH);
+        assert_eq!(
+            format("en-US", &date_time_pattern(en_us, "long", "long")),
+            "Saturday, February 3, 2001 4:05:06 PM"
+        );
+        assert_eq!(
+            format("en-US", &date_time_pattern(en_us, "short", "none")),
+            "2/3/2001"
+        );
+    }
+
+    #[test]
+    fn patterns() {
+        assert_eq!(format("en-US", "yy-MM-dd HH:mm"), "01-02-03 16:05");
+        assert_eq!(format("en-US", "'o''clock' h a"), "o'clock 4 PM");
+        assert_eq!(format("de-DE", "EEEE, d. MMMM"), "Samstag, 3. Februar");
+        assert_eq!(format("ru-RU", "d MMMM LLLL"), "3 февраля февраль");
+        assert_eq!(format("en-US", "z Z"), "GMT+05:45 +0545");
+    }
+}
@@ -0,0 +1,529 @@
+//! The conventions of the locales known to `flash.globalization`.
+//!
+//! Only these locales are covered: en-US, en-GB, de-DE, fr-FR, es-ES, it-IT, pt-BR, ru-RU,
+//! ja-JP, zh-CN and ko-KR. Their values were copied by hand from the CLDR data, and aren't
+//! generated from it. Any other locale falls back to one of the same language, or to en-US.
+
+/// The formatting conventions of a single locale.
+pub struct LocaleData {
+    /// The name of the locale, e.g. `en-US`.
+    pub name: &'static str,
+
+    pub decimal_separator: &'static str,
+    pub grouping_separator: &'static str,
+    pub grouping_pattern: &'static str,
+    pub negative_number_format: u32,
+
+    pub currency_iso_code: &'static str,
+    pub currency_symbol: &'static str,
+    pub currency_fractional_digits: i32,
+    pub positive_currency_format: u32,
+    pub negative_currency_format: u32,
+
+    pub month_names: [&'static str; 12],
+
+    /// Month names as used inside of a date, for languages that decline them.
+    pub format_month_names: Option<[&'static str; 12]>,
+
+    pub month_abbreviations: [&'static str; 12],
+    pub weekday_names: [&'static str; 7],
+    pub weekday_abbreviations: [&'static str; 7],
+    pub am_pm: [&'static str; 2],
+
+    /// The first day of the week, where Sunday is 0.
+    pub first_weekday: i32,
+
+    /// The long, medium and short date patterns.
+    pub date_patterns: [&'static str; 3],
+
+    /// The long, medium and short time patterns.
+    pub time_patterns: [&'static str; 3],
+}
+
+pub static LOCALES: &[LocaleData] = &[
+    LocaleData {
+        name: "en-US",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "USD",
+        currency_symbol: "$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        weekday_names: [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ],
+        weekday_abbreviations: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 0,
+        date_patterns: ["EEEE, MMMM d, yyyy", "MMM d, yyyy", "M/d/yyyy"],
+        time_patterns: ["h:mm:ss a", "h:mm:ss a", "h:mm a"],
+    },
+    LocaleData {
+        name: "en-GB",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "GBP",
+        currency_symbol: "£",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        weekday_names: [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ],
+        weekday_abbreviations: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+        am_pm: ["am", "pm"],
+        first_weekday: 1,
+        date_patterns: ["EEEE, d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "de-DE",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+        ],
+        weekday_names: [
+            "Sonntag",
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+        ],
+        weekday_abbreviations: ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"],
+        am_pm: ["vorm.", "nachm."],
+        first_weekday: 1,
+        date_patterns: ["EEEE, d. MMMM yyyy", "dd.MM.yyyy", "dd.MM.yy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "fr-FR",
+        decimal_separator: ",",
+        grouping_separator: "\u{A0}",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekday_names: [
+            "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+        ],
+        weekday_abbreviations: ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "es-ES",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+        ],
+        weekday_names: [
+            "domingo",
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+        ],
+        weekday_abbreviations: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+        am_pm: ["a. m.", "p. m."],
+        first_weekday: 1,
+        date_patterns: ["EEEE, d 'de' MMMM 'de' yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "it-IT",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "EUR",
+        currency_symbol: "€",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekday_names: [
+            "domenica",
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+        ],
+        weekday_abbreviations: ["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: ["EEEE d MMMM yyyy", "d MMM yyyy", "dd/MM/yyyy"],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "pt-BR",
+        decimal_separator: ",",
+        grouping_separator: ".",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "BRL",
+        currency_symbol: "R$",
+        currency_fractional_digits: 2,
+        positive_currency_format: 2,
+        negative_currency_format: 9,
+        month_names: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+        ],
+        weekday_names: [
+            "domingo",
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+        ],
+        weekday_abbreviations: ["dom", "seg", "ter", "qua", "qui", "sex", "sáb"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 0,
+        date_patterns: [
+            "EEEE, d 'de' MMMM 'de' yyyy",
+            "d 'de' MMM 'de' yyyy",
+            "dd/MM/yyyy",
+        ],
+        time_patterns: ["HH:mm:ss", "HH:mm:ss", "HH:mm"],
+    },
+    LocaleData {
+        name: "ru-RU",
+        decimal_separator: ",",
+        grouping_separator: "\u{A0}",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "RUB",
+        currency_symbol: "₽",
+        currency_fractional_digits: 2,
+        positive_currency_format: 3,
+        negative_currency_format: 8,
+        month_names: [
+            "январь",
+            "февраль",
+            "март",
+            "апрель",
+            "май",
+            "июнь",
+            "июль",
+            "август",
+            "сентябрь",
+            "октябрь",
+            "ноябрь",
+            "декабрь",
+        ],
+        format_month_names: Some([
+            "января",
+            "февраля",
+            "марта",
+            "апреля",
+            "мая",
+            "июня",
+            "июля",
+            "августа",
+            "сентября",
+            "октября",
+            "ноября",
+            "декабря",
+        ]),
+        month_abbreviations: [
+            "янв.",
+            "февр.",
+            "март",
+            "апр.",
+            "май",
+            "июнь",
+            "июль",
+            "авг.",
+            "сент.",
+            "окт.",
+            "нояб.",
+            "дек.",
+        ],
+        weekday_names: [
+            "воскресенье",
+            "понедельник",
+            "вторник",
+            "среда",
+            "четверг",
+            "пятница",
+            "суббота",
+        ],
+        weekday_abbreviations: ["вс", "пн", "вт", "ср", "чт", "пт", "сб"],
+        am_pm: ["AM", "PM"],
+        first_weekday: 1,
+        date_patterns: ["d MMMM yyyy 'г.'", "d MMM yyyy 'г.'", "dd.MM.yyyy"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "ja-JP",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "JPY",
+        currency_symbol: "￥",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        weekday_names: [
+            "日曜日",
+            "月曜日",
+            "火曜日",
+            "水曜日",
+            "木曜日",
+            "金曜日",
+            "土曜日",
+        ],
+        weekday_abbreviations: ["日", "月", "火", "水", "木", "金", "土"],
+        am_pm: ["午前", "午後"],
+        first_weekday: 0,
+        date_patterns: ["yyyy'年'M'月'd'日'", "yyyy/MM/dd", "yyyy/MM/dd"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "zh-CN",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "CNY",
+        currency_symbol: "¥",
+        currency_fractional_digits: 2,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "一月",
+            "二月",
+            "三月",
+            "四月",
+            "五月",
+            "六月",
+            "七月",
+            "八月",
+            "九月",
+            "十月",
+            "十一月",
+            "十二月",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        weekday_names: [
+            "星期日",
+            "星期一",
+            "星期二",
+            "星期三",
+            "星期四",
+            "星期五",
+            "星期六",
+        ],
+        weekday_abbreviations: ["周日", "周一", "周二", "周三", "周四", "周五", "周六"],
+        am_pm: ["上午", "下午"],
+        first_weekday: 1,
+        date_patterns: ["yyyy'年'M'月'd'日'", "yyyy-M-d", "yyyy/M/d"],
+        time_patterns: ["H:mm:ss", "H:mm:ss", "H:mm"],
+    },
+    LocaleData {
+        name: "ko-KR",
+        decimal_separator: ".",
+        grouping_separator: ",",
+        grouping_pattern: "3;*",
+        negative_number_format: 1,
+        currency_iso_code: "KRW",
+        currency_symbol: "₩",
+        currency_fractional_digits: 0,
+        positive_currency_format: 0,
+        negative_currency_format: 1,
+        month_names: [
+            "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+        ],
+        format_month_names: None,
+        month_abbreviations: [
+            "1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+        ],
+        weekday_names: [
+            "일요일",
+            "월요일",
+            "화요일",
+            "수요일",
+            "목요일",
+            "금요일",
+            "토요일",
+        ],
+        weekday_abbreviations: ["일", "월", "화", "수", "목", "금", "토"],
+        am_pm: ["오전", "오후"],
+        first_weekday: 0,
+        date_patterns: ["yyyy'년' M'월' d'일' EEEE", "yyyy. M. d.", "yy. M. d."],
+        time_patterns: ["a h:mm:ss", "a h:mm:ss", "a h:mm"],
+    },
+];
@@ -0,0 +1,398 @@
+//! Formatting and parsing of numbers and currency amounts.
+
+/// The settings of a `NumberFormatter` or `CurrencyFormatter` that decide how numbers are written.
+#[derive(Clone, Debug)]
+pub struct NumberFormat {
+    pub decimal_separator: String,
+    pub grouping_separator: String,
+    pub grouping_pattern: String,
+    pub use_grouping: bool,
+    pub fractional_digits: u32,
+    pub trailing_zeros: bool,
+    pub leading_zero: bool,
+    pub negative_symbol: String,
+    pub negative_number_format: u32,
+
+    /// The code point of the zero digit of the digits to write numbers with.
+    pub digits_type: u32,
+}
+
+impl NumberFormat {
+    /// Formats the absolute value of a number, also returning whether it rounded to zero.
+    fn format_magnitude(&self, value: f64) -> (String, bool) {
+        if value.is_infinite() {
+            return ("Infinity".to_string(), false);
+        }
+
+        let digits = self.fractional_digits.min(20) as usize;
+        let text = format!("{value:.digits$}");
+        let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+        let fraction = if self.trailing_zeros {
+            fraction
+        } else {
+            fraction.trim_end_matches('0')
+        };
+        let is_zero = integer.bytes().chain(fraction.bytes()).all(|b| b == b'0');
+
+        let mut out = String::new();
+        if integer != "0" || self.leading_zero || fraction.is_empty() {
+            out.push_str(&self.group(integer));
+        }
+        if !fraction.is_empty() {
+            out.push_str(&self.decimal_separator);
+            out.push_str(fraction);
+        }
+        (self.localize_digits(&out), is_zero)
+    }
+
+    /// Inserts grouping separators into the integer part of a number.
+    fn group(&self, integer: &str) -> String {
+        let Some((sizes, repeat)) = self
+            .use_grouping
+            .then(|| parse_grouping_pattern(&self.grouping_pattern))
+            .flatten()
+        else {
+            return integer.to_string();
+        };
+
+        // Groups are counted from the right.
+        let mut groups = Vec::new();
+        let mut end = integer.len();
+        while end > 0 {
+            let size = match sizes.get(groups.len()) {
+                Some(size) => *size,
+                None if repeat => sizes[sizes.len() - 1],
+                None => end,
+            };
+            let start = end.saturating_sub(size);
+            groups.push(&integer[start..end]);
+            end = start;
+        }
+        groups.reverse();
+        groups.join(&self.grouping_separator)
+    }
+
+    /// Replaces the European digits of `text` with the digits of `digits_type`.
+    fn localize_digits(&self, text: &str) -> String {
+        text.chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .and_then(|digit| char::from_u32(self.digits_type + digit))
+                    .unwrap_or(c)
+            })
+            .collect()
+    }
+
+    /// Returns the value of a digit, written either with European digits or the digits of `digits_type`.
+    fn digit_value(&self, c: char) -> Option<char> {
+        if c.is_ascii_digit() {
+            return Some(c);
+        }
+        let digit = (c as u32).checked_sub(self.digits_type)?;
+        (digit < 10).then(|| char::from_digit(digit, 10)).flatten()
+    }
+
+    /// Parses the digits of a number that starts at `start`, returning its value and where it ends.
+    fn parse_magnitude(&self, chars: &[char], start: usize) -> Option<(f64, usize)> {
+        let mut digits = String::new();
+        let mut has_digits = false;
+        let mut has_decimal = false;
+        let mut i = start;
+        loop {
+            if let Some(digit) = chars.get(i).and_then(|c| self.digit_value(*c)) {
+                digits.push(digit);
+                has_digits = true;
+                i += 1;
+                continue;
+            }
+
+            // Separators only count when a digit follows them.
+            let followed_by_digit = |index: usize| {
+                chars
+                    .get(index)
+                    .is_some_and(|c| self.digit_value(*c).is_some())
+            };
+            if !has_decimal {
+                if let Some(len) = match_at(chars, i, &self.decimal_separator) {
+                    if followed_by_digit(i + len) {
+                        digits.push('.');
+                        has_decimal = true;
+                        i += len;
+                        continue;
+                    }
+                }
+                if let Some(len) = match_at(chars, i, &self.grouping_separator) {
+                    if has_digits && followed_by_digit(i + len) {
+                        i += len;
+                        continue;
+                    }
+                }
+            }
+            break;
+        }
+
+        if !has_digits {
+            return None;
+        }
+        Some((digits.parse().ok()?, i))
+    }
+
+    /// Parses a number that starts at `start`, along with any negative sign or parentheses
+    /// around it, returning its value and where it ends.
+    fn parse_signed(&self, chars: &[char], start: usize) -> Option<(f64, usize)> {
+        let skip_whitespace = |mut i: usize| {
+            while chars.get(i).is_some_and(|c| c.is_whitespace()) {
+                i += 1;
+            }
+            i
+        };
+
+        if chars.get(start) == Some(&'(') {
+            let (value, end) = self.parse_magnitude(chars, skip_whitespace(start + 1))?;
+            let end = skip_whitespace(end);
+            return (chars.get(end) == Some(&')')).then_some((-value, end + 1));
+        }
+
+        if let Some(len) = match_at(chars, start, &self.negative_symbol) {
+            let (value, end) = self.parse_magnitude(chars, skip_whitespace(start + len))?;
+            return Some((-value, end));
+        }
+
+        let (value, end) = self.parse_magnitude(chars, start)?;
+        let after_space = if chars.get(end) == Some(&' ') {
+            end + 1
+        } else {
+            end
+        };
+        if let Some(len) = match_at(chars, after_space, &self.negative_symbol) {
+            return Some((-value, after_space + len));
+        }
+        Some((value, end))
+    }
+}
+
+/// Parses a grouping pattern such as `3;2;*` into its group sizes, from the right,
+/// and whether the last size repeats.
+fn parse_grouping_pattern(pattern: &str) -> Option<(Vec<usize>, bool)> {
+    let mut sizes = Vec::new();
+    let mut repeat = false;
+    for part in pattern.split(';') {
+        if repeat {
+            // Nothing can follow the '*'.
+            return None;
+        }
+        if part == "*" {
+            repeat = true;
+            continue;
+        }
+        match part.parse() {
+            Ok(size) if size > 0 => sizes.push(size),
+            _ => return None,
+        }
+    }
+    (!sizes.is_empty()).then_some((sizes, repeat))
+}
+
+/// Returns the number of characters of `text` if it appears at `index` of `chars`.
+fn match_at(chars: &[char], index: usize, text: &str) -> Option<usize> {
+    let len = text.chars().count();
+    let matches = len > 0
+        && chars
+            .get(index..index + len)
+            .is_some_and(|slice| slice.iter().copied().eq(text.chars()));
+    matches.then_some(len)
+}
+
+/// Returns the UTF-16 index of the character at `index`, as strings are indexed in ActionScript.
+fn utf16_index(chars: &[char], index: usize) -> usize {
+    chars[..index].iter().map(|c| c.len_utf16()).sum()
+}
+
+pub fn format_number(value: f64, format: &NumberFormat) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+
+    let (magnitude, is_zero) = format.format_magnitude(value.abs());
+    if value >= 0.0 || is_zero {
+        return magnitude;
+    }
+
+    let symbol = &format.negative_symbol;
+    match format.negative_number_format {
+        0 => format!("({magnitude})"),
+        2 => format!("{symbol} {magnitude}"),
+        3 => format!("{magnitude}{symbol}"),
+        4 => format!("{magnitude} {symbol}"),
+        _ => format!("{symbol}{magnitude}"),
+    }
+}
+
+/// Finds the first number in `text`, returning its value and the UTF-16 range it spans.
+///
+/// If `whole` is set, the number must make up all of `text` other than surrounding whitespace.
+pub fn parse_number(text: &str, format: &NumberFormat, whole: bool) -> Option<(f64, usize, usize)> {
+    let chars: Vec<char> = text.chars().collect();
+    let first = chars
+        .iter()
+        .position(|c| !c.is_whitespace())
+        .unwrap_or(chars.len());
+
+    let (start, (value, end)) = if whole {
+        (first, format.parse_signed(&chars, first)?)
+    } else {
+        (first..chars.len()).find_map(|start| Some((start, format.parse_signed(&chars, start)?)))?
+    };
+
+    if whole && chars[end..].iter().any(|c| !c.is_whitespace()) {
+        return None;
+    }
+    Some((value, utf16_index(&chars, start), utf16_index(&chars, end)))
+}
+
+pub fn format_currency(
+    value: f64,
+    format: &NumberFormat,
+    symbol: &str,
+    positive_format: u32,
+    negative_format: u32,
+) -> String {
+    if value.is_nan() {
+        return "NaN".to_string();
+    }
+
+    let (n, is_zero) = format.format_magnitude(value.abs());
+    let s = symbol;
+    if value >= 0.0 || is_zero {
+        return match positive_format {
+            0 => format!("{s}{n}"),
+            1 => format!("{n}{s}"),
+            2 => format!("{s} {n}"),
+            _ => format!("{n} {s}"),
+        };
+    }
+
+    let m = &format.negative_symbol;
+    match negative_format {
+        0 => format!("({s}{n})"),
+        1 => format!("{m}{s}{n}"),
+        2 => format!("{s}{m}{n}"),
+        3 => format!("{s}{n}{m}"),
+        4 => format!("({n}{s})"),
+        5 => format!("{m}{n}{s}"),
+        6 => format!("{n}{m}{s}"),
+        7 => format!("{n}{s}{m}"),
+        8 => format!("{m}{n} {s}"),
+        9 => format!("{m}{s} {n}"),
+        10 => format!("{n} {s}{m}"),
+        11 => format!("{s} {n}{m}"),
+        12 => format!("{s} {m}{n}"),
+        13 => format!("{n}{m} {s}"),
+        14 => format!("({s} {n})"),
+        _ => format!("({n} {s})"),
+    }
+}
+
+/// Parses a currency amount, returning its value and the currency string it was written with.
+///
+/// The currency string is the ISO code or the symbol, whichever appears in `text`,
+/// or empty if the amount has neither.
+pub fn parse_currency(
+    text: &str,
+    format: &NumberFormat,
+    iso_code: &str,
+    symbol: &str,
+) -> Option<(f64, String)> {
+    let currency = [iso_code, symbol]
+        .into_iter()
+        .find(|currency| !currency.is_empty() && text.contains(currency))
+        .unwrap_or_default();
+    let amount = if currency.is_empty() {
+        text.to_string()
+    } else {
+        text.replacen(currency, "", 1)
+    };
+
+    let (value, _, _) = parse_number(&amount, format, true)?;
+    Some((value, currency.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn en_us() -> NumberFormat {
+        NumberFormat {
+            decimal_separator: ".".to_string(),
+            grouping_separator: ",".to_string(),
+            grouping_pattern: "3;*".to_string(),
+            use_grouping: true,
+            fractional_digits: 2,
+            trailing_zeros: true,
+            leading_zero: true,
+            negative_symbol: "-".to_string(),
+            negative_number_format: 1,
+            digits_type: '0' as u32,
+        }
+    }
+
+    #[test]
+    fn format_grouping() {
+        let mut format = en_us();
+        assert_eq!(format_number(1234567.891, &format), "1,234,567.89");
+        format.grouping_pattern = "3;2;*".to_string();
+        assert_eq!(format_number(1234567.0, &format), "12,34,567.00");
+        format.grouping_pattern = "3".to_string();
+        assert_eq!(format_number(1234567.0, &format), "1234,567.00");
+        format.use_grouping = false;
+        assert_eq!(format_number(1234567.0, &format), "1234567.00");
+    }
+
+    #[test]
+    fn format_fraction() {
+        let mut format = en_us();
+        format.trailing_zeros = false;
+        format.leading_zero = false;
+        assert_eq!(format_number(0.5, &format), ".5");
+        assert_eq!(format_number(2.0, &format), "2");
+        assert_eq!(format_number(-0.001, &format), "0");
+    }
+
+    #[test]
+    fn format_negative() {
+        let mut format = en_us();
+        let formats = ["(1.50)", "-1.50", "- 1.50", "1.50-", "1.50 -"];
+        for (negative_number_format, expected) in formats.into_iter().enumerate() {
+            format.negative_number_format = negative_number_format as u32;
+            assert_eq!(format_number(-1.5, &format), expected);
+        }
+    }
+
+    #[test]
+    fn format_currencies() {
+        let format = en_us();
+        assert_eq!(format_currency(-1234.5, &format, "$", 0, 1), "-$1,234.50");
+        assert_eq!(format_currency(1234.5, &format, "€", 3, 8), "1,234.50 €");
+        assert_eq!(format_currency(-1234.5, &format, "€", 3, 8), "-1,234.50 €");
+    }
+
+    #[test]
+    fn parse() {
+        let format = en_us();
+        assert_eq!(
+            parse_number("  -1,234.5 apples", &format, false),
+            Some((-1234.5, 2, 10))
+        );
+        assert_eq!(parse_number("(12)", &format, true), Some((-12.0, 0, 4)));
+        assert_eq!(parse_number("12 -", &format, true), Some((-12.0, 0, 4)));
+        assert_eq!(parse_number("12 apples", &format, true), None);
+        assert_eq!(
+            parse_currency("-$1,234.50", &format, "USD", "$"),
+            Some((-1234.5, "$".to_string()))
+        );
+        assert_eq!(
+            parse_currency("USD 3", &format, "USD", "$"),
+            Some((3.0, "USD".to_string()))
+        );
+    }
+}
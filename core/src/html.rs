@@ -7,7 +7,8 @@ mod text_format;
 
 pub use dimensions::Position;
 pub use layout::{
-    lower_from_text_spans, Layout, LayoutBox, LayoutContent, LayoutLine, LayoutMetrics,
+    lower_from_text_spans, resolve_font, Layout, LayoutBox, LayoutContent, LayoutLine,
+    LayoutMetrics,
 };
 pub use stylesheet::{transform_dashes_to_camel_case, CssStream};
 pub use text_format::{FormatSpans, TextDisplay, TextFormat, TextSpan};
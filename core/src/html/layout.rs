@@ -13,7 +13,7 @@ use ruffle_render::shape_utils::DrawCommand;
 use std::cmp::{max, min, Ordering};
 use std::fmt::{Debug, Formatter};
 use std::mem;
-use std::ops::{Deref, Range};
+use std::ops::Range;
 use std::slice::Iter;
 use std::sync::Arc;
 use swf::{Point, Rectangle, Twips};
@@ -621,96 +621,14 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         context: &mut UpdateContext<'gc>,
         span: &TextSpan,
     ) -> Option<Font<'gc>> {
-        let font_name = span.font.face.to_utf8_lossy();
-
-        // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
-        // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
-        if self.font_type != FontType::Device {
-            if let Some(font) = context
-                .library
-                .get_embedded_font_by_name(
-                    &font_name,
-                    self.font_type,
-                    span.style.bold,
-                    span.style.italic,
-                    Some(self.movie.clone()),
-                )
-                .filter(|f| f.has_glyphs())
-            {
-                return Some(font);
-            }
-            // TODO: If set to use embedded fonts and we couldn't find any matching font, show nothing
-            // However - at time of writing, we don't support DefineFont4. If we matched this behaviour,
-            // then a bunch of SWFs would just show no text suddenly.
-            // return None;
-        }
-
-        // Check if the font name is one of the known default fonts.
-        if let Some(default_font) = match font_name.deref() {
-            "_serif" => Some(DefaultFont::Serif),
-            "_sans" => Some(DefaultFont::Sans),
-            "_typewriter" => Some(DefaultFont::Typewriter),
-            "_ゴシック" => Some(DefaultFont::JapaneseGothic),
-            "_等幅" => Some(DefaultFont::JapaneseGothicMono),
-            "_明朝" => Some(DefaultFont::JapaneseMincho),
-            _ => None,
-        } {
-            return context
-                .library
-                .default_font(
-                    default_font,
-                    span.style.bold,
-                    span.style.italic,
-                    context.ui,
-                    context.renderer,
-                    context.gc_context,
-                )
-                .first()
-                .copied();
-        }
-
-        if let Some(font) = context.library.get_or_load_device_font(
-            &font_name,
+        resolve_font(
+            context,
+            &self.movie,
+            self.font_type,
+            &span.font.face.to_utf8_lossy(),
             span.style.bold,
             span.style.italic,
-            context.ui,
-            context.renderer,
-            context.gc_context,
-        ) {
-            return Some(font);
-        }
-
-        // TODO: handle multiple fonts for a definition, each covering different sets of glyphs
-
-        // At this point, the font name was neither one of the default
-        // fonts nor matched any device font. We explicitly handle some of the
-        // well-known aliases for the default fonts for better compatibility
-        // with devices that don't have those fonts installed. As a last resort
-        // we fall back to using sans (like Flash).
-        let default_font = match font_name.deref() {
-            "Times New Roman" => DefaultFont::Serif,
-            "Arial" => DefaultFont::Sans,
-            "Courier New" => DefaultFont::Typewriter,
-            _ => {
-                if font_name.contains("Ming") || font_name.contains('明') {
-                    DefaultFont::JapaneseMincho
-                } else {
-                    DefaultFont::Sans
-                }
-            }
-        };
-        context
-            .library
-            .default_font(
-                default_font,
-                span.style.bold,
-                span.style.italic,
-                context.ui,
-                context.renderer,
-                context.gc_context,
-            )
-            .first()
-            .copied()
+        )
     }
 
     /// Append text to the current line of the ongoing layout operation.
@@ -1477,3 +1395,99 @@ impl<'layout, 'gc> Iterator for LayoutBoxIter<'layout, 'gc> {
         }
     }
 }
+
+/// Find the font used to render text with the given font name and style.
+///
+/// Embedded fonts are preferred unless `font_type` asks for device fonts,
+/// falling back to the default fonts like Flash does.
+pub fn resolve_font<'gc>(
+    context: &mut UpdateContext<'gc>,
+    movie: &Arc<SwfMovie>,
+    font_type: FontType,
+    font_name: &str,
+    bold: bool,
+    italic: bool,
+) -> Option<Font<'gc>> {
+    // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
+    // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
+    if font_type != FontType::Device {
+        if let Some(font) = context
+            .library
+            .get_embedded_font_by_name(font_name, font_type, bold, italic, Some(movie.clone()))
+            .filter(|f| f.has_glyphs())
+        {
+            return Some(font);
+        }
+        // TODO: If set to use embedded fonts and we couldn't find any matching font, show nothing
+        // However - at time of writing, we don't support DefineFont4. If we matched this behaviour,
+        // then a bunch of SWFs would just show no text suddenly.
+        // return None;
+    }
+
+    // Check if the font name is one of the known default fonts.
+    if let Some(default_font) = match font_name {
+        "_serif" => Some(DefaultFont::Serif),
+        "_sans" => Some(DefaultFont::Sans),
+        "_typewriter" => Some(DefaultFont::Typewriter),
+        "_ゴシック" => Some(DefaultFont::JapaneseGothic),
+        "_等幅" => Some(DefaultFont::JapaneseGothicMono),
+        "_明朝" => Some(DefaultFont::JapaneseMincho),
+        _ => None,
+    } {
+        return context
+            .library
+            .default_font(
+                default_font,
+                bold,
+                italic,
+                context.ui,
+                context.renderer,
+                context.gc_context,
+            )
+            .first()
+            .copied();
+    }
+
+    if let Some(font) = context.library.get_or_load_device_font(
+        font_name,
+        bold,
+        italic,
+        context.ui,
+        context.renderer,
+        context.gc_context,
+    ) {
+        return Some(font);
+    }
+
+    // TODO: handle multiple fonts for a definition, each covering different sets of glyphs
+
+    // At this point, the font name was neither one of the default
+    // fonts nor matched any device font. We explicitly handle some of the
+    // well-known aliases for the default fonts for better compatibility
+    // with devices that don't have those fonts installed. As a last resort
+    // we fall back to using sans (like Flash).
+    let default_font = match font_name {
+        "Times New Roman" => DefaultFont::Serif,
+        "Arial" => DefaultFont::Sans,
+        "Courier New" => DefaultFont::Typewriter,
+        _ => {
+            if font_name.contains("Ming") || font_name.contains('明') {
+                DefaultFont::JapaneseMincho
+            } else {
+                DefaultFont::Sans
+            }
+        }
+    };
+    context
+        .library
+        .default_font(
+            default_font,
+            bold,
+            italic,
+            context.ui,
+            context.renderer,
+            context.gc_context,
+        )
+        .first()
+        .copied()
+}
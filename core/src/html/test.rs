@@ -868,3 +868,97 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn formatspans_replace_with_formatted() {
+    let tf1 = TextFormat {
+        font: Some(WString::from_utf8("same!")),
+        ..Default::default()
+    };
+
+    let tf2 = TextFormat {
+        font: Some(WString::from_utf8("difference!")),
+        ..Default::default()
+    };
+
+    let mut fs = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"abcdefghi"),
+        &[
+            TextSpan::with_length_and_format(5, &tf1),
+            TextSpan::with_length_and_format(4, &tf2),
+        ],
+    );
+
+    let with = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"123"),
+        &[
+            TextSpan::with_length_and_format(2, &tf2),
+            TextSpan::with_length_and_format(1, &tf1),
+        ],
+    );
+
+    fs.replace_with_formatted(1, 3, &with);
+
+    assert_eq!(WStr::from_units(b"a123defghi"), fs.text());
+
+    assert_eq!(tf1.font, fs.get_text_format(0, 1).font);
+    assert_eq!(tf2.font, fs.get_text_format(1, 3).font);
+    assert_eq!(tf1.font, fs.get_text_format(3, 6).font);
+    assert_eq!(tf2.font, fs.get_text_format(6, 10).font);
+}
+
+#[test]
+fn formatspans_slice() {
+    let tf1 = TextFormat {
+        font: Some(WString::from_utf8("same!")),
+        ..Default::default()
+    };
+
+    let tf2 = TextFormat {
+        font: Some(WString::from_utf8("difference!")),
+        ..Default::default()
+    };
+
+    let fs = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"abcdefghi"),
+        &[
+            TextSpan::with_length_and_format(5, &tf1),
+            TextSpan::with_length_and_format(4, &tf2),
+        ],
+    );
+
+    let slice = fs.slice(3, 7);
+
+    assert_eq!(WStr::from_units(b"defg"), slice.text());
+
+    assert_eq!(tf1.font, slice.get_text_format(0, 2).font);
+    assert_eq!(tf2.font, slice.get_text_format(2, 4).font);
+}
+
+#[test]
+fn formatspans_slice_whole_spans() {
+    let tf1 = TextFormat {
+        bold: Some(true),
+        ..Default::default()
+    };
+
+    let tf2 = TextFormat {
+        bold: Some(false),
+        ..Default::default()
+    };
+
+    let fs = FormatSpans::from_str_and_spans(
+        WStr::from_units(b"abcdefghi"),
+        &[
+            TextSpan::with_length_and_format(4, &tf1),
+            TextSpan::with_length_and_format(5, &tf2),
+        ],
+    );
+
+    let slice = fs.slice(0, 9);
+
+    assert_eq!(WStr::from_units(b"abcdefghi"), slice.text());
+
+    assert_eq!(tf1.bold, slice.get_text_format(0, 4).bold);
+    assert_eq!(tf2.bold, slice.get_text_format(4, 9).bold);
+}
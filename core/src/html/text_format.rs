@@ -1347,6 +1347,37 @@ impl FormatSpans {
         self.normalize();
     }
 
+    /// Replace the text in the range [from, to) with the contents of `with`,
+    /// keeping the formatting of `with` instead of adjusting it to match.
+    pub fn replace_with_formatted(&mut self, from: usize, to: usize, with: &FormatSpans) {
+        if to < from {
+            return;
+        }
+
+        let from = from.min(self.text.len());
+        self.replace_text(from, to, &with.text);
+
+        let mut pos = from;
+        for span in &with.spans {
+            let end = pos + span.span_length;
+            self.set_text_format(pos, end, &span.get_text_format());
+            pos = end;
+        }
+    }
+
+    /// Copy the text in the range [from, to), along with its formatting.
+    pub fn slice(&self, from: usize, to: usize) -> Self {
+        let mut slice = self.clone();
+        // Replacing an empty range would reset the format of the span after it.
+        if to < slice.text.len() {
+            slice.replace_text(to, slice.text.len(), WStr::empty());
+        }
+        if from > 0 {
+            slice.replace_text(0, from, WStr::empty());
+        }
+        slice
+    }
+
     /// Iterate over all text spans in the current list of format spans.
     ///
     /// The iterator returned by this function yields a tuple for each span,
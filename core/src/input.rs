@@ -1,6 +1,8 @@
-use crate::events::{GamepadButton, KeyCode, MouseButton, PlayerEvent, TextControlCode};
+use crate::events::{
+    GamepadAxis, GamepadButton, GamepadId, KeyCode, MouseButton, PlayerEvent, TextControlCode,
+};
 use chrono::{DateTime, TimeDelta, Utc};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 struct ClickEventData {
     x: f64,
@@ -17,6 +19,74 @@ impl ClickEventData {
     }
 }
 
+/// A control of a gamepad, as exposed to movies through `GameInputControl`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum GamepadControl {
+    Axis(GamepadAxis),
+    Button(GamepadButton),
+}
+
+impl GamepadControl {
+    /// The controls of every gamepad, in the order `GameInputDevice` lists them.
+    pub const ALL: [GamepadControl; 18] = [
+        GamepadControl::Axis(GamepadAxis::LeftStickX),
+        GamepadControl::Axis(GamepadAxis::LeftStickY),
+        GamepadControl::Axis(GamepadAxis::RightStickX),
+        GamepadControl::Axis(GamepadAxis::RightStickY),
+        GamepadControl::Button(GamepadButton::South),
+        GamepadControl::Button(GamepadButton::East),
+        GamepadControl::Button(GamepadButton::North),
+        GamepadControl::Button(GamepadButton::West),
+        GamepadControl::Button(GamepadButton::LeftTrigger),
+        GamepadControl::Button(GamepadButton::LeftTrigger2),
+        GamepadControl::Button(GamepadButton::RightTrigger),
+        GamepadControl::Button(GamepadButton::RightTrigger2),
+        GamepadControl::Button(GamepadButton::Select),
+        GamepadControl::Button(GamepadButton::Start),
+        GamepadControl::Button(GamepadButton::DPadUp),
+        GamepadControl::Button(GamepadButton::DPadDown),
+        GamepadControl::Button(GamepadButton::DPadLeft),
+        GamepadControl::Button(GamepadButton::DPadRight),
+    ];
+
+    /// The number of axes at the start of `ALL`.
+    const AXIS_COUNT: usize = 4;
+
+    pub fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|control| *control == self)
+            .unwrap_or_default()
+    }
+
+    /// The identifier of the control, such as `AXIS_0` or `BUTTON_4`.
+    pub fn id(self) -> String {
+        let index = self.index();
+        match self {
+            GamepadControl::Axis(_) => format!("AXIS_{index}"),
+            GamepadControl::Button(_) => format!("BUTTON_{}", index - Self::AXIS_COUNT),
+        }
+    }
+
+    pub fn min_value(self) -> f64 {
+        match self {
+            GamepadControl::Axis(_) => -1.0,
+            GamepadControl::Button(_) => 0.0,
+        }
+    }
+
+    pub fn max_value(self) -> f64 {
+        1.0
+    }
+}
+
+/// The state of the controls of a connected gamepad.
+#[derive(Default)]
+struct GamepadState {
+    axes: HashMap<GamepadAxis, f64>,
+    buttons_down: HashSet<GamepadButton>,
+}
+
 pub struct InputManager {
     keys_down: HashSet<KeyCode>,
     keys_toggled: HashSet<KeyCode>,
@@ -27,6 +97,9 @@ pub struct InputManager {
 
     /// A map from gamepad buttons to key codes.
     gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
+
+    /// The state of each connected gamepad.
+    gamepads: BTreeMap<GamepadId, GamepadState>,
 }
 
 impl InputManager {
@@ -39,6 +112,7 @@ impl InputManager {
             last_text_control: None,
             last_click: None,
             gamepad_button_mapping,
+            gamepads: BTreeMap::new(),
         }
     }
 
@@ -70,7 +144,7 @@ impl InputManager {
     pub fn map_input_event(&mut self, event: PlayerEvent) -> Option<PlayerEvent> {
         // Optionally transform gamepad button events into key events.
         match event {
-            PlayerEvent::GamepadButtonDown { button } => {
+            PlayerEvent::GamepadButtonDown { button, .. } => {
                 if let Some(key_code) = self.gamepad_button_mapping.get(&button) {
                     Some(PlayerEvent::KeyDown {
                         key_code: *key_code,
@@ -81,7 +155,7 @@ impl InputManager {
                     None
                 }
             }
-            PlayerEvent::GamepadButtonUp { button } => {
+            PlayerEvent::GamepadButtonUp { button, .. } => {
                 if let Some(key_code) = self.gamepad_button_mapping.get(&button) {
                     Some(PlayerEvent::KeyUp {
                         key_code: *key_code,
@@ -126,6 +200,71 @@ impl InputManager {
         }
     }
 
+    /// Updates the state of the gamepads, returning whether anything changed.
+    pub fn handle_gamepad_event(&mut self, event: &PlayerEvent) -> bool {
+        match *event {
+            PlayerEvent::GamepadConnected { gamepad } => {
+                if self.gamepads.contains_key(&gamepad) {
+                    return false;
+                }
+                self.gamepads.insert(gamepad, GamepadState::default());
+                true
+            }
+            PlayerEvent::GamepadDisconnected { gamepad } => {
+                self.gamepads.remove(&gamepad).is_some()
+            }
+            PlayerEvent::GamepadAxisChanged {
+                gamepad,
+                axis,
+                value,
+            } => {
+                // Input from gamepads that aren't connected is ignored, so that it doesn't
+                // bring back a disconnected gamepad without announcing it.
+                let Some(state) = self.gamepads.get_mut(&gamepad) else {
+                    return false;
+                };
+                let value = value.clamp(-1.0, 1.0);
+                state.axes.insert(axis, value) != Some(value)
+            }
+            PlayerEvent::GamepadButtonDown { gamepad, button } => self
+                .gamepads
+                .get_mut(&gamepad)
+                .is_some_and(|state| state.buttons_down.insert(button)),
+            PlayerEvent::GamepadButtonUp { gamepad, button } => self
+                .gamepads
+                .get_mut(&gamepad)
+                .is_some_and(|state| state.buttons_down.remove(&button)),
+            _ => false,
+        }
+    }
+
+    /// The connected gamepads, ordered by their identifiers.
+    pub fn gamepads(&self) -> impl Iterator<Item = GamepadId> + '_ {
+        self.gamepads.keys().copied()
+    }
+
+    pub fn is_gamepad_connected(&self, gamepad: GamepadId) -> bool {
+        self.gamepads.contains_key(&gamepad)
+    }
+
+    /// The current value of a gamepad control, or 0 if the gamepad isn't connected.
+    pub fn gamepad_control_value(&self, gamepad: GamepadId, control: GamepadControl) -> f64 {
+        let Some(state) = self.gamepads.get(&gamepad) else {
+            return 0.0;
+        };
+
+        match control {
+            GamepadControl::Axis(axis) => state.axes.get(&axis).copied().unwrap_or_default(),
+            GamepadControl::Button(button) => {
+                if state.buttons_down.contains(&button) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
     fn update_last_click(&mut self, x: f64, y: f64, index: Option<usize>) {
         let time = Utc::now();
         let index = index.unwrap_or_else(|| {
@@ -27,12 +27,14 @@ pub mod buffer;
 mod character;
 pub mod context;
 pub mod context_menu;
+pub mod datagram_socket;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
 pub mod focus_tracker;
 mod font;
 mod frame_lifecycle;
+mod globalization;
 mod html;
 mod input;
 mod library;
@@ -44,11 +46,14 @@ mod net_connection;
 pub mod pixel_bender;
 mod player;
 mod prelude;
+mod printing;
 pub mod sandbox;
 pub mod socket;
 mod streams;
 pub mod string;
 pub mod tag_utils;
+mod text_engine;
+mod text_snapshot;
 pub mod timer;
 mod types;
 mod vminterface;
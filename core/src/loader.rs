@@ -291,6 +291,30 @@ impl<'gc> LoadManager<'gc> {
         self.0.remove(handle);
     }
 
+    /// Cancel the loads of a `Loader`'s content, identified by its `LoaderInfo`.
+    ///
+    /// In-flight fetches of the removed loaders end with `Error::Cancelled`.
+    pub fn cancel_movie_loads(&mut self, loader_info: Avm2Object<'gc>) {
+        self.0.retain(|_, loader| match loader {
+            Loader::Movie {
+                vm_data:
+                    MovieLoaderVMData::Avm2 {
+                        loader_info: other, ..
+                    },
+                ..
+            } => !Avm2Object::ptr_eq(*other, loader_info),
+            _ => true,
+        });
+    }
+
+    /// Cancel the downloads filling the buffer of a `NetStream`.
+    pub fn cancel_stream_loads(&mut self, stream: NetStream<'gc>) {
+        self.0.retain(|_, loader| match loader {
+            Loader::NetStream { target_stream, .. } => *target_stream != stream,
+            _ => true,
+        });
+    }
+
     /// Retrieve a loader by handle.
     pub fn get_loader(&self, handle: LoaderHandle) -> Option<&Loader<'gc>> {
         self.0.get(handle)
@@ -2589,8 +2613,8 @@ impl<'gc> Loader<'gc> {
             }
         }
 
-        if let Loader::Movie { loader_status, .. } = uc.load_manager.get_loader_mut(handle).unwrap()
-        {
+        // The load may have been cancelled by the events above.
+        if let Some(Loader::Movie { loader_status, .. }) = uc.load_manager.get_loader_mut(handle) {
             *loader_status = LoaderStatus::Succeeded;
         };
 
@@ -2686,8 +2710,8 @@ impl<'gc> Loader<'gc> {
             }
         }
 
-        if let Loader::Movie { loader_status, .. } = uc.load_manager.get_loader_mut(handle).unwrap()
-        {
+        // The load may have been cancelled by the events above.
+        if let Some(Loader::Movie { loader_status, .. }) = uc.load_manager.get_loader_mut(handle) {
             *loader_status = LoaderStatus::Failed;
         };
 
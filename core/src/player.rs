@@ -5,14 +5,17 @@ use crate::avm1::SystemProperties;
 use crate::avm1::VariableDumper;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{TObject, Value};
+use crate::avm2::game_input;
+use crate::avm2::media_capture::update_capture_devices;
 use crate::avm2::{Activation as Avm2Activation, Avm2, CallStack, Object as Avm2Object};
 use crate::backend::ui::FontDefinition;
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
     log::LogBackend,
+    media_capture::MediaCaptureBackend,
     navigator::{NavigatorBackend, Request},
     storage::StorageBackend,
-    ui::{MouseCursor, UiBackend},
+    ui::{CustomCursorId, CustomMouseCursor, MouseCursor, UiBackend},
 };
 use crate::compatibility_rules::CompatibilityRules;
 use crate::config::Letterbox;
@@ -20,6 +23,7 @@ use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{
     BuiltInItemFlags, ContextMenuCallback, ContextMenuItem, ContextMenuState,
 };
+use crate::datagram_socket::DatagramSockets;
 use crate::display_object::Avm2MousePick;
 use crate::display_object::{
     EditText, InteractiveObject, Stage, StageAlign, StageDisplayState, StageScaleMode,
@@ -39,6 +43,7 @@ use crate::local_connection::LocalConnections;
 use crate::locale::get_current_date_time;
 use crate::net_connection::NetConnections;
 use crate::prelude::*;
+use crate::printing::PrintJob;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::StringContext;
@@ -110,6 +115,18 @@ pub struct MouseData<'gc> {
     pub pressed: Option<InteractiveObject<'gc>>,
     pub right_pressed: Option<InteractiveObject<'gc>>,
     pub middle_pressed: Option<InteractiveObject<'gc>>,
+
+    /// The cursor chosen with `Mouse.cursor`, which replaces the one picked for the hovered
+    /// object. `None` stands for `MouseCursor.AUTO`.
+    #[collect(require_static)]
+    pub cursor: Option<MouseCursor>,
+
+    /// The cursors registered with `Mouse.registerCursor`, by name.
+    #[collect(require_static)]
+    pub custom_cursors: HashMap<String, CustomCursorId>,
+
+    #[collect(require_static)]
+    next_custom_cursor_id: u32,
 }
 
 impl<'gc> MouseData<'gc> {
@@ -130,6 +147,35 @@ impl<'gc> MouseData<'gc> {
             MouseButton::Middle => self.middle_pressed = value,
         }
     }
+
+    /// Registers a cursor under a name, replacing any cursor registered under the same name.
+    pub fn register_cursor(
+        &mut self,
+        ui: &mut dyn UiBackend,
+        name: String,
+        cursor: CustomMouseCursor,
+    ) {
+        let id = CustomCursorId(self.next_custom_cursor_id);
+        self.next_custom_cursor_id += 1;
+        ui.register_mouse_cursor(id, cursor);
+
+        if let Some(old_id) = self.custom_cursors.insert(name, id) {
+            if self.cursor == Some(MouseCursor::Custom(old_id)) {
+                self.cursor = Some(MouseCursor::Custom(id));
+            }
+            ui.unregister_mouse_cursor(old_id);
+        }
+    }
+
+    /// Forgets a cursor registered under a name, going back to automatic cursors if it's in use.
+    pub fn unregister_cursor(&mut self, ui: &mut dyn UiBackend, name: &str) {
+        if let Some(id) = self.custom_cursors.remove(name) {
+            if self.cursor == Some(MouseCursor::Custom(id)) {
+                self.cursor = None;
+            }
+            ui.unregister_mouse_cursor(id);
+        }
+    }
 }
 
 #[derive(Collect)]
@@ -184,6 +230,8 @@ struct GcRootData<'gc> {
 
     sockets: Sockets<'gc>,
 
+    datagram_sockets: DatagramSockets<'gc>,
+
     /// List of active NetConnection objects.
     net_connections: NetConnections<'gc>,
 
@@ -228,6 +276,7 @@ impl<'gc> GcRootData<'gc> {
         &mut AudioManager<'gc>,
         &mut StreamManager<'gc>,
         &mut Sockets<'gc>,
+        &mut DatagramSockets<'gc>,
         &mut NetConnections<'gc>,
         &mut LocalConnections<'gc>,
         &mut Vec<PostFrameCallback<'gc>>,
@@ -252,6 +301,7 @@ impl<'gc> GcRootData<'gc> {
             &mut self.audio_manager,
             &mut self.stream_manager,
             &mut self.sockets,
+            &mut self.datagram_sockets,
             &mut self.net_connections,
             &mut self.local_connections,
             &mut self.post_frame_callbacks,
@@ -268,6 +318,7 @@ type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
 type Log = Box<dyn LogBackend>;
+type MediaCapture = Box<dyn MediaCaptureBackend>;
 type Ui = Box<dyn UiBackend>;
 type Video = Box<dyn VideoBackend>;
 
@@ -308,6 +359,7 @@ pub struct Player {
     log: Log,
     ui: Ui,
     video: Video,
+    media_capture: MediaCapture,
 
     transform_stack: TransformStack,
 
@@ -315,6 +367,9 @@ pub struct Player {
 
     gc_arena: Rc<RefCell<GcArena>>,
 
+    /// The print job that was started and hasn't been sent yet.
+    print_job: Option<PrintJob>,
+
     frame_rate: f64,
     forced_frame_rate: bool,
     actions_since_timeout_check: u16,
@@ -342,6 +397,9 @@ pub struct Player {
     mouse_cursor: MouseCursor,
     mouse_cursor_needs_check: bool,
 
+    /// The cursor shown by the UI backend, which may have been chosen with `Mouse.cursor`.
+    displayed_mouse_cursor: MouseCursor,
+
     system: SystemProperties,
 
     page_url: Option<String>,
@@ -560,11 +618,16 @@ impl Player {
             });
 
             self.update_sockets();
+            self.update_datagram_sockets();
             self.update_net_connections();
             self.update_timers(dt);
             self.update(|context| {
                 StreamManager::tick(context, dt);
             });
+            self.update(|context| {
+                update_capture_devices(context, dt);
+            });
+            self.update_mouse_cursor();
             self.audio.tick();
         }
     }
@@ -1006,13 +1069,31 @@ impl Player {
             | PlayerEvent::MouseDown { .. }
             | PlayerEvent::MouseLeave
             | PlayerEvent::MouseWheel { .. }
-            | PlayerEvent::GamepadButtonDown { .. }
-            | PlayerEvent::GamepadButtonUp { .. }
             | PlayerEvent::TextInput { .. }
             | PlayerEvent::TextControl { .. } => self.handle_input_event(event),
+            PlayerEvent::GamepadButtonDown { .. } | PlayerEvent::GamepadButtonUp { .. } => {
+                // Buttons are seen both by `GameInput` and as mapped key presses.
+                let handled = self.handle_gamepad_event(event);
+                self.handle_input_event(event) || handled
+            }
+            PlayerEvent::GamepadConnected { .. }
+            | PlayerEvent::GamepadDisconnected { .. }
+            | PlayerEvent::GamepadAxisChanged { .. } => self.handle_gamepad_event(event),
         }
     }
 
+    /// Updates the state of the gamepads, and lets `GameInput` know about the change.
+    fn handle_gamepad_event(&mut self, event: PlayerEvent) -> bool {
+        if !self.input.handle_gamepad_event(&event) {
+            return false;
+        }
+
+        self.mutate_with_update_context(|context| {
+            game_input::dispatch_gamepad_event(context, event);
+        });
+        true
+    }
+
     fn handle_focus_event(&mut self, event: PlayerEvent) -> bool {
         if let PlayerEvent::FocusLost = event {
             self.mutate_with_update_context(|context| {
@@ -1480,6 +1561,18 @@ impl Player {
         }
     }
 
+    /// Updates the mouse cursor if it has changed, preferring the one chosen with `Mouse.cursor`
+    /// over the one picked for the hovered object.
+    fn update_mouse_cursor(&mut self) {
+        let cursor = self
+            .enter_arena(|_, root, _| root.mouse_data.cursor)
+            .unwrap_or(self.mouse_cursor);
+        if cursor != self.displayed_mouse_cursor {
+            self.displayed_mouse_cursor = cursor;
+            self.ui.set_mouse_cursor(cursor);
+        }
+    }
+
     /// Updates the hover state of buttons.
     fn update_mouse_state(
         &mut self,
@@ -1791,12 +1884,9 @@ impl Player {
             needs_render
         });
 
-        // Update mouse cursor if it has changed.
-        if new_cursor != self.mouse_cursor {
-            self.mouse_cursor = new_cursor;
-            self.ui.set_mouse_cursor(new_cursor)
-        }
+        self.mouse_cursor = new_cursor;
         self.mouse_cursor_needs_check = mouse_cursor_needs_check;
+        self.update_mouse_cursor();
 
         needs_render
     }
@@ -2063,6 +2153,10 @@ impl Player {
         &mut self.ui
     }
 
+    pub fn media_capture_mut(&mut self) -> &mut MediaCapture {
+        &mut self.media_capture
+    }
+
     pub fn run_actions(context: &mut UpdateContext<'_>) {
         // Note that actions can queue further actions, so a while loop is necessary here.
         while let Some(action) = context.action_queue.pop_action() {
@@ -2184,6 +2278,7 @@ impl Player {
                 audio_manager,
                 stream_manager,
                 sockets,
+                datagram_sockets,
                 net_connections,
                 local_connections,
                 post_frame_callbacks,
@@ -2200,6 +2295,7 @@ impl Player {
                 audio: this.audio.deref_mut(),
                 navigator: this.navigator.deref_mut(),
                 ui: this.ui.deref_mut(),
+                media_capture: this.media_capture.deref_mut(),
                 action_queue,
                 gc_context,
                 strings: StringContext::from_parts(gc_context, interner),
@@ -2239,8 +2335,10 @@ impl Player {
                 stub_tracker: &mut this.stub_tracker,
                 stream_manager,
                 sockets,
+                datagram_sockets,
                 net_connections,
                 local_connections,
+                print_job: &mut this.print_job,
                 dynamic_root,
                 post_frame_callbacks,
             };
@@ -2356,6 +2454,13 @@ impl Player {
         })
     }
 
+    /// Update bound DatagramSockets.
+    pub fn update_datagram_sockets(&mut self) {
+        self.mutate_with_update_context(|context| {
+            DatagramSockets::update_sockets(context);
+        })
+    }
+
     /// Update connected NetConnections.
     pub fn update_net_connections(&mut self) {
         self.mutate_with_update_context(|context| {
@@ -2448,6 +2553,7 @@ pub struct PlayerBuilder {
     storage: Option<Storage>,
     ui: Option<Ui>,
     video: Option<Video>,
+    media_capture: Option<MediaCapture>,
 
     // Misc. player configuration
     autoplay: bool,
@@ -2495,6 +2601,7 @@ impl PlayerBuilder {
             storage: None,
             ui: None,
             video: None,
+            media_capture: None,
 
             autoplay: false,
             align: StageAlign::default(),
@@ -2600,6 +2707,13 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the media capture backend of the player.
+    #[inline]
+    pub fn with_media_capture(mut self, media_capture: impl 'static + MediaCaptureBackend) -> Self {
+        self.media_capture = Some(Box::new(media_capture));
+        self
+    }
+
     /// Sets the stage scale mode and optionally prevents movies from changing it.
     #[inline]
     pub fn with_align(mut self, align: StageAlign, force: bool) -> Self {
@@ -2775,6 +2889,9 @@ impl PlayerBuilder {
                 pressed: None,
                 right_pressed: None,
                 middle_pressed: None,
+                cursor: None,
+                custom_cursors: HashMap::new(),
+                next_custom_cursor_id: 0,
             },
             avm1_shared_objects: HashMap::new(),
             avm2_shared_objects: HashMap::new(),
@@ -2783,6 +2900,7 @@ impl PlayerBuilder {
             unbound_text_fields: Vec::new(),
             stream_manager: StreamManager::new(),
             sockets: Sockets::empty(),
+            datagram_sockets: DatagramSockets::empty(),
             net_connections: NetConnections::default(),
             local_connections: LocalConnections::empty(),
             dynamic_root: DynamicRootSet::new(gc_context),
@@ -2824,6 +2942,9 @@ impl PlayerBuilder {
         let video = self
             .video
             .unwrap_or_else(|| Box::new(null::NullVideoBackend::new()));
+        let media_capture = self
+            .media_capture
+            .unwrap_or_else(|| Box::new(media_capture::NullMediaCaptureBackend::new()));
 
         let player_version = self.player_version.unwrap_or(NEWEST_PLAYER_VERSION);
 
@@ -2841,6 +2962,7 @@ impl PlayerBuilder {
                 storage,
                 ui,
                 video,
+                media_capture,
 
                 // SWF info
                 swf: fake_movie.clone(),
@@ -2864,6 +2986,7 @@ impl PlayerBuilder {
                 mouse_position: Point::ZERO,
                 mouse_cursor: MouseCursor::Arrow,
                 mouse_cursor_needs_check: false,
+                displayed_mouse_cursor: MouseCursor::Arrow,
 
                 // Misc. state
                 rng: SmallRng::seed_from_u64(get_current_date_time().timestamp_millis() as u64),
@@ -2887,6 +3010,8 @@ impl PlayerBuilder {
                 #[cfg(feature = "egui")]
                 debug_ui: Default::default(),
 
+                print_job: None,
+
                 // GC data
                 gc_arena: Rc::new(RefCell::new(GcArena::new(|gc_context| {
                     Self::create_gc_root(
@@ -0,0 +1,133 @@
+//! Printing of display objects, as done by `PrintJob` and the AVM1 `print` functions.
+//!
+//! Pages are rendered offscreen into images, which are then handed to the UI backend.
+
+use crate::backend::ui::PrintedPage;
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::{DisplayObject, TDisplayObject};
+use ruffle_render::bitmap::PixelRegion;
+use ruffle_render::commands::{CommandHandler, CommandList};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::transform::{Transform, TransformStack};
+use swf::{Color, Rectangle, Twips};
+
+/// The size of the paper, in points. We always print on US Letter paper in portrait orientation.
+pub const PAPER_WIDTH: u32 = 612;
+pub const PAPER_HEIGHT: u32 = 792;
+
+/// The size of the printable area of the paper, in points, which leaves a margin of a quarter
+/// inch on each side.
+pub const PAGE_WIDTH: u32 = 576;
+pub const PAGE_HEIGHT: u32 = 756;
+
+const POINTS_PER_INCH: f64 = 72.0;
+
+/// The resolution pages are rendered at, so that vector content stays sharp on paper.
+const VECTOR_DPI: f64 = 288.0;
+
+/// The resolution pages printed as bitmaps are rendered at, which is the screen resolution.
+const BITMAP_DPI: f64 = 72.0;
+
+/// The pages added to a `PrintJob` that hasn't been sent yet.
+///
+/// Only one print job can be active at a time.
+#[derive(Default)]
+pub struct PrintJob {
+    pub pages: Vec<PrintedPage>,
+}
+
+/// Renders an area of a display object, given in its own coordinate space, onto a page.
+///
+/// With `fit_to_page`, the area gets scaled to fill the printable area of the page. Otherwise,
+/// each pixel of the area is printed as a point, and anything not fitting on the page is cut off.
+pub fn render_page<'gc>(
+    context: &mut UpdateContext<'gc>,
+    object: DisplayObject<'gc>,
+    area: Rectangle<Twips>,
+    as_bitmap: bool,
+    fit_to_page: bool,
+) -> Option<PrintedPage> {
+    let area_width = area.width().to_pixels();
+    let area_height = area.height().to_pixels();
+    if area_width <= 0.0 || area_height <= 0.0 {
+        return None;
+    }
+
+    let page_scale = if fit_to_page {
+        (PAGE_WIDTH as f64 / area_width).min(PAGE_HEIGHT as f64 / area_height)
+    } else {
+        1.0
+    };
+    let dpi = if as_bitmap { BITMAP_DPI } else { VECTOR_DPI };
+    let scale = page_scale * dpi / POINTS_PER_INCH;
+
+    let max_width = PAGE_WIDTH as f64 * dpi / POINTS_PER_INCH;
+    let max_height = PAGE_HEIGHT as f64 * dpi / POINTS_PER_INCH;
+    let width = (area_width * scale).min(max_width).ceil().max(1.0) as u32;
+    let height = (area_height * scale).min(max_height).ceil().max(1.0) as u32;
+
+    let handle = match context.renderer.create_empty_texture(width, height) {
+        Ok(handle) => handle,
+        Err(e) => {
+            tracing::error!("Couldn't create a texture to print a page: {}", e);
+            return None;
+        }
+    };
+
+    let mut transform_stack = TransformStack::new();
+    transform_stack.push(&Transform {
+        matrix: Matrix::scale(scale as f32, scale as f32)
+            * Matrix::translate(-area.x_min, -area.y_min),
+        ..Default::default()
+    });
+
+    let mut cache_draws = vec![];
+    let mut render_context = RenderContext {
+        renderer: context.renderer,
+        commands: CommandList::new(),
+        cache_draws: &mut cache_draws,
+        gc_context: context.gc_context,
+        library: context.library,
+        transform_stack: &mut transform_stack,
+        is_offscreen: true,
+        use_bitmap_cache: false,
+        stage: context.stage,
+    };
+
+    // Pages are printed on white paper.
+    render_context.commands.draw_rect(
+        Color::WHITE,
+        Matrix::create_box(width as f32, height as f32, Twips::ZERO, Twips::ZERO),
+    );
+    object.render_self(&mut render_context);
+
+    let commands = render_context.commands;
+    let quality = context.stage.quality();
+    let sync_handle = context.renderer.render_offscreen(
+        handle,
+        commands,
+        quality,
+        PixelRegion::for_whole_size(width, height),
+    )?;
+
+    let mut rgba = Vec::with_capacity(width as usize * height as usize * 4);
+    let result = context.renderer.resolve_sync_handle(
+        sync_handle,
+        Box::new(|buffer: &[u8], buffer_width: u32| {
+            for row in buffer.chunks(buffer_width as usize).take(height as usize) {
+                rgba.extend_from_slice(&row[..width as usize * 4]);
+            }
+        }),
+    );
+    if let Err(e) = result {
+        tracing::error!("Couldn't read back a printed page: {}", e);
+        return None;
+    }
+
+    Some(PrintedPage {
+        width,
+        height,
+        dpi,
+        rgba,
+    })
+}
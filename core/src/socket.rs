@@ -7,12 +7,14 @@ use crate::{
     backend::navigator::NavigatorBackend,
     context::UpdateContext,
     string::AvmString,
+    tag_utils::SwfMovie,
 };
 use async_channel::{unbounded, Receiver, Sender as AsyncSender, Sender};
 use gc_arena::Collect;
 use slotmap::{new_key_type, SlotMap};
 use std::{
     cell::{Cell, RefCell},
+    sync::Arc,
     time::Duration,
 };
 
@@ -33,14 +35,19 @@ struct Socket<'gc> {
     target: SocketKind<'gc>,
     sender: RefCell<AsyncSender<Vec<u8>>>,
     connected: Cell<bool>,
+
+    /// The movie that opened this socket.
+    #[collect(require_static)]
+    movie: Arc<SwfMovie>,
 }
 
 impl<'gc> Socket<'gc> {
-    fn new(target: SocketKind<'gc>, sender: AsyncSender<Vec<u8>>) -> Self {
+    fn new(target: SocketKind<'gc>, sender: AsyncSender<Vec<u8>>, movie: Arc<SwfMovie>) -> Self {
         Self {
             target,
             sender: RefCell::new(sender),
             connected: Cell::new(false),
+            movie,
         }
     }
 }
@@ -92,10 +99,11 @@ impl<'gc> Sockets<'gc> {
         target: SocketObject<'gc>,
         host: String,
         port: u16,
+        movie: Arc<SwfMovie>,
     ) {
         let (sender, receiver) = unbounded();
 
-        let socket = Socket::new(SocketKind::Avm2(target), sender);
+        let socket = Socket::new(SocketKind::Avm2(target), sender, movie);
         let handle = self.sockets.insert(socket);
 
         // NOTE: This call will send SocketAction::Connect to sender with connection status.
@@ -121,6 +129,7 @@ impl<'gc> Sockets<'gc> {
         target: Avm1Object<'gc>,
         host: String,
         port: u16,
+        movie: Arc<SwfMovie>,
     ) {
         let (sender, receiver) = unbounded();
 
@@ -129,7 +138,7 @@ impl<'gc> Sockets<'gc> {
             None => return,
         };
 
-        let socket = Socket::new(SocketKind::Avm1(target), sender);
+        let socket = Socket::new(SocketKind::Avm1(target), sender, movie);
         let handle = self.sockets.insert(socket);
 
         // NOTE: This call will send SocketAction::Connect to sender with connection status.
@@ -179,11 +188,26 @@ impl<'gc> Sockets<'gc> {
         }
     }
 
+    /// Closes all sockets opened by the given movie.
+    pub fn close_for_movie(&mut self, movie: &Arc<SwfMovie>) {
+        let handles: Vec<_> = self
+            .sockets
+            .iter()
+            .filter(|(_, socket)| Arc::ptr_eq(&socket.movie, movie))
+            .map(|(handle, _)| handle)
+            .collect();
+
+        for handle in handles {
+            self.close(handle);
+        }
+    }
+
     fn close_internal(socket: Socket) {
         let Socket {
             sender,
             target,
             connected: _,
+            movie: _,
         } = socket;
 
         drop(sender); // NOTE: By dropping the sender, the reading task will close automatically.
@@ -19,6 +19,7 @@ use crate::context::UpdateContext;
 use crate::display_object::{MovieClip, TDisplayObject};
 use crate::loader::Error;
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use crate::vminterface::AvmObject;
 use flv_rs::{
     AudioData as FlvAudioData, AudioDataType as FlvAudioDataType, Error as FlvError, FlvReader,
@@ -33,6 +34,7 @@ use ruffle_video::frame::EncodedFrame;
 use ruffle_video::VideoStreamHandle;
 use std::cmp::max;
 use std::io::{Seek, SeekFrom};
+use std::sync::Arc;
 use swf::{AudioCompression, SoundFormat, VideoCodec, VideoDeblocking};
 use thiserror::Error;
 use url::Url;
@@ -131,6 +133,16 @@ impl<'gc> StreamManager<'gc> {
             stream.tick(context, dt)
         }
     }
+
+    /// Close all active streams created by the given movie.
+    pub fn close_for_movie(context: &mut UpdateContext<'gc>, movie: &Arc<SwfMovie>) {
+        let streams = context.stream_manager.active_streams.clone();
+        for stream in streams {
+            if Arc::ptr_eq(&stream.0.read().movie, movie) {
+                stream.close(context);
+            }
+        }
+    }
 }
 
 /// A stream representing download of some (audiovisual) data.
@@ -179,6 +191,22 @@ pub enum NetStreamType {
     },
 }
 
+/// An action that controls how bytes appended to a stream in data generation
+/// mode are interpreted. Corresponds to `NetStreamAppendBytesAction` in AS3.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppendBytesAction {
+    /// Discard all appended data and expect a new FLV header.
+    ResetBegin,
+
+    /// Discard all appended data and expect FLV tags continuing from the
+    /// position that was seeked to.
+    ResetSeek,
+
+    /// No more data will be appended; the stream ends once it has played the
+    /// data appended so far.
+    EndSequence,
+}
+
 #[derive(Clone, Debug, Collect)]
 #[collect(no_drop)]
 pub struct NetStreamData<'gc> {
@@ -213,6 +241,16 @@ pub struct NetStreamData<'gc> {
     /// The current seek offset in the stream in milliseconds.
     stream_time: f64,
 
+    /// The offset added to tag timestamps to get the stream time they play
+    /// at, in milliseconds.
+    tag_time_offset: f64,
+
+    /// True if `tag_time_offset` should be recalculated so that the next
+    /// processed tag plays at the current stream time.
+    ///
+    /// This is used when the timescale is reset in data generation mode.
+    resync_tag_time: bool,
+
     /// The next queued seek offset in milliseconds.
     ///
     /// Seeks are only executed on the next stream tick.
@@ -252,10 +290,26 @@ pub struct NetStreamData<'gc> {
 
     /// True if the stream should play when ticked.
     playing: bool,
+
+    /// True if the stream is in data generation mode, where its data is
+    /// appended by the movie itself instead of downloaded.
+    data_generation: bool,
+
+    /// True if the stream has reported running out of appended data, which is
+    /// only reported again once the movie appends more data.
+    data_underrun_reported: bool,
+
+    /// The movie that created this stream.
+    #[collect(require_static)]
+    movie: Arc<SwfMovie>,
 }
 
 impl<'gc> NetStream<'gc> {
-    pub fn new(gc_context: &Mutation<'gc>, avm_object: Option<AvmObject<'gc>>) -> Self {
+    pub fn new(
+        gc_context: &Mutation<'gc>,
+        avm_object: Option<AvmObject<'gc>>,
+        movie: Arc<SwfMovie>,
+    ) -> Self {
         // IMPORTANT: When adding new fields consider if they need to be
         // initialized in `reset_buffer` as well.
         Self(GcCell::new(
@@ -266,6 +320,8 @@ impl<'gc> NetStream<'gc> {
                 preload_offset: 0,
                 stream_type: None,
                 stream_time: 0.0,
+                tag_time_offset: 0.0,
+                resync_tag_time: false,
                 queued_seek_time: None,
                 buffer_time: 0.1,
                 last_decoded_bitmap: None,
@@ -276,7 +332,10 @@ impl<'gc> NetStream<'gc> {
                 sound_instance: None,
                 attached_to: None,
                 playing: false,
+                data_generation: false,
+                data_underrun_reported: false,
                 expected_length: Some(0),
+                movie,
             },
         ))
     }
@@ -304,6 +363,21 @@ impl<'gc> NetStream<'gc> {
     pub fn reset_buffer(self, context: &mut UpdateContext<'gc>) {
         let mut write = self.0.write(context.gc_context);
 
+        Self::stop_sound_instance(context, &mut write);
+
+        write.buffer = Buffer::new();
+        write.offset = 0;
+        write.preload_offset = 0;
+        write.stream_type = None;
+        write.stream_time = 0.0;
+        write.tag_time_offset = 0.0;
+        write.resync_tag_time = false;
+        write.queued_seek_time = None;
+        write.expected_length = Some(0);
+    }
+
+    /// Stop the currently playing audio track, if any.
+    fn stop_sound_instance(context: &mut UpdateContext<'gc>, write: &mut NetStreamData<'gc>) {
         if let Some(instance) = write.sound_instance {
             // We stop the sound twice because sounds may have either been
             // played through the audio manager or through the backend directly
@@ -313,15 +387,75 @@ impl<'gc> NetStream<'gc> {
             context.audio_manager.stop_sound(context.audio, instance);
         }
 
-        write.buffer = Buffer::new();
-        write.offset = 0;
-        write.preload_offset = 0;
-        write.stream_type = None;
-        write.stream_time = 0.0;
-        write.queued_seek_time = None;
         write.audio_stream = None;
         write.sound_instance = None;
-        write.expected_length = Some(0);
+    }
+
+    /// Put the stream into data generation mode, where the movie appends the
+    /// data to play with `append_bytes` instead of downloading it.
+    ///
+    /// This corresponds to calling `NetStream.play(null)` in AS3.
+    pub fn start_data_generation(self, context: &mut UpdateContext<'gc>) {
+        self.reset_buffer(context);
+        self.0.write(context.gc_context).data_generation = true;
+    }
+
+    /// Append FLV data generated by the movie to the stream.
+    ///
+    /// Data can be appended in chunks of any size, but the chunks must be
+    /// appended in order. The data must start with an FLV header after a
+    /// `ResetBegin` action, or with a whole FLV tag after a `ResetSeek`
+    /// action.
+    pub fn append_bytes(self, context: &mut UpdateContext<'gc>, data: &mut Vec<u8>) {
+        let mut write = self.0.write(context.gc_context);
+        if !write.data_generation {
+            tracing::warn!("NetStream.appendBytes called outside of data generation mode");
+            return;
+        }
+
+        write.buffer.append(data);
+        write.data_underrun_reported = false;
+        drop(write);
+
+        StreamManager::activate(context, self);
+    }
+
+    /// Change how the stream interprets appended data.
+    pub fn append_bytes_action(self, context: &mut UpdateContext<'gc>, action: AppendBytesAction) {
+        if !self.0.read().data_generation {
+            tracing::warn!("NetStream.appendBytesAction called outside of data generation mode");
+            return;
+        }
+        self.0.write(context.gc_context).data_underrun_reported = false;
+
+        match action {
+            AppendBytesAction::ResetBegin => {
+                self.reset_buffer(context);
+                self.0.write(context.gc_context).resync_tag_time = true;
+            }
+            AppendBytesAction::ResetSeek => {
+                let mut write = self.0.write(context.gc_context);
+                Self::stop_sound_instance(context, &mut write);
+
+                write.buffer = Buffer::new();
+                if write.stream_type.is_some() {
+                    // The FLV header has already been parsed, so the new data
+                    // starts with a tag. Tags are preceded by the size of the
+                    // tag before them, which is missing here, so pretend that
+                    // this is the first tag of the stream.
+                    write.buffer.extend_from_slice(&[0; 4]);
+                }
+                write.offset = 0;
+                write.preload_offset = 0;
+                write.queued_seek_time = None;
+                write.expected_length = Some(0);
+                write.resync_tag_time = true;
+            }
+            AppendBytesAction::EndSequence => {
+                self.finish_buffer(context);
+                StreamManager::activate(context, self);
+            }
+        }
     }
 
     /// Set the total number of bytes expected to be downloaded.
@@ -431,6 +565,25 @@ impl<'gc> NetStream<'gc> {
     /// This function should be run during stream ticks and *not* called by AVM
     /// code to service seek requests.
     pub fn execute_seek(self, context: &mut UpdateContext<'gc>, offset: f64) {
+        if self.0.read().data_generation {
+            // Appended data can't be seeked through. Instead, everything
+            // appended so far is discarded, and the movie is expected to
+            // append data for the new position after a `ResetSeek` action.
+            let mut write = self.0.write(context.gc_context);
+            Self::stop_sound_instance(context, &mut write);
+            write.buffer = Buffer::new();
+            write.offset = 0;
+            write.preload_offset = 0;
+            write.stream_time = offset;
+            drop(write);
+
+            self.trigger_status_event(
+                context,
+                vec![("code", "NetStream.Seek.Notify"), ("level", "status")],
+            );
+            return;
+        }
+
         self.trigger_status_event(
             context,
             vec![("code", "NetStream.Seek.Notify"), ("level", "status")],
@@ -498,7 +651,7 @@ impl<'gc> NetStream<'gc> {
                 }
 
                 let tag = tag.unwrap();
-                write.stream_time = tag.timestamp as f64;
+                write.stream_time = tag.timestamp as f64 + write.tag_time_offset;
 
                 if skipping_back && write.stream_time > offset
                     || !skipping_back && write.stream_time < offset
@@ -559,6 +712,7 @@ impl<'gc> NetStream<'gc> {
             let mut write = self.0.write(context.gc_context);
             write.url = Some(request.url().to_string());
             write.preload_offset = 0;
+            write.data_generation = false;
             let future = context
                 .load_manager
                 .load_netstream(context.player.clone(), self, request);
@@ -599,6 +753,20 @@ impl<'gc> NetStream<'gc> {
         StreamManager::activate(context, self);
     }
 
+    /// Stop playback and downloading, and reset the stream for another use.
+    pub fn close(self, context: &mut UpdateContext<'gc>) {
+        context.load_manager.cancel_stream_loads(self);
+        self.reset_buffer(context);
+
+        let mut write = self.0.write(context.gc_context);
+        write.url = None;
+        write.playing = false;
+        write.data_generation = false;
+        drop(write);
+
+        StreamManager::deactivate(context, self);
+    }
+
     /// Resume stream playback if paused, pause otherwise.
     pub fn toggle_paused(self, context: &mut UpdateContext<'gc>) {
         let mut write = self.0.write(context.gc_context);
@@ -1189,7 +1357,12 @@ impl<'gc> NetStream<'gc> {
                 }
 
                 let tag = tag.expect("valid tag");
-                is_lookahead_tag = tag.timestamp as f64 >= max_time; //FLV timestamps are also ms
+                if write.resync_tag_time {
+                    write.tag_time_offset = write.stream_time - tag.timestamp as f64;
+                    write.resync_tag_time = false;
+                }
+                let tag_time = tag.timestamp as f64 + write.tag_time_offset;
+                is_lookahead_tag = tag_time >= max_time; //FLV timestamps are also ms
                 if is_lookahead_tag && max_lookahead_audio_tags == 0 {
                     break;
                 }
@@ -1242,6 +1415,12 @@ impl<'gc> NetStream<'gc> {
             //TODO: Fire an error event at AS.
             tracing::error!("Error committing sound stream: {}", e);
         }
+        // A movie appending data only as it's played would otherwise run out of
+        // data on most ticks.
+        if buffer_underrun && write.data_generation {
+            buffer_underrun = !write.data_underrun_reported;
+            write.data_underrun_reported = true;
+        }
         drop(write);
 
         if buffer_underrun {
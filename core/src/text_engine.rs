@@ -0,0 +1,690 @@
+//! Line layout for the Flash Text Engine (`flash.text.engine`).
+//!
+//! The content of a `TextBlock` is flattened into runs of uniformly formatted
+//! text or graphics, which are then broken into lines one at a time, as the
+//! block is asked to create them.
+
+use crate::display_object::DisplayObject;
+use crate::font::{EvalParameters, Font};
+use crate::html::TextFormat;
+use crate::string::{WStr, WString};
+use gc_arena::Collect;
+use swf::Twips;
+
+/// The widest a line can be, see `TextLine.MAX_LINE_WIDTH`.
+pub const MAX_LINE_WIDTH: f64 = 1000000.0;
+
+/// The distance between the implicit tab stops used past the last tab stop of a block.
+const DEFAULT_TAB_INTERVAL: f64 = 48.0;
+
+/// How the characters of an element may be broken across lines.
+#[derive(Copy, Clone, Collect, Debug, PartialEq, Eq)]
+#[collect(require_static)]
+pub enum BreakOpportunity {
+    /// Break according to the usual rules, e.g. between words.
+    Auto,
+
+    /// Break between any two characters.
+    Any,
+
+    /// Never break.
+    None,
+
+    /// Break between every two characters.
+    All,
+}
+
+/// Which lines of a block are justified.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LineJustification {
+    Unjustified,
+    AllButLast,
+    AllButMandatoryBreak,
+    AllIncludingLast,
+}
+
+/// How the extra width of a justified line is spread over its content.
+#[derive(Copy, Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct Justifier {
+    pub line_justification: LineJustification,
+
+    /// Whether the extra width is spread between all characters, rather than
+    /// just over the spaces between words.
+    ///
+    /// East Asian justification always spreads it between characters.
+    pub letter_spacing: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TabAlignment {
+    Start,
+    Center,
+    End,
+    Decimal,
+}
+
+#[derive(Clone, Collect, Debug)]
+#[collect(require_static)]
+pub struct TabStop {
+    pub alignment: TabAlignment,
+    pub position: f64,
+    pub decimal_alignment_token: WString,
+}
+
+/// The formatting of a run of content, as given by its `ElementFormat`.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct RunFormat<'gc> {
+    /// The font used to measure the run, if any could be found.
+    pub font: Option<Font<'gc>>,
+
+    /// The format used to render the text of the run.
+    pub text_format: TextFormat,
+
+    pub is_device_font: bool,
+    pub size: f64,
+    pub alpha: f64,
+    pub tracking_left: f64,
+    pub tracking_right: f64,
+
+    /// How far the run is moved down from the baseline.
+    pub baseline_shift: f64,
+
+    pub kerning: bool,
+    pub break_opportunity: BreakOpportunity,
+}
+
+impl<'gc> RunFormat<'gc> {
+    /// The spacing added between each two characters of the run.
+    pub fn letter_spacing(&self) -> f64 {
+        self.tracking_left + self.tracking_right
+    }
+
+    /// The ascent and descent of text in this format, relative to the baseline of the line.
+    fn metrics(&self) -> (f64, f64) {
+        let (ascent, descent) = self
+            .font
+            .map(|font| {
+                let height = Twips::from_pixels(self.size);
+                (
+                    font.get_baseline_for_height(height).to_pixels(),
+                    font.get_descent_for_height(height).to_pixels(),
+                )
+            })
+            .unwrap_or_default();
+        (ascent - self.baseline_shift, descent + self.baseline_shift)
+    }
+}
+
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum RunContent<'gc> {
+    Text(#[collect(require_static)] WString),
+    Graphic {
+        graphic: Option<DisplayObject<'gc>>,
+        width: f64,
+        height: f64,
+    },
+}
+
+/// A piece of content with a single format, such as the text of a `TextElement`.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct ContentRun<'gc> {
+    /// The index of the first character of this run in the text of the block.
+    pub begin_index: usize,
+    pub content: RunContent<'gc>,
+    pub format: RunFormat<'gc>,
+}
+
+/// The outcome of laying out a line, see `TextLineCreationResult`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LineCreationResult {
+    /// The line was broken at a break opportunity.
+    Success,
+
+    /// There was no break opportunity on the line, so it was broken where the width ran out.
+    Emergency,
+
+    /// Not even a single atom fits in the given width.
+    InsufficientWidth,
+
+    /// All of the content of the block has already been laid out.
+    Complete,
+}
+
+impl LineCreationResult {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineCreationResult::Success => "success",
+            LineCreationResult::Emergency => "emergency",
+            LineCreationResult::InsufficientWidth => "insufficientWidth",
+            LineCreationResult::Complete => "complete",
+        }
+    }
+}
+
+/// An atom on a laid out line, with its bounds relative to the origin of the line.
+#[derive(Copy, Clone, Debug)]
+pub struct LineAtom {
+    pub begin_index: usize,
+    pub end_index: usize,
+    pub x: f64,
+    pub width: f64,
+    pub ascent: f64,
+    pub descent: f64,
+}
+
+/// Something to display on a laid out line.
+#[derive(Copy, Clone, Debug)]
+pub enum LineItem {
+    /// Text from a single run, rendered as a contiguous string.
+    Text {
+        run: usize,
+        begin_index: usize,
+        end_index: usize,
+        x: f64,
+
+        /// The spacing added between characters, including any from justification.
+        letter_spacing: f64,
+    },
+
+    /// The graphic of a `GraphicElement`.
+    Graphic { run: usize, x: f64 },
+}
+
+/// A single line of a block, as laid out by [`BlockContent::layout_line`].
+#[derive(Clone, Debug)]
+pub struct LineLayout {
+    pub begin_index: usize,
+    pub end_index: usize,
+    pub atoms: Vec<LineAtom>,
+    pub items: Vec<LineItem>,
+    pub ascent: f64,
+    pub descent: f64,
+    pub text_width: f64,
+    pub unjustified_text_width: f64,
+    pub has_tabs: bool,
+    pub has_graphic_element: bool,
+    pub result: LineCreationResult,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum AtomKind {
+    Glyph,
+    Space,
+    Tab,
+    LineBreak,
+    Graphic,
+}
+
+/// The smallest unit of content that can't be split across lines.
+#[derive(Copy, Clone, Debug)]
+struct Atom {
+    begin: usize,
+    end: usize,
+    run: usize,
+    kind: AtomKind,
+    character: char,
+    advance: f64,
+    ascent: f64,
+    descent: f64,
+}
+
+impl Atom {
+    fn is_whitespace(&self) -> bool {
+        matches!(self.kind, AtomKind::Space | AtomKind::Tab)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum LineBreak {
+    Mandatory,
+    Allowed,
+    Prohibited,
+}
+
+/// The flattened content of a `TextBlock`, along with the block-wide settings used to lay it out.
+#[derive(Clone, Collect, Debug, Default)]
+#[collect(no_drop)]
+pub struct BlockContent<'gc> {
+    runs: Vec<ContentRun<'gc>>,
+    #[collect(require_static)]
+    text: WString,
+    tab_stops: Vec<TabStop>,
+    justifier: Option<Justifier>,
+}
+
+impl<'gc> BlockContent<'gc> {
+    /// Set the block-wide settings, which can change without the content being flattened again.
+    pub fn set_settings(&mut self, tab_stops: Vec<TabStop>, justifier: Option<Justifier>) {
+        self.tab_stops = tab_stops;
+        self.justifier = justifier;
+    }
+
+    pub fn push_text(&mut self, text: &WStr, format: RunFormat<'gc>) {
+        self.runs.push(ContentRun {
+            begin_index: self.text.len(),
+            content: RunContent::Text(text.into()),
+            format,
+        });
+        self.text.push_str(text);
+    }
+
+    pub fn push_graphic(
+        &mut self,
+        graphic: Option<DisplayObject<'gc>>,
+        width: f64,
+        height: f64,
+        format: RunFormat<'gc>,
+    ) {
+        self.runs.push(ContentRun {
+            begin_index: self.text.len(),
+            content: RunContent::Graphic {
+                graphic,
+                width,
+                height,
+            },
+            format,
+        });
+        self.text.push(0xFDEF);
+    }
+
+    pub fn runs(&self) -> &[ContentRun<'gc>] {
+        &self.runs
+    }
+
+    /// The raw text of the block, with a placeholder character for each graphic.
+    pub fn text(&self) -> &WStr {
+        &self.text
+    }
+
+    /// Lay out the line starting at `begin_index`, fitting it into `width` if possible.
+    ///
+    /// `line_offset` is the distance of the line from the start of the block,
+    /// which tab stops are measured from.
+    pub fn layout_line(
+        &self,
+        begin_index: usize,
+        width: f64,
+        line_offset: f64,
+        fit_something: bool,
+    ) -> Result<LineLayout, LineCreationResult> {
+        let atoms = self.atoms();
+        let first = atoms
+            .iter()
+            .position(|atom| atom.begin >= begin_index)
+            .unwrap_or(atoms.len());
+
+        // The position and width of each atom on the line.
+        let mut positions: Vec<(f64, f64)> = vec![];
+        let mut x = 0.0;
+        let mut end = first;
+        let mut last_break = None;
+        let mut result = LineCreationResult::Success;
+        let mut mandatory_break = false;
+        while end < atoms.len() {
+            let atom = &atoms[end];
+            if end > first {
+                match self.break_before(&atoms[end - 1], atom) {
+                    LineBreak::Mandatory => {
+                        mandatory_break = true;
+                        break;
+                    }
+                    LineBreak::Allowed => last_break = Some(end),
+                    LineBreak::Prohibited => {}
+                }
+            }
+
+            let advance = if atom.kind == AtomKind::Tab {
+                self.tab_advance(&atoms[end + 1..], x + line_offset)
+            } else {
+                atom.advance
+            };
+
+            // Whitespace is allowed to hang past the end of the line.
+            if !atom.is_whitespace() && x + advance > width {
+                if end == first {
+                    if !fit_something {
+                        return Err(LineCreationResult::InsufficientWidth);
+                    }
+                    positions.push((x, advance));
+                    end += 1;
+                    result = LineCreationResult::Emergency;
+                } else if let Some(last_break) = last_break {
+                    positions.truncate(last_break - first);
+                    end = last_break;
+                } else {
+                    result = LineCreationResult::Emergency;
+                }
+                break;
+            }
+
+            positions.push((x, advance));
+            x += advance;
+            end += 1;
+
+            if atom.kind == AtomKind::LineBreak {
+                // A CRLF pair only breaks the line once.
+                if atom.character == '\r' && atoms.get(end).is_some_and(|a| a.character == '\n') {
+                    positions.push((x, 0.0));
+                    end += 1;
+                }
+                mandatory_break = true;
+                break;
+            }
+        }
+
+        let line_atoms = &atoms[first..end];
+        let unjustified_text_width = positions.last().map_or(0.0, |(x, width)| x + width);
+
+        // Trailing whitespace is left out when justifying the line.
+        let visible_len = line_atoms
+            .iter()
+            .rposition(|atom| !atom.is_whitespace() && atom.kind != AtomKind::LineBreak)
+            .map_or(0, |i| i + 1);
+        let extras = self.justification(
+            line_atoms,
+            &positions,
+            visible_len,
+            width,
+            end == atoms.len(),
+            mandatory_break,
+        );
+        let mut shift = 0.0;
+        for (position, extra) in positions.iter_mut().zip(&extras) {
+            position.0 += shift;
+            position.1 += extra;
+            shift += extra;
+        }
+        let letter_spacing_extra = match self.justifier {
+            Some(justifier) if justifier.letter_spacing => extras.first().copied().unwrap_or(0.0),
+            _ => 0.0,
+        };
+
+        let mut items: Vec<LineItem> = vec![];
+        let mut split_segment = false;
+        for (i, atom) in line_atoms.iter().enumerate() {
+            let x = positions[i].0;
+            match atom.kind {
+                AtomKind::Graphic => items.push(LineItem::Graphic { run: atom.run, x }),
+                AtomKind::Glyph | AtomKind::Space => {
+                    let continues = match items.last_mut() {
+                        Some(LineItem::Text { run, end_index, .. })
+                            if !split_segment && *run == atom.run && *end_index == atom.begin =>
+                        {
+                            *end_index = atom.end;
+                            true
+                        }
+                        _ => false,
+                    };
+                    if !continues {
+                        items.push(LineItem::Text {
+                            run: atom.run,
+                            begin_index: atom.begin,
+                            end_index: atom.end,
+                            x,
+                            letter_spacing: self.runs[atom.run].format.letter_spacing()
+                                + letter_spacing_extra,
+                        });
+                    }
+                }
+                AtomKind::Tab | AtomKind::LineBreak => {}
+            }
+            // Spaces stretched by justification push the rest of the text along.
+            split_segment =
+                atom.kind == AtomKind::Space && extras[i] > 0.0 && letter_spacing_extra == 0.0;
+        }
+
+        let (ascent, descent) = if line_atoms.is_empty() {
+            self.empty_line_metrics(begin_index)
+        } else {
+            line_atoms
+                .iter()
+                .fold((0.0f64, 0.0f64), |(ascent, descent), atom| {
+                    (ascent.max(atom.ascent), descent.max(atom.descent))
+                })
+        };
+
+        Ok(LineLayout {
+            begin_index,
+            end_index: line_atoms.last().map_or(begin_index, |atom| atom.end),
+            atoms: line_atoms
+                .iter()
+                .zip(&positions)
+                .map(|(atom, (x, width))| LineAtom {
+                    begin_index: atom.begin,
+                    end_index: atom.end,
+                    x: *x,
+                    width: *width,
+                    ascent: atom.ascent,
+                    descent: atom.descent,
+                })
+                .collect(),
+            items,
+            ascent,
+            descent,
+            text_width: positions.last().map_or(0.0, |(x, width)| x + width),
+            unjustified_text_width,
+            has_tabs: line_atoms.iter().any(|atom| atom.kind == AtomKind::Tab),
+            has_graphic_element: line_atoms.iter().any(|atom| atom.kind == AtomKind::Graphic),
+            result,
+        })
+    }
+
+    /// Split the content of the block into atoms, measuring each of them.
+    fn atoms(&self) -> Vec<Atom> {
+        let mut atoms = vec![];
+        for (run_index, run) in self.runs.iter().enumerate() {
+            let format = &run.format;
+            let (ascent, descent) = format.metrics();
+            match &run.content {
+                RunContent::Text(text) => {
+                    let mut advances = vec![0.0; text.len()];
+                    if let Some(font) = format.font {
+                        let params = EvalParameters::from_parts(
+                            Twips::from_pixels(format.size),
+                            Twips::from_pixels(format.letter_spacing()),
+                            format.kerning,
+                        );
+                        font.evaluate(
+                            text,
+                            Default::default(),
+                            params,
+                            |pos, _transform, _glyph, advance, _x| {
+                                advances[pos] = advance.to_pixels();
+                            },
+                        );
+                    }
+
+                    let characters: Vec<_> = text
+                        .char_indices()
+                        .map(|(pos, c)| (pos, c.unwrap_or(char::REPLACEMENT_CHARACTER)))
+                        .collect();
+                    for (i, &(pos, character)) in characters.iter().enumerate() {
+                        let end = characters.get(i + 1).map_or(text.len(), |(end, _)| *end);
+                        let kind = match character {
+                            ' ' | '\u{3000}' => AtomKind::Space,
+                            '\t' => AtomKind::Tab,
+                            '\n' | '\r' | '\u{2028}' | '\u{2029}' => AtomKind::LineBreak,
+                            _ => AtomKind::Glyph,
+                        };
+                        atoms.push(Atom {
+                            begin: run.begin_index + pos,
+                            end: run.begin_index + end,
+                            run: run_index,
+                            kind,
+                            character,
+                            advance: if kind == AtomKind::LineBreak {
+                                0.0
+                            } else {
+                                advances[pos]
+                            },
+                            ascent,
+                            descent,
+                        });
+                    }
+                }
+                RunContent::Graphic { width, height, .. } => atoms.push(Atom {
+                    begin: run.begin_index,
+                    end: run.begin_index + 1,
+                    run: run_index,
+                    kind: AtomKind::Graphic,
+                    character: '\u{FDEF}',
+                    advance: *width,
+                    ascent: height - format.baseline_shift,
+                    descent: format.baseline_shift,
+                }),
+            }
+        }
+        atoms
+    }
+
+    /// Whether the line may be broken between two adjacent atoms.
+    fn break_before(&self, previous: &Atom, next: &Atom) -> LineBreak {
+        match self.runs[next.run].format.break_opportunity {
+            BreakOpportunity::All => LineBreak::Mandatory,
+            BreakOpportunity::Any => LineBreak::Allowed,
+            BreakOpportunity::None => LineBreak::Prohibited,
+            BreakOpportunity::Auto => {
+                let allowed = !next.is_whitespace()
+                    && next.kind != AtomKind::LineBreak
+                    && (previous.is_whitespace()
+                        || matches!(previous.character, '-' | '\u{200B}')
+                        || previous.kind == AtomKind::Graphic
+                        || next.kind == AtomKind::Graphic
+                        || is_ideographic(previous.character)
+                        || is_ideographic(next.character));
+                if allowed {
+                    LineBreak::Allowed
+                } else {
+                    LineBreak::Prohibited
+                }
+            }
+        }
+    }
+
+    /// The width of a tab at `position`, with `following` being the atoms after it.
+    fn tab_advance(&self, following: &[Atom], position: f64) -> f64 {
+        let Some(tab_stop) = self
+            .tab_stops
+            .iter()
+            .find(|tab_stop| tab_stop.position > position)
+        else {
+            let next_stop =
+                ((position / DEFAULT_TAB_INTERVAL).floor() + 1.0) * DEFAULT_TAB_INTERVAL;
+            return next_stop - position;
+        };
+
+        // Aligning to anything but the start of the text depends on the text up to the next tab.
+        let following_len = following
+            .iter()
+            .position(|atom| matches!(atom.kind, AtomKind::Tab | AtomKind::LineBreak))
+            .unwrap_or(following.len());
+        let following = &following[..following_len];
+        let width_until =
+            |len: usize| -> f64 { following[..len].iter().map(|atom| atom.advance).sum() };
+
+        let offset = match tab_stop.alignment {
+            TabAlignment::Start => 0.0,
+            TabAlignment::Center => width_until(following.len()) / 2.0,
+            TabAlignment::End => width_until(following.len()),
+            TabAlignment::Decimal => {
+                let token = &tab_stop.decimal_alignment_token;
+                let token_index = match (following.first(), following.last()) {
+                    (Some(first), Some(last)) if !token.is_empty() => self.text
+                        [first.begin..last.end]
+                        .find(&**token)
+                        .map(|index| first.begin + index),
+                    _ => None,
+                };
+                let len = token_index.map_or(following.len(), |index| {
+                    following
+                        .iter()
+                        .position(|atom| atom.begin >= index)
+                        .unwrap_or(following.len())
+                });
+                width_until(len)
+            }
+        };
+
+        (tab_stop.position - offset - position).max(0.0)
+    }
+
+    /// The extra width given to each atom of the line by the justifier of the block.
+    fn justification(
+        &self,
+        atoms: &[Atom],
+        positions: &[(f64, f64)],
+        visible_len: usize,
+        width: f64,
+        is_last_line: bool,
+        mandatory_break: bool,
+    ) -> Vec<f64> {
+        let mut extras = vec![0.0; atoms.len()];
+        let Some(justifier) = self.justifier else {
+            return extras;
+        };
+
+        let justify = match justifier.line_justification {
+            LineJustification::Unjustified => false,
+            LineJustification::AllButLast => !is_last_line,
+            LineJustification::AllButMandatoryBreak => !is_last_line && !mandatory_break,
+            LineJustification::AllIncludingLast => true,
+        };
+        if !justify || width >= MAX_LINE_WIDTH || visible_len == 0 {
+            return extras;
+        }
+
+        let (x, visible_width) = positions[visible_len - 1];
+        let extra = width - (x + visible_width);
+        if extra <= 0.0 {
+            return extras;
+        }
+
+        if justifier.letter_spacing {
+            let gaps = visible_len - 1;
+            if gaps > 0 {
+                extras[..gaps].fill(extra / gaps as f64);
+            }
+        } else {
+            // Leading spaces keep their width, only the spaces between words are stretched.
+            let words_start = atoms[..visible_len]
+                .iter()
+                .position(|atom| !atom.is_whitespace())
+                .unwrap_or(visible_len);
+            let spaces: Vec<usize> = (words_start..visible_len)
+                .filter(|&i| atoms[i].kind == AtomKind::Space)
+                .collect();
+            for &i in &spaces {
+                extras[i] = extra / spaces.len() as f64;
+            }
+        }
+        extras
+    }
+
+    /// The ascent and descent of a line without any atoms, which takes its
+    /// metrics from the format of the content it starts at.
+    fn empty_line_metrics(&self, begin_index: usize) -> (f64, f64) {
+        self.runs
+            .iter()
+            .rev()
+            .find(|run| run.begin_index <= begin_index)
+            .or(self.runs.first())
+            .map_or((0.0, 0.0), |run| run.format.metrics())
+    }
+}
+
+/// Whether the line may be broken on either side of this character, as with CJK text.
+fn is_ideographic(character: char) -> bool {
+    matches!(
+        character,
+        '\u{3040}'..='\u{30FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{AC00}'..='\u{D7AF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{2FFFF}'
+    )
+}
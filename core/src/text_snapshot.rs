@@ -0,0 +1,190 @@
+//! Snapshots of the static text inside a container, backing `TextSnapshot` in both AVMs.
+
+use crate::display_object::{StaticTextChar, Text};
+use crate::library::Library;
+use crate::prelude::*;
+use gc_arena::Mutation;
+use std::ops::Range;
+
+/// A character of a text snapshot, along with the text it belongs to.
+pub struct SnapshotChar<'gc> {
+    text: Text<'gc>,
+
+    /// The index of this character within its text.
+    index_in_text: usize,
+
+    /// The character itself, positioned in the space of the snapshot's container.
+    pub char: StaticTextChar<'gc>,
+}
+
+/// The characters of all the static texts that are direct children of a container,
+/// indexed as a single string in render order.
+///
+/// A snapshot doesn't hold any state of its own: selections are stored on the texts,
+/// so a snapshot can be taken again whenever it's needed.
+#[derive(Default)]
+pub struct TextSnapshot<'gc> {
+    texts: Vec<Text<'gc>>,
+    chars: Vec<SnapshotChar<'gc>>,
+}
+
+impl<'gc> TextSnapshot<'gc> {
+    pub fn new(container: DisplayObjectContainer<'gc>, library: &Library<'gc>) -> Self {
+        let mut texts = Vec::new();
+        let mut chars = Vec::new();
+        for child in container.iter_render_list() {
+            let DisplayObject::Text(text) = child else {
+                continue;
+            };
+            let matrix = *text.base().matrix();
+            for (index_in_text, mut char) in text.chars(library).into_iter().enumerate() {
+                // The first character of each text begins a new line.
+                char.starts_line |= index_in_text == 0;
+                char.matrix = matrix * char.matrix;
+                char.corners = char.corners.map(|corner| matrix * corner);
+                chars.push(SnapshotChar {
+                    text,
+                    index_in_text,
+                    char,
+                });
+            }
+            texts.push(text);
+        }
+        Self { texts, chars }
+    }
+
+    pub fn char_count(&self) -> usize {
+        self.chars.len()
+    }
+
+    /// Converts the indices given to a `TextSnapshot` method into a range of characters.
+    ///
+    /// An end index that doesn't come after the begin index still covers one character.
+    pub fn range(&self, begin_index: i32, end_index: i32) -> Range<usize> {
+        let len = self.chars.len();
+        let begin = (begin_index.max(0) as usize).min(len);
+        let end = (end_index.max(begin_index.saturating_add(1)).max(0) as usize).clamp(begin, len);
+        begin..end
+    }
+
+    /// Returns the text of the given range, with line endings between characters on different lines if requested.
+    pub fn get_text(&self, range: Range<usize>, include_line_endings: bool) -> String {
+        self.collect_text(
+            self.chars[range].iter().map(|c| &c.char),
+            include_line_endings,
+        )
+    }
+
+    /// Returns the text of every selected character.
+    pub fn selected_text(&self, include_line_endings: bool) -> String {
+        self.collect_text(
+            self.chars
+                .iter()
+                .filter(|c| c.text.is_char_selected(c.index_in_text))
+                .map(|c| &c.char),
+            include_line_endings,
+        )
+    }
+
+    fn collect_text<'a>(
+        &self,
+        chars: impl Iterator<Item = &'a StaticTextChar<'gc>>,
+        include_line_endings: bool,
+    ) -> String
+    where
+        'gc: 'a,
+    {
+        let mut text = String::new();
+        for char in chars {
+            if include_line_endings && char.starts_line && !text.is_empty() {
+                text.push('\n');
+            }
+            text.push(char.character);
+        }
+        text
+    }
+
+    /// Finds the first occurrence of `needle` that starts at or after `begin`.
+    pub fn find_text(&self, begin: usize, needle: &str, case_sensitive: bool) -> Option<usize> {
+        let needle: Vec<char> = needle.chars().collect();
+        if needle.is_empty() || needle.len() > self.chars.len() {
+            return None;
+        }
+
+        let matches =
+            |a: char, b: char| a == b || (!case_sensitive && a.to_lowercase().eq(b.to_lowercase()));
+        (begin..=self.chars.len() - needle.len()).find(|&start| {
+            self.chars[start..start + needle.len()]
+                .iter()
+                .zip(&needle)
+                .all(|(c, &n)| matches(c.char.character, n))
+        })
+    }
+
+    /// Returns whether any of the characters in the given range are selected.
+    pub fn is_selected(&self, range: Range<usize>) -> bool {
+        self.chars[range]
+            .iter()
+            .any(|c| c.text.is_char_selected(c.index_in_text))
+    }
+
+    pub fn set_selected(&self, gc_context: &Mutation<'gc>, range: Range<usize>, selected: bool) {
+        // The characters of a text are contiguous, so select them a text at a time.
+        let mut chars = self.chars[range].iter().peekable();
+        while let Some(first) = chars.next() {
+            let mut end = first.index_in_text + 1;
+            while let Some(c) =
+                chars.next_if(|c| DisplayObject::ptr_eq(c.text.into(), first.text.into()))
+            {
+                end = c.index_in_text + 1;
+            }
+            first
+                .text
+                .set_chars_selected(gc_context, first.index_in_text..end, selected);
+        }
+    }
+
+    pub fn set_select_color(&self, gc_context: &Mutation<'gc>, color: Color) {
+        for text in &self.texts {
+            text.set_select_color(gc_context, color);
+        }
+    }
+
+    /// Returns the index of the character closest to `point`, given in the container's space,
+    /// if it's no further away than `max_distance`.
+    pub fn hit_test_near(&self, point: Point<Twips>, max_distance: Twips) -> Option<usize> {
+        let mut closest = None;
+        for (index, c) in self.chars.iter().enumerate() {
+            let mut bounds = Rectangle::default();
+            for corner in c.char.corners {
+                bounds = bounds.encompass(corner);
+            }
+
+            let dx = (bounds.x_min - point.x)
+                .max(point.x - bounds.x_max)
+                .max(Twips::ZERO);
+            let dy = (bounds.y_min - point.y)
+                .max(point.y - bounds.y_max)
+                .max(Twips::ZERO);
+            let distance = (dx.to_pixels().powi(2) + dy.to_pixels().powi(2)).sqrt();
+            if distance <= max_distance.to_pixels()
+                && closest.is_none_or(|(_, closest)| distance < closest)
+            {
+                closest = Some((index, distance));
+            }
+        }
+        closest.map(|(index, _)| index)
+    }
+
+    /// Returns the characters in the given range, with their indices and whether they're selected.
+    pub fn run_info(
+        &self,
+        range: Range<usize>,
+    ) -> impl Iterator<Item = (usize, bool, &StaticTextChar<'gc>)> {
+        let start = range.start;
+        self.chars[range]
+            .iter()
+            .enumerate()
+            .map(move |(i, c)| (start + i, c.text.is_char_selected(c.index_in_text), &c.char))
+    }
+}
@@ -12,8 +12,10 @@ use crate::avm2::{Activation as Avm2Activation, Object as Avm2Object, Value as A
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, TDisplayObject};
 use crate::string::AvmString;
+use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
 use std::collections::{binary_heap::PeekMut, BinaryHeap};
+use std::sync::Arc;
 
 /// Manages the collection of timers.
 pub struct Timers<'gc> {
@@ -225,12 +227,13 @@ impl<'gc> Timers<'gc> {
         self.timers.iter().any(|t| t.id == id)
     }
 
-    /// Registers a new timer and returns the timer ID.
+    /// Registers a new timer, created by the given movie, and returns the timer ID.
     pub fn add_timer(
         &mut self,
         callback: TimerCallback<'gc>,
         interval: i32,
         is_timeout: bool,
+        movie: Arc<SwfMovie>,
     ) -> i32 {
         // SANITY: Set a minimum interval so we don't spam too much.
         let interval = interval.max(Self::MIN_INTERVAL) as u64 * (Self::TIMER_SCALE as u64);
@@ -245,6 +248,7 @@ impl<'gc> Timers<'gc> {
             tick_time: self.cur_time + interval,
             interval,
             is_timeout,
+            movie,
         };
         self.timers.push(timer);
         id
@@ -264,8 +268,19 @@ impl<'gc> Timers<'gc> {
         self.timers.clear()
     }
 
+    /// Removes all timers created by the given movie, and returns their callbacks.
+    pub fn remove_for_movie(&mut self, movie: &Arc<SwfMovie>) -> Vec<TimerCallback<'gc>> {
+        let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.timers)
+            .into_iter()
+            .partition(|t| Arc::ptr_eq(&t.movie, movie));
+        self.timers = kept.into();
+        removed.into_iter().map(|t| t.callback).collect()
+    }
+
     /// Changes the delay of a timer.
-    pub fn set_delay(&mut self, id: i32, interval: i32) {
+    ///
+    /// Returns `false` if the timer doesn't exist anymore.
+    pub fn set_delay(&mut self, id: i32, interval: i32) -> bool {
         // SANITY: Set a minimum interval so we don't spam too much.
         let interval = interval.max(Self::MIN_INTERVAL) as u64 * (Self::TIMER_SCALE as u64);
 
@@ -278,14 +293,15 @@ impl<'gc> Timers<'gc> {
             }
         }
 
-        if let Some(mut timer) = timer {
-            self.remove(id);
-            timer.interval = interval;
-            timer.tick_time = self.cur_time + interval;
-            self.timers.push(timer);
-        } else {
-            panic!("Changing delay of non-existent timer");
-        }
+        // The timer may have been removed behind the movie's back, by unloading it.
+        let Some(mut timer) = timer else {
+            return false;
+        };
+        self.remove(id);
+        timer.interval = interval;
+        timer.tick_time = self.cur_time + interval;
+        self.timers.push(timer);
+        true
     }
 
     fn peek(&self) -> Option<&Timer<'gc>> {
@@ -334,6 +350,10 @@ pub struct Timer<'gc> {
 
     /// This timer only fires once if `is_timeout` is true.
     is_timeout: bool,
+
+    /// The movie that created this timer.
+    #[collect(require_static)]
+    movie: Arc<SwfMovie>,
 }
 
 // Implement `Ord` so that timers can be stored in the BinaryHeap (as a min-heap).
@@ -1,10 +1,11 @@
+use crate::backends::DesktopUiBackend;
 use crate::custom_event::RuffleEvent;
 use crate::gui::{GuiController, MENU_HEIGHT};
 use crate::player::{LaunchOptions, PlayerController};
 use crate::preferences::GlobalPreferences;
 use crate::util::{
-    get_screen_size, gilrs_button_to_gamepad_button, parse_url, plot_stats_in_tracy,
-    winit_to_ruffle_key_code, winit_to_ruffle_text_control,
+    get_screen_size, gilrs_axis_to_gamepad_axis, gilrs_button_to_gamepad_button, parse_url,
+    plot_stats_in_tracy, winit_to_ruffle_key_code, winit_to_ruffle_text_control,
 };
 use anyhow::Error;
 use gilrs::{Event, EventType, Gilrs};
@@ -35,6 +36,10 @@ struct MainWindow {
     preferred_height: Option<f64>,
     start_fullscreen: bool,
     loaded: LoadingState,
+
+    /// Whether the player was told about the gamepads connected before the movie was loaded.
+    gamepads_announced: bool,
+
     time: Instant,
     next_frame_time: Option<Instant>,
     event_loop_proxy: EventLoopProxy<RuffleEvent>,
@@ -48,9 +53,9 @@ impl MainWindow {
                 if let Some(mut player) = self.player.get() {
                     // Even if the movie is paused, user interaction with debug tools can change the render output
                     player.render();
-                    self.gui.render(Some(player));
+                    self.gui.render(event_loop, Some(player));
                 } else {
-                    self.gui.render(None);
+                    self.gui.render(event_loop, None);
                 }
                 plot_stats_in_tracy(&self.gui.descriptors().wgpu_instance);
             }
@@ -247,6 +252,8 @@ impl MainWindow {
     }
 
     fn on_metadata(&mut self, swf_header: HeaderExt) {
+        self.gamepads_announced = false;
+
         let height_offset = if self.gui.window().fullscreen().is_some() || self.no_gui {
             0.0
         } else {
@@ -340,23 +347,54 @@ impl MainWindow {
         }
     }
 
-    fn about_to_wait(&mut self, gilrs: Option<&mut Gilrs>) {
-        if let Some(Event { event, .. }) = gilrs.and_then(|gilrs| gilrs.next_event()) {
+    fn about_to_wait(&mut self, mut gilrs: Option<&mut Gilrs>) {
+        if !self.gamepads_announced && matches!(self.loaded, LoadingState::Loaded) {
+            if let Some(gilrs) = gilrs.as_deref() {
+                for (id, _) in gilrs.gamepads() {
+                    self.player
+                        .handle_event(PlayerEvent::GamepadConnected { gamepad: id.into() });
+                }
+            }
+            self.gamepads_announced = true;
+        }
+
+        if let Some(Event { id, event, .. }) = gilrs.and_then(|gilrs| gilrs.next_event()) {
+            let gamepad = id.into();
             match event {
                 EventType::ButtonPressed(button, _) => {
                     if let Some(button) = gilrs_button_to_gamepad_button(button) {
                         self.player
-                            .handle_event(PlayerEvent::GamepadButtonDown { button });
+                            .handle_event(PlayerEvent::GamepadButtonDown { gamepad, button });
                         self.check_redraw();
                     }
                 }
                 EventType::ButtonReleased(button, _) => {
                     if let Some(button) = gilrs_button_to_gamepad_button(button) {
                         self.player
-                            .handle_event(PlayerEvent::GamepadButtonUp { button });
+                            .handle_event(PlayerEvent::GamepadButtonUp { gamepad, button });
                         self.check_redraw();
                     }
                 }
+                EventType::AxisChanged(axis, value, _) => {
+                    if let Some(axis) = gilrs_axis_to_gamepad_axis(axis) {
+                        self.player.handle_event(PlayerEvent::GamepadAxisChanged {
+                            gamepad,
+                            axis,
+                            value: value.into(),
+                        });
+                        self.check_redraw();
+                    }
+                }
+                EventType::Connected => {
+                    self.player
+                        .handle_event(PlayerEvent::GamepadConnected { gamepad });
+                    self.check_redraw();
+                }
+                EventType::Disconnected => {
+                    self.player
+                        .handle_event(PlayerEvent::GamepadDisconnected { gamepad });
+                    self.check_redraw();
+                }
                 _ => {}
             }
         }
@@ -382,7 +420,14 @@ impl MainWindow {
 
     fn check_redraw(&self) {
         let player = self.player.get();
-        if player.map(|p| p.needs_render()).unwrap_or_default() || self.gui.needs_render() {
+        let player_needs_render = player.is_some_and(|p| {
+            // Animated cursors go through their frames when the window is redrawn.
+            p.needs_render()
+                || p.ui()
+                    .downcast_ref::<DesktopUiBackend>()
+                    .is_some_and(DesktopUiBackend::is_cursor_animated)
+        });
+        if player_needs_render || self.gui.needs_render() {
             self.gui.window().request_redraw();
         }
     }
@@ -503,6 +548,7 @@ impl ApplicationHandler<RuffleEvent> for App {
                 preferred_height,
                 start_fullscreen,
                 loaded,
+                gamepads_announced: false,
                 minimized: false,
                 mouse_pos: PhysicalPosition::new(0.0, 0.0),
                 modifiers: Modifiers::default(),
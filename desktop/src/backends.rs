@@ -1,10 +1,12 @@
 mod external_interface;
 mod fscommand;
+mod media_capture;
 mod navigator;
 mod ui;
 
 pub use external_interface::DesktopExternalInterfaceProvider;
 pub use fscommand::DesktopFSCommandProvider;
+pub use media_capture::DesktopMediaCaptureBackend;
 pub use navigator::DesktopNavigatorInterface;
 pub use navigator::PathAllowList;
 pub use ui::DesktopUiBackend;
@@ -0,0 +1,189 @@
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample};
+use rfd::{MessageButtons, MessageDialog, MessageDialogResult, MessageLevel};
+use ruffle_core::backend::media_capture::{
+    CameraMode, CapturedFrame, MediaCaptureBackend, Resampler,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A microphone being captured, whose samples are collected until the next poll.
+struct CapturingMicrophone {
+    _stream: cpal::Stream,
+    samples: Arc<Mutex<Vec<f32>>>,
+}
+
+/// Captures microphones through cpal.
+///
+/// Cameras aren't captured on desktop, so none are listed and movies see `Camera.names` as empty.
+pub struct DesktopMediaCaptureBackend {
+    microphones: Vec<cpal::Device>,
+    capturing: HashMap<usize, CapturingMicrophone>,
+
+    /// The microphones the user allowed the movie to capture.
+    allowed: HashSet<usize>,
+}
+
+impl DesktopMediaCaptureBackend {
+    pub fn new() -> Self {
+        let microphones = match cpal::default_host().input_devices() {
+            Ok(devices) => devices.collect(),
+            Err(e) => {
+                tracing::error!("Unable to list microphones: {}", e);
+                vec![]
+            }
+        };
+        Self {
+            microphones,
+            capturing: HashMap::new(),
+            allowed: HashSet::new(),
+        }
+    }
+
+    fn microphone_name(device: &cpal::Device) -> String {
+        device.name().unwrap_or_else(|_| "Microphone".to_string())
+    }
+
+    fn is_allowed(&mut self, index: usize, name: &str) -> bool {
+        if self.allowed.contains(&index) {
+            return true;
+        }
+
+        let allowed = MessageDialog::new()
+            .set_title("Allow microphone access?")
+            .set_level(MessageLevel::Info)
+            .set_description(format!(
+                "The content is trying to listen to your microphone \"{name}\".\n\nDo you want to allow it?"
+            ))
+            .set_buttons(MessageButtons::YesNo)
+            .show()
+            == MessageDialogResult::Yes;
+        if allowed {
+            self.allowed.insert(index);
+        } else {
+            tracing::info!("SWF tried to capture a microphone, but the user declined the request");
+        }
+        allowed
+    }
+
+    fn capture(
+        device: &cpal::Device,
+        sample_rate: u32,
+    ) -> Result<CapturingMicrophone, Box<dyn std::error::Error>> {
+        let config = device.default_input_config()?;
+        let sample_format = config.sample_format();
+        let config = cpal::StreamConfig::from(config);
+        let samples = Arc::new(Mutex::new(Vec::new()));
+
+        let stream = match sample_format {
+            SampleFormat::F32 => build_stream::<f32>(device, &config, sample_rate, &samples),
+            SampleFormat::I16 => build_stream::<i16>(device, &config, sample_rate, &samples),
+            SampleFormat::U16 => build_stream::<u16>(device, &config, sample_rate, &samples),
+            _ => return Err(format!("Unsupported sample format {sample_format:?}").into()),
+        }?;
+        stream.play()?;
+
+        Ok(CapturingMicrophone {
+            _stream: stream,
+            samples,
+        })
+    }
+}
+
+impl Default for DesktopMediaCaptureBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds a stream collecting the samples of a microphone, mixed down to mono.
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    sample_rate: u32,
+    samples: &Arc<Mutex<Vec<f32>>>,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let channels = usize::from(config.channels.max(1));
+    let mut resampler = Resampler::new(config.sample_rate.0, sample_rate);
+    // Samples nobody polls for are thrown away after a second.
+    let max_samples = sample_rate as usize;
+    let samples = samples.clone();
+
+    device.build_input_stream(
+        config,
+        move |data: &[T], _| {
+            let mono = data.chunks_exact(channels).map(|frame| {
+                frame.iter().map(|s| f32::from_sample_(*s)).sum::<f32>() / channels as f32
+            });
+            let mut samples = samples.lock().expect("Non-poisoned samples");
+            resampler.process(mono, &mut samples);
+            if samples.len() > max_samples {
+                let excess = samples.len() - max_samples;
+                samples.drain(..excess);
+            }
+        },
+        |err| tracing::error!("Microphone stream error: {}", err),
+        None,
+    )
+}
+
+impl MediaCaptureBackend for DesktopMediaCaptureBackend {
+    fn camera_names(&self) -> Vec<String> {
+        vec![]
+    }
+
+    fn start_camera(&mut self, _index: usize, _mode: CameraMode) -> Option<CameraMode> {
+        None
+    }
+
+    fn stop_camera(&mut self, _index: usize) {}
+
+    fn camera_frame(&mut self, _index: usize, _elapsed: Duration) -> Option<CapturedFrame> {
+        None
+    }
+
+    fn microphone_names(&self) -> Vec<String> {
+        self.microphones.iter().map(Self::microphone_name).collect()
+    }
+
+    fn start_microphone(&mut self, index: usize, sample_rate: u32) -> bool {
+        self.stop_microphone(index);
+
+        let Some(name) = self.microphones.get(index).map(Self::microphone_name) else {
+            return false;
+        };
+        if !self.is_allowed(index, &name) {
+            return false;
+        }
+
+        match Self::capture(&self.microphones[index], sample_rate) {
+            Ok(microphone) => {
+                self.capturing.insert(index, microphone);
+                true
+            }
+            Err(e) => {
+                tracing::error!("Unable to capture microphone: {}", e);
+                false
+            }
+        }
+    }
+
+    fn stop_microphone(&mut self, index: usize) {
+        // Dropping the stream stops it.
+        self.capturing.remove(&index);
+    }
+
+    fn microphone_samples(&mut self, index: usize, _elapsed: Duration) -> Vec<f32> {
+        self.capturing
+            .get(&index)
+            .map(|microphone| {
+                std::mem::take(&mut *microphone.samples.lock().expect("Non-poisoned samples"))
+            })
+            .unwrap_or_default()
+    }
+}
@@ -3,6 +3,7 @@ use crate::custom_event::RuffleEvent;
 use crate::gui::dialogs::message_dialog::MessageDialogConfiguration;
 use crate::gui::{DialogDescriptor, FilePicker, LocalizableText};
 use crate::preferences::GlobalPreferences;
+use crate::print::save_pages;
 use anyhow::Error;
 use chrono::{DateTime, Utc};
 use egui_winit::clipboard::Clipboard;
@@ -11,16 +12,19 @@ use rfd::{
     AsyncFileDialog, FileHandle, MessageButtons, MessageDialog, MessageDialogResult, MessageLevel,
 };
 use ruffle_core::backend::ui::{
+    ClipboardBitmap, ClipboardData, ClipboardFormat, CustomCursorId, CustomMouseCursor,
     DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend,
+    FullscreenError, LanguageIdentifier, MouseCursor, PrintedPage, UiBackend,
 };
+use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
-use tracing::error;
+use std::time::Instant;
+use tracing::{error, warn};
 use url::Url;
-use winit::event_loop::EventLoopProxy;
+use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
 use winit::raw_window_handle::HasDisplayHandle;
-use winit::window::{Fullscreen, Window};
+use winit::window::{CustomCursor, Fullscreen, Window};
 
 pub struct DesktopFileDialogResult {
     handle: Option<FileHandle>,
@@ -116,13 +120,53 @@ impl FileDialogResult for DesktopFileDialogResult {
     }
 }
 
+/// A cursor registered by the movie.
+struct RegisteredCursor {
+    cursor: CustomMouseCursor,
+
+    /// The frames of the cursor, which are only created once the event loop is available.
+    frames: Option<Vec<CustomCursor>>,
+}
+
+impl RegisteredCursor {
+    fn frames(&mut self, event_loop: &ActiveEventLoop) -> &[CustomCursor] {
+        let cursor = &self.cursor;
+        self.frames.get_or_insert_with(|| {
+            cursor
+                .frames
+                .iter()
+                .filter_map(|rgba| {
+                    CustomCursor::from_rgba(
+                        rgba.as_slice(),
+                        cursor.width as u16,
+                        cursor.height as u16,
+                        cursor.hot_spot.0 as u16,
+                        cursor.hot_spot.1 as u16,
+                    )
+                    .inspect_err(|e| error!("Couldn't create a custom cursor: {e}"))
+                    .ok()
+                })
+                .map(|source| event_loop.create_custom_cursor(source))
+                .collect()
+        })
+    }
+}
+
 pub struct DesktopUiBackend {
     window: Arc<Window>,
     event_loop: EventLoopProxy<RuffleEvent>,
     cursor_visible: bool,
     clipboard: Clipboard,
+
+    /// The clipboard used for the formats other than plain text, which egui's clipboard doesn't support.
+    /// This is missing if the system clipboard isn't available to arboard, like on Wayland without X11.
+    typed_clipboard: Option<arboard::Clipboard>,
     preferences: GlobalPreferences,
     preferred_cursor: MouseCursor,
+
+    /// When the preferred cursor was set, from which animated cursors go through their frames.
+    preferred_cursor_since: Instant,
+    custom_cursors: HashMap<CustomCursorId, RegisteredCursor>,
     font_database: Rc<fontdb::Database>,
     file_picker: FilePicker,
 }
@@ -144,13 +188,19 @@ impl DesktopUiBackend {
                 .ok()
                 .map(|handle| handle.as_raw()),
         );
+        let typed_clipboard = arboard::Clipboard::new()
+            .inspect_err(|e| warn!("Couldn't open the clipboard for formats other than text: {e}"))
+            .ok();
         Ok(Self {
             window,
             event_loop,
             cursor_visible: true,
             clipboard,
+            typed_clipboard,
             preferences,
             preferred_cursor: MouseCursor::Arrow,
+            preferred_cursor_since: Instant::now(),
+            custom_cursors: HashMap::new(),
             font_database,
             file_picker,
         })
@@ -163,11 +213,51 @@ impl DesktopUiBackend {
                 MouseCursor::Hand => egui::CursorIcon::PointingHand,
                 MouseCursor::IBeam => egui::CursorIcon::Text,
                 MouseCursor::Grab => egui::CursorIcon::Grab,
+                // Custom cursors are displayed by `custom_cursor`, this is only used if they couldn't be created.
+                MouseCursor::Custom(_) => egui::CursorIcon::Default,
             }
         } else {
             egui::CursorIcon::None
         }
     }
+
+    /// The frame of the custom cursor to display now instead of `cursor`, if the movie picked one.
+    pub fn custom_cursor(&mut self, event_loop: &ActiveEventLoop) -> Option<CustomCursor> {
+        let MouseCursor::Custom(id) = self.preferred_cursor else {
+            return None;
+        };
+        if !self.cursor_visible {
+            return None;
+        }
+
+        let registered = self.custom_cursors.get_mut(&id)?;
+        let frame_rate = registered.cursor.frame_rate;
+        let frames = registered.frames(event_loop);
+        if frames.is_empty() {
+            return None;
+        }
+        let elapsed = self.preferred_cursor_since.elapsed().as_secs_f64();
+        let frame = if frame_rate > 0.0 {
+            (elapsed * frame_rate) as usize % frames.len()
+        } else {
+            0
+        };
+        Some(frames[frame].clone())
+    }
+
+    /// Whether the displayed cursor is animated, which needs the window to be redrawn to go through its frames.
+    pub fn is_cursor_animated(&self) -> bool {
+        match self.preferred_cursor {
+            MouseCursor::Custom(id) => {
+                self.cursor_visible
+                    && self
+                        .custom_cursors
+                        .get(&id)
+                        .is_some_and(|registered| registered.cursor.frames.len() > 1)
+            }
+            _ => false,
+        }
+    }
 }
 
 impl UiBackend for DesktopUiBackend {
@@ -181,6 +271,25 @@ impl UiBackend for DesktopUiBackend {
 
     fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
         self.preferred_cursor = cursor;
+        self.preferred_cursor_since = Instant::now();
+    }
+
+    fn register_mouse_cursor(&mut self, id: CustomCursorId, cursor: CustomMouseCursor) {
+        self.custom_cursors.insert(
+            id,
+            RegisteredCursor {
+                cursor,
+                frames: None,
+            },
+        );
+    }
+
+    fn unregister_mouse_cursor(&mut self, id: CustomCursorId) {
+        self.custom_cursors.remove(&id);
+    }
+
+    fn supports_custom_mouse_cursors(&self) -> bool {
+        true
     }
 
     fn clipboard_content(&mut self) -> String {
@@ -191,6 +300,103 @@ impl UiBackend for DesktopUiBackend {
         self.clipboard.set(content);
     }
 
+    fn supports_clipboard_format(&self, format: ClipboardFormat) -> bool {
+        match format {
+            ClipboardFormat::Text => true,
+            ClipboardFormat::Html | ClipboardFormat::Bitmap => self.typed_clipboard.is_some(),
+            ClipboardFormat::RichText | ClipboardFormat::Url | ClipboardFormat::FileList => false,
+        }
+    }
+
+    fn clipboard_formats(&mut self) -> Vec<ClipboardFormat> {
+        let mut formats = vec![];
+        if !self.clipboard_content().is_empty() {
+            formats.push(ClipboardFormat::Text);
+        }
+        if let Some(clipboard) = &mut self.typed_clipboard {
+            if clipboard.get().html().is_ok() {
+                formats.push(ClipboardFormat::Html);
+            }
+            if clipboard
+                .get()
+                .file_list()
+                .is_ok_and(|files| !files.is_empty())
+            {
+                formats.push(ClipboardFormat::FileList);
+            }
+            if clipboard.get_image().is_ok() {
+                formats.push(ClipboardFormat::Bitmap);
+            }
+        }
+        formats
+    }
+
+    fn clipboard_data(&mut self, format: ClipboardFormat) -> Option<ClipboardData> {
+        if format == ClipboardFormat::Text {
+            return Some(self.clipboard_content())
+                .filter(|text| !text.is_empty())
+                .map(ClipboardData::Text);
+        }
+
+        let clipboard = self.typed_clipboard.as_mut()?;
+        match format {
+            ClipboardFormat::Html => clipboard.get().html().ok().map(ClipboardData::Html),
+            ClipboardFormat::FileList => clipboard
+                .get()
+                .file_list()
+                .ok()
+                .filter(|files| !files.is_empty())
+                .map(|files| {
+                    ClipboardData::FileList(
+                        files
+                            .iter()
+                            .map(|path| path.to_string_lossy().into_owned())
+                            .collect(),
+                    )
+                }),
+            ClipboardFormat::Bitmap => clipboard.get_image().ok().map(|image| {
+                ClipboardData::Bitmap(ClipboardBitmap {
+                    width: image.width as u32,
+                    height: image.height as u32,
+                    rgba: image.bytes.into_owned(),
+                })
+            }),
+            _ => None,
+        }
+    }
+
+    fn set_clipboard_data(&mut self, data: Vec<ClipboardData>) {
+        let mut text = None;
+        let mut html = None;
+        let mut bitmap = None;
+        for data in data {
+            match data {
+                ClipboardData::Text(data) => text = Some(data),
+                ClipboardData::Html(data) => html = Some(data),
+                ClipboardData::Bitmap(data) => bitmap = Some(data),
+                _ => {}
+            }
+        }
+
+        // Every write replaces the whole clipboard, and only HTML can be written along with text.
+        // So HTML takes precedence over a bitmap, and a bitmap is written on its own.
+        let result = match (&mut self.typed_clipboard, html, bitmap) {
+            (Some(clipboard), Some(html), _) => clipboard.set_html(html, text),
+            (Some(clipboard), None, Some(bitmap)) => clipboard.set_image(arboard::ImageData {
+                width: bitmap.width as usize,
+                height: bitmap.height as usize,
+                bytes: bitmap.rgba.into(),
+            }),
+            _ => {
+                self.clipboard.set(text.unwrap_or_default());
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            error!("Couldn't set the clipboard data: {e}");
+        }
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
         self.window.set_fullscreen(if is_full {
             Some(Fullscreen::Borderless(None))
@@ -373,4 +579,29 @@ impl UiBackend for DesktopUiBackend {
     }
 
     fn close_file_dialog(&mut self) {}
+
+    fn print_pages(&mut self, pages: Vec<PrintedPage>) {
+        if pages.is_empty() {
+            return;
+        }
+
+        let dialog = AsyncFileDialog::new()
+            .set_title("Save printed pages")
+            .set_file_name("print.pdf")
+            .add_filter("PDF document", &["pdf"])
+            .add_filter("PNG image", &["png"]);
+
+        let Some(result) = self.file_picker.show_dialog(dialog, |d| d.save_file()) else {
+            tracing::warn!("Couldn't show a dialog to save the printed pages");
+            return;
+        };
+
+        tokio::spawn(async move {
+            if let Some(handle) = result.await {
+                if let Err(e) = save_pages(handle.path(), &pages) {
+                    error!("Couldn't save printed pages to {:?}: {}", handle.path(), e);
+                }
+            }
+        });
+    }
 }
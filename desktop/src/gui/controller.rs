@@ -19,12 +19,16 @@ use url::Url;
 use wgpu::SurfaceError;
 use winit::dpi::PhysicalSize;
 use winit::event::WindowEvent;
-use winit::event_loop::EventLoopProxy;
+use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
 use winit::keyboard::{Key, NamedKey};
 use winit::window::{Theme, Window};
 
 use super::{DialogDescriptor, FilePicker};
 
+/// The cursor egui is told to display while a custom cursor is displayed instead.
+/// It's otherwise unused, so that egui sets the window cursor again once the custom cursor is gone.
+const CUSTOM_CURSOR_STAND_IN: egui::CursorIcon = egui::CursorIcon::Alias;
+
 /// Integration layer connecting wgpu+winit to egui.
 pub struct GuiController {
     descriptors: Arc<Descriptors>,
@@ -255,7 +259,7 @@ impl GuiController {
         );
     }
 
-    pub fn render(&mut self, mut player: Option<MutexGuard<Player>>) {
+    pub fn render(&mut self, event_loop: &ActiveEventLoop, mut player: Option<MutexGuard<Player>>) {
         let surface_texture = match self.surface.get_current_texture() {
             Ok(surface_texture) => surface_texture,
             Err(e @ (SurfaceError::Lost | SurfaceError::Outdated)) => {
@@ -305,17 +309,28 @@ impl GuiController {
             .repaint_delay;
 
         // If we're not in a UI, tell egui which cursor we prefer to use instead
+        let mut custom_cursor = None;
         if !self.egui_winit.egui_ctx().wants_pointer_input() {
-            if let Some(player) = player.as_deref() {
-                full_output.platform_output.cursor_icon = player
-                    .ui()
-                    .downcast_ref::<DesktopUiBackend>()
-                    .unwrap_or_else(|| panic!("UI Backend should be DesktopUiBackend"))
-                    .cursor();
+            if let Some(player) = player.as_deref_mut() {
+                let ui = player
+                    .ui_mut()
+                    .downcast_mut::<DesktopUiBackend>()
+                    .unwrap_or_else(|| panic!("UI Backend should be DesktopUiBackend"));
+                custom_cursor = ui.custom_cursor(event_loop);
+                full_output.platform_output.cursor_icon = if custom_cursor.is_some() {
+                    CUSTOM_CURSOR_STAND_IN
+                } else {
+                    ui.cursor()
+                };
             }
         }
         self.egui_winit
             .handle_platform_output(&self.window, full_output.platform_output);
+        // egui only sets the window cursor when its own changes, which includes the pointer
+        // coming back into the window, so the custom cursor is set again on every frame.
+        if let Some(custom_cursor) = custom_cursor {
+            self.window.set_cursor(custom_cursor);
+        }
 
         let clipped_primitives = self
             .egui_winit
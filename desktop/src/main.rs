@@ -15,6 +15,7 @@ mod gui;
 mod log;
 mod player;
 mod preferences;
+mod print;
 #[cfg(feature = "tracy")]
 mod tracy;
 mod util;
@@ -1,6 +1,6 @@
 use crate::backends::{
-    DesktopExternalInterfaceProvider, DesktopFSCommandProvider, DesktopNavigatorInterface,
-    DesktopUiBackend,
+    DesktopExternalInterfaceProvider, DesktopFSCommandProvider, DesktopMediaCaptureBackend,
+    DesktopNavigatorInterface, DesktopUiBackend,
 };
 use crate::cli::FilesystemAccessMode;
 use crate::cli::GameModePreference;
@@ -307,6 +307,7 @@ impl ActivePlayer {
                 )
                 .expect("Couldn't create ui backend"),
             )
+            .with_media_capture(DesktopMediaCaptureBackend::new())
             .with_autoplay(true)
             .with_letterbox(opt.player.letterbox.unwrap_or(Letterbox::On))
             .with_max_execution_duration(opt.player.max_execution_duration.unwrap_or(Duration::MAX))
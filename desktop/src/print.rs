@@ -0,0 +1,159 @@
+//! Saving the pages printed by a movie, either as a PDF document or as PNG images.
+
+use anyhow::{anyhow, Error};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use image::RgbaImage;
+use ruffle_core::backend::ui::PrintedPage;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Saves printed pages to the given path, in the format matching its extension.
+///
+/// Every page ends up in a single PDF document, while each page gets its own PNG image.
+pub fn save_pages(path: &Path, pages: &[PrintedPage]) -> Result<(), Error> {
+    let is_png = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+
+    if is_png {
+        for (index, page) in pages.iter().enumerate() {
+            let image = RgbaImage::from_raw(page.width, page.height, page.rgba.clone())
+                .ok_or_else(|| anyhow!("Page {} has an invalid size", index + 1))?;
+            image.save(png_path(path, index, pages.len()))?;
+        }
+        Ok(())
+    } else {
+        std::fs::write(path, write_pdf(pages)?)?;
+        Ok(())
+    }
+}
+
+/// The path of a page image, numbered when there are several pages.
+fn png_path(path: &Path, index: usize, page_count: usize) -> PathBuf {
+    if page_count <= 1 {
+        return path.to_owned();
+    }
+
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{stem}-{}.png", index + 1))
+}
+
+/// Writes a PDF document with each page as a single image filling it.
+fn write_pdf(pages: &[PrintedPage]) -> Result<Vec<u8>, Error> {
+    let mut pdf = PdfWriter::default();
+
+    // Objects 1 and 2 are the catalog and the page tree, and each page uses three more objects:
+    // the page itself, its content stream and its image.
+    let page_id = |index: usize| 3 + index * 3;
+    let kids = (0..pages.len())
+        .map(|index| format!("{} 0 R", page_id(index)))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    pdf.object(b"<< /Type /Catalog /Pages 2 0 R >>");
+    pdf.object(format!("<< /Type /Pages /Kids [{kids}] /Count {} >>", pages.len()).as_bytes());
+
+    for (index, page) in pages.iter().enumerate() {
+        let id = page_id(index);
+
+        // Page sizes are given in points, of which there are 72 per inch.
+        let width = page.width as f64 * 72.0 / page.dpi;
+        let height = page.height as f64 * 72.0 / page.dpi;
+
+        pdf.object(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {width:.2} {height:.2}] \
+                 /Resources << /XObject << /Im0 {} 0 R >> >> /Contents {} 0 R >>",
+                id + 2,
+                id + 1
+            )
+            .as_bytes(),
+        );
+        pdf.stream(
+            "",
+            format!("q {width:.2} 0 0 {height:.2} 0 0 cm /Im0 Do Q").as_bytes(),
+        )?;
+
+        // PDF images have no alpha channel, and pages are opaque anyway.
+        let rgb = page
+            .rgba
+            .chunks_exact(4)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+            .collect::<Vec<_>>();
+        pdf.stream(
+            &format!(
+                "/Type /XObject /Subtype /Image /Width {} /Height {} \
+                 /ColorSpace /DeviceRGB /BitsPerComponent 8",
+                page.width, page.height
+            ),
+            &rgb,
+        )?;
+    }
+
+    Ok(pdf.finish())
+}
+
+/// A minimal writer for PDF documents, which are a list of objects followed by a table of
+/// where each object starts.
+#[derive(Default)]
+struct PdfWriter {
+    data: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl PdfWriter {
+    fn begin_object(&mut self) {
+        if self.data.is_empty() {
+            self.data.extend_from_slice(b"%PDF-1.4\n");
+        }
+        self.offsets.push(self.data.len());
+        self.data
+            .extend_from_slice(format!("{} 0 obj\n", self.offsets.len()).as_bytes());
+    }
+
+    fn object(&mut self, contents: &[u8]) {
+        self.begin_object();
+        self.data.extend_from_slice(contents);
+        self.data.extend_from_slice(b"\nendobj\n");
+    }
+
+    /// Writes a stream object, compressing its contents.
+    fn stream(&mut self, dictionary: &str, contents: &[u8]) -> Result<(), Error> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(contents)?;
+        let contents = encoder.finish()?;
+
+        self.begin_object();
+        self.data.extend_from_slice(
+            format!(
+                "<< {dictionary} /Filter /FlateDecode /Length {} >>\nstream\n",
+                contents.len()
+            )
+            .as_bytes(),
+        );
+        self.data.extend_from_slice(&contents);
+        self.data.extend_from_slice(b"\nendstream\nendobj\n");
+        Ok(())
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let xref_offset = self.data.len();
+        let object_count = self.offsets.len() + 1;
+
+        self.data
+            .extend_from_slice(format!("xref\n0 {object_count}\n").as_bytes());
+        self.data.extend_from_slice(b"0000000000 65535 f \n");
+        for offset in &self.offsets {
+            self.data
+                .extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+        }
+        self.data.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {object_count} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n"
+            )
+            .as_bytes(),
+        );
+        self.data
+    }
+}
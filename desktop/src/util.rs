@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Error};
-use gilrs::Button;
-use ruffle_core::events::{GamepadButton, KeyCode, TextControlCode};
+use gilrs::{Axis, Button};
+use ruffle_core::events::{GamepadAxis, GamepadButton, KeyCode, TextControlCode};
 use std::path::Path;
 use url::Url;
 use winit::dpi::PhysicalSize;
@@ -200,6 +200,17 @@ pub fn gilrs_button_to_gamepad_button(button: Button) -> Option<GamepadButton> {
     }
 }
 
+pub fn gilrs_axis_to_gamepad_axis(axis: Axis) -> Option<GamepadAxis> {
+    match axis {
+        Axis::LeftStickX => Some(GamepadAxis::LeftStickX),
+        Axis::LeftStickY => Some(GamepadAxis::LeftStickY),
+        Axis::RightStickX => Some(GamepadAxis::RightStickX),
+        Axis::RightStickY => Some(GamepadAxis::RightStickY),
+        // The triggers are reported as buttons, and the D-pad axes duplicate its buttons.
+        _ => None,
+    }
+}
+
 pub fn get_screen_size(window: &Window) -> PhysicalSize<u32> {
     let mut min_x = 0;
     let mut min_y = 0;
@@ -0,0 +1,174 @@
+use anyhow::Result;
+use ruffle_core::backend::audio::{
+    swf, AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
+    SoundStreamInfo, SoundTransform,
+};
+use ruffle_core::impl_audio_mixer_backend;
+use std::fs::File;
+use std::io::{BufWriter, Seek, Write};
+use std::path::Path;
+
+/// An audio backend that records everything the movie plays, instead of sending it to a device.
+///
+/// Audio is only mixed when the backend is ticked, with each tick mixing exactly one frame's worth
+/// of samples, so the recording stays in step with the frames run by the player.
+pub struct CaptureAudioBackend {
+    mixer: AudioMixer,
+
+    /// The number of samples (per channel) that make up one frame.
+    samples_per_frame: f64,
+
+    /// The fraction of a sample left over from previous frames.
+    sample_remainder: f64,
+
+    buffer: Vec<i16>,
+    samples: Vec<i16>,
+}
+
+impl Default for CaptureAudioBackend {
+    fn default() -> Self {
+        Self {
+            mixer: AudioMixer::new(Self::NUM_CHANNELS, Self::SAMPLE_RATE),
+            samples_per_frame: 0.0,
+            sample_remainder: 0.0,
+            buffer: vec![],
+            samples: vec![],
+        }
+    }
+}
+
+impl CaptureAudioBackend {
+    const NUM_CHANNELS: u8 = 2;
+    const SAMPLE_RATE: u32 = 44100;
+
+    /// Throws away everything recorded so far, such as the audio of skipped frames.
+    pub fn discard(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Takes the interleaved samples recorded so far.
+    pub fn take_samples(&mut self) -> Vec<i16> {
+        std::mem::take(&mut self.samples)
+    }
+}
+
+impl AudioBackend for CaptureAudioBackend {
+    impl_audio_mixer_backend!(mixer);
+    fn play(&mut self) {}
+    fn pause(&mut self) {}
+
+    fn set_frame_rate(&mut self, frame_rate: f64) {
+        self.samples_per_frame = Self::SAMPLE_RATE as f64 / frame_rate;
+    }
+
+    fn tick(&mut self) {
+        // Frames rarely last a whole number of samples, so carry the remainder over
+        // to keep the recording from drifting away from the frames.
+        let samples = self.samples_per_frame + self.sample_remainder;
+        self.sample_remainder = samples.fract();
+
+        self.buffer
+            .resize(samples as usize * Self::NUM_CHANNELS as usize, 0);
+        self.mixer.mix::<i16>(self.buffer.as_mut());
+        self.samples.extend_from_slice(&self.buffer);
+    }
+}
+
+/// Writes interleaved samples recorded by a `CaptureAudioBackend` to a WAV file.
+pub fn write_wav(samples: &[i16], path: &Path) -> Result<()> {
+    write_wav_to(samples, BufWriter::new(File::create(path)?))
+}
+
+fn write_wav_to(samples: &[i16], writer: impl Write + Seek) -> Result<()> {
+    let spec = hound::WavSpec {
+        channels: CaptureAudioBackend::NUM_CHANNELS.into(),
+        sample_rate: CaptureAudioBackend::SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::new(writer, spec)?;
+    for sample in samples {
+        writer.write_sample(*sample)?;
+    }
+    writer.finalize()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Records three frames at 24 fps of a sound that holds each channel at a constant level.
+    fn record_constant_sound() -> Vec<i16> {
+        let data: Vec<u8> = [1000i16, -2000]
+            .repeat(44100)
+            .into_iter()
+            .flat_map(i16::to_le_bytes)
+            .collect();
+        let sound = swf::Sound {
+            id: 1,
+            format: swf::SoundFormat {
+                compression: swf::AudioCompression::Uncompressed,
+                sample_rate: 44100,
+                is_stereo: true,
+                is_16_bit: true,
+            },
+            num_samples: 44100,
+            data: &data,
+        };
+
+        let mut backend = CaptureAudioBackend::default();
+        backend.set_frame_rate(24.0);
+        let handle = backend.register_sound(&sound).unwrap();
+        backend
+            .start_sound(
+                handle,
+                &swf::SoundInfo {
+                    event: swf::SoundEvent::Event,
+                    in_sample: None,
+                    out_sample: None,
+                    num_loops: 1,
+                    envelope: None,
+                },
+            )
+            .unwrap();
+        for _ in 0..3 {
+            backend.tick();
+        }
+        backend.take_samples()
+    }
+
+    #[test]
+    fn capture_in_step_with_frames() {
+        let samples = record_constant_sound();
+
+        // A frame at 24 fps lasts 1837.5 samples, so the frames alternate between 1837 and 1838.
+        assert_eq!(samples.len(), (1837 + 1838 + 1837) * 2);
+        for frame in samples.chunks(2) {
+            assert!((frame[0] - 1000).abs() <= 1, "left sample {}", frame[0]);
+            assert!((frame[1] + 2000).abs() <= 1, "right sample {}", frame[1]);
+        }
+    }
+
+    #[test]
+    fn wav_round_trip() {
+        let samples = record_constant_sound();
+        let mut bytes = Cursor::new(Vec::new());
+        write_wav_to(&samples, &mut bytes).unwrap();
+
+        let bytes = bytes.into_inner();
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(&bytes[8..12], b"WAVE");
+
+        let reader = hound::WavReader::new(Cursor::new(bytes)).unwrap();
+        let spec = reader.spec();
+        assert_eq!(spec.channels, 2);
+        assert_eq!(spec.sample_rate, 44100);
+        assert_eq!(spec.bits_per_sample, 16);
+        assert_eq!(spec.sample_format, hound::SampleFormat::Int);
+        assert_eq!(reader.duration(), 1837 + 1838 + 1837);
+        let read: Vec<i16> = reader.into_samples().map(Result::unwrap).collect();
+        assert_eq!(read, samples);
+    }
+}
@@ -0,0 +1,282 @@
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+use std::fs::{create_dir_all, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// How the captured frames of a movie are written out.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// A PNG file, or a directory of them if there is more than one frame
+    #[default]
+    Png,
+
+    /// A single animated PNG file
+    Apng,
+
+    /// A single animated GIF file
+    Gif,
+
+    /// A directory of PNG files, along with a `metadata.json` describing their timing
+    Sequence,
+}
+
+impl OutputFormat {
+    /// Whether this format writes the given number of frames to a single file,
+    /// rather than to a directory.
+    pub fn is_single_file(self, frames: u32) -> bool {
+        match self {
+            OutputFormat::Png => frames == 1,
+            OutputFormat::Apng | OutputFormat::Gif => true,
+            OutputFormat::Sequence => false,
+        }
+    }
+
+    /// The extension of the file written by this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Png | OutputFormat::Apng | OutputFormat::Sequence => "png",
+            OutputFormat::Gif => "gif",
+        }
+    }
+}
+
+/// The playback timing of a captured animation.
+#[derive(Debug, Clone, Copy)]
+pub struct Timing {
+    /// The number of frames per second.
+    pub frame_rate: f64,
+
+    /// The number of times the animation plays, or 0 to play forever.
+    pub loops: u32,
+}
+
+impl Timing {
+    /// The duration of a frame in seconds, as a fraction that fits in 16 bits.
+    /// Frame rates are given to a hundredth of a frame per second.
+    fn frame_delay(&self) -> (u16, u16) {
+        let denominator = (self.frame_rate * 100.0)
+            .round()
+            .clamp(1.0, u16::MAX as f64);
+        (100, denominator as u16)
+    }
+}
+
+/// Writes captured frames to `output` in the given format.
+pub fn write_frames(
+    format: OutputFormat,
+    frames: &[RgbaImage],
+    timing: Timing,
+    output: &Path,
+) -> Result<()> {
+    if format.is_single_file(frames.len() as u32) {
+        if let Some(parent) = output.parent() {
+            let _ = create_dir_all(parent);
+        }
+        let mut file = BufWriter::new(File::create(output)?);
+        write_single_file(format, frames, timing, &mut file)?;
+        file.flush()?;
+        return Ok(());
+    }
+
+    let _ = create_dir_all(output);
+    for (frame, image) in frames.iter().enumerate() {
+        image.save(output.join(format!("{frame}.png")))?;
+    }
+    if format == OutputFormat::Sequence {
+        write_sequence_metadata(frames, timing, output)?;
+    }
+    Ok(())
+}
+
+/// Writes captured frames as a single file, for formats where `is_single_file` holds.
+pub fn write_single_file(
+    format: OutputFormat,
+    frames: &[RgbaImage],
+    timing: Timing,
+    writer: &mut impl Write,
+) -> Result<()> {
+    match format {
+        OutputFormat::Png => {
+            let image = frames
+                .first()
+                .ok_or_else(|| anyhow!("No frames to write"))?;
+            let mut bytes: Vec<u8> = Vec::new();
+            image.write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::Png,
+            )?;
+            writer.write_all(&bytes)?;
+        }
+        OutputFormat::Apng => write_apng(frames, timing, writer)?,
+        OutputFormat::Gif => write_gif(frames, timing, writer)?,
+        OutputFormat::Sequence => return Err(anyhow!("Sequences can't be written to a file")),
+    }
+    Ok(())
+}
+
+fn write_apng(frames: &[RgbaImage], timing: Timing, writer: &mut impl Write) -> Result<()> {
+    let first = frames
+        .first()
+        .ok_or_else(|| anyhow!("No frames to write"))?;
+    let mut encoder = png::Encoder::new(writer, first.width(), first.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, timing.loops)?;
+    let (numerator, denominator) = timing.frame_delay();
+    encoder.set_frame_delay(numerator, denominator)?;
+
+    let mut writer = encoder.write_header()?;
+    for image in frames {
+        writer.write_image_data(image.as_raw())?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+fn write_gif(frames: &[RgbaImage], timing: Timing, writer: &mut impl Write) -> Result<()> {
+    let mut encoder = GifEncoder::new(writer);
+    // GIF counts the times the animation repeats after the first play.
+    encoder.set_repeat(match timing.loops {
+        0 => Repeat::Infinite,
+        loops => Repeat::Finite((loops - 1).min(u16::MAX as u32) as u16),
+    })?;
+
+    let (numerator, denominator) = timing.frame_delay();
+    let delay = Delay::from_numer_denom_ms(u32::from(numerator) * 1000, u32::from(denominator));
+    encoder.encode_frames(
+        frames
+            .iter()
+            .map(|image| Frame::from_parts(image.clone(), 0, 0, delay)),
+    )?;
+    Ok(())
+}
+
+/// Describes the frames of a sequence, which are written next to it as `{frame}.png`.
+fn sequence_metadata(frames: &[RgbaImage], timing: Timing) -> serde_json::Value {
+    let (width, height) = frames
+        .first()
+        .map(|image| image.dimensions())
+        .unwrap_or_default();
+    serde_json::json!({
+        "width": width,
+        "height": height,
+        "frame_rate": timing.frame_rate,
+        "loops": timing.loops,
+        "frames": frames
+            .iter()
+            .enumerate()
+            .map(|(frame, _)| {
+                serde_json::json!({
+                    "file": format!("{frame}.png"),
+                    "time": frame as f64 / timing.frame_rate,
+                })
+            })
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn write_sequence_metadata(frames: &[RgbaImage], timing: Timing, output: &Path) -> Result<()> {
+    let file = BufWriter::new(File::create(output.join("metadata.json"))?);
+    serde_json::to_writer_pretty(file, &sequence_metadata(frames, timing))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::gif::GifDecoder;
+    use image::{AnimationDecoder, Rgba};
+    use std::io::Cursor;
+
+    fn frames(count: u8) -> Vec<RgbaImage> {
+        (0..count)
+            .map(|i| RgbaImage::from_pixel(4, 3, Rgba([i * 50, 0, 0, 255])))
+            .collect()
+    }
+
+    fn encode(format: OutputFormat, frame_rate: f64, loops: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_single_file(format, &frames(3), Timing { frame_rate, loops }, &mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn frame_delay() {
+        let delay = |frame_rate| {
+            Timing {
+                frame_rate,
+                loops: 0,
+            }
+            .frame_delay()
+        };
+        assert_eq!(delay(24.0), (100, 2400));
+        assert_eq!(delay(29.97), (100, 2997));
+        assert_eq!(delay(0.001), (100, 1));
+        assert_eq!(delay(1000.0), (100, u16::MAX));
+    }
+
+    #[test]
+    fn apng_timing() {
+        let bytes = encode(OutputFormat::Apng, 12.5, 2);
+        let reader = png::Decoder::new(Cursor::new(bytes)).read_info().unwrap();
+        let animation = reader.info().animation_control.unwrap();
+        assert_eq!(animation.num_frames, 3);
+        assert_eq!(animation.num_plays, 2);
+        let frame = reader.info().frame_control.unwrap();
+        assert_eq!((frame.delay_num, frame.delay_den), (100, 1250));
+    }
+
+    #[test]
+    fn gif_timing() {
+        /// Finds the repeat count of the looping extension.
+        fn repeat(bytes: &[u8]) -> Option<u16> {
+            let position = bytes.windows(11).position(|w| w == b"NETSCAPE2.0")?;
+            let count = &bytes[position + 13..position + 15];
+            Some(u16::from_le_bytes([count[0], count[1]]))
+        }
+
+        let bytes = encode(OutputFormat::Gif, 25.0, 0);
+        assert_eq!(repeat(&bytes), Some(0));
+        let decoded = GifDecoder::new(Cursor::new(bytes))
+            .unwrap()
+            .into_frames()
+            .collect_frames()
+            .unwrap();
+        assert_eq!(decoded.len(), 3);
+        for frame in decoded {
+            assert_eq!(frame.delay().numer_denom_ms(), (40, 1));
+        }
+
+        // GIF stores the number of times the animation repeats after playing once.
+        assert_eq!(repeat(&encode(OutputFormat::Gif, 25.0, 3)), Some(2));
+    }
+
+    #[test]
+    fn sequence_metadata_describes_frames() {
+        let timing = Timing {
+            frame_rate: 4.0,
+            loops: 1,
+        };
+        assert_eq!(
+            sequence_metadata(&frames(3), timing),
+            serde_json::json!({
+                "width": 4,
+                "height": 3,
+                "frame_rate": 4.0,
+                "loops": 1,
+                "frames": [
+                    { "file": "0.png", "time": 0.0 },
+                    { "file": "1.png", "time": 0.25 },
+                    { "file": "2.png", "time": 0.5 },
+                ],
+            })
+        );
+        assert_eq!(
+            sequence_metadata(&[], timing)["frames"],
+            serde_json::json!([])
+        );
+    }
+}
@@ -1,17 +1,23 @@
+mod audio;
+mod format;
+
+use crate::audio::{write_wav, CaptureAudioBackend};
+use crate::format::{write_frames, write_single_file, OutputFormat, Timing};
 use anyhow::{anyhow, Result};
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
 use image::RgbaImage;
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::PlayerBuilder;
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::descriptors::Descriptors;
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::wgpu;
-use std::fs::create_dir_all;
 use std::io::{self, Write};
 use std::panic::catch_unwind;
 use std::path::{Path, PathBuf};
@@ -56,6 +62,24 @@ struct Opt {
     #[clap(long = "skipframes", default_value = "0")]
     skipframes: u32,
 
+    /// How to write the captured frames
+    #[clap(long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// Optionally override the frame rate of animated output.
+    /// By default, the frame rate of the movie is used.
+    /// Can't be combined with --audio, as the sound is recorded at the frame rate of the movie.
+    #[clap(long = "fps", value_parser(parse_frame_rate), conflicts_with = "audio")]
+    frame_rate: Option<f64>,
+
+    /// Number of times animated output plays, or 0 to play forever
+    #[clap(long = "loops", default_value = "0")]
+    loops: u32,
+
+    /// Also record the sound of the movie, and save it as a WAV file next to the captured frames
+    #[clap(long, action)]
+    audio: bool,
+
     /// Don't show a progress bar
     #[clap(short, long, action)]
     silent: bool,
@@ -76,19 +100,89 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// Render on the CPU instead of the GPU. This is slower, but works without any graphics device.
+    #[clap(long, action)]
+    software: bool,
+}
+
+fn parse_frame_rate(value: &str) -> Result<f64> {
+    let frame_rate: f64 = value.parse()?;
+    if frame_rate > 0.0 && frame_rate.is_finite() {
+        Ok(frame_rate)
+    } else {
+        Err(anyhow!("The frame rate must be greater than 0"))
+    }
+}
+
+/// The frame rate used for movies with a frame rate of 0, the same default as the player's.
+const DEFAULT_FRAME_RATE: f64 = 12.0;
+
+/// Returns the frame rate a movie is captured at, along with the frame rate the player
+/// has to be forced to run at if it differs from the movie's own.
+fn movie_frame_rate(frame_rate: f64) -> (f64, Option<f64>) {
+    // A frame rate of 0 would make every frame last forever, in the output and in the recorded audio.
+    if frame_rate > 0.0 {
+        (frame_rate, None)
+    } else {
+        (DEFAULT_FRAME_RATE, Some(DEFAULT_FRAME_RATE))
+    }
+}
+
+/// The frames captured from a movie.
+struct Capture {
+    frames: Vec<RgbaImage>,
+
+    /// The frame rate of the movie.
+    frame_rate: f64,
+
+    /// The interleaved stereo samples played during the captured frames, if recorded.
+    audio: Option<Vec<i16>>,
+}
+
+impl Capture {
+    fn timing(&self, opt: &Opt) -> Timing {
+        Timing {
+            frame_rate: opt.frame_rate.unwrap_or(self.frame_rate),
+            loops: opt.loops,
+        }
+    }
+
+    /// Writes the recorded audio, if any, next to frames written to `output`.
+    fn write_audio(&self, format: OutputFormat, output: &Path) -> Result<()> {
+        if let Some(samples) = &self.audio {
+            let path = if format.is_single_file(self.frames.len() as u32) {
+                output.with_extension("wav")
+            } else {
+                output.join("audio.wav")
+            };
+            write_wav(samples, &path)?;
+        }
+        Ok(())
+    }
+}
+
+/// The render backend that frames are captured with.
+#[derive(Clone)]
+enum Renderer {
+    Wgpu(Arc<Descriptors>),
+    Software,
 }
 
 /// Captures a screenshot. The resulting image uses straight alpha
+#[allow(clippy::too_many_arguments)]
 fn take_screenshot(
-    descriptors: Arc<Descriptors>,
+    renderer: Renderer,
     swf_path: &Path,
     frames: u32,
     skipframes: u32,
     progress: &Option<ProgressBar>,
     size: SizeOpt,
     skip_unsupported: bool,
-) -> Result<Vec<RgbaImage>> {
+    audio: bool,
+) -> Result<Capture> {
     let movie = SwfMovie::from_path(swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
+    let (frame_rate, forced_frame_rate) = movie_frame_rate(movie.frame_rate().to_f64());
 
     if movie.is_action_script_3() && skip_unsupported {
         return Err(anyhow!("Skipping unsupported movie"));
@@ -106,13 +200,26 @@ fn take_screenshot(
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height))
-        .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
-        .with_renderer(
-            WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
-        )
+    let builder = match renderer {
+        Renderer::Wgpu(descriptors) => {
+            let target = TextureTarget::new(&descriptors.device, (width, height))
+                .map_err(|e| anyhow!(e.to_string()))?;
+            PlayerBuilder::new().with_renderer(
+                WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
+            )
+        }
+        Renderer::Software => {
+            PlayerBuilder::new().with_renderer(SoftwareRenderBackend::new(width, height))
+        }
+    };
+    let builder = if audio {
+        builder.with_audio(CaptureAudioBackend::default())
+    } else {
+        builder
+    };
+    let player = builder
         .with_movie(movie)
+        .with_frame_rate(forced_frame_rate)
         .with_viewport_dimensions(width, height, size.scale)
         .build();
 
@@ -131,15 +238,29 @@ fn take_screenshot(
         player.lock().unwrap().preload(&mut ExecutionLimit::none());
 
         player.lock().unwrap().run_frame();
+        if audio {
+            let mut player = player.lock().unwrap();
+            let backend = player.audio_mut();
+            backend.tick();
+            if i < skipframes {
+                if let Some(backend) = backend.downcast_mut::<CaptureAudioBackend>() {
+                    backend.discard();
+                }
+            }
+        }
         if i >= skipframes {
             let image = || {
                 player.lock().unwrap().render();
                 let mut player = player.lock().unwrap();
-                let renderer = player
-                    .renderer_mut()
-                    .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                    .unwrap();
-                renderer.capture_frame()
+                let renderer = player.renderer_mut();
+                if let Some(renderer) = renderer.downcast_mut::<SoftwareRenderBackend>() {
+                    Some(renderer.capture_frame())
+                } else {
+                    renderer
+                        .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+                        .unwrap()
+                        .capture_frame()
+                }
             };
             match catch_unwind(image) {
                 Ok(Some(image)) => result.push(image),
@@ -159,7 +280,20 @@ fn take_screenshot(
             progress.inc(1);
         }
     }
-    Ok(result)
+    let audio = audio.then(|| {
+        player
+            .lock()
+            .unwrap()
+            .audio_mut()
+            .downcast_mut::<CaptureAudioBackend>()
+            .map(CaptureAudioBackend::take_samples)
+            .unwrap_or_default()
+    });
+    Ok(Capture {
+        frames: result,
+        frame_rate,
+        audio,
+    })
 }
 
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
@@ -192,20 +326,16 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(renderer: Renderer, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
-        if opt.frames == 1 {
-            result.set_extension("png");
+        if opt.format.is_single_file(opt.frames) {
+            result.set_extension(opt.format.extension());
         }
         result
     });
 
-    if opt.frames > 1 {
-        let _ = create_dir_all(&output);
-    }
-
     let progress = if !opt.silent {
         let progress = ProgressBar::new(opt.frames as u64);
         progress.set_style(
@@ -220,39 +350,33 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         None
     };
 
-    let frames = take_screenshot(
-        descriptors,
+    let capture = take_screenshot(
+        renderer,
         &opt.swf,
         opt.frames,
         opt.skipframes,
         &progress,
         opt.size,
         opt.skip_unsupported,
+        opt.audio,
     )?;
 
     if let Some(progress) = &progress {
         progress.set_message(opt.swf.file_stem().unwrap().to_string_lossy().into_owned());
     }
 
-    if frames.len() == 1 {
-        let image = frames.first().unwrap();
-        if opt.output_path == Some(PathBuf::from("-")) {
-            let mut bytes: Vec<u8> = Vec::new();
-            image
-                .write_to(&mut io::Cursor::new(&mut bytes), image::ImageFormat::Png)
-                .expect("Encoding failed");
-            io::stdout()
-                .write_all(bytes.as_slice())
-                .expect("Writing to stdout failed");
-        } else {
-            image.save(&output)?;
-        }
+    let frames = &capture.frames;
+    let timing = capture.timing(opt);
+    if opt.output_path == Some(PathBuf::from("-")) && opt.format.is_single_file(frames.len() as u32)
+    {
+        let mut bytes: Vec<u8> = Vec::new();
+        write_single_file(opt.format, frames, timing, &mut bytes).expect("Encoding failed");
+        io::stdout()
+            .write_all(bytes.as_slice())
+            .expect("Writing to stdout failed");
     } else {
-        for (frame, image) in frames.iter().enumerate() {
-            let mut path: PathBuf = (&output).into();
-            path.push(format!("{frame}.png"));
-            image.save(&path)?;
-        }
+        write_frames(opt.format, frames, timing, &output)?;
+        capture.write_audio(opt.format, &output)?;
     }
 
     let message = if frames.len() == 1 {
@@ -285,8 +409,7 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
     Ok(())
 }
 
-#[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_multiple_swfs(renderer: Renderer, opt: &Opt) -> Result<()> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -314,14 +437,15 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                     .into_owned(),
             );
         }
-        if let Ok(frames) = take_screenshot(
-            descriptors.clone(),
+        if let Ok(capture) = take_screenshot(
+            renderer.clone(),
             file.path(),
             opt.frames,
             opt.skipframes,
             &progress,
             opt.size,
             opt.skip_unsupported,
+            opt.audio,
         ) {
             let mut relative_path = file
                 .path()
@@ -329,25 +453,16 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                 .unwrap_or_else(|_| file.path())
                 .to_path_buf();
 
-            if frames.len() == 1 {
-                let mut destination: PathBuf = (&output).into();
-                relative_path.set_extension("png");
-                destination.push(relative_path);
-                if let Some(parent) = destination.parent() {
-                    let _ = create_dir_all(parent);
-                }
-                frames.first().unwrap().save(&destination)?;
+            let frames = &capture.frames;
+            if opt.format.is_single_file(frames.len() as u32) {
+                relative_path.set_extension(opt.format.extension());
             } else {
-                let mut parent: PathBuf = (&output).into();
                 relative_path.set_extension("");
-                parent.push(&relative_path);
-                let _ = create_dir_all(&parent);
-                for (frame, image) in frames.iter().enumerate() {
-                    let mut destination = parent.clone();
-                    destination.push(format!("{frame}.png"));
-                    image.save(&destination)?;
-                }
             }
+            let mut destination: PathBuf = (&output).into();
+            destination.push(relative_path);
+            write_frames(opt.format, frames, capture.timing(opt), &destination)?;
+            capture.write_audio(opt.format, &destination)?;
         }
 
         Ok(())
@@ -383,27 +498,41 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
 
 fn main() -> Result<()> {
     let opt: Opt = Opt::parse();
-    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-        backends: opt.graphics.into(),
-        ..Default::default()
-    });
-    let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
-        opt.graphics.into(),
-        &instance,
-        None,
-        opt.power.into(),
-        trace_path(&opt),
-    ))
-    .map_err(|e| anyhow!(e.to_string()))?;
+    if opt.audio && opt.output_path == Some(PathBuf::from("-")) {
+        // Reported like any other invalid usage: on stderr, before anything is captured.
+        Opt::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--audio cannot be used when writing to stdout",
+            )
+            .exit();
+    }
 
-    let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
+    let renderer = if opt.software {
+        Renderer::Software
+    } else {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: opt.graphics.into(),
+            ..Default::default()
+        });
+        let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
+            opt.graphics.into(),
+            &instance,
+            None,
+            opt.power.into(),
+            trace_path(&opt),
+        ))
+        .map_err(|e| anyhow!(e.to_string()))?;
+
+        Renderer::Wgpu(Arc::new(Descriptors::new(instance, adapter, device, queue)))
+    };
 
     if opt.swf.is_file() {
-        capture_single_swf(descriptors, &opt)?;
+        capture_single_swf(renderer, &opt)?;
     } else if !opt.swf.is_dir() {
         return Err(anyhow!("Given path is not a file or directory."));
     } else if opt.output_path.is_some() {
-        capture_multiple_swfs(descriptors, &opt)?;
+        capture_multiple_swfs(renderer, &opt)?;
     } else {
         return Err(anyhow!(
             "Output directory is required when exporting multiple files."
@@ -412,3 +541,26 @@ fn main() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_rate_option() {
+        assert_eq!(parse_frame_rate("24").unwrap(), 24.0);
+        assert_eq!(parse_frame_rate("0.5").unwrap(), 0.5);
+        for invalid in ["0", "-12", "NaN", "inf", "fast"] {
+            assert!(parse_frame_rate(invalid).is_err(), "{invalid} was accepted");
+        }
+    }
+
+    #[test]
+    fn zero_frame_rate_movies() {
+        assert_eq!(movie_frame_rate(30.0), (30.0, None));
+        assert_eq!(
+            movie_frame_rate(0.0),
+            (DEFAULT_FRAME_RATE, Some(DEFAULT_FRAME_RATE))
+        );
+    }
+}
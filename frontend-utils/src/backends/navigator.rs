@@ -11,6 +11,7 @@ use ruffle_core::backend::navigator::{
     async_return, create_fetch_error, get_encoding, ErrorResponse, NavigationMethod,
     NavigatorBackend, OwnedFuture, Request, SocketMode, SuccessResponse,
 };
+use ruffle_core::datagram_socket::{Datagram, DatagramSocketAction, DatagramSocketHandle};
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
@@ -23,7 +24,7 @@ use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::net::TcpStream;
+use tokio::net::{TcpStream, UdpSocket};
 use tracing::warn;
 use url::{ParseError, Url};
 
@@ -465,6 +466,164 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
 
         tokio::spawn(future);
     }
+
+    fn bind_datagram_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        handle: DatagramSocketHandle,
+        receiver: Receiver<Datagram>,
+        sender: Sender<DatagramSocketAction>,
+    ) {
+        /// Tries to send the given action properly handling failures.
+        ///
+        /// Returns `true` when the action has been sent properly,
+        /// `false` when the channel is closed.
+        async fn send_action(
+            sender: &Sender<DatagramSocketAction>,
+            action: DatagramSocketAction,
+        ) -> bool {
+            sender
+                .send(action)
+                .await
+                .inspect_err(|err| tracing::warn!("Failed to send DatagramSocketAction: {}", err))
+                .is_ok()
+        }
+
+        let socket_allowed = self.socket_allowed.clone();
+        let socket_mode = self.socket_mode;
+        let interface = self.interface.clone();
+
+        let future = Box::pin(async move {
+            // Binding needs to be allowed like connecting, since it lets the movie receive datagrams.
+            let addr = format!("{}:{}", host, port);
+            let is_bind_allowed = socket_allowed.contains(&addr)
+                || match socket_mode {
+                    SocketMode::Allow => true,
+                    SocketMode::Deny => {
+                        tracing::warn!(
+                            "SWF tried to bind a datagram socket, but binding datagram sockets is not allowed"
+                        );
+                        false
+                    }
+                    SocketMode::Ask => interface.confirm_socket(&host, port).await,
+                };
+            if !is_bind_allowed {
+                let _ = send_action(&sender, DatagramSocketAction::BindFailed(handle)).await;
+                return;
+            }
+
+            let socket = match UdpSocket::bind((host.as_str(), port)).await {
+                Ok(socket) => socket,
+                Err(err) => {
+                    warn!(
+                        "Failed to bind datagram socket to {}:{}, error: {}",
+                        host, port, err
+                    );
+                    let _ = send_action(&sender, DatagramSocketAction::BindFailed(handle)).await;
+                    return;
+                }
+            };
+
+            // When binding to port 0, the system picks the actual port.
+            let port = socket.local_addr().map_or(port, |addr| addr.port());
+            let action = DatagramSocketAction::Bound(handle, host, port);
+            if !send_action(&sender, action).await {
+                return;
+            }
+
+            let read = async {
+                // This fits any UDP packet.
+                let mut buffer = vec![0; 65536];
+
+                loop {
+                    match socket.recv_from(&mut buffer).await {
+                        // Some systems report packets previously sent to a closed port this way.
+                        Err(e) if e.kind() == ErrorKind::ConnectionReset => {}
+                        Err(e) => {
+                            warn!("Failed to receive datagram, error: {}", e);
+                            let _ = send_action(&sender, DatagramSocketAction::Close(handle)).await;
+                            return;
+                        }
+                        Ok((read, source)) => {
+                            let datagram = Datagram {
+                                address: source.ip().to_string(),
+                                port: source.port(),
+                                data: buffer[..read].to_vec(),
+                            };
+
+                            let action = DatagramSocketAction::Data(handle, datagram);
+                            if !send_action(&sender, action).await {
+                                return;
+                            }
+                        }
+                    }
+                }
+            };
+
+            // The interface is moved into the writing half, as it may not be shared between threads.
+            let socket = &socket;
+            let write = async move {
+                // As with sockets, the movie needs to be allowed to send to each destination.
+                // The user only gets asked once for each of them.
+                let mut allowed = socket_allowed;
+                let mut denied = HashSet::new();
+
+                //NOTE: The channel is closed once the movie closes the socket.
+                while let Ok(datagram) = receiver.recv().await {
+                    let addr = format!("{}:{}", datagram.address, datagram.port);
+
+                    if denied.contains(&addr) {
+                        continue;
+                    }
+
+                    if !allowed.contains(&addr) {
+                        let is_allowed = match socket_mode {
+                            SocketMode::Allow => true,
+                            SocketMode::Deny => {
+                                tracing::warn!(
+                                    "SWF tried to send a datagram to {}, but sending datagrams is not allowed",
+                                    addr
+                                );
+                                false
+                            }
+                            SocketMode::Ask => {
+                                interface
+                                    .confirm_socket(&datagram.address, datagram.port)
+                                    .await
+                            }
+                        };
+
+                        if !is_allowed {
+                            denied.insert(addr);
+                            continue;
+                        }
+                        allowed.insert(addr);
+                    }
+
+                    let destination = (datagram.address.as_str(), datagram.port);
+                    if let Err(e) = socket.send_to(&datagram.data, destination).await {
+                        warn!(
+                            "Failed to send datagram to {}:{}, error: {}",
+                            datagram.address, datagram.port, e
+                        );
+                    }
+                }
+            };
+
+            //NOTE: If one future exits, this will take the other one down too.
+            tokio::select! {
+               _ = read => {},
+               _ = write => {},
+            };
+        });
+
+        tokio::spawn(future);
+    }
+
+    fn supports_datagram_sockets(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -758,4 +917,213 @@ mod tests {
 
         assert_eq!(read_server(&mut server_socket).await, "Sending some data");
     }
+
+    fn bind_test_datagram_socket(
+        socket_allow: bool,
+        port: u16,
+    ) -> (Sender<Datagram>, Receiver<DatagramSocketAction>) {
+        let mut backend = new_test_backend(socket_allow);
+
+        let (write, receiver) = async_channel::unbounded();
+        let (sender, read) = async_channel::unbounded();
+
+        backend.bind_datagram_socket(
+            "127.0.0.1".to_string(),
+            port,
+            DatagramSocketHandle::default(),
+            receiver,
+            sender,
+        );
+
+        (write, read)
+    }
+
+    async fn bound_port(client_read: &Receiver<DatagramSocketAction>) -> u16 {
+        match client_read.recv().or(async_timeout!()).await {
+            Ok(DatagramSocketAction::Bound(_, _, port)) => port,
+            action => panic!("Expected the socket to be bound, got {:?}", action),
+        }
+    }
+
+    #[macro_rules_attribute::apply(async_test)]
+    async fn test_datagram_socket_basic_communication() {
+        let peer = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let peer_port = peer.local_addr().unwrap().port();
+        let (client_write, client_read) = bind_test_datagram_socket(true, 0);
+        let port = bound_port(&client_read).await;
+
+        client_write
+            .send(Datagram {
+                address: "127.0.0.1".to_string(),
+                port: peer_port,
+                data: b"Hello from client".to_vec(),
+            })
+            .await
+            .unwrap();
+
+        let mut buffer = [0; 4096];
+        let (read, source) = peer
+            .recv_from(&mut buffer)
+            .or(async_timeout!())
+            .await
+            .unwrap();
+        assert_eq!(&buffer[..read], b"Hello from client");
+        assert_eq!(source.port(), port);
+
+        peer.send_to(b"Hello World!", source).await.unwrap();
+
+        assert_next_socket_actions!(
+            client_read;
+            DatagramSocketAction::Data(
+                DatagramSocketHandle::default(),
+                Datagram {
+                    address: "127.0.0.1".to_string(),
+                    port: peer_port,
+                    data: b"Hello World!".to_vec(),
+                },
+            ),
+        );
+    }
+
+    #[macro_rules_attribute::apply(async_test)]
+    async fn test_datagram_socket_deny() {
+        let (_client_write, client_read) = bind_test_datagram_socket(false, 0);
+
+        assert_next_socket_actions!(
+            client_read;
+            DatagramSocketAction::BindFailed(DatagramSocketHandle::default()),
+        );
+    }
+
+    #[macro_rules_attribute::apply(async_test)]
+    async fn test_datagram_socket_bind_fail() {
+        let taken = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let taken_port = taken.local_addr().unwrap().port();
+        let (_client_write, client_read) = bind_test_datagram_socket(true, taken_port);
+
+        assert_next_socket_actions!(
+            client_read;
+            DatagramSocketAction::BindFailed(DatagramSocketHandle::default()),
+        );
+    }
+
+    #[macro_rules_attribute::apply(async_test)]
+    async fn test_datagram_socket_bind_port() {
+        let free_port = UdpSocket::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let (_client_write, client_read) = bind_test_datagram_socket(true, free_port);
+
+        assert_next_socket_actions!(
+            client_read;
+            DatagramSocketAction::Bound(
+                DatagramSocketHandle::default(),
+                "127.0.0.1".to_string(),
+                free_port,
+            ),
+        );
+    }
+
+    #[macro_rules_attribute::apply(async_test)]
+    async fn test_datagram_socket_close() {
+        let (client_write, client_read) = bind_test_datagram_socket(true, 0);
+        let port = bound_port(&client_read).await;
+
+        // Closing the socket in the movie drops its channel, which stops the backend.
+        drop(client_write);
+        assert!(client_read.recv().or(async_timeout!()).await.is_err());
+
+        // The port is free again once the socket is closed.
+        UdpSocket::bind(("127.0.0.1", port)).await.unwrap();
+    }
+
+    #[macro_rules_attribute::apply(async_test)]
+    async fn test_datagram_socket_multiple_peers() {
+        let first = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let first_port = first.local_addr().unwrap().port();
+        let second = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let second_port = second.local_addr().unwrap().port();
+        let (client_write, client_read) = bind_test_datagram_socket(true, 0);
+        let port = bound_port(&client_read).await;
+
+        for (peer_port, data) in [
+            (first_port, &b"To first"[..]),
+            (second_port, &b"To second"[..]),
+        ] {
+            client_write
+                .send(Datagram {
+                    address: "127.0.0.1".to_string(),
+                    port: peer_port,
+                    data: data.to_vec(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let mut buffer = [0; 4096];
+        for (peer, data) in [(&first, &b"To first"[..]), (&second, &b"To second"[..])] {
+            let (read, source) = peer
+                .recv_from(&mut buffer)
+                .or(async_timeout!())
+                .await
+                .unwrap();
+            assert_eq!(&buffer[..read], data);
+            assert_eq!(source.port(), port);
+        }
+
+        first
+            .send_to(b"From first", ("127.0.0.1", port))
+            .await
+            .unwrap();
+        second
+            .send_to(b"From second", ("127.0.0.1", port))
+            .await
+            .unwrap();
+        assert_next_socket_actions!(
+            client_read;
+            DatagramSocketAction::Data(
+                DatagramSocketHandle::default(),
+                Datagram {
+                    address: "127.0.0.1".to_string(),
+                    port: first_port,
+                    data: b"From first".to_vec(),
+                },
+            ),
+            DatagramSocketAction::Data(
+                DatagramSocketHandle::default(),
+                Datagram {
+                    address: "127.0.0.1".to_string(),
+                    port: second_port,
+                    data: b"From second".to_vec(),
+                },
+            ),
+        );
+    }
+
+    #[macro_rules_attribute::apply(async_test)]
+    async fn test_datagram_socket_large_datagram() {
+        let peer = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let peer_port = peer.local_addr().unwrap().port();
+        let (_client_write, client_read) = bind_test_datagram_socket(true, 0);
+        let port = bound_port(&client_read).await;
+
+        // The largest payload of a UDP packet over IPv4.
+        let data: Vec<u8> = (0..65507).map(|i| i as u8).collect();
+        peer.send_to(&data, ("127.0.0.1", port)).await.unwrap();
+
+        assert_next_socket_actions!(
+            client_read;
+            DatagramSocketAction::Data(
+                DatagramSocketHandle::default(),
+                Datagram {
+                    address: "127.0.0.1".to_string(),
+                    port: peer_port,
+                    data,
+                },
+            ),
+        );
+    }
 }
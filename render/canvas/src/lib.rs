@@ -7,15 +7,23 @@ use ruffle_render::backend::{
     RenderBackend, ShapeHandle, ShapeHandleImpl, ViewportDimensions,
 };
 use ruffle_render::bitmap::{
-    Bitmap, BitmapHandle, BitmapHandleImpl, BitmapSource, PixelRegion, PixelSnapping, RgbaBufRead,
-    SyncHandle,
+    Bitmap, BitmapFormat, BitmapHandle, BitmapHandleImpl, BitmapSource, PixelRegion, PixelSnapping,
+    RgbaBufRead, SyncHandle,
 };
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::error::Error;
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::pixel_bender::interpreter::{
+    run_shader, InterpretedShader, OutOfRangeMode, ShaderImage,
+};
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, DrawCommand, LineScaleMode, LineScales};
 use ruffle_render::transform::Transform;
+use ruffle_render::utils::{premultiply_alpha_rgba, unmultiply_alpha_rgba};
 use ruffle_web_common::{JsError, JsResult};
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -154,6 +162,30 @@ struct BitmapData {
 
 impl BitmapHandleImpl for BitmapData {}
 
+/// A bitmap written to by a shader, which is read back from its canvas.
+#[derive(Debug)]
+struct CanvasSyncHandle {
+    handle: BitmapHandle,
+}
+
+impl SyncHandle for CanvasSyncHandle {}
+
+/// Canvas can only draw with affine transforms, so perspective is approximated by the transform
+/// that places the top-left, top-right and bottom-left corners of the bitmap correctly.
+fn affine_approximation(matrix3d: &Matrix3D, width: f64, height: f64) -> Option<Matrix> {
+    let (x0, y0) = matrix3d.project_point(0.0, 0.0)?;
+    let (x1, y1) = matrix3d.project_point(width, 0.0)?;
+    let (x2, y2) = matrix3d.project_point(0.0, height)?;
+    Some(Matrix {
+        a: ((x1 - x0) / width) as f32,
+        b: ((y1 - y0) / width) as f32,
+        c: ((x2 - x0) / height) as f32,
+        d: ((y2 - y0) / height) as f32,
+        tx: Twips::from_pixels(x0),
+        ty: Twips::from_pixels(y0),
+    })
+}
+
 fn as_bitmap_data(handle: &BitmapHandle) -> &BitmapData {
     <dyn BitmapHandleImpl>::downcast_ref(&*handle.0)
         .expect("Bitmap handle must be a Canvas BitmapData")
@@ -211,6 +243,26 @@ impl BitmapData {
             .into_js_result()?;
         Ok(())
     }
+
+    fn width(&self) -> u32 {
+        self.canvas.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.canvas.height()
+    }
+
+    /// Reads back the pixels of the canvas as premultiplied RGBA.
+    fn pixels(&self) -> Result<Vec<u8>, JsValue> {
+        let image_data = self
+            .context
+            .get_image_data(0.0, 0.0, self.width().into(), self.height().into())
+            .into_js_result()?;
+        // `getImageData` returns straight alpha.
+        let mut pixels = image_data.data().0;
+        premultiply_alpha_rgba(&mut pixels);
+        Ok(pixels)
+    }
 }
 
 impl WebCanvasRenderBackend {
@@ -569,26 +621,74 @@ impl RenderBackend for WebCanvasRenderBackend {
 
     fn compile_pixelbender_shader(
         &mut self,
-        _shader: ruffle_render::pixel_bender::PixelBenderShader,
-    ) -> Result<ruffle_render::pixel_bender::PixelBenderShaderHandle, Error> {
-        Err(Error::Unimplemented("compile_pixelbender_shader".into()))
+        shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Ok(PixelBenderShaderHandle(Arc::new(InterpretedShader::new(
+            shader,
+        ))))
     }
 
     fn run_pixelbender_shader(
         &mut self,
-        _handle: ruffle_render::pixel_bender::PixelBenderShaderHandle,
-        _arguments: &[ruffle_render::pixel_bender::PixelBenderShaderArgument],
-        _target: &PixelBenderTarget,
+        handle: PixelBenderShaderHandle,
+        arguments: &[PixelBenderShaderArgument],
+        target: &PixelBenderTarget,
     ) -> Result<PixelBenderOutput, Error> {
-        Err(Error::Unimplemented("run_pixelbender_shader".into()))
+        // Canvas can't run shaders, so they're interpreted on the CPU.
+        let (width, height) = match target {
+            PixelBenderTarget::Bitmap(bitmap) => {
+                let data = as_bitmap_data(bitmap);
+                (data.width(), data.height())
+            }
+            PixelBenderTarget::Bytes { width, height } => (*width, *height),
+        };
+        let output = run_shader(
+            handle.0.parsed_shader(),
+            arguments,
+            width,
+            height,
+            OutOfRangeMode::Clamp,
+            |bitmap| {
+                let data = as_bitmap_data(bitmap);
+                let pixels = data.pixels().map_err(Error::JavascriptError)?;
+                Ok(ShaderImage::from_rgba8(
+                    data.width(),
+                    data.height(),
+                    &pixels,
+                ))
+            },
+        )?;
+
+        match target {
+            PixelBenderTarget::Bitmap(bitmap) => {
+                // `putImageData` expects straight alpha.
+                let mut pixels = output.to_rgba8();
+                unmultiply_alpha_rgba(&mut pixels);
+                as_bitmap_data(bitmap)
+                    .update_pixels(Bitmap::new(width, height, BitmapFormat::Rgba, pixels))
+                    .map_err(Error::JavascriptError)?;
+                Ok(PixelBenderOutput::Bitmap(Box::new(CanvasSyncHandle {
+                    handle: bitmap.clone(),
+                })))
+            }
+            PixelBenderTarget::Bytes { .. } => {
+                Ok(PixelBenderOutput::Bytes(output.to_float_bytes()))
+            }
+        }
     }
 
     fn resolve_sync_handle(
         &mut self,
-        _handle: Box<dyn SyncHandle>,
-        _with_rgba: RgbaBufRead,
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
     ) -> Result<(), Error> {
-        Err(Error::Unimplemented("Sync handle resolution".into()))
+        let handle = handle
+            .downcast::<CanvasSyncHandle>()
+            .map_err(|_| Error::Unimplemented("Foreign sync handle".into()))?;
+        let data = as_bitmap_data(&handle.handle);
+        let pixels = data.pixels().map_err(Error::JavascriptError)?;
+        with_rgba(&pixels, data.width() * 4);
+        Ok(())
     }
 
     fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
@@ -609,11 +709,22 @@ impl CommandHandler for WebCanvasRenderBackend {
             return;
         }
 
+        let bitmap = as_bitmap_data(&bitmap);
+        let matrix = if let Some(matrix3d) = transform.matrix3d {
+            let width = bitmap.canvas.width() as f64;
+            let height = bitmap.canvas.height() as f64;
+            match affine_approximation(&matrix3d, width, height) {
+                Some(matrix) => matrix,
+                None => return,
+            }
+        } else {
+            transform.matrix
+        };
+
         self.context.set_image_smoothing_enabled(smoothing);
 
-        self.set_transform(&transform.matrix);
+        self.set_transform(&matrix);
         self.set_color_filter(&transform);
-        let bitmap = as_bitmap_data(&bitmap);
         let _ = self
             .context
             .draw_image_with_html_canvas_element(&bitmap.canvas, 0.0, 0.0);
@@ -0,0 +1,415 @@
+use crate::commands::draw_commands;
+use crate::filters::{apply_filter, is_filter_supported, FilterImage};
+use crate::mesh::{Draw, DrawType, Gradient, Mesh};
+use crate::pixel_bender::run_software_shader;
+use crate::surface::Surface;
+use crate::{as_texture, Texture};
+use ruffle_render::backend::{
+    BitmapCacheEntry, Context3D, Context3DProfile, PixelBenderOutput, PixelBenderTarget,
+    RenderBackend, ShapeHandle, ViewportDimensions,
+};
+use ruffle_render::bitmap::{
+    Bitmap, BitmapHandle, BitmapSource, PixelRegion, RgbaBufRead, SyncHandle,
+};
+use ruffle_render::commands::CommandList;
+use ruffle_render::error::Error;
+use ruffle_render::filters::Filter;
+use ruffle_render::pixel_bender::interpreter::{InterpretedShader, OutOfRangeMode};
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::DistilledShape;
+use ruffle_render::tessellator::{DrawType as TessDrawType, ShapeTessellator};
+use std::borrow::Cow;
+use std::sync::Arc;
+use swf::Color;
+
+/// A render backend that draws everything on the CPU into an in-memory frame buffer.
+pub struct SoftwareRenderBackend {
+    shape_tessellator: ShapeTessellator,
+    quality: StageQuality,
+    width: u32,
+    height: u32,
+    viewport_scale_factor: f64,
+    /// The premultiplied RGBA contents of the last submitted frame.
+    frame: Vec<u8>,
+}
+
+/// Marks a texture whose contents are already up to date.
+/// Everything is drawn synchronously, so there's nothing left to wait for.
+#[derive(Debug)]
+struct SoftwareSyncHandle {
+    handle: BitmapHandle,
+    region: PixelRegion,
+}
+
+impl SyncHandle for SoftwareSyncHandle {}
+
+impl SoftwareRenderBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            shape_tessellator: ShapeTessellator::new(),
+            quality: StageQuality::High,
+            width,
+            height,
+            viewport_scale_factor: 1.0,
+            frame: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    /// Returns the last submitted frame, with straight (non-premultiplied) alpha.
+    pub fn capture_frame(&self) -> image::RgbaImage {
+        let mut bytes = self.frame.clone();
+        ruffle_render::utils::unmultiply_alpha_rgba(&mut bytes);
+        image::RgbaImage::from_raw(self.width, self.height, bytes)
+            .expect("Frame buffer must be a valid RgbaImage")
+    }
+
+    fn register_shape_internal(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> Mesh {
+        let lyon_mesh = self
+            .shape_tessellator
+            .tessellate_shape(shape, bitmap_source);
+
+        let mut draws = Vec::with_capacity(lyon_mesh.draws.len());
+        for draw in lyon_mesh.draws {
+            let draw_type = match draw.draw_type {
+                TessDrawType::Color => DrawType::Color,
+                TessDrawType::Gradient { matrix, gradient } => {
+                    DrawType::Gradient { matrix, gradient }
+                }
+                TessDrawType::Bitmap(bitmap) => {
+                    let Some(handle) = bitmap_source.bitmap_handle(bitmap.bitmap_id, self) else {
+                        tracing::warn!("Couldn't find bitmap {}", bitmap.bitmap_id);
+                        continue;
+                    };
+                    DrawType::Bitmap {
+                        matrix: bitmap.matrix,
+                        bitmap: handle,
+                        is_smoothed: bitmap.is_smoothed,
+                        is_repeating: bitmap.is_repeating,
+                    }
+                }
+            };
+            draws.push(Draw {
+                draw_type,
+                num_mask_indices: (draw.mask_index_count as usize).min(draw.indices.len()),
+                vertices: draw.vertices,
+                indices: draw.indices,
+            });
+        }
+
+        Mesh {
+            draws,
+            gradients: lyon_mesh.gradients.into_iter().map(Gradient::new).collect(),
+        }
+    }
+
+    /// Draws the commands onto the given texture, starting from its current contents.
+    /// Only the pixels inside `region` are changed.
+    fn render_to_texture(
+        &self,
+        texture: &Texture,
+        commands: CommandList,
+        quality: StageQuality,
+        clear: Option<Color>,
+        filters: Vec<Filter>,
+        mut region: PixelRegion,
+    ) {
+        let surface = match clear {
+            Some(clear) => Surface::new(
+                texture.width(),
+                texture.height(),
+                quality,
+                premultiply_color(clear),
+            ),
+            None => Surface::from_pixels(
+                texture.width(),
+                texture.height(),
+                quality,
+                &texture.pixels(),
+            ),
+        };
+        let surface = draw_commands(surface, commands);
+
+        let mut image = FilterImage {
+            width: surface.width(),
+            height: surface.height(),
+            pixels: surface.resolve(),
+        };
+        for filter in &filters {
+            image = apply_filter(image, filter);
+        }
+
+        region.clamp(texture.width(), texture.height());
+        if region == PixelRegion::for_whole_size(texture.width(), texture.height()) {
+            *texture.pixels_mut() = image.pixels;
+        } else {
+            copy_region(
+                &mut texture.pixels_mut(),
+                &image.pixels,
+                texture.width(),
+                region,
+            );
+        }
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn viewport_dimensions(&self) -> ViewportDimensions {
+        ViewportDimensions {
+            width: self.width,
+            height: self.height,
+            scale_factor: self.viewport_scale_factor,
+        }
+    }
+
+    fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.width = dimensions.width;
+        self.height = dimensions.height;
+        self.viewport_scale_factor = dimensions.scale_factor;
+        self.frame = vec![0; self.width as usize * self.height as usize * 4];
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        bitmap_source: &dyn BitmapSource,
+    ) -> ShapeHandle {
+        let mesh = self.register_shape_internal(shape, bitmap_source);
+        ShapeHandle(Arc::new(mesh))
+    }
+
+    fn render_offscreen(
+        &mut self,
+        handle: BitmapHandle,
+        commands: CommandList,
+        quality: StageQuality,
+        bounds: PixelRegion,
+    ) -> Option<Box<dyn SyncHandle>> {
+        self.render_to_texture(as_texture(&handle), commands, quality, None, vec![], bounds);
+        Some(Box::new(SoftwareSyncHandle {
+            handle,
+            region: bounds,
+        }))
+    }
+
+    fn is_filter_supported(&self, filter: &Filter) -> bool {
+        is_filter_supported(filter)
+    }
+
+    fn is_offscreen_supported(&self) -> bool {
+        true
+    }
+
+    fn apply_filter(
+        &mut self,
+        source: BitmapHandle,
+        source_point: (u32, u32),
+        source_size: (u32, u32),
+        destination: BitmapHandle,
+        dest_point: (u32, u32),
+        filter: Filter,
+    ) -> Option<Box<dyn SyncHandle>> {
+        let source_texture = as_texture(&source);
+        let dest_texture = as_texture(&destination);
+
+        let image = FilterImage::from_region(
+            &source_texture.pixels(),
+            source_texture.width(),
+            source_texture.height(),
+            source_point,
+            source_size,
+        );
+        let image = apply_filter(image, &filter);
+        image.copy_to(
+            &mut dest_texture.pixels_mut(),
+            dest_texture.width(),
+            dest_texture.height(),
+            dest_point,
+        );
+
+        let region = PixelRegion::for_whole_size(dest_texture.width(), dest_texture.height());
+        Some(Box::new(SoftwareSyncHandle {
+            handle: destination,
+            region,
+        }))
+    }
+
+    fn submit_frame(
+        &mut self,
+        clear: Color,
+        commands: CommandList,
+        cache_entries: Vec<BitmapCacheEntry>,
+    ) {
+        for entry in cache_entries {
+            let texture = as_texture(&entry.handle);
+            self.render_to_texture(
+                texture,
+                entry.commands,
+                self.quality,
+                Some(entry.clear),
+                entry.filters,
+                PixelRegion::for_whole_size(texture.width(), texture.height()),
+            );
+        }
+
+        let surface = Surface::new(
+            self.width,
+            self.height,
+            self.quality,
+            premultiply_color(clear),
+        );
+        self.frame = draw_commands(surface, commands).resolve();
+    }
+
+    fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
+        if width == 0 || height == 0 {
+            return Err(Error::InvalidSize);
+        }
+        let pixels = vec![0; width as usize * height as usize * 4];
+        Ok(BitmapHandle(Arc::new(Texture::new(width, height, pixels))))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapHandle, Error> {
+        let bitmap = bitmap.to_rgba();
+        let (width, height) = (bitmap.width(), bitmap.height());
+        let mut pixels = bitmap.data().to_vec();
+        pixels.resize(width as usize * height as usize * 4, 0);
+        Ok(BitmapHandle(Arc::new(Texture::new(width, height, pixels))))
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: &BitmapHandle,
+        bitmap: Bitmap,
+        mut region: PixelRegion,
+    ) -> Result<(), Error> {
+        let texture = as_texture(handle);
+        let bitmap = bitmap.to_rgba();
+        if bitmap.width() != texture.width() || bitmap.height() != texture.height() {
+            return Err(Error::InvalidSize);
+        }
+
+        region.clamp(texture.width(), texture.height());
+        copy_region(
+            &mut texture.pixels_mut(),
+            bitmap.data(),
+            texture.width(),
+            region,
+        );
+        Ok(())
+    }
+
+    fn create_context3d(
+        &mut self,
+        _profile: Context3DProfile,
+    ) -> Result<Box<dyn Context3D>, Error> {
+        Err(Error::Unimplemented("Context3D".into()))
+    }
+
+    fn context3d_present(&mut self, _context: &mut dyn Context3D) -> Result<(), Error> {
+        Err(Error::Unimplemented("Context3D".into()))
+    }
+
+    fn debug_info(&self) -> Cow<'static, str> {
+        let result = [
+            "Renderer: software".to_string(),
+            format!("Surface quality: {}", self.quality),
+            format!("Surface size: {}x{}", self.width, self.height),
+        ];
+        Cow::Owned(result.join("\n"))
+    }
+
+    fn name(&self) -> &'static str {
+        "software"
+    }
+
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+    }
+
+    fn compile_pixelbender_shader(
+        &mut self,
+        shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        Ok(PixelBenderShaderHandle(Arc::new(InterpretedShader::new(
+            shader,
+        ))))
+    }
+
+    fn run_pixelbender_shader(
+        &mut self,
+        handle: PixelBenderShaderHandle,
+        arguments: &[PixelBenderShaderArgument],
+        target: &PixelBenderTarget,
+    ) -> Result<PixelBenderOutput, Error> {
+        match target {
+            PixelBenderTarget::Bitmap(bitmap) => {
+                let texture = as_texture(bitmap);
+                let (width, height) = (texture.width(), texture.height());
+                // The inputs are read before the target is written to,
+                // so the target may also be used as an input.
+                let output =
+                    run_software_shader(&handle, arguments, width, height, OutOfRangeMode::Clamp)?;
+                *texture.pixels_mut() = output.to_rgba8();
+                Ok(PixelBenderOutput::Bitmap(Box::new(SoftwareSyncHandle {
+                    handle: bitmap.clone(),
+                    region: PixelRegion::for_whole_size(width, height),
+                })))
+            }
+            PixelBenderTarget::Bytes { width, height } => {
+                let output = run_software_shader(
+                    &handle,
+                    arguments,
+                    *width,
+                    *height,
+                    OutOfRangeMode::Clamp,
+                )?;
+                Ok(PixelBenderOutput::Bytes(output.to_float_bytes()))
+            }
+        }
+    }
+
+    fn resolve_sync_handle(
+        &mut self,
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
+    ) -> Result<(), Error> {
+        let handle = handle
+            .downcast::<SoftwareSyncHandle>()
+            .map_err(|_| Error::Unimplemented("Foreign sync handle".into()))?;
+        let texture = as_texture(&handle.handle);
+        let pixels = texture.pixels();
+        let region = handle.region;
+        let start = (region.y_min * texture.width() + region.x_min) as usize * 4;
+        with_rgba(&pixels[start.min(pixels.len())..], texture.width() * 4);
+        Ok(())
+    }
+}
+
+fn premultiply_color(color: Color) -> [u8; 4] {
+    let a = u16::from(color.a);
+    let premultiply = |c: u8| ((u16::from(c) * a + 127) / 255) as u8;
+    [
+        premultiply(color.r),
+        premultiply(color.g),
+        premultiply(color.b),
+        color.a,
+    ]
+}
+
+/// Copies the pixels inside `region` between two RGBA buffers of the same size.
+/// The region must already be clamped to that size.
+fn copy_region(destination: &mut [u8], source: &[u8], width: u32, region: PixelRegion) {
+    let row_length = width as usize * 4;
+    for y in region.y_min..region.y_max {
+        let start = y as usize * row_length + region.x_min as usize * 4;
+        let end = y as usize * row_length + region.x_max as usize * 4;
+        destination[start..end].copy_from_slice(&source[start..end]);
+    }
+}
@@ -0,0 +1,152 @@
+use ruffle_render::commands::RenderBlendMode;
+use ruffle_render::pixel_bender::PixelBenderShaderHandle;
+use swf::BlendMode;
+
+/// A premultiplied RGBA color, with every component in the range `0.0..=1.0`.
+pub type Rgba = [f32; 4];
+
+#[derive(Debug, Copy, Clone)]
+pub enum ComplexBlend {
+    Multiply,
+    Lighten,
+    Darken,
+    Difference,
+    Invert,
+    Alpha,
+    Erase,
+    Overlay,
+    HardLight,
+}
+
+#[derive(Debug, Clone)]
+pub enum BlendType {
+    /// Blends that can be expressed as a fixed function of the source and destination.
+    Trivial(TrivialBlend),
+
+    /// Blends that need to look at the unmultiplied source and destination colors.
+    Complex(ComplexBlend),
+
+    /// Invoke a custom `PixelBender` shader.
+    Shader(PixelBenderShaderHandle),
+}
+
+impl BlendType {
+    pub fn from(mode: RenderBlendMode) -> BlendType {
+        match mode {
+            RenderBlendMode::Builtin(BlendMode::Normal) => BlendType::Trivial(TrivialBlend::Normal),
+            RenderBlendMode::Builtin(BlendMode::Layer) => BlendType::Trivial(TrivialBlend::Normal),
+            RenderBlendMode::Builtin(BlendMode::Multiply) => {
+                BlendType::Complex(ComplexBlend::Multiply)
+            }
+            RenderBlendMode::Builtin(BlendMode::Screen) => BlendType::Trivial(TrivialBlend::Screen),
+            RenderBlendMode::Builtin(BlendMode::Lighten) => {
+                BlendType::Complex(ComplexBlend::Lighten)
+            }
+            RenderBlendMode::Builtin(BlendMode::Darken) => BlendType::Complex(ComplexBlend::Darken),
+            RenderBlendMode::Builtin(BlendMode::Difference) => {
+                BlendType::Complex(ComplexBlend::Difference)
+            }
+            RenderBlendMode::Builtin(BlendMode::Add) => BlendType::Trivial(TrivialBlend::Add),
+            RenderBlendMode::Builtin(BlendMode::Subtract) => {
+                BlendType::Trivial(TrivialBlend::Subtract)
+            }
+            RenderBlendMode::Builtin(BlendMode::Invert) => BlendType::Complex(ComplexBlend::Invert),
+            RenderBlendMode::Builtin(BlendMode::Alpha) => BlendType::Complex(ComplexBlend::Alpha),
+            RenderBlendMode::Builtin(BlendMode::Erase) => BlendType::Complex(ComplexBlend::Erase),
+            RenderBlendMode::Builtin(BlendMode::Overlay) => {
+                BlendType::Complex(ComplexBlend::Overlay)
+            }
+            RenderBlendMode::Builtin(BlendMode::HardLight) => {
+                BlendType::Complex(ComplexBlend::HardLight)
+            }
+            RenderBlendMode::Shader(shader) => BlendType::Shader(shader),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum TrivialBlend {
+    Normal,
+    Add,
+    Subtract,
+    Screen,
+}
+
+impl TrivialBlend {
+    /// Blends `src` onto `dst`, matching the blend states used by the wgpu backend.
+    /// Alpha is always composited with the "over" operator.
+    pub fn apply(self, src: Rgba, dst: Rgba) -> Rgba {
+        let alpha = src[3] + dst[3] * (1.0 - src[3]);
+        let channel = |s: f32, d: f32| match self {
+            TrivialBlend::Normal => s + d * (1.0 - src[3]),
+            TrivialBlend::Add => s + d,
+            TrivialBlend::Subtract => d - s,
+            TrivialBlend::Screen => s + d * (1.0 - s),
+        };
+        [
+            channel(src[0], dst[0]),
+            channel(src[1], dst[1]),
+            channel(src[2], dst[2]),
+            alpha,
+        ]
+    }
+}
+
+impl ComplexBlend {
+    /// Blends `src` onto `dst`.
+    ///
+    /// Returns `None` if the destination should be left untouched,
+    /// which Flash does whenever the source is fully transparent.
+    pub fn apply(self, src: Rgba, dst: Rgba) -> Option<Rgba> {
+        if src[3] <= 0.0 {
+            return None;
+        }
+        match self {
+            ComplexBlend::Alpha => {
+                return Some([
+                    dst[0] * src[3],
+                    dst[1] * src[3],
+                    dst[2] * src[3],
+                    dst[3] * src[3],
+                ]);
+            }
+            ComplexBlend::Erase => {
+                let inv = 1.0 - src[3];
+                return Some([dst[0] * inv, dst[1] * inv, dst[2] * inv, dst[3] * inv]);
+            }
+            _ => {}
+        }
+        if dst[3] <= 0.0 {
+            return Some(src);
+        }
+
+        let mut out = [0.0; 4];
+        for i in 0..3 {
+            let s = src[i] / src[3];
+            let d = dst[i] / dst[3];
+            let blended = match self {
+                ComplexBlend::Multiply => s * d,
+                ComplexBlend::Lighten => s.max(d),
+                ComplexBlend::Darken => s.min(d),
+                ComplexBlend::Difference => (d - s).abs(),
+                ComplexBlend::Invert => 1.0 - d,
+                ComplexBlend::Overlay => hard_light(d, s),
+                ComplexBlend::HardLight => hard_light(s, d),
+                ComplexBlend::Alpha | ComplexBlend::Erase => unreachable!(),
+            };
+            out[i] = src[i] * (1.0 - dst[3]) + dst[i] * (1.0 - src[3]) + src[3] * dst[3] * blended;
+        }
+        out[3] = src[3] + dst[3] * (1.0 - src[3]);
+        Some(out)
+    }
+}
+
+/// The hard light function, where `a` decides between multiply and screen.
+/// Overlay is the same function with the arguments swapped.
+fn hard_light(a: f32, b: f32) -> f32 {
+    if a <= 0.5 {
+        2.0 * a * b
+    } else {
+        1.0 - 2.0 * (1.0 - a) * (1.0 - b)
+    }
+}
@@ -0,0 +1,387 @@
+use crate::blend::{BlendType, ComplexBlend, Rgba, TrivialBlend};
+use crate::mesh::{sample_texture, transform_uv, DrawType, Mesh};
+use crate::pixel_bender::run_software_shader;
+use crate::surface::{MaskState, Point, Surface};
+use crate::{as_texture, Texture};
+use ruffle_render::backend::{ShapeHandle, ShapeHandleImpl};
+use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::lines::{emulate_line, emulate_line_rect};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::pixel_bender::interpreter::OutOfRangeMode;
+use ruffle_render::pixel_bender::{PixelBenderShaderArgument, PixelBenderShaderHandle};
+use ruffle_render::transform::Transform;
+use std::sync::Arc;
+use swf::{BlendMode, Color, ColorTransform};
+
+pub fn as_mesh(handle: &ShapeHandle) -> &Mesh {
+    <dyn ShapeHandleImpl>::downcast_ref(&*handle.0).expect("Shape handle must be a software Mesh")
+}
+
+struct Layer {
+    surface: Surface,
+    /// Whether this surface is, or is nested inside of, a `BlendMode::Layer`.
+    /// Alpha and Erase blends are ignored outside of layers.
+    in_layer: bool,
+}
+
+/// Executes a `CommandList` on the given surface.
+pub fn draw_commands(surface: Surface, commands: CommandList) -> Surface {
+    let mut renderer = CommandRenderer {
+        layers: vec![Layer {
+            surface,
+            in_layer: false,
+        }],
+    };
+    commands.execute(&mut renderer);
+    renderer
+        .layers
+        .pop()
+        .expect("Root layer must still exist")
+        .surface
+}
+
+struct CommandRenderer {
+    layers: Vec<Layer>,
+}
+
+impl CommandRenderer {
+    fn current(&mut self) -> &mut Layer {
+        self.layers
+            .last_mut()
+            .expect("There must always be a layer to draw on")
+    }
+
+    fn draw_quad(
+        &mut self,
+        matrix: Matrix,
+        color_transform: &ColorTransform,
+        bitmap: Option<(&BitmapHandle, bool)>,
+    ) {
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let positions = corners.map(|(x, y)| to_world(&matrix, x, y));
+        let mult = color_transform.mult_rgba_normalized();
+        let add = color_transform.add_rgba_normalized();
+        let surface = &mut self.current().surface;
+
+        for [i0, i1, i2] in [[0, 1, 2], [0, 2, 3]] {
+            let vertices = [positions[i0], positions[i1], positions[i2]];
+            match bitmap {
+                Some((bitmap, is_smoothed)) => {
+                    let texture = as_texture(bitmap);
+                    let pixels = texture.pixels();
+                    surface.fill_triangle(vertices, |barycentric| {
+                        let (u, v) =
+                            interpolate(barycentric, [corners[i0], corners[i1], corners[i2]]);
+                        let color = sample_texture(texture, &pixels, u, v, is_smoothed, false);
+                        transform_premultiplied(color, mult, add)
+                    });
+                }
+                None => {
+                    let color = transform_straight([1.0; 4], mult, add);
+                    surface.fill_triangle(vertices, |_| color);
+                }
+            }
+        }
+    }
+
+    /// Draws a bitmap through a projection, interpolating texture coordinates
+    /// in a perspective-correct way.
+    fn draw_projected_bitmap(
+        &mut self,
+        matrix3d: Matrix3D,
+        color_transform: &ColorTransform,
+        bitmap: &BitmapHandle,
+        is_smoothed: bool,
+    ) {
+        let texture = as_texture(bitmap);
+        let (width, height) = (f64::from(texture.width()), f64::from(texture.height()));
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)];
+        let projected = corners
+            .map(|(u, v)| matrix3d.transform_point(f64::from(u) * width, f64::from(v) * height));
+        if projected.iter().any(|p| p[3] <= 0.0) {
+            // Part of the bitmap is behind the viewer.
+            return;
+        }
+        let positions = projected.map(|[x, y, _, w]| Point {
+            x: (x / w) as f32,
+            y: (y / w) as f32,
+        });
+        let inverse_w = projected.map(|p| (1.0 / p[3]) as f32);
+        let mult = color_transform.mult_rgba_normalized();
+        let add = color_transform.add_rgba_normalized();
+        let pixels = texture.pixels();
+        let surface = &mut self.current().surface;
+
+        for [i0, i1, i2] in [[0, 1, 2], [0, 2, 3]] {
+            let vertices = [positions[i0], positions[i1], positions[i2]];
+            surface.fill_triangle(vertices, |barycentric| {
+                // u/w, v/w and 1/w are linear in screen space, whereas u and v are not.
+                let weights = [
+                    barycentric[0] * inverse_w[i0],
+                    barycentric[1] * inverse_w[i1],
+                    barycentric[2] * inverse_w[i2],
+                ];
+                let total = weights[0] + weights[1] + weights[2];
+                let (u, v) = interpolate(
+                    weights.map(|weight| weight / total),
+                    [corners[i0], corners[i1], corners[i2]],
+                );
+                let color = sample_texture(texture, &pixels, u, v, is_smoothed, false);
+                transform_premultiplied(color, mult, add)
+            });
+        }
+    }
+}
+
+impl CommandHandler for CommandRenderer {
+    fn render_bitmap(
+        &mut self,
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+        pixel_snapping: PixelSnapping,
+    ) {
+        if let Some(matrix3d) = transform.matrix3d {
+            self.draw_projected_bitmap(matrix3d, &transform.color_transform, &bitmap, smoothing);
+            return;
+        }
+        let mut matrix = transform.matrix;
+        {
+            let texture = as_texture(&bitmap);
+            pixel_snapping.apply(&mut matrix);
+            matrix *= Matrix::scale(texture.width() as f32, texture.height() as f32);
+        }
+        self.draw_quad(
+            matrix,
+            &transform.color_transform,
+            Some((&bitmap, smoothing)),
+        );
+    }
+
+    fn render_stage3d(&mut self, bitmap: BitmapHandle, transform: Transform) {
+        let mut matrix = transform.matrix;
+        {
+            let texture = as_texture(&bitmap);
+            matrix *= Matrix::scale(texture.width() as f32, texture.height() as f32);
+        }
+        self.draw_quad(matrix, &transform.color_transform, Some((&bitmap, false)));
+    }
+
+    /// Shapes are drawn with the 2D `matrix`, and any `matrix3d` is ignored,
+    /// like in the other backends.
+    fn render_shape(&mut self, shape: ShapeHandle, transform: Transform) {
+        let mesh = as_mesh(&shape);
+        let matrix = transform.matrix;
+        let mult = transform.color_transform.mult_rgba_normalized();
+        let add = transform.color_transform.add_rgba_normalized();
+        let surface = &mut self.current().surface;
+        let drawing_stencil = matches!(
+            surface.mask_state(),
+            MaskState::DrawMaskStencil | MaskState::ClearMaskStencil
+        );
+
+        for draw in &mesh.draws {
+            let indices = if drawing_stencil {
+                &draw.indices[..draw.num_mask_indices]
+            } else {
+                &draw.indices[..]
+            };
+            let texture = match &draw.draw_type {
+                DrawType::Bitmap { bitmap, .. } => {
+                    let texture = as_texture(bitmap);
+                    Some((texture, texture.pixels()))
+                }
+                _ => None,
+            };
+
+            for triangle in indices.chunks_exact(3) {
+                let local = [
+                    &draw.vertices[triangle[0] as usize],
+                    &draw.vertices[triangle[1] as usize],
+                    &draw.vertices[triangle[2] as usize],
+                ];
+                let vertices = local.map(|v| to_world(&matrix, v.x, v.y));
+                let positions = local.map(|v| (v.x, v.y));
+
+                match &draw.draw_type {
+                    DrawType::Color => {
+                        let colors = local.map(|v| {
+                            [
+                                f32::from(v.color.r) / 255.0,
+                                f32::from(v.color.g) / 255.0,
+                                f32::from(v.color.b) / 255.0,
+                                f32::from(v.color.a) / 255.0,
+                            ]
+                        });
+                        surface.fill_triangle(vertices, |barycentric| {
+                            let mut color = [0.0; 4];
+                            for (i, channel) in color.iter_mut().enumerate() {
+                                *channel = barycentric[0] * colors[0][i]
+                                    + barycentric[1] * colors[1][i]
+                                    + barycentric[2] * colors[2][i];
+                            }
+                            transform_straight(color, mult, add)
+                        });
+                    }
+                    DrawType::Gradient { matrix, gradient } => {
+                        let gradient = &mesh.gradients[*gradient];
+                        surface.fill_triangle(vertices, |barycentric| {
+                            let (x, y) = interpolate(barycentric, positions);
+                            let (u, v) = transform_uv(matrix, x, y);
+                            transform_straight(gradient.sample(u, v), mult, add)
+                        });
+                    }
+                    DrawType::Bitmap {
+                        matrix,
+                        is_smoothed,
+                        is_repeating,
+                        ..
+                    } => {
+                        let Some((texture, pixels)) = &texture else {
+                            continue;
+                        };
+                        surface.fill_triangle(vertices, |barycentric| {
+                            let (x, y) = interpolate(barycentric, positions);
+                            let (u, v) = transform_uv(matrix, x, y);
+                            let color =
+                                sample_texture(texture, pixels, u, v, *is_smoothed, *is_repeating);
+                            transform_premultiplied(color, mult, add)
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: Matrix) {
+        self.draw_quad(matrix, &ColorTransform::multiply_from(color), None);
+    }
+
+    fn draw_line(&mut self, color: Color, matrix: Matrix) {
+        emulate_line(self, color, matrix);
+    }
+
+    fn draw_line_rect(&mut self, color: Color, matrix: Matrix) {
+        emulate_line_rect(self, color, matrix);
+    }
+
+    fn push_mask(&mut self) {
+        let surface = &mut self.current().surface;
+        debug_assert!(matches!(
+            surface.mask_state(),
+            MaskState::NoMask | MaskState::DrawMaskedContent
+        ));
+        let num_masks = surface.num_masks() + 1;
+        surface.set_mask_state(num_masks, MaskState::DrawMaskStencil);
+    }
+
+    fn activate_mask(&mut self) {
+        let surface = &mut self.current().surface;
+        debug_assert!(surface.num_masks() > 0);
+        surface.set_mask_state(surface.num_masks(), MaskState::DrawMaskedContent);
+    }
+
+    fn deactivate_mask(&mut self) {
+        let surface = &mut self.current().surface;
+        debug_assert!(surface.num_masks() > 0);
+        surface.set_mask_state(surface.num_masks(), MaskState::ClearMaskStencil);
+    }
+
+    fn pop_mask(&mut self) {
+        let surface = &mut self.current().surface;
+        debug_assert!(surface.num_masks() > 0);
+        let num_masks = surface.num_masks().saturating_sub(1);
+        let mask_state = if num_masks == 0 {
+            MaskState::NoMask
+        } else {
+            MaskState::DrawMaskedContent
+        };
+        surface.set_mask_state(num_masks, mask_state);
+    }
+
+    fn blend(&mut self, commands: CommandList, blend_mode: RenderBlendMode) {
+        let parent = self.current();
+        let in_layer =
+            parent.in_layer || matches!(blend_mode, RenderBlendMode::Builtin(BlendMode::Layer));
+        let surface = parent.surface.new_layer();
+        self.layers.push(Layer { surface, in_layer });
+        commands.execute(self);
+        let layer = self.layers.pop().expect("Blend layer must still exist");
+
+        let parent = self.current();
+        match BlendType::from(blend_mode) {
+            BlendType::Trivial(blend) => parent.surface.composite_trivial(&layer.surface, blend),
+            BlendType::Complex(ComplexBlend::Alpha | ComplexBlend::Erase) if !parent.in_layer => {
+                // An Alpha or Erase with no Layer above it should be ignored
+            }
+            BlendType::Complex(blend) => parent.surface.composite_complex(&layer.surface, blend),
+            BlendType::Shader(shader) => blend_shader(&mut parent.surface, &layer.surface, &shader),
+        }
+    }
+}
+
+/// Runs a shader blend mode, passing the parent as the "background" input and the layer
+/// as the "foreground" input, and draws the output over the parent like the wgpu backend.
+fn blend_shader(parent: &mut Surface, layer: &Surface, shader: &PixelBenderShaderHandle) {
+    let (width, height) = (parent.width(), parent.height());
+    let input = |index: u8, name: &str, surface: &Surface| {
+        let texture = Texture::new(width, height, surface.resolve());
+        PixelBenderShaderArgument::ImageInput {
+            index,
+            channels: 0xFF,
+            name: name.to_string(),
+            texture: Some(BitmapHandle(Arc::new(texture)).into()),
+        }
+    };
+    let arguments = [
+        input(0, "background", parent),
+        input(1, "foreground", layer),
+    ];
+    match run_software_shader(shader, &arguments, width, height, OutOfRangeMode::Zero) {
+        Ok(output) => {
+            let output = parent.new_layer_from_pixels(&output.to_rgba8());
+            parent.composite_trivial(&output, TrivialBlend::Normal);
+        }
+        Err(error) => tracing::error!("Failed to run shader blend mode: {error}"),
+    }
+}
+
+fn to_world(matrix: &Matrix, x: f32, y: f32) -> Point {
+    Point {
+        x: matrix.a * x + matrix.c * y + matrix.tx.to_pixels() as f32,
+        y: matrix.b * x + matrix.d * y + matrix.ty.to_pixels() as f32,
+    }
+}
+
+fn interpolate(barycentric: [f32; 3], values: [(f32, f32); 3]) -> (f32, f32) {
+    (
+        barycentric[0] * values[0].0 + barycentric[1] * values[1].0 + barycentric[2] * values[2].0,
+        barycentric[0] * values[0].1 + barycentric[1] * values[1].1 + barycentric[2] * values[2].1,
+    )
+}
+
+/// Applies a color transform to a non-premultiplied color, returning a premultiplied color.
+fn transform_straight(color: Rgba, mult: [f32; 4], add: [f32; 4]) -> Rgba {
+    let alpha = (color[3] * mult[3] + add[3]).clamp(0.0, 1.0);
+    [
+        (color[0] * mult[0] + add[0]).clamp(0.0, 1.0) * alpha,
+        (color[1] * mult[1] + add[1]).clamp(0.0, 1.0) * alpha,
+        (color[2] * mult[2] + add[2]).clamp(0.0, 1.0) * alpha,
+        alpha,
+    ]
+}
+
+/// Applies a color transform to a premultiplied color, as sampled from a bitmap.
+///
+/// Fully transparent pixels are transformed too, as the additive terms can make them visible.
+fn transform_premultiplied(color: Rgba, mult: [f32; 4], add: [f32; 4]) -> Rgba {
+    let alpha = color[3];
+    let straight = if alpha > 0.0 {
+        [color[0] / alpha, color[1] / alpha, color[2] / alpha, alpha]
+    } else {
+        [0.0, 0.0, 0.0, 0.0]
+    };
+    transform_straight(straight, mult, add)
+}
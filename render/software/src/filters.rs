@@ -0,0 +1,229 @@
+use crate::pixel_bender::run_software_shader;
+use crate::{as_texture, Texture};
+use ruffle_render::bitmap::BitmapHandle;
+use ruffle_render::filters::{Filter, ShaderFilter};
+use ruffle_render::pixel_bender::interpreter::OutOfRangeMode;
+use ruffle_render::pixel_bender::PixelBenderShaderArgument;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, OnceLock};
+use swf::{BlurFilter, ColorMatrixFilter};
+
+/// A premultiplied RGBA image that filters operate on.
+pub struct FilterImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl FilterImage {
+    /// Copies the given area out of a larger premultiplied RGBA image.
+    pub fn from_region(
+        pixels: &[u8],
+        image_width: u32,
+        image_height: u32,
+        point: (u32, u32),
+        size: (u32, u32),
+    ) -> Self {
+        let mut out = vec![0; size.0 as usize * size.1 as usize * 4];
+        for y in 0..size.1 {
+            let source_y = point.1 + y;
+            if source_y >= image_height {
+                break;
+            }
+            let width = size.0.min(image_width.saturating_sub(point.0)) as usize;
+            let source_start = (source_y * image_width + point.0) as usize * 4;
+            let dest_start = (y * size.0) as usize * 4;
+            out[dest_start..dest_start + width * 4]
+                .copy_from_slice(&pixels[source_start..source_start + width * 4]);
+        }
+        Self {
+            width: size.0,
+            height: size.1,
+            pixels: out,
+        }
+    }
+
+    /// Copies this image into a larger premultiplied RGBA image, clipping it as needed.
+    pub fn copy_to(
+        &self,
+        pixels: &mut [u8],
+        image_width: u32,
+        image_height: u32,
+        point: (u32, u32),
+    ) {
+        let width = self.width.min(image_width.saturating_sub(point.0)) as usize;
+        for y in 0..self.height.min(image_height.saturating_sub(point.1)) {
+            let source_start = (y * self.width) as usize * 4;
+            let dest_start = ((point.1 + y) * image_width + point.0) as usize * 4;
+            pixels[dest_start..dest_start + width * 4]
+                .copy_from_slice(&self.pixels[source_start..source_start + width * 4]);
+        }
+    }
+}
+
+pub fn is_filter_supported(filter: &Filter) -> bool {
+    matches!(
+        filter,
+        Filter::BlurFilter(_) | Filter::ColorMatrixFilter(_) | Filter::ShaderFilter(_)
+    )
+}
+
+/// Applies a filter to an image, returning an image of the same size.
+/// Unsupported filters leave the image untouched.
+pub fn apply_filter(image: FilterImage, filter: &Filter) -> FilterImage {
+    match filter {
+        Filter::BlurFilter(filter) => apply_blur(image, filter),
+        Filter::ColorMatrixFilter(filter) => apply_color_matrix(image, filter),
+        Filter::ShaderFilter(filter) => apply_shader(image, filter),
+        filter => {
+            static WARNED_FILTERS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+            let name = match filter {
+                Filter::BevelFilter(_) => "BevelFilter",
+                Filter::ConvolutionFilter(_) => "ConvolutionFilter",
+                Filter::DisplacementMapFilter(_) => "DisplacementMapFilter",
+                Filter::DropShadowFilter(_) => "DropShadowFilter",
+                Filter::GlowFilter(_) => "GlowFilter",
+                Filter::GradientBevelFilter(_) => "GradientBevelFilter",
+                Filter::GradientGlowFilter(_) => "GradientGlowFilter",
+                Filter::BlurFilter(_) | Filter::ColorMatrixFilter(_) | Filter::ShaderFilter(_) => {
+                    unreachable!()
+                }
+            };
+            // Only warn once per filter type
+            if WARNED_FILTERS
+                .get_or_init(Default::default)
+                .lock()
+                .expect("Mutex should not be poisoned")
+                .insert(name)
+            {
+                tracing::warn!("Unsupported filter {filter:?}");
+            }
+            image
+        }
+    }
+}
+
+fn apply_color_matrix(mut image: FilterImage, filter: &ColorMatrixFilter) -> FilterImage {
+    let m = &filter.matrix;
+    for pixel in image.pixels.chunks_exact_mut(4) {
+        let a = f32::from(pixel[3]) / 255.0;
+        let (r, g, b) = if a > 0.0 {
+            (
+                f32::from(pixel[0]) / 255.0 / a,
+                f32::from(pixel[1]) / 255.0 / a,
+                f32::from(pixel[2]) / 255.0 / a,
+            )
+        } else {
+            (0.0, 0.0, 0.0)
+        };
+        let row = |i: usize| {
+            (m[i * 5] * r
+                + m[i * 5 + 1] * g
+                + m[i * 5 + 2] * b
+                + m[i * 5 + 3] * a
+                + m[i * 5 + 4] / 255.0)
+                .clamp(0.0, 1.0)
+        };
+        let out_a = row(3);
+        pixel[0] = (row(0) * out_a * 255.0).round() as u8;
+        pixel[1] = (row(1) * out_a * 255.0).round() as u8;
+        pixel[2] = (row(2) * out_a * 255.0).round() as u8;
+        pixel[3] = (out_a * 255.0).round() as u8;
+    }
+    image
+}
+
+/// Runs the shader of a `ShaderFilter`, with the image bound to its first image input.
+fn apply_shader(image: FilterImage, filter: &ShaderFilter) -> FilterImage {
+    let (width, height) = (image.width, image.height);
+    let source = BitmapHandle(Arc::new(Texture::new(width, height, image.pixels)));
+    let mut arguments = filter.shader_args.clone();
+    if let Some(PixelBenderShaderArgument::ImageInput { texture, .. }) = arguments
+        .iter_mut()
+        .find(|argument| matches!(argument, PixelBenderShaderArgument::ImageInput { .. }))
+    {
+        *texture = Some(source.clone().into());
+    }
+
+    let pixels = match run_software_shader(
+        &filter.shader,
+        &arguments,
+        width,
+        height,
+        OutOfRangeMode::Zero,
+    ) {
+        Ok(output) => output.to_rgba8(),
+        Err(error) => {
+            tracing::error!("Failed to run shader filter: {error}");
+            as_texture(&source).pixels().clone()
+        }
+    };
+    FilterImage {
+        width,
+        height,
+        pixels,
+    }
+}
+
+/// A box blur with fractional kernel sizes, matching the algorithm used by the wgpu backend.
+/// See https://fgiesen.wordpress.com/2012/08/01/fast-blurs-2/ for details.
+fn apply_blur(mut image: FilterImage, filter: &BlurFilter) -> FilterImage {
+    let (width, height) = (image.width as usize, image.height as usize);
+    let mut scratch = vec![0.0f32; width.max(height) * 4];
+
+    for _ in 0..filter.num_passes() {
+        for horizontal in [true, false] {
+            let strength = if horizontal {
+                filter.blur_x.to_f32()
+            } else {
+                filter.blur_y.to_f32()
+            };
+            // Full width of the kernel (left edge to right edge)
+            let full_size = strength.min(255.0);
+            if full_size <= 1.0 {
+                // A width of 1 or less is a noop (it'd just sample itself and nothing else)
+                continue;
+            }
+
+            // This is how much the blur "extends past" the center pixel to either side.
+            let radius = (full_size - 1.0) / 2.0;
+            // The number of pixels on each side of the center that have a weight of 1.
+            let m = radius.ceil() as isize - 1;
+            // The weight of the outermost pixel on each side, rounded like Flash Player does.
+            let alpha = ((radius - m as f32) * 255.0).floor() / 255.0;
+
+            let (lines, length, step, line_step) = if horizontal {
+                (height, width, 4, width * 4)
+            } else {
+                (width, height, width * 4, 4)
+            };
+            for line in 0..lines {
+                let start = line * line_step;
+                let texel = |pixels: &[u8], i: isize, c: usize| -> f32 {
+                    if i < 0 || i as usize >= length {
+                        0.0
+                    } else {
+                        f32::from(pixels[start + i as usize * step + c])
+                    }
+                };
+                for c in 0..4 {
+                    // Running sum of the pixels with a weight of 1, from `i - m` to `i + m`.
+                    let mut center: f32 = (-m..=m).map(|j| texel(&image.pixels, j, c)).sum();
+                    for i in 0..length as isize {
+                        let edges =
+                            texel(&image.pixels, i - m - 1, c) + texel(&image.pixels, i + m + 1, c);
+                        scratch[i as usize * 4 + c] = (center + edges * alpha) / full_size;
+                        center +=
+                            texel(&image.pixels, i + m + 1, c) - texel(&image.pixels, i - m, c);
+                    }
+                }
+                for i in 0..length {
+                    for c in 0..4 {
+                        image.pixels[start + i * step + c] = scratch[i * 4 + c].floor() as u8;
+                    }
+                }
+            }
+        }
+    }
+    image
+}
@@ -0,0 +1,69 @@
+#![deny(clippy::unwrap_used)]
+// Bitmap handles are `Arc`s, but the pixel data lives in a `RefCell`
+#![allow(clippy::arc_with_non_send_sync)]
+
+//! A render backend that rasterizes entirely on the CPU.
+//!
+//! This is slower than the GPU backends, but it doesn't need any graphics
+//! device to be present, which makes it suitable for headless environments
+//! such as CI machines and the exporter.
+
+use ruffle_render::bitmap::{BitmapHandle, BitmapHandleImpl};
+use std::cell::{Ref, RefCell, RefMut};
+
+mod backend;
+mod blend;
+mod commands;
+mod filters;
+mod mesh;
+mod pixel_bender;
+mod surface;
+
+pub use backend::SoftwareRenderBackend;
+
+/// A bitmap owned by the software renderer.
+///
+/// The pixels are stored as premultiplied RGBA, 4 bytes per pixel.
+#[derive(Debug)]
+pub struct Texture {
+    width: u32,
+    height: u32,
+    pixels: RefCell<Vec<u8>>,
+}
+
+impl Texture {
+    fn new(width: u32, height: u32, pixels: Vec<u8>) -> Self {
+        debug_assert_eq!(pixels.len(), width as usize * height as usize * 4);
+        Self {
+            width,
+            height,
+            pixels: RefCell::new(pixels),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> Ref<'_, Vec<u8>> {
+        self.pixels.borrow()
+    }
+
+    fn pixels_mut(&self) -> RefMut<'_, Vec<u8>> {
+        self.pixels.borrow_mut()
+    }
+}
+
+impl BitmapHandleImpl for Texture {}
+
+pub fn as_texture(handle: &BitmapHandle) -> &Texture {
+    <dyn BitmapHandleImpl>::downcast_ref(&*handle.0)
+        .expect("Bitmap handle must be a software Texture")
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,252 @@
+use crate::blend::Rgba;
+use crate::Texture;
+use ruffle_render::backend::ShapeHandleImpl;
+use ruffle_render::bitmap::BitmapHandle;
+use ruffle_render::shape_utils::GradientType;
+use ruffle_render::tessellator::{Gradient as TessGradient, Vertex};
+use swf::{GradientInterpolation, GradientSpread};
+
+const GRADIENT_SIZE: usize = 256;
+
+#[derive(Debug)]
+pub struct Mesh {
+    pub draws: Vec<Draw>,
+    pub gradients: Vec<Gradient>,
+}
+
+impl ShapeHandleImpl for Mesh {}
+
+#[derive(Debug)]
+pub struct Draw {
+    pub draw_type: DrawType,
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+    pub num_mask_indices: usize,
+}
+
+#[derive(Debug)]
+pub enum DrawType {
+    Color,
+    Gradient {
+        matrix: [[f32; 3]; 3],
+        gradient: usize,
+    },
+    Bitmap {
+        matrix: [[f32; 3]; 3],
+        bitmap: BitmapHandle,
+        is_smoothed: bool,
+        is_repeating: bool,
+    },
+}
+
+/// Applies a column-major texture matrix (as produced by the tessellator) to a point.
+pub fn transform_uv(matrix: &[[f32; 3]; 3], x: f32, y: f32) -> (f32, f32) {
+    (
+        matrix[0][0] * x + matrix[1][0] * y + matrix[2][0],
+        matrix[0][1] * x + matrix[1][1] * y + matrix[2][1],
+    )
+}
+
+/// A gradient fill, baked into a lookup table the same way the wgpu backend does.
+#[derive(Debug)]
+pub struct Gradient {
+    colors: Box<[[u8; 4]; GRADIENT_SIZE]>,
+    gradient_type: GradientType,
+    repeat_mode: GradientSpread,
+    focal_point: f32,
+    interpolation: GradientInterpolation,
+}
+
+impl Gradient {
+    pub fn new(gradient: TessGradient) -> Self {
+        let mut colors = Box::new([[0; 4]; GRADIENT_SIZE]);
+        if !gradient.records.is_empty() {
+            let convert = if gradient.interpolation == GradientInterpolation::LinearRgb {
+                |c| srgb_to_linear(c / 255.0) * 255.0
+            } else {
+                |c| c
+            };
+
+            for (t, color) in colors.iter_mut().enumerate() {
+                let mut last = 0;
+                let mut next = 0;
+                for (i, record) in gradient.records.iter().enumerate().rev() {
+                    if (record.ratio as usize) < t {
+                        last = i;
+                        next = (i + 1).min(gradient.records.len() - 1);
+                        break;
+                    }
+                }
+
+                let last_record = &gradient.records[last];
+                let next_record = &gradient.records[next];
+                let a = if next == last {
+                    // This can happen if we are before the first gradient record, or after the last one
+                    0.0
+                } else {
+                    (t as f32 - last_record.ratio as f32)
+                        / (next_record.ratio as f32 - last_record.ratio as f32)
+                };
+                *color = [
+                    lerp(
+                        convert(last_record.color.r as f32),
+                        convert(next_record.color.r as f32),
+                        a,
+                    ) as u8,
+                    lerp(
+                        convert(last_record.color.g as f32),
+                        convert(next_record.color.g as f32),
+                        a,
+                    ) as u8,
+                    lerp(
+                        convert(last_record.color.b as f32),
+                        convert(next_record.color.b as f32),
+                        a,
+                    ) as u8,
+                    lerp(last_record.color.a as f32, next_record.color.a as f32, a) as u8,
+                ];
+            }
+        }
+
+        Self {
+            colors,
+            gradient_type: gradient.gradient_type,
+            repeat_mode: gradient.repeat_mode,
+            focal_point: gradient.focal_point.to_f32().clamp(-0.98, 0.98),
+            interpolation: gradient.interpolation,
+        }
+    }
+
+    /// Returns the (non-premultiplied) color of the gradient at the given gradient space position.
+    pub fn sample(&self, u: f32, v: f32) -> Rgba {
+        let t = match self.gradient_type {
+            GradientType::Linear => u,
+            GradientType::Radial => {
+                let (x, y) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                (x * x + y * y).sqrt()
+            }
+            GradientType::Focal => {
+                let (x, y) = (u * 2.0 - 1.0, v * 2.0 - 1.0);
+                let focal = self.focal_point;
+                let (dx, dy) = (focal - x, -y);
+                let l = (dx * dx + dy * dy).sqrt();
+                if l == 0.0 {
+                    0.0
+                } else {
+                    let (dx, dy) = (dx / l, dy / l);
+                    l / ((1.0 - focal * focal * dy * dy).sqrt() + focal * dx)
+                }
+            }
+        };
+
+        let t = match self.repeat_mode {
+            GradientSpread::Pad => t.clamp(0.0, 1.0),
+            GradientSpread::Reflect => {
+                let t = t.abs();
+                if (t as i32) & 1 == 0 {
+                    t.fract()
+                } else {
+                    1.0 - t.fract()
+                }
+            }
+            GradientSpread::Repeat => t - t.floor(),
+        };
+
+        // Linearly filter between the two nearest entries, like a texture sampler would.
+        let position = (t * GRADIENT_SIZE as f32 - 0.5).clamp(0.0, (GRADIENT_SIZE - 1) as f32);
+        let index = position as usize;
+        let next = (index + 1).min(GRADIENT_SIZE - 1);
+        let fraction = position - index as f32;
+        let mut color = [0.0; 4];
+        for (i, channel) in color.iter_mut().enumerate() {
+            *channel = lerp(
+                f32::from(self.colors[index][i]),
+                f32::from(self.colors[next][i]),
+                fraction,
+            ) / 255.0;
+        }
+
+        if self.interpolation == GradientInterpolation::LinearRgb {
+            for channel in &mut color[..3] {
+                *channel = linear_to_srgb(*channel);
+            }
+        }
+        color
+    }
+}
+
+/// Samples a texture at the given normalized coordinates, returning a premultiplied color.
+pub fn sample_texture(
+    texture: &Texture,
+    pixels: &[u8],
+    u: f32,
+    v: f32,
+    is_smoothed: bool,
+    is_repeating: bool,
+) -> Rgba {
+    let width = texture.width() as i32;
+    let height = texture.height() as i32;
+    let texel = |x: i32, y: i32| -> Rgba {
+        let (x, y) = if is_repeating {
+            (x.rem_euclid(width), y.rem_euclid(height))
+        } else {
+            (x.clamp(0, width - 1), y.clamp(0, height - 1))
+        };
+        let offset = (y * width + x) as usize * 4;
+        [
+            f32::from(pixels[offset]) / 255.0,
+            f32::from(pixels[offset + 1]) / 255.0,
+            f32::from(pixels[offset + 2]) / 255.0,
+            f32::from(pixels[offset + 3]) / 255.0,
+        ]
+    };
+
+    if width == 0 || height == 0 || !u.is_finite() || !v.is_finite() {
+        return [0.0; 4];
+    }
+
+    let x = u * width as f32;
+    let y = v * height as f32;
+    if !is_smoothed {
+        return texel(x.floor() as i32, y.floor() as i32);
+    }
+
+    let x = x - 0.5;
+    let y = y - 0.5;
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let (x0, y0) = (x0 as i32, y0 as i32);
+    let top_left = texel(x0, y0);
+    let top_right = texel(x0 + 1, y0);
+    let bottom_left = texel(x0, y0 + 1);
+    let bottom_right = texel(x0 + 1, y0 + 1);
+    let mut color = [0.0; 4];
+    for (i, channel) in color.iter_mut().enumerate() {
+        let top = lerp(top_left[i], top_right[i], fx);
+        let bottom = lerp(bottom_left[i], bottom_right[i], fx);
+        *channel = lerp(top, bottom, fy);
+    }
+    color
+}
+
+/// Converts an RGBA color from sRGB space to linear color space.
+fn srgb_to_linear(color: f32) -> f32 {
+    if color <= 0.04045 {
+        color / 12.92
+    } else {
+        f32::powf((color + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Converts a color from linear to sRGB color space.
+fn linear_to_srgb(color: f32) -> f32 {
+    if color < 0.0031308 {
+        color * 12.92
+    } else {
+        1.055 * color.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
@@ -0,0 +1,31 @@
+use crate::as_texture;
+use ruffle_render::error::Error;
+use ruffle_render::pixel_bender::interpreter::{
+    run_shader, OutOfRangeMode, ShaderImage, ShaderOutput,
+};
+use ruffle_render::pixel_bender::{PixelBenderShaderArgument, PixelBenderShaderHandle};
+
+/// Runs a shader over a `width` by `height` output, reading bitmap inputs from software textures.
+pub fn run_software_shader(
+    shader: &PixelBenderShaderHandle,
+    arguments: &[PixelBenderShaderArgument],
+    width: u32,
+    height: u32,
+    mode: OutOfRangeMode,
+) -> Result<ShaderOutput, Error> {
+    run_shader(
+        shader.0.parsed_shader(),
+        arguments,
+        width,
+        height,
+        mode,
+        |bitmap| {
+            let texture = as_texture(bitmap);
+            Ok(ShaderImage::from_rgba8(
+                texture.width(),
+                texture.height(),
+                &texture.pixels(),
+            ))
+        },
+    )
+}
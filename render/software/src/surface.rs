@@ -0,0 +1,291 @@
+use crate::blend::{ComplexBlend, Rgba, TrivialBlend};
+use ruffle_render::quality::StageQuality;
+
+/// How stencil-based masking affects the next draws, using the same scheme as the WebGL backend.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MaskState {
+    NoMask,
+    DrawMaskStencil,
+    DrawMaskedContent,
+    ClearMaskStencil,
+}
+
+/// A point in pixel coordinates of a surface.
+#[derive(Clone, Copy, Debug)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// A supersampled render target.
+///
+/// Every pixel is backed by `scale * scale` samples, which are averaged together
+/// when the surface is resolved. Colors are stored as premultiplied RGBA.
+pub struct Surface {
+    width: u32,
+    height: u32,
+    scale: u32,
+    samples: Vec<[u8; 4]>,
+    stencil: Vec<u8>,
+    num_masks: u32,
+    mask_state: MaskState,
+}
+
+impl Surface {
+    pub fn new(width: u32, height: u32, quality: StageQuality, clear: [u8; 4]) -> Self {
+        let scale = samples_per_axis(quality);
+        let len = (width * scale) as usize * (height * scale) as usize;
+        Self {
+            width,
+            height,
+            scale,
+            samples: vec![clear; len],
+            stencil: vec![0; len],
+            num_masks: 0,
+            mask_state: MaskState::NoMask,
+        }
+    }
+
+    /// Creates a surface starting out with the given premultiplied RGBA pixels.
+    pub fn from_pixels(width: u32, height: u32, quality: StageQuality, pixels: &[u8]) -> Self {
+        let mut surface = Self::new(width, height, quality, [0; 4]);
+        surface.fill_from_pixels(pixels);
+        surface
+    }
+
+    /// Creates an empty surface with the same size and quality as this one.
+    pub fn new_layer(&self) -> Self {
+        let len = self.samples.len();
+        Self {
+            width: self.width,
+            height: self.height,
+            scale: self.scale,
+            samples: vec![[0; 4]; len],
+            stencil: vec![0; len],
+            num_masks: 0,
+            mask_state: MaskState::NoMask,
+        }
+    }
+
+    /// Creates a surface with the same size and quality as this one,
+    /// starting out with the given premultiplied RGBA pixels.
+    pub fn new_layer_from_pixels(&self, pixels: &[u8]) -> Self {
+        let mut surface = self.new_layer();
+        surface.fill_from_pixels(pixels);
+        surface
+    }
+
+    /// Sets every sample of every pixel to the given premultiplied RGBA pixels.
+    fn fill_from_pixels(&mut self, pixels: &[u8]) {
+        let scale = self.scale as usize;
+        let sample_width = self.sample_width();
+        for (y, row) in pixels.chunks_exact(self.width as usize * 4).enumerate() {
+            for (x, pixel) in row.chunks_exact(4).enumerate() {
+                let color = [pixel[0], pixel[1], pixel[2], pixel[3]];
+                for sy in 0..scale {
+                    let start = (y * scale + sy) * sample_width + x * scale;
+                    self.samples[start..start + scale].fill(color);
+                }
+            }
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn sample_width(&self) -> usize {
+        (self.width * self.scale) as usize
+    }
+
+    fn sample_height(&self) -> usize {
+        (self.height * self.scale) as usize
+    }
+
+    pub fn num_masks(&self) -> u32 {
+        self.num_masks
+    }
+
+    pub fn set_mask_state(&mut self, num_masks: u32, mask_state: MaskState) {
+        self.num_masks = num_masks;
+        self.mask_state = mask_state;
+    }
+
+    pub fn mask_state(&self) -> MaskState {
+        self.mask_state
+    }
+
+    /// Averages the samples of every pixel, returning premultiplied RGBA pixels.
+    pub fn resolve(&self) -> Vec<u8> {
+        let scale = self.scale as usize;
+        let count = (scale * scale) as u32;
+        let sample_width = self.sample_width();
+        let mut out = Vec::with_capacity(self.width as usize * self.height as usize * 4);
+        for y in 0..self.height as usize {
+            for x in 0..self.width as usize {
+                let mut sum = [0u32; 4];
+                for sy in 0..scale {
+                    let start = (y * scale + sy) * sample_width + x * scale;
+                    for sample in &self.samples[start..start + scale] {
+                        for (total, value) in sum.iter_mut().zip(sample) {
+                            *total += u32::from(*value);
+                        }
+                    }
+                }
+                out.extend(sum.map(|total| ((total + count / 2) / count) as u8));
+            }
+        }
+        out
+    }
+
+    /// Runs the stencil test for a single sample, updating the stencil buffer if needed.
+    /// Returns whether the color of this sample may be written.
+    fn stencil_test(&mut self, index: usize) -> bool {
+        let stencil = &mut self.stencil[index];
+        match self.mask_state {
+            MaskState::NoMask => true,
+            MaskState::DrawMaskStencil => {
+                if u32::from(*stencil) + 1 == self.num_masks {
+                    *stencil = stencil.wrapping_add(1);
+                }
+                false
+            }
+            MaskState::DrawMaskedContent => u32::from(*stencil) == self.num_masks,
+            MaskState::ClearMaskStencil => {
+                if u32::from(*stencil) == self.num_masks {
+                    *stencil = stencil.wrapping_sub(1);
+                }
+                false
+            }
+        }
+    }
+
+    /// Rasterizes a triangle given in pixel coordinates, drawing it with normal blending.
+    ///
+    /// `shade` is called with the barycentric coordinates of every covered sample
+    /// that passes the stencil test, and returns the premultiplied color of that sample.
+    pub fn fill_triangle(&mut self, vertices: [Point; 3], mut shade: impl FnMut([f32; 3]) -> Rgba) {
+        let scale = self.scale as f64;
+        let [v0, v1, v2] = vertices.map(|v| (f64::from(v.x) * scale, f64::from(v.y) * scale));
+
+        let mut area = edge(v0, v1, v2);
+        if !area.is_finite() || area == 0.0 {
+            return;
+        }
+        // Normalize the winding so that the inside of the triangle is always positive.
+        let flipped = area < 0.0;
+        let (v1, v2) = if flipped {
+            area = -area;
+            (v2, v1)
+        } else {
+            (v1, v2)
+        };
+
+        let min_x = v0.0.min(v1.0).min(v2.0).floor().max(0.0) as usize;
+        let min_y = v0.1.min(v1.1).min(v2.1).floor().max(0.0) as usize;
+        let max_x = (v0.0.max(v1.0).max(v2.0).ceil() as usize).min(self.sample_width());
+        let max_y = (v0.1.max(v1.1).max(v2.1).ceil() as usize).min(self.sample_height());
+
+        let bias = [
+            is_top_left(v1, v2),
+            is_top_left(v2, v0),
+            is_top_left(v0, v1),
+        ];
+        let sample_width = self.sample_width();
+        for y in min_y..max_y {
+            let py = y as f64 + 0.5;
+            for x in min_x..max_x {
+                let p = (x as f64 + 0.5, py);
+                let w = [edge(v1, v2, p), edge(v2, v0, p), edge(v0, v1, p)];
+                let inside = w
+                    .iter()
+                    .zip(bias)
+                    .all(|(w, top_left)| *w > 0.0 || (*w == 0.0 && top_left));
+                if !inside {
+                    continue;
+                }
+
+                let index = y * sample_width + x;
+                if !self.stencil_test(index) {
+                    continue;
+                }
+
+                let mut barycentric = [
+                    (w[0] / area) as f32,
+                    (w[1] / area) as f32,
+                    (w[2] / area) as f32,
+                ];
+                if flipped {
+                    barycentric.swap(1, 2);
+                }
+                let src = shade(barycentric);
+                let dst = to_rgba(self.samples[index]);
+                self.samples[index] = from_rgba(TrivialBlend::Normal.apply(src, dst));
+            }
+        }
+    }
+
+    /// Composites an entire layer onto this surface with a trivial blend mode.
+    pub fn composite_trivial(&mut self, layer: &Surface, blend: TrivialBlend) {
+        for index in 0..self.samples.len() {
+            let src = layer.samples[index];
+            if src[3] == 0 && matches!(blend, TrivialBlend::Normal) {
+                continue;
+            }
+            if !self.stencil_test(index) {
+                continue;
+            }
+            let dst = to_rgba(self.samples[index]);
+            self.samples[index] = from_rgba(blend.apply(to_rgba(src), dst));
+        }
+    }
+
+    /// Composites an entire layer onto this surface with a complex blend mode.
+    pub fn composite_complex(&mut self, layer: &Surface, blend: ComplexBlend) {
+        for index in 0..self.samples.len() {
+            let src = to_rgba(layer.samples[index]);
+            let dst = to_rgba(self.samples[index]);
+            let Some(out) = blend.apply(src, dst) else {
+                continue;
+            };
+            if !self.stencil_test(index) {
+                continue;
+            }
+            self.samples[index] = from_rgba(out);
+        }
+    }
+}
+
+/// The number of samples taken along each axis of a pixel, approximating the MSAA
+/// sample counts used by the GPU backends.
+fn samples_per_axis(quality: StageQuality) -> u32 {
+    match quality.sample_count() {
+        0 | 1 => 1,
+        2..=4 => 2,
+        _ => 4,
+    }
+}
+
+fn edge(a: (f64, f64), b: (f64, f64), p: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+}
+
+/// Whether an edge is a top or left edge of a positively wound triangle.
+/// Samples exactly on such edges are considered inside, so that adjacent
+/// triangles never draw the same sample twice.
+fn is_top_left(a: (f64, f64), b: (f64, f64)) -> bool {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    (dy == 0.0 && dx > 0.0) || dy < 0.0
+}
+
+pub fn to_rgba(color: [u8; 4]) -> Rgba {
+    color.map(|c| f32::from(c) / 255.0)
+}
+
+pub fn from_rgba(color: Rgba) -> [u8; 4] {
+    color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
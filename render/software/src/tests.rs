@@ -0,0 +1,350 @@
+use crate::SoftwareRenderBackend;
+use ruffle_render::backend::null::NullBitmapSource;
+use ruffle_render::backend::{RenderBackend, ShapeHandle};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle, PixelRegion, PixelSnapping};
+use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::quality::StageQuality;
+use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
+use ruffle_render::transform::Transform;
+use swf::{
+    BlendMode, Color, ColorTransform, FillStyle, Fixed8, Gradient, GradientInterpolation,
+    GradientRecord, GradientSpread, Point, Rectangle, Twips,
+};
+
+const SIZE: u32 = 20;
+
+/// Submits a frame with the given commands, and returns its straight RGBA pixels.
+fn render(clear: Color, commands: CommandList) -> image::RgbaImage {
+    let mut backend = SoftwareRenderBackend::new(SIZE, SIZE);
+    backend.submit_frame(clear, commands, vec![]);
+    backend.capture_frame()
+}
+
+fn rect(x: f64, y: f64, width: f32, height: f32) -> Matrix {
+    Matrix::create_box(width, height, Twips::from_pixels(x), Twips::from_pixels(y))
+}
+
+fn assert_pixel(image: &image::RgbaImage, x: u32, y: u32, expected: [u8; 4]) {
+    let actual = image.get_pixel(x, y).0;
+    let matches = actual
+        .iter()
+        .zip(expected)
+        .all(|(actual, expected)| actual.abs_diff(expected) <= 2);
+    assert!(
+        matches,
+        "Pixel at ({x}, {y}) is {actual:?}, expected {expected:?}"
+    );
+}
+
+/// Registers a shape covering the whole frame with the given fill.
+fn register_square_shape(backend: &mut SoftwareRenderBackend, style: &FillStyle) -> ShapeHandle {
+    let size = Twips::from_pixels(SIZE.into());
+    let shape = DistilledShape {
+        paths: vec![DrawPath::Fill {
+            style,
+            commands: vec![
+                DrawCommand::MoveTo(Point::new(Twips::ZERO, Twips::ZERO)),
+                DrawCommand::LineTo(Point::new(size, Twips::ZERO)),
+                DrawCommand::LineTo(Point::new(size, size)),
+                DrawCommand::LineTo(Point::new(Twips::ZERO, size)),
+                DrawCommand::LineTo(Point::new(Twips::ZERO, Twips::ZERO)),
+            ],
+            winding_rule: FillRule::EvenOdd,
+        }],
+        shape_bounds: Rectangle {
+            x_min: Twips::ZERO,
+            x_max: size,
+            y_min: Twips::ZERO,
+            y_max: size,
+        },
+        edge_bounds: Rectangle {
+            x_min: Twips::ZERO,
+            x_max: size,
+            y_min: Twips::ZERO,
+            y_max: size,
+        },
+        id: 1,
+    };
+    backend.register_shape(shape, &NullBitmapSource)
+}
+
+/// Draws a shape covering the whole frame with the given fill.
+fn register_square(backend: &mut SoftwareRenderBackend, style: &FillStyle) -> CommandList {
+    let handle = register_square_shape(backend, style);
+    let mut commands = CommandList::new();
+    commands.render_shape(handle, Transform::default());
+    commands
+}
+
+/// A 2x2 bitmap, whose pixels are red, green, blue and transparent.
+fn register_bitmap(backend: &mut SoftwareRenderBackend) -> BitmapHandle {
+    #[rustfmt::skip]
+    let pixels = vec![
+        255, 0, 0, 255,   0, 255, 0, 255,
+        0, 0, 255, 255,   0, 0, 0, 0,
+    ];
+    backend
+        .register_bitmap(Bitmap::new(2, 2, BitmapFormat::Rgba, pixels))
+        .expect("Bitmap must be registered")
+}
+
+#[test]
+fn solid_rect() {
+    let mut commands = CommandList::new();
+    commands.draw_rect(Color::RED, rect(5.0, 5.0, 10.0, 10.0));
+    let image = render(Color::WHITE, commands);
+
+    assert_pixel(&image, 10, 10, [255, 0, 0, 255]);
+    assert_pixel(&image, 2, 2, [255, 255, 255, 255]);
+    assert_pixel(&image, 17, 17, [255, 255, 255, 255]);
+}
+
+#[test]
+fn solid_shape_fill() {
+    let mut backend = SoftwareRenderBackend::new(SIZE, SIZE);
+    let style = FillStyle::Color(Color::from_rgba(0x800000FF));
+    let commands = register_square(&mut backend, &style);
+    backend.submit_frame(Color::WHITE, commands, vec![]);
+    let image = backend.capture_frame();
+
+    // Half transparent blue over white.
+    assert_pixel(&image, 10, 10, [127, 127, 255, 255]);
+}
+
+#[test]
+fn linear_gradient_fill() {
+    let mut backend = SoftwareRenderBackend::new(SIZE, SIZE);
+    let size = SIZE as f32;
+    let style = FillStyle::LinearGradient(Gradient {
+        matrix: Matrix::create_gradient_box(size, size, 0.0, Twips::ZERO, Twips::ZERO).into(),
+        spread: GradientSpread::Pad,
+        interpolation: GradientInterpolation::Rgb,
+        records: vec![
+            GradientRecord {
+                ratio: 0,
+                color: Color::BLACK,
+            },
+            GradientRecord {
+                ratio: 255,
+                color: Color::WHITE,
+            },
+        ],
+    });
+    let commands = register_square(&mut backend, &style);
+    backend.submit_frame(Color::RED, commands, vec![]);
+    let image = backend.capture_frame();
+
+    let left = image.get_pixel(0, 10).0;
+    let middle = image.get_pixel(10, 10).0;
+    let right = image.get_pixel(19, 10).0;
+    assert!(left[0] < 20, "Left edge should be black, got {left:?}");
+    assert!(right[0] > 235, "Right edge should be white, got {right:?}");
+    assert!(
+        (112..=144).contains(&middle[0]),
+        "Middle should be gray, got {middle:?}"
+    );
+    assert_eq!(middle[0], middle[1]);
+    assert_eq!(middle[1], middle[2]);
+}
+
+#[test]
+fn bitmap() {
+    let mut backend = SoftwareRenderBackend::new(SIZE, SIZE);
+    let bitmap = register_bitmap(&mut backend);
+    let mut commands = CommandList::new();
+    commands.render_bitmap(
+        bitmap,
+        Transform {
+            matrix: Matrix::scale(10.0, 10.0),
+            ..Default::default()
+        },
+        false,
+        PixelSnapping::Never,
+    );
+    backend.submit_frame(Color::WHITE, commands, vec![]);
+    let image = backend.capture_frame();
+
+    assert_pixel(&image, 5, 5, [255, 0, 0, 255]);
+    assert_pixel(&image, 15, 5, [0, 255, 0, 255]);
+    assert_pixel(&image, 5, 15, [0, 0, 255, 255]);
+    assert_pixel(&image, 15, 15, [255, 255, 255, 255]);
+}
+
+/// Draws a bitmap over the whole frame.
+fn render_bitmap_over_frame(backend: &mut SoftwareRenderBackend, bitmap: BitmapHandle, scale: f32) {
+    let mut commands = CommandList::new();
+    commands.render_bitmap(
+        bitmap,
+        Transform {
+            matrix: Matrix::scale(scale, scale),
+            ..Default::default()
+        },
+        false,
+        PixelSnapping::Never,
+    );
+    backend.submit_frame(Color::WHITE, commands, vec![]);
+}
+
+#[test]
+fn update_texture_clamps_region() {
+    let mut backend = SoftwareRenderBackend::new(SIZE, SIZE);
+    let bitmap = register_bitmap(&mut backend);
+    let white = || Bitmap::new(2, 2, BitmapFormat::Rgba, vec![255; 16]);
+
+    // Entirely outside of the texture.
+    backend
+        .update_texture(&bitmap, white(), PixelRegion::for_region(5, 5, 3, 3))
+        .expect("Texture must be updated");
+    // Partly outside of the texture, only changing its right column.
+    backend
+        .update_texture(&bitmap, white(), PixelRegion::for_region(1, 0, 4, 4))
+        .expect("Texture must be updated");
+    render_bitmap_over_frame(&mut backend, bitmap, 10.0);
+    let image = backend.capture_frame();
+
+    assert_pixel(&image, 5, 5, [255, 0, 0, 255]);
+    assert_pixel(&image, 5, 15, [0, 0, 255, 255]);
+    assert_pixel(&image, 15, 5, [255, 255, 255, 255]);
+    assert_pixel(&image, 15, 15, [255, 255, 255, 255]);
+}
+
+#[test]
+fn render_offscreen_bounds() {
+    let mut backend = SoftwareRenderBackend::new(SIZE, SIZE);
+    let texture = backend
+        .create_empty_texture(SIZE, SIZE)
+        .expect("Texture must be created");
+    let mut commands = CommandList::new();
+    commands.draw_rect(Color::RED, rect(0.0, 0.0, 20.0, 20.0));
+    backend.render_offscreen(
+        texture.clone(),
+        commands,
+        StageQuality::High,
+        PixelRegion::for_region(0, 0, 10, SIZE),
+    );
+    render_bitmap_over_frame(&mut backend, texture, 1.0);
+    let image = backend.capture_frame();
+
+    assert_pixel(&image, 5, 10, [255, 0, 0, 255]);
+    // Pixels outside of the bounds are left transparent.
+    assert_pixel(&image, 15, 10, [255, 255, 255, 255]);
+}
+
+#[test]
+fn shape_ignores_matrix3d() {
+    let mut backend = SoftwareRenderBackend::new(SIZE, SIZE);
+    let style = FillStyle::Color(Color::BLUE);
+    let shape = register_square_shape(&mut backend, &style);
+    let mut commands = CommandList::new();
+    commands.render_shape(
+        shape,
+        Transform {
+            matrix3d: Some(Matrix3D::scale(0.5, 0.5, 1.0)),
+            ..Default::default()
+        },
+    );
+    backend.submit_frame(Color::WHITE, commands, vec![]);
+    let image = backend.capture_frame();
+
+    // Only bitmaps are projected, so the square still covers the whole frame.
+    assert_pixel(&image, 15, 15, [0, 0, 255, 255]);
+}
+
+#[test]
+fn bitmap_color_transform() {
+    let mut backend = SoftwareRenderBackend::new(SIZE, SIZE);
+    let bitmap = register_bitmap(&mut backend);
+    let mut commands = CommandList::new();
+    commands.render_bitmap(
+        bitmap,
+        Transform {
+            matrix: Matrix::scale(10.0, 10.0),
+            color_transform: ColorTransform {
+                r_multiply: Fixed8::from_f32(0.5),
+                b_add: 128,
+                a_add: 255,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        false,
+        PixelSnapping::Never,
+    );
+    backend.submit_frame(Color::WHITE, commands, vec![]);
+    let image = backend.capture_frame();
+
+    assert_pixel(&image, 5, 5, [128, 0, 128, 255]);
+    assert_pixel(&image, 5, 15, [0, 0, 255, 255]);
+    // Transparent pixels are made visible by the additive terms.
+    assert_pixel(&image, 15, 15, [0, 0, 128, 255]);
+}
+
+#[test]
+fn mask() {
+    let mut commands = CommandList::new();
+    commands.push_mask();
+    commands.draw_rect(Color::BLACK, rect(0.0, 0.0, 10.0, 20.0));
+    commands.activate_mask();
+    commands.draw_rect(Color::RED, rect(0.0, 0.0, 20.0, 20.0));
+    commands.deactivate_mask();
+    commands.draw_rect(Color::BLACK, rect(0.0, 0.0, 10.0, 20.0));
+    commands.pop_mask();
+    commands.draw_rect(Color::BLUE, rect(0.0, 15.0, 20.0, 5.0));
+    let image = render(Color::WHITE, commands);
+
+    assert_pixel(&image, 5, 5, [255, 0, 0, 255]);
+    assert_pixel(&image, 15, 5, [255, 255, 255, 255]);
+    // Content drawn after the mask is popped isn't masked.
+    assert_pixel(&image, 5, 17, [0, 0, 255, 255]);
+    assert_pixel(&image, 15, 17, [0, 0, 255, 255]);
+}
+
+/// Blends a rect of `src` onto a rect of `dst`, inside a layer so that every blend mode applies.
+fn blend(dst: Color, src: Color, blend_mode: BlendMode) -> image::RgbaImage {
+    let mut blended = CommandList::new();
+    blended.draw_rect(src, rect(0.0, 0.0, 20.0, 20.0));
+    let mut layer = CommandList::new();
+    layer.draw_rect(dst, rect(0.0, 0.0, 20.0, 20.0));
+    layer.blend(blended, RenderBlendMode::Builtin(blend_mode));
+    let mut commands = CommandList::new();
+    commands.blend(layer, RenderBlendMode::Builtin(BlendMode::Layer));
+    render(Color::TRANSPARENT, commands)
+}
+
+#[test]
+fn blend_modes() {
+    let dst = Color::from_rgb(0xC86432, 255);
+    let src = Color::from_rgb(0x6496C8, 255);
+    let cases = [
+        (BlendMode::Normal, [100, 150, 200, 255]),
+        (BlendMode::Layer, [100, 150, 200, 255]),
+        (BlendMode::Multiply, [78, 59, 39, 255]),
+        (BlendMode::Screen, [222, 191, 211, 255]),
+        (BlendMode::Lighten, [200, 150, 200, 255]),
+        (BlendMode::Darken, [100, 100, 50, 255]),
+        (BlendMode::Difference, [100, 50, 150, 255]),
+        (BlendMode::Add, [255, 250, 250, 255]),
+        (BlendMode::Subtract, [100, 0, 0, 255]),
+        (BlendMode::Invert, [55, 155, 205, 255]),
+        (BlendMode::Overlay, [188, 118, 78, 255]),
+        (BlendMode::HardLight, [157, 127, 166, 255]),
+    ];
+    for (blend_mode, expected) in cases {
+        let image = blend(dst, src, blend_mode);
+        assert_pixel(&image, 10, 10, expected);
+    }
+}
+
+#[test]
+fn alpha_and_erase_blend_modes() {
+    let dst = Color::from_rgb(0xC86432, 255);
+    let src = Color::from_rgb(0xFFFFFF, 64);
+
+    let image = blend(dst, src, BlendMode::Alpha);
+    assert_pixel(&image, 10, 10, [200, 100, 50, 64]);
+
+    let image = blend(dst, src, BlendMode::Erase);
+    assert_pixel(&image, 10, 10, [200, 100, 50, 191]);
+}
@@ -40,6 +40,9 @@ pub enum Error {
     #[error("Unknown handle {0:?}")]
     UnknownHandle(BitmapHandle),
 
+    #[error("Shader run on a bitmap target didn't write to the bitmap")]
+    UnexpectedShaderOutput,
+
     #[error("Not yet implemented: {0}")]
     Unimplemented(Cow<'static, str>),
 }
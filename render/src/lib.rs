@@ -9,6 +9,7 @@ pub mod filters;
 pub mod lines;
 pub mod matrix;
 pub mod matrix3d;
+pub mod perspective_projection;
 pub mod pixel_bender;
 // The `renderdoc` crate doesn't compile on apple platforms
 #[cfg(all(feature = "renderdoc", not(target_vendor = "apple")))]
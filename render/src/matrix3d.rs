@@ -48,3 +48,318 @@ impl From<Matrix3D> for Matrix {
         }
     }
 }
+
+impl Default for Matrix3D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Matrix3D {
+    pub const IDENTITY: Self = Self {
+        raw_data: [
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, 1.0, 0.0, //
+            0.0, 0.0, 0.0, 1.0, //
+        ],
+    };
+
+    /// Returns the element at the given row and column.
+    #[inline]
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.raw_data[column * 4 + row]
+    }
+
+    #[inline]
+    fn set(&mut self, row: usize, column: usize, value: f64) {
+        self.raw_data[column * 4 + row] = value;
+    }
+
+    pub fn translation(x: f64, y: f64, z: f64) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.set(0, 3, x);
+        matrix.set(1, 3, y);
+        matrix.set(2, 3, z);
+        matrix
+    }
+
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.set(0, 0, x);
+        matrix.set(1, 1, y);
+        matrix.set(2, 2, z);
+        matrix
+    }
+
+    /// A rotation around the X axis, in radians.
+    pub fn rotation_x(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.set(1, 1, cos);
+        matrix.set(1, 2, -sin);
+        matrix.set(2, 1, sin);
+        matrix.set(2, 2, cos);
+        matrix
+    }
+
+    /// A rotation around the Y axis, in radians.
+    pub fn rotation_y(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.set(0, 0, cos);
+        matrix.set(0, 2, sin);
+        matrix.set(2, 0, -sin);
+        matrix.set(2, 2, cos);
+        matrix
+    }
+
+    /// A rotation around the Z axis, in radians.
+    pub fn rotation_z(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let mut matrix = Self::IDENTITY;
+        matrix.set(0, 0, cos);
+        matrix.set(0, 1, -sin);
+        matrix.set(1, 0, sin);
+        matrix.set(1, 1, cos);
+        matrix
+    }
+
+    /// Builds a matrix the same way Flash builds one from a display object's properties:
+    /// scale first, then rotate around X, Y and Z in that order, then translate.
+    pub fn recompose(components: &Matrix3DComponents) -> Self {
+        let [tx, ty, tz] = components.translation;
+        let [rx, ry, rz] = components.rotation;
+        let [sx, sy, sz] = components.scale;
+        Self::translation(tx, ty, tz)
+            * Self::rotation_z(rz)
+            * Self::rotation_y(ry)
+            * Self::rotation_x(rx)
+            * Self::scale(sx, sy, sz)
+    }
+
+    /// Splits this matrix into the components that `recompose` would build it from.
+    /// Any skew or projection in the matrix is lost.
+    pub fn decompose(&self) -> Matrix3DComponents {
+        let translation = [self.get(0, 3), self.get(1, 3), self.get(2, 3)];
+        let column_length = |c: usize| (0..3).map(|r| self.get(r, c).powi(2)).sum::<f64>().sqrt();
+        let scale = [column_length(0), column_length(1), column_length(2)];
+
+        // Normalize the basis vectors to get the pure rotation `Rz * Ry * Rx`.
+        let rotation_element = |row: usize, column: usize| {
+            if scale[column] == 0.0 {
+                0.0
+            } else {
+                self.get(row, column) / scale[column]
+            }
+        };
+        let rotation_y = (-rotation_element(2, 0)).clamp(-1.0, 1.0).asin();
+        let rotation_x = rotation_element(2, 1).atan2(rotation_element(2, 2));
+        let rotation_z = rotation_element(1, 0).atan2(rotation_element(0, 0));
+
+        Matrix3DComponents {
+            translation,
+            rotation: [rotation_x, rotation_y, rotation_z],
+            scale,
+        }
+    }
+
+    /// Transforms a point on the z = 0 plane, returning the homogeneous result `(x, y, z, w)`.
+    pub fn transform_point(&self, x: f64, y: f64) -> [f64; 4] {
+        std::array::from_fn(|row| self.get(row, 0) * x + self.get(row, 1) * y + self.get(row, 3))
+    }
+
+    /// Transforms a point on the z = 0 plane, and projects the result back onto a plane.
+    /// Returns `None` if the point ends up behind the viewer.
+    pub fn project_point(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let [x, y, _, w] = self.transform_point(x, y);
+        (w > 0.0).then(|| (x / w, y / w))
+    }
+
+    /// Finds the point on the z = 0 plane that `project_point` maps to `(x, y)`.
+    /// Returns `None` if there's no such point in front of the viewer.
+    pub fn unproject_point(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        // Restricted to the z = 0 plane, the projection is a 3x3 homography,
+        // mapping `(x, y, 1)` to `(x', y', w)`. Invert it through its adjugate.
+        let m = |row, column| self.get(row, column);
+        let [a, b, c] = [m(0, 0), m(0, 1), m(0, 3)];
+        let [d, e, f] = [m(1, 0), m(1, 1), m(1, 3)];
+        let [g, h, i] = [m(3, 0), m(3, 1), m(3, 3)];
+        let u = (e * i - f * h) * x + (c * h - b * i) * y + (b * f - c * e);
+        let v = (f * g - d * i) * x + (a * i - c * g) * y + (c * d - a * f);
+        let w = (d * h - e * g) * x + (b * g - a * h) * y + (a * e - b * d);
+        if w == 0.0 {
+            return None;
+        }
+        let (u, v) = (u / w, v / w);
+        let [_, _, _, w] = self.transform_point(u, v);
+        (w > 0.0).then_some((u, v))
+    }
+
+    /// Converts this to a column-major `f32` matrix for uploading to the GPU.
+    /// The z output is dropped, so that nothing gets clipped away by depth.
+    pub fn to_gpu_matrix(&self) -> [[f32; 4]; 4] {
+        std::array::from_fn(|column| {
+            std::array::from_fn(|row| {
+                if row == 2 {
+                    0.0
+                } else {
+                    self.get(row, column) as f32
+                }
+            })
+        })
+    }
+
+    /// Whether this matrix does anything that a 2D `Matrix` can't represent.
+    pub fn is_3d(&self) -> bool {
+        self.get(0, 2) != 0.0
+            || self.get(1, 2) != 0.0
+            || self.get(2, 0) != 0.0
+            || self.get(2, 1) != 0.0
+            || self.get(2, 2) != 1.0
+            || self.get(2, 3) != 0.0
+            || self.get(3, 0) != 0.0
+            || self.get(3, 1) != 0.0
+            || self.get(3, 2) != 0.0
+            || self.get(3, 3) != 1.0
+    }
+}
+
+impl std::ops::Mul for Matrix3D {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let mut out = Self {
+            raw_data: [0.0; 16],
+        };
+        for row in 0..4 {
+            for column in 0..4 {
+                let value = (0..4).map(|i| self.get(row, i) * rhs.get(i, column)).sum();
+                out.set(row, column, value);
+            }
+        }
+        out
+    }
+}
+
+impl std::ops::MulAssign for Matrix3D {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+/// The individual transformations that make up a `Matrix3D`,
+/// as exposed through the 3D properties of a display object.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Matrix3DComponents {
+    pub translation: [f64; 3],
+    /// Rotations around the X, Y and Z axes, in radians.
+    pub rotation: [f64; 3],
+    pub scale: [f64; 3],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::perspective_projection::PerspectiveProjection;
+
+    fn assert_matrix_eq(a: Matrix3D, b: Matrix3D) {
+        for (x, y) in a.raw_data.iter().zip(b.raw_data.iter()) {
+            assert!((x - y).abs() < 1e-6, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn multiply_matches_2d() {
+        let a = Matrix::rotate(0.5) * Matrix::scale(2.0, 3.0);
+        let b = Matrix::translate(Twips::from_pixels(10.0), Twips::from_pixels(-4.0));
+        let product = Matrix3D::from(a) * Matrix3D::from(b);
+        let expected = Matrix3D::from(a * b);
+        // 2D translations are rounded to the nearest twip.
+        for (x, y) in product.raw_data.iter().zip(expected.raw_data.iter()) {
+            assert!((x - y).abs() <= 0.05, "{product:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn recompose_round_trip() {
+        let components = Matrix3DComponents {
+            translation: [10.0, -20.0, 30.0],
+            rotation: [0.3, -0.7, 1.2],
+            scale: [2.0, 0.5, 1.5],
+        };
+        let matrix = Matrix3D::recompose(&components);
+        let decomposed = matrix.decompose();
+        assert_matrix_eq(Matrix3D::recompose(&decomposed), matrix);
+    }
+
+    #[test]
+    fn decompose_components() {
+        let components = Matrix3DComponents {
+            translation: [5.0, 6.0, -7.0],
+            rotation: [-0.4, 0.9, 2.5],
+            scale: [1.0, 3.0, 0.25],
+        };
+        let decomposed = Matrix3D::recompose(&components).decompose();
+        let actual = decomposed
+            .translation
+            .iter()
+            .chain(&decomposed.rotation)
+            .chain(&decomposed.scale);
+        let expected = components
+            .translation
+            .iter()
+            .chain(&components.rotation)
+            .chain(&components.scale);
+        for (x, y) in actual.zip(expected) {
+            assert!((x - y).abs() < 1e-9, "{decomposed:?} != {components:?}");
+        }
+    }
+
+    #[test]
+    fn decompose_2d() {
+        let matrix = Matrix::translate(Twips::from_pixels(3.0), Twips::from_pixels(4.0))
+            * Matrix::rotate(-1.0)
+            * Matrix::scale(2.0, 0.5);
+        let components = Matrix3D::from(matrix).decompose();
+        assert_eq!(components.translation, [3.0, 4.0, 0.0]);
+        assert_eq!(components.rotation[0], 0.0);
+        assert_eq!(components.rotation[1], 0.0);
+        assert!((components.rotation[2] + 1.0).abs() < 1e-6);
+        assert!((components.scale[0] - 2.0).abs() < 1e-6);
+        assert!((components.scale[1] - 0.5).abs() < 1e-6);
+        assert_eq!(components.scale[2], 1.0);
+    }
+
+    #[test]
+    fn project_point_divides_by_w() {
+        let mut matrix = Matrix3D::translation(10.0, 20.0, 0.0);
+        matrix.set(3, 3, 2.0);
+        assert_eq!(matrix.project_point(2.0, 4.0), Some((6.0, 12.0)));
+        matrix.set(3, 3, -1.0);
+        assert_eq!(matrix.project_point(2.0, 4.0), None);
+    }
+
+    #[test]
+    fn unproject_point_round_trip() {
+        let matrix = PerspectiveProjection::for_viewport(550.0, 400.0).to_matrix3d(550.0)
+            * Matrix3D::translation(100.0, 50.0, 20.0)
+            * Matrix3D::rotation_y(0.8)
+            * Matrix3D::rotation_x(-0.3);
+        let (x, y) = matrix.project_point(30.0, -40.0).unwrap();
+        let (u, v) = matrix.unproject_point(x, y).unwrap();
+        assert!((u - 30.0).abs() < 1e-6 && (v + 40.0).abs() < 1e-6);
+
+        // Seen edge-on, the plane covers no point.
+        let edge_on = Matrix3D::scale(0.0, 1.0, 1.0);
+        assert_eq!(edge_on.unproject_point(0.0, 0.0), None);
+    }
+
+    #[test]
+    fn rotation_z_matches_2d() {
+        let expected = Matrix3D::from(Matrix::rotate(0.25));
+        assert_matrix_eq(Matrix3D::rotation_z(0.25), expected);
+        assert!(!Matrix3D::rotation_z(0.25).is_3d());
+        assert!(Matrix3D::rotation_x(0.25).is_3d());
+    }
+}
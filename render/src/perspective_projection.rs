@@ -0,0 +1,121 @@
+use crate::matrix3d::Matrix3D;
+
+/// The perspective used to flatten 3D display objects onto the screen.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PerspectiveProjection {
+    /// The angle of the field of view, in degrees. Always between 0 and 180.
+    pub field_of_view: f64,
+
+    /// The vanishing point of the projection, in pixels.
+    pub center: (f64, f64),
+}
+
+impl PerspectiveProjection {
+    /// The default field of view for the root of a movie.
+    pub const DEFAULT_FIELD_OF_VIEW: f64 = 55.0;
+
+    /// The default projection for a viewport of the given size.
+    /// The vanishing point is in the middle of the viewport.
+    pub fn for_viewport(width: f64, height: f64) -> Self {
+        Self {
+            field_of_view: Self::DEFAULT_FIELD_OF_VIEW,
+            center: (width / 2.0, height / 2.0),
+        }
+    }
+
+    /// The distance between the viewer and the z = 0 plane,
+    /// such that the field of view spans a viewport of the given width.
+    pub fn focal_length(&self, width: f64) -> f64 {
+        (width / 2.0) / (self.field_of_view.to_radians() / 2.0).tan()
+    }
+
+    /// Sets the field of view so that the focal length for a viewport of the given width matches.
+    pub fn set_focal_length(&mut self, focal_length: f64, width: f64) {
+        self.field_of_view = ((width / 2.0) / focal_length).atan().to_degrees() * 2.0;
+    }
+
+    /// Returns a matrix that projects points towards the vanishing point,
+    /// for a viewport of the given width.
+    ///
+    /// A point at depth `z` ends up scaled by `f / (f + z)` around the center,
+    /// where `f` is the focal length. The scale is left in the `w` component,
+    /// so the result must be divided by `w` afterwards.
+    pub fn to_matrix3d(&self, width: f64) -> Matrix3D {
+        let focal_length = self.focal_length(width);
+        let (center_x, center_y) = self.center;
+        Matrix3D {
+            raw_data: [
+                // 1st column
+                1.0,
+                0.0,
+                0.0,
+                0.0,
+                // 2nd column
+                0.0,
+                1.0,
+                0.0,
+                0.0,
+                // 3rd column
+                center_x / focal_length,
+                center_y / focal_length,
+                1.0,
+                1.0 / focal_length,
+                // 4th column
+                0.0,
+                0.0,
+                0.0,
+                1.0,
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_point_eq(actual: Option<(f64, f64)>, expected: (f64, f64)) {
+        let (x, y) = actual.expect("point should be in front of the viewer");
+        assert!(
+            (x - expected.0).abs() < 1e-9 && (y - expected.1).abs() < 1e-9,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn focal_length() {
+        let mut projection = PerspectiveProjection::for_viewport(500.0, 400.0);
+        assert_eq!(projection.center, (250.0, 200.0));
+        assert!((projection.focal_length(500.0) - 480.24553).abs() < 1e-5);
+
+        projection.field_of_view = 90.0;
+        assert!((projection.focal_length(500.0) - 250.0).abs() < 1e-9);
+        assert!((projection.focal_length(800.0) - 400.0).abs() < 1e-9);
+
+        projection.set_focal_length(100.0, 200.0);
+        assert!((projection.field_of_view - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn projects_towards_center() {
+        let mut projection = PerspectiveProjection::for_viewport(500.0, 400.0);
+        projection.field_of_view = 90.0;
+        let matrix = projection.to_matrix3d(500.0);
+
+        // Points on the z = 0 plane stay where they are.
+        assert_point_eq(matrix.project_point(100.0, 50.0), (100.0, 50.0));
+
+        // Pushing a point back by the focal length halves its distance to the center.
+        let pushed_back = matrix * Matrix3D::translation(0.0, 0.0, 250.0);
+        assert_point_eq(pushed_back.project_point(250.0, 200.0), (250.0, 200.0));
+        assert_point_eq(pushed_back.project_point(50.0, 0.0), (150.0, 100.0));
+
+        // Pulling a point forward by half the focal length doubles it.
+        let pulled_forward = matrix * Matrix3D::translation(0.0, 0.0, -125.0);
+        assert_point_eq(pulled_forward.project_point(200.0, 200.0), (150.0, 200.0));
+
+        // Points behind the viewer aren't visible.
+        let behind = matrix * Matrix3D::translation(0.0, 0.0, -300.0);
+        assert_eq!(behind.project_point(0.0, 0.0), None);
+    }
+}
@@ -1,6 +1,7 @@
 //! Pixel bender bytecode parsing code.
 //! This is heavily based on https://github.com/jamesward/pbjas and https://github.com/HaxeFoundation/format/tree/master/format/pbj
 
+pub mod interpreter;
 #[cfg(test)]
 mod tests;
 
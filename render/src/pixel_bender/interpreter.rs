@@ -0,0 +1,712 @@
+//! A CPU interpreter for Pixel Bender shaders.
+//!
+//! This runs the `PixelBenderShader` IR produced by [`parse_shader`](super::parse_shader)
+//! one pixel at a time, following the same semantics as the `naga-pixelbender`
+//! translation used by the wgpu backend. It's much slower than running the shader
+//! on a GPU, but it allows any backend to run shaders, and shader output to be
+//! tested without a graphics device.
+
+#[cfg(test)]
+mod tests;
+
+use std::borrow::Cow;
+
+use crate::bitmap::BitmapHandle;
+use crate::error::Error;
+use crate::pixel_bender::{
+    ImageInputTexture, Opcode, Operation, PixelBenderParam, PixelBenderParamQualifier,
+    PixelBenderReg, PixelBenderRegChannel, PixelBenderRegKind, PixelBenderShader,
+    PixelBenderShaderArgument, PixelBenderShaderImpl, PixelBenderType, OUT_COORD_NAME,
+};
+
+/// How texture samples with coordinates outside of the image are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutOfRangeMode {
+    /// Clamp the coordinates to the edge of the image.
+    /// This is what Flash Player does when a shader is run through a `ShaderJob`.
+    Clamp,
+    /// Produce transparent black.
+    /// This is what Flash Player does for a `ShaderFilter` or a shader blend mode.
+    Zero,
+}
+
+/// An image input to a shader, with every channel stored as a float.
+#[derive(Debug, Clone)]
+pub struct ShaderImage {
+    width: u32,
+    height: u32,
+    channels: usize,
+    values: Vec<f32>,
+}
+
+impl ShaderImage {
+    /// Creates an image from premultiplied RGBA pixels, 4 bytes per pixel.
+    pub fn from_rgba8(width: u32, height: u32, pixels: &[u8]) -> Self {
+        Self {
+            width,
+            height,
+            channels: 4,
+            values: pixels.iter().map(|v| f32::from(*v) / 255.0).collect(),
+        }
+    }
+
+    /// Creates an image from little-endian `f32` values, `channels` per pixel.
+    /// This is the format used for `ByteArray` and `Vector.<Number>` inputs.
+    pub fn from_float_bytes(width: u32, height: u32, channels: u32, bytes: &[u8]) -> Self {
+        Self {
+            width,
+            height,
+            channels: channels as usize,
+            values: bytes
+                .chunks_exact(4)
+                .map(|v| f32::from_le_bytes([v[0], v[1], v[2], v[3]]))
+                .collect(),
+        }
+    }
+
+    /// Reads a single pixel. Missing color channels are read as 0.0, and a missing alpha
+    /// channel as 1.0, like a GPU does when sampling a texture with fewer channels.
+    fn texel(&self, x: u32, y: u32) -> [f32; 4] {
+        let start = (y as usize * self.width as usize + x as usize) * self.channels;
+        let mut texel = [0.0, 0.0, 0.0, 1.0];
+        for (i, value) in texel.iter_mut().take(self.channels).enumerate() {
+            *value = self.values.get(start + i).copied().unwrap_or_default();
+        }
+        texel
+    }
+
+    fn sample(&self, x: f32, y: f32, linear: bool, mode: OutOfRangeMode) -> [f32; 4] {
+        if self.width == 0 || self.height == 0 {
+            return [0.0; 4];
+        }
+        let (width, height) = (self.width as f32, self.height as f32);
+        if mode == OutOfRangeMode::Zero
+            && (!(0.0..=width).contains(&x) || !(0.0..=height).contains(&y))
+        {
+            return [0.0; 4];
+        }
+
+        let clamp_x = |x: f32| (x.max(0.0) as u32).min(self.width - 1);
+        let clamp_y = |y: f32| (y.max(0.0) as u32).min(self.height - 1);
+        if !linear {
+            return self.texel(clamp_x(x.floor()), clamp_y(y.floor()));
+        }
+
+        // Texel centers are at half-integer coordinates
+        let (x, y) = (x - 0.5, y - 0.5);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (tx, ty) = (x - x0, y - y0);
+        let top_left = self.texel(clamp_x(x0), clamp_y(y0));
+        let top_right = self.texel(clamp_x(x0 + 1.0), clamp_y(y0));
+        let bottom_left = self.texel(clamp_x(x0), clamp_y(y0 + 1.0));
+        let bottom_right = self.texel(clamp_x(x0 + 1.0), clamp_y(y0 + 1.0));
+        std::array::from_fn(|i| {
+            let top = top_left[i] + (top_right[i] - top_left[i]) * tx;
+            let bottom = bottom_left[i] + (bottom_right[i] - bottom_left[i]) * tx;
+            top + (bottom - top) * ty
+        })
+    }
+}
+
+/// The result of running a shader over every pixel of its output.
+#[derive(Debug, Clone)]
+pub struct ShaderOutput {
+    pub width: u32,
+    pub height: u32,
+    /// The number of channels of the shader's output parameter (3 or 4).
+    pub channels: usize,
+    /// The value of the output register for every pixel, in row-major order.
+    pub pixels: Vec<[f32; 4]>,
+}
+
+impl ShaderOutput {
+    /// Converts the output to RGBA bytes, as it would be stored in a bitmap.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|pixel| pixel.map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8))
+            .collect()
+    }
+
+    /// Converts the output to little-endian `f32` values, `channels` per pixel.
+    /// This is the format used for `ByteArray` and `Vector.<Number>` targets.
+    pub fn to_float_bytes(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|pixel| &pixel[..self.channels])
+            .flat_map(|v| v.to_le_bytes())
+            .collect()
+    }
+}
+
+/// A shader registered with a backend that runs it through this interpreter.
+/// Shaders are interpreted directly from their parsed form, so there is nothing to compile.
+#[derive(Debug)]
+pub struct InterpretedShader {
+    shader: PixelBenderShader,
+}
+
+impl InterpretedShader {
+    pub fn new(shader: PixelBenderShader) -> Self {
+        Self { shader }
+    }
+}
+
+impl PixelBenderShaderImpl for InterpretedShader {
+    fn parsed_shader(&self) -> &PixelBenderShader {
+        &self.shader
+    }
+}
+
+/// Runs a shader over every pixel of a `width` by `height` output.
+///
+/// Image inputs given as a `BitmapHandle` are loaded through `load_bitmap`,
+/// as only the backend that owns the bitmap knows how to read it.
+pub fn run_shader(
+    shader: &PixelBenderShader,
+    arguments: &[PixelBenderShaderArgument],
+    width: u32,
+    height: u32,
+    mode: OutOfRangeMode,
+    mut load_bitmap: impl FnMut(&BitmapHandle) -> Result<ShaderImage, Error>,
+) -> Result<ShaderOutput, Error> {
+    let (output, output_channels) = shader
+        .params
+        .iter()
+        .find_map(|param| match param {
+            PixelBenderParam::Normal {
+                qualifier: PixelBenderParamQualifier::Output,
+                reg,
+                ..
+            } => Some((reg, reg.channels.len())),
+            _ => None,
+        })
+        .ok_or_else(|| invalid("missing output parameter"))?;
+    let out_coord = shader.params.iter().find_map(|param| match param {
+        PixelBenderParam::Normal {
+            qualifier: PixelBenderParamQualifier::Input,
+            reg,
+            name,
+            ..
+        } if name == OUT_COORD_NAME => Some(reg),
+        _ => None,
+    });
+
+    let mut images = Vec::new();
+    let mut registers = Registers::default();
+    for argument in arguments {
+        match argument {
+            PixelBenderShaderArgument::ImageInput { index, texture, .. } => {
+                let image = match texture {
+                    Some(ImageInputTexture::Bitmap(handle)) => Some(load_bitmap(handle)?),
+                    Some(ImageInputTexture::Bytes {
+                        width,
+                        height,
+                        channels,
+                        bytes,
+                    }) => Some(ShaderImage::from_float_bytes(
+                        *width, *height, *channels, bytes,
+                    )),
+                    Some(ImageInputTexture::TextureRef(_)) => {
+                        return Err(Error::Unimplemented(
+                            "Raw texture inputs to interpreted shaders".into(),
+                        ))
+                    }
+                    None => None,
+                };
+                let index = *index as usize;
+                if index >= images.len() {
+                    images.resize(index + 1, None);
+                }
+                images[index] = image;
+            }
+            PixelBenderShaderArgument::ValueInput { index, value } => {
+                let Some(PixelBenderParam::Normal { reg, name, .. }) =
+                    shader.params.get(*index as usize)
+                else {
+                    return Err(invalid(format!("no parameter with index {index}")));
+                };
+                if name != OUT_COORD_NAME {
+                    registers.write_param(reg, value)?;
+                }
+            }
+        }
+    }
+
+    let jumps = find_jumps(&shader.operations)?;
+    let mut interpreter = Interpreter {
+        operations: &shader.operations,
+        jumps,
+        images,
+        mode,
+        registers: Registers::default(),
+    };
+
+    let mut pixels = Vec::with_capacity(width as usize * height as usize);
+    for y in 0..height {
+        for x in 0..width {
+            interpreter.registers.clone_from(&registers);
+            if let Some(out_coord) = out_coord {
+                // Coordinates refer to the center of the pixel
+                let coord = [x as f32 + 0.5, y as f32 + 0.5, 0.0, 0.0];
+                interpreter.registers.write(out_coord, coord)?;
+            }
+            interpreter.run()?;
+            pixels.push(interpreter.registers.read(output)?);
+        }
+    }
+
+    Ok(ShaderOutput {
+        width,
+        height,
+        channels: output_channels,
+        pixels,
+    })
+}
+
+fn invalid(message: impl Into<Cow<'static, str>>) -> Error {
+    let message: Cow<'static, str> = message.into();
+    Error::Unimplemented(format!("Invalid Pixel Bender shader: {message}").into())
+}
+
+/// Finds the target of every `If` and `Else` operation: the index of the matching
+/// `Else` or `EndIf` for an `If`, and of the matching `EndIf` for an `Else`.
+fn find_jumps(operations: &[Operation]) -> Result<Vec<usize>, Error> {
+    let mut jumps = vec![0; operations.len()];
+    let mut open = Vec::new();
+    for (i, operation) in operations.iter().enumerate() {
+        match operation {
+            Operation::If { .. } => open.push(i),
+            Operation::Else => {
+                let start = open.pop().ok_or_else(|| invalid("'else' without 'if'"))?;
+                if matches!(operations[start], Operation::Else) {
+                    return Err(invalid("multiple 'else' for a single 'if'"));
+                }
+                jumps[start] = i;
+                open.push(i);
+            }
+            Operation::EndIf => {
+                let start = open.pop().ok_or_else(|| invalid("'endif' without 'if'"))?;
+                jumps[start] = i;
+            }
+            _ => {}
+        }
+    }
+    if !open.is_empty() {
+        return Err(invalid("unterminated 'if'"));
+    }
+    Ok(jumps)
+}
+
+/// The register file of a shader. Like the GPU translation, every register
+/// holds 4 components, and registers that were never written read as zero.
+#[derive(Default, Clone)]
+struct Registers {
+    floats: Vec<[f32; 4]>,
+    ints: Vec<[i32; 4]>,
+}
+
+/// A square matrix stored as columns, using the first `size` components of each.
+#[derive(Clone, Copy)]
+struct Matrix {
+    size: usize,
+    columns: [[f32; 4]; 4],
+}
+
+impl Matrix {
+    fn mul(&self, other: &Matrix) -> Matrix {
+        let mut columns = [[0.0; 4]; 4];
+        for (column, other_column) in columns.iter_mut().zip(&other.columns).take(self.size) {
+            *column = self.mul_vec(*other_column);
+        }
+        Matrix {
+            size: self.size,
+            columns,
+        }
+    }
+
+    fn mul_vec(&self, vec: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        for (column, factor) in self.columns.iter().zip(vec).take(self.size) {
+            for (out, value) in out.iter_mut().zip(column).take(self.size) {
+                *out += value * factor;
+            }
+        }
+        out
+    }
+
+    fn vec_mul(&self, vec: [f32; 4]) -> [f32; 4] {
+        let mut out = [0.0; 4];
+        for (out, column) in out.iter_mut().zip(&self.columns).take(self.size) {
+            *out = dot(column, &vec, self.size);
+        }
+        out
+    }
+
+    fn zip(&self, other: &Matrix, f: impl Fn(f32, f32) -> f32) -> Matrix {
+        Matrix {
+            size: self.size,
+            columns: std::array::from_fn(|i| zip(self.columns[i], other.columns[i], &f)),
+        }
+    }
+}
+
+fn channel_index(channel: PixelBenderRegChannel) -> Result<usize, Error> {
+    match channel {
+        PixelBenderRegChannel::R => Ok(0),
+        PixelBenderRegChannel::G => Ok(1),
+        PixelBenderRegChannel::B => Ok(2),
+        PixelBenderRegChannel::A => Ok(3),
+        _ => Err(invalid(format!("unexpected matrix channel {channel:?}"))),
+    }
+}
+
+fn matrix_size(reg: &PixelBenderReg) -> Option<usize> {
+    match reg.channels.as_slice() {
+        [PixelBenderRegChannel::M2x2] => Some(2),
+        [PixelBenderRegChannel::M3x3] => Some(3),
+        [PixelBenderRegChannel::M4x4] => Some(4),
+        _ => None,
+    }
+}
+
+impl Registers {
+    fn raw(&self, kind: PixelBenderRegKind, index: usize) -> [f32; 4] {
+        match kind {
+            PixelBenderRegKind::Float => self.floats.get(index).copied().unwrap_or_default(),
+            PixelBenderRegKind::Int => self
+                .ints
+                .get(index)
+                .copied()
+                .unwrap_or_default()
+                .map(|v| v as f32),
+        }
+    }
+
+    fn float_mut(&mut self, index: usize) -> &mut [f32; 4] {
+        if index >= self.floats.len() {
+            self.floats.resize(index + 1, [0.0; 4]);
+        }
+        &mut self.floats[index]
+    }
+
+    fn int_mut(&mut self, index: usize) -> &mut [i32; 4] {
+        if index >= self.ints.len() {
+            self.ints.resize(index + 1, [0; 4]);
+        }
+        &mut self.ints[index]
+    }
+
+    /// Reads the swizzled channels of a register.
+    /// Components past the number of channels are padded with the register's alpha channel.
+    fn read(&self, reg: &PixelBenderReg) -> Result<[f32; 4], Error> {
+        let raw = self.raw(reg.kind, reg.index as usize);
+        let mut value = [raw[3]; 4];
+        for (value, channel) in value.iter_mut().zip(&reg.channels) {
+            *value = raw[channel_index(*channel)?];
+        }
+        Ok(value)
+    }
+
+    /// Writes the components of `value` in order to the channels of a register,
+    /// converting them to integers for an int register.
+    fn write(&mut self, reg: &PixelBenderReg, value: [f32; 4]) -> Result<(), Error> {
+        let index = reg.index as usize;
+        for (channel, value) in reg.channels.iter().zip(value) {
+            let channel = channel_index(*channel)?;
+            match reg.kind {
+                PixelBenderRegKind::Float => self.float_mut(index)[channel] = value,
+                PixelBenderRegKind::Int => self.int_mut(index)[channel] = value as i32,
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a matrix, which is stored as a single register for a 2x2 matrix,
+    /// or as one register per column otherwise.
+    fn read_matrix(&self, reg: &PixelBenderReg, size: usize) -> Matrix {
+        let index = reg.index as usize;
+        let mut columns = [[0.0; 4]; 4];
+        if size == 2 {
+            let raw = self.raw(PixelBenderRegKind::Float, index);
+            columns[0] = [raw[0], raw[1], 0.0, 0.0];
+            columns[1] = [raw[2], raw[3], 0.0, 0.0];
+        } else {
+            for (i, column) in columns.iter_mut().enumerate().take(size) {
+                *column = self.raw(PixelBenderRegKind::Float, index + i);
+            }
+        }
+        Matrix { size, columns }
+    }
+
+    fn write_matrix(&mut self, reg: &PixelBenderReg, matrix: &Matrix) {
+        let index = reg.index as usize;
+        if matrix.size == 2 {
+            let [c0, c1, ..] = matrix.columns;
+            *self.float_mut(index) = [c0[0], c0[1], c1[0], c1[1]];
+        } else {
+            for (i, column) in matrix.columns.iter().enumerate().take(matrix.size) {
+                self.float_mut(index + i)[..matrix.size].copy_from_slice(&column[..matrix.size]);
+            }
+        }
+    }
+
+    fn write_param(&mut self, reg: &PixelBenderReg, value: &PixelBenderType) -> Result<(), Error> {
+        // Matrices are passed in column-major order. Despite what the Flash docs claim,
+        // this seems to be what Flash Player does.
+        let matrix = |size: usize, values: &[f32]| Matrix {
+            size,
+            columns: std::array::from_fn(|i| {
+                std::array::from_fn(|j| {
+                    if i < size && j < size {
+                        values[i * size + j]
+                    } else {
+                        0.0
+                    }
+                })
+            }),
+        };
+        let value = match value {
+            PixelBenderType::TFloat(f1) => [*f1, 0.0, 0.0, 0.0],
+            PixelBenderType::TFloat2(f1, f2) => [*f1, *f2, 0.0, 0.0],
+            PixelBenderType::TFloat3(f1, f2, f3) => [*f1, *f2, *f3, 0.0],
+            PixelBenderType::TFloat4(f1, f2, f3, f4) => [*f1, *f2, *f3, *f4],
+            PixelBenderType::TInt(i1) => [*i1, 0, 0, 0].map(f32::from),
+            PixelBenderType::TInt2(i1, i2) => [*i1, *i2, 0, 0].map(f32::from),
+            PixelBenderType::TInt3(i1, i2, i3) => [*i1, *i2, *i3, 0].map(f32::from),
+            PixelBenderType::TInt4(i1, i2, i3, i4) => [*i1, *i2, *i3, *i4].map(f32::from),
+            PixelBenderType::TFloat2x2(values) => {
+                self.write_matrix(reg, &matrix(2, values));
+                return Ok(());
+            }
+            PixelBenderType::TFloat3x3(values) => {
+                self.write_matrix(reg, &matrix(3, values));
+                return Ok(());
+            }
+            PixelBenderType::TFloat4x4(values) => {
+                self.write_matrix(reg, &matrix(4, values));
+                return Ok(());
+            }
+            PixelBenderType::TString(_) => return Ok(()),
+        };
+        self.write(reg, value)
+    }
+}
+
+struct Interpreter<'a> {
+    operations: &'a [Operation],
+    jumps: Vec<usize>,
+    images: Vec<Option<ShaderImage>>,
+    mode: OutOfRangeMode,
+    registers: Registers,
+}
+
+impl Interpreter<'_> {
+    /// Runs every operation of the shader for the current pixel.
+    fn run(&mut self) -> Result<(), Error> {
+        let mut pc = 0;
+        while let Some(operation) = self.operations.get(pc) {
+            match operation {
+                Operation::Nop | Operation::EndIf => {}
+                Operation::Normal { opcode, dst, src } => self.normal(*opcode, dst, src)?,
+                Operation::LoadInt { dst, val } => self.registers.write(dst, [*val as f32; 4])?,
+                Operation::LoadFloat { dst, val } => self.registers.write(dst, [*val; 4])?,
+                Operation::If { src } => {
+                    if self.registers.read(src)?[0] == 0.0 {
+                        // Continue after the matching `Else` or `EndIf`
+                        pc = self.jumps[pc];
+                    }
+                }
+                // We only reach an `Else` after running the `If` branch, so skip to the `EndIf`
+                Operation::Else => pc = self.jumps[pc],
+                Operation::SampleNearest { dst, src, tf }
+                | Operation::SampleLinear { dst, src, tf } => {
+                    let image = self
+                        .images
+                        .get(*tf as usize)
+                        .and_then(Option::as_ref)
+                        .ok_or_else(|| invalid(format!("missing image input {tf}")))?;
+                    let coord = self.registers.read(src)?;
+                    let linear = matches!(operation, Operation::SampleLinear { .. });
+                    let value = image.sample(coord[0], coord[1], linear, self.mode);
+                    self.registers.write(dst, value)?;
+                }
+                Operation::Select {
+                    src1,
+                    src2,
+                    condition,
+                    dst,
+                } => {
+                    let value = if self.registers.read(condition)?[0] != 0.0 {
+                        self.registers.read(src1)?
+                    } else {
+                        self.registers.read(src2)?
+                    };
+                    self.registers.write(dst, value)?;
+                }
+            }
+            pc += 1;
+        }
+        Ok(())
+    }
+
+    fn normal(
+        &mut self,
+        opcode: Opcode,
+        dst: &PixelBenderReg,
+        src: &PixelBenderReg,
+    ) -> Result<(), Error> {
+        if let Some(size) = matrix_size(src) {
+            return self.matrix(opcode, dst, src, size);
+        }
+
+        let s = self.registers.read(src)?;
+        // Some operations use the destination as their first operand
+        let d = self.registers.read(dst)?;
+        // Operations that work on whole vectors only look at the channels that were read
+        let len = src.channels.len();
+        let splat = |v: f32| [v; 4];
+
+        let value = match opcode {
+            Opcode::Nop => return Ok(()),
+            Opcode::Mov => s,
+            Opcode::Add => zip(d, s, |d, s| d + s),
+            Opcode::Sub => zip(d, s, |d, s| d - s),
+            Opcode::Mul => zip(d, s, |d, s| d * s),
+            Opcode::Div => zip(d, s, |d, s| d / s),
+            Opcode::Rcp => s.map(|s| 1.0 / s),
+            Opcode::Atan2 => zip(d, s, f32::atan2),
+            Opcode::Pow => zip(d, s, f32::powf),
+            Opcode::Mod => zip(d, s, |d, s| d % s),
+            Opcode::Min => zip(d, s, f32::min),
+            Opcode::Max => zip(d, s, f32::max),
+            Opcode::Step => zip(d, s, |edge, x| if x < edge { 0.0 } else { 1.0 }),
+            Opcode::Sin => s.map(f32::sin),
+            Opcode::Cos => s.map(f32::cos),
+            Opcode::Tan => s.map(f32::tan),
+            Opcode::Asin => s.map(f32::asin),
+            Opcode::Acos => s.map(f32::acos),
+            Opcode::Atan => s.map(f32::atan),
+            Opcode::Exp => s.map(f32::exp),
+            Opcode::Exp2 => s.map(f32::exp2),
+            Opcode::Log => s.map(f32::ln),
+            Opcode::Log2 => s.map(f32::log2),
+            Opcode::Sqrt => s.map(f32::sqrt),
+            Opcode::RSqrt => s.map(|s| 1.0 / s.sqrt()),
+            Opcode::Abs => s.map(f32::abs),
+            Opcode::Sign => s.map(|s| if s == 0.0 { 0.0 } else { s.signum() }),
+            Opcode::Floor => s.map(f32::floor),
+            Opcode::Ceil => s.map(f32::ceil),
+            Opcode::Fract => s.map(|s| s - s.floor()),
+            Opcode::FloatToInt => s.map(f32::trunc),
+            Opcode::IntToFloat | Opcode::BoolToFloat | Opcode::BoolToInt => s,
+            Opcode::FloatToBool | Opcode::IntToBool => s.map(|s| to_float(s != 0.0)),
+            Opcode::Length => splat(dot(&s, &s, len).sqrt()),
+            Opcode::Distance => {
+                let diff = zip(d, s, |d, s| d - s);
+                splat(dot(&diff, &diff, len).sqrt())
+            }
+            Opcode::DotProduct => splat(dot(&d, &s, len)),
+            Opcode::Normalize => {
+                let length = dot(&s, &s, len).sqrt();
+                s.map(|s| s / length)
+            }
+            Opcode::CrossProduct => [
+                d[1] * s[2] - d[2] * s[1],
+                d[2] * s[0] - d[0] * s[2],
+                d[0] * s[1] - d[1] * s[0],
+                0.0,
+            ],
+            Opcode::Equal | Opcode::NotEqual | Opcode::LessThan | Opcode::LessThanEqual => {
+                let value = zip(d, s, |d, s| {
+                    to_float(match opcode {
+                        Opcode::Equal => d == s,
+                        Opcode::NotEqual => d != s,
+                        Opcode::LessThan => d < s,
+                        _ => d <= s,
+                    })
+                });
+                // Comparisons write their result to the first component of int register 0,
+                // where a following `If` or `Select` picks it up
+                let result = PixelBenderReg {
+                    index: 0,
+                    channels: vec![PixelBenderRegChannel::R],
+                    kind: PixelBenderRegKind::Int,
+                };
+                return self.registers.write(&result, value);
+            }
+            Opcode::VectorEqual | Opcode::VectorNotEqual => {
+                let equal = d[..len] == s[..len];
+                splat(to_float(equal == (opcode == Opcode::VectorEqual)))
+            }
+            Opcode::LogicalNot => s.map(|s| to_float(s == 0.0)),
+            Opcode::LogicalAnd => zip(d, s, |d, s| to_float(d != 0.0 && s != 0.0)),
+            Opcode::LogicalOr => zip(d, s, |d, s| to_float(d != 0.0 || s != 0.0)),
+            Opcode::LogicalXor => zip(d, s, |d, s| to_float((d != 0.0) != (s != 0.0))),
+            Opcode::BoolAny => splat(to_float(s[..len].iter().any(|s| *s != 0.0))),
+            Opcode::BoolAll => splat(to_float(s[..len].iter().all(|s| *s != 0.0))),
+            _ => {
+                return Err(Error::Unimplemented(
+                    format!("Pixel Bender opcode {opcode:?} in the interpreter").into(),
+                ))
+            }
+        };
+        self.registers.write(dst, value)
+    }
+
+    /// Runs an operation whose source is a matrix.
+    fn matrix(
+        &mut self,
+        opcode: Opcode,
+        dst: &PixelBenderReg,
+        src: &PixelBenderReg,
+        size: usize,
+    ) -> Result<(), Error> {
+        let s = self.registers.read_matrix(src, size);
+        match (opcode, matrix_size(dst)) {
+            (Opcode::Mov, Some(_)) => self.registers.write_matrix(dst, &s),
+            (Opcode::Add, Some(_)) => {
+                let d = self.registers.read_matrix(dst, size);
+                self.registers.write_matrix(dst, &d.zip(&s, |d, s| d + s));
+            }
+            (Opcode::Sub, Some(_)) => {
+                let d = self.registers.read_matrix(dst, size);
+                self.registers.write_matrix(dst, &d.zip(&s, |d, s| d - s));
+            }
+            (Opcode::Mul | Opcode::MatMatMul, Some(_)) => {
+                let d = self.registers.read_matrix(dst, size);
+                self.registers.write_matrix(dst, &d.mul(&s));
+            }
+            (Opcode::MatVecMul, None) => {
+                let d = self.registers.read(dst)?;
+                self.registers.write(dst, s.mul_vec(d))?;
+            }
+            (Opcode::VecMatMul, None) => {
+                let d = self.registers.read(dst)?;
+                self.registers.write(dst, s.vec_mul(d))?;
+            }
+            _ => {
+                return Err(Error::Unimplemented(
+                    format!("Pixel Bender matrix opcode {opcode:?} in the interpreter").into(),
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+fn zip(a: [f32; 4], b: [f32; 4], f: impl Fn(f32, f32) -> f32) -> [f32; 4] {
+    std::array::from_fn(|i| f(a[i], b[i]))
+}
+
+fn dot(a: &[f32; 4], b: &[f32; 4], len: usize) -> f32 {
+    a.iter().zip(b).take(len).map(|(a, b)| a * b).sum()
+}
+
+fn to_float(value: bool) -> f32 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,408 @@
+use crate::error::Error;
+use crate::pixel_bender::{
+    ImageInputTexture, Opcode, Operation, PixelBenderParam, PixelBenderParamQualifier,
+    PixelBenderReg, PixelBenderRegChannel, PixelBenderRegKind, PixelBenderShader,
+    PixelBenderShaderArgument, PixelBenderType, PixelBenderTypeOpcode, OUT_COORD_NAME,
+};
+
+use super::{run_shader, OutOfRangeMode, ShaderImage, ShaderOutput};
+
+use PixelBenderRegChannel::{A, B, G, R};
+
+fn float_reg(index: u32, channels: &[PixelBenderRegChannel]) -> PixelBenderReg {
+    PixelBenderReg {
+        index,
+        channels: channels.to_vec(),
+        kind: PixelBenderRegKind::Float,
+    }
+}
+
+fn int_reg(index: u32, channels: &[PixelBenderRegChannel]) -> PixelBenderReg {
+    PixelBenderReg {
+        index,
+        channels: channels.to_vec(),
+        kind: PixelBenderRegKind::Int,
+    }
+}
+
+fn param(
+    qualifier: PixelBenderParamQualifier,
+    param_type: PixelBenderTypeOpcode,
+    reg: PixelBenderReg,
+    name: &str,
+) -> PixelBenderParam {
+    PixelBenderParam::Normal {
+        qualifier,
+        param_type,
+        reg,
+        name: name.to_string(),
+        metadata: vec![],
+    }
+}
+
+/// Builds a shader with `_OutCoord` in f0.rg, an RGBA output in f1,
+/// and the given extra parameters.
+fn shader(extra_params: Vec<PixelBenderParam>, operations: Vec<Operation>) -> PixelBenderShader {
+    let mut params = vec![
+        param(
+            PixelBenderParamQualifier::Input,
+            PixelBenderTypeOpcode::TFloat2,
+            float_reg(0, &[R, G]),
+            OUT_COORD_NAME,
+        ),
+        param(
+            PixelBenderParamQualifier::Output,
+            PixelBenderTypeOpcode::TFloat4,
+            float_reg(1, &[R, G, B, A]),
+            "dst",
+        ),
+    ];
+    params.extend(extra_params);
+    PixelBenderShader {
+        name: "Test".to_string(),
+        version: 1,
+        params,
+        metadata: vec![],
+        operations,
+    }
+}
+
+fn image_input(
+    index: u8,
+    texture: ImageInputTexture<'static>,
+) -> PixelBenderShaderArgument<'static> {
+    PixelBenderShaderArgument::ImageInput {
+        index,
+        channels: 4,
+        name: format!("src{index}"),
+        texture: Some(texture),
+    }
+}
+
+fn float_bytes(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+fn run(
+    shader: &PixelBenderShader,
+    arguments: &[PixelBenderShaderArgument],
+    size: (u32, u32),
+    mode: OutOfRangeMode,
+) -> ShaderOutput {
+    run_shader(shader, arguments, size.0, size.1, mode, |_| {
+        Err(Error::Unimplemented("bitmaps".into()))
+    })
+    .expect("Shader should run")
+}
+
+#[test]
+fn out_coord_is_pixel_center() {
+    let shader = shader(
+        vec![],
+        vec![
+            Operation::LoadFloat {
+                dst: float_reg(1, &[B, A]),
+                val: 1.0,
+            },
+            Operation::Normal {
+                opcode: Opcode::Mov,
+                dst: float_reg(1, &[R, G]),
+                src: float_reg(0, &[R, G]),
+            },
+        ],
+    );
+    let output = run(&shader, &[], (2, 2), OutOfRangeMode::Clamp);
+    assert_eq!(
+        output.pixels,
+        vec![
+            [0.5, 0.5, 1.0, 1.0],
+            [1.5, 0.5, 1.0, 1.0],
+            [0.5, 1.5, 1.0, 1.0],
+            [1.5, 1.5, 1.0, 1.0],
+        ]
+    );
+}
+
+#[test]
+fn sample_nearest_copies_input() {
+    let shader = shader(
+        vec![PixelBenderParam::Texture {
+            index: 0,
+            channels: 4,
+            name: "src".to_string(),
+        }],
+        vec![Operation::SampleNearest {
+            dst: float_reg(1, &[R, G, B, A]),
+            src: float_reg(0, &[R, G]),
+            tf: 0,
+        }],
+    );
+    let pixels = vec![255, 0, 0, 255, 0, 128, 0, 128, 0, 0, 0, 0, 10, 20, 30, 40];
+    let input = ShaderImage::from_rgba8(2, 2, &pixels);
+    let handle = crate::bitmap::BitmapHandle(std::sync::Arc::new(TestBitmap));
+    let output = run_shader(
+        &shader,
+        &[image_input(0, ImageInputTexture::Bitmap(handle))],
+        2,
+        2,
+        OutOfRangeMode::Clamp,
+        |_| Ok(input.clone()),
+    )
+    .expect("Shader should run");
+    assert_eq!(output.to_rgba8(), pixels);
+}
+
+#[derive(Debug)]
+struct TestBitmap;
+
+impl crate::bitmap::BitmapHandleImpl for TestBitmap {}
+
+#[test]
+fn out_of_range_samples() {
+    // Sample one pixel to the right of the current one
+    let shader = shader(
+        vec![],
+        vec![
+            Operation::LoadFloat {
+                dst: float_reg(2, &[R]),
+                val: 1.0,
+            },
+            Operation::Normal {
+                opcode: Opcode::Add,
+                dst: float_reg(0, &[R]),
+                src: float_reg(2, &[R]),
+            },
+            Operation::SampleNearest {
+                dst: float_reg(1, &[R, G, B, A]),
+                src: float_reg(0, &[R, G]),
+                tf: 0,
+            },
+        ],
+    );
+    let input = ImageInputTexture::Bytes {
+        width: 2,
+        height: 1,
+        channels: 1,
+        bytes: float_bytes(&[0.25, 0.75]),
+    };
+    let arguments = [image_input(0, input)];
+
+    let output = run(&shader, &arguments, (2, 1), OutOfRangeMode::Clamp);
+    assert_eq!(
+        output.pixels,
+        vec![[0.75, 0.0, 0.0, 1.0], [0.75, 0.0, 0.0, 1.0]]
+    );
+
+    let output = run(&shader, &arguments, (2, 1), OutOfRangeMode::Zero);
+    assert_eq!(output.pixels, vec![[0.75, 0.0, 0.0, 1.0], [0.0; 4]]);
+}
+
+#[test]
+fn sample_linear_interpolates() {
+    let shader = shader(
+        vec![],
+        vec![
+            // Sample halfway between the two pixels
+            Operation::LoadFloat {
+                dst: float_reg(2, &[R]),
+                val: 0.5,
+            },
+            Operation::Normal {
+                opcode: Opcode::Add,
+                dst: float_reg(0, &[R]),
+                src: float_reg(2, &[R]),
+            },
+            Operation::SampleLinear {
+                dst: float_reg(1, &[R, G, B, A]),
+                src: float_reg(0, &[R, G]),
+                tf: 0,
+            },
+        ],
+    );
+    let input = ImageInputTexture::Bytes {
+        width: 2,
+        height: 1,
+        channels: 2,
+        bytes: float_bytes(&[0.0, 1.0, 1.0, 0.0]),
+    };
+    let output = run(
+        &shader,
+        &[image_input(0, input)],
+        (1, 1),
+        OutOfRangeMode::Clamp,
+    );
+    assert_eq!(output.pixels, vec![[0.5, 0.5, 0.0, 1.0]]);
+}
+
+#[test]
+fn if_else_uses_comparison_result() {
+    // if (_OutCoord.x < threshold) { dst = 1.0 } else { dst = 0.25 }
+    let shader = shader(
+        vec![param(
+            PixelBenderParamQualifier::Input,
+            PixelBenderTypeOpcode::TFloat,
+            float_reg(2, &[R]),
+            "threshold",
+        )],
+        vec![
+            Operation::Normal {
+                opcode: Opcode::LessThan,
+                dst: float_reg(0, &[R]),
+                src: float_reg(2, &[R]),
+            },
+            Operation::If {
+                src: int_reg(0, &[R]),
+            },
+            Operation::LoadFloat {
+                dst: float_reg(1, &[R, G, B, A]),
+                val: 1.0,
+            },
+            Operation::Else,
+            Operation::LoadFloat {
+                dst: float_reg(1, &[R, G, B, A]),
+                val: 0.25,
+            },
+            Operation::EndIf,
+        ],
+    );
+    let arguments = [PixelBenderShaderArgument::ValueInput {
+        index: 2,
+        value: PixelBenderType::TFloat(2.0),
+    }];
+    let output = run(&shader, &arguments, (4, 1), OutOfRangeMode::Clamp);
+    assert_eq!(
+        output.pixels,
+        vec![[1.0; 4], [1.0; 4], [0.25; 4], [0.25; 4]]
+    );
+}
+
+#[test]
+fn select_and_int_registers() {
+    // i1.r = int(_OutCoord.x); dst.r = i1.r != 0 ? 0.5 : 0.75
+    let shader = shader(
+        vec![],
+        vec![
+            Operation::Normal {
+                opcode: Opcode::FloatToInt,
+                dst: int_reg(1, &[R]),
+                src: float_reg(0, &[R]),
+            },
+            Operation::LoadFloat {
+                dst: float_reg(2, &[R]),
+                val: 0.5,
+            },
+            Operation::LoadFloat {
+                dst: float_reg(2, &[G]),
+                val: 0.75,
+            },
+            Operation::Select {
+                src1: float_reg(2, &[R]),
+                src2: float_reg(2, &[G]),
+                condition: int_reg(1, &[R]),
+                dst: float_reg(1, &[R]),
+            },
+        ],
+    );
+    let output = run(&shader, &[], (2, 1), OutOfRangeMode::Clamp);
+    assert_eq!(output.pixels[0][0], 0.75);
+    assert_eq!(output.pixels[1][0], 0.5);
+}
+
+#[test]
+fn matrix_vector_multiplication() {
+    let matrix_reg = PixelBenderReg {
+        index: 3,
+        channels: vec![PixelBenderRegChannel::M3x3],
+        kind: PixelBenderRegKind::Float,
+    };
+    let shader = shader(
+        vec![
+            param(
+                PixelBenderParamQualifier::Input,
+                PixelBenderTypeOpcode::TFloat3x3,
+                matrix_reg.clone(),
+                "matrix",
+            ),
+            param(
+                PixelBenderParamQualifier::Input,
+                PixelBenderTypeOpcode::TFloat3,
+                float_reg(2, &[R, G, B]),
+                "vector",
+            ),
+        ],
+        vec![
+            Operation::Normal {
+                opcode: Opcode::MatVecMul,
+                dst: float_reg(2, &[R, G, B]),
+                src: matrix_reg,
+            },
+            Operation::Normal {
+                opcode: Opcode::Mov,
+                dst: float_reg(1, &[R, G, B]),
+                src: float_reg(2, &[R, G, B]),
+            },
+        ],
+    );
+    let arguments = [
+        // Column-major: the first column is (1, 2, 3)
+        PixelBenderShaderArgument::ValueInput {
+            index: 2,
+            value: PixelBenderType::TFloat3x3([1.0, 2.0, 3.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]),
+        },
+        PixelBenderShaderArgument::ValueInput {
+            index: 3,
+            value: PixelBenderType::TFloat3(1.0, 1.0, 1.0),
+        },
+    ];
+    let output = run(&shader, &arguments, (1, 1), OutOfRangeMode::Clamp);
+    assert_eq!(output.pixels[0][..3], [1.0, 3.0, 4.0]);
+}
+
+#[test]
+fn float3_output_as_bytes() {
+    let mut shader = shader(
+        vec![],
+        vec![
+            Operation::Normal {
+                opcode: Opcode::Mov,
+                dst: float_reg(1, &[R, G]),
+                src: float_reg(0, &[R, G]),
+            },
+            Operation::LoadFloat {
+                dst: float_reg(1, &[B]),
+                val: -2.0,
+            },
+        ],
+    );
+    shader.params[1] = param(
+        PixelBenderParamQualifier::Output,
+        PixelBenderTypeOpcode::TFloat3,
+        float_reg(1, &[R, G, B]),
+        "dst",
+    );
+    let output = run(&shader, &[], (2, 1), OutOfRangeMode::Clamp);
+    assert_eq!(output.channels, 3);
+    assert_eq!(
+        output.to_float_bytes(),
+        float_bytes(&[0.5, 0.5, -2.0, 1.5, 0.5, -2.0])
+    );
+}
+
+#[test]
+fn unbalanced_if_is_an_error() {
+    let shader = shader(
+        vec![],
+        vec![Operation::If {
+            src: int_reg(0, &[R]),
+        }],
+    );
+    let result = run_shader(
+        &shader,
+        &[],
+        1,
+        1,
+        OutOfRangeMode::Clamp,
+        |_| unreachable!(),
+    );
+    assert!(result.is_err());
+}
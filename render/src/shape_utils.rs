@@ -1,4 +1,11 @@
+use crate::backend::{PixelBenderOutput, PixelBenderTarget, RenderBackend};
+use crate::bitmap::{Bitmap, BitmapFormat, BitmapHandle, SyncHandle};
+use crate::error::Error;
 use crate::matrix::Matrix;
+use crate::pixel_bender::{
+    Opcode, Operation, PixelBenderParam, PixelBenderReg, PixelBenderRegChannel, PixelBenderRegKind,
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle, OUT_COORD_NAME,
+};
 use enum_map::Enum;
 use smallvec::SmallVec;
 use swf::{CharacterId, FillStyle, LineStyle, Rectangle, Shape, ShapeRecord, Twips};
@@ -95,6 +102,210 @@ pub enum DrawPath<'a> {
     },
 }
 
+/// A fill whose colors are computed by a Pixel Bender shader.
+///
+/// Backends can't draw these directly: the shader is first run into a bitmap,
+/// which is then used as a regular bitmap fill.
+#[derive(Clone, Debug)]
+pub struct ShaderFill {
+    pub shader: PixelBenderShaderHandle,
+    pub arguments: Vec<PixelBenderShaderArgument<'static>>,
+
+    /// Maps the shader's coordinates to the shape's coordinates, like the matrix of a bitmap fill.
+    pub matrix: swf::Matrix,
+}
+
+/// The area of a shader's coordinate space that a shader fill is rendered over, in pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShaderFillArea {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ShaderFill {
+    /// The largest bitmap, on either side, that a shader fill will be rendered into.
+    pub const MAX_SIZE: u32 = 2048;
+
+    /// The area of the shader's coordinate space needed to cover `bounds`,
+    /// which are in the shape's coordinate space.
+    ///
+    /// Areas larger than `max_width` or `max_height` (or `MAX_SIZE`) are cut short,
+    /// and repeat to cover the rest of the bounds.
+    pub fn area_for_bounds(
+        &self,
+        bounds: &Rectangle<Twips>,
+        max_width: u32,
+        max_height: u32,
+    ) -> Option<ShaderFillArea> {
+        let inverse = Matrix::from(self.matrix).inverse()?;
+        // The matrix already includes the scale from pixels to twips,
+        // so the "twips" of the result are really shader pixels.
+        let bounds = inverse * bounds.clone();
+        if !bounds.is_valid() {
+            return None;
+        }
+        let size = |min: Twips, max: Twips, max_size: u32| {
+            (i64::from(max.get()) - i64::from(min.get()))
+                .clamp(0, max_size.min(Self::MAX_SIZE).into()) as u32
+        };
+        let area = ShaderFillArea {
+            x: bounds.x_min.get(),
+            y: bounds.y_min.get(),
+            width: size(bounds.x_min, bounds.x_max, max_width),
+            height: size(bounds.y_min, bounds.y_max, max_height),
+        };
+        (area.width > 0 && area.height > 0).then_some(area)
+    }
+
+    /// The shader to run over `area`.
+    ///
+    /// Unless the area starts at the origin, this is a rewritten copy of the shader
+    /// which has to be compiled, so callers should keep it for as long as the area is the same.
+    pub fn shader_for_area(
+        &self,
+        renderer: &mut dyn RenderBackend,
+        area: ShaderFillArea,
+    ) -> Result<PixelBenderShaderHandle, Error> {
+        if area.x == 0 && area.y == 0 {
+            return Ok(self.shader.clone());
+        }
+        match wrap_shader_coordinates(self.shader.0.parsed_shader(), area) {
+            Some(shader) => renderer.compile_pixelbender_shader(shader),
+            None => Ok(self.shader.clone()),
+        }
+    }
+
+    /// Runs `shader`, as returned by `shader_for_area`, over `area` into a new bitmap,
+    /// returning it along with the handle that the backend uses to track the shader's writes to it.
+    ///
+    /// The fill repeats the bitmap (see `bitmap_fill`), so a pixel of the bitmap is
+    /// drawn at every shader coordinate that it's equal to modulo the size of the area.
+    /// Each pixel is computed at the one of these coordinates that lies within `area`.
+    pub fn render(
+        &self,
+        renderer: &mut dyn RenderBackend,
+        shader: PixelBenderShaderHandle,
+        area: ShaderFillArea,
+    ) -> Result<(BitmapHandle, Box<dyn SyncHandle>), Error> {
+        let bitmap = Bitmap::new(
+            area.width,
+            area.height,
+            BitmapFormat::Rgba,
+            vec![0; area.width as usize * area.height as usize * 4],
+        );
+        let handle = renderer.register_bitmap(bitmap)?;
+        let output = renderer.run_pixelbender_shader(
+            shader,
+            &self.arguments,
+            &PixelBenderTarget::Bitmap(handle.clone()),
+        )?;
+        match output {
+            PixelBenderOutput::Bitmap(sync_handle) => Ok((handle, sync_handle)),
+            PixelBenderOutput::Bytes(_) => Err(Error::UnexpectedShaderOutput),
+        }
+    }
+
+    /// The bitmap fill that draws the rendered shader, given its bitmap id.
+    pub fn bitmap_fill(&self, id: u16) -> FillStyle {
+        FillStyle::Bitmap {
+            id,
+            matrix: self.matrix,
+            is_smoothed: false,
+            is_repeating: true,
+        }
+    }
+}
+
+/// Rewrites `shader` so that a pixel at `outCoord` is computed at the coordinate within `area`
+/// that's equal to it modulo the size of the area, returning `None` if it doesn't use `outCoord`.
+///
+/// This is done by offsetting `outCoord` before any other operation runs:
+/// `outCoord = mod(outCoord + (-area.x mod width), width) + area.x`, and likewise for y.
+/// Both operands of `mod` are kept positive, where all backends agree on its result.
+fn wrap_shader_coordinates(
+    shader: &PixelBenderShader,
+    area: ShaderFillArea,
+) -> Option<PixelBenderShader> {
+    let out_coord = shader.params.iter().find_map(|param| match param {
+        PixelBenderParam::Normal { reg, name, .. } if name == OUT_COORD_NAME => Some(reg.clone()),
+        _ => None,
+    })?;
+
+    // Use a float register after every register that the shader uses,
+    // leaving room for matrices which take up several registers.
+    let mut used = shader
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            PixelBenderParam::Normal { reg, .. } => Some(reg),
+            PixelBenderParam::Texture { .. } => None,
+        })
+        .collect::<Vec<_>>();
+    for operation in &shader.operations {
+        match operation {
+            Operation::Normal { dst, src, .. }
+            | Operation::SampleNearest { dst, src, .. }
+            | Operation::SampleLinear { dst, src, .. } => used.extend([dst, src]),
+            Operation::LoadInt { dst, .. } | Operation::LoadFloat { dst, .. } => used.push(dst),
+            Operation::If { src } => used.push(src),
+            Operation::Select {
+                src1,
+                src2,
+                condition,
+                dst,
+            } => used.extend([src1, src2, condition, dst]),
+            Operation::Nop | Operation::Else | Operation::EndIf => {}
+        }
+    }
+    let index = used
+        .iter()
+        .filter(|reg| reg.kind == PixelBenderRegKind::Float)
+        .map(|reg| reg.index + 4)
+        .max()
+        .unwrap_or_default();
+    let reg = |channels: &[PixelBenderRegChannel]| PixelBenderReg {
+        index,
+        channels: channels.to_vec(),
+        kind: PixelBenderRegKind::Float,
+    };
+    let load = |x: f32, y: f32| {
+        [
+            Operation::LoadFloat {
+                dst: reg(&[PixelBenderRegChannel::R]),
+                val: x,
+            },
+            Operation::LoadFloat {
+                dst: reg(&[PixelBenderRegChannel::G]),
+                val: y,
+            },
+        ]
+    };
+    let apply = |opcode| Operation::Normal {
+        opcode,
+        dst: out_coord.clone(),
+        src: reg(&[PixelBenderRegChannel::R, PixelBenderRegChannel::G]),
+    };
+
+    let (width, height) = (i64::from(area.width), i64::from(area.height));
+    let shift_x = (-i64::from(area.x)).rem_euclid(width) as f32;
+    let shift_y = (-i64::from(area.y)).rem_euclid(height) as f32;
+    let mut operations = Vec::with_capacity(shader.operations.len() + 9);
+    operations.extend(load(shift_x, shift_y));
+    operations.push(apply(Opcode::Add));
+    operations.extend(load(area.width as f32, area.height as f32));
+    operations.push(apply(Opcode::Mod));
+    operations.extend(load(area.x as f32, area.y as f32));
+    operations.push(apply(Opcode::Add));
+    operations.extend(shader.operations.iter().cloned());
+
+    Some(PixelBenderShader {
+        operations,
+        ..shader.clone()
+    })
+}
+
 /// `DistilledShape` represents a ready-to-be-consumed collection of paths (both fills and strokes)
 /// that has been converted down from another source (such as SWF's `swf::Shape` format).
 #[derive(Clone, Debug, PartialEq)]
@@ -1462,4 +1673,215 @@ mod tests {
             1,
         );
     }
+
+    fn dummy_shader(
+        operations: Vec<crate::pixel_bender::Operation>,
+    ) -> crate::pixel_bender::PixelBenderShader {
+        use crate::pixel_bender::{
+            PixelBenderParamQualifier, PixelBenderRegChannel::*, PixelBenderTypeOpcode,
+        };
+
+        let param =
+            |qualifier, param_type, index, channels: &[PixelBenderRegChannel], name: &str| {
+                PixelBenderParam::Normal {
+                    qualifier,
+                    param_type,
+                    reg: PixelBenderReg {
+                        index,
+                        channels: channels.to_vec(),
+                        kind: PixelBenderRegKind::Float,
+                    },
+                    name: name.to_string(),
+                    metadata: vec![],
+                }
+            };
+        PixelBenderShader {
+            name: String::new(),
+            version: 1,
+            params: vec![
+                param(
+                    PixelBenderParamQualifier::Input,
+                    PixelBenderTypeOpcode::TFloat2,
+                    0,
+                    &[R, G],
+                    OUT_COORD_NAME,
+                ),
+                param(
+                    PixelBenderParamQualifier::Output,
+                    PixelBenderTypeOpcode::TFloat4,
+                    1,
+                    &[R, G, B, A],
+                    "dst",
+                ),
+            ],
+            metadata: vec![],
+            operations,
+        }
+    }
+
+    fn shader_fill(matrix: Matrix) -> ShaderFill {
+        #[derive(Debug)]
+        struct DummyShader(PixelBenderShader);
+
+        impl crate::pixel_bender::PixelBenderShaderImpl for DummyShader {
+            fn parsed_shader(&self) -> &PixelBenderShader {
+                &self.0
+            }
+        }
+
+        ShaderFill {
+            shader: PixelBenderShaderHandle(std::sync::Arc::new(DummyShader(dummy_shader(vec![])))),
+            arguments: vec![],
+            matrix: (matrix * Matrix::PIXELS_TO_TWIPS).into(),
+        }
+    }
+
+    #[test]
+    fn test_shader_fill_area_for_bounds() {
+        let area = |x, y, width, height| {
+            Some(ShaderFillArea {
+                x,
+                y,
+                width,
+                height,
+            })
+        };
+        let bounds = Rectangle {
+            x_min: Twips::from_pixels(10.0),
+            y_min: Twips::from_pixels(20.0),
+            x_max: Twips::from_pixels(110.0),
+            y_max: Twips::from_pixels(70.0),
+        };
+
+        assert_eq!(
+            shader_fill(Matrix::IDENTITY).area_for_bounds(&bounds, u32::MAX, u32::MAX),
+            area(10, 20, 100, 50)
+        );
+        assert_eq!(
+            shader_fill(Matrix::scale(2.0, 0.5)).area_for_bounds(&bounds, u32::MAX, u32::MAX),
+            area(5, 40, 50, 100)
+        );
+
+        // Bounds at negative shader coordinates are covered too.
+        assert_eq!(
+            shader_fill(Matrix::translate(
+                Twips::from_pixels(200.0),
+                Twips::from_pixels(200.0)
+            ))
+            .area_for_bounds(&bounds, u32::MAX, u32::MAX),
+            area(-190, -180, 100, 50)
+        );
+        assert_eq!(
+            shader_fill(Matrix::scale(-1.0, 1.0)).area_for_bounds(&bounds, u32::MAX, u32::MAX),
+            area(-110, 20, 100, 50)
+        );
+
+        // Large areas are limited, and degenerate matrices cover nothing.
+        let huge = Rectangle {
+            x_min: Twips::from_pixels(-5000.0),
+            y_min: Twips::ZERO,
+            x_max: Twips::from_pixels(5000.0),
+            y_max: Twips::from_pixels(1.0),
+        };
+        assert_eq!(
+            shader_fill(Matrix::IDENTITY).area_for_bounds(&huge, u32::MAX, u32::MAX),
+            area(-5000, 0, ShaderFill::MAX_SIZE, 1)
+        );
+        assert_eq!(
+            shader_fill(Matrix::IDENTITY).area_for_bounds(&huge, 550, 400),
+            area(-5000, 0, 550, 1)
+        );
+        assert_eq!(
+            shader_fill(Matrix::IDENTITY).area_for_bounds(&bounds, 50, 10),
+            area(10, 20, 50, 10)
+        );
+        assert_eq!(
+            shader_fill(Matrix::ZERO).area_for_bounds(&bounds, u32::MAX, u32::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn test_wrap_shader_coordinates() {
+        use crate::pixel_bender::interpreter::{run_shader, OutOfRangeMode};
+        use crate::pixel_bender::PixelBenderRegChannel::*;
+
+        let reg = |index, channels: &[PixelBenderRegChannel]| PixelBenderReg {
+            index,
+            channels: channels.to_vec(),
+            kind: PixelBenderRegKind::Float,
+        };
+        // Outputs its coordinates as the color.
+        let shader = dummy_shader(vec![
+            Operation::LoadFloat {
+                dst: reg(1, &[B, A]),
+                val: 1.0,
+            },
+            Operation::Normal {
+                opcode: Opcode::Mov,
+                dst: reg(1, &[R, G]),
+                src: reg(0, &[R, G]),
+            },
+        ]);
+
+        let run = |area: ShaderFillArea| {
+            let shader = wrap_shader_coordinates(&shader, area).expect("Shader uses outCoord");
+            run_shader(
+                &shader,
+                &[],
+                area.width,
+                area.height,
+                OutOfRangeMode::Clamp,
+                |_| Err(Error::Unimplemented("bitmaps".into())),
+            )
+            .expect("Shader should run")
+            .pixels
+            .iter()
+            .map(|pixel| (pixel[0], pixel[1]))
+            .collect::<Vec<_>>()
+        };
+
+        // Every pixel is computed at the coordinate within the area that it repeats at.
+        assert_eq!(
+            run(ShaderFillArea {
+                x: -2,
+                y: 5,
+                width: 3,
+                height: 2
+            }),
+            vec![
+                (0.5, 6.5),
+                (-1.5, 6.5),
+                (-0.5, 6.5),
+                (0.5, 5.5),
+                (-1.5, 5.5),
+                (-0.5, 5.5),
+            ]
+        );
+        assert_eq!(
+            run(ShaderFillArea {
+                x: 0,
+                y: 0,
+                width: 2,
+                height: 1
+            }),
+            vec![(0.5, 0.5), (1.5, 0.5)]
+        );
+
+        let no_coordinates = dummy_shader(vec![]);
+        let no_coordinates = PixelBenderShader {
+            params: no_coordinates.params[1..].to_vec(),
+            ..no_coordinates
+        };
+        assert!(wrap_shader_coordinates(
+            &no_coordinates,
+            ShaderFillArea {
+                x: 1,
+                y: 1,
+                width: 1,
+                height: 1
+            }
+        )
+        .is_none());
+    }
 }
@@ -1,4 +1,6 @@
 use crate::matrix::Matrix;
+use crate::matrix3d::Matrix3D;
+use crate::perspective_projection::PerspectiveProjection;
 use swf::ColorTransform;
 
 /// Represents the transform for a DisplayObject.
@@ -7,6 +9,18 @@ use swf::ColorTransform;
 pub struct Transform {
     pub matrix: Matrix,
     pub color_transform: ColorTransform,
+
+    /// The 3D transformation matrix, if this object has been moved in 3D space.
+    ///
+    /// On a display object, this replaces `matrix` when rendering, and `matrix` holds
+    /// its 2D components. When passed to `CommandHandler::render_bitmap`, this maps
+    /// bitmap pixels straight to homogeneous output pixels, with the perspective already applied.
+    /// Only `render_bitmap` honours it: objects with a 3D transform are drawn into a cached bitmap
+    /// first, so backends draw shapes with `matrix` alone.
+    pub matrix3d: Option<Matrix3D>,
+
+    /// The projection used for any 3D children of this object.
+    pub perspective_projection: Option<PerspectiveProjection>,
 }
 
 pub struct TransformStack(Vec<Transform>);
@@ -20,9 +34,13 @@ impl TransformStack {
         let cur_transform = self.transform();
         let matrix = cur_transform.matrix * transform.matrix;
         let color_transform = cur_transform.color_transform * transform.color_transform;
+        // 3D transforms don't stack: 3D objects are flattened into a bitmap first,
+        // which is then projected onto the parent.
         self.0.push(Transform {
             matrix,
             color_transform,
+            matrix3d: None,
+            perspective_projection: None,
         });
     }
 
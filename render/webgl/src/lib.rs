@@ -14,6 +14,13 @@ use ruffle_render::bitmap::{
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::error::Error as BitmapError;
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
+use ruffle_render::pixel_bender::interpreter::{
+    run_shader, InterpretedShader, OutOfRangeMode, ShaderImage,
+};
+use ruffle_render::pixel_bender::{
+    PixelBenderShader, PixelBenderShaderArgument, PixelBenderShaderHandle,
+};
 use ruffle_render::quality::StageQuality;
 use ruffle_render::shape_utils::{DistilledShape, GradientType};
 use ruffle_render::tessellator::{
@@ -173,6 +180,14 @@ fn as_registry_data(handle: &BitmapHandle) -> &RegistryData {
         .expect("Bitmap handle must be webgl RegistryData")
 }
 
+/// A bitmap written to by a shader, which is read back from its texture.
+#[derive(Debug)]
+struct WebGlSyncHandle {
+    handle: BitmapHandle,
+}
+
+impl SyncHandle for WebGlSyncHandle {}
+
 const MAX_GRADIENT_COLORS: usize = 15;
 
 impl WebGlRenderBackend {
@@ -781,6 +796,40 @@ impl WebGlRenderBackend {
             .blend_func_separate(src_rgb, dst_rgb, Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA);
     }
 
+    /// Reads back the RGBA pixels of a texture, by attaching it to a temporary framebuffer.
+    fn read_texture_pixels(&self, data: &RegistryData) -> Result<Vec<u8>, BitmapError> {
+        let framebuffer = self
+            .gl
+            .create_framebuffer()
+            .ok_or_else(|| BitmapError::JavascriptError("Unable to create framebuffer".into()))?;
+        self.gl
+            .bind_framebuffer(Gl::FRAMEBUFFER, Some(&framebuffer));
+        self.gl.framebuffer_texture_2d(
+            Gl::FRAMEBUFFER,
+            Gl::COLOR_ATTACHMENT0,
+            Gl::TEXTURE_2D,
+            Some(&data.texture),
+            0,
+        );
+
+        let mut pixels = vec![0; data.width as usize * data.height as usize * 4];
+        let result = self.gl.read_pixels_with_opt_u8_array(
+            0,
+            0,
+            data.width as i32,
+            data.height as i32,
+            Gl::RGBA,
+            Gl::UNSIGNED_BYTE,
+            Some(&mut pixels),
+        );
+        self.gl.bind_framebuffer(Gl::FRAMEBUFFER, None);
+        self.gl.delete_framebuffer(Some(&framebuffer));
+        result
+            .into_js_result()
+            .map_err(|e| BitmapError::JavascriptError(e.into()))?;
+        Ok(pixels)
+    }
+
     fn begin_frame(&mut self, clear: Color) {
         self.active_program = std::ptr::null();
         self.mask_state = MaskState::NoMask;
@@ -1197,30 +1246,69 @@ impl RenderBackend for WebGlRenderBackend {
 
     fn compile_pixelbender_shader(
         &mut self,
-        _shader: ruffle_render::pixel_bender::PixelBenderShader,
-    ) -> Result<ruffle_render::pixel_bender::PixelBenderShaderHandle, BitmapError> {
-        Err(BitmapError::Unimplemented(
-            "compile_pixelbender_shader".into(),
-        ))
+        shader: PixelBenderShader,
+    ) -> Result<PixelBenderShaderHandle, BitmapError> {
+        Ok(PixelBenderShaderHandle(Arc::new(InterpretedShader::new(
+            shader,
+        ))))
     }
 
     fn resolve_sync_handle(
         &mut self,
-        _handle: Box<dyn SyncHandle>,
-        _with_rgba: RgbaBufRead,
-    ) -> Result<(), ruffle_render::error::Error> {
-        Err(ruffle_render::error::Error::Unimplemented(
-            "Sync handle resolution".into(),
-        ))
+        handle: Box<dyn SyncHandle>,
+        with_rgba: RgbaBufRead,
+    ) -> Result<(), BitmapError> {
+        let handle = handle
+            .downcast::<WebGlSyncHandle>()
+            .map_err(|_| BitmapError::Unimplemented("Foreign sync handle".into()))?;
+        let data = as_registry_data(&handle.handle);
+        let pixels = self.read_texture_pixels(data)?;
+        with_rgba(&pixels, data.width * 4);
+        Ok(())
     }
 
     fn run_pixelbender_shader(
         &mut self,
-        _handle: ruffle_render::pixel_bender::PixelBenderShaderHandle,
-        _arguments: &[ruffle_render::pixel_bender::PixelBenderShaderArgument],
-        _target: &PixelBenderTarget,
+        handle: PixelBenderShaderHandle,
+        arguments: &[PixelBenderShaderArgument],
+        target: &PixelBenderTarget,
     ) -> Result<PixelBenderOutput, BitmapError> {
-        Err(BitmapError::Unimplemented("run_pixelbender_shader".into()))
+        // Shaders aren't translated to GLSL, so they're interpreted on the CPU.
+        let (width, height) = match target {
+            PixelBenderTarget::Bitmap(bitmap) => {
+                let data = as_registry_data(bitmap);
+                (data.width, data.height)
+            }
+            PixelBenderTarget::Bytes { width, height } => (*width, *height),
+        };
+        let output = run_shader(
+            handle.0.parsed_shader(),
+            arguments,
+            width,
+            height,
+            OutOfRangeMode::Clamp,
+            |bitmap| {
+                let data = as_registry_data(bitmap);
+                let pixels = self.read_texture_pixels(data)?;
+                Ok(ShaderImage::from_rgba8(data.width, data.height, &pixels))
+            },
+        )?;
+
+        match target {
+            PixelBenderTarget::Bitmap(bitmap) => {
+                self.update_texture(
+                    bitmap,
+                    Bitmap::new(width, height, BitmapFormat::Rgba, output.to_rgba8()),
+                    PixelRegion::for_whole_size(width, height),
+                )?;
+                Ok(PixelBenderOutput::Bitmap(Box::new(WebGlSyncHandle {
+                    handle: bitmap.clone(),
+                })))
+            }
+            PixelBenderTarget::Bytes { .. } => {
+                Ok(PixelBenderOutput::Bytes(output.to_float_bytes()))
+            }
+        }
     }
 
     fn create_empty_texture(
@@ -1273,21 +1361,27 @@ impl CommandHandler for WebGlRenderBackend {
         };
 
         // Scale the quad to the bitmap's dimensions.
-        let mut matrix = transform.matrix;
-        pixel_snapping.apply(&mut matrix);
-        matrix *= Matrix::scale(entry.width as f32, entry.height as f32);
+        let world_matrix = if let Some(matrix3d) = transform.matrix3d {
+            // Varyings are interpolated in a perspective-correct way, so we can leave the divide to the GPU.
+            (matrix3d * Matrix3D::scale(entry.width as f64, entry.height as f64, 1.0))
+                .to_gpu_matrix()
+        } else {
+            let mut matrix = transform.matrix;
+            pixel_snapping.apply(&mut matrix);
+            matrix *= Matrix::scale(entry.width as f32, entry.height as f32);
 
-        let world_matrix = [
-            [matrix.a, matrix.b, 0.0, 0.0],
-            [matrix.c, matrix.d, 0.0, 0.0],
-            [0.0, 0.0, 1.0, 0.0],
             [
-                matrix.tx.to_pixels() as f32,
-                matrix.ty.to_pixels() as f32,
-                0.0,
-                1.0,
-            ],
-        ];
+                [matrix.a, matrix.b, 0.0, 0.0],
+                [matrix.c, matrix.d, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [
+                    matrix.tx.to_pixels() as f32,
+                    matrix.ty.to_pixels() as f32,
+                    0.0,
+                    1.0,
+                ],
+            ]
+        };
 
         let mult_color = transform.color_transform.mult_rgba_normalized();
         let add_color = transform.color_transform.add_rgba_normalized();
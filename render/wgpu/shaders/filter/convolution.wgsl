@@ -0,0 +1,66 @@
+// NOTE: The `shader_filter_common.wgsl` source is prepended to this before compilation.
+
+struct Filter {
+    /// The matrix values, row by row, packed 4 to a vector
+    matrix: array<vec4<f32>, 64>,
+    default_color: vec4<f32>,
+    bias: f32,
+    divisor: f32,
+    preserve_alpha: u32,
+    clamp_edges: u32,
+    matrix_x: u32,
+    matrix_y: u32,
+    _padding0: u32,
+    _padding1: u32,
+    /// The area of the texture we may read from, as (left, top, right, bottom) in pixels
+    bounds: vec4<i32>,
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var<uniform> filter_args: Filter;
+
+@vertex
+fn main_vertex(in: filter__VertexInput) -> filter__VertexOutput {
+    return filter__main_vertex(in);
+}
+
+fn unpremultiply(color: vec4<f32>) -> vec4<f32> {
+    if (color.a == 0.0) {
+        return vec4<f32>(0.0);
+    }
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
+
+fn load(coord: vec2<i32>) -> vec4<f32> {
+    let bounds = filter_args.bounds;
+    var pos = coord;
+    if (pos.x < bounds.x || pos.y < bounds.y || pos.x >= bounds.z || pos.y >= bounds.w) {
+        if (filter_args.clamp_edges == 0u) {
+            return filter_args.default_color;
+        }
+        pos = clamp(pos, bounds.xy, bounds.zw - vec2<i32>(1));
+    }
+    return unpremultiply(textureLoad(texture, pos, 0));
+}
+
+@fragment
+fn main_fragment(in: filter__VertexOutput) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(texture));
+    let coord = vec2<i32>(floor(in.uv * size));
+    let center = vec2<i32>(i32(filter_args.matrix_x / 2u), i32(filter_args.matrix_y / 2u));
+
+    var sum = vec4<f32>(0.0);
+    for (var y = 0u; y < filter_args.matrix_y; y += 1u) {
+        for (var x = 0u; x < filter_args.matrix_x; x += 1u) {
+            let index = y * filter_args.matrix_x + x;
+            let weight = filter_args.matrix[index / 4u][index % 4u];
+            sum += weight * load(coord + vec2<i32>(i32(x), i32(y)) - center);
+        }
+    }
+
+    var color = saturate(sum / filter_args.divisor + vec4<f32>(filter_args.bias / 255.0));
+    if (filter_args.preserve_alpha > 0u) {
+        color.a = unpremultiply(textureLoad(texture, coord, 0)).a;
+    }
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
@@ -1,26 +1,30 @@
 mod bevel;
 mod blur;
 mod color_matrix;
+mod convolution;
 mod displacement_map;
 mod drop_shadow;
 mod glow;
+mod gradient_bevel;
+mod gradient_glow;
 mod shader;
 
-use std::collections::HashSet;
-use std::sync::{Mutex, OnceLock};
-
 use crate::buffer_pool::TexturePool;
 use crate::descriptors::Descriptors;
 use crate::filters::bevel::BevelFilter;
 use crate::filters::blur::BlurFilter;
 use crate::filters::color_matrix::ColorMatrixFilter;
+use crate::filters::convolution::ConvolutionFilter;
 use crate::filters::displacement_map::DisplacementMapFilter;
 use crate::filters::drop_shadow::DropShadowFilter;
 use crate::filters::glow::GlowFilter;
+use crate::filters::gradient_bevel::GradientBevelFilter;
+use crate::filters::gradient_glow::GradientGlowFilter;
 use crate::filters::shader::ShaderFilter;
 use crate::surface::target::CommandTarget;
 use bytemuck::{Pod, Zeroable};
 use ruffle_render::filters::Filter;
+use swf::GradientRecord;
 use wgpu::util::StagingBelt;
 use wgpu::vertex_attr_array;
 
@@ -192,6 +196,9 @@ pub struct Filters {
     pub glow: GlowFilter,
     pub bevel: BevelFilter,
     pub displacement_map: DisplacementMapFilter,
+    pub convolution: ConvolutionFilter,
+    pub gradient_glow: GradientGlowFilter,
+    pub gradient_bevel: GradientBevelFilter,
 }
 
 impl Filters {
@@ -203,6 +210,9 @@ impl Filters {
             glow: GlowFilter::new(device),
             bevel: BevelFilter::new(device),
             displacement_map: DisplacementMapFilter::new(device),
+            convolution: ConvolutionFilter::new(device),
+            gradient_glow: GradientGlowFilter::new(device),
+            gradient_bevel: GradientBevelFilter::new(device),
         }
     }
 
@@ -276,31 +286,32 @@ impl Filters {
                 &source,
                 &filter,
             ),
-            filter => {
-                static WARNED_FILTERS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
-                let name = match filter {
-                    Filter::GradientGlowFilter(_) => "GradientGlowFilter",
-                    Filter::GradientBevelFilter(_) => "GradientBevelFilter",
-                    Filter::ConvolutionFilter(_) => "ConvolutionFilter",
-                    Filter::ColorMatrixFilter(_)
-                    | Filter::BlurFilter(_)
-                    | Filter::GlowFilter(_)
-                    | Filter::DropShadowFilter(_)
-                    | Filter::BevelFilter(_)
-                    | Filter::DisplacementMapFilter(_)
-                    | Filter::ShaderFilter(_) => unreachable!(),
-                };
-                // Only warn once per filter type
-                if WARNED_FILTERS
-                    .get_or_init(Default::default)
-                    .lock()
-                    .unwrap()
-                    .insert(name)
-                {
-                    tracing::warn!("Unsupported filter {filter:?}");
-                }
-                None
-            }
+            Filter::ConvolutionFilter(filter) => descriptors.filters.convolution.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                staging_belt,
+                &source,
+                &filter,
+            ),
+            Filter::GradientGlowFilter(filter) => Some(descriptors.filters.gradient_glow.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                staging_belt,
+                &source,
+                &filter,
+                &self.blur,
+            )),
+            Filter::GradientBevelFilter(filter) => Some(descriptors.filters.gradient_bevel.apply(
+                descriptors,
+                texture_pool,
+                draw_encoder,
+                staging_belt,
+                &source,
+                &filter,
+                &self.blur,
+            )),
         };
 
         let target = target.unwrap_or_else(|| {
@@ -324,6 +335,52 @@ impl Filters {
     }
 }
 
+/// Converts the colors of a gradient filter into a lookup table of premultiplied colors,
+/// indexed by ratio.
+pub fn gradient_lookup_table(records: &[GradientRecord]) -> [[f32; 4]; 256] {
+    let to_rgba = |record: &GradientRecord| {
+        [
+            f32::from(record.color.r) / 255.0,
+            f32::from(record.color.g) / 255.0,
+            f32::from(record.color.b) / 255.0,
+            f32::from(record.color.a) / 255.0,
+        ]
+    };
+    let mut table = [[0.0; 4]; 256];
+    let (Some(first), Some(last)) = (records.first(), records.last()) else {
+        return table;
+    };
+    for (ratio, entry) in table.iter_mut().enumerate() {
+        let ratio = ratio as u8;
+        let color = if ratio <= first.ratio {
+            to_rgba(first)
+        } else if ratio >= last.ratio {
+            to_rgba(last)
+        } else {
+            let end = records
+                .iter()
+                .position(|record| record.ratio >= ratio)
+                .unwrap_or(records.len() - 1)
+                .max(1);
+            let (a, b) = (&records[end - 1], &records[end]);
+            let t = if b.ratio > a.ratio {
+                f32::from(ratio.saturating_sub(a.ratio)) / f32::from(b.ratio - a.ratio)
+            } else {
+                1.0
+            };
+            let (a, b) = (to_rgba(a), to_rgba(b));
+            std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+        };
+        *entry = [
+            color[0] * color[3],
+            color[1] * color[3],
+            color[2] * color[3],
+            color[3],
+        ];
+    }
+    table
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 pub struct FilterVertex {
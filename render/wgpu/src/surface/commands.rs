@@ -13,6 +13,7 @@ use ruffle_render::bitmap::{BitmapHandle, PixelSnapping};
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
 use ruffle_render::lines::{emulate_line, emulate_line_rect};
 use ruffle_render::matrix::Matrix;
+use ruffle_render::matrix3d::Matrix3D;
 use ruffle_render::pixel_bender::PixelBenderShaderHandle;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
@@ -567,8 +568,8 @@ impl<'a> WgpuCommandHandler<'a> {
         color_transform: ColorTransform,
         command_builder: impl FnOnce(wgpu::DynamicOffset) -> DrawCommand,
     ) {
-        let transform = Transforms {
-            world_matrix: [
+        self.add_world_matrix_to_current(
+            [
                 [matrix.a, matrix.b, 0.0, 0.0],
                 [matrix.c, matrix.d, 0.0, 0.0],
                 [0.0, 0.0, 1.0, 0.0],
@@ -579,6 +580,19 @@ impl<'a> WgpuCommandHandler<'a> {
                     1.0,
                 ],
             ],
+            color_transform,
+            command_builder,
+        );
+    }
+
+    fn add_world_matrix_to_current(
+        &mut self,
+        world_matrix: [[f32; 4]; 4],
+        color_transform: ColorTransform,
+        command_builder: impl FnOnce(wgpu::DynamicOffset) -> DrawCommand,
+    ) {
+        let transform = Transforms {
+            world_matrix,
             mult_color: color_transform.mult_rgba_normalized(),
             add_color: color_transform.add_rgba_normalized(),
         };
@@ -641,7 +655,7 @@ impl CommandHandler for WgpuCommandHandler<'_> {
             BlendType::Trivial(blend_mode) => {
                 let transform = Transform {
                     matrix: Matrix::scale(target.width() as f32, target.height() as f32),
-                    color_transform: Default::default(),
+                    ..Default::default()
                 };
                 let texture = target.take_color_texture();
                 let bind_group =
@@ -717,6 +731,30 @@ impl CommandHandler for WgpuCommandHandler<'_> {
         smoothing: bool,
         pixel_snapping: PixelSnapping,
     ) {
+        if let Some(matrix3d) = transform.matrix3d {
+            let world_matrix = {
+                let texture = as_texture(&bitmap);
+                let scale = Matrix3D::scale(
+                    texture.texture.width() as f64,
+                    texture.texture.height() as f64,
+                    1.0,
+                );
+                (matrix3d * scale).to_gpu_matrix()
+            };
+            self.add_world_matrix_to_current(
+                world_matrix,
+                transform.color_transform,
+                |transform_buffer| DrawCommand::RenderBitmap {
+                    bitmap,
+                    transform_buffer,
+                    smoothing,
+                    blend_mode: TrivialBlend::Normal,
+                    render_stage3d: false,
+                },
+            );
+            return;
+        }
+
         let mut matrix = transform.matrix;
         {
             let texture = as_texture(&bitmap);
@@ -1,4 +1,4 @@
-use crate::{BlurFilter, BlurFilterFlags, Fixed16, Fixed8, GradientRecord};
+use crate::{BlurFilter, BlurFilterFlags, Fixed16, Fixed8, GradientRecord, Rectangle, Twips};
 use bitflags::bitflags;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -46,6 +46,20 @@ impl GradientFilter {
             flags: BlurFilterFlags::from_passes(self.num_passes()),
         }
     }
+
+    pub fn calculate_dest_rect(&self, source_rect: Rectangle<Twips>) -> Rectangle<Twips> {
+        // Gradient bevels extend in both directions, so this is also generous enough for glows.
+        let mut result = self.inner_blur_filter().calculate_dest_rect(source_rect);
+        let distance = self.distance.to_f64();
+        let angle = self.angle.to_f64();
+        let x = Twips::from_pixels((angle.cos() * distance).abs());
+        let y = Twips::from_pixels((angle.sin() * distance).abs());
+        result.x_min -= x;
+        result.x_max += x;
+        result.y_min -= y;
+        result.y_max += y;
+        result
+    }
 }
 
 bitflags! {
@@ -1,9 +1,11 @@
 mod audio;
 mod log;
+mod media_capture;
 mod navigator;
 mod ui;
 
 pub use audio::TestAudioBackend;
 pub use log::TestLogBackend;
+pub use media_capture::TestMediaCaptureBackend;
 pub use navigator::TestNavigatorBackend;
 pub use ui::TestUiBackend;
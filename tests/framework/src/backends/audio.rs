@@ -7,6 +7,9 @@ use ruffle_core::impl_audio_mixer_backend;
 pub struct TestAudioBackend {
     mixer: AudioMixer,
     buffer: Vec<f32>,
+
+    /// Everything mixed since recording was started, as interleaved samples.
+    recording: Option<Vec<f32>>,
 }
 
 impl Default for TestAudioBackend {
@@ -14,13 +17,24 @@ impl Default for TestAudioBackend {
         Self {
             mixer: AudioMixer::new(Self::NUM_CHANNELS, Self::SAMPLE_RATE),
             buffer: vec![],
+            recording: None,
         }
     }
 }
 
 impl TestAudioBackend {
-    const NUM_CHANNELS: u8 = 2;
-    const SAMPLE_RATE: u32 = 44100;
+    pub const NUM_CHANNELS: u8 = 2;
+    pub const SAMPLE_RATE: u32 = 44100;
+
+    /// Starts keeping a copy of everything that gets mixed.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(vec![]);
+    }
+
+    /// Takes everything mixed since recording was started, if it was.
+    pub fn take_recording(&mut self) -> Option<Vec<f32>> {
+        self.recording.take()
+    }
 }
 
 impl AudioBackend for TestAudioBackend {
@@ -36,5 +50,8 @@ impl AudioBackend for TestAudioBackend {
     fn tick(&mut self) {
         debug_assert!(!self.buffer.is_empty());
         self.mixer.mix::<f32>(self.buffer.as_mut());
+        if let Some(recording) = &mut self.recording {
+            recording.extend_from_slice(&self.buffer);
+        }
     }
 }
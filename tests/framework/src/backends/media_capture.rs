@@ -0,0 +1,201 @@
+use crate::util::read_bytes;
+use anyhow::{anyhow, Result};
+use image::ImageFormat;
+use ruffle_core::backend::media_capture::{CameraMode, CapturedFrame, MediaCaptureBackend};
+use std::io::Cursor;
+use std::time::Duration;
+use vfs::VfsPath;
+
+/// A camera replaying a sequence of images, at the frame rate it's captured at.
+struct VirtualCamera {
+    frames: Vec<CapturedFrame>,
+    next_frame: usize,
+    fps: f64,
+
+    /// The time until the next frame is captured, if the camera is capturing.
+    until_next_frame: Option<Duration>,
+}
+
+/// A microphone replaying the sound of a WAV file.
+struct VirtualMicrophone {
+    /// The mono samples of the file.
+    samples: Vec<f32>,
+    source_rate: u32,
+
+    /// The position in the file, in samples of the file.
+    position: f64,
+
+    /// The rate the microphone is captured at, if it's capturing.
+    sample_rate: Option<u32>,
+
+    /// The fraction of a sample left over from the previous poll.
+    remainder: f64,
+}
+
+/// A media capture backend with file-backed virtual devices, which loop forever.
+#[derive(Default)]
+pub struct TestMediaCaptureBackend {
+    camera: Option<VirtualCamera>,
+    microphone: Option<VirtualMicrophone>,
+}
+
+impl TestMediaCaptureBackend {
+    /// Adds a camera replaying the PNG images of a directory, in the order of their names.
+    pub fn with_camera(mut self, directory: &VfsPath) -> Result<Self> {
+        let mut paths = directory
+            .read_dir()?
+            .filter(|path| path.extension().as_deref() == Some("png"))
+            .collect::<Vec<_>>();
+        paths.sort_by_key(|path| path.filename());
+
+        let frames = paths
+            .iter()
+            .map(|path| {
+                let image =
+                    image::load_from_memory_with_format(&read_bytes(path)?, ImageFormat::Png)?
+                        .to_rgb8();
+                Ok(CapturedFrame {
+                    width: image.width(),
+                    height: image.height(),
+                    rgb: image.into_raw(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if frames.is_empty() {
+            return Err(anyhow!(
+                "Camera directory {} contains no PNG image",
+                directory.as_str()
+            ));
+        }
+
+        self.camera = Some(VirtualCamera {
+            frames,
+            next_frame: 0,
+            fps: 0.0,
+            until_next_frame: None,
+        });
+        Ok(self)
+    }
+
+    /// Adds a microphone replaying a WAV file, mixed down to mono.
+    pub fn with_microphone(mut self, path: &VfsPath) -> Result<Self> {
+        let reader = hound::WavReader::new(Cursor::new(read_bytes(path)?))?;
+        let spec = reader.spec();
+        let samples: Vec<f32> = match spec.sample_format {
+            hound::SampleFormat::Float => reader.into_samples::<f32>().collect::<Result<_, _>>()?,
+            hound::SampleFormat::Int => {
+                let scale = (1u32 << (spec.bits_per_sample - 1)) as f32;
+                reader
+                    .into_samples::<i32>()
+                    .map(|sample| sample.map(|sample| sample as f32 / scale))
+                    .collect::<Result<_, _>>()?
+            }
+        };
+        let samples = samples
+            .chunks_exact(spec.channels.into())
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect::<Vec<_>>();
+        if samples.is_empty() {
+            return Err(anyhow!("Microphone file {} is empty", path.as_str()));
+        }
+
+        self.microphone = Some(VirtualMicrophone {
+            samples,
+            source_rate: spec.sample_rate,
+            position: 0.0,
+            sample_rate: None,
+            remainder: 0.0,
+        });
+        Ok(self)
+    }
+}
+
+impl MediaCaptureBackend for TestMediaCaptureBackend {
+    fn camera_names(&self) -> Vec<String> {
+        self.camera
+            .iter()
+            .map(|_| "Virtual Camera".to_string())
+            .collect()
+    }
+
+    fn start_camera(&mut self, index: usize, mode: CameraMode) -> Option<CameraMode> {
+        let camera = self.camera.as_mut().filter(|_| index == 0)?;
+        camera.fps = mode.fps;
+        camera.until_next_frame = Some(Duration::ZERO);
+
+        // The images can't be scaled, so they're captured at their own size.
+        Some(CameraMode {
+            width: camera.frames[0].width,
+            height: camera.frames[0].height,
+            fps: mode.fps,
+        })
+    }
+
+    fn stop_camera(&mut self, index: usize) {
+        if let Some(camera) = self.camera.as_mut().filter(|_| index == 0) {
+            camera.until_next_frame = None;
+        }
+    }
+
+    fn camera_frame(&mut self, index: usize, elapsed: Duration) -> Option<CapturedFrame> {
+        let camera = self.camera.as_mut().filter(|_| index == 0)?;
+        let until_next_frame = camera.until_next_frame.as_mut()?;
+
+        if *until_next_frame > elapsed {
+            *until_next_frame -= elapsed;
+            return None;
+        }
+
+        // Frames are never captured faster than polled, so late frames are skipped.
+        *until_next_frame = Duration::from_secs_f64(1.0 / camera.fps);
+        let frame = camera.frames[camera.next_frame].clone();
+        camera.next_frame = (camera.next_frame + 1) % camera.frames.len();
+        Some(frame)
+    }
+
+    fn microphone_names(&self) -> Vec<String> {
+        self.microphone
+            .iter()
+            .map(|_| "Virtual Microphone".to_string())
+            .collect()
+    }
+
+    fn start_microphone(&mut self, index: usize, sample_rate: u32) -> bool {
+        let Some(microphone) = self.microphone.as_mut().filter(|_| index == 0) else {
+            return false;
+        };
+        microphone.sample_rate = Some(sample_rate);
+        microphone.remainder = 0.0;
+        true
+    }
+
+    fn stop_microphone(&mut self, index: usize) {
+        if let Some(microphone) = self.microphone.as_mut().filter(|_| index == 0) {
+            microphone.sample_rate = None;
+        }
+    }
+
+    fn microphone_samples(&mut self, index: usize, elapsed: Duration) -> Vec<f32> {
+        let Some(microphone) = self.microphone.as_mut().filter(|_| index == 0) else {
+            return vec![];
+        };
+        let Some(sample_rate) = microphone.sample_rate else {
+            return vec![];
+        };
+
+        let wanted = f64::from(sample_rate) * elapsed.as_secs_f64() + microphone.remainder;
+        let count = wanted.floor();
+        microphone.remainder = wanted - count;
+
+        // The file is resampled to the rate of the microphone by picking the nearest sample.
+        let step = f64::from(microphone.source_rate) / f64::from(sample_rate);
+        let len = microphone.samples.len() as f64;
+        (0..count as usize)
+            .map(|_| {
+                let sample = microphone.samples[microphone.position as usize];
+                microphone.position = (microphone.position + step) % len;
+                sample
+            })
+            .collect()
+    }
+}
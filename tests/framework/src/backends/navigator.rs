@@ -7,12 +7,16 @@ use ruffle_core::backend::navigator::{
     async_return, create_fetch_error, ErrorResponse, NavigationMethod, NavigatorBackend,
     NullExecutor, NullSpawner, OwnedFuture, Request, SuccessResponse,
 };
+use ruffle_core::datagram_socket::{Datagram, DatagramSocketAction, DatagramSocketHandle};
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
 use ruffle_core::swf::Encoding;
 use ruffle_socket_format::SocketEvent;
 use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 use std::time::Duration;
 use url::{ParseError, Url};
 use vfs::VfsPath;
@@ -72,10 +76,13 @@ impl SuccessResponse for TestResponse {
 ///
 /// These are formatted as query params, rather than domains/whole URLs, so that real/real-invalid
 /// URLs can be used in Flash Player when writing tests
+///
+/// Datagram sockets are bound to a [`LoopbackNetwork`], so that they can only talk to each other.
 pub struct TestNavigatorBackend {
     spawner: NullSpawner,
     relative_base_path: VfsPath,
     socket_events: Option<Vec<SocketEvent>>,
+    loopback: Rc<RefCell<LoopbackNetwork>>,
     log: Option<TestLogBackend>,
 }
 
@@ -90,6 +97,7 @@ impl TestNavigatorBackend {
             spawner: executor.spawner(),
             relative_base_path: path,
             socket_events,
+            loopback: Default::default(),
             log,
         })
     }
@@ -322,4 +330,107 @@ impl NavigatorBackend for TestNavigatorBackend {
             }));
         }
     }
+
+    fn bind_datagram_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        handle: DatagramSocketHandle,
+        receiver: Receiver<Datagram>,
+        sender: Sender<DatagramSocketAction>,
+    ) {
+        if let Some(log) = &self.log {
+            log.avm_trace("Navigator::bind_datagram_socket");
+            log.avm_trace(&format!("    Host: {}; Port: {}", host, port));
+        }
+
+        let Some(port) = self
+            .loopback
+            .borrow_mut()
+            .bind(port, handle, sender.clone())
+        else {
+            sender
+                .try_send(DatagramSocketAction::BindFailed(handle))
+                .expect("working channel send");
+            return;
+        };
+        sender
+            .try_send(DatagramSocketAction::Bound(handle, host, port))
+            .expect("working channel send");
+
+        let loopback = self.loopback.clone();
+        self.spawn_future(Box::pin(async move {
+            while let Ok(datagram) = receiver.recv().await {
+                loopback.borrow().deliver(port, datagram);
+            }
+
+            loopback.borrow_mut().unbind(port, handle);
+            Ok(())
+        }));
+    }
+
+    fn supports_datagram_sockets(&self) -> bool {
+        true
+    }
+}
+
+/// The first port given to sockets bound to port 0, as picked by most systems.
+const FIRST_EPHEMERAL_PORT: u16 = 49152;
+
+/// A network of the datagram sockets bound by a test, which lets LAN games be tested offline.
+///
+/// Every address is treated as the local machine, so packets are delivered to whichever socket
+/// is bound to their destination port, and appear to come from `127.0.0.1`.
+#[derive(Default)]
+struct LoopbackNetwork {
+    sockets: HashMap<u16, (DatagramSocketHandle, Sender<DatagramSocketAction>)>,
+}
+
+impl LoopbackNetwork {
+    /// Binds a socket to a port, or to a free port when it's 0.
+    ///
+    /// Returns the bound port, or `None` when the port is already in use.
+    fn bind(
+        &mut self,
+        port: u16,
+        handle: DatagramSocketHandle,
+        sender: Sender<DatagramSocketAction>,
+    ) -> Option<u16> {
+        let port = if port == 0 {
+            (FIRST_EPHEMERAL_PORT..=u16::MAX).find(|port| !self.sockets.contains_key(port))?
+        } else if self.sockets.contains_key(&port) {
+            return None;
+        } else {
+            port
+        };
+
+        self.sockets.insert(port, (handle, sender));
+        Some(port)
+    }
+
+    /// Frees the port of a closed socket, unless another socket was bound to it since.
+    fn unbind(&mut self, port: u16, handle: DatagramSocketHandle) {
+        if self
+            .sockets
+            .get(&port)
+            .is_some_and(|(bound, _)| *bound == handle)
+        {
+            self.sockets.remove(&port);
+        }
+    }
+
+    fn deliver(&self, source_port: u16, datagram: Datagram) {
+        let Some((handle, sender)) = self.sockets.get(&datagram.port) else {
+            // Like with UDP, packets sent to nobody are silently lost.
+            return;
+        };
+
+        let datagram = Datagram {
+            address: "127.0.0.1".to_string(),
+            port: source_port,
+            data: datagram.data,
+        };
+        // The receiving socket may have been closed in the meantime.
+        let _ = sender.try_send(DatagramSocketAction::Data(*handle, datagram));
+    }
 }
@@ -1,8 +1,11 @@
+use crate::backends::TestLogBackend;
 use crate::test::Font;
 use chrono::{DateTime, Utc};
+use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::ui::{
-    DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    ClipboardData, ClipboardFormat, CustomCursorId, CustomMouseCursor, DialogLoaderError,
+    DialogResultFuture, FileDialogResult, FileFilter, FontDefinition, FullscreenError,
+    LanguageIdentifier, MouseCursor, PrintedPage, UiBackend, US_ENGLISH,
 };
 use url::Url;
 
@@ -75,17 +78,20 @@ impl FileDialogResult for TestFileDialogResult {
 ///   otherwise a user cancellation will be simulated
 /// * Attempting to display a file save dialog with a file name hint of "debug-success.txt" will simulate successfully selecting a destination
 ///   otherwise a user cancellation will be simulated
-/// * Simulated in-memory clipboard
+/// * Simulated in-memory clipboard, which supports every format
+/// * Printed pages are described in the trace output
 pub struct TestUiBackend {
     fonts: Vec<Font>,
-    clipboard: String,
+    clipboard: Vec<ClipboardData>,
+    log: TestLogBackend,
 }
 
 impl TestUiBackend {
-    pub fn new(fonts: Vec<Font>) -> Self {
+    pub fn new(fonts: Vec<Font>, log: TestLogBackend) -> Self {
         Self {
             fonts,
-            clipboard: "".to_string(),
+            clipboard: Vec::new(),
+            log,
         }
     }
 }
@@ -99,12 +105,42 @@ impl UiBackend for TestUiBackend {
 
     fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
 
+    fn register_mouse_cursor(&mut self, _id: CustomCursorId, _cursor: CustomMouseCursor) {}
+
+    fn unregister_mouse_cursor(&mut self, _id: CustomCursorId) {}
+
     fn clipboard_content(&mut self) -> String {
-        self.clipboard.clone()
+        match self.clipboard_data(ClipboardFormat::Text) {
+            Some(ClipboardData::Text(text)) => text,
+            _ => "".to_string(),
+        }
     }
 
     fn set_clipboard_content(&mut self, content: String) {
-        self.clipboard = content;
+        self.clipboard = if content.is_empty() {
+            Vec::new()
+        } else {
+            vec![ClipboardData::Text(content)]
+        };
+    }
+
+    fn supports_clipboard_format(&self, _format: ClipboardFormat) -> bool {
+        true
+    }
+
+    fn clipboard_formats(&mut self) -> Vec<ClipboardFormat> {
+        self.clipboard.iter().map(ClipboardData::format).collect()
+    }
+
+    fn clipboard_data(&mut self, format: ClipboardFormat) -> Option<ClipboardData> {
+        self.clipboard
+            .iter()
+            .find(|data| data.format() == format)
+            .cloned()
+    }
+
+    fn set_clipboard_data(&mut self, data: Vec<ClipboardData>) {
+        self.clipboard = data;
     }
 
     fn set_fullscreen(&mut self, _is_full: bool) -> Result<(), FullscreenError> {
@@ -187,4 +223,14 @@ impl UiBackend for TestUiBackend {
     }
 
     fn close_file_dialog(&mut self) {}
+
+    fn print_pages(&mut self, pages: Vec<PrintedPage>) {
+        self.log.avm_trace("UiBackend::print_pages");
+        for page in pages {
+            self.log.avm_trace(&format!(
+                "    Page: {}x{} at {} dpi",
+                page.width, page.height, page.dpi
+            ));
+        }
+    }
 }
@@ -26,6 +26,7 @@ pub trait Environment {
     /// If [Self::is_render_supported] returned false, this won't be attempted.
     fn create_renderer(
         &self,
+        _requirements: &RenderOptions,
         _width: u32,
         _height: u32,
     ) -> Option<(Box<dyn RenderInterface>, Box<dyn RenderBackend>)> {
@@ -39,6 +40,11 @@ pub trait RenderInterface {
     /// This name may be used in file paths, so it should contain appropriate characters for such.
     fn name(&self) -> String;
 
+    /// Whether this renders on the CPU, which makes image comparisons use their `software` tolerances.
+    fn is_software(&self) -> bool {
+        false
+    }
+
     /// Capture the stage rendered out by the given render backend.
     ///
     /// The provided backend is guaranteed to be the same one paired with this interface.
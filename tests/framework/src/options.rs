@@ -1,7 +1,7 @@
 use crate::backends::TestAudioBackend;
 use crate::environment::{Environment, RenderInterface};
 use crate::image_trigger::ImageTrigger;
-use crate::util::write_image;
+use crate::util::{write_image, write_wav};
 use anyhow::{anyhow, Result};
 use approx::relative_eq;
 use image::ImageFormat;
@@ -24,6 +24,7 @@ pub struct TestOptions {
     pub output_path: String,
     pub sleep_to_meet_frame_rate: bool,
     pub image_comparisons: HashMap<String, ImageComparison>,
+    pub audio_comparison: Option<AudioComparison>,
     pub ignore: bool,
     pub known_failure: bool,
     pub approximations: Option<Approximations>,
@@ -31,6 +32,7 @@ pub struct TestOptions {
     pub log_fetch: bool,
     pub required_features: RequiredFeatures,
     pub fonts: HashMap<String, FontOptions>,
+    pub media_capture: MediaCaptureOptions,
 }
 
 impl Default for TestOptions {
@@ -42,6 +44,7 @@ impl Default for TestOptions {
             output_path: "output.txt".to_string(),
             sleep_to_meet_frame_rate: false,
             image_comparisons: Default::default(),
+            audio_comparison: None,
             ignore: false,
             known_failure: false,
             approximations: None,
@@ -49,6 +52,7 @@ impl Default for TestOptions {
             log_fetch: false,
             required_features: RequiredFeatures::default(),
             fonts: Default::default(),
+            media_capture: Default::default(),
         }
     }
 }
@@ -75,6 +79,12 @@ impl TestOptions {
             }
         }
 
+        if self.audio_comparison.is_some() && !self.player_options.with_audio {
+            return Err(anyhow!(
+                "An audio comparison is set up, but the test doesn't run with audio"
+            ));
+        }
+
         Ok(())
     }
 
@@ -228,8 +238,8 @@ impl PlayerOptions {
         environment: &impl Environment,
         dimensions: ViewportDimensions,
     ) -> Option<(Box<dyn RenderInterface>, Box<dyn RenderBackend>)> {
-        if self.with_renderer.is_some() {
-            environment.create_renderer(dimensions.width, dimensions.height)
+        if let Some(render_options) = &self.with_renderer {
+            environment.create_renderer(render_options, dimensions.width, dimensions.height)
         } else {
             None
         }
@@ -242,6 +252,17 @@ pub struct ImageComparison {
     tolerance: u8,
     max_outliers: usize,
     pub trigger: ImageTrigger,
+
+    /// The tolerances to use instead when comparing the output of the software renderer,
+    /// which isn't pixel-identical to the wgpu output the expected images were made with.
+    software: Option<ImageTolerance>,
+}
+
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct ImageTolerance {
+    tolerance: u8,
+    max_outliers: usize,
 }
 
 fn calc_difference(lhs: u8, rhs: u8) -> u8 {
@@ -256,10 +277,16 @@ impl ImageComparison {
         expected_image: image::RgbaImage,
         test_path: &VfsPath,
         environment_name: String,
+        is_software: bool,
         known_failure: bool,
     ) -> Result<()> {
         use anyhow::Context;
 
+        let (tolerance, max_outliers) = match &self.software {
+            Some(software) if is_software => (software.tolerance, software.max_outliers),
+            _ => (self.tolerance, self.max_outliers),
+        };
+
         let save_actual_image = || {
             if !known_failure {
                 // If we're expecting failure, spamming files isn't productive.
@@ -310,10 +337,10 @@ impl ImageComparison {
         let outliers: usize = difference_data
             .chunks_exact(4)
             .map(|colors| {
-                (colors[0] > self.tolerance) as usize
-                    + (colors[1] > self.tolerance) as usize
-                    + (colors[2] > self.tolerance) as usize
-                    + (colors[3] > self.tolerance) as usize
+                (colors[0] > tolerance) as usize
+                    + (colors[1] > tolerance) as usize
+                    + (colors[2] > tolerance) as usize
+                    + (colors[3] > tolerance) as usize
             })
             .sum();
 
@@ -323,7 +350,7 @@ impl ImageComparison {
             .max()
             .unwrap();
 
-        if outliers > self.max_outliers {
+        if outliers > max_outliers {
             save_actual_image()?;
 
             let mut difference_color = Vec::with_capacity(
@@ -377,7 +404,7 @@ impl ImageComparison {
                 "Image '{}' failed: Number of outliers ({}) is bigger than allowed limit of {}. Max difference is {}",
                 name,
                 outliers,
-                self.max_outliers,
+                max_outliers,
                 max_difference
             ));
         } else {
@@ -388,11 +415,81 @@ impl ImageComparison {
     }
 }
 
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct AudioComparison {
+    /// The largest difference allowed between any two samples, where samples range from -1 to 1.
+    max_sample_delta: f32,
+
+    /// The largest root mean square of the differences between all samples.
+    max_rms_error: f64,
+}
+
+impl AudioComparison {
+    pub fn test(
+        &self,
+        actual_samples: &[f32],
+        expected_samples: &[f32],
+        test_path: &VfsPath,
+        known_failure: bool,
+    ) -> Result<()> {
+        let save_actual_audio = || {
+            if !known_failure {
+                // If we're expecting failure, spamming files isn't productive.
+                write_wav(&test_path.join("output.actual.wav")?, actual_samples)
+            } else {
+                Ok(())
+            }
+        };
+
+        if actual_samples.len() != expected_samples.len() {
+            save_actual_audio()?;
+            return Err(anyhow!(
+                "Audio is not the right length. Expected = {} samples, actual = {} samples.",
+                expected_samples.len(),
+                actual_samples.len()
+            ));
+        }
+
+        let mut max_delta: f32 = 0.0;
+        let mut sum_of_squares = 0.0;
+        for (expected, actual) in expected_samples.iter().zip(actual_samples) {
+            let delta = (expected - actual).abs();
+            max_delta = max_delta.max(delta);
+            sum_of_squares += f64::from(delta) * f64::from(delta);
+        }
+        let rms_error = if expected_samples.is_empty() {
+            0.0
+        } else {
+            (sum_of_squares / expected_samples.len() as f64).sqrt()
+        };
+
+        if max_delta > self.max_sample_delta || rms_error > self.max_rms_error {
+            save_actual_audio()?;
+            return Err(anyhow!(
+                "Audio failed: Max sample difference is {} (allowed {}), RMS error is {} (allowed {})",
+                max_delta,
+                self.max_sample_delta,
+                rms_error,
+                self.max_rms_error
+            ));
+        } else {
+            println!("Audio succeeded: max sample difference {max_delta}, RMS error {rms_error}");
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Clone, Deserialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct RenderOptions {
     optional: bool,
     pub sample_count: u32,
+
+    /// Whether the images of this test may be compared against the output of the software renderer,
+    /// which isn't pixel-identical to wgpu.
+    pub software: bool,
 }
 
 impl Default for RenderOptions {
@@ -400,6 +497,7 @@ impl Default for RenderOptions {
         Self {
             optional: false,
             sample_count: 1,
+            software: false,
         }
     }
 }
@@ -412,3 +510,13 @@ pub struct FontOptions {
     pub bold: bool,
     pub italic: bool,
 }
+
+#[derive(Clone, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct MediaCaptureOptions {
+    /// A directory of PNG images, replayed in the order of their names as the frames of a camera.
+    pub camera: Option<String>,
+
+    /// A WAV file, replayed as the sound of a microphone.
+    pub microphone: Option<String>,
+}
@@ -1,22 +1,25 @@
-use crate::backends::{TestLogBackend, TestNavigatorBackend, TestUiBackend};
+use crate::backends::{TestAudioBackend, TestLogBackend, TestNavigatorBackend, TestUiBackend};
 use crate::environment::RenderInterface;
 use crate::fs_commands::{FsCommand, TestFsCommandProvider};
 use crate::image_trigger::ImageTrigger;
-use crate::options::{ImageComparison, TestOptions};
+use crate::options::{AudioComparison, ImageComparison, TestOptions};
 use crate::test::Test;
-use crate::util::{read_bytes, write_image};
+use crate::util::{read_bytes, read_wav, write_image, write_wav};
 use anyhow::{anyhow, Error, Result};
 use image::ImageFormat;
 use pretty_assertions::Comparison;
 use ruffle_core::backend::navigator::NullExecutor;
-use ruffle_core::events::{KeyCode, TextControlCode as RuffleTextControlCode};
+use ruffle_core::events::{
+    GamepadAxis as RuffleGamepadAxis, GamepadButton as RuffleGamepadButton, KeyCode,
+    TextControlCode as RuffleTextControlCode,
+};
 use ruffle_core::events::{MouseButton as RuffleMouseButton, MouseWheelDelta};
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{Player, PlayerBuilder, PlayerEvent};
 use ruffle_input_format::{
-    AutomatedEvent, InputInjector, MouseButton as InputMouseButton,
-    TextControlCode as InputTextControlCode,
+    AutomatedEvent, GamepadAxis as InputGamepadAxis, GamepadButton as InputGamepadButton,
+    InputInjector, MouseButton as InputMouseButton, TextControlCode as InputTextControlCode,
 };
 use ruffle_render::backend::{RenderBackend, ViewportDimensions};
 use ruffle_socket_format::SocketEvent;
@@ -87,7 +90,8 @@ impl TestRunner {
             .with_navigator(navigator)
             .with_max_execution_duration(Duration::from_secs(300))
             .with_fs_commands(Box::new(fs_command_provider))
-            .with_ui(TestUiBackend::new(test.fonts()?))
+            .with_ui(TestUiBackend::new(test.fonts()?, log.clone()))
+            .with_media_capture(test.media_capture()?)
             .with_viewport_dimensions(
                 viewport_dimensions.width,
                 viewport_dimensions.height,
@@ -110,6 +114,17 @@ impl TestRunner {
             .with_autoplay(true) //.tick() requires playback
             .build();
 
+        if test.options.audio_comparison.is_some() {
+            if let Some(audio) = player
+                .lock()
+                .unwrap()
+                .audio_mut()
+                .downcast_mut::<TestAudioBackend>()
+            {
+                audio.start_recording();
+            }
+        }
+
         let images = test.options.image_comparisons.clone();
 
         let remaining_iterations = test
@@ -291,6 +306,38 @@ impl TestRunner {
                 },
                 AutomatedEvent::FocusGained => PlayerEvent::FocusGained,
                 AutomatedEvent::FocusLost => PlayerEvent::FocusLost,
+                AutomatedEvent::GamepadConnected { gamepad } => {
+                    PlayerEvent::GamepadConnected { gamepad: *gamepad }
+                }
+                AutomatedEvent::GamepadDisconnected { gamepad } => {
+                    PlayerEvent::GamepadDisconnected { gamepad: *gamepad }
+                }
+                AutomatedEvent::GamepadButtonDown { gamepad, button } => {
+                    PlayerEvent::GamepadButtonDown {
+                        gamepad: *gamepad,
+                        button: gamepad_button(*button),
+                    }
+                }
+                AutomatedEvent::GamepadButtonUp { gamepad, button } => {
+                    PlayerEvent::GamepadButtonUp {
+                        gamepad: *gamepad,
+                        button: gamepad_button(*button),
+                    }
+                }
+                AutomatedEvent::GamepadAxisChanged {
+                    gamepad,
+                    axis,
+                    value,
+                } => PlayerEvent::GamepadAxisChanged {
+                    gamepad: *gamepad,
+                    axis: match axis {
+                        InputGamepadAxis::LeftStickX => RuffleGamepadAxis::LeftStickX,
+                        InputGamepadAxis::LeftStickY => RuffleGamepadAxis::LeftStickY,
+                        InputGamepadAxis::RightStickX => RuffleGamepadAxis::RightStickX,
+                        InputGamepadAxis::RightStickY => RuffleGamepadAxis::RightStickY,
+                    },
+                    value: *value,
+                },
                 AutomatedEvent::Wait | AutomatedEvent::SetClipboardText { .. } => unreachable!(),
             });
 
@@ -367,6 +414,15 @@ impl TestRunner {
                 ));
             }
 
+            if let Some(audio_comparison) = &self.options.audio_comparison {
+                capture_and_compare_audio(
+                    &self.root_path,
+                    &self.player,
+                    audio_comparison,
+                    self.options.known_failure,
+                )?;
+            }
+
             self.executor.run();
 
             let trace = self.log.trace_output();
@@ -493,6 +549,25 @@ impl TestRunner {
     }
 }
 
+fn gamepad_button(button: InputGamepadButton) -> RuffleGamepadButton {
+    match button {
+        InputGamepadButton::South => RuffleGamepadButton::South,
+        InputGamepadButton::East => RuffleGamepadButton::East,
+        InputGamepadButton::North => RuffleGamepadButton::North,
+        InputGamepadButton::West => RuffleGamepadButton::West,
+        InputGamepadButton::LeftTrigger => RuffleGamepadButton::LeftTrigger,
+        InputGamepadButton::LeftTrigger2 => RuffleGamepadButton::LeftTrigger2,
+        InputGamepadButton::RightTrigger => RuffleGamepadButton::RightTrigger,
+        InputGamepadButton::RightTrigger2 => RuffleGamepadButton::RightTrigger2,
+        InputGamepadButton::Select => RuffleGamepadButton::Select,
+        InputGamepadButton::Start => RuffleGamepadButton::Start,
+        InputGamepadButton::DPadUp => RuffleGamepadButton::DPadUp,
+        InputGamepadButton::DPadDown => RuffleGamepadButton::DPadDown,
+        InputGamepadButton::DPadLeft => RuffleGamepadButton::DPadLeft,
+        InputGamepadButton::DPadRight => RuffleGamepadButton::DPadRight,
+    }
+}
+
 fn capture_and_compare_image(
     base_path: &VfsPath,
     player: &Arc<Mutex<Player>>,
@@ -521,6 +596,7 @@ fn capture_and_compare_image(
                 expected_image,
                 base_path,
                 render_interface.name(),
+                render_interface.is_software(),
                 known_failure,
             )?;
         } else if known_failure {
@@ -542,6 +618,35 @@ fn capture_and_compare_image(
     Ok(())
 }
 
+fn capture_and_compare_audio(
+    base_path: &VfsPath,
+    player: &Arc<Mutex<Player>>,
+    audio_comparison: &AudioComparison,
+    known_failure: bool,
+) -> Result<()> {
+    let actual_samples = player
+        .lock()
+        .unwrap()
+        .audio_mut()
+        .downcast_mut::<TestAudioBackend>()
+        .and_then(TestAudioBackend::take_recording)
+        .ok_or_else(|| anyhow!("No audio was recorded to compare"))?;
+
+    let expected_audio_path = base_path.join("output.wav")?;
+    if expected_audio_path.is_file()? {
+        let expected_samples = read_wav(&expected_audio_path)?;
+        audio_comparison.test(&actual_samples, &expected_samples, base_path, known_failure)?;
+    } else if known_failure {
+        return Err(anyhow!(
+            "No audio to compare to, pretending this failed since we don't know if it worked."
+        ));
+    } else {
+        write_wav(&expected_audio_path, &actual_samples)?;
+    }
+
+    Ok(())
+}
+
 /// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
 /// Used in different `assert*!` macros in combination with `pretty_assertions` crate to make
 /// test failures to show nice diffs.
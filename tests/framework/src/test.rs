@@ -1,3 +1,4 @@
+use crate::backends::TestMediaCaptureBackend;
 use crate::environment::Environment;
 use crate::options::TestOptions;
 use crate::runner::TestRunner;
@@ -104,6 +105,18 @@ impl Test {
             .collect()
     }
 
+    pub fn media_capture(&self) -> Result<TestMediaCaptureBackend> {
+        let options = &self.options.media_capture;
+        let mut backend = TestMediaCaptureBackend::default();
+        if let Some(camera) = &options.camera {
+            backend = backend.with_camera(&self.root_path.join(camera)?)?;
+        }
+        if let Some(microphone) = &options.microphone {
+            backend = backend.with_microphone(&self.root_path.join(microphone)?)?;
+        }
+        Ok(backend)
+    }
+
     pub fn should_run(&self, check_renderer: bool, environment: &impl Environment) -> bool {
         if self.options.ignore {
             return false;
@@ -1,3 +1,5 @@
+use crate::backends::TestAudioBackend;
+use anyhow::anyhow;
 use image::{EncodableLayout, ImageBuffer, ImageFormat, Pixel, PixelWithColorType};
 use std::io::{Cursor, Read, Write};
 use std::ops::Deref;
@@ -29,3 +31,105 @@ where
     write_bytes(path, &buffer)?;
     Ok(())
 }
+
+/// Reads interleaved samples from a WAV file in the format recorded by `TestAudioBackend`.
+pub fn read_wav(path: &VfsPath) -> anyhow::Result<Vec<f32>> {
+    let reader = hound::WavReader::new(Cursor::new(read_bytes(path)?))?;
+    let spec = reader.spec();
+    if spec.channels != TestAudioBackend::NUM_CHANNELS as u16
+        || spec.sample_rate != TestAudioBackend::SAMPLE_RATE
+    {
+        return Err(anyhow!(
+            "Expected audio to have {} channels at {}Hz, but it has {} channels at {}Hz",
+            TestAudioBackend::NUM_CHANNELS,
+            TestAudioBackend::SAMPLE_RATE,
+            spec.channels,
+            spec.sample_rate
+        ));
+    }
+
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader.into_samples::<f32>().collect::<Result<_, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = (1u32 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .into_samples::<i32>()
+                .map(|sample| sample.map(|sample| sample as f32 / scale))
+                .collect::<Result<_, _>>()?
+        }
+    };
+    Ok(samples)
+}
+
+/// Writes interleaved samples recorded by `TestAudioBackend` to a WAV file.
+pub fn write_wav(path: &VfsPath, samples: &[f32]) -> anyhow::Result<()> {
+    let spec = hound::WavSpec {
+        channels: TestAudioBackend::NUM_CHANNELS as u16,
+        sample_rate: TestAudioBackend::SAMPLE_RATE,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+    let mut buffer = Cursor::new(vec![]);
+    let mut writer = hound::WavWriter::new(&mut buffer, spec)?;
+    for sample in samples {
+        writer.write_sample(*sample)?;
+    }
+    writer.finalize()?;
+    write_bytes(path, buffer.get_ref())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vfs::MemoryFS;
+
+    fn wav_path() -> VfsPath {
+        VfsPath::new(MemoryFS::new()).join("output.wav").unwrap()
+    }
+
+    fn write_int_wav(path: &VfsPath, channels: u16, samples: &[i16]) {
+        let spec = hound::WavSpec {
+            channels,
+            sample_rate: TestAudioBackend::SAMPLE_RATE,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut buffer = Cursor::new(vec![]);
+        let mut writer = hound::WavWriter::new(&mut buffer, spec).unwrap();
+        for sample in samples {
+            writer.write_sample(*sample).unwrap();
+        }
+        writer.finalize().unwrap();
+        write_bytes(path, buffer.get_ref()).unwrap();
+    }
+
+    #[test]
+    fn wav_round_trip() {
+        let path = wav_path();
+        let samples = [0.0, -0.0, 1.0, -1.0, 0.25, -0.125, 0.333, f32::MIN_POSITIVE];
+        write_wav(&path, &samples).unwrap();
+        assert_eq!(read_wav(&path).unwrap(), samples);
+    }
+
+    #[test]
+    fn empty_wav_round_trip() {
+        let path = wav_path();
+        write_wav(&path, &[]).unwrap();
+        assert!(read_wav(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn read_int_wav() {
+        let path = wav_path();
+        write_int_wav(&path, 2, &[0, i16::MIN, 16384, -8192]);
+        assert_eq!(read_wav(&path).unwrap(), [0.0, -1.0, 0.5, -0.25]);
+    }
+
+    #[test]
+    fn read_wav_with_other_channels() {
+        let path = wav_path();
+        write_int_wav(&path, 1, &[0, 0]);
+        assert!(read_wav(&path).is_err());
+    }
+}
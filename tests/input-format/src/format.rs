@@ -19,6 +19,34 @@ pub enum MouseButton {
     Right,
 }
 
+/// A button of a gamepad.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub enum GamepadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftTrigger,
+    LeftTrigger2,
+    RightTrigger,
+    RightTrigger2,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// An analog stick axis of a gamepad.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+}
+
 /// Control inputs to a text field
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TextControlCode {
@@ -101,6 +129,31 @@ pub enum AutomatedEvent {
 
     /// Inform the player that the focus has been lost (i.e. the user focused another window).
     FocusLost,
+
+    /// Connect a gamepad
+    GamepadConnected { gamepad: usize },
+
+    /// Disconnect a gamepad
+    GamepadDisconnected { gamepad: usize },
+
+    /// Press a button of a gamepad
+    GamepadButtonDown {
+        gamepad: usize,
+        button: GamepadButton,
+    },
+
+    /// Release a button of a gamepad
+    GamepadButtonUp {
+        gamepad: usize,
+        button: GamepadButton,
+    },
+
+    /// Move an analog stick of a gamepad along one axis, to a value from -1.0 to 1.0
+    GamepadAxisChanged {
+        gamepad: usize,
+        axis: GamepadAxis,
+        value: f64,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
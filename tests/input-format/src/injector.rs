@@ -102,7 +102,12 @@ impl InputInjector {
                     | AutomatedEvent::SetClipboardText { .. }
                     | AutomatedEvent::MouseWheel { .. }
                     | AutomatedEvent::FocusGained
-                    | AutomatedEvent::FocusLost => {}
+                    | AutomatedEvent::FocusLost
+                    | AutomatedEvent::GamepadConnected { .. }
+                    | AutomatedEvent::GamepadDisconnected { .. }
+                    | AutomatedEvent::GamepadButtonDown { .. }
+                    | AutomatedEvent::GamepadButtonUp { .. }
+                    | AutomatedEvent::GamepadAxisChanged { .. } => {}
                     AutomatedEvent::MouseDown { btn, .. } => {
                         self.buttons |= (*btn).into();
                     }
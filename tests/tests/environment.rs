@@ -3,24 +3,89 @@ use ruffle_test_framework::environment::Environment;
 pub struct NativeEnvironment;
 
 impl Environment for NativeEnvironment {
-    #[cfg(feature = "imgtests")]
+    #[cfg(any(feature = "imgtests", feature = "software_imgtests"))]
     fn is_render_supported(
         &self,
         requirements: &ruffle_test_framework::options::RenderOptions,
     ) -> bool {
-        renderer::is_supported(requirements)
+        #[cfg(feature = "imgtests")]
+        if renderer::is_supported(requirements) {
+            return true;
+        }
+        #[cfg(feature = "software_imgtests")]
+        if software_renderer::is_supported(requirements) {
+            return true;
+        }
+        false
     }
 
-    #[cfg(feature = "imgtests")]
+    #[cfg(any(feature = "imgtests", feature = "software_imgtests"))]
+    #[cfg_attr(not(feature = "software_imgtests"), allow(unused_variables))]
     fn create_renderer(
         &self,
+        requirements: &ruffle_test_framework::options::RenderOptions,
         width: u32,
         height: u32,
     ) -> Option<(
         Box<dyn ruffle_test_framework::environment::RenderInterface>,
         Box<dyn ruffle_test_framework::environment::RenderBackend>,
     )> {
-        renderer::NativeRenderInterface::create_pair(width, height)
+        // Prefer the GPU, and only fall back to the CPU if no device is available.
+        #[cfg(feature = "imgtests")]
+        if let Some(pair) = renderer::NativeRenderInterface::create_pair(width, height) {
+            return Some(pair);
+        }
+        #[cfg(feature = "software_imgtests")]
+        if software_renderer::is_supported(requirements) {
+            return software_renderer::SoftwareRenderInterface::create_pair(width, height);
+        }
+        None
+    }
+}
+
+#[cfg(feature = "software_imgtests")]
+mod software_renderer {
+    use image::RgbaImage;
+    use ruffle_render_software::SoftwareRenderBackend;
+    use ruffle_test_framework::environment::{RenderBackend, RenderInterface};
+    use ruffle_test_framework::options::RenderOptions;
+
+    pub struct SoftwareRenderInterface;
+
+    impl SoftwareRenderInterface {
+        pub fn create_pair(
+            width: u32,
+            height: u32,
+        ) -> Option<(Box<dyn RenderInterface>, Box<dyn RenderBackend>)> {
+            Some((
+                Box::new(Self),
+                Box::new(SoftwareRenderBackend::new(width, height)),
+            ))
+        }
+    }
+
+    impl RenderInterface for SoftwareRenderInterface {
+        fn name(&self) -> String {
+            "software".to_string()
+        }
+
+        fn is_software(&self) -> bool {
+            true
+        }
+
+        fn capture(&self, backend: &mut Box<dyn RenderBackend>) -> RgbaImage {
+            let renderer = backend
+                .downcast_mut::<SoftwareRenderBackend>()
+                .expect("Backend must be the software renderer");
+
+            renderer.capture_frame()
+        }
+    }
+
+    /// Only tests that opted in are compared against the software renderer,
+    /// as their reference images were made with wgpu.
+    pub fn is_supported(requirements: &RenderOptions) -> bool {
+        requirements.software
     }
 }
 
@@ -0,0 +1,150 @@
+# Generates test.swf and output.wav.
+#
+# The movie plays a 4-bit mono ADPCM tone that spans two packets. The expected
+# output is decoded as described in the SWF specification, where each packet of
+# 4096 samples starts with its first sample, followed by 4095 codes.
+import math
+import struct
+
+SAMPLE_RATE = 44100
+FRAME_RATE = 25
+NUM_TICKS = 6
+NUM_SAMPLES = 6096
+
+INDEX_TABLE = [-1, -1, -1, -1, 2, 4, 6, 8]
+STEP_TABLE = [
+    7, 8, 9, 10, 11, 12, 13, 14, 16, 17, 19, 21, 23, 25, 28, 31, 34, 37, 41, 45, 50, 55, 60, 66,
+    73, 80, 88, 97, 107, 118, 130, 143, 157, 173, 190, 209, 230, 253, 279, 307, 337, 371, 408, 449,
+    494, 544, 598, 658, 724, 796, 876, 963, 1060, 1166, 1282, 1411, 1552, 1707, 1878, 2066, 2272,
+    2499, 2749, 3024, 3327, 3660, 4026, 4428, 4871, 5358, 5894, 6484, 7132, 7845, 8630, 9493,
+    10442, 11487, 12635, 13899, 15289, 16818, 18500, 20350, 22385, 24623, 27086, 29794, 32767,
+]
+
+
+class BitWriter:
+    def __init__(self):
+        self.bits = []
+
+    def write(self, value, num_bits):
+        for i in reversed(range(num_bits)):
+            self.bits.append((value >> i) & 1)
+
+    def bytes(self):
+        bits = self.bits + [0] * (-len(self.bits) % 8)
+        return bytes(
+            int("".join(map(str, bits[i:i + 8])), 2) for i in range(0, len(bits), 8)
+        )
+
+
+def tone():
+    # A 441Hz sine that fades in and out, followed by silence.
+    samples = []
+    for i in range(NUM_SAMPLES):
+        fade = min(1.0, i / 300, max(0.0, (NUM_SAMPLES - 200 - i) / 300))
+        samples.append(round(math.sin(2 * math.pi * 441 * i / SAMPLE_RATE) * fade * 12000))
+    return samples
+
+
+def step_delta(step, magnitude):
+    delta = step >> 3
+    if magnitude & 1:
+        delta += step >> 2
+    if magnitude & 2:
+        delta += step >> 1
+    if magnitude & 4:
+        delta += step
+    return delta
+
+
+def apply_code(sample, index, code):
+    delta = step_delta(STEP_TABLE[index], code & 7)
+    if code & 8:
+        sample = max(sample - delta, -32768)
+    else:
+        sample = min(sample + delta, 32767)
+    index = min(max(index + INDEX_TABLE[code & 7], 0), len(STEP_TABLE) - 1)
+    return sample, index
+
+
+def encode(samples):
+    writer = BitWriter()
+    writer.write(2, 2)  # 4 bits per code
+    sample, index = 0, 0
+    for i, target in enumerate(samples):
+        if i % 4096 == 0:
+            sample = target
+            writer.write(sample & 0xFFFF, 16)
+            writer.write(index, 6)
+            continue
+        # Pick the code that lands closest to the target.
+        code = min(range(16), key=lambda c: abs(apply_code(sample, index, c)[0] - target))
+        sample, index = apply_code(sample, index, code)
+        writer.write(code, 4)
+    return writer.bytes()
+
+
+def decode(data):
+    bits = "".join(f"{byte:08b}" for byte in data)
+    pos = 2
+    samples = []
+    sample, index = 0, 0
+    while True:
+        if len(samples) % 4096 == 0:
+            if pos + 22 > len(bits):
+                return samples
+            sample = int(bits[pos:pos + 16], 2)
+            sample -= (sample & 0x8000) << 1
+            index = int(bits[pos + 16:pos + 22], 2)
+            pos += 22
+        else:
+            if pos + 4 > len(bits):
+                return samples
+            sample, index = apply_code(sample, index, int(bits[pos:pos + 4], 2))
+            pos += 4
+        samples.append(sample)
+
+
+def tag(code, body):
+    if len(body) < 0x3F:
+        return struct.pack("<H", code << 6 | len(body)) + body
+    return struct.pack("<HI", code << 6 | 0x3F, len(body)) + body
+
+
+def swf(tags):
+    rect = BitWriter()
+    rect.write(15, 5)
+    for value in [0, 550 * 20, 0, 400 * 20]:
+        rect.write(value, 15)
+    body = rect.bytes() + struct.pack("<HH", FRAME_RATE << 8, 1) + b"".join(tags)
+    return b"FWS" + struct.pack("<BI", 6, 8 + len(body)) + body
+
+
+def write_wav(path, samples):
+    data = struct.pack(f"<{len(samples)}f", *samples)
+    fmt = struct.pack("<HHIIHH", 3, 2, SAMPLE_RATE, SAMPLE_RATE * 8, 8, 32)
+    with open(path, "wb") as file:
+        file.write(b"RIFF" + struct.pack("<I", 4 + 8 + len(fmt) + 8 + len(data)) + b"WAVE")
+        file.write(b"fmt " + struct.pack("<I", len(fmt)) + fmt)
+        file.write(b"data" + struct.pack("<I", len(data)) + data)
+
+
+data = encode(tone())
+decoded = decode(data)
+assert len(decoded) == NUM_SAMPLES
+
+# ADPCM, 44kHz, 16-bit, mono.
+define_sound = struct.pack("<HBI", 1, 0x1E, NUM_SAMPLES) + data
+start_sound = struct.pack("<HB", 1, 0)
+stop = b"\x07\x00"
+with open("test.swf", "wb") as file:
+    file.write(swf([
+        tag(14, define_sound),
+        tag(15, start_sound),
+        tag(12, stop),
+        tag(1, b""),
+        tag(0, b""),
+    ]))
+
+num_frames = NUM_TICKS * SAMPLE_RATE // FRAME_RATE
+expected = [sample / 32768 for sample in decoded] + [0.0] * (num_frames - NUM_SAMPLES)
+write_wav("output.wav", [sample for sample in expected for _channel in range(2)])
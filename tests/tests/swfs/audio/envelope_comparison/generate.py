@@ -0,0 +1,101 @@
+# Generates test.swf and output.wav.
+#
+# The movie plays an uncompressed stereo square wave with a sound envelope that
+# pans it from left to right, then back to the center at half volume.
+# The levels of the envelope are interpolated linearly between its points.
+import struct
+
+SAMPLE_RATE = 44100
+FRAME_RATE = 25
+NUM_TICKS = 4
+NUM_SAMPLES = 4410
+
+# (position in 44kHz samples, left level, right level), where 32768 is full volume.
+ENVELOPE = [(0, 32768, 0), (2205, 0, 32768), (4410, 16384, 16384)]
+
+
+class BitWriter:
+    def __init__(self):
+        self.bits = []
+
+    def write(self, value, num_bits):
+        for i in reversed(range(num_bits)):
+            self.bits.append((value >> i) & 1)
+
+    def bytes(self):
+        bits = self.bits + [0] * (-len(self.bits) % 8)
+        return bytes(
+            int("".join(map(str, bits[i:i + 8])), 2) for i in range(0, len(bits), 8)
+        )
+
+
+def square_wave():
+    # A 441Hz square wave, inverted on the right, followed by silence.
+    frames = []
+    for i in range(NUM_SAMPLES):
+        left = 0 if i >= NUM_SAMPLES - 20 else 8192 if i % 100 < 50 else -8192
+        frames.append((left, -left))
+    return frames
+
+
+def envelope_levels(position):
+    if position <= ENVELOPE[0][0]:
+        return ENVELOPE[0][1] / 32768, ENVELOPE[0][2] / 32768
+    for (start, start_left, start_right), (end, end_left, end_right) in zip(ENVELOPE, ENVELOPE[1:]):
+        if position <= end:
+            t = (position - start) / (end - start)
+            left = start_left + (end_left - start_left) * t
+            right = start_right + (end_right - start_right) * t
+            return left / 32768, right / 32768
+    return ENVELOPE[-1][1] / 32768, ENVELOPE[-1][2] / 32768
+
+
+def tag(code, body):
+    if len(body) < 0x3F:
+        return struct.pack("<H", code << 6 | len(body)) + body
+    return struct.pack("<HI", code << 6 | 0x3F, len(body)) + body
+
+
+def swf(tags):
+    rect = BitWriter()
+    rect.write(15, 5)
+    for value in [0, 550 * 20, 0, 400 * 20]:
+        rect.write(value, 15)
+    body = rect.bytes() + struct.pack("<HH", FRAME_RATE << 8, 1) + b"".join(tags)
+    return b"FWS" + struct.pack("<BI", 6, 8 + len(body)) + body
+
+
+def write_wav(path, samples):
+    data = struct.pack(f"<{len(samples)}f", *samples)
+    fmt = struct.pack("<HHIIHH", 3, 2, SAMPLE_RATE, SAMPLE_RATE * 8, 8, 32)
+    with open(path, "wb") as file:
+        file.write(b"RIFF" + struct.pack("<I", 4 + 8 + len(fmt) + 8 + len(data)) + b"WAVE")
+        file.write(b"fmt " + struct.pack("<I", len(fmt)) + fmt)
+        file.write(b"data" + struct.pack("<I", len(data)) + data)
+
+
+frames = square_wave()
+
+# Uncompressed little-endian, 44kHz, 16-bit, stereo.
+data = b"".join(struct.pack("<hh", left, right) for left, right in frames)
+define_sound = struct.pack("<HBI", 1, 0x3F, NUM_SAMPLES) + data
+start_sound = struct.pack("<HBB", 1, 0x08, len(ENVELOPE)) + b"".join(
+    struct.pack("<IHH", *point) for point in ENVELOPE
+)
+stop = b"\x07\x00"
+with open("test.swf", "wb") as file:
+    file.write(swf([
+        tag(14, define_sound),
+        tag(15, start_sound),
+        tag(12, stop),
+        tag(1, b""),
+        tag(0, b""),
+    ]))
+
+num_frames = NUM_TICKS * SAMPLE_RATE // FRAME_RATE
+expected = []
+for i in range(num_frames):
+    left, right = frames[i] if i < NUM_SAMPLES else (0, 0)
+    left_level, right_level = envelope_levels(i)
+    expected += [left * left_level / 32768, right * right_level / 32768]
+write_wav("output.wav", expected)
@@ -0,0 +1,105 @@
+# Generates test.swf, whose sprites have frames labeled `#p` and `#b`, and which prints them with
+# `getURL("print:...")` and `getURL("printasbitmap:...")`.
+
+import struct
+
+class Bits:
+    def __init__(self): self.bits=[]
+    def u(self, v, n):
+        for i in range(n-1,-1,-1): self.bits.append((v>>i)&1)
+    def s(self, v, n): self.u(v & ((1<<n)-1), n)
+    def bytes(self):
+        b=self.bits+[0]*((-len(self.bits))%8)
+        return bytes(int(''.join(map(str,b[i:i+8])),2) for i in range(0,len(b),8))
+
+def sbits(*vals):
+    n=1
+    for v in vals:
+        while not (-(1<<(n-1)) <= v < (1<<(n-1))): n+=1
+    return n
+
+def rect(xmin,xmax,ymin,ymax):
+    b=Bits(); n=sbits(xmin,xmax,ymin,ymax); b.u(n,5)
+    for v in (xmin,xmax,ymin,ymax): b.s(v,n)
+    return b.bytes()
+
+def tag(code, data):
+    if len(data) < 63: return struct.pack('<H', code<<6|len(data))+data
+    return struct.pack('<HI', code<<6|0x3f, len(data))+data
+
+def string(s): return s.encode()+b'\0'
+
+def define_shape(cid, w, h, color):
+    # Width and height in pixels, drawn from the origin.
+    W,H=w*20,h*20
+    d=struct.pack('<H',cid)+rect(0,W,0,H)
+    d+=b'\x01'+b'\x00'+bytes(color)+b'\x00'
+    b=Bits(); b.u(1,4); b.u(0,4)
+    # Style change: move to (0, 0), fill style 1.
+    b.u(0,1); b.u(0,1); b.u(0,1); b.u(1,1); b.u(0,1); b.u(1,1)
+    b.u(1,5); b.s(0,1); b.s(0,1)
+    b.u(1,1)
+    for dx,dy in ((W,0),(0,H),(-W,0),(0,-H)):
+        n=max(sbits(dx,dy),2)
+        b.u(1,1); b.u(1,1); b.u(n-2,4)
+        if dx==0: b.u(0,1); b.u(1,1); b.s(dy,n)
+        else: b.u(0,1); b.u(0,1); b.s(dx,n)
+    b.u(0,6)
+    return tag(2, d+b.bytes())
+
+def place(depth, cid=None, name=None):
+    flags=0x04|(0x02 if cid is not None else 0)|(0x20 if name else 0)
+    d=bytes([flags])+struct.pack('<H',depth)
+    if cid is not None: d+=struct.pack('<H',cid)
+    d+=b'\x00'
+    if name: d+=string(name)
+    return tag(26,d)
+
+def remove(depth): return tag(28, struct.pack('<H',depth))
+def label(name): return tag(43, string(name))
+def show(): return tag(1,b'')
+
+def push(s):
+    p=b'\x00'+string(s); return b'\x96'+struct.pack('<H',len(p))+p
+def trace(s): return push(s)+b'\x26'
+def geturl(url,target):
+    p=string(url)+string(target); return b'\x83'+struct.pack('<H',len(p))+p
+STOP=b'\x07'
+def do_action(*actions): return tag(12, b''.join(actions)+b'\x00')
+
+def sprite(cid, frames, tags):
+    return tag(39, struct.pack('<HH',cid,frames)+b''.join(tags)+tag(0,b''))
+
+body=b''.join([
+    tag(9, bytes([255,255,255])),
+    define_shape(1, 100, 50, (255,0,0)),
+    define_shape(2, 200, 100, (0,255,0)),
+    define_shape(3, 50, 300, (0,0,255)),
+    # Printed frames are labeled `#p`, and frame 3 has a script that must not run when it's printed.
+    sprite(10, 3, [
+        place(1, 1), do_action(STOP), show(),
+        label('#p'), remove(1), place(1, 2), show(),
+        label('#p'), place(2, 3), do_action(trace('labeled: frame 3 script ran')), show(),
+    ]),
+    # Every frame is printed, and the bounds of the frame labeled `#b` are used for `#bmovie`.
+    sprite(11, 2, [
+        place(1, 1), do_action(STOP), show(),
+        label('#b'), place(2, 2), show(),
+    ]),
+    place(1, 10, 'labeled'),
+    place(2, 11, 'unlabeled'),
+    do_action(
+        trace('// print(labeled, "bframe")'), geturl('print:#bframe', 'labeled'),
+        trace('// print(labeled, "bmax")'), geturl('print:#bmax', 'labeled'),
+        trace('// print(labeled, "bmovie")'), geturl('print:#bmovie', 'labeled'),
+        trace('// printAsBitmap(unlabeled, "bmovie")'), geturl('printasbitmap:#bmovie', 'unlabeled'),
+        trace('// print(unlabeled, "bframe")'), geturl('print:#bframe', 'unlabeled'),
+        trace('// done'),
+    ),
+    show(),
+    tag(0,b''),
+])
+header=rect(0,400*20,0,300*20)+struct.pack('<HH',24<<8,1)
+data=header+body
+swf=b'FWS'+bytes([8])+struct.pack('<I',8+len(data))+data
+open("test.swf",'wb').write(swf)
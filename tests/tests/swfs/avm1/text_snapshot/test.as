@@ -0,0 +1,64 @@
+// Frame 1 of a movie that places two static texts using a font of square glyphs:
+// "Hello" and "World" on two lines at (10, 10), and "HELLO abc" at (10, 100).
+
+var s = this.getTextSnapshot();
+trace("getCount(): " + s.getCount());
+trace("getText(0, 19): " + s.getText(0, 19));
+trace("getText(0, 19, true): " + s.getText(0, 19, true));
+trace("getText(3, 7, true): " + s.getText(3, 7, true));
+trace("getText(5, 5): " + s.getText(5, 5));
+trace("getText(8, 2): " + s.getText(8, 2));
+trace("getText(-5, 100): " + s.getText(-5, 100));
+
+trace("findText(0, \"hello\", true): " + s.findText(0, "hello", true));
+trace("findText(0, \"hello\", false): " + s.findText(0, "hello", false));
+trace("findText(1, \"hello\", false): " + s.findText(1, "hello", false));
+trace("findText(0, \"World\", true): " + s.findText(0, "World", true));
+trace("findText(0, \"oW\", true): " + s.findText(0, "oW", true));
+trace("findText(0, \"ABC\", false): " + s.findText(0, "ABC", false));
+trace("findText(0, \"\", true): " + s.findText(0, "", true));
+
+trace("hitTestTextNearPos(15, 20): " + s.hitTestTextNearPos(15, 20));
+trace("hitTestTextNearPos(88, 20): " + s.hitTestTextNearPos(88, 20));
+trace("hitTestTextNearPos(88, 20, 5): " + s.hitTestTextNearPos(88, 20, 5));
+trace("hitTestTextNearPos(12, 110): " + s.hitTestTextNearPos(12, 110));
+trace("hitTestTextNearPos(300, 300, 10): " + s.hitTestTextNearPos(300, 300, 10));
+
+trace("getSelected(0, 19): " + s.getSelected(0, 19));
+s.setSelected(2, 6, true);
+s.setSelected(8, 12, true);
+trace("getSelected(0, 2): " + s.getSelected(0, 2));
+trace("getSelected(5, 8): " + s.getSelected(5, 8));
+trace("getSelected(6, 8): " + s.getSelected(6, 8));
+trace("getSelected(7, 7): " + s.getSelected(7, 7));
+trace("getSelectedText(): " + s.getSelectedText());
+trace("getSelectedText(true): " + s.getSelectedText(true));
+s.setSelected(3, 10, false);
+trace("getSelectedText() after deselecting 3-10: " + s.getSelectedText());
+
+traceRunInfo(1, 3);
+traceRunInfo(9, 11);
+
+s.setSelected(0, 19, false);
+trace("getSelectedText() after deselecting everything: \"" + s.getSelectedText() + "\"");
+
+var empty = this.createEmptyMovieClip("empty", 10);
+var e = empty.getTextSnapshot();
+trace("Empty getCount(): " + e.getCount());
+trace("Empty getText(0, 10): \"" + e.getText(0, 10) + "\"");
+trace("Empty findText(0, \"a\", true): " + e.findText(0, "a", true));
+trace("Empty hitTestTextNearPos(0, 0, 100): " + e.hitTestTextNearPos(0, 0, 100));
+
+// Written out inline in the SWF for each run.
+function traceRunInfo(begin, end) {
+	var runs = s.getTextRunInfo(begin, end);
+	trace("getTextRunInfo(" + begin + ", " + end + ").length: " + runs.length);
+	for (var i = 0; i < runs.length; i++) {
+		var r = runs[i];
+		trace("  indexInRun=" + r.indexInRun + " selected=" + r.selected + " font=" + r.font
+			+ " color=" + r.color + " height=" + r.height + " matrix_a=" + r.matrix_a
+			+ " matrix_d=" + r.matrix_d + " matrix_tx=" + r.matrix_tx + " matrix_ty=" + r.matrix_ty
+			+ " corner00x=" + r.corner00x + " corner00y=" + r.corner00y
+			+ " corner11x=" + r.corner11x + " corner11y=" + r.corner11y);
+	}
+}
@@ -0,0 +1,95 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.JPEGEncoderOptions;
+	import flash.display.Loader;
+	import flash.display.MovieClip;
+	import flash.display.PNGEncoderOptions;
+	import flash.events.Event;
+	import flash.geom.Rectangle;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var bitmap:BitmapData = new BitmapData(10, 8, true, 0xFF0000FF);
+			bitmap.fillRect(new Rectangle(5, 4, 2, 2), 0xFF00FF00);
+
+			trace("// PNG of the whole bitmap");
+			describePNG(bitmap.encode(bitmap.rect, new PNGEncoderOptions()));
+
+			trace("// PNG with fast compression of a rect sticking out of the bitmap");
+			var png:ByteArray = bitmap.encode(new Rectangle(5, 4, 20, 20), new PNGEncoderOptions(true));
+			describePNG(png);
+
+			trace("// PNG written into an existing ByteArray");
+			var existing:ByteArray = new ByteArray();
+			existing.writeUTFBytes("Hello");
+			existing.position = 2;
+			var result:ByteArray = bitmap.encode(new Rectangle(0, 0, 3, 3), new PNGEncoderOptions(), existing);
+			trace("Same ByteArray: " + (result === existing));
+			trace("Kept bytes before the position: " + existing[0] + "," + existing[1]);
+			trace("Signature at the old position: " + (existing[2] == 0x89 && existing[3] == 0x50));
+			trace("Position at the end: " + (existing.position == existing.length));
+
+			trace("// JPEG of the whole bitmap");
+			describeJPEG(bitmap.encode(bitmap.rect, new JPEGEncoderOptions()));
+
+			trace("// JPEG of a rect with a negative origin");
+			describeJPEG(bitmap.encode(new Rectangle(-2, -2, 6, 5), new JPEGEncoderOptions(50)));
+
+			trace("// Invalid compressors");
+			tryEncode(bitmap, null);
+			tryEncode(bitmap, {});
+
+			trace("// Loading the clipped PNG back");
+			var loader:Loader = new Loader();
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+				var loaded:BitmapData = Bitmap(loader.content).bitmapData;
+				trace("Loaded size: " + loaded.width + "x" + loaded.height);
+				trace("Top left: " + loaded.getPixel32(0, 0).toString(16));
+				trace("Bottom right: " + loaded.getPixel32(4, 3).toString(16));
+			});
+			loader.loadBytes(png);
+		}
+
+		private static function tryEncode(bitmap:BitmapData, compressor:Object):void {
+			try {
+				bitmap.encode(bitmap.rect, compressor);
+				trace("No error");
+			} catch (e:Error) {
+				trace(Object(e).constructor + ": " + e.message);
+			}
+		}
+
+		private static function describePNG(bytes:ByteArray):void {
+			trace("Position at the end: " + (bytes.position == bytes.length));
+			trace("Signature: " + (bytes[0] == 0x89 && bytes[1] == 0x50 && bytes[2] == 0x4E && bytes[3] == 0x47));
+			bytes.position = 16;
+			trace("Size: " + bytes.readUnsignedInt() + "x" + bytes.readUnsignedInt());
+			trace("Bit depth: " + bytes.readUnsignedByte() + ", color type: " + bytes.readUnsignedByte());
+		}
+
+		private static function describeJPEG(bytes:ByteArray):void {
+			trace("Position at the end: " + (bytes.position == bytes.length));
+			trace("Start of image: " + (bytes[0] == 0xFF && bytes[1] == 0xD8));
+			trace("End of image: " + (bytes[bytes.length - 2] == 0xFF && bytes[bytes.length - 1] == 0xD9));
+
+			// Walks the segments until the baseline frame header, which holds the size.
+			bytes.position = 2;
+			while (bytes.bytesAvailable > 4) {
+				var marker:uint = bytes.readUnsignedShort();
+				var length:uint = bytes.readUnsignedShort();
+				if (marker == 0xFFC0) {
+					bytes.readUnsignedByte();
+					var height:uint = bytes.readUnsignedShort();
+					var width:uint = bytes.readUnsignedShort();
+					trace("Size: " + width + "x" + height);
+					trace("Components: " + bytes.readUnsignedByte());
+					return;
+				}
+				bytes.position += length - 2;
+			}
+			trace("No frame header");
+		}
+	}
+}
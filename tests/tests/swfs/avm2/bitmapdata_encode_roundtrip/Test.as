@@ -0,0 +1,84 @@
+package {
+	import flash.display.Bitmap;
+	import flash.display.BitmapData;
+	import flash.display.JPEGEncoderOptions;
+	import flash.display.Loader;
+	import flash.display.MovieClip;
+	import flash.display.PNGEncoderOptions;
+	import flash.events.Event;
+	import flash.geom.Rectangle;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		private var checks:Array = [];
+
+		public function Test() {
+			var opaque:BitmapData = new BitmapData(16, 16, false);
+			for (var y:int = 0; y < 16; y++) {
+				for (var x:int = 0; x < 16; x++) {
+					opaque.setPixel(x, y, (x * 16) << 16 | (y * 16) << 8 | (x + y) * 8);
+				}
+			}
+
+			// Premultiplying loses precision for most channel values of translucent pixels,
+			// both when drawing and when decoding, so only fully on or off channels are used.
+			var alphas:Array = [0xFF, 0xC0, 0x80, 0x40, 0x00];
+			var transparent:BitmapData = new BitmapData(10, 8, true, 0);
+			for (y = 0; y < 8; y++) {
+				for (x = 0; x < 10; x++) {
+					var r:uint = (x & 1) ? 0xFF : 0;
+					var g:uint = (y & 1) ? 0xFF : 0;
+					var b:uint = ((x + y) & 2) ? 0xFF : 0;
+					transparent.setPixel32(x, y, alphas[x % 5] << 24 | r << 16 | g << 8 | b);
+				}
+			}
+
+			// Each block covers a whole JPEG MCU, so chroma subsampling doesn't mix colors.
+			var blocks:BitmapData = new BitmapData(32, 16, false, 0x3366CC);
+			blocks.fillRect(new Rectangle(16, 0, 16, 16), 0xCC9933);
+
+			checks.push(["PNG of an opaque bitmap", opaque, opaque.encode(opaque.rect, new PNGEncoderOptions()), 0]);
+			checks.push(["PNG with fast compression of an opaque bitmap", opaque, opaque.encode(opaque.rect, new PNGEncoderOptions(true)), 0]);
+			checks.push(["PNG of a transparent bitmap", transparent, transparent.encode(transparent.rect, new PNGEncoderOptions()), 0]);
+			checks.push(["JPEG of solid blocks", blocks, blocks.encode(blocks.rect, new JPEGEncoderOptions(100)), 8]);
+			loadNext();
+		}
+
+		private function loadNext():void {
+			if (checks.length == 0) {
+				trace("Done");
+				return;
+			}
+
+			var check:Array = checks.shift();
+			var source:BitmapData = check[1];
+			var tolerance:int = check[3];
+			var loader:Loader = new Loader();
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+				var loaded:BitmapData = Bitmap(loader.content).bitmapData;
+				trace("// " + check[0]);
+				trace("Loaded size: " + loaded.width + "x" + loaded.height);
+				var mismatches:int = 0;
+				for (var y:int = 0; y < source.height; y++) {
+					for (var x:int = 0; x < source.width; x++) {
+						if (difference(source.getPixel32(x, y), loaded.getPixel32(x, y)) > tolerance) {
+							mismatches++;
+						}
+					}
+				}
+				trace("Pixels differing by more than " + tolerance + ": " + mismatches);
+				loadNext();
+			});
+			loader.loadBytes(check[2]);
+		}
+
+		// The largest difference between the channels of two colors.
+		private static function difference(a:uint, b:uint):int {
+			var largest:int = 0;
+			for (var shift:int = 0; shift < 32; shift += 8) {
+				largest = Math.max(largest, Math.abs(int((a >>> shift) & 0xFF) - int((b >>> shift) & 0xFF)));
+			}
+			return largest;
+		}
+	}
+}
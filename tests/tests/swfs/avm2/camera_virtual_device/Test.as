@@ -0,0 +1,78 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.events.ActivityEvent;
+	import flash.events.Event;
+	import flash.events.StatusEvent;
+	import flash.geom.Rectangle;
+	import flash.media.Camera;
+	import flash.media.Microphone;
+	import flash.media.Video;
+
+	// Captures the virtual camera of the test, which replays a red, a half green and a blue frame.
+	public class Test extends MovieClip {
+		private var camera:Camera;
+		private var video:Video;
+		private var frame:int = 0;
+
+		public function Test() {
+			trace("Camera.isSupported: " + Camera.isSupported);
+			trace("Camera.names: " + Camera.names);
+			trace("Microphone.names: " + Microphone.names.length);
+			trace("Microphone.getMicrophone(): " + Microphone.getMicrophone());
+
+			camera = Camera.getCamera();
+			trace("name: " + camera.name + ", index: " + camera.index + ", muted: " + camera.muted);
+			trace("getCamera(\"0\") is the same object: " + (Camera.getCamera("0") === camera));
+			trace("getCamera(\"1\"): " + Camera.getCamera("1"));
+			trace("getCamera(\"other\"): " + Camera.getCamera("other"));
+			traceMode("default");
+
+			camera.setMode(320, 240, 12);
+			camera.setMotionLevel(10, 100);
+			traceMode("requested");
+			trace("motionLevel: " + camera.motionLevel + ", motionTimeout: " + camera.motionTimeout);
+
+			camera.addEventListener(ActivityEvent.ACTIVITY, function(event:ActivityEvent):void {
+				trace("// activity, activating: " + event.activating);
+			});
+			camera.addEventListener(StatusEvent.STATUS, function(event:StatusEvent):void {
+				trace("// status: " + event.code);
+			});
+
+			video = new Video(8, 6);
+			video.attachCamera(camera);
+			addChild(video);
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event:Event):void {
+			frame++;
+			trace("// frame " + frame);
+			traceMode("captured");
+			trace("currentFPS: " + camera.currentFPS + ", activityLevel: " + Math.round(camera.activityLevel));
+
+			var pixels:Vector.<uint> = new Vector.<uint>();
+			camera.copyToVector(new Rectangle(0, 0, 8, 1), pixels);
+			trace("pixels: " + pixels.length + ", left: " + pixels[0].toString(16) + ", right: " + pixels[7].toString(16));
+
+			if (frame == 6) {
+				var bitmap:BitmapData = new BitmapData(4, 4, false, 0x808080);
+				camera.drawToBitmapData(bitmap);
+				trace("drawToBitmapData: " + bitmap.getPixel(0, 0).toString(16) + ", " + bitmap.getPixel(3, 3).toString(16));
+			}
+			if (frame == 8) {
+				video.attachCamera(null);
+				trace("detached");
+			}
+			if (frame == 11) {
+				removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+				trace("done");
+			}
+		}
+
+		private function traceMode(name:String):void {
+			trace(name + " mode: " + camera.width + "x" + camera.height + " at " + camera.fps + " fps");
+		}
+	}
+}
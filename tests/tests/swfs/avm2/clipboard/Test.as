@@ -0,0 +1,215 @@
+package {
+    import flash.desktop.Clipboard;
+    import flash.desktop.ClipboardFormats;
+    import flash.desktop.ClipboardTransferMode;
+    import flash.display.BitmapData;
+    import flash.display.MovieClip;
+    import flash.events.KeyboardEvent;
+    import flash.filesystem.File;
+    import flash.text.TextField;
+    import flash.text.TextFieldType;
+    import flash.utils.ByteArray;
+
+    public class Test extends MovieClip {
+        private var clipboard:Clipboard = Clipboard.generalClipboard;
+        private var fields:Array = [];
+        private var step:int = 0;
+
+        public function Test() {
+            trace("// generalClipboard is a singleton");
+            trace(Clipboard.generalClipboard === clipboard);
+
+            trace("// empty");
+            clipboard.clear();
+            describe(clipboard);
+
+            trace("// text");
+            trace("setData: " + clipboard.setData(ClipboardFormats.TEXT_FORMAT, "Hello"));
+            describe(clipboard);
+
+            trace("// text replaced");
+            clipboard.setData(ClipboardFormats.TEXT_FORMAT, 123);
+            describe(clipboard);
+
+            trace("// html");
+            clipboard.setData(ClipboardFormats.HTML_FORMAT, "<b>Hello</b>");
+            describe(clipboard);
+
+            trace("// rich text");
+            var rtf:ByteArray = new ByteArray();
+            rtf.writeUTFBytes("{\\rtf1 Hello}");
+            clipboard.setData(ClipboardFormats.RICH_TEXT_FORMAT, rtf);
+            describe(clipboard);
+
+            trace("// rich text that isn't a ByteArray");
+            clipboard.setData(ClipboardFormats.RICH_TEXT_FORMAT, "{\\rtf1 Hello}");
+            describe(clipboard);
+
+            trace("// url");
+            clipboard.setData(ClipboardFormats.URL_FORMAT, "https://example.com/");
+            describe(clipboard);
+
+            trace("// file list");
+            clipboard.setData(ClipboardFormats.FILE_LIST_FORMAT, [new File("/tmp/a.txt"), new File("/tmp/b.txt")]);
+            describe(clipboard);
+
+            trace("// bitmap");
+            var bitmap:BitmapData = new BitmapData(2, 1, true, 0x80FF0000);
+            bitmap.setPixel32(1, 0, 0xFF00FF00);
+            clipboard.setData(ClipboardFormats.BITMAP_FORMAT, bitmap);
+            describe(clipboard);
+            trace("same BitmapData: " + (clipboard.getData(ClipboardFormats.BITMAP_FORMAT) === bitmap));
+
+            trace("// custom format");
+            var custom:Object = {value: 5};
+            clipboard.setData("custom", custom);
+            describe(clipboard);
+            trace("same object: " + (clipboard.getData("custom") === custom));
+            trace("same object (clone only): " + (clipboard.getData("custom", ClipboardTransferMode.CLONE_ONLY) === custom));
+
+            trace("// clearData(html)");
+            clipboard.clearData(ClipboardFormats.HTML_FORMAT);
+            describe(clipboard);
+
+            trace("// clearData(custom)");
+            clipboard.clearData("custom");
+            describe(clipboard);
+
+            trace("// clearData(missing)");
+            clipboard.clearData("missing");
+            describe(clipboard);
+
+            trace("// clear");
+            clipboard.clear();
+            describe(clipboard);
+
+            trace("// setDataHandler");
+            clipboard.setDataHandler(ClipboardFormats.TEXT_FORMAT, function():String {
+                trace("text handler called");
+                return "From handler";
+            });
+            clipboard.setDataHandler("custom", function():Array {
+                trace("custom handler called");
+                return [1, 2, 3];
+            });
+            trace("formats: " + clipboard.formats);
+            trace("hasFormat(custom): " + clipboard.hasFormat("custom"));
+            trace("getData(custom): " + clipboard.getData("custom"));
+            trace("getData(custom): " + clipboard.getData("custom"));
+            trace("getData(text): " + clipboard.getData(ClipboardFormats.TEXT_FORMAT));
+
+            trace("// setData replaces a handler");
+            clipboard.setDataHandler("custom", function():String {
+                trace("replaced handler called");
+                return "unused";
+            });
+            clipboard.setData("custom", "data");
+            trace("getData(custom): " + clipboard.getData("custom"));
+            clipboard.clear();
+
+            trace("// new Clipboard");
+            var local:Clipboard = new Clipboard();
+            local.setData(ClipboardFormats.TEXT_FORMAT, "Local");
+            local.setDataHandler("custom", function():String {
+                return "Local handler";
+            });
+            describe(local);
+            trace("general clipboard formats: " + clipboard.formats);
+            local.clear();
+            describe(local);
+
+            // The copying field uses the rich text clipboard, the pasting fields differ.
+            addField(true, "<b>Bold</b> plain");
+            addField(true, "");
+            addField(false, "");
+            addField(false, "<i>Italic</i> text");
+
+            stage.addEventListener(KeyboardEvent.KEY_DOWN, onKeyDown);
+            focus(0);
+            fields[0].setSelection(0, 10);
+        }
+
+        private function addField(richText:Boolean, htmlText:String):void {
+            var field:TextField = new TextField();
+            field.type = TextFieldType.INPUT;
+            field.useRichTextClipboard = richText;
+            field.htmlText = htmlText;
+            field.y = fields.length * 30;
+            field.width = 300;
+            field.height = 25;
+            addChild(field);
+            fields.push(field);
+        }
+
+        private function focus(index:int):void {
+            stage.focus = fields[index];
+            fields[index].setSelection(0, fields[index].length);
+        }
+
+        private function onKeyDown(event:KeyboardEvent):void {
+            if (event.keyCode != 27) {
+                return;
+            }
+            switch (step++) {
+                case 0:
+                    trace("// copied from a rich text field");
+                    describe(clipboard);
+                    focus(1);
+                    break;
+                case 1:
+                    trace("// pasted into a rich text field");
+                    trace(fields[1].htmlText);
+                    focus(2);
+                    break;
+                case 2:
+                    trace("// pasted into a plain text field");
+                    trace(fields[2].htmlText);
+                    focus(3);
+                    break;
+                case 3:
+                    trace("// copied from a plain text field");
+                    describe(clipboard);
+                    clipboard.clear();
+                    clipboard.setData(ClipboardFormats.TEXT_FORMAT, "Other");
+                    clipboard.setData(ClipboardFormats.HTML_FORMAT, "<b>Unrelated</b>");
+                    focus(1);
+                    break;
+                case 4:
+                    trace("// pasted html that doesn't match the text");
+                    trace(fields[1].htmlText);
+                    break;
+            }
+        }
+
+        private static function describe(clipboard:Clipboard):void {
+            trace("formats: " + clipboard.formats);
+            for each (var format:String in clipboard.formats) {
+                trace("hasFormat(" + format + "): " + clipboard.hasFormat(format));
+                trace("getData(" + format + "): " + dataToString(clipboard.getData(format)));
+            }
+            trace("hasFormat(air:text): " + clipboard.hasFormat(ClipboardFormats.TEXT_FORMAT));
+            trace("getData(missing): " + clipboard.getData("missing"));
+        }
+
+        private static function dataToString(data:Object):String {
+            if (data is ByteArray) {
+                var bytes:ByteArray = ByteArray(data);
+                bytes.position = 0;
+                return "ByteArray " + bytes.readUTFBytes(bytes.length);
+            }
+            if (data is BitmapData) {
+                var bitmap:BitmapData = BitmapData(data);
+                return "BitmapData " + bitmap.width + "x" + bitmap.height + " " +
+                    bitmap.getPixel32(0, 0).toString(16) + " " + bitmap.getPixel32(1, 0).toString(16);
+            }
+            if (data is Array) {
+                var items:Array = [];
+                for each (var item:Object in data) {
+                    items.push(item is File ? "File " + File(item).nativePath : item);
+                }
+                return "[" + items.join(", ") + "]";
+            }
+            return String(data);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.DatagramSocketDataEvent;
+	import flash.events.Event;
+	import flash.events.IOErrorEvent;
+	import flash.net.DatagramSocket;
+	import flash.utils.ByteArray;
+
+	// Talks between DatagramSockets bound by the same movie, over the test's loopback network.
+	public class Test extends MovieClip {
+		private var server:DatagramSocket;
+		private var client:DatagramSocket;
+		private var connected:DatagramSocket;
+		private var rebound:DatagramSocket;
+		private var step:int = 0;
+
+		public function Test() {
+			server = makeSocket("server");
+			client = makeSocket("client");
+
+			server.bind(5000, "127.0.0.1");
+			server.receive();
+			client.bind(0, "127.0.0.1");
+			client.receive();
+			trace("server bound: " + server.bound + ", " + server.localAddress + ":" + server.localPort);
+			trace("client bound: " + client.bound + ", " + client.localAddress + ":" + client.localPort);
+
+			var taken:DatagramSocket = makeSocket("taken");
+			tryCall("bind to a port in use", function():void {
+				taken.bind(5000, "127.0.0.1");
+			});
+			trace("taken bound: " + taken.bound + ", " + taken.localAddress + ":" + taken.localPort);
+
+			tryCall("bind to a negative port", function():void {
+				new DatagramSocket().bind(-1);
+			});
+			tryCall("bind to an invalid address", function():void {
+				new DatagramSocket().bind(5001, "not an address");
+			});
+			tryCall("bind twice", function():void {
+				server.bind(5001, "127.0.0.1");
+			});
+			tryCall("send without an address", function():void {
+				client.send(bytes("nowhere"));
+			});
+			tryCall("close an unbound socket", function():void {
+				new DatagramSocket().close();
+			});
+
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event:Event):void {
+			step++;
+			trace("// frame " + step);
+			switch (step) {
+				case 1:
+					trace("client local port: " + client.localPort);
+					client.send(bytes("hello"), 0, 0, "127.0.0.1", 5000);
+					client.send(bytes("partial message"), 8, 3, "127.0.0.1", 5000);
+					client.send(bytes("lost"), 0, 0, "127.0.0.1", 6000);
+					break;
+				case 3:
+					connected = makeSocket("connected");
+					connected.connect("127.0.0.1", 5000);
+					connected.receive();
+					trace("connected: " + connected.connected + ", " + connected.remoteAddress + ":" + connected.remotePort + ", bound: " + connected.bound);
+					tryCall("connect twice", function():void {
+						connected.connect("127.0.0.1", 5001);
+					});
+					tryCall("send to an address from a connected socket", function():void {
+						connected.send(bytes("elsewhere"), 0, 0, "127.0.0.1", 5000);
+					});
+					connected.send(bytes("from a connected socket"));
+					break;
+				case 4:
+					trace("connected local port: " + connected.localPort);
+					// A connected socket ignores packets from other addresses.
+					client.send(bytes("ignored"), 0, 0, "127.0.0.1", connected.localPort);
+					break;
+				case 6:
+					server.close();
+					trace("server closed, bound: " + server.bound + ", " + server.localAddress + ":" + server.localPort);
+					client.send(bytes("after close"), 0, 0, "127.0.0.1", 5000);
+					break;
+				case 8:
+					rebound = makeSocket("rebound");
+					rebound.bind(5000, "127.0.0.1");
+					rebound.receive();
+					client.send(bytes("rebound"), 0, 0, "127.0.0.1", 5000);
+					break;
+				case 10:
+					trace("rebound bound: " + rebound.bound + ", " + rebound.localAddress + ":" + rebound.localPort);
+					client.close();
+					connected.close();
+					rebound.close();
+					removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+					trace("done");
+					break;
+			}
+		}
+
+		private function makeSocket(name:String):DatagramSocket {
+			var socket:DatagramSocket = new DatagramSocket();
+			socket.addEventListener(DatagramSocketDataEvent.DATA, function(event:DatagramSocketDataEvent):void {
+				var text:String = event.data.readUTFBytes(event.data.length);
+				trace(name + " received \"" + text + "\" from " + event.srcAddress + ":" + event.srcPort + " at " + event.dstAddress + ":" + event.dstPort);
+				if (socket === server) {
+					socket.send(bytes("re: " + text), 0, 0, event.srcAddress, event.srcPort);
+				}
+			});
+			socket.addEventListener(IOErrorEvent.IO_ERROR, function(event:IOErrorEvent):void {
+				trace(name + " IOErrorEvent: " + event.text + ", bound: " + socket.bound);
+			});
+			socket.addEventListener(Event.CLOSE, function(event:Event):void {
+				trace(name + " closed by the backend");
+			});
+			return socket;
+		}
+
+		private static function bytes(text:String):ByteArray {
+			var data:ByteArray = new ByteArray();
+			data.writeUTFBytes(text);
+			return data;
+		}
+
+		private static function tryCall(name:String, fn:Function):void {
+			try {
+				fn();
+				trace(name + ": no error");
+			} catch (e:Error) {
+				trace(name + ": " + e);
+			}
+		}
+	}
+}
@@ -0,0 +1,62 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	// Bounds and hit tests of display objects in 3D space follow their projected shape.
+	public class Test extends MovieClip {
+		public function Test() {
+			var flat:Sprite = square();
+			flat.x = 100;
+			flat.y = 250;
+
+			// Turned around its left edge, so its right side is squashed towards it.
+			var turned:Sprite = square();
+			turned.x = 100;
+			turned.y = 100;
+			turned.rotationY = 60;
+
+			var bounds:Rectangle = turned.getBounds(this);
+			trace("flat width: " + flat.width);
+			trace("turned bounds narrower: " + (bounds.width < 100) + ", left: " + Math.round(bounds.left));
+			trace("turned width matches bounds: " + (turned.width == bounds.width));
+
+			trace("// hitTestPoint near the left edge");
+			trace("flat, shape: " + flat.hitTestPoint(110, 300, true));
+			trace("turned, shape: " + turned.hitTestPoint(110, 150, true));
+			trace("turned, bounds: " + turned.hitTestPoint(110, 150, false));
+
+			trace("// hitTestPoint near where the right edge would be in 2D");
+			trace("flat, shape: " + flat.hitTestPoint(190, 300, true));
+			trace("turned, shape: " + turned.hitTestPoint(190, 150, true));
+			trace("turned, bounds: " + turned.hitTestPoint(190, 150, false));
+
+			trace("// Points survive a round trip through the projection");
+			var global:Point = turned.localToGlobal(new Point(50, 25));
+			trace("moved left of 2D position: " + (global.x < 150));
+			var local:Point = turned.globalToLocal(global);
+			trace("back to local: " + Math.round(local.x) + ", " + Math.round(local.y));
+
+			trace("// A container's bounds include the projected child");
+			var container:Sprite = new Sprite();
+			addChild(container);
+			container.x = 300;
+			var child:Sprite = square();
+			removeChild(child);
+			container.addChild(child);
+			child.rotationY = 60;
+			trace("container narrower: " + (container.width < 100));
+			trace("container, shape: " + container.hitTestPoint(310, 50, true) + " " + container.hitTestPoint(390, 50, true));
+		}
+
+		private function square():Sprite {
+			var sprite:Sprite = new Sprite();
+			sprite.graphics.beginFill(0xFF0000);
+			sprite.graphics.drawRect(0, 0, 100, 100);
+			sprite.graphics.endFill();
+			addChild(sprite);
+			return sprite;
+		}
+	}
+}
@@ -0,0 +1,152 @@
+package {
+    import flash.display.MovieClip;
+    import flash.display.Sprite;
+    import flash.geom.Matrix;
+    import flash.geom.Matrix3D;
+    import flash.geom.PerspectiveProjection;
+    import flash.geom.Point;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            var sprite:Sprite = new Sprite();
+            addChild(sprite);
+            sprite.x = 10;
+            sprite.y = 20;
+
+            trace("// 2D object");
+            describe(sprite);
+
+            trace("// z = 100");
+            sprite.z = 100;
+            describe(sprite);
+
+            trace("// rotationX = 30, rotationY = 45, scaleZ = 2");
+            sprite.rotationX = 30;
+            sprite.rotationY = 45;
+            sprite.scaleZ = 2;
+            describe(sprite);
+
+            trace("// matrix3D copied to another object");
+            var copy:Sprite = new Sprite();
+            addChild(copy);
+            copy.transform.matrix3D = sprite.transform.matrix3D;
+            describe(copy);
+
+            trace("// matrix3D changed after being read");
+            var matrix3D:Matrix3D = copy.transform.matrix3D;
+            matrix3D.appendTranslation(0, 0, 50);
+            describe(copy);
+            copy.transform.matrix3D = matrix3D;
+            describe(copy);
+
+            trace("// matrix3D with rotationZ");
+            matrix3D = new Matrix3D();
+            matrix3D.appendScale(2, 3, 4);
+            matrix3D.appendRotation(60, new flash.geom.Vector3D(0, 0, 1));
+            matrix3D.appendTranslation(5, 6, 7);
+            copy.transform.matrix3D = matrix3D;
+            describe(copy);
+
+            trace("// matrix set on a 3D object");
+            sprite.transform.matrix = new Matrix(2, 0, 0, 2, 30, 40);
+            describe(sprite);
+
+            trace("// matrix3D = null");
+            copy.transform.matrix3D = null;
+            describe(copy);
+
+            trace("// root perspectiveProjection");
+            var rootProjection:PerspectiveProjection = root.transform.perspectiveProjection;
+            describeProjection(rootProjection);
+
+            trace("// sprite perspectiveProjection");
+            trace("projection: " + sprite.transform.perspectiveProjection);
+
+            trace("// new PerspectiveProjection");
+            var projection:PerspectiveProjection = new PerspectiveProjection();
+            describeProjection(projection);
+
+            trace("// fieldOfView = 90");
+            projection.fieldOfView = 90;
+            describeProjection(projection);
+
+            trace("// focalLength = 100");
+            projection.focalLength = 100;
+            describeProjection(projection);
+
+            trace("// projectionCenter is copied");
+            var center:Point = new Point(10, 20);
+            projection.projectionCenter = center;
+            center.x = 1000;
+            projection.projectionCenter.y = 2000;
+            describeProjection(projection);
+
+            trace("// invalid values");
+            for each (var fieldOfView:Number in [0, 180, -5, NaN]) {
+                try {
+                    projection.fieldOfView = fieldOfView;
+                } catch (e:ArgumentError) {
+                    trace(e.errorID + ": " + e.message);
+                }
+            }
+            for each (var focalLength:Number in [0, -1, NaN]) {
+                try {
+                    projection.focalLength = focalLength;
+                } catch (e:ArgumentError) {
+                    trace(e.errorID + ": " + e.message);
+                }
+            }
+            describeProjection(projection);
+
+            trace("// sprite perspectiveProjection set");
+            sprite.transform.perspectiveProjection = projection;
+            describeProjection(sprite.transform.perspectiveProjection);
+            trace("same object: " + (sprite.transform.perspectiveProjection === projection));
+            projection.fieldOfView = 10;
+            describeProjection(sprite.transform.perspectiveProjection);
+
+            trace("// sprite perspectiveProjection = null");
+            sprite.transform.perspectiveProjection = null;
+            trace("projection: " + sprite.transform.perspectiveProjection);
+
+            trace("// root perspectiveProjection set");
+            projection.fieldOfView = 120;
+            projection.projectionCenter = new Point(0, 0);
+            root.transform.perspectiveProjection = projection;
+            describeProjection(root.transform.perspectiveProjection);
+
+            trace("// root perspectiveProjection = null");
+            root.transform.perspectiveProjection = null;
+            describeProjection(root.transform.perspectiveProjection);
+        }
+
+        private static function round(value:Number):Number {
+            var rounded:Number = Math.round(value * 1000) / 1000;
+            // Avoid printing "-0".
+            return rounded == 0 ? 0 : rounded;
+        }
+
+        private static function describe(object:Sprite):void {
+            trace("x, y, z: " + round(object.x) + ", " + round(object.y) + ", " + round(object.z));
+            trace("rotation: " + round(object.rotationX) + ", " + round(object.rotationY) + ", " + round(object.rotationZ) + " (" + round(object.rotation) + ")");
+            trace("scale: " + round(object.scaleX) + ", " + round(object.scaleY) + ", " + round(object.scaleZ));
+            trace("matrix: " + object.transform.matrix);
+            var matrix3D:Matrix3D = object.transform.matrix3D;
+            if (matrix3D == null) {
+                trace("matrix3D: null");
+            } else {
+                var rawData:Array = [];
+                for each (var value:Number in matrix3D.rawData) {
+                    rawData.push(round(value));
+                }
+                trace("matrix3D: " + rawData);
+            }
+        }
+
+        private static function describeProjection(projection:PerspectiveProjection):void {
+            trace("fieldOfView: " + round(projection.fieldOfView));
+            trace("focalLength: " + round(projection.focalLength));
+            trace("projectionCenter: " + projection.projectionCenter);
+        }
+    }
+}
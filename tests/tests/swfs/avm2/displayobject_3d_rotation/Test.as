@@ -0,0 +1,55 @@
+package {
+    import flash.display.MovieClip;
+    import flash.display.Sprite;
+    import flash.geom.PerspectiveProjection;
+    import flash.geom.Point;
+
+    public class Test extends MovieClip {
+        public function Test() {
+            // Rotated around the Y axis, so the right side recedes.
+            var rotatedY:Sprite = square(0xFF0000);
+            rotatedY.x = 100;
+            rotatedY.y = 100;
+            rotatedY.rotationY = 50;
+
+            // Rotated around the X axis, so the bottom comes towards the viewer.
+            var rotatedX:Sprite = square(0x00FF00);
+            rotatedX.x = 300;
+            rotatedX.y = 100;
+            rotatedX.rotationX = -40;
+
+            // Pushed away from the viewer.
+            var pushedBack:Sprite = square(0x0000FF);
+            pushedBack.x = 100;
+            pushedBack.y = 300;
+            pushedBack.z = 200;
+
+            // Rotated inside a container with its own projection.
+            var container:Sprite = new Sprite();
+            container.x = 300;
+            container.y = 300;
+            var projection:PerspectiveProjection = new PerspectiveProjection();
+            projection.fieldOfView = 100;
+            projection.projectionCenter = new Point(0, 0);
+            container.transform.perspectiveProjection = projection;
+            addChild(container);
+            var nested:Sprite = square(0xFF00FF);
+            nested.rotationY = -30;
+            nested.rotationX = 20;
+            removeChild(nested);
+            container.addChild(nested);
+        }
+
+        private function square(color:uint):Sprite {
+            var sprite:Sprite = new Sprite();
+            sprite.graphics.beginFill(color);
+            sprite.graphics.drawRect(-60, -60, 120, 120);
+            sprite.graphics.endFill();
+            sprite.graphics.beginFill(0x000000);
+            sprite.graphics.drawRect(-60, -60, 30, 30);
+            sprite.graphics.endFill();
+            addChild(sprite);
+            return sprite;
+        }
+    }
+}
@@ -0,0 +1,73 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.GameInputEvent;
+	import flash.ui.GameInput;
+	import flash.ui.GameInputControl;
+	import flash.ui.GameInputDevice;
+
+	// Gamepads are connected, used and disconnected by the test input.
+	public class Test extends MovieClip {
+		private var gameInput:GameInput;
+		private var devices:Array = [];
+		private var frame:int = 0;
+
+		public function Test() {
+			trace("GameInput.isSupported: " + GameInput.isSupported);
+			trace("GameInput.numDevices: " + GameInput.numDevices);
+
+			gameInput = new GameInput();
+			gameInput.addEventListener(GameInputEvent.DEVICE_ADDED, function(event:GameInputEvent):void {
+				var device:GameInputDevice = event.device;
+				trace("deviceAdded: " + device.id + ", name: " + device.name + ", enabled: " + device.enabled + ", numDevices: " + GameInput.numDevices);
+				trace("    same as getDeviceAt: " + (GameInput.getDeviceAt(GameInput.numDevices - 1) === device));
+				trace("    numControls: " + device.numControls);
+				for each (var index:int in [0, 3, 4, 17]) {
+					var control:GameInputControl = device.getControlAt(index);
+					trace("    control " + index + ": " + control.id + ", range: " + control.minValue + " to " + control.maxValue + ", device matches: " + (control.device === device));
+				}
+				tryCall("    getControlAt(18)", function():void {
+					device.getControlAt(18);
+				});
+
+				// Only enabled devices dispatch change events.
+				device.enabled = devices.length == 0;
+				for (var i:int = 0; i < device.numControls; i++) {
+					device.getControlAt(i).addEventListener(Event.CHANGE, onChange);
+				}
+				devices.push(device);
+			});
+			gameInput.addEventListener(GameInputEvent.DEVICE_REMOVED, function(event:GameInputEvent):void {
+				trace("deviceRemoved: " + event.device.id + ", numDevices: " + GameInput.numDevices);
+			});
+
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onChange(event:Event):void {
+			var control:GameInputControl = event.target as GameInputControl;
+			trace("change: " + control.device.id + " " + control.id + " = " + control.value);
+		}
+
+		private function onEnterFrame(event:Event):void {
+			frame++;
+			trace("// frame " + frame + ", numDevices: " + GameInput.numDevices);
+			for each (var device:GameInputDevice in devices) {
+				var values:Array = [];
+				for each (var index:int in [0, 1, 4, 5]) {
+					values.push(device.getControlAt(index).id + "=" + device.getControlAt(index).value);
+				}
+				trace("    " + device.id + ": " + values.join(", "));
+			}
+		}
+
+		private static function tryCall(name:String, fn:Function):void {
+			try {
+				fn();
+				trace(name + ": no error");
+			} catch (e:Error) {
+				trace(name + ": " + e);
+			}
+		}
+	}
+}
@@ -0,0 +1,118 @@
+package {
+	import flash.display.MovieClip;
+	import flash.globalization.CurrencyFormatter;
+	import flash.globalization.CurrencyParseResult;
+	import flash.globalization.DateTimeFormatter;
+	import flash.globalization.DateTimeStyle;
+	import flash.globalization.LocaleID;
+	import flash.globalization.NumberFormatter;
+	import flash.globalization.NumberParseResult;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			testNumberFormatter();
+			testCurrencyFormatter();
+			testDateTimeFormatter();
+			testLocaleID();
+			testErrors();
+		}
+
+		private static function status(name:String, formatter:Object):void {
+			trace(name + ": requested " + formatter.requestedLocaleIDName + ", actual " + formatter.actualLocaleIDName
+				+ ", lastOperationStatus " + formatter.lastOperationStatus);
+		}
+
+		private static function testNumberFormatter():void {
+			trace("// NumberFormatter");
+			for each (var locale:String in ["en-US", "de-DE", "fr-FR", "ja-JP", "de-AT", "xx-YY", LocaleID.DEFAULT]) {
+				var f:NumberFormatter = new NumberFormatter(locale);
+				status("new NumberFormatter(\"" + locale + "\")", f);
+				trace("  formatNumber(-1234567.891): " + f.formatNumber(-1234567.891));
+				trace("  formatInt(-42): " + f.formatInt(-42) + ", formatUint(4000000000): " + f.formatUint(4000000000));
+			}
+
+			var n:NumberFormatter = new NumberFormatter("en-US");
+			n.fractionalDigits = 4;
+			n.trailingZeros = false;
+			n.leadingZero = false;
+			n.groupingPattern = "3;2;*";
+			n.negativeNumberFormat = 0;
+			trace("Customised formatNumber(-1234567.5): " + n.formatNumber(-1234567.5));
+			trace("Customised formatNumber(0.25): " + n.formatNumber(0.25));
+			n.useGrouping = false;
+			trace("Without grouping formatNumber(1234567): " + n.formatNumber(1234567));
+
+			var parsed:NumberParseResult = n.parse("  -1,234.5 ");
+			trace("parse(\"  -1,234.5 \"): value " + parsed.value + ", start " + parsed.startIndex + ", end " + parsed.endIndex
+				+ ", lastOperationStatus " + n.lastOperationStatus);
+			trace("parseNumber(\"12.5\"): " + n.parseNumber("12.5") + ", lastOperationStatus " + n.lastOperationStatus);
+			trace("parseNumber(\"abc\"): " + n.parseNumber("abc") + ", lastOperationStatus " + n.lastOperationStatus);
+			n.useGrouping = true;
+			trace("Setting a property: lastOperationStatus " + n.lastOperationStatus);
+		}
+
+		private static function testCurrencyFormatter():void {
+			trace("// CurrencyFormatter");
+			for each (var locale:String in ["en-US", "de-DE", "ja-JP", "xx-YY"]) {
+				var f:CurrencyFormatter = new CurrencyFormatter(locale);
+				status("new CurrencyFormatter(\"" + locale + "\")", f);
+				trace("  currencyISOCode " + f.currencyISOCode + ", currencySymbol " + f.currencySymbol);
+				trace("  format(1234.5): " + f.format(1234.5) + ", format(-1234.5, true): " + f.format(-1234.5, true));
+			}
+
+			var c:CurrencyFormatter = new CurrencyFormatter("en-US");
+			trace("formattingWithCurrencySymbolIsSafe(\"USD\"): " + c.formattingWithCurrencySymbolIsSafe("USD"));
+			trace("formattingWithCurrencySymbolIsSafe(\"EUR\"): " + c.formattingWithCurrencySymbolIsSafe("EUR"));
+			c.setCurrency("EUR", "€");
+			trace("After setCurrency: format(-5, true): " + c.format(-5, true));
+			var parsed:CurrencyParseResult = c.parse("€12.50");
+			trace("parse(\"€12.50\"): value " + parsed.value + ", currencyString " + parsed.currencyString
+				+ ", lastOperationStatus " + c.lastOperationStatus);
+			parsed = c.parse("twelve");
+			trace("parse(\"twelve\"): value " + parsed.value + ", lastOperationStatus " + c.lastOperationStatus);
+		}
+
+		private static function testDateTimeFormatter():void {
+			trace("// DateTimeFormatter");
+			var date:Date = new Date(Date.UTC(2024, 1, 29, 15, 4, 5, 60));
+			for each (var locale:String in ["en-US", "en-GB", "de-DE", "fr-FR", "ja-JP", "xx-YY"]) {
+				var f:DateTimeFormatter = new DateTimeFormatter(locale);
+				status("new DateTimeFormatter(\"" + locale + "\")", f);
+				trace("  long: " + f.formatUTC(date));
+				f.setDateTimeStyles(DateTimeStyle.SHORT, DateTimeStyle.NONE);
+				trace("  short date: " + f.formatUTC(date) + " (" + f.getDateTimePattern() + ")");
+				trace("  first weekday " + f.getFirstWeekday() + ", months " + f.getMonthNames("shortAbbreviation").join(",")
+					+ ", weekdays " + f.getWeekdayNames().join(","));
+			}
+
+			var p:DateTimeFormatter = new DateTimeFormatter("en-US");
+			p.setDateTimePattern("EEEE, MMM d, yyyy 'at' h:mm:ss.SSS a");
+			trace("Pattern: " + p.formatUTC(date) + ", getDateStyle " + p.getDateStyle() + ", getTimeStyle " + p.getTimeStyle());
+		}
+
+		private static function testLocaleID():void {
+			trace("// LocaleID");
+			for each (var locale:String in ["en-US", "de_DE", "de-AT", "xx-YY", LocaleID.DEFAULT]) {
+				var id:LocaleID = new LocaleID(locale);
+				trace("new LocaleID(\"" + locale + "\"): language " + id.getLanguage() + ", region " + id.getRegion()
+					+ ", lastOperationStatus " + id.lastOperationStatus);
+			}
+		}
+
+		private static function attempt(name:String, f:Function):void {
+			try {
+				f();
+				trace(name + ": no error");
+			} catch (e:Error) {
+				trace(name + ": " + Object(e).constructor + ": " + e.message);
+			}
+		}
+
+		private static function testErrors():void {
+			trace("// Errors");
+			attempt("new NumberFormatter(null)", function():void { new NumberFormatter(null); });
+			attempt("NumberFormatter.negativeNumberFormat = 5", function():void { new NumberFormatter("en-US").negativeNumberFormat = 5; });
+			attempt("new DateTimeFormatter(\"en-US\", \"other\")", function():void { new DateTimeFormatter("en-US", "other"); });
+		}
+	}
+}
@@ -0,0 +1,103 @@
+package {
+	import flash.display.GraphicsGradientFill;
+	import flash.display.GraphicsPath;
+	import flash.display.GraphicsSolidFill;
+	import flash.display.GraphicsStroke;
+	import flash.display.IGraphicsData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.display.Sprite;
+	import flash.geom.Matrix;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("// beginFill and drawRect");
+			var shape:Shape = new Shape();
+			shape.graphics.beginFill(0xFF0000, 1);
+			shape.graphics.drawRect(10, 20, 30, 40);
+			shape.graphics.endFill();
+			describe(shape.graphics.readGraphicsData());
+
+			trace("// lineStyle, lineTo and curveTo");
+			shape = new Shape();
+			shape.graphics.lineStyle(3, 0x00FF00, 1, false, "horizontal", "square", "miter", 5);
+			shape.graphics.moveTo(5, 5);
+			shape.graphics.lineTo(25, 5);
+			shape.graphics.curveTo(35, 15, 25, 25);
+			describe(shape.graphics.readGraphicsData());
+
+			trace("// beginGradientFill");
+			shape = new Shape();
+			var box:Matrix = new Matrix();
+			box.createGradientBox(100, 50, 0, 0, 0);
+			shape.graphics.beginGradientFill("linear", [0xFF0000, 0x0000FF], [1, 0], [0, 255], box, "reflect", "linearRGB");
+			shape.graphics.drawRect(0, 0, 100, 50);
+			shape.graphics.endFill();
+			describe(shape.graphics.readGraphicsData());
+
+			trace("// Lines around a fill that isn't ended");
+			shape = new Shape();
+			shape.graphics.lineStyle(1, 0);
+			shape.graphics.beginFill(0x0000FF, 1);
+			shape.graphics.lineTo(10, 0);
+			shape.graphics.lineTo(10, 10);
+			describe(shape.graphics.readGraphicsData());
+
+			var container:Sprite = new Sprite();
+			container.graphics.beginFill(0xFF0000, 1);
+			container.graphics.drawRect(0, 0, 10, 10);
+			container.graphics.endFill();
+
+			var child:Shape = new Shape();
+			child.graphics.beginGradientFill("linear", [0xFF0000, 0x0000FF], [1, 1], [0, 255], box);
+			child.graphics.drawRect(0, 0, 100, 50);
+			child.graphics.endFill();
+			child.x = 100;
+			child.y = 50;
+			child.scaleX = 2;
+			container.addChild(child);
+
+			var nested:Sprite = new Sprite();
+			nested.x = 5;
+			var grandchild:Shape = new Shape();
+			grandchild.graphics.lineStyle(2, 0x00FF00);
+			grandchild.graphics.moveTo(1, 1);
+			grandchild.graphics.lineTo(3, 1);
+			grandchild.y = 7;
+			nested.addChild(grandchild);
+			container.addChild(nested);
+
+			trace("// Container with recurse = false");
+			describe(container.graphics.readGraphicsData(false));
+
+			trace("// Container with recurse = true");
+			describe(container.graphics.readGraphicsData());
+		}
+
+		private function describe(data:Vector.<IGraphicsData>):void {
+			for each (var item:IGraphicsData in data) {
+				trace(describeItem(item));
+			}
+		}
+
+		private function describeItem(item:Object):String {
+			if (item is GraphicsSolidFill) {
+				return "GraphicsSolidFill color=" + item.color.toString(16) + " alpha=" + item.alpha;
+			}
+			if (item is GraphicsGradientFill) {
+				return "GraphicsGradientFill type=" + item.type + " colors=" + item.colors + " alphas=" + item.alphas +
+					" ratios=" + item.ratios + " matrix=" + item.matrix + " spreadMethod=" + item.spreadMethod +
+					" interpolationMethod=" + item.interpolationMethod + " focalPointRatio=" + item.focalPointRatio;
+			}
+			if (item is GraphicsStroke) {
+				return "GraphicsStroke thickness=" + item.thickness + " pixelHinting=" + item.pixelHinting +
+					" scaleMode=" + item.scaleMode + " caps=" + item.caps + " joints=" + item.joints +
+					" miterLimit=" + item.miterLimit + " fill=" + (item.fill ? describeItem(item.fill) : null);
+			}
+			if (item is GraphicsPath) {
+				return "GraphicsPath commands=" + item.commands + " data=" + item.data + " winding=" + item.winding;
+			}
+			return String(item);
+		}
+	}
+}
@@ -0,0 +1,75 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.GraphicsBitmapFill;
+	import flash.display.GraphicsEndFill;
+	import flash.display.GraphicsPath;
+	import flash.display.GraphicsStroke;
+	import flash.display.IGraphicsData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.geom.Matrix;
+
+	public class Test extends MovieClip {
+		private var bitmap:BitmapData = new BitmapData(4, 2, false, 0xFF0000);
+
+		public function Test() {
+			var matrix:Matrix = new Matrix(2, 0, 0, 3, 10, 20);
+
+			trace("// beginBitmapFill");
+			var shape:Shape = new Shape();
+			shape.graphics.beginBitmapFill(bitmap, matrix, false, true);
+			shape.graphics.drawRect(0, 0, 50, 50);
+			shape.graphics.endFill();
+			describe(shape.graphics.readGraphicsData());
+
+			trace("// lineBitmapStyle");
+			shape = new Shape();
+			shape.graphics.lineStyle(2);
+			shape.graphics.lineBitmapStyle(bitmap, matrix, true, false);
+			shape.graphics.moveTo(0, 0);
+			shape.graphics.lineTo(50, 50);
+			describe(shape.graphics.readGraphicsData());
+
+			trace("// drawGraphicsData with a GraphicsBitmapFill");
+			shape = new Shape();
+			var path:GraphicsPath = new GraphicsPath();
+			path.moveTo(0, 0);
+			path.lineTo(50, 0);
+			path.lineTo(50, 50);
+			path.lineTo(0, 0);
+			var data:Vector.<IGraphicsData> = new Vector.<IGraphicsData>();
+			data.push(new GraphicsBitmapFill(bitmap, matrix, true, true));
+			data.push(path);
+			data.push(new GraphicsEndFill());
+			shape.graphics.drawGraphicsData(data);
+			var read:Vector.<IGraphicsData> = shape.graphics.readGraphicsData();
+			describe(read);
+
+			trace("// Drawing the data read back");
+			shape = new Shape();
+			shape.graphics.drawGraphicsData(read);
+			describe(shape.graphics.readGraphicsData());
+		}
+
+		private function describe(data:Vector.<IGraphicsData>):void {
+			for each (var item:IGraphicsData in data) {
+				var fill:Object = item;
+				if (item is GraphicsStroke) {
+					trace("GraphicsStroke, thickness " + GraphicsStroke(item).thickness);
+					fill = GraphicsStroke(item).fill;
+				}
+				if (fill is GraphicsBitmapFill) {
+					var bitmapFill:GraphicsBitmapFill = GraphicsBitmapFill(fill);
+					trace("GraphicsBitmapFill");
+					trace("  same bitmapData: " + (bitmapFill.bitmapData === bitmap));
+					trace("  matrix: " + bitmapFill.matrix);
+					trace("  repeat: " + bitmapFill.repeat + ", smooth: " + bitmapFill.smooth);
+				} else if (item is GraphicsPath) {
+					trace("GraphicsPath " + GraphicsPath(item).commands + " / " + GraphicsPath(item).data);
+				} else if (!(item is GraphicsStroke)) {
+					trace(item);
+				}
+			}
+		}
+	}
+}
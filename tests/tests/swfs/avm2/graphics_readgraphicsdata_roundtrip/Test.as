@@ -0,0 +1,109 @@
+package {
+	import flash.display.GraphicsEndFill;
+	import flash.display.GraphicsGradientFill;
+	import flash.display.GraphicsPath;
+	import flash.display.GraphicsSolidFill;
+	import flash.display.GraphicsStroke;
+	import flash.display.IGraphicsData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.geom.Matrix;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var fillPath:GraphicsPath = new GraphicsPath();
+			fillPath.moveTo(10, 10);
+			fillPath.lineTo(50, 10);
+			fillPath.curveTo(60, 30, 50, 50);
+			fillPath.cubicCurveTo(40, 60, 20, 60, 10, 50);
+			fillPath.lineTo(10, 10);
+
+			var linePath:GraphicsPath = new GraphicsPath();
+			linePath.moveTo(0, 0);
+			linePath.lineTo(100, 0);
+			linePath.lineTo(100, 100);
+
+			var gradientPath:GraphicsPath = new GraphicsPath(null, null, "nonZero");
+			gradientPath.moveTo(0, 0);
+			gradientPath.lineTo(100, 0);
+			gradientPath.lineTo(100, 100);
+			gradientPath.lineTo(0, 100);
+			gradientPath.lineTo(0, 0);
+
+			var box:Matrix = new Matrix();
+			box.createGradientBox(100, 100, 0, 0, 0);
+
+			var data:Vector.<IGraphicsData> = new Vector.<IGraphicsData>();
+			data.push(new GraphicsSolidFill(0xFF0000, 1));
+			data.push(fillPath);
+			data.push(new GraphicsEndFill());
+			data.push(new GraphicsStroke(2, false, "normal", "none", "miter", 4, new GraphicsSolidFill(0x0000FF, 1)));
+			data.push(linePath);
+			data.push(new GraphicsStroke());
+			data.push(new GraphicsGradientFill("radial", [0xFF0000, 0x0000FF], [1, 0], [0, 255], box, "repeat", "rgb", 0.5));
+			data.push(gradientPath);
+			data.push(new GraphicsEndFill());
+
+			trace("// drawGraphicsData, then readGraphicsData");
+			var shape:Shape = new Shape();
+			shape.graphics.drawGraphicsData(data);
+			var read:Vector.<IGraphicsData> = shape.graphics.readGraphicsData();
+			var first:String = describe(read);
+			trace(first);
+
+			trace("// Drawing the data read back");
+			shape = new Shape();
+			shape.graphics.drawGraphicsData(read);
+			var second:String = describe(shape.graphics.readGraphicsData());
+			trace(second);
+			trace("// Same data: " + (first == second));
+
+			trace("// Drawing calls, then drawing the data read back");
+			shape = new Shape();
+			shape.graphics.lineStyle(1, 0x00FF00);
+			shape.graphics.beginFill(0xFF, 1);
+			shape.graphics.drawRect(5, 5, 10, 10);
+			shape.graphics.endFill();
+			shape.graphics.lineStyle();
+			shape.graphics.beginFill(0xFF0000, 1);
+			shape.graphics.moveTo(0, 0);
+			shape.graphics.lineTo(20, 0);
+			shape.graphics.lineTo(20, 20);
+			shape.graphics.endFill();
+			first = describe(shape.graphics.readGraphicsData());
+			var copy:Shape = new Shape();
+			copy.graphics.drawGraphicsData(shape.graphics.readGraphicsData());
+			second = describe(copy.graphics.readGraphicsData());
+			trace(second);
+			trace("// Same data: " + (first == second));
+		}
+
+		private function describe(data:Vector.<IGraphicsData>):String {
+			var lines:Array = [];
+			for each (var item:IGraphicsData in data) {
+				lines.push(describeItem(item));
+			}
+			return lines.join("\n");
+		}
+
+		private function describeItem(item:Object):String {
+			if (item is GraphicsSolidFill) {
+				return "GraphicsSolidFill color=" + item.color.toString(16) + " alpha=" + item.alpha;
+			}
+			if (item is GraphicsGradientFill) {
+				return "GraphicsGradientFill type=" + item.type + " colors=" + item.colors + " alphas=" + item.alphas +
+					" ratios=" + item.ratios + " matrix=" + item.matrix + " spreadMethod=" + item.spreadMethod +
+					" interpolationMethod=" + item.interpolationMethod + " focalPointRatio=" + item.focalPointRatio;
+			}
+			if (item is GraphicsStroke) {
+				return "GraphicsStroke thickness=" + item.thickness + " pixelHinting=" + item.pixelHinting +
+					" scaleMode=" + item.scaleMode + " caps=" + item.caps + " joints=" + item.joints +
+					" miterLimit=" + item.miterLimit + " fill=" + (item.fill ? describeItem(item.fill) : null);
+			}
+			if (item is GraphicsPath) {
+				return "GraphicsPath commands=" + item.commands + " data=" + item.data + " winding=" + item.winding;
+			}
+			return String(item);
+		}
+	}
+}
@@ -0,0 +1,95 @@
+package {
+	import flash.display.GraphicsEndFill;
+	import flash.display.GraphicsPath;
+	import flash.display.GraphicsShaderFill;
+	import flash.display.IGraphicsData;
+	import flash.display.MovieClip;
+	import flash.display.Shader;
+	import flash.display.Shape;
+	import flash.geom.Matrix;
+	import flash.utils.ByteArray;
+
+	public class Test extends MovieClip {
+		// A shader that colors each pixel by its coordinates:
+		// dst = float4(fract(outCoord() / 40.0), 0.5, 1.0)
+		private static const SHADER_BYTES:Array = [
+			165, 1, 0, 0, 0, 164, 17, 0, 67, 111, 111, 114, 100, 105, 110, 97, 116, 101, 83, 116,
+			114, 105, 112, 101, 115, 161, 1, 2, 0, 0, 12, 95, 79, 117, 116, 67, 111, 111, 114, 100,
+			0, 161, 2, 4, 1, 0, 15, 100, 115, 116, 0, 50, 2, 0, 128, 60, 204, 204, 205, 29,
+			3, 0, 193, 0, 0, 16, 0, 3, 3, 0, 193, 2, 0, 0, 0, 28, 3, 0, 193, 3,
+			0, 16, 0, 29, 1, 0, 193, 3, 0, 16, 0, 50, 1, 0, 32, 63, 0, 0, 0, 50,
+			1, 0, 16, 63, 128, 0, 0
+		];
+
+		private var shader:Shader;
+
+		public function Test() {
+			var bytes:ByteArray = new ByteArray();
+			for each (var b:int in SHADER_BYTES) {
+				bytes.writeByte(b);
+			}
+			shader = new Shader(bytes);
+
+			trace("// beginShaderFill over negative shader coordinates");
+			var shape:Shape = new Shape();
+			shape.graphics.beginShaderFill(shader);
+			shape.graphics.drawRect(-40, -40, 80, 80);
+			shape.graphics.endFill();
+			shape.x = 50;
+			shape.y = 50;
+			addChild(shape);
+			describe(shape.graphics.readGraphicsData());
+
+			trace("// beginShaderFill with a matrix");
+			shape = new Shape();
+			shape.graphics.beginShaderFill(shader, new Matrix(2, 0, 0, 2, 10, 10));
+			shape.graphics.drawRect(0, 0, 80, 80);
+			shape.graphics.endFill();
+			shape.x = 110;
+			shape.y = 10;
+			addChild(shape);
+			describe(shape.graphics.readGraphicsData());
+
+			trace("// drawGraphicsData with a GraphicsShaderFill");
+			shape = new Shape();
+			var path:GraphicsPath = new GraphicsPath();
+			path.moveTo(0, 0);
+			path.lineTo(80, 0);
+			path.lineTo(0, 40);
+			path.lineTo(0, 0);
+			var data:Vector.<IGraphicsData> = new Vector.<IGraphicsData>();
+			data.push(new GraphicsShaderFill(shader, new Matrix(1, 0, 0, 1, -20, 0)));
+			data.push(path);
+			data.push(new GraphicsEndFill());
+			shape.graphics.drawGraphicsData(data);
+			shape.x = 10;
+			shape.y = 100;
+			addChild(shape);
+			var read:Vector.<IGraphicsData> = shape.graphics.readGraphicsData();
+			describe(read);
+
+			trace("// Drawing the data read back");
+			shape = new Shape();
+			shape.graphics.drawGraphicsData(read);
+			shape.x = 110;
+			shape.y = 100;
+			addChild(shape);
+			describe(shape.graphics.readGraphicsData());
+		}
+
+		private function describe(data:Vector.<IGraphicsData>):void {
+			for each (var item:IGraphicsData in data) {
+				if (item is GraphicsShaderFill) {
+					var fill:GraphicsShaderFill = GraphicsShaderFill(item);
+					trace("GraphicsShaderFill");
+					trace("  shader is the original: " + (fill.shader === shader));
+					trace("  matrix: " + fill.matrix);
+				} else if (item is GraphicsPath) {
+					trace("GraphicsPath, data: " + GraphicsPath(item).data);
+				} else {
+					trace(item);
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,61 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.SampleDataEvent;
+	import flash.events.TimerEvent;
+	import flash.media.Sound;
+	import flash.media.SoundChannel;
+	import flash.utils.Timer;
+
+	public class Child extends MovieClip {
+		public var timer:Timer;
+		public var channel:SoundChannel;
+
+		public function Child() {
+			timer = new Timer(50);
+			timer.addEventListener(TimerEvent.TIMER, onTimer);
+			timer.start();
+
+			var sound:Sound = new Sound();
+			sound.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+			channel = sound.play();
+
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+			addEventListener(Event.ADDED_TO_STAGE, onAddedToStage);
+		}
+
+		private function onAddedToStage(event:Event):void {
+			stage.addEventListener(Event.ENTER_FRAME, onStageEnterFrame);
+			stage.addEventListener("custom", onStageCustom);
+			parent.parent.addEventListener(Event.EXIT_FRAME, onParentExitFrame);
+		}
+
+		private function onStageEnterFrame(event:Event):void {
+			trace("Child: stage enterFrame");
+		}
+
+		private function onStageCustom(event:Event):void {
+			trace("Child: stage custom");
+		}
+
+		private function onParentExitFrame(event:Event):void {
+			trace("Child: parent exitFrame");
+		}
+
+		private function onTimer(event:TimerEvent):void {
+			trace("Child: timer");
+		}
+
+		private function onSampleData(event:SampleDataEvent):void {
+			trace("Child: sampleData");
+			for (var i:int = 0; i < 4096; i++) {
+				event.data.writeFloat(0);
+				event.data.writeFloat(0);
+			}
+		}
+
+		private function onEnterFrame(event:Event):void {
+			trace("Child: enterFrame");
+		}
+	}
+}
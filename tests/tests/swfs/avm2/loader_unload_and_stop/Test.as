@@ -0,0 +1,50 @@
+package {
+	import flash.display.Loader;
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.net.URLRequest;
+
+	public class Test extends MovieClip {
+		private var loader:Loader = new Loader();
+		private var child:Object;
+		private var frames:int = 0;
+
+		public function Test() {
+			loader.contentLoaderInfo.addEventListener(Event.COMPLETE, function(e:Event):void {
+				trace("Parent: complete");
+				child = loader.content;
+			});
+			loader.contentLoaderInfo.addEventListener(Event.UNLOAD, function(e:Event):void {
+				trace("Parent: unload, content: " + loader.content);
+			});
+			loader.load(new URLRequest("child.swf"));
+			addChild(loader);
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onStageCustom(event:Event):void {
+			trace("Parent: stage custom");
+		}
+
+		private function onEnterFrame(event:Event):void {
+			if (child == null) {
+				return;
+			}
+			frames++;
+			trace("Parent: frame " + frames);
+			if (frames == 1) {
+				stage.addEventListener("custom", onStageCustom);
+			}
+			stage.dispatchEvent(new Event("custom"));
+			if (frames == 3) {
+				trace("Parent: calling unloadAndStop");
+				loader.unloadAndStop();
+				trace("Parent: timer running: " + child.timer.running);
+				trace("Parent: child on the stage: " + (child.stage != null));
+			} else if (frames == 6) {
+				trace("Parent: timer running: " + child.timer.running);
+				removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+			}
+		}
+	}
+}
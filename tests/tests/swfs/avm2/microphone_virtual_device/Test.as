@@ -0,0 +1,72 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.ActivityEvent;
+	import flash.events.Event;
+	import flash.events.SampleDataEvent;
+	import flash.media.Camera;
+	import flash.media.Microphone;
+
+	// Captures the virtual microphone of the test, which replays half a second of a square wave
+	// at half volume followed by half a second of silence, at 8 kHz.
+	public class Test extends MovieClip {
+		private var microphone:Microphone;
+		private var frame:int = 0;
+
+		public function Test() {
+			trace("Microphone.isSupported: " + Microphone.isSupported);
+			trace("Microphone.names: " + Microphone.names);
+			trace("Camera.names: " + Camera.names.length);
+			trace("Camera.getCamera(): " + Camera.getCamera());
+
+			microphone = Microphone.getMicrophone();
+			trace("name: " + microphone.name + ", index: " + microphone.index + ", muted: " + microphone.muted);
+			trace("getMicrophone(0) is the same object: " + (Microphone.getMicrophone(0) === microphone));
+			trace("getMicrophone(1): " + Microphone.getMicrophone(1));
+			trace("getMicrophone(-2): " + Microphone.getMicrophone(-2));
+			trace("rate: " + microphone.rate + ", gain: " + microphone.gain + ", activityLevel: " + microphone.activityLevel);
+
+			microphone.setSilenceLevel(10, 100);
+			trace("silenceLevel: " + microphone.silenceLevel + ", silenceTimeout: " + microphone.silenceTimeout);
+
+			microphone.addEventListener(ActivityEvent.ACTIVITY, function(event:ActivityEvent):void {
+				trace("// activity, activating: " + event.activating);
+			});
+			microphone.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onSampleData(event:SampleDataEvent):void {
+			var count:int = event.data.length / 4;
+			var first:Number = event.data.readFloat();
+			var peak:Number = Math.abs(first);
+			for (var i:int = 1; i < count; i++) {
+				peak = Math.max(peak, Math.abs(event.data.readFloat()));
+			}
+			trace("sampleData at " + event.position + ": " + count + " samples, first: " + first + ", peak: " + peak + ", activityLevel: " + microphone.activityLevel);
+		}
+
+		private function onEnterFrame(event:Event):void {
+			frame++;
+			trace("// frame " + frame);
+			switch (frame) {
+				case 4:
+					microphone.gain = 100;
+					trace("gain: " + microphone.gain);
+					break;
+				case 8:
+					microphone.rate = 16;
+					trace("rate: " + microphone.rate);
+					break;
+				case 16:
+					microphone.gain = 50;
+					microphone.removeEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+					trace("stopped listening");
+					break;
+				case 18:
+					removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+					trace("done");
+					break;
+			}
+		}
+	}
+}
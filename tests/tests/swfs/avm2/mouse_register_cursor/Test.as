@@ -0,0 +1,99 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.geom.Point;
+	import flash.ui.Mouse;
+	import flash.ui.MouseCursor;
+	import flash.ui.MouseCursorData;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("Default cursor: " + Mouse.cursor);
+			for each (var builtin:String in [MouseCursor.ARROW, MouseCursor.BUTTON, MouseCursor.HAND, MouseCursor.IBEAM, MouseCursor.AUTO]) {
+				Mouse.cursor = builtin;
+				trace("Set " + builtin + ", got " + Mouse.cursor);
+			}
+
+			trace("// Setting an unregistered cursor");
+			attempt(function():void {
+				Mouse.cursor = "custom";
+			});
+			trace("Cursor: " + Mouse.cursor);
+
+			trace("// Registering a 16x16 cursor");
+			attempt(function():void {
+				Mouse.registerCursor("custom", cursor([bitmap(16, 16), bitmap(16, 16)], new Point(15, 15)));
+			});
+			Mouse.cursor = "custom";
+			trace("Cursor: " + Mouse.cursor);
+
+			trace("// Registering a 32x32 cursor");
+			attempt(function():void {
+				Mouse.registerCursor("large", cursor([bitmap(32, 32)], new Point(0, 0)));
+			});
+
+			trace("// Registering a cursor wider than 32 pixels");
+			attempt(function():void {
+				Mouse.registerCursor("wide", cursor([bitmap(33, 16)], new Point(0, 0)));
+			});
+
+			trace("// Registering a cursor taller than 32 pixels");
+			attempt(function():void {
+				Mouse.registerCursor("tall", cursor([bitmap(16, 33)], new Point(0, 0)));
+			});
+
+			trace("// Registering a cursor with frames of different sizes");
+			attempt(function():void {
+				Mouse.registerCursor("mismatched", cursor([bitmap(16, 16), bitmap(8, 8)], new Point(0, 0)));
+			});
+
+			trace("// Registering a cursor with its hot spot outside of it");
+			attempt(function():void {
+				Mouse.registerCursor("outside", cursor([bitmap(16, 16)], new Point(16, 0)));
+			});
+			attempt(function():void {
+				Mouse.registerCursor("outside", cursor([bitmap(16, 16)], new Point(0, 16)));
+			});
+			attempt(function():void {
+				Mouse.registerCursor("outside", cursor([bitmap(16, 16)], new Point(-1, 0)));
+			});
+
+			trace("// Registering a cursor without frames");
+			attempt(function():void {
+				Mouse.registerCursor("empty", cursor([], new Point(0, 0)));
+			});
+
+			trace("// Setting cursors that failed to register");
+			for each (var name:String in ["wide", "tall", "mismatched", "outside", "empty"]) {
+				attempt(function():void {
+					Mouse.cursor = name;
+				});
+			}
+			trace("Cursor: " + Mouse.cursor);
+
+			trace("// Unregistering the current cursor");
+			Mouse.unregisterCursor("custom");
+			trace("Cursor: " + Mouse.cursor);
+		}
+
+		private static function bitmap(width:int, height:int):BitmapData {
+			return new BitmapData(width, height, true, 0xFF00FF00);
+		}
+
+		private static function cursor(frames:Array, hotSpot:Point):MouseCursorData {
+			var data:MouseCursorData = new MouseCursorData();
+			data.data = Vector.<BitmapData>(frames);
+			data.hotSpot = hotSpot;
+			return data;
+		}
+
+		private static function attempt(f:Function):void {
+			try {
+				f();
+				trace("OK");
+			} catch (e:Error) {
+				trace(e);
+			}
+		}
+	}
+}
@@ -0,0 +1,121 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.NetStatusEvent;
+	import flash.net.NetConnection;
+	import flash.net.NetStream;
+	import flash.net.NetStreamAppendBytesAction;
+	import flash.utils.ByteArray;
+
+	// Plays FLV data generated by the movie, made of script data tags that call `onTag` on the
+	// client of the stream with the name of the tag.
+	public class Test extends MovieClip {
+		private var stream:NetStream;
+		private var frame:int = 0;
+
+		public function Test() {
+			var connection:NetConnection = new NetConnection();
+			connection.connect(null);
+			stream = new NetStream(connection);
+			stream.client = {
+				onTag: function(name:String):void {
+					trace("onTag: " + name + ", time: " + stream.time);
+				}
+			};
+			stream.addEventListener(NetStatusEvent.NET_STATUS, function(event:NetStatusEvent):void {
+				trace("netStatus: " + event.info.code);
+			});
+
+			trace("appendBytes before play(null):");
+			stream.appendBytes(header());
+
+			stream.play(null);
+			stream.appendBytesAction(NetStreamAppendBytesAction.RESET_BEGIN);
+			var data:ByteArray = header();
+			data.writeBytes(tag("first", 0));
+			data.writeBytes(tag("second", 100));
+			data.writeBytes(tag("third", 250));
+			// The data may be appended in pieces of any size.
+			stream.appendBytes(slice(data, 0, 20));
+			stream.appendBytes(slice(data, 20, data.length - 20));
+
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(event:Event):void {
+			frame++;
+			trace("// frame " + frame);
+			switch (frame) {
+				case 2:
+					trace("// Tags that haven't been played yet are dropped when seeking.");
+					stream.appendBytes(tag("dropped", 400));
+					stream.seek(10);
+					break;
+				case 3:
+					trace("// After RESET_SEEK, tags continue from the time seeked to.");
+					stream.appendBytesAction(NetStreamAppendBytesAction.RESET_SEEK);
+					stream.appendBytes(tag("after seek", 10000));
+					stream.appendBytes(tag("after seek + 200", 10200));
+					break;
+				case 7:
+					trace("// After RESET_BEGIN, a new FLV header is expected.");
+					stream.appendBytesAction(NetStreamAppendBytesAction.RESET_BEGIN);
+					var data:ByteArray = header();
+					data.writeBytes(tag("restarted", 5000));
+					data.writeBytes(tag("restarted + 100", 5100));
+					stream.appendBytes(data);
+					break;
+				case 9:
+					trace("// END_SEQUENCE ends the stream once everything appended has played.");
+					stream.appendBytes(tag("last", 5300));
+					stream.appendBytesAction(NetStreamAppendBytesAction.END_SEQUENCE);
+					break;
+				case 13:
+					removeEventListener(Event.ENTER_FRAME, onEnterFrame);
+					trace("done");
+					break;
+			}
+		}
+
+		private static function header():ByteArray {
+			var data:ByteArray = new ByteArray();
+			data.writeUTFBytes("FLV");
+			data.writeByte(1);
+			data.writeByte(0);
+			data.writeUnsignedInt(9);
+			// The size of the previous tag, of which there is none.
+			data.writeUnsignedInt(0);
+			return data;
+		}
+
+		private static function tag(name:String, timestamp:uint):ByteArray {
+			var body:ByteArray = new ByteArray();
+			body.writeByte(2);
+			body.writeUTF("onTag");
+			body.writeByte(2);
+			body.writeUTF(name);
+
+			var data:ByteArray = new ByteArray();
+			data.writeByte(18);
+			writeUI24(data, body.length);
+			writeUI24(data, timestamp & 0xFFFFFF);
+			data.writeByte(timestamp >>> 24);
+			writeUI24(data, 0);
+			data.writeBytes(body);
+			data.writeUnsignedInt(11 + body.length);
+			return data;
+		}
+
+		private static function writeUI24(data:ByteArray, value:uint):void {
+			data.writeByte(value >>> 16);
+			data.writeByte(value >>> 8);
+			data.writeByte(value);
+		}
+
+		private static function slice(data:ByteArray, offset:uint, length:uint):ByteArray {
+			var result:ByteArray = new ByteArray();
+			result.writeBytes(data, offset, length);
+			return result;
+		}
+	}
+}
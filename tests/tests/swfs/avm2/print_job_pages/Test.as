@@ -0,0 +1,68 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Sprite;
+	import flash.geom.Rectangle;
+	import flash.printing.PrintJob;
+	import flash.printing.PrintJobOptions;
+
+	// Prints pages through the test UI backend, which describes each page it receives.
+	public class Test extends MovieClip {
+		public function Test() {
+			var sprite:Sprite = new Sprite();
+			sprite.graphics.beginFill(0xFF0000);
+			sprite.graphics.drawRect(0, 0, 100, 50);
+			sprite.graphics.endFill();
+			addChild(sprite);
+
+			trace("PrintJob.isSupported: " + PrintJob.isSupported);
+
+			var job:PrintJob = new PrintJob();
+			tryCall("addPage before start", function():void {
+				job.addPage(sprite);
+			});
+			trace("// send before start");
+			job.send();
+
+			trace("start: " + job.start());
+			trace("paper: " + job.paperWidth + "x" + job.paperHeight + ", page: " + job.pageWidth + "x" + job.pageHeight + ", orientation: " + job.orientation);
+			trace("start of another job while printing: " + new PrintJob().start());
+
+			// Vector pages are rendered at 288 dpi, and bitmap pages at 72 dpi.
+			job.addPage(sprite);
+			job.addPage(sprite, new Rectangle(0, 0, 50, 50), new PrintJobOptions(true));
+			// Only the part of the area that fits on the page is printed.
+			job.addPage(sprite, new Rectangle(0, 0, 1000, 1000), new PrintJobOptions(true));
+			// An empty area doesn't print anything.
+			job.addPage(sprite, new Rectangle(0, 0, 0, 10));
+			tryCall("addPage with no sprite", function():void {
+				job.addPage(null);
+			});
+			trace("// send");
+			job.send();
+			trace("// send again");
+			job.send();
+
+			var terminated:PrintJob = new PrintJob();
+			trace("start: " + terminated.start());
+			terminated.addPage(sprite);
+			trace("// terminate");
+			terminated.terminate();
+			trace("// send after terminate");
+			terminated.send();
+
+			var last:PrintJob = new PrintJob();
+			trace("start after terminate: " + last.start());
+			trace("// send without pages");
+			last.send();
+		}
+
+		private static function tryCall(name:String, fn:Function):void {
+			try {
+				fn();
+				trace(name + ": no error");
+			} catch (e:Error) {
+				trace(name + ": " + e);
+			}
+		}
+	}
+}
@@ -0,0 +1,96 @@
+package {
+	import flash.display.MovieClip;
+	import flash.sampler.*;
+	import flash.utils.Dictionary;
+	import flash.utils.getQualifiedClassName;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			trace("// Allocations while sampling");
+			startSampling();
+			var object:Object = {a: 1};
+			var array:Array = [1, 2, 3];
+			var func:Function = function():void {};
+			var vector:Vector.<int> = new Vector.<int>();
+			var point:Point3 = new Point3();
+			var old:Object = new OldStyle();
+			var filtered:Vector.<int> = vector.filter(function(item:int, index:int, v:Vector.<int>):Boolean {
+				return true;
+			});
+			pauseSampling();
+			describe(getSamples());
+
+			trace("// Allocations while paused");
+			var ignored:Object = {};
+			describe(getSamples());
+
+			trace("// Clearing the samples");
+			clearSamples();
+			trace("Sample count: " + getSampleCount());
+
+			trace("// Many allocations without a callback");
+			startSampling();
+			for (var i:int = 0; i < 70000; i++) {
+				ignored = {};
+			}
+			pauseSampling();
+			var count:Number = getSampleCount();
+			trace("Samples were capped: " + (count > 0 && count <= 65536));
+
+			trace("// Sampler callback");
+			clearSamples();
+			var calls:int = 0;
+			setSamplerCallback(function():void {
+				calls++;
+				clearSamples();
+			});
+			startSampling();
+			for (i = 0; i < 20000; i++) {
+				ignored = {};
+			}
+			pauseSampling();
+			trace("Callback was called: " + (calls > 0));
+			trace("Samples left below the buffer size: " + (getSampleCount() < 8192));
+
+			trace("// Stopping from the callback");
+			setSamplerCallback(function():void {
+				stopSampling();
+			});
+			startSampling();
+			for (i = 0; i < 10000; i++) {
+				ignored = {};
+			}
+			trace("Sample count: " + getSampleCount());
+
+			stopSampling();
+			trace("Sample count after stopping: " + getSampleCount());
+		}
+
+		private static function describe(samples:Object):void {
+			var counts:Dictionary = new Dictionary();
+			var names:Array = [];
+			for each (var sample:Sample in samples) {
+				if (sample is NewObjectSample) {
+					var name:String = getQualifiedClassName(NewObjectSample(sample).type);
+					if (!(name in counts)) {
+						counts[name] = 0;
+						names.push(name);
+					}
+					counts[name]++;
+				}
+			}
+			names.sort();
+			for each (name in names) {
+				trace("NewObjectSample " + name + ": " + counts[name]);
+			}
+		}
+	}
+}
+
+class Point3 {
+	public var x:Number = 0;
+}
+
+function OldStyle():void {
+	this.value = 1;
+}
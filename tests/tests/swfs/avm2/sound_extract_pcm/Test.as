@@ -0,0 +1,115 @@
+package {
+	import flash.display.MovieClip;
+	import flash.media.Sound;
+	import flash.utils.ByteArray;
+	import flash.utils.Endian;
+
+	// Registers sounds from raw PCM data, and extracts their samples back.
+	public class Test extends MovieClip {
+		public function Test() {
+			var empty:Sound = new Sound();
+			var out:ByteArray = new ByteArray();
+			trace("extract from an empty sound: " + empty.extract(out, 10) + ", bytes: " + out.length);
+
+			// 100 stereo samples at 44.1 kHz, which don't need to be resampled.
+			var floats:ByteArray = new ByteArray();
+			for (var i:int = 0; i < 100; i++) {
+				floats.writeFloat(i / 100);
+				floats.writeFloat(-i / 200);
+			}
+			floats.position = 0;
+			var stereo:Sound = new Sound();
+			stereo.loadPCMFromByteArray(floats, 100, "float", true, 44100);
+			trace("float stereo, bytes left: " + floats.bytesAvailable);
+
+			traceExtract(stereo, 4, 0);
+			traceExtract(stereo, 4, 50);
+			trace("// A negative start continues from the last extraction.");
+			traceExtract(stereo, 4, -1);
+			traceExtract(stereo, 4, -1);
+			trace("// Extracting past the end returns the samples left.");
+			traceExtract(stereo, 10, 95);
+			traceExtract(stereo, 10, -1);
+			traceExtract(stereo, 10, 200);
+
+			// 4 mono little-endian shorts at 22.05 kHz, which get doubled to 44.1 kHz.
+			var shorts:ByteArray = new ByteArray();
+			shorts.endian = Endian.LITTLE_ENDIAN;
+			for each (var value:int in [0, 8192, 16384, -16384]) {
+				shorts.writeShort(value);
+			}
+			// An incomplete sample at the end is ignored.
+			shorts.writeByte(1);
+			shorts.position = 0;
+			var mono:Sound = new Sound();
+			mono.loadPCMFromByteArray(shorts, 10, "short", false, 22050);
+			trace("short mono, bytes left: " + shorts.bytesAvailable);
+			traceExtract(mono, 20, 0);
+
+			// Only the given number of samples is read.
+			floats.position = 0;
+			var partial:Sound = new Sound();
+			partial.loadPCMFromByteArray(floats, 3, "float", true, 44100);
+			trace("partial, bytes left: " + floats.bytesAvailable);
+			traceExtract(partial, 10, 0);
+
+			trace("// Float samples are extracted exactly as they were loaded.");
+			var precise:ByteArray = new ByteArray();
+			for (i = 0; i < 200; i++) {
+				precise.writeFloat(Math.sin(i) * 0.9);
+			}
+			precise.position = 0;
+			var preciseSound:Sound = new Sound();
+			preciseSound.loadPCMFromByteArray(precise, 100, "float", true, 44100);
+			var preciseOut:ByteArray = new ByteArray();
+			trace("extracted: " + preciseSound.extract(preciseOut, 100, 0));
+			precise.position = 0;
+			preciseOut.position = 0;
+			var mismatches:int = 0;
+			while (precise.bytesAvailable >= 4) {
+				if (precise.readFloat() != preciseOut.readFloat()) {
+					mismatches++;
+				}
+			}
+			trace("mismatches: " + mismatches);
+
+			trace("// Errors");
+			tryCall("unknown format", function():void {
+				new Sound().loadPCMFromByteArray(new ByteArray(), 0, "double");
+			});
+			tryCall("zero sample rate", function():void {
+				new Sound().loadPCMFromByteArray(new ByteArray(), 0, "float", true, 0);
+			});
+			tryCall("loading twice", function():void {
+				floats.position = 0;
+				stereo.loadPCMFromByteArray(floats, 1);
+			});
+			tryCall("loading compressed data after PCM", function():void {
+				stereo.loadCompressedDataFromByteArray(new ByteArray(), 0);
+			});
+			tryCall("extract into null", function():void {
+				stereo.extract(null, 1);
+			});
+		}
+
+		private static function traceExtract(sound:Sound, length:Number, start:Number):void {
+			var out:ByteArray = new ByteArray();
+			var extracted:Number = sound.extract(out, length, start);
+			var samples:Array = [];
+			out.position = 0;
+			while (out.bytesAvailable >= 8) {
+				samples.push(out.readFloat().toFixed(3) + "/" + out.readFloat().toFixed(3));
+			}
+			trace("extract(" + length + ", " + start + "): " + extracted + " [" + samples.join(", ") + "]");
+		}
+
+		private static function tryCall(name:String, fn:Function):void {
+			try {
+				fn();
+				trace(name + ": no error");
+			} catch (e:Error) {
+				trace(name + ": " + e);
+			}
+		}
+	}
+}
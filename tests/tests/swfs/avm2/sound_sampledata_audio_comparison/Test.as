@@ -0,0 +1,36 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.SampleDataEvent;
+	import flash.media.Sound;
+	import flash.media.SoundChannel;
+
+	public class Test extends MovieClip {
+		private var channel:SoundChannel;
+		private var frames:int = 0;
+
+		public function Test() {
+			var sound:Sound = new Sound();
+			sound.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+			channel = sound.play();
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		// A square wave of 441Hz, quieter on the right.
+		private function onSampleData(event:SampleDataEvent):void {
+			for (var i:int = 0; i < 2048; i++) {
+				var sample:Number = ((event.position + i) % 100) < 50 ? 0.5 : -0.5;
+				event.data.writeFloat(sample);
+				event.data.writeFloat(sample / 2);
+			}
+		}
+
+		private function onEnterFrame(event:Event):void {
+			frames++;
+			if (frames == 3) {
+				trace("Stopping the sound");
+				channel.stop();
+			}
+		}
+	}
+}
@@ -0,0 +1,40 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.SampleDataEvent;
+	import flash.media.Sound;
+	import flash.media.SoundChannel;
+
+	// This movie runs at 2 fps, so every frame plays more samples than one sampleData event can provide.
+	public class Test extends MovieClip {
+		private var channel:SoundChannel;
+		private var requests:int = 0;
+		private var frames:int = 0;
+
+		public function Test() {
+			var sound:Sound = new Sound();
+			sound.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+			channel = sound.play();
+			trace("Playing");
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onSampleData(event:SampleDataEvent):void {
+			requests++;
+			trace("sampleData #" + requests + ", position: " + event.position);
+			for (var i:int = 0; i < 8192; i++) {
+				event.data.writeFloat(0.25);
+				event.data.writeFloat(0.25);
+			}
+		}
+
+		private function onEnterFrame(event:Event):void {
+			frames++;
+			trace("Frame " + frames);
+			if (frames == 3) {
+				trace("Stopping the sound");
+				channel.stop();
+			}
+		}
+	}
+}
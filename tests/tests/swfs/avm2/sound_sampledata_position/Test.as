@@ -0,0 +1,40 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.SampleDataEvent;
+	import flash.media.Sound;
+	import flash.media.SoundChannel;
+
+	public class Test extends MovieClip {
+		private var channel:SoundChannel;
+		private var requests:int = 0;
+		private var frames:int = 0;
+
+		public function Test() {
+			var sound:Sound = new Sound();
+			sound.addEventListener(SampleDataEvent.SAMPLE_DATA, onSampleData);
+			channel = sound.play();
+			trace("Position after play: " + channel.position);
+			channel.addEventListener(Event.SOUND_COMPLETE, function(e:Event):void {
+				trace("soundComplete, position: " + channel.position);
+			});
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onSampleData(event:SampleDataEvent):void {
+			requests++;
+			trace("sampleData #" + requests + ", position: " + event.position);
+			// The third request provides too few samples, which ends the sound.
+			var count:int = requests < 3 ? 4096 : 1000;
+			for (var i:int = 0; i < count; i++) {
+				event.data.writeFloat(0);
+				event.data.writeFloat(0);
+			}
+		}
+
+		private function onEnterFrame(event:Event):void {
+			frames++;
+			trace("Frame " + frames + ", channel position: " + channel.position);
+		}
+	}
+}
@@ -0,0 +1,14 @@
+package {
+	import flash.display.MovieClip;
+	import flash.media.SoundTransform;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			// Plays the left channel on both sides, at half volume.
+			var transform:SoundTransform = new SoundTransform(0.5);
+			transform.leftToRight = 1;
+			transform.rightToRight = 0;
+			new ToneSound().play(0, 0, transform);
+		}
+	}
+}
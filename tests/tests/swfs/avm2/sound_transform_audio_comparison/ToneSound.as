@@ -0,0 +1,7 @@
+package {
+	import flash.media.Sound;
+
+	// Linked to the sound that generate.py adds to test.swf.
+	public class ToneSound extends Sound {
+	}
+}
@@ -0,0 +1,83 @@
+# Adds the sound linked to `ToneSound` to test.swf, and generates output.wav.
+#
+# Compile Test.as and ToneSound.as into test.swf at 25 FPS before running this.
+# The sound is an uncompressed stereo square wave, which the movie plays with a
+# SoundTransform that moves its left channel to both sides at half volume.
+import struct
+
+SAMPLE_RATE = 44100
+FRAME_RATE = 25
+NUM_TICKS = 4
+NUM_SAMPLES = 4410
+
+
+def square_wave():
+    # Square waves of 441Hz on the left and 735Hz on the right, followed by silence.
+    frames = []
+    for i in range(NUM_SAMPLES):
+        if i >= NUM_SAMPLES - 20:
+            frames.append((0, 0))
+        else:
+            frames.append((16384 if i % 100 < 50 else -16384, 4096 if i % 60 < 30 else -4096))
+    return frames
+
+
+def read_tags(swf):
+    nbits = swf[8] >> 3
+    pos = 8 + (5 + 4 * nbits + 7) // 8 + 4
+    header, tags = swf[:pos], []
+    while pos < len(swf):
+        code, length = swf[pos + 1] << 2 | swf[pos] >> 6, swf[pos] & 0x3F
+        pos += 2
+        if length == 0x3F:
+            length = struct.unpack("<I", swf[pos:pos + 4])[0]
+            pos += 4
+        tags.append((code, swf[pos:pos + length]))
+        pos += length
+    return header, tags
+
+
+def tag(code, body):
+    if len(body) < 0x3F:
+        return struct.pack("<H", code << 6 | len(body)) + body
+    return struct.pack("<HI", code << 6 | 0x3F, len(body)) + body
+
+
+def write_wav(path, samples):
+    data = struct.pack(f"<{len(samples)}f", *samples)
+    fmt = struct.pack("<HHIIHH", 3, 2, SAMPLE_RATE, SAMPLE_RATE * 8, 8, 32)
+    with open(path, "wb") as file:
+        file.write(b"RIFF" + struct.pack("<I", 4 + 8 + len(fmt) + 8 + len(data)) + b"WAVE")
+        file.write(b"fmt " + struct.pack("<I", len(fmt)) + fmt)
+        file.write(b"data" + struct.pack("<I", len(data)) + data)
+
+
+frames = square_wave()
+
+with open("test.swf", "rb") as file:
+    header, tags = read_tags(file.read())
+assert header[:3] == b"FWS", "test.swf must be uncompressed"
+
+# Uncompressed little-endian, 44kHz, 16-bit, stereo.
+data = b"".join(struct.pack("<hh", left, right) for left, right in frames)
+define_sound = struct.pack("<HBI", 1, 0x3F, NUM_SAMPLES) + data
+symbol_class = struct.pack("<H", 2) + b"\x00\x00Test\x00" + b"\x01\x00ToneSound\x00"
+
+body = b""
+for code, tag_body in tags:
+    if code == 14:
+        continue
+    if code in (72, 82):
+        body += tag(14, define_sound)
+    if code == 76:
+        tag_body = symbol_class
+    body += tag(code, tag_body)
+with open("test.swf", "wb") as file:
+    file.write(header[:4] + struct.pack("<I", len(header) + len(body)) + header[8:] + body)
+
+num_frames = NUM_TICKS * SAMPLE_RATE // FRAME_RATE
+expected = []
+for i in range(num_frames):
+    left = frames[i][0] if i < NUM_SAMPLES else 0
+    expected += [left * 0.5 / 32768] * 2
+write_wav("output.wav", expected)
@@ -0,0 +1,99 @@
+package {
+	import flash.display.MovieClip;
+	import flash.geom.Rectangle;
+	import flash.text.engine.ElementFormat;
+	import flash.text.engine.FontDescription;
+	import flash.text.engine.FontLookup;
+	import flash.text.engine.GroupElement;
+	import flash.text.engine.ContentElement;
+	import flash.text.engine.TextBlock;
+	import flash.text.engine.TextElement;
+	import flash.text.engine.TextLine;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var format:ElementFormat = new ElementFormat(new FontDescription("Tinos", "normal", "normal", FontLookup.DEVICE), 20);
+			var element:TextElement = new TextElement("Hello world, this is a test", format);
+			var block:TextBlock = new TextBlock(element);
+			trace("element.textBlock == block: " + (element.textBlock == block));
+
+			trace("// createTextLine until the block is complete");
+			var lines:Array = createLines(block, 100);
+			trace("textLineCreationResult: " + block.textLineCreationResult);
+			trace("firstLine == lines[0]: " + (block.firstLine == lines[0]));
+			trace("lastLine == last line: " + (block.lastLine == lines[lines.length - 1]));
+
+			trace("// recreateTextLine of the first line with a wider width");
+			var first:TextLine = lines[0];
+			var recreated:TextLine = block.recreateTextLine(first, null, 200);
+			trace("Same line: " + (recreated == first));
+			describe(recreated);
+			trace("Following lines: " + lines.slice(1).map(function(line:TextLine, ...rest):String {
+				return line.validity + "/" + (line.textBlock == null);
+			}).join(", "));
+			trace("lastLine == first: " + (block.lastLine == first));
+
+			trace("// recreateTextLine with invalid arguments");
+			try {
+				block.recreateTextLine(null);
+			} catch (e:Error) {
+				trace(e);
+			}
+			try {
+				block.recreateTextLine(first, first);
+			} catch (e:Error) {
+				trace(e);
+			}
+
+			trace("// releaseLines");
+			var second:TextLine = block.createTextLine(first, 200);
+			block.releaseLines(first, second);
+			trace("firstLine: " + block.firstLine + ", lastLine: " + block.lastLine);
+			trace("Released: " + first.validity + "/" + first.textBlock + ", " + second.validity + "/" + second.textBlock);
+			try {
+				block.releaseLines(first, second);
+			} catch (e:Error) {
+				trace(e);
+			}
+
+			trace("// Changing the text of the element");
+			element.text = "Changed";
+			createLines(block, 100);
+
+			trace("// Replacing the elements of a group");
+			var group:GroupElement = new GroupElement(Vector.<ContentElement>([new TextElement("One ", format), new TextElement("two", format)]));
+			block.content = group;
+			trace("element.textBlock: " + element.textBlock);
+			trace("group children have the block: " + (group.getElementAt(1).textBlock == block));
+			createLines(block, 1000);
+			var removed:Vector.<ContentElement> = group.replaceElements(1, 2, Vector.<ContentElement>([new TextElement("three", format)]));
+			trace("removed.textBlock: " + removed[0].textBlock);
+			createLines(block, 1000);
+		}
+
+		private function createLines(block:TextBlock, width:Number):Array {
+			var lines:Array = [];
+			var line:TextLine = block.createTextLine(null, width);
+			while (line != null) {
+				describe(line);
+				lines.push(line);
+				line = block.createTextLine(line, width);
+			}
+			return lines;
+		}
+
+		private function describe(line:TextLine):void {
+			trace("Line at " + line.textBlockBeginIndex + ", length " + line.rawTextLength + ": \"" + line.textBlock.content.text.substr(line.textBlockBeginIndex, line.rawTextLength) + "\"");
+			trace("  atomCount: " + line.atomCount + ", validity: " + line.validity);
+			trace("  ascent: " + round(line.ascent) + ", descent: " + round(line.descent) + ", textWidth: " + round(line.textWidth));
+			var bounds:Rectangle = line.getAtomBounds(0);
+			trace("  first atom: " + round(bounds.x) + ", " + round(bounds.y) + ", " + round(bounds.width) + ", " + round(bounds.height));
+			bounds = line.getAtomBounds(line.atomCount - 1);
+			trace("  last atom: " + round(bounds.x) + ", " + round(bounds.y) + ", " + round(bounds.width) + ", " + round(bounds.height));
+		}
+
+		private static function round(value:Number):Number {
+			return Math.round(value * 100) / 100;
+		}
+	}
+}
@@ -1,7 +1,7 @@
 use crate::external_interface::JavascriptInterface;
 use crate::navigator::{OpenUrlMode, WebNavigatorBackend};
 use crate::{
-    audio, log_adapter, storage, ui, JavascriptPlayer, RuffleHandle, SocketProxy,
+    audio, log_adapter, media_capture, storage, ui, JavascriptPlayer, RuffleHandle, SocketProxy,
     RUFFLE_GLOBAL_PANIC,
 };
 use js_sys::Promise;
@@ -647,6 +647,7 @@ impl RuffleInstanceBuilder {
             .with_log(log_adapter::WebLogBackend::new(trace_observer.clone()))
             .with_ui(ui::WebUiBackend::new(js_player.clone(), &canvas))
             .with_video(SoftwareVideoBackend::new())
+            .with_media_capture(media_capture::WebMediaCaptureBackend::new())
             .with_letterbox(self.letterbox)
             .with_max_execution_duration(self.max_execution_duration)
             .with_player_version(self.player_version)
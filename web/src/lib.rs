@@ -7,6 +7,7 @@ mod builder;
 mod external_interface;
 mod input;
 mod log_adapter;
+mod media_capture;
 mod navigator;
 mod storage;
 mod ui;
@@ -0,0 +1,419 @@
+use js_sys::{Array, Object, Reflect};
+use ruffle_core::backend::media_capture::{
+    CameraMode, CapturedFrame, MediaCaptureBackend, Resampler,
+};
+use ruffle_web_common::JsResult;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    AudioContext, AudioProcessingEvent, CanvasRenderingContext2d, ContextAttributes2d,
+    HtmlCanvasElement, HtmlVideoElement, MediaDeviceInfo, MediaDeviceKind, MediaStream,
+    MediaStreamConstraints, MediaStreamTrack, ScriptProcessorNode,
+};
+
+/// The number of samples a microphone delivers at once.
+const MICROPHONE_BUFFER_SIZE: u32 = 4096;
+
+/// A device listed by the browser.
+struct Device {
+    /// The ID to request the device with, which is empty until the user grants access to a
+    /// device of its kind.
+    id: String,
+    name: String,
+}
+
+/// A stream requested from the browser, which is only available once the user allows it.
+///
+/// The tracks of the stream are stopped when it's dropped, even if access is granted later on.
+struct RequestedStream(Rc<RefCell<Option<MediaStream>>>);
+
+impl RequestedStream {
+    /// Asks the browser for a stream, calling `on_stream` once the user allows it.
+    fn request(
+        constraints: &MediaStreamConstraints,
+        on_stream: impl FnOnce(&MediaStream) + 'static,
+    ) -> Result<Self, JsValue> {
+        let window = web_sys::window().ok_or("window() is unavailable")?;
+        let promise = window
+            .navigator()
+            .media_devices()?
+            .get_user_media_with_constraints(constraints)?;
+
+        let stream = Rc::new(RefCell::new(None));
+        let requested = stream.clone();
+        spawn_local(async move {
+            let media_stream: MediaStream = match JsFuture::from(promise).await {
+                Ok(media_stream) => media_stream.unchecked_into(),
+                Err(e) => {
+                    tracing::warn!("Unable to capture a media device: {:?}", e);
+                    return;
+                }
+            };
+            if Rc::strong_count(&requested) == 1 {
+                // The capture was stopped while the user was being asked.
+                stop_tracks(&media_stream);
+                return;
+            }
+            on_stream(&media_stream);
+            *requested.borrow_mut() = Some(media_stream);
+        });
+        Ok(Self(stream))
+    }
+}
+
+impl Drop for RequestedStream {
+    fn drop(&mut self) {
+        if let Some(media_stream) = self.0.borrow_mut().take() {
+            stop_tracks(&media_stream);
+        }
+    }
+}
+
+fn stop_tracks(media_stream: &MediaStream) {
+    for track in media_stream.get_tracks().iter() {
+        track.unchecked_into::<MediaStreamTrack>().stop();
+    }
+}
+
+/// The constraints selecting a device, with the given extra constraints.
+fn device_constraints(device: &Device, constraints: &[(&str, f64)]) -> Result<JsValue, JsValue> {
+    let object = Object::new();
+    if !device.id.is_empty() {
+        let exact = Object::new();
+        Reflect::set(&exact, &"exact".into(), &device.id.as_str().into())?;
+        Reflect::set(&object, &"deviceId".into(), &exact)?;
+    }
+    for (name, value) in constraints {
+        let ideal = Object::new();
+        Reflect::set(&ideal, &"ideal".into(), &(*value).into())?;
+        Reflect::set(&object, &(*name).into(), &ideal)?;
+    }
+    Ok(object.into())
+}
+
+/// A camera being captured, whose video gets drawn to a canvas at the size the movie asked for.
+struct CapturingCamera {
+    _stream: RequestedStream,
+    video: HtmlVideoElement,
+    context: CanvasRenderingContext2d,
+    mode: CameraMode,
+
+    /// The time since the last captured frame.
+    since_frame: Duration,
+}
+
+impl CapturingCamera {
+    fn start(device: &Device, mode: CameraMode) -> Result<Self, JsValue> {
+        let document = web_sys::window()
+            .and_then(|window| window.document())
+            .ok_or("document is unavailable")?;
+
+        let video: HtmlVideoElement = document.create_element("video")?.dyn_into()?;
+        video.set_muted(true);
+        video.set_autoplay(true);
+        video.set_attribute("playsinline", "")?;
+
+        let canvas: HtmlCanvasElement = document.create_element("canvas")?.dyn_into()?;
+        canvas.set_width(mode.width);
+        canvas.set_height(mode.height);
+        let attributes = ContextAttributes2d::new();
+        attributes.set_will_read_frequently(true);
+        let context: CanvasRenderingContext2d = canvas
+            .get_context_with_context_options("2d", &attributes)?
+            .ok_or("Unable to create a 2D context")?
+            .dyn_into()?;
+
+        let constraints = MediaStreamConstraints::new();
+        constraints.set_video(&device_constraints(
+            device,
+            &[
+                ("width", mode.width.into()),
+                ("height", mode.height.into()),
+                ("frameRate", mode.fps),
+            ],
+        )?);
+        let stream_video = video.clone();
+        let stream = RequestedStream::request(&constraints, move |media_stream| {
+            stream_video.set_src_object(Some(media_stream));
+            // Autoplay takes over if playing is refused.
+            let _ = stream_video.play();
+        })?;
+
+        Ok(Self {
+            _stream: stream,
+            video,
+            context,
+            mode,
+            since_frame: Duration::ZERO,
+        })
+    }
+
+    fn frame(&mut self, elapsed: Duration) -> Option<CapturedFrame> {
+        self.since_frame += elapsed;
+        if self.since_frame.as_secs_f64() * self.mode.fps < 1.0 {
+            return None;
+        }
+        // `HAVE_CURRENT_DATA`: the video has a frame to draw.
+        if self.video.ready_state() < 2 {
+            return None;
+        }
+        self.since_frame = Duration::ZERO;
+
+        let width = f64::from(self.mode.width);
+        let height = f64::from(self.mode.height);
+        self.context
+            .draw_image_with_html_video_element_and_dw_and_dh(&self.video, 0.0, 0.0, width, height)
+            .warn_on_error();
+        let rgba = self
+            .context
+            .get_image_data(0.0, 0.0, width, height)
+            .ok()?
+            .data();
+
+        Some(CapturedFrame {
+            width: self.mode.width,
+            height: self.mode.height,
+            rgb: rgba
+                .chunks_exact(4)
+                .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+                .collect(),
+        })
+    }
+}
+
+/// A microphone being captured, whose samples are collected until the next poll.
+struct CapturingMicrophone {
+    _stream: RequestedStream,
+    context: AudioContext,
+    processor: ScriptProcessorNode,
+    samples: Rc<RefCell<Vec<f32>>>,
+    _on_audio_process: Closure<dyn FnMut(AudioProcessingEvent)>,
+}
+
+impl CapturingMicrophone {
+    fn start(device: &Device, sample_rate: u32) -> Result<Self, JsValue> {
+        let context = AudioContext::new()?;
+        let processor = context
+            .create_script_processor_with_buffer_size_and_number_of_input_channels_and_number_of_output_channels(
+                MICROPHONE_BUFFER_SIZE,
+                1,
+                1,
+            )?;
+
+        let samples = Rc::new(RefCell::new(Vec::new()));
+        let mut resampler = Resampler::new(context.sample_rate() as u32, sample_rate);
+        // Samples nobody polls for are thrown away after a second.
+        let max_samples = sample_rate as usize;
+        let processed = samples.clone();
+        let on_audio_process = Closure::new(move |event: AudioProcessingEvent| {
+            let Ok(input) = event
+                .input_buffer()
+                .and_then(|buffer| buffer.get_channel_data(0))
+            else {
+                return;
+            };
+            let mut samples = processed.borrow_mut();
+            resampler.process(input.into_iter(), &mut samples);
+            if samples.len() > max_samples {
+                let excess = samples.len() - max_samples;
+                samples.drain(..excess);
+            }
+        });
+        processor.set_onaudioprocess(Some(on_audio_process.as_ref().unchecked_ref()));
+
+        let constraints = MediaStreamConstraints::new();
+        constraints.set_audio(&device_constraints(device, &[])?);
+        let stream_context = context.clone();
+        let stream_processor = processor.clone();
+        let stream = RequestedStream::request(&constraints, move |media_stream| {
+            let source = match stream_context.create_media_stream_source(media_stream) {
+                Ok(source) => source,
+                Err(e) => {
+                    tracing::error!("Unable to capture microphone: {:?}", e);
+                    return;
+                }
+            };
+            // The processor only runs while it's connected to the output, which it keeps silent.
+            source
+                .connect_with_audio_node(&stream_processor)
+                .and_then(|_| {
+                    stream_processor.connect_with_audio_node(&stream_context.destination())
+                })
+                .warn_on_error();
+            let _ = stream_context.resume();
+        })?;
+
+        Ok(Self {
+            _stream: stream,
+            context,
+            processor,
+            samples,
+            _on_audio_process: on_audio_process,
+        })
+    }
+}
+
+impl Drop for CapturingMicrophone {
+    fn drop(&mut self) {
+        self.processor.set_onaudioprocess(None);
+        let _ = self.context.close();
+    }
+}
+
+/// Captures cameras and microphones through `getUserMedia`.
+///
+/// The browser asks the user for access when a device is first captured. Since that answer
+/// arrives asynchronously, a denied device keeps capturing nothing instead of being muted.
+pub struct WebMediaCaptureBackend {
+    cameras: Rc<RefCell<Vec<Device>>>,
+    microphones: Rc<RefCell<Vec<Device>>>,
+    capturing_cameras: HashMap<usize, CapturingCamera>,
+    capturing_microphones: HashMap<usize, CapturingMicrophone>,
+}
+
+impl WebMediaCaptureBackend {
+    pub fn new() -> Self {
+        let backend = Self {
+            cameras: Rc::new(RefCell::new(vec![])),
+            microphones: Rc::new(RefCell::new(vec![])),
+            capturing_cameras: HashMap::new(),
+            capturing_microphones: HashMap::new(),
+        };
+        if let Err(e) = backend.enumerate_devices() {
+            tracing::warn!("Unable to list media devices: {:?}", e);
+        }
+        backend
+    }
+
+    /// Lists the devices of the browser, which are only named once the user grants access
+    /// to a device of their kind.
+    fn enumerate_devices(&self) -> Result<(), JsValue> {
+        let window = web_sys::window().ok_or("window() is unavailable")?;
+        let promise = window.navigator().media_devices()?.enumerate_devices()?;
+        let cameras = self.cameras.clone();
+        let microphones = self.microphones.clone();
+        spawn_local(async move {
+            let devices: Array = match JsFuture::from(promise).await {
+                Ok(devices) => devices.unchecked_into(),
+                Err(e) => {
+                    tracing::warn!("Unable to list media devices: {:?}", e);
+                    return;
+                }
+            };
+            for info in devices.iter() {
+                let info: MediaDeviceInfo = info.unchecked_into();
+                let (devices, kind) = match info.kind() {
+                    MediaDeviceKind::Videoinput => (&cameras, "Camera"),
+                    MediaDeviceKind::Audioinput => (&microphones, "Microphone"),
+                    _ => continue,
+                };
+                let mut devices = devices.borrow_mut();
+                let name = match info.label() {
+                    label if label.is_empty() => format!("{kind} {}", devices.len() + 1),
+                    label => label,
+                };
+                devices.push(Device {
+                    id: info.device_id(),
+                    name,
+                });
+            }
+        });
+        Ok(())
+    }
+}
+
+impl Default for WebMediaCaptureBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MediaCaptureBackend for WebMediaCaptureBackend {
+    fn camera_names(&self) -> Vec<String> {
+        self.cameras
+            .borrow()
+            .iter()
+            .map(|d| d.name.clone())
+            .collect()
+    }
+
+    fn start_camera(&mut self, index: usize, mode: CameraMode) -> Option<CameraMode> {
+        self.stop_camera(index);
+
+        // The video gets scaled to the requested size, whatever the camera captures at.
+        let mode = CameraMode {
+            width: mode.width.max(1),
+            height: mode.height.max(1),
+            fps: if mode.fps > 0.0 { mode.fps } else { 15.0 },
+        };
+        let camera = {
+            let cameras = self.cameras.borrow();
+            CapturingCamera::start(cameras.get(index)?, mode)
+        };
+        match camera {
+            Ok(camera) => {
+                self.capturing_cameras.insert(index, camera);
+                Some(mode)
+            }
+            Err(e) => {
+                tracing::error!("Unable to capture camera: {:?}", e);
+                None
+            }
+        }
+    }
+
+    fn stop_camera(&mut self, index: usize) {
+        // Dropping the stream stops it.
+        self.capturing_cameras.remove(&index);
+    }
+
+    fn camera_frame(&mut self, index: usize, elapsed: Duration) -> Option<CapturedFrame> {
+        self.capturing_cameras.get_mut(&index)?.frame(elapsed)
+    }
+
+    fn microphone_names(&self) -> Vec<String> {
+        self.microphones
+            .borrow()
+            .iter()
+            .map(|d| d.name.clone())
+            .collect()
+    }
+
+    fn start_microphone(&mut self, index: usize, sample_rate: u32) -> bool {
+        self.stop_microphone(index);
+
+        let microphone = {
+            let microphones = self.microphones.borrow();
+            let Some(device) = microphones.get(index) else {
+                return false;
+            };
+            CapturingMicrophone::start(device, sample_rate)
+        };
+        match microphone {
+            Ok(microphone) => {
+                self.capturing_microphones.insert(index, microphone);
+                true
+            }
+            Err(e) => {
+                tracing::error!("Unable to capture microphone: {:?}", e);
+                false
+            }
+        }
+    }
+
+    fn stop_microphone(&mut self, index: usize) {
+        self.capturing_microphones.remove(&index);
+    }
+
+    fn microphone_samples(&mut self, index: usize, _elapsed: Duration) -> Vec<f32> {
+        self.capturing_microphones
+            .get(&index)
+            .map(|microphone| std::mem::take(&mut *microphone.samples.borrow_mut()))
+            .unwrap_or_default()
+    }
+}
@@ -10,6 +10,7 @@ use ruffle_core::backend::navigator::{
     NavigationMethod, NavigatorBackend, OwnedFuture, Request, SuccessResponse,
 };
 use ruffle_core::config::NetworkingAccessMode;
+use ruffle_core::datagram_socket::{Datagram, DatagramSocketAction, DatagramSocketHandle};
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
@@ -501,6 +502,25 @@ impl NavigatorBackend for WebNavigatorBackend {
             Ok(())
         }));
     }
+
+    fn bind_datagram_socket(
+        &mut self,
+        host: String,
+        port: u16,
+        handle: DatagramSocketHandle,
+        _receiver: Receiver<Datagram>,
+        sender: Sender<DatagramSocketAction>,
+    ) {
+        // NOTE: Browsers don't give access to UDP, and WebSocket proxies only carry streams.
+        tracing::warn!(
+            "Can't bind a datagram socket to {}:{} in a browser",
+            host,
+            port
+        );
+        sender
+            .try_send(DatagramSocketAction::BindFailed(handle))
+            .expect("working channel send");
+    }
 }
 
 struct WebResponseWrapper {
@@ -1,17 +1,23 @@
 use super::JavascriptPlayer;
 use rfd::{AsyncFileDialog, FileHandle};
 use ruffle_core::backend::ui::{
-    DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter,
+    CustomCursorId, CustomMouseCursor, FontDefinition, FullscreenError, LanguageIdentifier,
+    MouseCursor, PrintedPage, UiBackend, US_ENGLISH,
 };
 use ruffle_core::backend::ui::{
-    FontDefinition, FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter,
 };
 use ruffle_web_common::JsResult;
 use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
 use url::Url;
-use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{Clamped, JsCast, JsValue};
 use web_sys::{
-    Blob, HtmlCanvasElement, HtmlDocument, HtmlElement, HtmlTextAreaElement, Url as JsUrl,
+    Blob, CanvasRenderingContext2d, HtmlCanvasElement, HtmlDocument, HtmlElement,
+    HtmlTextAreaElement, ImageData, Url as JsUrl,
 };
 
 use chrono::{DateTime, Utc};
@@ -91,6 +97,88 @@ fn get_extension_from_filename(filename: &str) -> Option<String> {
         .map(|x| ".".to_owned() + x)
 }
 
+/// A cursor registered by the movie, as the CSS `cursor` values displaying each of its frames.
+struct WebCustomCursor {
+    frames: Vec<String>,
+    frame_rate: f64,
+}
+
+impl WebCustomCursor {
+    fn new(cursor: &CustomMouseCursor) -> Result<Self, JsValue> {
+        let frames = cursor
+            .frames
+            .iter()
+            .map(|frame| custom_cursor_css(cursor, frame))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            frames,
+            frame_rate: cursor.frame_rate,
+        })
+    }
+}
+
+/// The CSS `cursor` value displaying a frame of a custom cursor.
+fn custom_cursor_css(cursor: &CustomMouseCursor, frame: &[u8]) -> Result<String, JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("document() is unavailable")?;
+    let canvas: HtmlCanvasElement = document.create_element("canvas")?.dyn_into()?;
+    canvas.set_width(cursor.width);
+    canvas.set_height(cursor.height);
+    let context: CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or("2d context is unavailable")?
+        .dyn_into()?;
+    let image_data =
+        ImageData::new_with_u8_clamped_array_and_sh(Clamped(frame), cursor.width, cursor.height)?;
+    context.put_image_data(&image_data, 0.0, 0.0)?;
+
+    Ok(format!(
+        "url({}) {} {}, auto",
+        canvas.to_data_url()?,
+        cursor.hot_spot.0,
+        cursor.hot_spot.1
+    ))
+}
+
+/// Goes through the frames of an animated cursor on a timer, as CSS cursors can't be animated.
+/// The timer is stopped when this is dropped.
+struct CursorAnimation {
+    interval: i32,
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl CursorAnimation {
+    fn start(canvas: HtmlCanvasElement, cursor: &WebCustomCursor) -> Result<Self, JsValue> {
+        let window = web_sys::window().ok_or("window() is unavailable")?;
+        let frames = cursor.frames.clone();
+        let frame = Rc::new(Cell::new(0));
+        let closure = Closure::<dyn FnMut()>::new(move || {
+            frame.set((frame.get() + 1) % frames.len());
+            canvas
+                .style()
+                .set_property("cursor", &frames[frame.get()])
+                .warn_on_error();
+        });
+        let interval = window.set_interval_with_callback_and_timeout_and_arguments_0(
+            closure.as_ref().unchecked_ref(),
+            (1000.0 / cursor.frame_rate) as i32,
+        )?;
+        Ok(Self {
+            interval,
+            _closure: closure,
+        })
+    }
+}
+
+impl Drop for CursorAnimation {
+    fn drop(&mut self) {
+        if let Some(window) = web_sys::window() {
+            window.clear_interval_with_handle(self.interval);
+        }
+    }
+}
+
 fn download_as_file(filename: Option<&str>, data: &[u8]) -> Result<(), JsValue> {
     let array = Uint8Array::from(data);
     let blob = Blob::new_with_u8_array_sequence(&Array::of1(&array))?;
@@ -158,6 +246,10 @@ pub struct WebUiBackend {
     canvas: HtmlCanvasElement,
     cursor_visible: bool,
     cursor: MouseCursor,
+    custom_cursors: HashMap<CustomCursorId, WebCustomCursor>,
+
+    /// The animation of the displayed cursor, if it's an animated custom cursor.
+    cursor_animation: Option<CursorAnimation>,
     language: LanguageIdentifier,
     clipboard_content: String,
 
@@ -177,19 +269,33 @@ impl WebUiBackend {
             canvas: canvas.clone(),
             cursor_visible: true,
             cursor: MouseCursor::Arrow,
+            custom_cursors: HashMap::new(),
+            cursor_animation: None,
             language,
             clipboard_content: "".into(),
             dialog_open: false,
         }
     }
 
-    fn update_mouse_cursor(&self) {
-        let cursor = if self.cursor_visible {
+    fn update_mouse_cursor(&mut self) {
+        self.cursor_animation = None;
+
+        let custom_cursor = match self.cursor {
+            MouseCursor::Custom(id) if self.cursor_visible => self
+                .custom_cursors
+                .get(&id)
+                .filter(|cursor| !cursor.frames.is_empty()),
+            _ => None,
+        };
+        let cursor = if let Some(custom_cursor) = custom_cursor {
+            &custom_cursor.frames[0]
+        } else if self.cursor_visible {
             match self.cursor {
                 MouseCursor::Arrow => "auto",
                 MouseCursor::Hand => "pointer",
                 MouseCursor::IBeam => "text",
                 MouseCursor::Grab => "grab",
+                MouseCursor::Custom(_) => "auto",
             }
         } else {
             "none"
@@ -198,6 +304,15 @@ impl WebUiBackend {
             .style()
             .set_property("cursor", cursor)
             .warn_on_error();
+
+        if let Some(custom_cursor) =
+            custom_cursor.filter(|cursor| cursor.frames.len() > 1 && cursor.frame_rate > 0.0)
+        {
+            match CursorAnimation::start(self.canvas.clone(), custom_cursor) {
+                Ok(animation) => self.cursor_animation = Some(animation),
+                Err(e) => tracing::error!("Couldn't animate a custom cursor: {:?}", e),
+            }
+        }
     }
 
     pub fn set_clipboard_content_buffer(&mut self, content: String) {
@@ -220,6 +335,23 @@ impl UiBackend for WebUiBackend {
         self.update_mouse_cursor();
     }
 
+    fn register_mouse_cursor(&mut self, id: CustomCursorId, cursor: CustomMouseCursor) {
+        match WebCustomCursor::new(&cursor) {
+            Ok(cursor) => {
+                self.custom_cursors.insert(id, cursor);
+            }
+            Err(e) => tracing::error!("Couldn't create a custom cursor: {:?}", e),
+        }
+    }
+
+    fn unregister_mouse_cursor(&mut self, id: CustomCursorId) {
+        self.custom_cursors.remove(&id);
+    }
+
+    fn supports_custom_mouse_cursors(&self) -> bool {
+        true
+    }
+
     fn clipboard_content(&mut self) -> String {
         // On web, clipboard content is not directly accessible due to security restrictions,
         // but pasting from the clipboard is supported via the JS `paste` event
@@ -383,4 +515,8 @@ impl UiBackend for WebUiBackend {
             result
         }))
     }
+
+    fn print_pages(&mut self, pages: Vec<PrintedPage>) {
+        tracing::warn!("Printing {} page(s) is not supported on web", pages.len());
+    }
 }